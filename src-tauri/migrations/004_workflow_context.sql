@@ -6,7 +6,7 @@
 -- new table and copy data. Instead, we rely on the runner ignoring
 -- "duplicate column name" errors for ALTER TABLE statements.
 --
--- NOTE: These statements are handled specially by init() which ignores
--- "duplicate column name" errors on ALTER TABLE.
+-- NOTE: These statements are handled specially by sqlite_migrations, which
+-- ignores "duplicate column name" errors on ALTER TABLE.
 ALTER TABLE workflow_steps ADD COLUMN pass_context INTEGER NOT NULL DEFAULT 0;
 ALTER TABLE workflow_steps ADD COLUMN result_output TEXT;
@@ -0,0 +1,3 @@
+-- Per-step failure handling: fail_workflow | continue | skip_descendants.
+-- Relies on init() ignoring "duplicate column name" errors (see 004).
+ALTER TABLE workflow_steps ADD COLUMN failure_policy TEXT NOT NULL DEFAULT 'fail_workflow';
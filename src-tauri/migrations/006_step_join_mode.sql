@@ -0,0 +1,3 @@
+-- Per-step dependency join semantics: all | any.
+-- Relies on init() ignoring "duplicate column name" errors (see 004).
+ALTER TABLE workflow_steps ADD COLUMN join_mode TEXT NOT NULL DEFAULT 'all';
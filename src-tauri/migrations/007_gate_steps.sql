@@ -0,0 +1,5 @@
+-- Quality gate steps and conditional (on_failure) edges.
+-- Relies on init() ignoring "duplicate column name" errors (see 004).
+ALTER TABLE workflow_steps ADD COLUMN kind TEXT NOT NULL DEFAULT 'agent';
+ALTER TABLE workflow_steps ADD COLUMN gate_command TEXT;
+ALTER TABLE workflow_edges ADD COLUMN condition TEXT NOT NULL DEFAULT 'on_success';
@@ -0,0 +1,14 @@
+-- Reusable step prompt templates with {{variable}} placeholders.
+
+CREATE TABLE IF NOT EXISTS prompt_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    body TEXT NOT NULL,
+    variables TEXT NOT NULL DEFAULT '{}',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- Relies on init() ignoring "duplicate column name" errors (see 004).
+ALTER TABLE workflow_steps ADD COLUMN prompt_template_id TEXT;
+ALTER TABLE workflow_steps ADD COLUMN template_vars TEXT NOT NULL DEFAULT '{}';
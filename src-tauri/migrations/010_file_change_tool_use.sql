@@ -0,0 +1,6 @@
+-- Tie each file change to the tool call that made it, so the cumulative
+-- stream-json resends of a tool_use block record it only once
+
+ALTER TABLE file_changes ADD COLUMN tool_use_id TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_file_changes_tool_use ON file_changes(session_id, tool_use_id);
@@ -0,0 +1,6 @@
+-- Large log entries are stored gzip-compressed in content_gz with an empty
+-- content column; content_size keeps the original length for stats
+
+ALTER TABLE log_entries ADD COLUMN content_gz BLOB;
+
+ALTER TABLE log_entries ADD COLUMN content_size INTEGER
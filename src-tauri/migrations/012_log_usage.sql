@@ -0,0 +1,5 @@
+-- Per-message token usage for cost attribution (assistant messages only)
+
+ALTER TABLE log_entries ADD COLUMN input_tokens INTEGER;
+
+ALTER TABLE log_entries ADD COLUMN output_tokens INTEGER
@@ -0,0 +1,5 @@
+-- The project a session ran in, for project-scoped search
+
+ALTER TABLE sessions ADD COLUMN project_dir TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_sessions_project ON sessions(project_dir)
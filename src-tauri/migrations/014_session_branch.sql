@@ -0,0 +1,3 @@
+-- The git branch created for a session's work, if any
+
+ALTER TABLE sessions ADD COLUMN branch TEXT
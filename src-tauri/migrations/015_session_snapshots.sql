@@ -0,0 +1,6 @@
+-- Working-tree snapshots (git tree hashes) taken when a session starts and
+-- ends, to diff just that session's changes
+
+ALTER TABLE sessions ADD COLUMN start_tree TEXT;
+
+ALTER TABLE sessions ADD COLUMN end_tree TEXT
@@ -0,0 +1,3 @@
+-- Commit message template for steps that commit the working tree when they complete
+
+ALTER TABLE workflow_steps ADD COLUMN commit_message TEXT
@@ -0,0 +1,14 @@
+-- Claude Code's cumulative per-model usage from stats-cache.json, one row
+-- per model per day, for charting spend over time
+
+CREATE TABLE IF NOT EXISTS usage_snapshots (
+    date TEXT NOT NULL,
+    model TEXT NOT NULL,
+    input_tokens INTEGER NOT NULL,
+    output_tokens INTEGER NOT NULL,
+    cache_read_tokens INTEGER NOT NULL,
+    cache_creation_tokens INTEGER NOT NULL,
+    cost_usd REAL NOT NULL,
+    recorded_at TEXT NOT NULL,
+    PRIMARY KEY (date, model)
+);
@@ -0,0 +1,3 @@
+-- Set when Claude Code reported no cost and it was estimated from tokens
+
+ALTER TABLE sessions ADD COLUMN cost_estimated INTEGER NOT NULL DEFAULT 0
@@ -0,0 +1,29 @@
+-- Claude profiles: the profile each session ran under, and usage snapshots
+-- per profile. SQLite can't change a primary key, so the snapshot table is
+-- rebuilt; existing rows belong to the default profile ('').
+
+ALTER TABLE sessions ADD COLUMN profile TEXT;
+
+CREATE TABLE usage_snapshots_by_profile (
+    date TEXT NOT NULL,
+    profile TEXT NOT NULL DEFAULT '',
+    model TEXT NOT NULL,
+    input_tokens INTEGER NOT NULL,
+    output_tokens INTEGER NOT NULL,
+    cache_read_tokens INTEGER NOT NULL,
+    cache_creation_tokens INTEGER NOT NULL,
+    cost_usd REAL NOT NULL,
+    recorded_at TEXT NOT NULL,
+    PRIMARY KEY (date, profile, model)
+);
+
+INSERT INTO usage_snapshots_by_profile
+    (date, profile, model, input_tokens, output_tokens, cache_read_tokens,
+     cache_creation_tokens, cost_usd, recorded_at)
+SELECT date, '', model, input_tokens, output_tokens, cache_read_tokens,
+       cache_creation_tokens, cost_usd, recorded_at
+FROM usage_snapshots;
+
+DROP TABLE usage_snapshots;
+
+ALTER TABLE usage_snapshots_by_profile RENAME TO usage_snapshots
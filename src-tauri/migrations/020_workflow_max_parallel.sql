@@ -0,0 +1,3 @@
+-- Most steps of a workflow running at once; NULL for no limit.
+
+ALTER TABLE workflows ADD COLUMN max_parallel INTEGER;
@@ -1,5 +1,5 @@
 use crate::domain::error::DomainError;
-use crate::domain::models::{AgentStatus, StreamMessage};
+use crate::domain::models::{AgentStatus, MessageUsage, StreamMessage};
 use crate::domain::ports::{AgentRunner, ResumeConfig, SpawnConfig};
 use crate::domain::session_manager::SessionManager;
 use crate::domain::stream_parser;
@@ -37,7 +37,7 @@ fn extract_reset_time(text: &str) -> Option<String> {
 }
 
 /// Env var allowlist for spawned processes (P0 Security #3).
-const ENV_ALLOWLIST: &[&str] = &[
+pub const ENV_ALLOWLIST: &[&str] = &[
     "PATH",
     "HOME",
     "USER",
@@ -51,6 +51,40 @@ const ENV_ALLOWLIST: &[&str] = &[
     "TERM",
     "ANTHROPIC_API_KEY",
     "CLAUDE_CODE_API_KEY",
+    "CLAUDE_CONFIG_DIR",
+];
+
+/// Never passed on, even when a project's allowlist names them: they would
+/// let the variable's value run code inside every agent process, through
+/// the dynamic loader, Node (Claude Code itself), the shells and
+/// interpreters its tools start, or the programs git runs. This includes
+/// `NODE_OPTIONS`: `--require` in it loads a module into Claude Code itself.
+/// `GIT_CONFIG_KEY_<n>` and `GIT_CONFIG_VALUE_<n>` are left out, since git
+/// ignores them without `GIT_CONFIG_COUNT`.
+pub const ENV_DENYLIST: &[&str] = &[
+    "LD_PRELOAD",
+    "LD_LIBRARY_PATH",
+    "LD_AUDIT",
+    "DYLD_INSERT_LIBRARIES",
+    "DYLD_LIBRARY_PATH",
+    "DYLD_FRAMEWORK_PATH",
+    "NODE_OPTIONS",
+    "NODE_PATH",
+    "BASH_ENV",
+    "ENV",
+    "PYTHONSTARTUP",
+    "PYTHONPATH",
+    "PYTHONHOME",
+    "PERL5OPT",
+    "PERL5LIB",
+    "RUBYOPT",
+    "RUBYLIB",
+    "GIT_SSH",
+    "GIT_SSH_COMMAND",
+    "GIT_EXEC_PATH",
+    "GIT_ASKPASS",
+    "GIT_CONFIG_COUNT",
+    "GIT_CONFIG_PARAMETERS",
 ];
 
 struct RunningProcess {
@@ -73,9 +107,12 @@ impl ClaudeCliRunner {
         }
     }
 
-    fn build_env() -> Vec<(String, String)> {
+    fn build_env(extra: &[String]) -> Vec<(String, String)> {
         ENV_ALLOWLIST
             .iter()
+            .copied()
+            .chain(extra.iter().map(String::as_str))
+            .filter(|key| !ENV_DENYLIST.contains(key))
             .filter_map(|key| std::env::var(key).ok().map(|val| (key.to_string(), val)))
             .collect()
     }
@@ -119,16 +156,18 @@ impl ClaudeCliRunner {
                             }
                         }
                         // Extract authoritative cost from the result message.
-                        // Claude Code reports cost_usd regardless of success/error.
-                        let cost_usd = r.extra.get("cost_usd")
-                            .and_then(|v| v.as_f64())
-                            .unwrap_or(0.0);
+                        // Claude Code reports it regardless of success/error,
+                        // except with an API key, where it is estimated instead.
+                        let cost_usd = r.cost_usd().unwrap_or(0.0);
                         if cost_usd > 0.0 {
                             sm.on_agent_cost(&sid, cost_usd).await;
+                        } else {
+                            sm.on_agent_cost_unreported(&sid).await;
                         }
                     }
 
                     // Extract token usage
+                    let mut message_usage = None;
                     if let StreamMessage::Assistant(ref a) = msg {
                         if let Some(message) = &a.message {
                             if let Some(usage) = message.get("usage") {
@@ -142,12 +181,24 @@ impl ClaudeCliRunner {
                                     .unwrap_or(0);
                                 if input > 0 || output > 0 {
                                     sm.on_agent_usage(&sid, input, output).await;
+                                    message_usage = Some(MessageUsage {
+                                        input_tokens: input,
+                                        output_tokens: output,
+                                    });
                                 }
                             }
                         }
                     }
 
-                    sm.on_agent_message(&sid, &msg_type, &redacted, &Utc::now().to_rfc3339())
+                    let timestamp = Utc::now().to_rfc3339();
+                    for tool_use in stream_parser::tool_uses(&msg) {
+                        sm.on_tool_use(&sid, &tool_use, &timestamp).await;
+                    }
+                    for result in stream_parser::tool_results(&msg) {
+                        sm.on_tool_result(&sid, &result, &timestamp).await;
+                    }
+
+                    sm.on_agent_message(&sid, &msg_type, &redacted, &timestamp, message_usage)
                         .await;
                 }
             }
@@ -156,7 +207,7 @@ impl ClaudeCliRunner {
             let mut stderr_reader = BufReader::new(stderr).lines();
             while let Ok(Some(line)) = stderr_reader.next_line().await {
                 let redacted = stream_parser::redact_secrets(&line);
-                sm.on_agent_message(&sid, "stderr", &redacted, &Utc::now().to_rfc3339())
+                sm.on_agent_message(&sid, "stderr", &redacted, &Utc::now().to_rfc3339(), None)
                     .await;
             }
 
@@ -167,39 +218,84 @@ impl ClaudeCliRunner {
         })
     }
 
-    /// Build and spawn a Claude CLI Command.
-    fn build_command(args: &[&str], project_dir: &str) -> Result<tokio::process::Child, DomainError> {
-        let env_vars = Self::build_env();
+    /// Build and spawn a Claude CLI Command. `config_dir` selects a Claude
+    /// profile, overriding any inherited `CLAUDE_CONFIG_DIR`; `extra_env`
+    /// names variables passed on top of `ENV_ALLOWLIST`.
+    fn build_command(
+        args: &[&str],
+        project_dir: &str,
+        config_dir: Option<&str>,
+        extra_env: &[String],
+    ) -> Result<tokio::process::Child, DomainError> {
+        Self::command("claude", args, project_dir, config_dir, extra_env)
+            .spawn()
+            .map_err(|e| DomainError::Process(e.to_string()))
+    }
+
+    /// The Command `build_command` spawns. The child is killed when dropped,
+    /// which happens when `kill` aborts the reader task that owns it;
+    /// otherwise a stopped session's process would keep running.
+    fn command(
+        program: &str,
+        args: &[&str],
+        project_dir: &str,
+        config_dir: Option<&str>,
+        extra_env: &[String],
+    ) -> Command {
+        let mut env_vars = Self::build_env(extra_env);
+        if let Some(dir) = config_dir {
+            env_vars.retain(|(key, _)| key != "CLAUDE_CONFIG_DIR");
+            env_vars.push(("CLAUDE_CONFIG_DIR".to_string(), dir.to_string()));
+        }
 
-        let mut cmd = Command::new("claude");
+        let mut cmd = Command::new(program);
         cmd.args(args);
         cmd.current_dir(project_dir);
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
         cmd.stdin(Stdio::null());
+        cmd.kill_on_drop(true);
         cmd.env_clear();
         for (key, value) in &env_vars {
             cmd.env(key, value);
         }
+        cmd
+    }
+}
 
-        cmd.spawn().map_err(|e| DomainError::Process(e.to_string()))
+/// Claude Code arguments for a new session.
+fn spawn_args(config: &SpawnConfig) -> Vec<&str> {
+    let mut args = vec!["--print", "--output-format", "stream-json", "--verbose"];
+    if config.read_only {
+        args.extend(["--permission-mode", "plan"]);
     }
+    args.extend([
+        "--agent", &config.agent_name,
+        "--session-id", &config.session_id,
+        "--model", &config.model,
+        &config.prompt,
+    ]);
+    args
+}
+
+/// Claude Code arguments for resuming a session.
+fn resume_args(config: &ResumeConfig) -> Vec<&str> {
+    let mut args = vec!["--print", "--output-format", "stream-json", "--verbose"];
+    if config.read_only {
+        args.extend(["--permission-mode", "plan"]);
+    }
+    args.extend(["--resume", &config.session_id, &config.prompt]);
+    args
 }
 
 #[async_trait]
 impl AgentRunner for ClaudeCliRunner {
     async fn spawn(&self, config: SpawnConfig) -> Result<(), DomainError> {
         let mut child = Self::build_command(
-            &[
-                "--print",
-                "--output-format", "stream-json",
-                "--verbose",
-                "--agent", &config.agent_name,
-                "--session-id", &config.session_id,
-                "--model", &config.model,
-                &config.prompt,
-            ],
+            &spawn_args(&config),
             &config.project_dir,
+            config.config_dir.as_deref(),
+            &config.extra_env,
         )?;
 
         let stdout = child
@@ -231,14 +327,10 @@ impl AgentRunner for ClaudeCliRunner {
 
     async fn resume(&self, config: ResumeConfig) -> Result<(), DomainError> {
         let mut child = Self::build_command(
-            &[
-                "--print",
-                "--output-format", "stream-json",
-                "--verbose",
-                "--resume", &config.session_id,
-                &config.prompt,
-            ],
+            &resume_args(&config),
             &config.project_dir,
+            config.config_dir.as_deref(),
+            &config.extra_env,
         )?;
 
         let stdout = child
@@ -289,3 +381,60 @@ impl AgentRunner for ClaudeCliRunner {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_only_sessions_run_in_plan_mode() {
+        let mut config = SpawnConfig {
+            session_id: "s1".into(),
+            agent_name: "verifier".into(),
+            model: "sonnet".into(),
+            prompt: "Check the spec".into(),
+            project_dir: ".".into(),
+            config_dir: None,
+            extra_env: vec![],
+            read_only: true,
+        };
+        let args = spawn_args(&config);
+        let mode = args.iter().position(|a| *a == "--permission-mode");
+        assert_eq!(mode.map(|i| args[i + 1]), Some("plan"));
+        assert_eq!(args.last(), Some(&"Check the spec"));
+
+        config.read_only = false;
+        assert!(!spawn_args(&config).contains(&"--permission-mode"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn aborting_the_reader_task_ends_the_process() {
+        // Gone, or exited and waiting to be reaped
+        let running = |pid: u32| {
+            std::fs::read_to_string(format!("/proc/{pid}/stat"))
+                .is_ok_and(|stat| !stat.contains(") Z "))
+        };
+        tauri::async_runtime::block_on(async {
+            let mut child = ClaudeCliRunner::command("sleep", &["30"], ".", None, &[])
+                .spawn()
+                .unwrap();
+            let pid = child.id().unwrap();
+            // Owned by a task the way the reader task owns it, then aborted
+            // the way `kill` does when a step is held
+            let task = tokio::spawn(async move {
+                let _ = child.wait().await;
+            });
+            assert!(running(pid));
+            task.abort();
+            let _ = task.await;
+            for _ in 0..100 {
+                if !running(pid) {
+                    return;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+            panic!("process {pid} survived its session being stopped");
+        });
+    }
+}
@@ -62,9 +62,10 @@ impl SessionRepository for InMemorySessionRepository {
         }
     }
 
-    async fn update_cost(&self, session_id: &str, cost_usd: f64) {
+    async fn update_cost(&self, session_id: &str, cost_usd: f64, estimated: bool) {
         if let Some(s) = self.sessions.write().await.get_mut(session_id) {
             s.cost_usd = cost_usd;
+            s.cost_estimated = estimated;
         }
     }
 }
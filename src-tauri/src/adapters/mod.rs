@@ -3,5 +3,9 @@ pub mod claude_cli_runner;
 pub mod config_store;
 pub mod in_memory_session_repository;
 pub mod sqlite_log_repository;
+pub mod sqlite_maintenance;
+pub mod sqlite_migrations;
+pub mod sqlite_usage_repository;
 pub mod sqlite_workflow_repository;
 pub mod tauri_event_emitter;
+pub mod webhook_event_emitter;
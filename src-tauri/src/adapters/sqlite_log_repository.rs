@@ -1,7 +1,14 @@
+use crate::adapters::sqlite_migrations;
 use crate::domain::error::DomainError;
-use crate::domain::models::LogEntry;
+use crate::domain::models::{
+    AgentSession, AgentStatus, FileChange, LogCompressionStats, LogEntry, LogQueryFilter,
+    MessageUsage, SessionLogStats, SessionSearchResult, SessionSnapshot, SnapshotPoint, ToolCall,
+    TranscriptEntry,
+};
 use crate::domain::ports::LogRepository;
+use crate::services::log_mirror::LogMirror;
 use async_trait::async_trait;
+use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -11,14 +18,22 @@ struct BufferedEntry {
     message_type: String,
     content: String,
     timestamp: String,
+    usage: Option<MessageUsage>,
 }
 
 const BATCH_THRESHOLD: usize = 100;
 
+/// Entries at least this large (in bytes) are stored gzip-compressed.
+const COMPRESS_THRESHOLD: usize = 8 * 1024;
+
 /// LogRepository adapter backed by SQLite.
 pub struct SqliteLogRepository {
     db_path: String,
     buffer: Arc<Mutex<Vec<BufferedEntry>>>,
+    /// Serializes flushes so batches land in the order they were buffered.
+    flush_lock: Mutex<()>,
+    /// Optional JSONL copy of every appended entry.
+    mirror: Option<Arc<LogMirror>>,
 }
 
 impl SqliteLogRepository {
@@ -26,15 +41,70 @@ impl SqliteLogRepository {
         Self {
             db_path,
             buffer: Arc::new(Mutex::new(Vec::new())),
+            flush_lock: Mutex::new(()),
+            mirror: None,
         }
     }
 
+    pub fn with_mirror(mut self, mirror: Arc<LogMirror>) -> Self {
+        self.mirror = Some(mirror);
+        self
+    }
+
     async fn connect(&self) -> Result<sqlx::SqlitePool, DomainError> {
         let url = format!("sqlite:{}?mode=rwc", self.db_path);
         sqlx::SqlitePool::connect(&url)
             .await
             .map_err(|e| DomainError::Database(e.to_string()))
     }
+
+    /// Filtered entries of one session, or of all of them.
+    async fn filtered(
+        &self,
+        session_id: Option<&str>,
+        filter: &LogQueryFilter,
+        after_id: u64,
+        limit: u32,
+    ) -> Result<Vec<LogEntry>, DomainError> {
+        let db = self.connect().await?;
+        let mut entries = Vec::new();
+        let mut cursor = after_id;
+        // Compressed rows can't be matched with LIKE, so SQL lets them through
+        // and they are checked here after decompression. Keep paging until
+        // the page is full or the table is exhausted.
+        loop {
+            let rows = filtered_query(session_id, filter, cursor, limit)
+                .build_query_as::<LogEntryRow>()
+                .fetch_all(&db)
+                .await
+                .map_err(|e| DomainError::Database(e.to_string()));
+            let rows = match rows {
+                Ok(rows) => rows,
+                Err(e) => {
+                    db.close().await;
+                    return Err(e);
+                }
+            };
+            let exhausted = (rows.len() as u32) < limit;
+            if let Some(last) = rows.last() {
+                cursor = last.id as u64;
+            }
+            for row in rows {
+                let compressed = row.content_gz.is_some();
+                let entry = LogEntry::from(row);
+                if !compressed || matches_content(filter, &entry.message_type, &entry.content) {
+                    entries.push(entry);
+                }
+            }
+            if exhausted || entries.len() >= limit as usize {
+                break;
+            }
+        }
+        db.close().await;
+
+        entries.truncate(limit as usize);
+        Ok(entries)
+    }
 }
 
 #[async_trait]
@@ -45,37 +115,45 @@ impl LogRepository for SqliteLogRepository {
         message_type: &str,
         content: &str,
         timestamp: &str,
+        usage: Option<MessageUsage>,
     ) {
-        let mut buf = self.buffer.lock().await;
-        buf.push(BufferedEntry {
-            session_id: session_id.to_string(),
-            message_type: message_type.to_string(),
-            content: content.to_string(),
-            timestamp: timestamp.to_string(),
-        });
-        if buf.len() >= BATCH_THRESHOLD {
-            let batch: Vec<BufferedEntry> = buf.drain(..).collect();
-            let db_path = self.db_path.clone();
-            tokio::spawn(async move {
-                if let Err(e) = flush_batch(&db_path, &batch).await {
-                    eprintln!("Log flush error: {e}");
-                }
+        if let Some(mirror) = &self.mirror {
+            if let Err(e) = mirror.write(session_id, message_type, content, timestamp) {
+                eprintln!("Log mirror error: {e}");
+            }
+        }
+        let full = {
+            let mut buf = self.buffer.lock().await;
+            buf.push(BufferedEntry {
+                session_id: session_id.to_string(),
+                message_type: message_type.to_string(),
+                content: content.to_string(),
+                timestamp: timestamp.to_string(),
+                usage,
             });
+            buf.len() >= BATCH_THRESHOLD
+        };
+        if full {
+            self.flush().await;
         }
     }
 
+    /// Write every buffered entry in one transaction and wait for it. A
+    /// failed batch goes back to the front of the buffer for the next flush.
     async fn flush(&self) {
-        let mut buf = self.buffer.lock().await;
-        if buf.is_empty() {
+        let _flushing = self.flush_lock.lock().await;
+        let batch: Vec<BufferedEntry> = self.buffer.lock().await.drain(..).collect();
+        if batch.is_empty() {
             return;
         }
-        let batch: Vec<BufferedEntry> = buf.drain(..).collect();
-        let db_path = self.db_path.clone();
-        tokio::spawn(async move {
-            if let Err(e) = flush_batch(&db_path, &batch).await {
-                eprintln!("Log flush error: {e}");
-            }
-        });
+        if let Err(e) = flush_batch(&self.db_path, &batch).await {
+            eprintln!(
+                "Log flush error ({} entries kept for retry): {e}",
+                batch.len()
+            );
+            let mut buf = self.buffer.lock().await;
+            buf.splice(0..0, batch);
+        }
     }
 
     async fn query_logs(
@@ -86,7 +164,7 @@ impl LogRepository for SqliteLogRepository {
     ) -> Result<Vec<LogEntry>, DomainError> {
         let db = self.connect().await?;
         let rows = sqlx::query_as::<_, LogEntryRow>(
-            "SELECT id, session_id, message_type, content, timestamp
+            "SELECT id, session_id, message_type, content, content_gz, timestamp, input_tokens, output_tokens
              FROM log_entries
              WHERE session_id = ?
              ORDER BY id ASC
@@ -101,16 +179,105 @@ impl LogRepository for SqliteLogRepository {
 
         db.close().await;
 
-        Ok(rows
-            .into_iter()
-            .map(|r| LogEntry {
-                id: r.id as u64,
-                session_id: r.session_id,
-                message_type: r.message_type,
-                content: r.content,
-                timestamp: r.timestamp,
-            })
-            .collect())
+        Ok(rows.into_iter().map(LogEntry::from).collect())
+    }
+
+    async fn query_logs_after(
+        &self,
+        session_id: &str,
+        after_id: u64,
+        limit: u32,
+    ) -> Result<Vec<LogEntry>, DomainError> {
+        let db = self.connect().await?;
+        let rows = sqlx::query_as::<_, LogEntryRow>(
+            "SELECT id, session_id, message_type, content, content_gz, timestamp, input_tokens, output_tokens
+             FROM log_entries
+             WHERE session_id = ? AND id > ?
+             ORDER BY id ASC
+             LIMIT ?",
+        )
+        .bind(session_id)
+        .bind(after_id as i64)
+        .bind(limit)
+        .fetch_all(&db)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+
+        db.close().await;
+
+        Ok(rows.into_iter().map(LogEntry::from).collect())
+    }
+
+    async fn query_logs_tail(
+        &self,
+        session_id: &str,
+        limit: u32,
+    ) -> Result<Vec<LogEntry>, DomainError> {
+        let db = self.connect().await?;
+        let rows = sqlx::query_as::<_, LogEntryRow>(
+            "SELECT id, session_id, message_type, content, content_gz, timestamp, input_tokens, output_tokens
+             FROM log_entries
+             WHERE session_id = ?
+             ORDER BY id DESC
+             LIMIT ?",
+        )
+        .bind(session_id)
+        .bind(limit)
+        .fetch_all(&db)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+
+        db.close().await;
+
+        Ok(rows.into_iter().rev().map(LogEntry::from).collect())
+    }
+
+    async fn query_logs_filtered(
+        &self,
+        session_id: &str,
+        filter: &LogQueryFilter,
+        after_id: u64,
+        limit: u32,
+    ) -> Result<Vec<LogEntry>, DomainError> {
+        self.filtered(Some(session_id), filter, after_id, limit)
+            .await
+    }
+
+    async fn query_all_logs_filtered(
+        &self,
+        filter: &LogQueryFilter,
+        after_id: u64,
+        limit: u32,
+    ) -> Result<Vec<LogEntry>, DomainError> {
+        self.filtered(None, filter, after_id, limit).await
+    }
+
+    async fn session_stats(&self, session_id: &str) -> Result<SessionLogStats, DomainError> {
+        let db = self.connect().await?;
+        let stats = session_stats(&db, session_id).await;
+        db.close().await;
+        stats.map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    async fn compression_stats(&self) -> Result<LogCompressionStats, DomainError> {
+        let db = self.connect().await?;
+        let row: (i64, i64, i64, i64) = sqlx::query_as(
+            "SELECT COUNT(*),
+                    COUNT(content_gz),
+                    COALESCE(SUM(COALESCE(content_size, LENGTH(CAST(content AS BLOB)))), 0),
+                    COALESCE(SUM(LENGTH(CAST(content AS BLOB)) + COALESCE(LENGTH(content_gz), 0)), 0)
+             FROM log_entries",
+        )
+        .fetch_one(&db)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+        db.close().await;
+        Ok(LogCompressionStats {
+            total_entries: row.0 as u64,
+            compressed_entries: row.1 as u64,
+            raw_bytes: row.2 as u64,
+            stored_bytes: row.3 as u64,
+        })
     }
 
     async fn count_logs(&self, session_id: &str) -> Result<u64, DomainError> {
@@ -124,6 +291,257 @@ impl LogRepository for SqliteLogRepository {
         db.close().await;
         Ok(row.0 as u64)
     }
+
+    async fn record_tool_call(
+        &self,
+        session_id: &str,
+        tool_use_id: &str,
+        tool_name: &str,
+        input_summary: &str,
+        started_at: &str,
+    ) -> Result<(), DomainError> {
+        let db = self.connect().await?;
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO tool_calls
+                (session_id, tool_use_id, tool_name, input_summary, started_at)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(session_id)
+        .bind(tool_use_id)
+        .bind(tool_name)
+        .bind(input_summary)
+        .bind(started_at)
+        .execute(&db)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()));
+        db.close().await;
+        result.map(|_| ())
+    }
+
+    async fn finish_tool_call(
+        &self,
+        session_id: &str,
+        tool_use_id: &str,
+        finished_at: &str,
+        success: bool,
+    ) -> Result<(), DomainError> {
+        let db = self.connect().await?;
+        let started: Option<(String,)> = sqlx::query_as(
+            "SELECT started_at FROM tool_calls
+             WHERE session_id = ? AND tool_use_id = ? AND finished_at IS NULL",
+        )
+        .bind(session_id)
+        .bind(tool_use_id)
+        .fetch_optional(&db)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+        let Some((started_at,)) = started else {
+            db.close().await;
+            return Ok(());
+        };
+        let duration_ms = match (
+            chrono::DateTime::parse_from_rfc3339(&started_at),
+            chrono::DateTime::parse_from_rfc3339(finished_at),
+        ) {
+            (Ok(start), Ok(end)) => Some((end - start).num_milliseconds().max(0)),
+            _ => None,
+        };
+        let result = sqlx::query(
+            "UPDATE tool_calls SET finished_at = ?, duration_ms = ?, success = ?
+             WHERE session_id = ? AND tool_use_id = ? AND finished_at IS NULL",
+        )
+        .bind(finished_at)
+        .bind(duration_ms)
+        .bind(success)
+        .bind(session_id)
+        .bind(tool_use_id)
+        .execute(&db)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()));
+        db.close().await;
+        result.map(|_| ())
+    }
+
+    async fn query_tool_calls(&self, session_id: &str) -> Result<Vec<ToolCall>, DomainError> {
+        let db = self.connect().await?;
+        let rows = sqlx::query_as::<_, ToolCallRow>(
+            "SELECT id, session_id, tool_use_id, tool_name, input_summary,
+                    started_at, finished_at, duration_ms, success
+             FROM tool_calls
+             WHERE session_id = ?
+             ORDER BY id ASC",
+        )
+        .bind(session_id)
+        .fetch_all(&db)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+        db.close().await;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| ToolCall {
+                id: r.id as u64,
+                session_id: r.session_id,
+                tool_use_id: r.tool_use_id,
+                tool_name: r.tool_name,
+                input_summary: r.input_summary,
+                started_at: r.started_at,
+                finished_at: r.finished_at,
+                duration_ms: r.duration_ms.map(|d| d as u64),
+                success: r.success,
+            })
+            .collect())
+    }
+
+    async fn record_file_change(
+        &self,
+        session_id: &str,
+        tool_use_id: &str,
+        file_path: &str,
+        operation: &str,
+        timestamp: &str,
+    ) -> Result<(), DomainError> {
+        let db = self.connect().await?;
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO file_changes
+                (session_id, tool_use_id, file_path, operation, timestamp)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(session_id)
+        .bind(tool_use_id)
+        .bind(file_path)
+        .bind(operation)
+        .bind(timestamp)
+        .execute(&db)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()));
+        db.close().await;
+        result.map(|_| ())
+    }
+
+    async fn record_session(&self, session: &AgentSession) -> Result<(), DomainError> {
+        let db = self.connect().await?;
+        let result = upsert_session(&db, session).await;
+        db.close().await;
+        result
+    }
+
+    async fn known_session_ids(&self) -> Result<HashSet<String>, DomainError> {
+        let db = self.connect().await?;
+        let ids: Result<Vec<(String,)>, _> = sqlx::query_as("SELECT id FROM sessions")
+            .fetch_all(&db)
+            .await;
+        db.close().await;
+        let ids = ids.map_err(|e| DomainError::Database(e.to_string()))?;
+        Ok(ids.into_iter().map(|(id,)| id).collect())
+    }
+
+    async fn list_stored_sessions(
+        &self,
+        project_dir: &str,
+    ) -> Result<Vec<AgentSession>, DomainError> {
+        let db = self.connect().await?;
+        let rows: Result<Vec<SessionRow>, _> = sqlx::query_as(
+            "SELECT id, agent_name, model, status, prompt, input_tokens, output_tokens, cost_usd,
+                    started_at, ended_at, project_dir, branch, cost_estimated, profile
+             FROM sessions
+             WHERE project_dir = ? AND ended_at IS NOT NULL
+             ORDER BY started_at",
+        )
+        .bind(project_dir)
+        .fetch_all(&db)
+        .await;
+        db.close().await;
+        let rows = rows.map_err(|e| DomainError::Database(e.to_string()))?;
+        Ok(rows.into_iter().map(AgentSession::from).collect())
+    }
+
+    async fn import_session(
+        &self,
+        session: &AgentSession,
+        entries: &[TranscriptEntry],
+    ) -> Result<(), DomainError> {
+        let db = self.connect().await?;
+        let result = import_session(&db, session, entries).await;
+        db.close().await;
+        result
+    }
+
+    async fn search_logs(
+        &self,
+        project_dir: &str,
+        query: &str,
+        limit: u32,
+    ) -> Result<Vec<SessionSearchResult>, DomainError> {
+        let db = self.connect().await?;
+        let result = search_logs(&db, project_dir, query, limit).await;
+        db.close().await;
+        result.map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    async fn query_file_changes(&self, session_id: &str) -> Result<Vec<FileChange>, DomainError> {
+        let db = self.connect().await?;
+        let rows = sqlx::query_as::<_, FileChangeRow>(
+            "SELECT id, session_id, file_path, operation, timestamp
+             FROM file_changes
+             WHERE session_id = ?
+             ORDER BY id ASC",
+        )
+        .bind(session_id)
+        .fetch_all(&db)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+        db.close().await;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| FileChange {
+                id: r.id as u64,
+                session_id: r.session_id,
+                file_path: r.file_path,
+                operation: r.operation,
+                timestamp: r.timestamp,
+            })
+            .collect())
+    }
+
+    async fn record_snapshot(
+        &self,
+        session_id: &str,
+        point: SnapshotPoint,
+        tree: &str,
+    ) -> Result<(), DomainError> {
+        let sql = match point {
+            SnapshotPoint::Start => "UPDATE sessions SET start_tree = ? WHERE id = ?",
+            SnapshotPoint::End => "UPDATE sessions SET end_tree = ? WHERE id = ?",
+        };
+        let db = self.connect().await?;
+        let result = sqlx::query(sql)
+            .bind(tree)
+            .bind(session_id)
+            .execute(&db)
+            .await;
+        db.close().await;
+        result.map_err(|e| DomainError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_snapshot(&self, session_id: &str) -> Result<SessionSnapshot, DomainError> {
+        let db = self.connect().await?;
+        let row: Result<Option<(Option<String>, Option<String>)>, _> =
+            sqlx::query_as("SELECT start_tree, end_tree FROM sessions WHERE id = ?")
+                .bind(session_id)
+                .fetch_optional(&db)
+                .await;
+        db.close().await;
+        let (start_tree, end_tree) = row
+            .map_err(|e| DomainError::Database(e.to_string()))?
+            .unwrap_or_default();
+        Ok(SessionSnapshot {
+            start_tree,
+            end_tree,
+        })
+    }
 }
 
 // Infrastructure lifecycle methods — not part of the domain port.
@@ -131,31 +549,9 @@ impl SqliteLogRepository {
     /// Initialize the SQLite database and run migrations.
     pub async fn init(&self) -> Result<(), DomainError> {
         let db = self.connect().await?;
-        let migrations = [
-            include_str!("../../migrations/001_initial.sql"),
-            include_str!("../../migrations/002_file_changes.sql"),
-            include_str!("../../migrations/003_workflows.sql"),
-            include_str!("../../migrations/004_workflow_context.sql"),
-        ];
-        for migration in &migrations {
-            for statement in migration.split(';') {
-                let stmt = statement.trim();
-                if stmt.is_empty() {
-                    continue;
-                }
-                if let Err(e) = sqlx::query(stmt).execute(&db).await {
-                    // Tolerate "duplicate column name" errors from ALTER TABLE
-                    // so migrations are idempotent across app restarts.
-                    let msg = e.to_string();
-                    if msg.contains("duplicate column name") {
-                        continue;
-                    }
-                    return Err(DomainError::Database(format!("{e}: {stmt}")));
-                }
-            }
-        }
+        let result = sqlite_migrations::migrate(&db).await;
         db.close().await;
-        Ok(())
+        result.map(|_| ())
     }
 
     /// Start a periodic flush task that runs every 500ms.
@@ -171,12 +567,401 @@ impl SqliteLogRepository {
     }
 }
 
+#[derive(sqlx::FromRow)]
+struct SessionRow {
+    id: String,
+    agent_name: String,
+    model: String,
+    status: String,
+    prompt: String,
+    input_tokens: i64,
+    output_tokens: i64,
+    cost_usd: f64,
+    started_at: String,
+    ended_at: Option<String>,
+    project_dir: Option<String>,
+    branch: Option<String>,
+    cost_estimated: bool,
+    profile: Option<String>,
+}
+
+impl From<SessionRow> for AgentSession {
+    fn from(r: SessionRow) -> Self {
+        // Only finished sessions are read back; one interrupted mid-run counts as stopped
+        let status = match r.status.as_str() {
+            "completed" => AgentStatus::Completed,
+            "error" => AgentStatus::Error,
+            _ => AgentStatus::Stopped,
+        };
+        Self {
+            id: r.id,
+            agent_name: r.agent_name,
+            model: r.model,
+            status,
+            prompt: r.prompt,
+            started_at: r.started_at,
+            ended_at: r.ended_at,
+            input_tokens: r.input_tokens as u64,
+            output_tokens: r.output_tokens as u64,
+            cost_usd: r.cost_usd,
+            project_dir: r.project_dir,
+            branch: r.branch,
+            cost_estimated: r.cost_estimated,
+            profile: r.profile,
+        }
+    }
+}
+
 #[derive(sqlx::FromRow)]
 struct LogEntryRow {
     id: i64,
     session_id: String,
     message_type: String,
     content: String,
+    content_gz: Option<Vec<u8>>,
+    timestamp: String,
+    input_tokens: Option<i64>,
+    output_tokens: Option<i64>,
+}
+
+impl From<LogEntryRow> for LogEntry {
+    fn from(r: LogEntryRow) -> Self {
+        let content = match r.content_gz {
+            Some(gz) => decompress(&gz).unwrap_or_else(|e| {
+                eprintln!("Failed to decompress log entry {}: {e}", r.id);
+                "[unreadable compressed entry]".to_string()
+            }),
+            None => r.content,
+        };
+        Self {
+            id: r.id as u64,
+            session_id: r.session_id,
+            message_type: r.message_type,
+            content,
+            timestamp: r.timestamp,
+            input_tokens: r.input_tokens.map(|t| t as u64),
+            output_tokens: r.output_tokens.map(|t| t as u64),
+        }
+    }
+}
+
+/// Gzip `content` if it is over the threshold and compression pays off.
+/// Insert a session or update the copy already stored.
+async fn upsert_session<'e, E>(executor: E, session: &AgentSession) -> Result<(), DomainError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        "INSERT INTO sessions
+            (id, agent_name, model, status, prompt, input_tokens, output_tokens, cost_usd,
+             started_at, ended_at, project_dir, branch, cost_estimated, profile)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(id) DO UPDATE SET
+            agent_name = excluded.agent_name,
+            model = excluded.model,
+            status = excluded.status,
+            prompt = excluded.prompt,
+            project_dir = excluded.project_dir,
+            branch = excluded.branch,
+            profile = excluded.profile,
+            input_tokens = excluded.input_tokens,
+            output_tokens = excluded.output_tokens,
+            cost_usd = excluded.cost_usd,
+            cost_estimated = excluded.cost_estimated,
+            ended_at = excluded.ended_at",
+    )
+    .bind(&session.id)
+    .bind(&session.agent_name)
+    .bind(&session.model)
+    .bind(session.status.to_string())
+    .bind(&session.prompt)
+    .bind(session.input_tokens as i64)
+    .bind(session.output_tokens as i64)
+    .bind(session.cost_usd)
+    .bind(&session.started_at)
+    .bind(&session.ended_at)
+    .bind(&session.project_dir)
+    .bind(&session.branch)
+    .bind(session.cost_estimated)
+    .bind(&session.profile)
+    .execute(executor)
+    .await
+    .map_err(|e| DomainError::Database(e.to_string()))?;
+    Ok(())
+}
+
+async fn import_session(
+    db: &sqlx::SqlitePool,
+    session: &AgentSession,
+    entries: &[TranscriptEntry],
+) -> Result<(), DomainError> {
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+    upsert_session(&mut *tx, session).await?;
+    for entry in entries {
+        insert_entry(
+            &mut tx,
+            &session.id,
+            &entry.message_type,
+            &entry.content,
+            &entry.timestamp,
+            entry.usage,
+        )
+        .await?;
+    }
+    tx.commit()
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))
+}
+
+fn compress(content: &str) -> Option<Vec<u8>> {
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    if content.len() < COMPRESS_THRESHOLD {
+        return None;
+    }
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(content.as_bytes()).ok()?;
+    let gz = encoder.finish().ok()?;
+    (gz.len() < content.len()).then_some(gz)
+}
+
+fn decompress(gz: &[u8]) -> std::io::Result<String> {
+    use std::io::Read;
+
+    let mut content = String::new();
+    flate2::read::GzDecoder::new(gz).read_to_string(&mut content)?;
+    Ok(content)
+}
+
+async fn session_stats(
+    db: &sqlx::SqlitePool,
+    session_id: &str,
+) -> Result<SessionLogStats, sqlx::Error> {
+    let by_type: Vec<(String, i64)> = sqlx::query_as(
+        "SELECT message_type, COUNT(*) FROM log_entries WHERE session_id = ? GROUP BY message_type",
+    )
+    .bind(session_id)
+    .fetch_all(db)
+    .await?;
+    let by_tool: Vec<(String, i64)> = sqlx::query_as(
+        "SELECT tool_name, COUNT(*) FROM tool_calls WHERE session_id = ? GROUP BY tool_name",
+    )
+    .bind(session_id)
+    .fetch_all(db)
+    .await?;
+    let totals: (i64, Option<String>, Option<String>, i64) = sqlx::query_as(
+        "SELECT COALESCE(SUM(COALESCE(content_size, LENGTH(CAST(content AS BLOB)))), 0),
+                MIN(timestamp),
+                MAX(timestamp),
+                COUNT(CASE WHEN message_type = 'stderr'
+                             OR (message_type = 'result'
+                                 AND (content LIKE '%\"is_error\":true%'
+                                      OR content LIKE '%\"subtype\":\"error%'))
+                           THEN 1 END)
+         FROM log_entries
+         WHERE session_id = ?",
+    )
+    .bind(session_id)
+    .fetch_one(db)
+    .await?;
+    // Tool failures are counted from tool_calls, which also covers results
+    // stored compressed
+    let failed_tools: (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM tool_calls WHERE session_id = ? AND success = 0")
+            .bind(session_id)
+            .fetch_one(db)
+            .await?;
+
+    Ok(SessionLogStats {
+        session_id: session_id.to_string(),
+        total_entries: by_type.iter().map(|(_, n)| *n as u64).sum(),
+        messages_by_type: by_type.into_iter().map(|(t, n)| (t, n as u64)).collect(),
+        tool_calls_by_name: by_tool.into_iter().map(|(t, n)| (t, n as u64)).collect(),
+        content_bytes: totals.0 as u64,
+        first_timestamp: totals.1,
+        last_timestamp: totals.2,
+        error_count: (totals.3 + failed_tools.0) as u64,
+    })
+}
+
+async fn search_logs(
+    db: &sqlx::SqlitePool,
+    project_dir: &str,
+    query: &str,
+    limit: u32,
+) -> Result<Vec<SessionSearchResult>, sqlx::Error> {
+    let pattern = format!(
+        "%{}%",
+        query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+    let needle = query.to_lowercase();
+    let mut entries: Vec<LogEntry> = Vec::new();
+    let mut before_id = i64::MAX;
+    // Newest first. Compressed rows can't be matched with LIKE; they come
+    // through and are checked once decompressed.
+    loop {
+        let rows: Vec<LogEntryRow> = sqlx::query_as(
+            "SELECT le.id, le.session_id, le.message_type, le.content, le.content_gz,
+                    le.timestamp, le.input_tokens, le.output_tokens
+             FROM log_entries le
+             JOIN sessions s ON s.id = le.session_id
+             WHERE s.project_dir = ? AND le.id < ?
+               AND (le.content LIKE ? ESCAPE '\\' OR le.content_gz IS NOT NULL)
+             ORDER BY le.id DESC
+             LIMIT ?",
+        )
+        .bind(project_dir)
+        .bind(before_id)
+        .bind(&pattern)
+        .bind(limit)
+        .fetch_all(db)
+        .await?;
+        let exhausted = (rows.len() as u32) < limit;
+        if let Some(last) = rows.last() {
+            before_id = last.id;
+        }
+        for row in rows {
+            let compressed = row.content_gz.is_some();
+            let entry = LogEntry::from(row);
+            if !compressed || entry.content.to_lowercase().contains(&needle) {
+                entries.push(entry);
+            }
+        }
+        if exhausted || entries.len() >= limit as usize {
+            break;
+        }
+    }
+    entries.truncate(limit as usize);
+
+    let mut results: Vec<SessionSearchResult> = Vec::new();
+    for entry in entries {
+        if let Some(result) = results
+            .iter_mut()
+            .find(|r| r.session_id == entry.session_id)
+        {
+            result.matches.push(entry);
+            continue;
+        }
+        let (agent_name, model, prompt, started_at, project_dir): (
+            String,
+            String,
+            String,
+            String,
+            Option<String>,
+        ) = sqlx::query_as(
+            "SELECT agent_name, model, prompt, started_at, project_dir FROM sessions WHERE id = ?",
+        )
+        .bind(&entry.session_id)
+        .fetch_one(db)
+        .await?;
+        results.push(SessionSearchResult {
+            session_id: entry.session_id.clone(),
+            agent_name,
+            model,
+            prompt,
+            started_at,
+            project_dir,
+            matches: vec![entry],
+        });
+    }
+    Ok(results)
+}
+
+/// The SQL for one page of a filtered query. Content predicates also admit
+/// compressed rows, which `matches_content` re-checks once decompressed.
+fn filtered_query<'a>(
+    session_id: Option<&'a str>,
+    filter: &'a LogQueryFilter,
+    after_id: u64,
+    limit: u32,
+) -> sqlx::QueryBuilder<'a, sqlx::Sqlite> {
+    let mut query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+        "SELECT id, session_id, message_type, content, content_gz, timestamp, input_tokens, output_tokens
+         FROM log_entries
+         WHERE id > ",
+    );
+    query.push_bind(after_id as i64);
+    if let Some(session_id) = session_id {
+        query.push(" AND session_id = ").push_bind(session_id);
+    }
+
+    if !filter.message_types.is_empty() {
+        query.push(" AND (");
+        let mut types = query.separated(" OR ");
+        for message_type in &filter.message_types {
+            match message_type.as_str() {
+                "tool_use" => types.push(
+                    "(message_type = 'assistant'
+                      AND (content LIKE '%\"type\":\"tool_use\"%' OR content_gz IS NOT NULL))",
+                ),
+                "tool_result" => types.push(
+                    "(message_type = 'user'
+                      AND (content LIKE '%\"type\":\"tool_result\"%' OR content_gz IS NOT NULL))",
+                ),
+                other => types.push("message_type = ").push_bind_unseparated(other),
+            };
+        }
+        query.push(")");
+    }
+    if filter.errors_only {
+        query.push(
+            " AND (message_type = 'stderr'
+               OR (message_type IN ('user', 'result')
+                   AND (content LIKE '%\"is_error\":true%' OR content_gz IS NOT NULL))
+               OR (message_type = 'result' AND content LIKE '%\"subtype\":\"error%'))",
+        );
+    }
+    if let Some(since) = &filter.since {
+        query.push(" AND timestamp >= ").push_bind(since);
+    }
+    if let Some(until) = &filter.until {
+        query.push(" AND timestamp <= ").push_bind(until);
+    }
+    query.push(" ORDER BY id ASC LIMIT ").push_bind(limit);
+    query
+}
+
+/// `filtered_query`'s content predicates, for rows SQL couldn't look into.
+fn matches_content(filter: &LogQueryFilter, message_type: &str, content: &str) -> bool {
+    let type_matches = filter.message_types.is_empty()
+        || filter.message_types.iter().any(|t| match t.as_str() {
+            "tool_use" => message_type == "assistant" && content.contains(r#""type":"tool_use""#),
+            "tool_result" => message_type == "user" && content.contains(r#""type":"tool_result""#),
+            other => message_type == other,
+        });
+    let error_matches = !filter.errors_only
+        || message_type == "stderr"
+        || (matches!(message_type, "user" | "result") && content.contains(r#""is_error":true"#))
+        || (message_type == "result" && content.contains(r#""subtype":"error"#));
+    type_matches && error_matches
+}
+
+#[derive(sqlx::FromRow)]
+struct ToolCallRow {
+    id: i64,
+    session_id: String,
+    tool_use_id: String,
+    tool_name: String,
+    input_summary: String,
+    started_at: String,
+    finished_at: Option<String>,
+    duration_ms: Option<i64>,
+    success: Option<bool>,
+}
+
+#[derive(sqlx::FromRow)]
+struct FileChangeRow {
+    id: i64,
+    session_id: String,
+    file_path: String,
+    operation: String,
     timestamp: String,
 }
 
@@ -186,20 +971,109 @@ async fn flush_batch(db_path: &str, batch: &[BufferedEntry]) -> Result<(), Domai
         .await
         .map_err(|e| DomainError::Database(e.to_string()))?;
 
+    let result = insert_batch(&db, batch).await;
+    db.close().await;
+    result
+}
+
+/// Insert a batch atomically: either every entry is stored or none is.
+async fn insert_batch(db: &sqlx::SqlitePool, batch: &[BufferedEntry]) -> Result<(), DomainError> {
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+    // log_entries references sessions; give sessions that were never
+    // recorded (started before sessions were stored) a placeholder row
+    let mut seen: Vec<&str> = Vec::new();
     for entry in batch {
+        if seen.contains(&entry.session_id.as_str()) {
+            continue;
+        }
+        seen.push(&entry.session_id);
         sqlx::query(
-            "INSERT INTO log_entries (session_id, message_type, content, timestamp)
-             VALUES (?, ?, ?, ?)",
+            "INSERT OR IGNORE INTO sessions (id, agent_name, model, status, prompt, started_at)
+             VALUES (?, '', '', 'unknown', '', ?)",
         )
         .bind(&entry.session_id)
-        .bind(&entry.message_type)
-        .bind(&entry.content)
         .bind(&entry.timestamp)
-        .execute(&db)
+        .execute(&mut *tx)
         .await
         .map_err(|e| DomainError::Database(e.to_string()))?;
     }
+    for entry in batch {
+        insert_entry(
+            &mut tx,
+            &entry.session_id,
+            &entry.message_type,
+            &entry.content,
+            &entry.timestamp,
+            entry.usage,
+        )
+        .await?;
+    }
+    tx.commit()
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))
+}
 
-    db.close().await;
+async fn insert_entry(
+    conn: &mut sqlx::SqliteConnection,
+    session_id: &str,
+    message_type: &str,
+    content: &str,
+    timestamp: &str,
+    usage: Option<MessageUsage>,
+) -> Result<(), DomainError> {
+    let compressed = compress(content);
+    let (content, size) = match &compressed {
+        Some(_) => ("", Some(content.len() as i64)),
+        None => (content, None),
+    };
+    sqlx::query(
+        "INSERT INTO log_entries
+            (session_id, message_type, content, content_gz, content_size, timestamp,
+             input_tokens, output_tokens)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(session_id)
+    .bind(message_type)
+    .bind(content)
+    .bind(compressed)
+    .bind(size)
+    .bind(timestamp)
+    .bind(usage.map(|u| u.input_tokens as i64))
+    .bind(usage.map(|u| u.output_tokens as i64))
+    .execute(conn)
+    .await
+    .map_err(|e| DomainError::Database(e.to_string()))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_entries_round_trip_through_compression() {
+        assert!(compress("small").is_none());
+
+        let content = format!(
+            r#"{{"type":"user","message":{{"content":[{{"type":"tool_result","is_error":true,"content":"{}"}}]}}}}"#,
+            "x".repeat(COMPRESS_THRESHOLD)
+        );
+        let gz = compress(&content).unwrap();
+        assert!(gz.len() < content.len());
+        assert_eq!(decompress(&gz).unwrap(), content);
+
+        let errors = LogQueryFilter {
+            errors_only: true,
+            ..Default::default()
+        };
+        assert!(matches_content(&errors, "user", &content));
+        let tool_uses = LogQueryFilter {
+            message_types: vec!["tool_use".into()],
+            ..Default::default()
+        };
+        assert!(!matches_content(&tool_uses, "user", &content));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::domain::error::DomainError;
+use crate::domain::models::{DbInfo, DbIntegrityReport, DbTableInfo, DbVacuumResult};
+
+/// Size, vacuum and integrity checks on the SQLite file shared by the log
+/// and workflow repositories. No trait; only the maintenance commands use it.
+pub struct SqliteMaintenance {
+    db_path: String,
+}
+
+impl SqliteMaintenance {
+    pub fn new(db_path: String) -> Self {
+        Self { db_path }
+    }
+
+    async fn connect(&self) -> Result<sqlx::SqlitePool, DomainError> {
+        let url = format!("sqlite:{}?mode=rwc", self.db_path);
+        sqlx::SqlitePool::connect(&url)
+            .await
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Bytes on disk, including the WAL and shared-memory files if present.
+    fn file_size(&self) -> u64 {
+        ["", "-wal", "-shm"]
+            .iter()
+            .filter_map(|suffix| std::fs::metadata(format!("{}{suffix}", self.db_path)).ok())
+            .map(|meta| meta.len())
+            .sum()
+    }
+
+    pub async fn info(&self) -> Result<DbInfo, DomainError> {
+        let db = self.connect().await?;
+        let info = table_info(&db).await;
+        db.close().await;
+        let (tables, page_size, page_count, freelist_count) =
+            info.map_err(|e| DomainError::Database(e.to_string()))?;
+        Ok(DbInfo {
+            path: self.db_path.clone(),
+            file_size_bytes: self.file_size(),
+            free_bytes: page_size * freelist_count,
+            page_count,
+            tables,
+        })
+    }
+
+    /// Rebuild the file to reclaim the space of deleted rows. Holds an
+    /// exclusive lock for as long as it runs.
+    pub async fn vacuum(&self) -> Result<DbVacuumResult, DomainError> {
+        let size_before = self.file_size();
+        let db = self.connect().await?;
+        let result = sqlx::query("VACUUM").execute(&db).await;
+        // Fold the WAL back in so the size reported is the real one
+        let _ = sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(&db)
+            .await;
+        db.close().await;
+        result.map_err(|e| DomainError::Database(e.to_string()))?;
+        Ok(DbVacuumResult {
+            size_before,
+            size_after: self.file_size(),
+        })
+    }
+
+    pub async fn integrity_check(&self) -> Result<DbIntegrityReport, DomainError> {
+        let db = self.connect().await?;
+        let rows: Result<Vec<(String,)>, _> = sqlx::query_as("PRAGMA integrity_check")
+            .fetch_all(&db)
+            .await;
+        db.close().await;
+        let problems: Vec<String> = rows
+            .map_err(|e| DomainError::Database(e.to_string()))?
+            .into_iter()
+            .map(|(line,)| line)
+            .filter(|line| line != "ok")
+            .collect();
+        Ok(DbIntegrityReport {
+            ok: problems.is_empty(),
+            problems,
+        })
+    }
+}
+
+async fn table_info(
+    db: &sqlx::SqlitePool,
+) -> Result<(Vec<DbTableInfo>, u64, u64, u64), sqlx::Error> {
+    let names: Vec<(String,)> = sqlx::query_as(
+        "SELECT name FROM sqlite_master
+         WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+         ORDER BY name",
+    )
+    .fetch_all(db)
+    .await?;
+    let mut tables = Vec::with_capacity(names.len());
+    for (name,) in names {
+        // Names come from sqlite_master, not from the caller
+        let sql = format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\""));
+        let (rows,): (i64,) = sqlx::query_as(&sql).fetch_one(db).await?;
+        tables.push(DbTableInfo {
+            name,
+            rows: rows as u64,
+        });
+    }
+    let (page_size,): (i64,) = sqlx::query_as("PRAGMA page_size").fetch_one(db).await?;
+    let (page_count,): (i64,) = sqlx::query_as("PRAGMA page_count").fetch_one(db).await?;
+    let (freelist_count,): (i64,) = sqlx::query_as("PRAGMA freelist_count")
+        .fetch_one(db)
+        .await?;
+    Ok((
+        tables,
+        page_size as u64,
+        page_count as u64,
+        freelist_count as u64,
+    ))
+}
@@ -0,0 +1,259 @@
+use crate::domain::error::DomainError;
+use tokio::sync::Mutex;
+
+/// One schema change, applied once and recorded in `schema_version`.
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// Every migration, in the order they apply. Append only: never edit or
+/// renumber one that has shipped.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial",
+        sql: include_str!("../../migrations/001_initial.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "file_changes",
+        sql: include_str!("../../migrations/002_file_changes.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "workflows",
+        sql: include_str!("../../migrations/003_workflows.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "workflow_context",
+        sql: include_str!("../../migrations/004_workflow_context.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "step_failure_policy",
+        sql: include_str!("../../migrations/005_step_failure_policy.sql"),
+    },
+    Migration {
+        version: 6,
+        name: "step_join_mode",
+        sql: include_str!("../../migrations/006_step_join_mode.sql"),
+    },
+    Migration {
+        version: 7,
+        name: "gate_steps",
+        sql: include_str!("../../migrations/007_gate_steps.sql"),
+    },
+    Migration {
+        version: 8,
+        name: "prompt_templates",
+        sql: include_str!("../../migrations/008_prompt_templates.sql"),
+    },
+    Migration {
+        version: 9,
+        name: "tool_calls",
+        sql: include_str!("../../migrations/009_tool_calls.sql"),
+    },
+    Migration {
+        version: 10,
+        name: "file_change_tool_use",
+        sql: include_str!("../../migrations/010_file_change_tool_use.sql"),
+    },
+    Migration {
+        version: 11,
+        name: "log_compression",
+        sql: include_str!("../../migrations/011_log_compression.sql"),
+    },
+    Migration {
+        version: 12,
+        name: "log_usage",
+        sql: include_str!("../../migrations/012_log_usage.sql"),
+    },
+    Migration {
+        version: 13,
+        name: "session_project",
+        sql: include_str!("../../migrations/013_session_project.sql"),
+    },
+    Migration {
+        version: 14,
+        name: "session_branch",
+        sql: include_str!("../../migrations/014_session_branch.sql"),
+    },
+    Migration {
+        version: 15,
+        name: "session_snapshots",
+        sql: include_str!("../../migrations/015_session_snapshots.sql"),
+    },
+    Migration {
+        version: 16,
+        name: "step_auto_commit",
+        sql: include_str!("../../migrations/016_step_auto_commit.sql"),
+    },
+    Migration {
+        version: 17,
+        name: "usage_snapshots",
+        sql: include_str!("../../migrations/017_usage_snapshots.sql"),
+    },
+    Migration {
+        version: 18,
+        name: "session_cost_estimated",
+        sql: include_str!("../../migrations/018_session_cost_estimated.sql"),
+    },
+    Migration {
+        version: 19,
+        name: "claude_profiles",
+        sql: include_str!("../../migrations/019_claude_profiles.sql"),
+    },
+    Migration {
+        version: 20,
+        name: "workflow_max_parallel",
+        sql: include_str!("../../migrations/020_workflow_max_parallel.sql"),
+    },
+];
+
+/// Both SQLite adapters migrate the same file on startup; this keeps them
+/// from applying the same version twice.
+static MIGRATE_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Apply every migration newer than the recorded schema version, each in
+/// its own transaction. Returns the version the database ends up at.
+pub async fn migrate(db: &sqlx::SqlitePool) -> Result<u32, DomainError> {
+    let _migrating = MIGRATE_LOCK.lock().await;
+    let db_err = |e: sqlx::Error| DomainError::Database(e.to_string());
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        )",
+    )
+    .execute(db)
+    .await
+    .map_err(db_err)?;
+    let (current,): (i64,) = sqlx::query_as("SELECT COALESCE(MAX(version), 0) FROM schema_version")
+        .fetch_one(db)
+        .await
+        .map_err(db_err)?;
+
+    let mut version = current as u32;
+    let pending = MIGRATIONS.iter().filter(|m| m.version > current as u32);
+    for migration in pending {
+        let mut tx = db.begin().await.map_err(db_err)?;
+        for stmt in split_statements(migration.sql) {
+            if let Err(e) = sqlx::query(&stmt).execute(&mut *tx).await {
+                // Databases from before schema_version ran every file on
+                // each start, so their columns may already exist
+                if e.to_string().contains("duplicate column name") {
+                    continue;
+                }
+                return Err(DomainError::Database(format!(
+                    "migration {:03}_{}: {e}: {stmt}",
+                    migration.version, migration.name
+                )));
+            }
+        }
+        sqlx::query("INSERT INTO schema_version (version, name, applied_at) VALUES (?, ?, ?)")
+            .bind(migration.version as i64)
+            .bind(migration.name)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_err)?;
+        tx.commit().await.map_err(db_err)?;
+        version = migration.version;
+    }
+    Ok(version)
+}
+
+/// Split a SQL script on top-level `;`, skipping comments and leaving
+/// semicolons inside quoted strings and identifiers alone.
+pub fn split_statements(sql: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut chars = sql.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                current.push(c);
+                // A doubled quote is an escaped quote and stays inside
+                while let Some(inner) = chars.next() {
+                    current.push(inner);
+                    if inner == c {
+                        if chars.peek() == Some(&c) {
+                            current.push(chars.next().unwrap_or(c));
+                        } else {
+                            break;
+                        }
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for skipped in chars.by_ref() {
+                    if skipped == '\n' {
+                        current.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = '\0';
+                for skipped in chars.by_ref() {
+                    if prev == '*' && skipped == '/' {
+                        break;
+                    }
+                    prev = skipped;
+                }
+                current.push(' ');
+            }
+            ';' => {
+                let stmt = current.trim();
+                if !stmt.is_empty() {
+                    statements.push(stmt.to_string());
+                }
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    let stmt = current.trim();
+    if !stmt.is_empty() {
+        statements.push(stmt.to_string());
+    }
+    statements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statements_split_outside_quotes_and_comments() {
+        let sql = "-- header; with a semicolon\n\
+                   CREATE TABLE t (a TEXT DEFAULT 'x;y', \"b;c\" INTEGER);\n\
+                   /* block; comment */ INSERT INTO t (a) VALUES ('it''s; fine');\n\
+                   ALTER TABLE t ADD COLUMN d TEXT";
+        assert_eq!(
+            split_statements(sql),
+            [
+                "CREATE TABLE t (a TEXT DEFAULT 'x;y', \"b;c\" INTEGER)",
+                "INSERT INTO t (a) VALUES ('it''s; fine')",
+                "ALTER TABLE t ADD COLUMN d TEXT",
+            ]
+        );
+
+        let versions: Vec<u32> = MIGRATIONS.iter().map(|m| m.version).collect();
+        assert_eq!(versions, (1..=MIGRATIONS.len() as u32).collect::<Vec<_>>());
+        for migration in MIGRATIONS {
+            assert!(
+                !split_statements(migration.sql).is_empty(),
+                "{}",
+                migration.name
+            );
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::domain::error::DomainError;
+use crate::domain::models::{ModelUsage, UsageSnapshot};
+use crate::domain::ports::UsageRepository;
+use async_trait::async_trait;
+
+/// UsageRepository adapter backed by SQLite. Shares the log repository's
+/// database file, which migrates the schema.
+pub struct SqliteUsageRepository {
+    db_path: String,
+}
+
+impl SqliteUsageRepository {
+    pub fn new(db_path: String) -> Self {
+        Self { db_path }
+    }
+
+    async fn connect(&self) -> Result<sqlx::SqlitePool, DomainError> {
+        let url = format!("sqlite:{}?mode=rwc", self.db_path);
+        sqlx::SqlitePool::connect(&url)
+            .await
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl UsageRepository for SqliteUsageRepository {
+    async fn record_usage_snapshot(
+        &self,
+        date: &str,
+        profile: Option<&str>,
+        models: &[ModelUsage],
+    ) -> Result<(), DomainError> {
+        let db = self.connect().await?;
+        let result = insert_snapshot(&db, date, profile.unwrap_or(""), models).await;
+        db.close().await;
+        result
+    }
+
+    async fn usage_snapshots(
+        &self,
+        until: Option<&str>,
+    ) -> Result<Vec<UsageSnapshot>, DomainError> {
+        let db = self.connect().await?;
+        let rows: Result<Vec<SnapshotRow>, _> = sqlx::query_as(
+            "SELECT date, profile, model, input_tokens, output_tokens, cache_read_tokens,
+                    cache_creation_tokens, cost_usd
+             FROM usage_snapshots
+             WHERE ? IS NULL OR date <= ?
+             ORDER BY date, profile, model",
+        )
+        .bind(until)
+        .bind(until)
+        .fetch_all(&db)
+        .await;
+        db.close().await;
+        Ok(rows
+            .map_err(|e| DomainError::Database(e.to_string()))?
+            .into_iter()
+            .map(UsageSnapshot::from)
+            .collect())
+    }
+}
+
+/// Replace a day's snapshot atomically: every model is stored or none is.
+/// The default profile is stored as `''`.
+async fn insert_snapshot(
+    db: &sqlx::SqlitePool,
+    date: &str,
+    profile: &str,
+    models: &[ModelUsage],
+) -> Result<(), DomainError> {
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+    let recorded_at = chrono::Utc::now().to_rfc3339();
+    for usage in models {
+        sqlx::query(
+            "INSERT OR REPLACE INTO usage_snapshots
+             (date, profile, model, input_tokens, output_tokens, cache_read_tokens,
+              cache_creation_tokens, cost_usd, recorded_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(date)
+        .bind(profile)
+        .bind(&usage.model)
+        .bind(usage.input_tokens as i64)
+        .bind(usage.output_tokens as i64)
+        .bind(usage.cache_read_tokens as i64)
+        .bind(usage.cache_creation_tokens as i64)
+        .bind(usage.cost_usd)
+        .bind(&recorded_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+    }
+    tx.commit()
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))
+}
+
+#[derive(sqlx::FromRow)]
+struct SnapshotRow {
+    date: String,
+    profile: String,
+    model: String,
+    input_tokens: i64,
+    output_tokens: i64,
+    cache_read_tokens: i64,
+    cache_creation_tokens: i64,
+    cost_usd: f64,
+}
+
+impl From<SnapshotRow> for UsageSnapshot {
+    fn from(r: SnapshotRow) -> Self {
+        UsageSnapshot {
+            date: r.date,
+            profile: Some(r.profile).filter(|p| !p.is_empty()),
+            usage: ModelUsage {
+                model: r.model,
+                input_tokens: r.input_tokens as u64,
+                output_tokens: r.output_tokens as u64,
+                cache_read_tokens: r.cache_read_tokens as u64,
+                cache_creation_tokens: r.cache_creation_tokens as u64,
+                cost_usd: r.cost_usd,
+            },
+        }
+    }
+}
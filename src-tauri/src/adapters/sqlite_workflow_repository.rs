@@ -1,3 +1,4 @@
+use crate::adapters::sqlite_migrations;
 use crate::domain::error::DomainError;
 use crate::domain::models::*;
 use crate::domain::ports::WorkflowRepository;
@@ -18,6 +19,15 @@ impl SqliteWorkflowRepository {
             .await
             .map_err(|e| DomainError::Database(e.to_string()))
     }
+
+    /// Bring the schema up to date. Safe to run alongside the log
+    /// repository's `init`, which migrates the same file.
+    pub async fn init(&self) -> Result<(), DomainError> {
+        let db = self.connect().await?;
+        let result = sqlite_migrations::migrate(&db).await;
+        db.close().await;
+        result.map(|_| ())
+    }
 }
 
 #[async_trait]
@@ -25,8 +35,8 @@ impl WorkflowRepository for SqliteWorkflowRepository {
     async fn save_workflow(&self, w: &Workflow) -> Result<(), DomainError> {
         let db = self.connect().await?;
         sqlx::query(
-            "INSERT INTO workflows (id, name, description, status, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?)",
+            "INSERT INTO workflows (id, name, description, status, created_at, updated_at, max_parallel)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(&w.id)
         .bind(&w.name)
@@ -34,6 +44,7 @@ impl WorkflowRepository for SqliteWorkflowRepository {
         .bind(w.status.to_string())
         .bind(&w.created_at)
         .bind(&w.updated_at)
+        .bind(w.max_parallel.map(i64::from))
         .execute(&db)
         .await
         .map_err(|e| DomainError::Database(e.to_string()))?;
@@ -43,8 +54,8 @@ impl WorkflowRepository for SqliteWorkflowRepository {
 
     async fn get_workflow(&self, id: &str) -> Result<Option<Workflow>, DomainError> {
         let db = self.connect().await?;
-        let row = sqlx::query_as::<_, (String, String, Option<String>, String, String, String)>(
-            "SELECT id, name, description, status, created_at, updated_at FROM workflows WHERE id = ?",
+        let row = sqlx::query_as::<_, (String, String, Option<String>, String, String, String, Option<i64>)>(
+            "SELECT id, name, description, status, created_at, updated_at, max_parallel FROM workflows WHERE id = ?",
         )
         .bind(id)
         .fetch_optional(&db)
@@ -58,13 +69,14 @@ impl WorkflowRepository for SqliteWorkflowRepository {
             status: parse_workflow_status(&r.3),
             created_at: r.4,
             updated_at: r.5,
+            max_parallel: r.6.map(|n| n as u32),
         }))
     }
 
     async fn list_workflows(&self) -> Result<Vec<Workflow>, DomainError> {
         let db = self.connect().await?;
-        let rows = sqlx::query_as::<_, (String, String, Option<String>, String, String, String)>(
-            "SELECT id, name, description, status, created_at, updated_at FROM workflows ORDER BY updated_at DESC",
+        let rows = sqlx::query_as::<_, (String, String, Option<String>, String, String, String, Option<i64>)>(
+            "SELECT id, name, description, status, created_at, updated_at, max_parallel FROM workflows ORDER BY updated_at DESC",
         )
         .fetch_all(&db)
         .await
@@ -79,6 +91,7 @@ impl WorkflowRepository for SqliteWorkflowRepository {
                 status: parse_workflow_status(&r.3),
                 created_at: r.4,
                 updated_at: r.5,
+                max_parallel: r.6.map(|n| n as u32),
             })
             .collect())
     }
@@ -115,8 +128,8 @@ impl WorkflowRepository for SqliteWorkflowRepository {
     async fn save_step(&self, s: &WorkflowStep) -> Result<(), DomainError> {
         let db = self.connect().await?;
         sqlx::query(
-            "INSERT INTO workflow_steps (id, workflow_id, agent_name, model, prompt, spec_path, status, session_id, position_x, position_y, created_at, pass_context, result_output)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO workflow_steps (id, workflow_id, agent_name, model, prompt, spec_path, status, session_id, position_x, position_y, created_at, pass_context, result_output, failure_policy, join_mode, kind, gate_command, prompt_template_id, template_vars, commit_message)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(&s.id)
         .bind(&s.workflow_id)
@@ -131,6 +144,13 @@ impl WorkflowRepository for SqliteWorkflowRepository {
         .bind(&s.created_at)
         .bind(s.pass_context)
         .bind(&s.result_output)
+        .bind(s.failure_policy.to_string())
+        .bind(s.join_mode.to_string())
+        .bind(s.kind.to_string())
+        .bind(&s.gate_command)
+        .bind(&s.prompt_template_id)
+        .bind(vars_to_json(&s.template_vars))
+        .bind(&s.commit_message)
         .execute(&db)
         .await
         .map_err(|e| DomainError::Database(e.to_string()))?;
@@ -158,8 +178,8 @@ impl WorkflowRepository for SqliteWorkflowRepository {
 
     async fn get_steps(&self, workflow_id: &str) -> Result<Vec<WorkflowStep>, DomainError> {
         let db = self.connect().await?;
-        let rows = sqlx::query_as::<_, (String, String, String, String, String, Option<String>, String, Option<String>, f64, f64, String, i32, Option<String>)>(
-            "SELECT id, workflow_id, agent_name, model, prompt, spec_path, status, session_id, position_x, position_y, created_at, pass_context, result_output FROM workflow_steps WHERE workflow_id = ?",
+        let rows = sqlx::query_as::<_, WorkflowStepRow>(
+            "SELECT id, workflow_id, agent_name, model, prompt, spec_path, status, session_id, position_x, position_y, created_at, pass_context, result_output, failure_policy, join_mode, kind, gate_command, prompt_template_id, template_vars, commit_message FROM workflow_steps WHERE workflow_id = ?",
         )
         .bind(workflow_id)
         .fetch_all(&db)
@@ -169,19 +189,26 @@ impl WorkflowRepository for SqliteWorkflowRepository {
         Ok(rows
             .into_iter()
             .map(|r| WorkflowStep {
-                id: r.0,
-                workflow_id: r.1,
-                agent_name: r.2,
-                model: r.3,
-                prompt: r.4,
-                spec_path: r.5,
-                status: parse_step_status(&r.6),
-                session_id: r.7,
-                position_x: r.8,
-                position_y: r.9,
-                created_at: r.10,
-                pass_context: r.11 != 0,
-                result_output: r.12,
+                id: r.id,
+                workflow_id: r.workflow_id,
+                agent_name: r.agent_name,
+                model: r.model,
+                prompt: r.prompt,
+                spec_path: r.spec_path,
+                status: parse_step_status(&r.status),
+                session_id: r.session_id,
+                position_x: r.position_x,
+                position_y: r.position_y,
+                created_at: r.created_at,
+                pass_context: r.pass_context != 0,
+                result_output: r.result_output,
+                failure_policy: parse_failure_policy(&r.failure_policy),
+                join_mode: parse_join_mode(&r.join_mode),
+                kind: parse_step_kind(&r.kind),
+                gate_command: r.gate_command,
+                prompt_template_id: r.prompt_template_id,
+                template_vars: vars_from_json(&r.template_vars),
+                commit_message: r.commit_message,
             })
             .collect())
     }
@@ -189,7 +216,7 @@ impl WorkflowRepository for SqliteWorkflowRepository {
     async fn update_step(&self, s: &WorkflowStep) -> Result<(), DomainError> {
         let db = self.connect().await?;
         sqlx::query(
-            "UPDATE workflow_steps SET agent_name = ?, model = ?, prompt = ?, spec_path = ?, position_x = ?, position_y = ?, pass_context = ? WHERE id = ?",
+            "UPDATE workflow_steps SET agent_name = ?, model = ?, prompt = ?, spec_path = ?, position_x = ?, position_y = ?, pass_context = ?, failure_policy = ?, join_mode = ?, kind = ?, gate_command = ?, prompt_template_id = ?, template_vars = ?, commit_message = ? WHERE id = ?",
         )
         .bind(&s.agent_name)
         .bind(&s.model)
@@ -198,6 +225,13 @@ impl WorkflowRepository for SqliteWorkflowRepository {
         .bind(s.position_x)
         .bind(s.position_y)
         .bind(s.pass_context)
+        .bind(s.failure_policy.to_string())
+        .bind(s.join_mode.to_string())
+        .bind(s.kind.to_string())
+        .bind(&s.gate_command)
+        .bind(&s.prompt_template_id)
+        .bind(vars_to_json(&s.template_vars))
+        .bind(&s.commit_message)
         .bind(&s.id)
         .execute(&db)
         .await
@@ -218,6 +252,19 @@ impl WorkflowRepository for SqliteWorkflowRepository {
         Ok(())
     }
 
+    async fn claim_step(&self, id: &str) -> Result<bool, DomainError> {
+        let db = self.connect().await?;
+        let result = sqlx::query(
+            "UPDATE workflow_steps SET status = 'running' WHERE id = ? AND status = 'pending'",
+        )
+        .bind(id)
+        .execute(&db)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+        db.close().await;
+        Ok(result.rows_affected() == 1)
+    }
+
     async fn delete_step(&self, id: &str) -> Result<(), DomainError> {
         let db = self.connect().await?;
         sqlx::query("DELETE FROM workflow_steps WHERE id = ?")
@@ -232,13 +279,14 @@ impl WorkflowRepository for SqliteWorkflowRepository {
     async fn save_edge(&self, e: &WorkflowEdge) -> Result<(), DomainError> {
         let db = self.connect().await?;
         sqlx::query(
-            "INSERT INTO workflow_edges (id, workflow_id, source_step_id, target_step_id)
-             VALUES (?, ?, ?, ?)",
+            "INSERT INTO workflow_edges (id, workflow_id, source_step_id, target_step_id, condition)
+             VALUES (?, ?, ?, ?, ?)",
         )
         .bind(&e.id)
         .bind(&e.workflow_id)
         .bind(&e.source_step_id)
         .bind(&e.target_step_id)
+        .bind(e.condition.to_string())
         .execute(&db)
         .await
         .map_err(|e| DomainError::Database(e.to_string()))?;
@@ -248,8 +296,8 @@ impl WorkflowRepository for SqliteWorkflowRepository {
 
     async fn get_edges(&self, workflow_id: &str) -> Result<Vec<WorkflowEdge>, DomainError> {
         let db = self.connect().await?;
-        let rows = sqlx::query_as::<_, (String, String, String, String)>(
-            "SELECT id, workflow_id, source_step_id, target_step_id FROM workflow_edges WHERE workflow_id = ?",
+        let rows = sqlx::query_as::<_, (String, String, String, String, String)>(
+            "SELECT id, workflow_id, source_step_id, target_step_id, condition FROM workflow_edges WHERE workflow_id = ?",
         )
         .bind(workflow_id)
         .fetch_all(&db)
@@ -263,6 +311,7 @@ impl WorkflowRepository for SqliteWorkflowRepository {
                 workflow_id: r.1,
                 source_step_id: r.2,
                 target_step_id: r.3,
+                condition: parse_edge_condition(&r.4),
             })
             .collect())
     }
@@ -277,6 +326,119 @@ impl WorkflowRepository for SqliteWorkflowRepository {
         db.close().await;
         Ok(())
     }
+
+    async fn save_prompt_template(&self, t: &PromptTemplate) -> Result<(), DomainError> {
+        let db = self.connect().await?;
+        sqlx::query(
+            "INSERT INTO prompt_templates (id, name, body, variables, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&t.id)
+        .bind(&t.name)
+        .bind(&t.body)
+        .bind(vars_to_json(&t.variables))
+        .bind(&t.created_at)
+        .bind(&t.updated_at)
+        .execute(&db)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+        db.close().await;
+        Ok(())
+    }
+
+    async fn get_prompt_template(&self, id: &str) -> Result<Option<PromptTemplate>, DomainError> {
+        let db = self.connect().await?;
+        let row = sqlx::query_as::<_, (String, String, String, String, String, String)>(
+            "SELECT id, name, body, variables, created_at, updated_at FROM prompt_templates WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&db)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+        db.close().await;
+        Ok(row.map(|r| PromptTemplate {
+            id: r.0,
+            name: r.1,
+            body: r.2,
+            variables: vars_from_json(&r.3),
+            created_at: r.4,
+            updated_at: r.5,
+        }))
+    }
+
+    async fn list_prompt_templates(&self) -> Result<Vec<PromptTemplate>, DomainError> {
+        let db = self.connect().await?;
+        let rows = sqlx::query_as::<_, (String, String, String, String, String, String)>(
+            "SELECT id, name, body, variables, created_at, updated_at FROM prompt_templates ORDER BY name ASC",
+        )
+        .fetch_all(&db)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+        db.close().await;
+        Ok(rows
+            .into_iter()
+            .map(|r| PromptTemplate {
+                id: r.0,
+                name: r.1,
+                body: r.2,
+                variables: vars_from_json(&r.3),
+                created_at: r.4,
+                updated_at: r.5,
+            })
+            .collect())
+    }
+
+    async fn update_prompt_template(&self, t: &PromptTemplate) -> Result<(), DomainError> {
+        let db = self.connect().await?;
+        sqlx::query(
+            "UPDATE prompt_templates SET name = ?, body = ?, variables = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(&t.name)
+        .bind(&t.body)
+        .bind(vars_to_json(&t.variables))
+        .bind(&t.updated_at)
+        .bind(&t.id)
+        .execute(&db)
+        .await
+        .map_err(|e| DomainError::Database(e.to_string()))?;
+        db.close().await;
+        Ok(())
+    }
+
+    async fn delete_prompt_template(&self, id: &str) -> Result<(), DomainError> {
+        let db = self.connect().await?;
+        sqlx::query("DELETE FROM prompt_templates WHERE id = ?")
+            .bind(id)
+            .execute(&db)
+            .await
+            .map_err(|e| DomainError::Database(e.to_string()))?;
+        db.close().await;
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct WorkflowStepRow {
+    id: String,
+    workflow_id: String,
+    agent_name: String,
+    model: String,
+    prompt: String,
+    spec_path: Option<String>,
+    status: String,
+    session_id: Option<String>,
+    position_x: f64,
+    position_y: f64,
+    created_at: String,
+    pass_context: i32,
+    result_output: Option<String>,
+    failure_policy: String,
+    join_mode: String,
+    kind: String,
+    gate_command: Option<String>,
+    prompt_template_id: Option<String>,
+    template_vars: String,
+    commit_message: Option<String>,
 }
 
 fn parse_workflow_status(s: &str) -> WorkflowStatus {
@@ -299,3 +461,40 @@ fn parse_step_status(s: &str) -> StepStatus {
         _ => StepStatus::Pending,
     }
 }
+
+fn parse_failure_policy(s: &str) -> FailurePolicy {
+    match s {
+        "continue" => FailurePolicy::Continue,
+        "skip_descendants" => FailurePolicy::SkipDescendants,
+        _ => FailurePolicy::FailWorkflow,
+    }
+}
+
+fn parse_join_mode(s: &str) -> JoinMode {
+    match s {
+        "any" => JoinMode::Any,
+        _ => JoinMode::All,
+    }
+}
+
+fn vars_to_json(vars: &std::collections::HashMap<String, String>) -> String {
+    serde_json::to_string(vars).unwrap_or_else(|_| "{}".into())
+}
+
+fn vars_from_json(s: &str) -> std::collections::HashMap<String, String> {
+    serde_json::from_str(s).unwrap_or_default()
+}
+
+fn parse_step_kind(s: &str) -> StepKind {
+    match s {
+        "gate" => StepKind::Gate,
+        _ => StepKind::Agent,
+    }
+}
+
+fn parse_edge_condition(s: &str) -> EdgeCondition {
+    match s {
+        "on_failure" => EdgeCondition::OnFailure,
+        _ => EdgeCondition::OnSuccess,
+    }
+}
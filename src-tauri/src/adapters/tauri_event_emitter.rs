@@ -1,29 +1,87 @@
 use crate::domain::error::DomainError;
-use crate::domain::ports::{EventEmitter, MessageEvent, RateLimitedEvent, StatusChangedEvent, UsageUpdateEvent};
+use crate::domain::ports::{
+    EventEmitter, FileConflictEvent, MessageEvent, RateLimitedEvent, StatusChangedEvent,
+    UsageUpdateEvent,
+};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tauri::{AppHandle, Emitter};
 
+/// Most messages sent in one `agent:messages` event.
+const MAX_BATCH: usize = 200;
+
+/// How often buffered messages go out.
+const BATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Messages kept waiting for a batch; the oldest are dropped past this.
+/// They're in the session's stored logs either way.
+const MAX_PENDING: usize = 10_000;
+
 /// EventEmitter adapter that pushes events via Tauri IPC.
+///
+/// Agent messages are coalesced: they are buffered and sent as
+/// `agent:messages` batches of at most `MAX_BATCH` every `BATCH_INTERVAL`,
+/// so a chatty agent doesn't flood the webview with one emit per line.
 pub struct TauriEventEmitter {
     app: AppHandle,
+    pending: Mutex<VecDeque<MessageEvent>>,
 }
 
 impl TauriEventEmitter {
     pub fn new(app: AppHandle) -> Self {
-        Self { app }
+        Self {
+            app,
+            pending: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Start the task that sends buffered messages. Anything past
+    /// `MAX_BATCH` waits for the next tick.
+    pub fn start_batch_task(self: &Arc<Self>) {
+        let emitter = Arc::clone(self);
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(BATCH_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = emitter.emit_batch(MAX_BATCH) {
+                    eprintln!("Failed to emit agent messages: {e}");
+                }
+            }
+        });
+    }
+
+    fn emit_batch(&self, max: usize) -> Result<(), DomainError> {
+        let batch: Vec<MessageEvent> = {
+            let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            let n = pending.len().min(max);
+            pending.drain(..n).collect()
+        };
+        if batch.is_empty() {
+            return Ok(());
+        }
+        self.app
+            .emit("agent:messages", batch)
+            .map_err(|e| DomainError::EventEmission(e.to_string()))
     }
 }
 
 impl EventEmitter for TauriEventEmitter {
     fn emit_status_changed(&self, event: StatusChangedEvent) -> Result<(), DomainError> {
+        // Messages first, so a session's last output lands before it is
+        // shown as finished
+        self.emit_batch(usize::MAX)?;
         self.app
             .emit("agent:status-changed", event)
             .map_err(|e| DomainError::EventEmission(e.to_string()))
     }
 
     fn emit_agent_message(&self, event: MessageEvent) -> Result<(), DomainError> {
-        self.app
-            .emit("agent:message", event)
-            .map_err(|e| DomainError::EventEmission(e.to_string()))
+        push_message(
+            &mut self.pending.lock().unwrap_or_else(|e| e.into_inner()),
+            event,
+        );
+        Ok(())
     }
 
     fn emit_usage_update(&self, event: UsageUpdateEvent) -> Result<(), DomainError> {
@@ -37,4 +95,46 @@ impl EventEmitter for TauriEventEmitter {
             .emit("agent:rate-limited", event)
             .map_err(|e| DomainError::EventEmission(e.to_string()))
     }
+
+    fn emit_file_conflict(&self, event: FileConflictEvent) -> Result<(), DomainError> {
+        self.app
+            .emit("conflict:detected", event)
+            .map_err(|e| DomainError::EventEmission(e.to_string()))
+    }
+}
+
+/// Queue `event`, dropping the oldest past `MAX_PENDING` so a burst from
+/// several agents can't outgrow what the batches send.
+fn push_message(pending: &mut VecDeque<MessageEvent>, event: MessageEvent) {
+    pending.push_back(event);
+    while pending.len() > MAX_PENDING {
+        pending.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_messages_are_capped_dropping_the_oldest() {
+        let mut pending = VecDeque::new();
+        for i in 0..MAX_PENDING + 5 {
+            push_message(
+                &mut pending,
+                MessageEvent {
+                    session_id: "s1".into(),
+                    message_type: "assistant".into(),
+                    content: i.to_string(),
+                    timestamp: String::new(),
+                },
+            );
+        }
+        assert_eq!(pending.len(), MAX_PENDING);
+        assert_eq!(pending.front().unwrap().content, "5");
+        assert_eq!(
+            pending.back().unwrap().content,
+            (MAX_PENDING + 4).to_string()
+        );
+    }
 }
@@ -0,0 +1,274 @@
+use crate::domain::error::DomainError;
+use crate::domain::ports::{
+    EventEmitter, FileConflictEvent, MessageEvent, RateLimitedEvent, StatusChangedEvent,
+    UsageUpdateEvent,
+};
+use crate::services::config_store::{WebhookConfig, WebhookEventKind};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How often buffered events are POSTed.
+const BATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Most events sent in one request.
+const MAX_BATCH: usize = 100;
+
+/// Events kept while the endpoint can't be reached; the oldest are dropped.
+const MAX_PENDING: usize = 1000;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Header carrying `sha256=<hex HMAC of "<timestamp>.<body>">` when a
+/// secret is set.
+pub const SIGNATURE_HEADER: &str = "X-Clautron-Signature";
+
+/// Header carrying the Unix time the request was signed at. It's part of
+/// the signature, so receivers can reject old requests replayed later.
+pub const TIMESTAMP_HEADER: &str = "X-Clautron-Timestamp";
+
+#[derive(Debug, Clone, Serialize)]
+struct WebhookEvent {
+    #[serde(rename = "type")]
+    kind: WebhookEventKind,
+    session_id: String,
+    timestamp: String,
+    data: serde_json::Value,
+}
+
+#[derive(Serialize)]
+struct WebhookBody<'a> {
+    source: &'static str,
+    events: &'a [WebhookEvent],
+}
+
+/// EventEmitter adapter that forwards events to `inner` and also POSTs
+/// them to the configured webhook.
+///
+/// Events are filtered by `WebhookConfig::events` and sent as JSON batches
+/// every `BATCH_INTERVAL`. A session's usage updates within one batch are
+/// collapsed into the latest. Failed batches are retried on the next tick.
+pub struct WebhookEventEmitter {
+    inner: Arc<dyn EventEmitter>,
+    config: Mutex<Option<WebhookConfig>>,
+    pending: Mutex<VecDeque<WebhookEvent>>,
+    client: reqwest::Client,
+}
+
+impl WebhookEventEmitter {
+    pub fn new(inner: Arc<dyn EventEmitter>) -> Self {
+        Self {
+            inner,
+            config: Mutex::new(None),
+            pending: Mutex::new(VecDeque::new()),
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Point the webhook at `config`; None turns it off and drops anything
+    /// not sent yet.
+    pub fn set_config(&self, config: Option<WebhookConfig>) {
+        if config.is_none() {
+            self.pending
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clear();
+        }
+        *self.config.lock().unwrap_or_else(|e| e.into_inner()) = config;
+    }
+
+    /// Start the task that sends buffered events.
+    pub fn start_batch_task(self: &Arc<Self>) {
+        let emitter = Arc::clone(self);
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(BATCH_INTERVAL);
+            loop {
+                interval.tick().await;
+                emitter.flush().await;
+            }
+        });
+    }
+
+    async fn flush(&self) {
+        let Some(config) = self
+            .config
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+        else {
+            return;
+        };
+        let batch: Vec<WebhookEvent> = {
+            let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            let n = pending.len().min(MAX_BATCH);
+            pending.drain(..n).collect()
+        };
+        if batch.is_empty() {
+            return;
+        }
+        if let Err(e) = self.post(&config, &batch).await {
+            eprintln!("Webhook delivery failed: {e}");
+            let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            for event in batch.into_iter().rev() {
+                pending.push_front(event);
+            }
+            // Events queued meanwhile are newer than the batch
+            while pending.len() > MAX_PENDING {
+                pending.pop_front();
+            }
+        }
+    }
+
+    async fn post(&self, config: &WebhookConfig, events: &[WebhookEvent]) -> Result<(), String> {
+        let body = serde_json::to_vec(&WebhookBody {
+            source: "clautron",
+            events,
+        })
+        .map_err(|e| e.to_string())?;
+        let mut request = self
+            .client
+            .post(&config.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(secret) = config.secret.as_deref().filter(|s| !s.is_empty()) {
+            let timestamp = chrono::Utc::now().timestamp();
+            let signature = sign_request(secret.as_bytes(), timestamp, &body);
+            request = request
+                .header(TIMESTAMP_HEADER, timestamp.to_string())
+                .header(SIGNATURE_HEADER, format!("sha256={signature}"));
+        }
+        let response = request.body(body).send().await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("{} returned {}", config.url, response.status()));
+        }
+        Ok(())
+    }
+
+    fn enqueue(&self, kind: WebhookEventKind, session_id: &str, data: impl Serialize) {
+        let wanted = self
+            .config
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .is_some_and(|config| config.events.contains(&kind));
+        if !wanted {
+            return;
+        }
+        let Ok(data) = serde_json::to_value(data) else {
+            return;
+        };
+        let event = WebhookEvent {
+            kind,
+            session_id: session_id.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            data,
+        };
+        push_event(
+            &mut self.pending.lock().unwrap_or_else(|e| e.into_inner()),
+            event,
+        );
+    }
+}
+
+/// Queue `event`, replacing a usage update of the same session that's still
+/// waiting, and drop the oldest past `MAX_PENDING`.
+fn push_event(pending: &mut VecDeque<WebhookEvent>, event: WebhookEvent) {
+    if event.kind == WebhookEventKind::Usage {
+        if let Some(queued) = pending
+            .iter_mut()
+            .find(|e| e.kind == WebhookEventKind::Usage && e.session_id == event.session_id)
+        {
+            *queued = event;
+            return;
+        }
+    }
+    pending.push_back(event);
+    while pending.len() > MAX_PENDING {
+        pending.pop_front();
+    }
+}
+
+/// HMAC-SHA256 of `body` keyed with `secret`, hex encoded.
+pub fn sign(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC takes keys of any size");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// The signature of a request sent at `timestamp`: `sign` over
+/// `"<timestamp>.<body>"`.
+pub fn sign_request(secret: &[u8], timestamp: i64, body: &[u8]) -> String {
+    let mut payload = format!("{timestamp}.").into_bytes();
+    payload.extend_from_slice(body);
+    sign(secret, &payload)
+}
+
+impl EventEmitter for WebhookEventEmitter {
+    fn emit_status_changed(&self, event: StatusChangedEvent) -> Result<(), DomainError> {
+        self.enqueue(WebhookEventKind::Status, &event.session_id, &event);
+        self.inner.emit_status_changed(event)
+    }
+
+    fn emit_agent_message(&self, event: MessageEvent) -> Result<(), DomainError> {
+        self.enqueue(WebhookEventKind::Message, &event.session_id, &event);
+        self.inner.emit_agent_message(event)
+    }
+
+    fn emit_usage_update(&self, event: UsageUpdateEvent) -> Result<(), DomainError> {
+        self.enqueue(WebhookEventKind::Usage, &event.session_id, &event);
+        self.inner.emit_usage_update(event)
+    }
+
+    fn emit_rate_limited(&self, event: RateLimitedEvent) -> Result<(), DomainError> {
+        self.enqueue(WebhookEventKind::RateLimited, &event.session_id, &event);
+        self.inner.emit_rate_limited(event)
+    }
+
+    fn emit_file_conflict(&self, event: FileConflictEvent) -> Result<(), DomainError> {
+        self.enqueue(WebhookEventKind::FileConflict, &event.session_id, &event);
+        self.inner.emit_file_conflict(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: WebhookEventKind, session_id: &str, tokens: u64) -> WebhookEvent {
+        WebhookEvent {
+            kind,
+            session_id: session_id.into(),
+            timestamp: String::new(),
+            data: serde_json::json!({ "output_tokens": tokens }),
+        }
+    }
+
+    #[test]
+    fn usage_updates_collapse_and_bodies_are_signed() {
+        let mut pending = VecDeque::new();
+        push_event(&mut pending, event(WebhookEventKind::Status, "a", 0));
+        push_event(&mut pending, event(WebhookEventKind::Usage, "a", 10));
+        push_event(&mut pending, event(WebhookEventKind::Usage, "b", 5));
+        push_event(&mut pending, event(WebhookEventKind::Usage, "a", 20));
+        let queued: Vec<_> = pending
+            .iter()
+            .map(|e| (e.session_id.as_str(), e.data["output_tokens"].as_u64()))
+            .collect();
+        assert_eq!(queued, [("a", Some(0)), ("a", Some(20)), ("b", Some(5))]);
+
+        // RFC 4231, test case 2
+        assert_eq!(
+            sign(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            sign_request(b"Jefe", 1700000000, b"{}"),
+            sign(b"Jefe", b"1700000000.{}")
+        );
+    }
+}
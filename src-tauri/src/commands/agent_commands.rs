@@ -1,8 +1,17 @@
-use crate::domain::models::{AgentConfig, AgentConfigUpdate, AgentRelationship, AgentSession};
+use crate::domain::models::{
+    AgentConfig, AgentConfigUpdate, AgentDiagnostic, AgentRelationship, AgentSession, AgentStats,
+    AgentTestRun, Skill, SlashCommand, SlashCommandUpdate, StepKind,
+};
 use crate::domain::ports::WorkflowRepository;
 use crate::domain::session_manager::SessionManager;
 use crate::error::AppError;
+use crate::services::agent_approvals::AgentApprovalStore;
 use crate::services::agent_manager::AgentManager;
+use crate::services::agent_versions::AgentVersion;
+use crate::services::{
+    agent_generator, agent_library, agent_relationships, agent_sandbox, agent_stats, skills,
+    slash_commands,
+};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::State;
@@ -11,16 +20,36 @@ use tokio::process::Command as TokioCommand;
 #[tauri::command]
 pub async fn start_agent(
     session_manager: State<'_, Arc<SessionManager>>,
+    approvals: State<'_, Arc<AgentApprovalStore>>,
     name: String,
     model: String,
     prompt: String,
+    profile: Option<String>,
 ) -> Result<String, AppError> {
+    require_approved(&approvals, &session_manager, &name).await?;
     session_manager
-        .start_agent(name, model, prompt)
+        .start_agent_for(name, model, prompt, None, profile.as_deref())
         .await
         .map_err(AppError::from)
 }
 
+/// Refuse a project agent that was never approved or changed since. The
+/// commands and the HTTP API both check here, so neither relies on the
+/// frontend having asked first.
+pub(crate) async fn require_approved(
+    approvals: &AgentApprovalStore,
+    session_manager: &SessionManager,
+    name: &str,
+) -> Result<(), AppError> {
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .unwrap_or_else(|| ".".to_string());
+    approvals
+        .check_agent(&project_dir, name)
+        .map_err(AppError::AgentNotApproved)
+}
+
 #[tauri::command]
 pub async fn stop_agent(
     session_manager: State<'_, Arc<SessionManager>>,
@@ -51,6 +80,17 @@ pub async fn list_sessions(
     Ok(session_manager.list_sessions().await)
 }
 
+/// Usage per agent (runs, success rate, duration, tokens, cost) over the
+/// sessions of this app run.
+#[tauri::command]
+pub async fn get_agent_stats(
+    session_manager: State<'_, Arc<SessionManager>>,
+) -> Result<Vec<AgentStats>, AppError> {
+    Ok(agent_stats::agent_stats(
+        &session_manager.list_sessions().await,
+    ))
+}
+
 #[tauri::command]
 pub async fn get_session(
     session_manager: State<'_, Arc<SessionManager>>,
@@ -149,6 +189,18 @@ pub async fn generate_text(prompt: String) -> Result<String, AppError> {
     Ok(result)
 }
 
+/// Run an agent once in a throwaway sandbox (empty temp dir, plan permission
+/// mode) and return its transcript. Nothing is added to session history.
+#[tauri::command]
+pub async fn test_agent(
+    file_path: String,
+    sample_prompt: String,
+) -> Result<AgentTestRun, AppError> {
+    agent_sandbox::test_agent(&file_path, &sample_prompt)
+        .await
+        .map_err(AppError::Process)
+}
+
 /// Check if Claude Code CLI is authenticated.
 #[tauri::command]
 pub async fn check_claude_auth() -> Result<bool, AppError> {
@@ -206,6 +258,17 @@ pub async fn list_agents(
         .map_err(|e| AppError::Process(e))
 }
 
+/// Frontmatter problems in the project's agent files.
+#[tauri::command]
+pub async fn validate_agents(
+    agent_manager: State<'_, Arc<AgentManager>>,
+) -> Result<Vec<AgentDiagnostic>, AppError> {
+    agent_manager
+        .validate_agents()
+        .await
+        .map_err(AppError::Process)
+}
+
 /// Get a single agent config by file path.
 #[tauri::command]
 pub async fn get_agent(
@@ -228,11 +291,57 @@ pub async fn create_agent_config(
     color: String,
 ) -> Result<AgentConfig, AppError> {
     agent_manager
-        .create_agent(name, model, description, color)
+        .create_agent(name, model, description, color, None, String::new())
         .await
         .map_err(|e| AppError::Process(e))
 }
 
+/// Ask Claude for a complete agent definition (description, model, tools,
+/// system prompt) from a name and goals, validate it, and write the file.
+#[tauri::command]
+pub async fn generate_agent(
+    agent_manager: State<'_, Arc<AgentManager>>,
+    name: String,
+    goals: String,
+    color: Option<String>,
+) -> Result<AgentConfig, AppError> {
+    let name = name.trim().to_string();
+    if name.is_empty() || goals.trim().is_empty() {
+        return Err(AppError::Process("Agent name and goals are required".into()));
+    }
+    let output = generate_text(agent_generator::build_prompt(&name, goals.trim())).await?;
+    let generated = agent_generator::parse_generated(&output).map_err(AppError::Process)?;
+    agent_manager
+        .create_agent(
+            name,
+            generated.model,
+            generated.description,
+            color.unwrap_or_else(|| "blue".to_string()),
+            generated.tools,
+            generated.system_prompt,
+        )
+        .await
+        .map_err(AppError::Process)
+}
+
+/// Built-in agent templates that can be installed into the project.
+#[tauri::command]
+pub async fn list_agent_templates() -> Result<Vec<AgentConfig>, AppError> {
+    Ok(agent_library::list_templates())
+}
+
+/// Install a built-in agent template into .claude/agents/.
+#[tauri::command]
+pub async fn install_agent_template(
+    agent_manager: State<'_, Arc<AgentManager>>,
+    name: String,
+) -> Result<AgentConfig, AppError> {
+    agent_manager
+        .install_template(&name)
+        .await
+        .map_err(AppError::Process)
+}
+
 /// Update an existing agent config.
 #[tauri::command]
 pub async fn update_agent_config(
@@ -246,6 +355,28 @@ pub async fn update_agent_config(
         .map_err(|e| AppError::Process(e))
 }
 
+/// Earlier saved versions of an agent file, newest first.
+#[tauri::command]
+pub async fn get_agent_versions(
+    agent_manager: State<'_, Arc<AgentManager>>,
+    file_path: String,
+) -> Result<Vec<AgentVersion>, AppError> {
+    Ok(agent_manager.versions(&file_path))
+}
+
+/// Overwrite an agent file with one of its earlier versions.
+#[tauri::command]
+pub async fn restore_agent_version(
+    agent_manager: State<'_, Arc<AgentManager>>,
+    file_path: String,
+    version_id: String,
+) -> Result<AgentConfig, AppError> {
+    agent_manager
+        .restore_version(&file_path, &version_id)
+        .await
+        .map_err(AppError::Process)
+}
+
 /// Delete an agent config file.
 #[tauri::command]
 pub async fn delete_agent_config(
@@ -258,10 +389,80 @@ pub async fn delete_agent_config(
         .map_err(|e| AppError::Process(e))
 }
 
-/// Get agent relationships derived from workflow edges.
+// --- Slash commands (.claude/commands) ---
+
+async fn require_project_dir(agent_manager: &AgentManager) -> Result<String, AppError> {
+    agent_manager
+        .get_project_dir()
+        .await
+        .ok_or_else(|| AppError::Process("No project directory set".into()))
+}
+
+#[tauri::command]
+pub async fn list_slash_commands(
+    agent_manager: State<'_, Arc<AgentManager>>,
+) -> Result<Vec<SlashCommand>, AppError> {
+    let project_dir = require_project_dir(&agent_manager).await?;
+    slash_commands::list_commands(&project_dir).map_err(AppError::Process)
+}
+
+#[tauri::command]
+pub async fn get_slash_command(
+    agent_manager: State<'_, Arc<AgentManager>>,
+    file_path: String,
+) -> Result<SlashCommand, AppError> {
+    let project_dir = require_project_dir(&agent_manager).await?;
+    slash_commands::get_command(&project_dir, &file_path).map_err(AppError::Process)
+}
+
+/// Create `.claude/commands/<name>.md` (`dir:name` for a subfolder).
+#[tauri::command]
+pub async fn create_slash_command(
+    agent_manager: State<'_, Arc<AgentManager>>,
+    name: String,
+    description: Option<String>,
+) -> Result<SlashCommand, AppError> {
+    let project_dir = require_project_dir(&agent_manager).await?;
+    slash_commands::create_command(&project_dir, &name, description).map_err(AppError::Process)
+}
+
+#[tauri::command]
+pub async fn update_slash_command(
+    agent_manager: State<'_, Arc<AgentManager>>,
+    file_path: String,
+    update: SlashCommandUpdate,
+) -> Result<SlashCommand, AppError> {
+    let project_dir = require_project_dir(&agent_manager).await?;
+    slash_commands::update_command(&project_dir, &file_path, update).map_err(AppError::Process)
+}
+
+#[tauri::command]
+pub async fn delete_slash_command(
+    agent_manager: State<'_, Arc<AgentManager>>,
+    file_path: String,
+) -> Result<(), AppError> {
+    let project_dir = require_project_dir(&agent_manager).await?;
+    slash_commands::delete_command(&project_dir, &file_path).map_err(AppError::Process)
+}
+
+// --- Skills (.claude/skills) ---
+
+/// Skill packages in the project and the agents that mention each one.
+#[tauri::command]
+pub async fn list_skills(
+    agent_manager: State<'_, Arc<AgentManager>>,
+) -> Result<Vec<Skill>, AppError> {
+    let project_dir = require_project_dir(&agent_manager).await?;
+    let agents = agent_manager.list_agents().await.unwrap_or_default();
+    Ok(skills::list_skills(&project_dir, &agents))
+}
+
+/// Get agent relationships derived from workflow edges, merged with the
+/// pairings agents declare in `pairs_with:`.
 #[tauri::command]
 pub async fn get_agent_relationships(
     workflow_repo: State<'_, Arc<dyn WorkflowRepository>>,
+    agent_manager: State<'_, Arc<AgentManager>>,
 ) -> Result<Vec<AgentRelationship>, AppError> {
     let workflows = workflow_repo
         .list_workflows()
@@ -281,9 +482,10 @@ pub async fn get_agent_relationships(
             .await
             .map_err(AppError::from)?;
 
-        // Build step_id -> agent_name lookup
+        // Build step_id -> agent_name lookup (gate steps have no agent)
         let step_agents: HashMap<String, String> = steps
             .iter()
+            .filter(|s| s.kind == StepKind::Agent)
             .map(|s| (s.id.clone(), s.agent_name.clone()))
             .collect();
 
@@ -310,9 +512,12 @@ pub async fn get_agent_relationships(
                 target_agent,
                 workflow_names,
                 edge_count,
+                declared: false,
             }
         })
         .collect();
 
-    Ok(relationships)
+    // Without a project there are no agent files to read pairings from
+    let agents = agent_manager.list_agents().await.unwrap_or_default();
+    Ok(agent_relationships::merge_declared(relationships, &agents))
 }
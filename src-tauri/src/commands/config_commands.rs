@@ -1,9 +1,29 @@
+use crate::adapters::webhook_event_emitter::WebhookEventEmitter;
+use crate::commands::http_api::HttpApi;
+use crate::domain::models::{
+    AgentApproval, HooksSettings, McpHealth, McpScope, McpServer, MemoryFile, MemoryScope,
+    ModelCatalog, RedactionConfig, RedactionPattern, SettingsScope,
+};
 use crate::domain::session_manager::SessionManager;
+use crate::domain::stream_parser::{self, Redactor};
 use crate::error::AppError;
+use crate::services::agent_approvals::{self, AgentApprovalStore};
+use crate::services::agent_manager::AgentManager;
 use crate::services::agent_watcher;
-use crate::services::config_store::{AppConfig, ConfigStore};
+use crate::services::claude_hooks;
+use crate::services::claude_memory;
+use crate::services::config_store::{AppConfig, ConfigStore, KnownProject, ProjectSettings};
+use crate::services::log_mirror::LogMirror;
+use crate::services::mcp_registry;
+use crate::services::model_catalog;
+use crate::services::quota_service::{self, QuotaState};
+use crate::services::settings_bundle::{SettingsBundle, SettingsImportReport};
+use crate::services::spec_manager::SpecManager;
+use crate::services::text_diff;
+use crate::services::workflow_engine::WorkflowEngine;
+use crate::services::workflow_templates::WorkflowTemplateStore;
 use std::sync::Arc;
-use tauri::State;
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::RwLock;
 
 /// Managed state wrapping the current config.
@@ -20,32 +40,352 @@ pub async fn get_config(
 pub async fn save_config(
     config_state: State<'_, ConfigState>,
     config_store: State<'_, Arc<ConfigStore>>,
-    config: AppConfig,
+    session_manager: State<'_, Arc<SessionManager>>,
+    workflow_engine: State<'_, Arc<WorkflowEngine>>,
+    log_mirror: State<'_, Arc<LogMirror>>,
+    quota_state: State<'_, Arc<QuotaState>>,
+    app: AppHandle,
+    mut config: AppConfig,
 ) -> Result<(), AppError> {
+    // The frontend's copy doesn't carry the project's own file
+    config.project_file = config_state.read().await.project_file.clone();
+    // Reject patterns that don't compile before anything is written
+    let redactor = Redactor::new(&config.redaction()).map_err(AppError::Process)?;
     config_store.save(&config)?;
+    apply_settings(&config, &session_manager, &workflow_engine, &log_mirror);
+    app.state::<Arc<WebhookEventEmitter>>()
+        .set_config(config.integrations.webhook.clone());
+    stream_parser::set_redactor(redactor);
+    apply_http_api(&app, &config).await;
     *config_state.write().await = config;
+    // Apply changed budgets now rather than at the next poll
+    quota_service::poll_once(&app, &quota_state).await;
     Ok(())
 }
 
+/// Open a project, adding it to the known projects if it's new, and point
+/// sessions, specs and agents at it.
 #[tauri::command]
-pub async fn set_project_path(
+pub async fn open_project(
     config_state: State<'_, ConfigState>,
     config_store: State<'_, Arc<ConfigStore>>,
     session_manager: State<'_, Arc<SessionManager>>,
+    spec_manager: State<'_, Arc<SpecManager>>,
+    agent_manager: State<'_, Arc<AgentManager>>,
+    workflow_engine: State<'_, Arc<WorkflowEngine>>,
+    log_mirror: State<'_, Arc<LogMirror>>,
     path: String,
 ) -> Result<(), AppError> {
-    // Update session manager's working directory
     session_manager.set_project_dir(path.clone()).await;
+    spec_manager.set_project_dir(path.clone()).await;
+    agent_manager.set_project_dir(path.clone()).await;
 
     // Save to persistent config
     let mut config = config_state.read().await.clone();
-    config.project_path = Some(path);
+    config.open_project(&path);
     config_store.save(&config)?;
+    config.reload_project_file();
+    apply_settings(&config, &session_manager, &workflow_engine, &log_mirror);
+    if let Err(e) = stream_parser::set_redaction(&config.redaction()) {
+        eprintln!("Redaction patterns not applied: {e}");
+    }
     *config_state.write().await = config;
 
     Ok(())
 }
 
+/// Point the services at `config`'s settings for the open project.
+fn apply_settings(
+    config: &AppConfig,
+    session_manager: &SessionManager,
+    workflow_engine: &WorkflowEngine,
+    log_mirror: &LogMirror,
+) {
+    let project = config.project_settings();
+    log_mirror.set_enabled(project.mirror_logs);
+    session_manager.set_auto_branch(project.auto_branch);
+    session_manager.set_dirty_tree_policy(project.dirty_tree);
+    session_manager.set_pricing(config.pricing.clone());
+    session_manager.set_profiles(config.profiles.clone(), project.profile);
+    session_manager.set_extra_env(project.extra_env);
+    workflow_engine.set_hold_on_conflict(project.hold_on_conflict);
+}
+
+/// Start, move or stop the HTTP API for `config`; a port that can't be
+/// bound is reported and leaves the API as it was.
+async fn apply_http_api(app: &AppHandle, config: &AppConfig) {
+    let api = app.state::<Arc<HttpApi>>();
+    if let Err(e) = api.apply(app, &config.http_api).await {
+        eprintln!("HTTP API not started: {e}");
+    }
+}
+
+/// Re-read config.json after an edit outside the app, apply it and emit
+/// `config:changed` with the new config. The open project stays open even
+/// if the file names another (e.g. saved by another instance). Invalid
+/// edits are reported and ignored, and the app's own saves change nothing.
+pub async fn reload_config(app: &AppHandle) {
+    let config_store = app.state::<Arc<ConfigStore>>();
+    let config_state = app.state::<ConfigState>();
+    let mut config = match config_store.try_load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Config not reloaded: {e}");
+            return;
+        }
+    };
+    let current = config_state.read().await.clone();
+    config.project_path = current.project_path.clone();
+    if serde_json::to_value(&config).ok() == serde_json::to_value(&current).ok() {
+        return;
+    }
+    config.reload_project_file();
+
+    apply_settings(
+        &config,
+        &app.state::<Arc<SessionManager>>(),
+        &app.state::<Arc<WorkflowEngine>>(),
+        &app.state::<Arc<LogMirror>>(),
+    );
+    app.state::<Arc<WebhookEventEmitter>>()
+        .set_config(config.integrations.webhook.clone());
+    if let Err(e) = stream_parser::set_redaction(&config.redaction()) {
+        eprintln!("Redaction patterns not applied: {e}");
+    }
+    apply_http_api(app, &config).await;
+    *config_state.write().await = config.clone();
+    let _ = app.emit("config:changed", &config);
+    quota_service::poll_once(app, &app.state::<Arc<QuotaState>>()).await;
+}
+
+/// Write the app config, agent approvals and workflow templates to one
+/// archive at `path`. It holds credentials, so it's only readable by the
+/// user.
+#[tauri::command]
+pub async fn export_settings(
+    config_state: State<'_, ConfigState>,
+    approvals: State<'_, Arc<AgentApprovalStore>>,
+    templates: State<'_, Arc<WorkflowTemplateStore>>,
+    path: String,
+) -> Result<(), AppError> {
+    let bundle = SettingsBundle::new(
+        config_state.read().await.clone(),
+        approvals.export().map_err(AppError::Process)?,
+        templates.list().map_err(AppError::Process)?,
+    );
+    let archive = bundle.encode().map_err(AppError::Process)?;
+    agent_approvals::write_private(std::path::Path::new(&path), &archive)
+        .map_err(AppError::Process)
+}
+
+/// Restore an archive from `export_settings`: its config replaces this
+/// one's (the open project stays open), and its approvals and templates are
+/// added to the ones here.
+#[tauri::command]
+pub async fn import_settings(
+    app: AppHandle,
+    config_state: State<'_, ConfigState>,
+    config_store: State<'_, Arc<ConfigStore>>,
+    approvals: State<'_, Arc<AgentApprovalStore>>,
+    templates: State<'_, Arc<WorkflowTemplateStore>>,
+    path: String,
+) -> Result<SettingsImportReport, AppError> {
+    let mut bundle = SettingsBundle::decode(&std::fs::read(&path)?).map_err(AppError::Process)?;
+    bundle.config.project_path = config_state.read().await.project_path.clone();
+    // Reject patterns that don't compile before anything is written
+    Redactor::new(&bundle.config.redaction()).map_err(AppError::Process)?;
+
+    let report = SettingsImportReport {
+        agent_approvals: approvals
+            .import(&bundle.agent_approvals)
+            .map_err(AppError::Process)?,
+        workflow_templates: templates
+            .import(&bundle.workflow_templates)
+            .map_err(AppError::Process)?,
+    };
+    config_store.save(&bundle.config)?;
+    reload_config(&app).await;
+    Ok(report)
+}
+
+/// Variables the open project's agents receive beyond the default
+/// allowlist, and those its config file asks for that aren't allowed yet.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProjectEnv {
+    pub allowed: Vec<String>,
+    pub requested: Vec<String>,
+    /// Asked for by the file but on `ENV_DENYLIST`, so never passed.
+    pub refused: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn get_project_env(config_state: State<'_, ConfigState>) -> Result<ProjectEnv, AppError> {
+    let config = config_state.read().await;
+    Ok(ProjectEnv {
+        allowed: config.project_settings().extra_env,
+        requested: config.pending_project_env(),
+        refused: config.refused_project_env(),
+    })
+}
+
+/// Pass `names` from the app's environment to the open project's agents.
+/// Every agent in the project can read their values, so the caller must
+/// confirm the user acknowledged that.
+#[tauri::command]
+pub async fn allow_project_env(
+    config_state: State<'_, ConfigState>,
+    config_store: State<'_, Arc<ConfigStore>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    workflow_engine: State<'_, Arc<WorkflowEngine>>,
+    log_mirror: State<'_, Arc<LogMirror>>,
+    names: Vec<String>,
+    acknowledged: bool,
+) -> Result<ProjectEnv, AppError> {
+    if !acknowledged {
+        return Err(AppError::Process(
+            "Passing variables to agents must be acknowledged first".into(),
+        ));
+    }
+    update_project_env(&config_state, &config_store, |config| {
+        config.allow_project_env(&names)
+    })
+    .await?;
+    apply_settings(
+        &*config_state.read().await,
+        &session_manager,
+        &workflow_engine,
+        &log_mirror,
+    );
+    get_project_env(config_state).await
+}
+
+#[tauri::command]
+pub async fn revoke_project_env(
+    config_state: State<'_, ConfigState>,
+    config_store: State<'_, Arc<ConfigStore>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    workflow_engine: State<'_, Arc<WorkflowEngine>>,
+    log_mirror: State<'_, Arc<LogMirror>>,
+    names: Vec<String>,
+) -> Result<ProjectEnv, AppError> {
+    update_project_env(&config_state, &config_store, |config| {
+        config.revoke_project_env(&names)
+    })
+    .await?;
+    apply_settings(
+        &*config_state.read().await,
+        &session_manager,
+        &workflow_engine,
+        &log_mirror,
+    );
+    get_project_env(config_state).await
+}
+
+async fn update_project_env(
+    config_state: &ConfigState,
+    config_store: &ConfigStore,
+    update: impl FnOnce(&mut AppConfig) -> Result<(), AppError>,
+) -> Result<(), AppError> {
+    let mut config = config_state.write().await;
+    let mut updated = config.clone();
+    update(&mut updated)?;
+    config_store.save(&updated)?;
+    *config = updated;
+    Ok(())
+}
+
+/// The open project's settings, with its config file applied.
+#[tauri::command]
+pub async fn get_project_settings(
+    config_state: State<'_, ConfigState>,
+) -> Result<ProjectSettings, AppError> {
+    Ok(config_state.read().await.project_settings())
+}
+
+/// Models to offer in model pickers: a curated list plus any model the
+/// project's agents or past sessions use, and the default to preselect.
+#[tauri::command]
+pub async fn list_models(
+    config_state: State<'_, ConfigState>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    agent_manager: State<'_, Arc<AgentManager>>,
+) -> Result<ModelCatalog, AppError> {
+    let default_model = config_state.read().await.default_model();
+    let agents = agent_manager.list_agents().await.unwrap_or_default();
+    let sessions = session_manager.list_sessions().await;
+    let used = agents
+        .iter()
+        .map(|a| a.model.as_str())
+        .chain(sessions.iter().map(|s| s.model.as_str()));
+    Ok(model_catalog::model_catalog(used, default_model.as_deref()))
+}
+
+/// Known projects, most recently opened first.
+#[tauri::command]
+pub async fn list_projects(
+    config_state: State<'_, ConfigState>,
+) -> Result<Vec<KnownProject>, AppError> {
+    Ok(config_state.read().await.known_projects())
+}
+
+/// Add a project to the list without opening it.
+#[tauri::command]
+pub async fn add_project(
+    config_state: State<'_, ConfigState>,
+    config_store: State<'_, Arc<ConfigStore>>,
+    path: String,
+    name: Option<String>,
+) -> Result<(), AppError> {
+    let mut config = config_state.write().await;
+    let mut updated = config.clone();
+    updated.add_project(&path, name.filter(|n| !n.trim().is_empty()));
+    config_store.save(&updated)?;
+    *config = updated;
+    Ok(())
+}
+
+/// Forget a project and its settings.
+#[tauri::command]
+pub async fn remove_project(
+    config_state: State<'_, ConfigState>,
+    config_store: State<'_, Arc<ConfigStore>>,
+    path: String,
+) -> Result<(), AppError> {
+    let mut config = config_state.write().await;
+    let mut updated = config.clone();
+    updated.remove_project(&path)?;
+    config_store.save(&updated)?;
+    *config = updated;
+    Ok(())
+}
+
+/// The built-in secret patterns, for toggling in settings.
+#[tauri::command]
+pub async fn list_redaction_builtins() -> Result<Vec<RedactionPattern>, AppError> {
+    Ok(stream_parser::SECRET_PATTERNS
+        .iter()
+        .map(|(name, pattern)| RedactionPattern {
+            name: name.to_string(),
+            pattern: pattern.to_string(),
+        })
+        .collect())
+}
+
+/// What `sample` becomes under `redaction`, or under the patterns in
+/// effect when none is given.
+#[tauri::command]
+pub async fn test_redaction(
+    sample: String,
+    redaction: Option<RedactionConfig>,
+) -> Result<String, AppError> {
+    match redaction {
+        Some(config) => Ok(Redactor::new(&config)
+            .map_err(AppError::Process)?
+            .redact(&sample)),
+        None => Ok(stream_parser::redact_secrets(&sample)),
+    }
+}
+
 #[tauri::command]
 pub async fn get_project_path(
     config_state: State<'_, ConfigState>,
@@ -56,10 +396,9 @@ pub async fn get_project_path(
 /// Check which agents need approval (P0 Security #4).
 #[tauri::command]
 pub async fn check_agent_approval(
-    config_state: State<'_, ConfigState>,
+    approvals: State<'_, Arc<AgentApprovalStore>>,
     session_manager: State<'_, Arc<SessionManager>>,
 ) -> Result<Vec<agent_watcher::UnapprovedAgent>, AppError> {
-    let config = config_state.read().await;
     let project_dir = session_manager
         .get_project_dir()
         .await
@@ -74,57 +413,227 @@ pub async fn check_agent_approval(
     let mut unapproved = Vec::new();
 
     for path in md_files {
-        let rel_path = path
-            .strip_prefix(&project_dir)
-            .unwrap_or(&path)
-            .to_string_lossy()
-            .to_string();
-
-        let current_hash = match agent_watcher::hash_file(&path) {
-            Some(h) => h,
-            None => continue,
+        let Some(rel_path) = agent_approvals::relative_key(&project_dir, &path) else {
+            continue;
+        };
+
+        let Ok(raw) = std::fs::read(&path) else {
+            continue;
         };
+        let current_hash = agent_watcher::hash_bytes(&raw);
 
-        let approved_hash = config.approved_agent_hashes.get(&rel_path);
-
-        if approved_hash != Some(&current_hash) {
-            let content = std::fs::read_to_string(&path).unwrap_or_default();
-            let (name, model, description) = parse_basic_frontmatter(&content);
-
-            unapproved.push(agent_watcher::UnapprovedAgent {
-                file_path: rel_path,
-                name: name.unwrap_or_else(|| {
-                    path.file_stem()
-                        .unwrap_or_default()
-                        .to_string_lossy()
-                        .to_string()
-                }),
-                model: model.unwrap_or_else(|| "sonnet".to_string()),
-                description: description.unwrap_or_default(),
-                hash: current_hash,
-            });
+        let approved_hash = approvals.approved_hash(&project_dir, &rel_path);
+
+        if approved_hash.as_ref() == Some(&current_hash) {
+            // Backfill the copy for approvals made before copies were kept
+            if approvals.load_copy(&current_hash).is_none() {
+                let _ = approvals.save_copy(&current_hash, &raw);
+            }
+            continue;
         }
+
+        let content = String::from_utf8_lossy(&raw).into_owned();
+        let (name, model, description) = parse_basic_frontmatter(&content);
+        let diff = approved_hash
+            .as_deref()
+            .and_then(|hash| approvals.load_copy(hash))
+            .map(|previous| text_diff::diff_lines(&previous, &content, 3));
+
+        unapproved.push(agent_watcher::UnapprovedAgent {
+            file_path: rel_path,
+            name: name.unwrap_or_else(|| {
+                path.file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string()
+            }),
+            model: model.unwrap_or_else(|| "sonnet".to_string()),
+            description: description.unwrap_or_default(),
+            hash: current_hash,
+            previously_approved: approved_hash.is_some(),
+            diff,
+        });
     }
 
     Ok(unapproved)
 }
 
-/// Approve a list of agent files by storing their hashes.
+/// The CLAUDE.md memory files for the current project and user.
 #[tauri::command]
-pub async fn approve_agents(
+pub async fn list_memory_files(
+    session_manager: State<'_, Arc<SessionManager>>,
+) -> Result<Vec<MemoryFile>, AppError> {
+    let project_dir = session_manager.get_project_dir().await;
+    Ok(claude_memory::list_memory_files(project_dir.as_deref()))
+}
+
+#[tauri::command]
+pub async fn get_memory_file(
+    session_manager: State<'_, Arc<SessionManager>>,
+    scope: MemoryScope,
+) -> Result<MemoryFile, AppError> {
+    let project_dir = session_manager.get_project_dir().await;
+    claude_memory::read_memory(scope, project_dir.as_deref()).map_err(AppError::Process)
+}
+
+/// Write a memory file, creating it if it doesn't exist.
+#[tauri::command]
+pub async fn save_memory_file(
+    session_manager: State<'_, Arc<SessionManager>>,
+    scope: MemoryScope,
+    content: String,
+) -> Result<MemoryFile, AppError> {
+    let project_dir = session_manager.get_project_dir().await;
+    claude_memory::write_memory(scope, project_dir.as_deref(), &content).map_err(AppError::Process)
+}
+
+/// The `hooks` section of each Claude Code settings file, with schema errors.
+#[tauri::command]
+pub async fn list_hooks(
+    session_manager: State<'_, Arc<SessionManager>>,
+) -> Result<Vec<HooksSettings>, AppError> {
+    let project_dir = session_manager.get_project_dir().await;
+    claude_hooks::list_hooks(project_dir.as_deref()).map_err(AppError::Process)
+}
+
+/// Replace the `hooks` section of one settings file. Rejected if it doesn't
+/// match the schema.
+#[tauri::command]
+pub async fn save_hooks(
+    session_manager: State<'_, Arc<SessionManager>>,
+    scope: SettingsScope,
+    hooks: serde_json::Value,
+) -> Result<HooksSettings, AppError> {
+    let project_dir = session_manager.get_project_dir().await;
+    claude_hooks::write_hooks(scope, project_dir.as_deref(), hooks).map_err(AppError::Process)
+}
+
+/// MCP servers from the project's `.mcp.json` and the user's `~/.claude.json`.
+#[tauri::command]
+pub async fn list_mcp_servers(
+    session_manager: State<'_, Arc<SessionManager>>,
+) -> Result<Vec<McpServer>, AppError> {
+    let project_dir = session_manager.get_project_dir().await;
+    mcp_registry::list_servers(project_dir.as_deref()).map_err(AppError::Process)
+}
+
+/// A project stdio server added here is approved to launch, since the user
+/// typed its command.
+#[tauri::command]
+pub async fn add_mcp_server(
     config_state: State<'_, ConfigState>,
     config_store: State<'_, Arc<ConfigStore>>,
-    agents: Vec<(String, String)>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    server: McpServer,
 ) -> Result<(), AppError> {
-    let mut config = config_state.read().await.clone();
-    for (path, hash) in agents {
-        config.approved_agent_hashes.insert(path, hash);
+    let project_dir = session_manager.get_project_dir().await;
+    mcp_registry::add_server(&server, project_dir.as_deref()).map_err(AppError::Process)?;
+    if server.scope == McpScope::Project && server.transport == "stdio" {
+        let hash = mcp_registry::launch_hash(&server);
+        update_project_env(&config_state, &config_store, |config| {
+            config.approve_mcp_server(&server.name, hash)
+        })
+        .await?;
     }
-    config_store.save(&config)?;
-    *config_state.write().await = config;
     Ok(())
 }
 
+/// Let the health check launch the project's stdio server `name` as
+/// `.mcp.json` configures it now. Changing its command, args or env later
+/// needs a new approval.
+#[tauri::command]
+pub async fn approve_mcp_server(
+    config_state: State<'_, ConfigState>,
+    config_store: State<'_, Arc<ConfigStore>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    name: String,
+) -> Result<(), AppError> {
+    let project_dir = require_project_dir(&session_manager).await?;
+    let server = mcp_registry::list_servers(Some(&project_dir))
+        .map_err(AppError::Process)?
+        .into_iter()
+        .find(|s| s.scope == McpScope::Project && s.name == name)
+        .ok_or_else(|| AppError::Process(format!("MCP server not found: {name}")))?;
+    let hash = mcp_registry::launch_hash(&server);
+    update_project_env(&config_state, &config_store, |config| {
+        config.approve_mcp_server(&name, hash)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn remove_mcp_server(
+    session_manager: State<'_, Arc<SessionManager>>,
+    name: String,
+    scope: McpScope,
+) -> Result<(), AppError> {
+    let project_dir = session_manager.get_project_dir().await;
+    mcp_registry::remove_server(&name, scope, project_dir.as_deref()).map_err(AppError::Process)
+}
+
+/// Start (or reach) every configured MCP server and report whether it
+/// answers the `initialize` handshake. Project servers only see allowed
+/// variables, and stdio ones are only launched once approved.
+#[tauri::command]
+pub async fn check_mcp_servers(
+    config_state: State<'_, ConfigState>,
+    session_manager: State<'_, Arc<SessionManager>>,
+) -> Result<Vec<McpHealth>, AppError> {
+    let project_dir = session_manager.get_project_dir().await;
+    let settings = config_state.read().await.project_settings();
+    let policy = mcp_registry::CheckPolicy {
+        extra_env: settings.extra_env,
+        approved_servers: settings.approved_mcp_servers,
+    };
+    mcp_registry::check_servers(project_dir.as_deref(), policy)
+        .await
+        .map_err(AppError::Process)
+}
+
+/// Approve a list of `(file_path, hash)` pairs for the current project.
+#[tauri::command]
+pub async fn approve_agents(
+    approvals: State<'_, Arc<AgentApprovalStore>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    agents: Vec<(String, String)>,
+) -> Result<(), AppError> {
+    let project_dir = require_project_dir(&session_manager).await?;
+    approvals
+        .approve(&project_dir, &agents)
+        .map_err(AppError::Process)
+}
+
+/// Revoke approvals so the files show up in the approval dialog again.
+/// Returns how many were revoked.
+#[tauri::command]
+pub async fn revoke_agent_approvals(
+    approvals: State<'_, Arc<AgentApprovalStore>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    file_paths: Vec<String>,
+) -> Result<usize, AppError> {
+    let project_dir = require_project_dir(&session_manager).await?;
+    approvals
+        .revoke(&project_dir, &file_paths)
+        .map_err(AppError::Process)
+}
+
+/// Every agent approval recorded for the current project.
+#[tauri::command]
+pub async fn list_agent_approvals(
+    approvals: State<'_, Arc<AgentApprovalStore>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+) -> Result<Vec<AgentApproval>, AppError> {
+    let project_dir = require_project_dir(&session_manager).await?;
+    Ok(approvals.list(&project_dir))
+}
+
+async fn require_project_dir(session_manager: &SessionManager) -> Result<String, AppError> {
+    session_manager
+        .get_project_dir()
+        .await
+        .ok_or_else(|| AppError::Process("No project directory set".into()))
+}
+
 /// Quick frontmatter parser for agent display info.
 fn parse_basic_frontmatter(content: &str) -> (Option<String>, Option<String>, Option<String>) {
     let content = content.trim();
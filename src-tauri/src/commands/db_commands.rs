@@ -0,0 +1,33 @@
+use crate::adapters::sqlite_maintenance::SqliteMaintenance;
+use crate::domain::models::{DbInfo, DbIntegrityReport, DbVacuumResult};
+use crate::domain::ports::LogRepository;
+use crate::error::AppError;
+use std::sync::Arc;
+use tauri::State;
+
+/// File size, free space and row counts per table.
+#[tauri::command]
+pub async fn db_info(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    maintenance: State<'_, Arc<SqliteMaintenance>>,
+) -> Result<DbInfo, AppError> {
+    log_repo.flush().await;
+    maintenance.info().await.map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn db_vacuum(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    maintenance: State<'_, Arc<SqliteMaintenance>>,
+) -> Result<DbVacuumResult, AppError> {
+    // Buffered entries would otherwise be written mid-vacuum and hit a busy lock
+    log_repo.flush().await;
+    maintenance.vacuum().await.map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn db_integrity_check(
+    maintenance: State<'_, Arc<SqliteMaintenance>>,
+) -> Result<DbIntegrityReport, AppError> {
+    maintenance.integrity_check().await.map_err(AppError::from)
+}
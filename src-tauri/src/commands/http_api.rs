@@ -0,0 +1,649 @@
+//! Local HTTP API, so scripts and editors can drive the app without the
+//! frontend. Serves JSON on 127.0.0.1 and requires
+//! `Authorization: Bearer <token>` on every request. Each route calls the
+//! same command the frontend would:
+//!
+//! - `GET /sessions`, `GET /sessions/{id}`
+//! - `GET /sessions/{id}/logs?after_id=&limit=`
+//! - `POST /sessions/{id}/stop`
+//! - `POST /agents/start` with `{name, prompt, model?, profile?}`
+//! - `POST /specs/run` with `{spec_path, agent_name, model?, force?}`
+//! - `POST /workflows/{id}/start`
+//!
+//! Starting a project agent that wasn't approved, or changed since, is
+//! refused with 403, the same check the commands make.
+
+use crate::commands::config_commands::ConfigState;
+use crate::commands::{agent_commands, log_commands, spec_commands, workflow_commands};
+use crate::error::AppError;
+use crate::services::config_store::{ConfigStore, HttpApiConfig};
+use crate::services::model_catalog::FALLBACK_MODEL;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager, State};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+pub const DEFAULT_PORT: u16 = 7777;
+
+/// Longest request head (request line and headers) accepted.
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// How long a client gets to send its whole request.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Pause after a failed accept before trying again.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Whether the API is listening, as returned by its commands.
+#[derive(Debug, Clone, Serialize)]
+pub struct HttpApiStatus {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: Option<String>,
+    /// The port it's listening on; None while stopped.
+    pub listening: Option<u16>,
+}
+
+struct Listening {
+    port: u16,
+    task: JoinHandle<()>,
+}
+
+/// The running server, if any. Managed state.
+#[derive(Default)]
+pub struct HttpApi {
+    listening: Mutex<Option<Listening>>,
+    /// Checked on every request, so a new token applies without a rebind.
+    token: Arc<Mutex<String>>,
+}
+
+impl HttpApi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start, move or stop the server to match `config`. It only runs when
+    /// enabled with a token. A new port is bound before the old listener is
+    /// dropped, so when that fails the API keeps serving where it was.
+    pub async fn apply(&self, app: &AppHandle, config: &HttpApiConfig) -> Result<(), AppError> {
+        let port = config.port.unwrap_or(DEFAULT_PORT);
+        let token = config
+            .token
+            .clone()
+            .filter(|t| !t.is_empty() && config.enabled);
+        let Some(token) = token else {
+            self.stop();
+            return Ok(());
+        };
+        let current_port = self
+            .listening
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map(|l| l.port);
+        if current_port != Some(port) {
+            self.listen(app, port).await?;
+        }
+        *self.token.lock().unwrap_or_else(|e| e.into_inner()) = token;
+        Ok(())
+    }
+
+    async fn listen(&self, app: &AppHandle, port: u16) -> Result<(), AppError> {
+        let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+        let task = tauri::async_runtime::spawn(accept_loop(
+            listener,
+            app.clone(),
+            Arc::clone(&self.token),
+        ));
+        let previous = self
+            .listening
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .replace(Listening { port, task });
+        if let Some(previous) = previous {
+            previous.task.abort();
+        }
+        Ok(())
+    }
+
+    fn stop(&self) {
+        if let Some(current) = self
+            .listening
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+        {
+            current.task.abort();
+        }
+    }
+
+    pub fn status(&self, config: &HttpApiConfig) -> HttpApiStatus {
+        HttpApiStatus {
+            enabled: config.enabled,
+            port: config.port.unwrap_or(DEFAULT_PORT),
+            token: config.token.clone(),
+            listening: self
+                .listening
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .as_ref()
+                .map(|l| l.port),
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_http_api(
+    config_state: State<'_, ConfigState>,
+    http_api: State<'_, Arc<HttpApi>>,
+) -> Result<HttpApiStatus, AppError> {
+    Ok(http_api.status(&config_state.read().await.http_api))
+}
+
+/// Turn the API on or off. A token is created the first time it's enabled.
+/// Fails without saving, and leaves the API as it was, when the port can't
+/// be bound.
+#[tauri::command]
+pub async fn set_http_api(
+    app: AppHandle,
+    config_state: State<'_, ConfigState>,
+    config_store: State<'_, Arc<ConfigStore>>,
+    http_api: State<'_, Arc<HttpApi>>,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<HttpApiStatus, AppError> {
+    update_http_api(&app, &config_state, &config_store, &http_api, |api| {
+        api.enabled = enabled;
+        api.port = port;
+        if enabled && api.token.is_none() {
+            api.token = Some(new_token());
+        }
+    })
+    .await
+}
+
+/// Replace the token; clients using the old one are refused from now on.
+#[tauri::command]
+pub async fn regenerate_http_api_token(
+    app: AppHandle,
+    config_state: State<'_, ConfigState>,
+    config_store: State<'_, Arc<ConfigStore>>,
+    http_api: State<'_, Arc<HttpApi>>,
+) -> Result<HttpApiStatus, AppError> {
+    update_http_api(&app, &config_state, &config_store, &http_api, |api| {
+        api.token = Some(new_token());
+    })
+    .await
+}
+
+async fn update_http_api(
+    app: &AppHandle,
+    config_state: &ConfigState,
+    config_store: &ConfigStore,
+    http_api: &HttpApi,
+    update: impl FnOnce(&mut HttpApiConfig),
+) -> Result<HttpApiStatus, AppError> {
+    let mut config = config_state.write().await;
+    let mut updated = config.clone();
+    update(&mut updated.http_api);
+    http_api.apply(app, &updated.http_api).await?;
+    config_store.save(&updated)?;
+    *config = updated;
+    Ok(http_api.status(&config.http_api))
+}
+
+fn new_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+async fn accept_loop(listener: TcpListener, app: AppHandle, token: Arc<Mutex<String>>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let app = app.clone();
+                let token = token.lock().unwrap_or_else(|e| e.into_inner()).clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = serve(stream, &app, &token).await {
+                        eprintln!("HTTP API request failed: {e}");
+                    }
+                });
+            }
+            // e.g. out of file descriptors; retrying at once would spin
+            Err(e) => {
+                eprintln!("HTTP API accept failed: {e}");
+                tokio::time::sleep(ACCEPT_BACKOFF).await;
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Requests
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    authorization: Option<String>,
+    content_length: usize,
+}
+
+#[derive(Debug, PartialEq)]
+enum Route {
+    ListSessions,
+    GetSession(String),
+    SessionLogs(String),
+    StopSession(String),
+    StartAgent,
+    RunSpec,
+    StartWorkflow(String),
+}
+
+fn route(method: &str, path: &str) -> Option<Route> {
+    let segments = path
+        .trim_matches('/')
+        .split('/')
+        .map(percent_decode)
+        .collect::<Option<Vec<String>>>()?;
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    let route = match (method, segments.as_slice()) {
+        ("GET", ["sessions"]) => Route::ListSessions,
+        ("GET", ["sessions", id]) => Route::GetSession(id.to_string()),
+        ("GET", ["sessions", id, "logs"]) => Route::SessionLogs(id.to_string()),
+        ("POST", ["sessions", id, "stop"]) => Route::StopSession(id.to_string()),
+        ("POST", ["agents", "start"]) => Route::StartAgent,
+        ("POST", ["specs", "run"]) => Route::RunSpec,
+        ("POST", ["workflows", id, "start"]) => Route::StartWorkflow(id.to_string()),
+        _ => return None,
+    };
+    Some(route)
+}
+
+/// Decode `%XX` escapes in a path segment, so ids match however a client
+/// encoded them. `None` when an escape is malformed or the result isn't
+/// UTF-8.
+fn percent_decode(segment: &str) -> Option<String> {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes
+                .get(i + 1..i + 3)
+                .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))?;
+            decoded.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Parse the request line and the headers this API reads.
+fn parse_head(head: &str) -> Option<Request> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split(' ');
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key.to_string(), value.to_string())
+        })
+        .collect();
+
+    let mut request = Request {
+        method,
+        path: path.to_string(),
+        query,
+        authorization: None,
+        content_length: 0,
+    };
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "authorization" => request.authorization = Some(value.to_string()),
+            "content-length" => request.content_length = value.parse().ok()?,
+            _ => {}
+        }
+    }
+    Some(request)
+}
+
+/// Whether `authorization` carries `token`, compared in constant time.
+fn authorized(authorization: Option<&str>, token: &str) -> bool {
+    let Some(given) = authorization.and_then(|a| a.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    // Not set yet, e.g. right after binding
+    if token.is_empty() {
+        return false;
+    }
+    // Hashing first makes the comparison independent of the lengths
+    let (given, expected) = (Sha256::digest(given.trim()), Sha256::digest(token));
+    given
+        .iter()
+        .zip(expected.iter())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+async fn serve(mut stream: TcpStream, app: &AppHandle, token: &str) -> std::io::Result<()> {
+    let request = match tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream)).await {
+        Ok(Ok(Some(request))) => request,
+        Ok(Ok(None)) => return respond(&mut stream, 400, &error_body("Malformed request")).await,
+        Ok(Err(e)) => return Err(e),
+        Err(_) => return respond(&mut stream, 408, &error_body("Request timed out")).await,
+    };
+    let (request, body) = request;
+    let (status, body) =
+        dispatch(&request, token, |route| handle(app, route, &request, &body)).await;
+    respond(&mut stream, status, &body).await
+}
+
+/// Authorize and route `request`, run `handle` on the route and turn the
+/// outcome into a status and body. Takes the handler as a parameter so the
+/// mapping can be exercised without a running app.
+async fn dispatch<F, Fut>(request: &Request, token: &str, handle: F) -> (u16, String)
+where
+    F: FnOnce(Route) -> Fut,
+    Fut: Future<Output = Result<serde_json::Value, ApiError>>,
+{
+    if !authorized(request.authorization.as_deref(), token) {
+        return (401, error_body("Missing or wrong bearer token"));
+    }
+    let Some(route) = route(&request.method, &request.path) else {
+        return (404, error_body("No such route"));
+    };
+    match handle(route).await {
+        Ok(value) => (200, value.to_string()),
+        Err(ApiError::BadRequest(message)) => (400, error_body(&message)),
+        Err(ApiError::App(e @ AppError::AgentNotApproved(_))) => (403, error_body(&e.to_string())),
+        Err(ApiError::App(AppError::SessionNotFound(id))) => {
+            (404, error_body(&format!("Session not found: {id}")))
+        }
+        Err(ApiError::App(e)) => (500, error_body(&e.to_string())),
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Option<(Request, Vec<u8>)>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end;
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return Ok(None);
+        }
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+    let Some(request) = std::str::from_utf8(&buf[..head_end])
+        .ok()
+        .and_then(parse_head)
+    else {
+        return Ok(None);
+    };
+    if request.content_length > MAX_BODY_BYTES {
+        return Ok(None);
+    }
+    let mut body = buf.split_off(head_end + 4);
+    while body.len() < request.content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(request.content_length);
+    Ok(Some((request, body)))
+}
+
+async fn respond(stream: &mut TcpStream, status: u16, body: &str) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        408 => "Request Timeout",
+        _ => "Internal Server Error",
+    };
+    let response = format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+fn error_body(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+enum ApiError {
+    BadRequest(String),
+    App(AppError),
+}
+
+impl From<AppError> for ApiError {
+    fn from(e: AppError) -> Self {
+        ApiError::App(e)
+    }
+}
+
+#[derive(Deserialize)]
+struct StartAgentBody {
+    name: String,
+    prompt: String,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    profile: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RunSpecBody {
+    spec_path: String,
+    agent_name: String,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    force: Option<bool>,
+}
+
+async fn handle(
+    app: &AppHandle,
+    route: Route,
+    request: &Request,
+    body: &[u8],
+) -> Result<serde_json::Value, ApiError> {
+    let value = match route {
+        Route::ListSessions => json(agent_commands::list_sessions(app.state()).await?)?,
+        Route::GetSession(id) => json(agent_commands::get_session(app.state(), id).await?)?,
+        Route::SessionLogs(id) => {
+            let after_id = query_number(request, "after_id")?;
+            let limit = query_number(request, "limit")?.map(|l| l as u32);
+            json(log_commands::get_session_logs_after(app.state(), id, after_id, limit).await?)?
+        }
+        Route::StopSession(id) => {
+            agent_commands::stop_agent(app.state(), id).await?;
+            serde_json::json!({ "stopped": true })
+        }
+        Route::StartAgent => {
+            let start: StartAgentBody = parse_body(body)?;
+            let model = match start.model {
+                Some(model) => model,
+                None => default_model(app).await,
+            };
+            let session_id = agent_commands::start_agent(
+                app.state(),
+                app.state(),
+                start.name,
+                model,
+                start.prompt,
+                start.profile,
+            )
+            .await?;
+            serde_json::json!({ "session_id": session_id })
+        }
+        Route::RunSpec => {
+            let run: RunSpecBody = parse_body(body)?;
+            let model = match run.model {
+                Some(model) => model,
+                None => default_model(app).await,
+            };
+            let session_id = spec_commands::run_spec(
+                app.state(),
+                app.state(),
+                app.state(),
+                run.spec_path,
+                run.agent_name,
+                model,
+                run.force,
+            )
+            .await?;
+            serde_json::json!({ "session_id": session_id })
+        }
+        Route::StartWorkflow(id) => {
+            workflow_commands::start_workflow(app.state(), id).await?;
+            serde_json::json!({ "started": true })
+        }
+    };
+    Ok(value)
+}
+
+async fn default_model(app: &AppHandle) -> String {
+    app.state::<ConfigState>()
+        .read()
+        .await
+        .default_model()
+        .unwrap_or_else(|| FALLBACK_MODEL.to_string())
+}
+
+fn json(value: impl Serialize) -> Result<serde_json::Value, ApiError> {
+    serde_json::to_value(value).map_err(|e| ApiError::App(e.into()))
+}
+
+fn parse_body<T: DeserializeOwned>(body: &[u8]) -> Result<T, ApiError> {
+    serde_json::from_slice(body).map_err(|e| ApiError::BadRequest(format!("Invalid body: {e}")))
+}
+
+fn query_number(request: &Request, key: &str) -> Result<Option<u64>, ApiError> {
+    let Some((_, value)) = request.query.iter().find(|(k, _)| k == key) else {
+        return Ok(None);
+    };
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| ApiError::BadRequest(format!("{key} must be a number")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::agent_approvals::AgentApprovalStore;
+
+    #[test]
+    fn requests_are_parsed_routed_and_authorized() {
+        let head = "GET /sessions/abc/logs?after_id=42&limit=10 HTTP/1.1\r\n\
+                    Host: 127.0.0.1:7777\r\n\
+                    authorization: Bearer s3cret\r\n\
+                    Content-Length: 0";
+        let request = parse_head(head).unwrap();
+        assert_eq!(request.path, "/sessions/abc/logs");
+        assert_eq!(query_number(&request, "after_id").ok(), Some(Some(42)));
+        assert!(authorized(request.authorization.as_deref(), "s3cret"));
+        assert!(!authorized(request.authorization.as_deref(), "s3cret2"));
+        assert!(!authorized(None, "s3cret"));
+        assert!(!authorized(Some("Bearer "), ""));
+
+        assert_eq!(
+            route(&request.method, &request.path),
+            Some(Route::SessionLogs("abc".into()))
+        );
+        assert_eq!(route("POST", "/agents/start/"), Some(Route::StartAgent));
+        assert_eq!(route("GET", "/agents/start"), None);
+        assert_eq!(
+            route("POST", "/workflows/wf-1/start"),
+            Some(Route::StartWorkflow("wf-1".into()))
+        );
+        assert_eq!(
+            route("GET", "/sessions/a%20b%2Fc"),
+            Some(Route::GetSession("a b/c".into()))
+        );
+        assert_eq!(route("GET", "/sessions/a%2"), None);
+        assert_eq!(route("GET", "/sessions/%+1"), None);
+    }
+
+    #[test]
+    fn handler_outcomes_map_to_statuses() {
+        let root = std::env::temp_dir().join(format!("clautron-http-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("project/.claude/agents")).unwrap();
+        std::fs::write(
+            root.join("project/.claude/agents/dev.md"),
+            "---\nname: dev\n---\n",
+        )
+        .unwrap();
+        let project = root.join("project").to_string_lossy().to_string();
+        let approvals = AgentApprovalStore::new(root.join("data"));
+        let (approvals, project) = (&approvals, &project);
+        let request = |method: &str, path: &str, token: &str| {
+            parse_head(&format!(
+                "{method} {path} HTTP/1.1\r\nAuthorization: Bearer {token}"
+            ))
+            .unwrap()
+        };
+        let status = |request: Request| {
+            tauri::async_runtime::block_on(dispatch(&request, "s3cret", |route| async move {
+                match route {
+                    Route::StartAgent => approvals
+                        .check_agent(&project, "dev")
+                        .map(|()| serde_json::json!({ "session_id": "s1" }))
+                        .map_err(|e| ApiError::App(AppError::AgentNotApproved(e))),
+                    Route::GetSession(id) => Err(ApiError::App(AppError::SessionNotFound(id))),
+                    _ => Ok(serde_json::Value::Null),
+                }
+            }))
+            .0
+        };
+
+        assert_eq!(status(request("GET", "/sessions", "wrong")), 401);
+        assert_eq!(status(request("POST", "/agents/start", "s3cret")), 403);
+        assert_eq!(status(request("GET", "/sessions/nope", "s3cret")), 404);
+        assert_eq!(status(request("GET", "/nowhere", "s3cret")), 404);
+
+        approvals
+            .approve_file(&project, std::path::Path::new(".claude/agents/dev.md"))
+            .unwrap();
+        assert_eq!(status(request("POST", "/agents/start", "s3cret")), 200);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
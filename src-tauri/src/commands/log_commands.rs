@@ -1,6 +1,20 @@
-use crate::domain::models::LogEntry;
-use crate::domain::ports::LogRepository;
+use crate::domain::models::{
+    AgentSession, CostGroup, CostGroupBy, ErrorDigest, FileChange, LogCompressionStats, LogEntry,
+    LogQueryFilter, RunEstimate, SessionCostBreakdown, SessionLogStats, SessionSearchResult,
+    StepKind, ToolCall, TranscriptImportReport, UsageRange,
+};
+use crate::domain::ports::{LogRepository, WorkflowRepository};
+use crate::domain::session_manager::SessionManager;
 use crate::error::AppError;
+use crate::services::cost_breakdown;
+use crate::services::error_digest;
+use crate::services::log_export::{self, LogExportFormat};
+use crate::services::run_estimate;
+use crate::services::spec_manager::SpecManager;
+use crate::services::transcript_import;
+use crate::services::usage_export;
+use crate::services::workflow_engine::WorkflowEngine;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::State;
 
@@ -17,6 +31,154 @@ pub async fn get_session_logs(
         .map_err(AppError::from)
 }
 
+/// Entries after the last id the caller has seen (all entries when
+/// `after_id` is omitted), for incremental polling.
+#[tauri::command]
+pub async fn get_session_logs_after(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    session_id: String,
+    after_id: Option<u64>,
+    limit: Option<u32>,
+) -> Result<Vec<LogEntry>, AppError> {
+    log_repo
+        .query_logs_after(&session_id, after_id.unwrap_or(0), limit.unwrap_or(500))
+        .await
+        .map_err(AppError::from)
+}
+
+/// The most recent entries of a session, oldest first.
+#[tauri::command]
+pub async fn get_session_logs_tail(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    session_id: String,
+    limit: Option<u32>,
+) -> Result<Vec<LogEntry>, AppError> {
+    log_repo
+        .query_logs_tail(&session_id, limit.unwrap_or(500))
+        .await
+        .map_err(AppError::from)
+}
+
+/// Entries matching a message-type / time-range / errors-only filter,
+/// paged by the last id seen.
+#[tauri::command]
+pub async fn get_session_logs_filtered(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    session_id: String,
+    filter: LogQueryFilter,
+    after_id: Option<u64>,
+    limit: Option<u32>,
+) -> Result<Vec<LogEntry>, AppError> {
+    let since = filter.since.as_deref().map(normalize_timestamp);
+    let until = filter.until.as_deref().map(normalize_timestamp);
+    let filter = LogQueryFilter {
+        since: since.transpose()?,
+        until: until.transpose()?,
+        ..filter
+    };
+    log_repo
+        .query_logs_filtered(
+            &session_id,
+            &filter,
+            after_id.unwrap_or(0),
+            limit.unwrap_or(500),
+        )
+        .await
+        .map_err(AppError::from)
+}
+
+/// Sessions of the current project whose logs contain `query`, each with
+/// its matching entries, newest first.
+#[tauri::command]
+pub async fn search_project_logs(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    query: String,
+    limit: Option<u32>,
+) -> Result<Vec<SessionSearchResult>, AppError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(AppError::Process("Search query is empty".into()));
+    }
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .ok_or_else(|| AppError::Process("No project directory set".into()))?;
+    log_repo.flush().await;
+    log_repo
+        .search_logs(&project_dir, query, limit.unwrap_or(200))
+        .await
+        .map_err(AppError::from)
+}
+
+/// Import the current project's terminal sessions from Claude Code's
+/// transcripts, then list every finished session stored for the project.
+#[tauri::command]
+pub async fn import_transcripts(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+) -> Result<TranscriptImportReport, AppError> {
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .ok_or_else(|| AppError::Process("No project directory set".into()))?;
+    let report = transcript_import::import_project(log_repo.inner().as_ref(), &project_dir)
+        .await
+        .map_err(AppError::Process)?;
+    let stored = log_repo.list_stored_sessions(&project_dir).await?;
+    session_manager.restore_sessions(stored).await;
+    Ok(report)
+}
+
+/// Message, tool and error counts for a session summary.
+#[tauri::command]
+pub async fn get_session_stats(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    session_id: String,
+) -> Result<SessionLogStats, AppError> {
+    log_repo.flush().await;
+    log_repo
+        .session_stats(&session_id)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Recurring failure modes across every session since `since` (RFC 3339),
+/// or over the whole history when unset.
+#[tauri::command]
+pub async fn get_error_digest(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    since: Option<String>,
+) -> Result<ErrorDigest, AppError> {
+    log_repo.flush().await;
+    let filter = LogQueryFilter {
+        errors_only: true,
+        since: since.as_deref().map(normalize_timestamp).transpose()?,
+        ..Default::default()
+    };
+    let mut entries: Vec<LogEntry> = Vec::new();
+    loop {
+        let after_id = entries.last().map_or(0, |e| e.id);
+        let page = log_repo
+            .query_all_logs_filtered(&filter, after_id, 1000)
+            .await?;
+        let done = page.len() < 1000;
+        entries.extend(page);
+        if done {
+            break;
+        }
+    }
+    Ok(error_digest::digest(&entries, filter.since))
+}
+
+/// Entries are stamped with `Utc::now().to_rfc3339()`; bounds are rendered
+/// the same way so they compare correctly as strings.
+fn normalize_timestamp(value: &str) -> Result<String, AppError> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&chrono::Utc).to_rfc3339())
+        .map_err(|e| AppError::Process(format!("Invalid timestamp {value}: {e}")))
+}
+
 #[tauri::command]
 pub async fn get_session_log_count(
     log_repo: State<'_, Arc<dyn LogRepository>>,
@@ -27,3 +189,247 @@ pub async fn get_session_log_count(
         .await
         .map_err(AppError::from)
 }
+
+#[tauri::command]
+pub async fn get_log_compression_stats(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+) -> Result<LogCompressionStats, AppError> {
+    log_repo.compression_stats().await.map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn get_session_tool_calls(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    session_id: String,
+) -> Result<Vec<ToolCall>, AppError> {
+    log_repo
+        .query_tool_calls(&session_id)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Which assistant turns of a session used the most tokens, with the
+/// session's cost shared out between them.
+#[tauri::command]
+pub async fn get_session_cost_breakdown(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    session_id: String,
+) -> Result<SessionCostBreakdown, AppError> {
+    log_repo.flush().await;
+    let filter = LogQueryFilter {
+        message_types: vec!["assistant".into()],
+        ..Default::default()
+    };
+    let mut entries: Vec<LogEntry> = Vec::new();
+    loop {
+        let after_id = entries.last().map_or(0, |e| e.id);
+        let page = log_repo
+            .query_logs_filtered(&session_id, &filter, after_id, 1000)
+            .await?;
+        let done = page.len() < 1000;
+        entries.extend(page);
+        if done {
+            break;
+        }
+    }
+
+    let session = session_manager.get_session(&session_id).await;
+    let total_cost_usd = session.as_ref().map_or(0.0, |s| s.cost_usd);
+    let turns = cost_breakdown::breakdown(&entries, total_cost_usd);
+    Ok(SessionCostBreakdown {
+        session_id,
+        total_cost_usd,
+        input_tokens: turns.iter().map(|t| t.input_tokens).sum(),
+        output_tokens: turns.iter().map(|t| t.output_tokens).sum(),
+        turns,
+    })
+}
+
+/// The current project's session costs summed per workflow, agent or spec,
+/// costliest first. Running sessions count with their cost so far.
+#[tauri::command]
+pub async fn get_cost_breakdown(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    workflow_repo: State<'_, Arc<dyn WorkflowRepository>>,
+    spec_manager: State<'_, Arc<SpecManager>>,
+    group_by: CostGroupBy,
+) -> Result<Vec<CostGroup>, AppError> {
+    let sessions = project_sessions(&log_repo, &session_manager).await?;
+
+    // session id -> (group key, label)
+    let mut groups: HashMap<String, (String, String)> = HashMap::new();
+    match group_by {
+        CostGroupBy::Agent => {}
+        CostGroupBy::Workflow => {
+            for workflow in workflow_repo.list_workflows().await? {
+                for step in workflow_repo.get_steps(&workflow.id).await? {
+                    if let Some(session_id) = step.session_id {
+                        groups.insert(session_id, (workflow.id.clone(), workflow.name.clone()));
+                    }
+                }
+            }
+        }
+        CostGroupBy::Spec => {
+            let specs = spec_manager.list_specs().await.map_err(AppError::Process)?;
+            for spec in specs {
+                let group = (spec.file_path.clone(), spec.title.clone());
+                let ids = spec.sessions.iter().map(|r| r.session_id.clone());
+                for session_id in ids.chain(spec.assigned_session_id.clone()) {
+                    groups.insert(session_id, group.clone());
+                }
+            }
+        }
+    }
+
+    let group_of = |s: &AgentSession| match group_by {
+        CostGroupBy::Agent => Some((s.agent_name.clone(), s.agent_name.clone())),
+        CostGroupBy::Workflow | CostGroupBy::Spec => groups.get(&s.id).cloned(),
+    };
+    let ungrouped = match group_by {
+        CostGroupBy::Agent => "",
+        CostGroupBy::Workflow => "No workflow",
+        CostGroupBy::Spec => "No spec",
+    };
+    Ok(cost_breakdown::group_costs(&sessions, group_of, ungrouped))
+}
+
+/// Write the current project's token usage and cost per day, model and
+/// agent within `range` to `path` as CSV, for expense reports and
+/// chargeback.
+#[tauri::command]
+pub async fn export_usage(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    range: UsageRange,
+    path: String,
+) -> Result<(), AppError> {
+    if path.trim().is_empty() {
+        return Err(AppError::Process("No export path given".into()));
+    }
+    let sessions = project_sessions(&log_repo, &session_manager).await?;
+    let content = usage_export::export_usage_csv(&sessions, &range);
+    std::fs::write(&path, content)?;
+    Ok(())
+}
+
+/// Predict what running a workflow (`workflow_id`) or a spec (`spec_path`)
+/// will cost and take, from the current project's past sessions of each
+/// agent involved. A spec runs with `agent_name` when given, otherwise
+/// with its assignees in turn.
+#[tauri::command]
+pub async fn estimate_cost(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    workflow_repo: State<'_, Arc<dyn WorkflowRepository>>,
+    engine: State<'_, Arc<WorkflowEngine>>,
+    spec_manager: State<'_, Arc<SpecManager>>,
+    workflow_id: Option<String>,
+    spec_path: Option<String>,
+    agent_name: Option<String>,
+) -> Result<RunEstimate, AppError> {
+    // (wave, agent) of each agent run
+    let planned: Vec<(usize, String)> = match (workflow_id, spec_path) {
+        (Some(workflow_id), None) => {
+            let simulation = engine.simulate(&workflow_id).await?;
+            let steps = workflow_repo.get_steps(&workflow_id).await?;
+            simulation
+                .steps
+                .into_iter()
+                .filter(|planned| {
+                    steps
+                        .iter()
+                        .any(|s| s.id == planned.step_id && s.kind != StepKind::Gate)
+                })
+                .map(|planned| (planned.wave, planned.agent_name))
+                .collect()
+        }
+        (None, Some(spec_path)) => {
+            let spec = spec_manager
+                .get_spec(&spec_path)
+                .await
+                .map_err(AppError::Process)?;
+            let agents = match agent_name {
+                Some(agent) => vec![agent],
+                None if !spec.assigned_agents.is_empty() => spec.assigned_agents.clone(),
+                None => spec.assigned_agent.clone().into_iter().collect(),
+            };
+            if agents.is_empty() {
+                return Err(AppError::Process("No agent is assigned to the spec".into()));
+            }
+            agents.into_iter().enumerate().collect()
+        }
+        _ => {
+            return Err(AppError::Process(
+                "Give either a workflow or a spec to estimate".into(),
+            ))
+        }
+    };
+    let history = project_sessions(&log_repo, &session_manager).await?;
+    Ok(run_estimate::estimate_run(&planned, &history))
+}
+
+/// The current project's stored sessions, updated with the live state of
+/// those still running, oldest first.
+async fn project_sessions(
+    log_repo: &Arc<dyn LogRepository>,
+    session_manager: &SessionManager,
+) -> Result<Vec<AgentSession>, AppError> {
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .ok_or_else(|| AppError::Process("No project directory set".into()))?;
+
+    let mut sessions: HashMap<String, AgentSession> = log_repo
+        .list_stored_sessions(&project_dir)
+        .await?
+        .into_iter()
+        .map(|s| (s.id.clone(), s))
+        .collect();
+    for session in session_manager.list_sessions().await {
+        if session.project_dir.as_deref() == Some(project_dir.as_str()) {
+            sessions.insert(session.id.clone(), session);
+        }
+    }
+    let mut sessions: Vec<AgentSession> = sessions.into_values().collect();
+    sessions.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+    Ok(sessions)
+}
+
+/// Files a session's Edit/Write/NotebookEdit calls touched, relative to the
+/// project where possible.
+#[tauri::command]
+pub async fn get_session_file_changes(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    session_id: String,
+) -> Result<Vec<FileChange>, AppError> {
+    log_repo
+        .query_file_changes(&session_id)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Write a session's transcript to `path` as Markdown, HTML or JSONL, for
+/// attaching to PRs and bug reports.
+#[tauri::command]
+pub async fn export_logs(
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    session_id: String,
+    format: LogExportFormat,
+    path: String,
+) -> Result<(), AppError> {
+    if path.trim().is_empty() {
+        return Err(AppError::Process("No export path given".into()));
+    }
+    log_repo.flush().await;
+    let count = log_repo.count_logs(&session_id).await?;
+    let entries = log_repo
+        .query_logs(&session_id, 0, count.min(u32::MAX as u64) as u32)
+        .await?;
+    let session = session_manager.get_session(&session_id).await;
+    let content = log_export::export_logs(&entries, session.as_ref(), format);
+    std::fs::write(&path, content)?;
+    Ok(())
+}
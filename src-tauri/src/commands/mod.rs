@@ -1,5 +1,7 @@
 pub mod agent_commands;
 pub mod config_commands;
+pub mod db_commands;
+pub mod http_api;
 pub mod log_commands;
 pub mod quota_commands;
 pub mod review_commands;
@@ -1,3 +1,4 @@
+use crate::domain::models::{UsageGranularity, UsagePeriod};
 use crate::services::quota_service::{poll_once, QuotaState};
 use std::sync::Arc;
 use tauri::AppHandle;
@@ -12,3 +13,21 @@ pub async fn refresh_quota(
     poll_once(&app, &state).await;
     Ok(())
 }
+
+/// Spend per day, week or month between `from` and `to` (`YYYY-MM-DD`,
+/// inclusive; open-ended when omitted), from daily usage snapshots.
+#[tauri::command]
+pub async fn get_usage_history(
+    state: tauri::State<'_, Arc<QuotaState>>,
+    from: Option<String>,
+    to: Option<String>,
+    granularity: Option<UsageGranularity>,
+) -> Result<Vec<UsagePeriod>, String> {
+    state
+        .usage_history(
+            from.as_deref(),
+            to.as_deref(),
+            granularity.unwrap_or_default(),
+        )
+        .await
+}
@@ -1,6 +1,13 @@
+use crate::commands::agent_commands::generate_text;
+use crate::domain::models::AgentSession;
+use crate::domain::ports::LogRepository;
 use crate::domain::session_manager::SessionManager;
 use crate::error::AppError;
-use crate::services::git_service::{ChangedFile, FileDiff};
+use crate::services::commit_message;
+use crate::services::git_service::{BlameLine, ChangedFile, FileDiff, GitBranch, GitCommit};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use tauri::State;
 
@@ -30,3 +37,288 @@ pub async fn get_diff(
     crate::services::git_service::get_diff(&project_dir, paths)
         .map_err(|e| AppError::Process(e))
 }
+
+/// One file's staged or unstaged diff, for loading large changesets on
+/// demand. None when that side of the file has no changes.
+#[tauri::command]
+pub async fn get_file_diff(
+    session_manager: State<'_, Arc<SessionManager>>,
+    path: String,
+    staged: bool,
+) -> Result<Option<FileDiff>, AppError> {
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .unwrap_or_else(|| ".".to_string());
+
+    crate::services::git_service::get_file_diff(&project_dir, &path, staged)
+        .map_err(AppError::Process)
+}
+
+/// Changes a session made: its working-tree snapshot at start against the
+/// one at its end, or against the tree as it is now while it still runs,
+/// limited to the files its Edit/Write tool calls touched (and to `paths`,
+/// if given). Attribution is ambiguous when another agent edited the same
+/// files during the window, whose edits show up too, and for sessions with
+/// no recorded file changes (e.g. changes made through shell commands),
+/// which get every change in the window.
+#[tauri::command]
+pub async fn get_session_diff(
+    session_manager: State<'_, Arc<SessionManager>>,
+    log_repo: State<'_, Arc<dyn LogRepository>>,
+    session_id: String,
+    paths: Option<Vec<String>>,
+) -> Result<Vec<FileDiff>, AppError> {
+    let session = session_manager
+        .get_session(&session_id)
+        .await
+        .ok_or_else(|| AppError::Process(format!("Session not found: {session_id}")))?;
+    let project_dir = match session.project_dir {
+        Some(dir) => dir,
+        None => session_manager
+            .get_project_dir()
+            .await
+            .unwrap_or_else(|| ".".to_string()),
+    };
+    let snapshot = log_repo.get_snapshot(&session_id).await?;
+    let start = snapshot.start_tree.ok_or_else(|| {
+        AppError::Process("No snapshot was taken when this session started".into())
+    })?;
+
+    let mut touched: Vec<String> = Vec::new();
+    for change in log_repo.query_file_changes(&session_id).await? {
+        // Paths outside the project were stored absolute
+        if !Path::new(&change.file_path).is_absolute() && !touched.contains(&change.file_path) {
+            touched.push(change.file_path);
+        }
+    }
+    let paths = if touched.is_empty() {
+        paths
+    } else {
+        Some(match paths {
+            Some(paths) => paths.into_iter().filter(|p| touched.contains(p)).collect(),
+            None => touched,
+        })
+    };
+    if paths.as_ref().is_some_and(|p| p.is_empty()) {
+        return Ok(vec![]);
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let end = match snapshot.end_tree {
+            Some(tree) => tree,
+            None => crate::services::git_service::snapshot_tree(&project_dir)?,
+        };
+        crate::services::git_service::diff_trees(&project_dir, &start, &end, paths)
+    })
+    .await
+    .map_err(|e| AppError::Process(e.to_string()))?
+    .map_err(AppError::Process)
+}
+
+/// Commits made between `since` and `until` (open-ended when omitted), each
+/// linked to the session that was running in the project at the time.
+#[tauri::command]
+pub async fn get_commits(
+    session_manager: State<'_, Arc<SessionManager>>,
+    since: String,
+    until: Option<String>,
+    author: Option<String>,
+) -> Result<Vec<GitCommit>, AppError> {
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .unwrap_or_else(|| ".".to_string());
+
+    let mut commits = crate::services::git_service::get_commits(
+        &project_dir,
+        &since,
+        until.as_deref(),
+        author.as_deref(),
+    )
+    .map_err(AppError::Process)?;
+    let sessions: Vec<AgentSession> = session_manager
+        .list_sessions()
+        .await
+        .into_iter()
+        .filter(|s| s.project_dir.as_deref() == Some(project_dir.as_str()))
+        .collect();
+    link_sessions(&mut commits, &sessions);
+    Ok(commits)
+}
+
+/// Set each commit's `session_id` to the session whose run covers its date;
+/// the most recently started one when several overlap.
+fn link_sessions(commits: &mut [GitCommit], sessions: &[AgentSession]) {
+    let parse = |date: &str| {
+        DateTime::parse_from_rfc3339(date)
+            .ok()
+            .map(|d| d.with_timezone(&Utc))
+    };
+    let windows: Vec<(DateTime<Utc>, DateTime<Utc>, &str)> = sessions
+        .iter()
+        .filter_map(|s| {
+            let start = parse(&s.started_at)?;
+            let end = s
+                .ended_at
+                .as_deref()
+                .and_then(parse)
+                .unwrap_or_else(Utc::now);
+            Some((start, end, s.id.as_str()))
+        })
+        .collect();
+    for commit in commits {
+        let Some(date) = parse(&commit.date) else {
+            continue;
+        };
+        commit.session_id = windows
+            .iter()
+            .filter(|(start, end, _)| *start <= date && date <= *end)
+            .max_by_key(|(start, _, _)| *start)
+            .map(|(_, _, id)| id.to_string());
+    }
+}
+
+/// Author and commit of lines `start_line..=end_line` of a file, at
+/// `revision` or in the working tree when omitted.
+#[tauri::command]
+pub async fn get_blame(
+    session_manager: State<'_, Arc<SessionManager>>,
+    path: String,
+    start_line: u32,
+    end_line: u32,
+    revision: Option<String>,
+) -> Result<Vec<BlameLine>, AppError> {
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .unwrap_or_else(|| ".".to_string());
+
+    crate::services::git_service::get_blame(
+        &project_dir,
+        &path,
+        start_line,
+        end_line,
+        revision.as_deref(),
+    )
+    .map_err(AppError::Process)
+}
+
+/// Revert every change to a file, staged or not; untracked files are deleted.
+#[tauri::command]
+pub async fn discard_file(
+    session_manager: State<'_, Arc<SessionManager>>,
+    path: String,
+) -> Result<(), AppError> {
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .unwrap_or_else(|| ".".to_string());
+
+    crate::services::git_service::discard_file(&project_dir, &path).map_err(AppError::Process)
+}
+
+/// Revert one hunk of a file, identified by its `@@` header and side
+/// (`staged`) from `get_diff`.
+#[tauri::command]
+pub async fn discard_hunk(
+    session_manager: State<'_, Arc<SessionManager>>,
+    path: String,
+    hunk_header: String,
+    staged: bool,
+) -> Result<(), AppError> {
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .unwrap_or_else(|| ".".to_string());
+
+    crate::services::git_service::discard_hunk(&project_dir, &path, &hunk_header, staged)
+        .map_err(AppError::Process)
+}
+
+/// Files and hunks a pasted patch would change, before choosing which to
+/// apply.
+#[tauri::command]
+pub async fn preview_patch(diff_text: String) -> Result<Vec<FileDiff>, AppError> {
+    crate::services::git_service::preview_patch(&diff_text).map_err(AppError::Process)
+}
+
+/// Apply a patch, such as one a plan-mode agent wrote, to the working tree:
+/// only the files in `paths` when given, and of the files in `hunks` only
+/// the hunks at those indexes. Returns the paths changed.
+#[tauri::command]
+pub async fn apply_patch(
+    session_manager: State<'_, Arc<SessionManager>>,
+    diff_text: String,
+    paths: Option<Vec<String>>,
+    hunks: Option<HashMap<String, Vec<usize>>>,
+) -> Result<Vec<String>, AppError> {
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .unwrap_or_else(|| ".".to_string());
+
+    crate::services::git_service::apply_patch(
+        &project_dir,
+        &diff_text,
+        paths.as_deref(),
+        hunks.as_ref(),
+    )
+    .map_err(AppError::Process)
+}
+
+#[tauri::command]
+pub async fn list_branches(
+    session_manager: State<'_, Arc<SessionManager>>,
+) -> Result<Vec<GitBranch>, AppError> {
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .unwrap_or_else(|| ".".to_string());
+
+    crate::services::git_service::list_branches(&project_dir).map_err(AppError::Process)
+}
+
+/// Create a branch at HEAD; switches to it unless `checkout` is false.
+#[tauri::command]
+pub async fn create_branch(
+    session_manager: State<'_, Arc<SessionManager>>,
+    name: String,
+    checkout: Option<bool>,
+) -> Result<(), AppError> {
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .unwrap_or_else(|| ".".to_string());
+
+    crate::services::git_service::create_branch(&project_dir, name.trim(), checkout.unwrap_or(true))
+        .map_err(AppError::Process)
+}
+
+#[tauri::command]
+pub async fn checkout_branch(
+    session_manager: State<'_, Arc<SessionManager>>,
+    name: String,
+) -> Result<(), AppError> {
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .unwrap_or_else(|| ".".to_string());
+
+    crate::services::git_service::checkout_branch(&project_dir, &name).map_err(AppError::Process)
+}
+
+/// Ask Claude for a conventional-commit message describing the current
+/// changes (or only `paths`).
+#[tauri::command]
+pub async fn suggest_commit_message(
+    session_manager: State<'_, Arc<SessionManager>>,
+    paths: Option<Vec<String>>,
+) -> Result<String, AppError> {
+    let diffs = get_diff(session_manager, paths).await?;
+    if diffs.is_empty() {
+        return Err(AppError::Process("No changes to describe".into()));
+    }
+    let output = generate_text(commit_message::build_prompt(&diffs)).await?;
+    commit_message::parse_message(&output).map_err(AppError::Process)
+}
@@ -1,18 +1,64 @@
-use crate::domain::models::{Spec, SpecPriority, SpecUpdate};
+use crate::commands::agent_commands;
+use crate::domain::models::{
+    AcceptanceCriterion, ReviewDecision, Spec, SpecDiagnostic, SpecFilter, SpecPriority,
+    SpecSessionRecord, SpecStats, SpecStatus, SpecStructure, SpecUpdate, VerificationStatus,
+};
+use crate::domain::ports::WorkflowRepository;
 use crate::domain::session_manager::SessionManager;
+use crate::commands::config_commands::ConfigState;
 use crate::error::AppError;
-use crate::services::spec_manager::SpecManager;
+use crate::services::github_issues::{self, GithubIssue};
+use crate::services::jira_import;
+use crate::services::linear_sync::{self, LinearSync, SyncConflict, SyncReport};
+use crate::services::agent_approvals::AgentApprovalStore;
+use crate::services::agent_manager::AgentManager;
+use crate::services::spec_batch::{self, RunSpecsMode};
+use crate::services::spec_breakdown::{self, ProposedSpec};
+use crate::services::spec_export::{self, ExportFormat};
+use crate::services::spec_manager::{spec_matches_ref, SpecManager, SpecRevision};
+use crate::services::spec_verifier;
+use crate::services::spec_workflow::SpecWorkflow;
+use crate::services::workflow_engine::WorkflowEngine;
 use std::sync::Arc;
 use tauri::State;
 
+/// List specs, optionally narrowed down by label, status, priority or agent.
 #[tauri::command]
 pub async fn list_specs(
     spec_manager: State<'_, Arc<SpecManager>>,
+    filter: Option<SpecFilter>,
 ) -> Result<Vec<Spec>, AppError> {
-    spec_manager
+    let specs = spec_manager
         .list_specs()
         .await
-        .map_err(|e| AppError::Process(e))
+        .map_err(|e| AppError::Process(e))?;
+    Ok(match filter {
+        Some(filter) => specs.into_iter().filter(|s| filter.matches(s)).collect(),
+        None => specs,
+    })
+}
+
+/// Export specs as CSV or JSON for reporting. With `output_path` (picked in
+/// a save dialog) the export is also written to that file.
+#[tauri::command]
+pub async fn export_specs(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    format: ExportFormat,
+    filter: Option<SpecFilter>,
+    output_path: Option<String>,
+) -> Result<String, AppError> {
+    let specs: Vec<Spec> = spec_manager
+        .list_specs()
+        .await
+        .map_err(AppError::Process)?
+        .into_iter()
+        .filter(|s| filter.as_ref().is_none_or(|f| f.matches(s)))
+        .collect();
+    let content = spec_export::export_specs(&specs, format).map_err(AppError::Process)?;
+    if let Some(path) = output_path {
+        std::fs::write(&path, &content)?;
+    }
+    Ok(content)
 }
 
 #[tauri::command]
@@ -26,28 +72,235 @@ pub async fn get_spec(
         .map_err(|e| AppError::Process(e))
 }
 
+/// Every agent run made on a spec, oldest first. Runs still in progress
+/// show their live usage so far.
+#[tauri::command]
+pub async fn get_spec_sessions(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    file_path: String,
+) -> Result<Vec<SpecSessionRecord>, AppError> {
+    let spec = spec_manager
+        .get_spec(&file_path)
+        .await
+        .map_err(AppError::Process)?;
+    let mut records = spec.sessions;
+    for record in records.iter_mut().filter(|r| r.outcome.is_none()) {
+        if let Some(session) = session_manager.get_session(&record.session_id).await {
+            record.cost_usd = Some(session.cost_usd);
+            record.input_tokens = Some(session.input_tokens);
+            record.output_tokens = Some(session.output_tokens);
+        }
+    }
+    Ok(records)
+}
+
+/// Create a spec, optionally inside an epic folder (`auth/login`) of specs/
+/// and with a standard set of body sections.
 #[tauri::command]
 pub async fn create_spec(
     spec_manager: State<'_, Arc<SpecManager>>,
     title: String,
     priority: SpecPriority,
+    epic: Option<String>,
+    structure: Option<SpecStructure>,
 ) -> Result<Spec, AppError> {
     spec_manager
-        .create_spec(title, priority)
+        .create_spec(title, priority, epic, structure.unwrap_or_default())
         .await
         .map_err(|e| AppError::Process(e))
 }
 
+/// Update a spec and roll its status up into its parent specs. Fails with a
+/// conflict carrying both versions if the file changed on disk meanwhile.
 #[tauri::command]
 pub async fn update_spec(
     spec_manager: State<'_, Arc<SpecManager>>,
     file_path: String,
     update: SpecUpdate,
 ) -> Result<Spec, AppError> {
+    if let Some(status) = &update.status {
+        spec_manager
+            .check_transition(&file_path, status)
+            .await
+            .map_err(AppError::Process)?;
+    }
+    let spec = spec_manager
+        .update_spec_checked(&file_path, update)
+        .await
+        .map_err(AppError::from)?;
+    spec_manager.rollup_parents(&spec).await;
+    Ok(spec)
+}
+
+/// Move a spec within (or into) a board column, persisting its rank.
+#[tauri::command]
+pub async fn reorder_spec(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    file_path: String,
+    status: SpecStatus,
+    index: usize,
+) -> Result<Spec, AppError> {
+    let spec = spec_manager
+        .reorder_spec(&file_path, status, index)
+        .await
+        .map_err(AppError::Process)?;
+    spec_manager.rollup_parents(&spec).await;
+    Ok(spec)
+}
+
+#[tauri::command]
+pub async fn list_child_specs(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    file_path: String,
+) -> Result<Vec<Spec>, AppError> {
     spec_manager
-        .update_spec(&file_path, update)
+        .list_children(&file_path)
         .await
-        .map_err(|e| AppError::Process(e))
+        .map_err(AppError::Process)
+}
+
+#[tauri::command]
+pub async fn create_child_spec(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    parent_path: String,
+    title: String,
+    priority: SpecPriority,
+) -> Result<Spec, AppError> {
+    spec_manager
+        .create_child_spec(&parent_path, title, priority)
+        .await
+        .map_err(AppError::Process)
+}
+
+/// Committed versions of a spec with per-commit diffs, newest first.
+#[tauri::command]
+pub async fn get_spec_history(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    file_path: String,
+    limit: Option<usize>,
+) -> Result<Vec<SpecRevision>, AppError> {
+    spec_manager
+        .history(&file_path, limit.unwrap_or(50))
+        .await
+        .map_err(AppError::Process)
+}
+
+/// The project's statuses (board order) and allowed transitions, from
+/// `.clautron/spec-workflow.json` or the built-in defaults.
+#[tauri::command]
+pub async fn get_spec_workflow(
+    spec_manager: State<'_, Arc<SpecManager>>,
+) -> Result<SpecWorkflow, AppError> {
+    match spec_manager.get_project_dir().await {
+        Some(project_dir) => SpecWorkflow::load(&project_dir).map_err(AppError::Process),
+        None => Ok(SpecWorkflow::default()),
+    }
+}
+
+/// Backlog size: open points per priority and per status.
+#[tauri::command]
+pub async fn get_spec_stats(
+    spec_manager: State<'_, Arc<SpecManager>>,
+) -> Result<SpecStats, AppError> {
+    spec_manager.stats().await.map_err(AppError::Process)
+}
+
+/// Approve a spec: mark it done and log the decision with an optional comment.
+#[tauri::command]
+pub async fn approve_spec(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    file_path: String,
+    comment: Option<String>,
+) -> Result<Spec, AppError> {
+    let spec = spec_manager
+        .review_spec(&file_path, ReviewDecision::Approved, comment)
+        .await
+        .map_err(AppError::Process)?;
+    spec_manager.rollup_parents(&spec).await;
+    Ok(spec)
+}
+
+/// Reject a spec with a comment. The comment is included in the prompt the
+/// next time the spec is run.
+#[tauri::command]
+pub async fn reject_spec(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    file_path: String,
+    comment: String,
+) -> Result<Spec, AppError> {
+    let spec = spec_manager
+        .review_spec(&file_path, ReviewDecision::Rejected, Some(comment))
+        .await
+        .map_err(AppError::Process)?;
+    spec_manager.rollup_parents(&spec).await;
+    Ok(spec)
+}
+
+/// Schema problems in every spec file (missing title, invalid status,
+/// malformed dates, unknown fields), including files that fail to parse.
+#[tauri::command]
+pub async fn validate_specs(
+    spec_manager: State<'_, Arc<SpecManager>>,
+) -> Result<Vec<SpecDiagnostic>, AppError> {
+    spec_manager
+        .validate_specs()
+        .await
+        .map_err(AppError::Process)
+}
+
+/// Ask Claude to split a spec into child specs. Nothing is written; the
+/// proposals are returned for confirmation via `apply_spec_breakdown`.
+#[tauri::command]
+pub async fn breakdown_spec(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    agent_manager: State<'_, Arc<AgentManager>>,
+    spec_path: String,
+) -> Result<Vec<ProposedSpec>, AppError> {
+    let spec = spec_manager
+        .get_spec(&spec_path)
+        .await
+        .map_err(AppError::Process)?;
+    let agents = agent_manager.list_agents().await.unwrap_or_default();
+    let prompt = spec_breakdown::build_prompt(&spec, &agents);
+    let output = agent_commands::generate_text(prompt).await?;
+    spec_breakdown::parse_proposals(&output, &agents).map_err(AppError::Process)
+}
+
+/// Write the confirmed proposals as child specs of `spec_path`.
+#[tauri::command]
+pub async fn apply_spec_breakdown(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    spec_path: String,
+    children: Vec<ProposedSpec>,
+) -> Result<Vec<Spec>, AppError> {
+    let mut created = Vec::new();
+    for child in children {
+        let spec = spec_manager
+            .create_child_spec(&spec_path, child.title, child.priority)
+            .await
+            .map_err(AppError::Process)?;
+        let spec = spec_manager
+            .update_spec(
+                &spec.file_path,
+                SpecUpdate {
+                    body: Some(child.body),
+                    acceptance_criteria: Some(
+                        child
+                            .acceptance_criteria
+                            .into_iter()
+                            .map(AcceptanceCriterion::new)
+                            .collect(),
+                    ),
+                    suggested_agent: Some(child.suggested_agent),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(AppError::Process)?;
+        created.push(spec);
+    }
+    Ok(created)
 }
 
 #[tauri::command]
@@ -62,26 +315,44 @@ pub async fn delete_spec(
 }
 
 /// Run a spec by assigning it to an agent and starting the agent.
+/// Refuses while `depends_on` specs aren't done, unless `force` is set.
 #[tauri::command]
 pub async fn run_spec(
     spec_manager: State<'_, Arc<SpecManager>>,
     session_manager: State<'_, Arc<SessionManager>>,
+    approvals: State<'_, Arc<AgentApprovalStore>>,
     spec_path: String,
     agent_name: String,
     model: String,
+    force: Option<bool>,
 ) -> Result<String, AppError> {
+    agent_commands::require_approved(&approvals, &session_manager, &agent_name).await?;
+
     // Read the spec
     let spec = spec_manager
         .get_spec(&spec_path)
         .await
         .map_err(|e| AppError::Process(e))?;
 
+    if !force.unwrap_or(false) {
+        let unmet = spec_manager
+            .unmet_dependencies(&spec)
+            .await
+            .map_err(AppError::Process)?;
+        if !unmet.is_empty() {
+            return Err(AppError::Process(format!(
+                "Spec is blocked by unfinished dependencies: {}",
+                unmet.join(", ")
+            )));
+        }
+    }
+
     // Build prompt from spec
     let prompt = SpecManager::build_prompt_from_spec(&spec);
 
     // Start the agent
     let session_id = session_manager
-        .start_agent(agent_name.clone(), model, prompt)
+        .start_agent_for(agent_name.clone(), model, prompt, Some(&spec.title), None)
         .await
         .map_err(AppError::from)?;
 
@@ -93,3 +364,273 @@ pub async fn run_spec(
 
     Ok(session_id)
 }
+
+/// Run several specs with one agent through an ad-hoc workflow, either one
+/// after another or several at a time. Each step is bound to its
+/// spec exactly like `run_spec`. A spec that depends on another in the batch
+/// waits for it; refuses while other `depends_on` specs aren't done, unless
+/// `force` is set. Returns the workflow ID.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn run_specs(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    workflow_repo: State<'_, Arc<dyn WorkflowRepository>>,
+    engine: State<'_, Arc<WorkflowEngine>>,
+    spec_paths: Vec<String>,
+    agent_name: String,
+    model: String,
+    mode: RunSpecsMode,
+    force: Option<bool>,
+) -> Result<String, AppError> {
+    let mut specs = Vec::with_capacity(spec_paths.len());
+    for path in &spec_paths {
+        specs.push(
+            spec_manager
+                .get_spec(path)
+                .await
+                .map_err(AppError::Process)?,
+        );
+    }
+    if !force.unwrap_or(false) {
+        let mut unmet = Vec::new();
+        for spec in &specs {
+            let mut outside = spec.clone();
+            outside
+                .depends_on
+                .retain(|dep| !spec_paths.iter().any(|p| spec_matches_ref(p, dep)));
+            unmet.extend(
+                spec_manager
+                    .unmet_dependencies(&outside)
+                    .await
+                    .map_err(AppError::Process)?,
+            );
+        }
+        unmet.sort();
+        unmet.dedup();
+        if !unmet.is_empty() {
+            return Err(AppError::Process(format!(
+                "Spec is blocked by unfinished dependencies: {}",
+                unmet.join(", ")
+            )));
+        }
+    }
+    let workflow = spec_batch::create_batch_workflow(
+        workflow_repo.as_ref(),
+        &spec_paths,
+        &spec_batch::batch_dependencies(&specs),
+        &agent_name,
+        &model,
+        &mode,
+    )
+    .await
+    .map_err(AppError::Process)?;
+    engine.start(&workflow.id).await.map_err(AppError::from)?;
+    Ok(workflow.id)
+}
+
+/// Run a spec with all of its `assigned_agents`: the first implements it and
+/// the others review in turn, as an ad-hoc workflow. Returns the workflow ID.
+/// Refuses while `depends_on` specs aren't done, unless `force` is set.
+#[tauri::command]
+pub async fn run_spec_pair(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    workflow_repo: State<'_, Arc<dyn WorkflowRepository>>,
+    engine: State<'_, Arc<WorkflowEngine>>,
+    spec_path: String,
+    model: String,
+    force: Option<bool>,
+) -> Result<String, AppError> {
+    let spec = spec_manager
+        .get_spec(&spec_path)
+        .await
+        .map_err(AppError::Process)?;
+    if !force.unwrap_or(false) {
+        let unmet = spec_manager
+            .unmet_dependencies(&spec)
+            .await
+            .map_err(AppError::Process)?;
+        if !unmet.is_empty() {
+            return Err(AppError::Process(format!(
+                "Spec is blocked by unfinished dependencies: {}",
+                unmet.join(", ")
+            )));
+        }
+    }
+    let workflow = spec_batch::create_pair_workflow(
+        workflow_repo.as_ref(),
+        &spec_path,
+        &spec.title,
+        &spec.assigned_agents,
+        &model,
+    )
+    .await
+    .map_err(AppError::Process)?;
+    engine.start(&workflow.id).await.map_err(AppError::from)?;
+    Ok(workflow.id)
+}
+
+/// Launch a verification agent, in plan permission mode so it can't change
+/// the project, that checks the spec's acceptance criteria. Results are
+/// applied to the spec when the session finishes.
+#[tauri::command]
+pub async fn verify_spec(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    session_manager: State<'_, Arc<SessionManager>>,
+    spec_path: String,
+    agent_name: String,
+    model: String,
+) -> Result<String, AppError> {
+    let spec = spec_manager
+        .get_spec(&spec_path)
+        .await
+        .map_err(AppError::Process)?;
+    if spec.acceptance_criteria.is_empty() {
+        return Err(AppError::Process(
+            "Spec has no acceptance criteria to verify".into(),
+        ));
+    }
+    if spec
+        .verification
+        .as_ref()
+        .is_some_and(|v| v.status == VerificationStatus::Running)
+    {
+        return Err(AppError::Process("Spec is already being verified".into()));
+    }
+
+    let prompt = spec_verifier::build_prompt(&spec);
+    let session_id = session_manager
+        .start_read_only_agent(agent_name, model, prompt)
+        .await
+        .map_err(AppError::from)?;
+
+    spec_manager
+        .start_verification(&spec_path, &session_id)
+        .await
+        .map_err(AppError::Process)?;
+
+    Ok(session_id)
+}
+
+// --- Issue tracker imports ---
+
+#[tauri::command]
+pub async fn list_github_issues(
+    config_state: State<'_, ConfigState>,
+    repo: String,
+    limit: Option<u32>,
+) -> Result<Vec<GithubIssue>, AppError> {
+    let token = config_state.read().await.integrations.github_token.clone();
+    github_issues::list_issues(&repo, token.as_deref(), limit.unwrap_or(50))
+        .await
+        .map_err(AppError::Process)
+}
+
+/// Import the selected GitHub issues as specs. Already-imported issues are
+/// returned as their existing specs.
+#[tauri::command]
+pub async fn import_github_issues(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    config_state: State<'_, ConfigState>,
+    repo: String,
+    numbers: Vec<u64>,
+) -> Result<Vec<Spec>, AppError> {
+    let token = config_state.read().await.integrations.github_token.clone();
+    let issues = github_issues::fetch_issues(&repo, &numbers, token.as_deref())
+        .await
+        .map_err(AppError::Process)?;
+    let mut specs = Vec::new();
+    for issue in &issues {
+        specs.push(
+            spec_manager
+                .import_issue(issue)
+                .await
+                .map_err(AppError::Process)?,
+        );
+    }
+    Ok(specs)
+}
+
+/// Import every ticket matching a JQL query as a spec.
+#[tauri::command]
+pub async fn import_jira_issues(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    config_state: State<'_, ConfigState>,
+    jql: String,
+    limit: Option<u32>,
+) -> Result<Vec<Spec>, AppError> {
+    let jira = config_state
+        .read()
+        .await
+        .integrations
+        .jira
+        .clone()
+        .ok_or_else(|| AppError::Process("Jira is not configured".into()))?;
+    let issues = jira_import::search_issues(&jira, &jql, limit.unwrap_or(50))
+        .await
+        .map_err(AppError::Process)?;
+    let mut specs = Vec::new();
+    for issue in &issues {
+        specs.push(
+            spec_manager
+                .import_issue(issue)
+                .await
+                .map_err(AppError::Process)?,
+        );
+    }
+    Ok(specs)
+}
+
+async fn linear_api_key(config_state: &ConfigState) -> Result<String, AppError> {
+    config_state
+        .read()
+        .await
+        .integrations
+        .linear_api_key
+        .clone()
+        .ok_or_else(|| AppError::Process("Linear is not configured".into()))
+}
+
+/// Import open Linear issues (optionally for one team key) as specs.
+#[tauri::command]
+pub async fn import_linear_issues(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    config_state: State<'_, ConfigState>,
+    team_key: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<Spec>, AppError> {
+    let api_key = linear_api_key(&config_state).await?;
+    let issues = linear_sync::list_issues(&api_key, team_key.as_deref(), limit.unwrap_or(50))
+        .await
+        .map_err(AppError::Process)?;
+    let mut specs = Vec::new();
+    for issue in &issues {
+        specs.push(
+            spec_manager
+                .import_issue(issue)
+                .await
+                .map_err(AppError::Process)?,
+        );
+    }
+    Ok(specs)
+}
+
+/// Run a Linear sync pass now instead of waiting for the background task.
+#[tauri::command]
+pub async fn sync_linear(
+    spec_manager: State<'_, Arc<SpecManager>>,
+    config_state: State<'_, ConfigState>,
+    linear: State<'_, Arc<LinearSync>>,
+) -> Result<SyncReport, AppError> {
+    let api_key = linear_api_key(&config_state).await?;
+    linear
+        .sync(&api_key, &spec_manager)
+        .await
+        .map_err(AppError::Process)
+}
+
+#[tauri::command]
+pub async fn list_linear_conflicts(
+    linear: State<'_, Arc<LinearSync>>,
+) -> Result<Vec<SyncConflict>, AppError> {
+    Ok(linear.conflicts().await)
+}
@@ -1,8 +1,15 @@
 use crate::domain::models::*;
 use crate::domain::ports::WorkflowRepository;
+use crate::domain::session_manager::SessionManager;
 use crate::error::AppError;
+use crate::services::agent_manager::AgentManager;
+use crate::services::command_importer::{self, CustomCommand};
 use crate::services::workflow_engine::WorkflowEngine;
+use crate::services::workflow_templates::{
+    TemplateInstantiation, WorkflowTemplate, WorkflowTemplateStore,
+};
 use chrono::Utc;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::State;
 use uuid::Uuid;
@@ -23,6 +30,7 @@ pub async fn create_workflow(
         status: WorkflowStatus::Draft,
         created_at: now.clone(),
         updated_at: now,
+        max_parallel: None,
     };
     repo.save_workflow(&workflow)
         .await
@@ -67,6 +75,12 @@ pub async fn add_workflow_step(
     position_x: f64,
     position_y: f64,
     pass_context: Option<bool>,
+    failure_policy: Option<FailurePolicy>,
+    join_mode: Option<JoinMode>,
+    kind: Option<StepKind>,
+    gate_command: Option<String>,
+    prompt_template_id: Option<String>,
+    template_vars: Option<HashMap<String, String>>,
 ) -> Result<WorkflowStep, AppError> {
     let step = WorkflowStep {
         id: Uuid::new_v4().to_string(),
@@ -82,6 +96,13 @@ pub async fn add_workflow_step(
         created_at: Utc::now().to_rfc3339(),
         pass_context: pass_context.unwrap_or(false),
         result_output: None,
+        failure_policy: failure_policy.unwrap_or_default(),
+        join_mode: join_mode.unwrap_or_default(),
+        kind: kind.unwrap_or_default(),
+        gate_command,
+        prompt_template_id,
+        template_vars: template_vars.unwrap_or_default(),
+        commit_message: None,
     };
     repo.save_step(&step).await.map_err(AppError::from)?;
     Ok(step)
@@ -117,12 +138,14 @@ pub async fn add_workflow_edge(
     workflow_id: String,
     source_step_id: String,
     target_step_id: String,
+    condition: Option<EdgeCondition>,
 ) -> Result<WorkflowEdge, AppError> {
     let edge = WorkflowEdge {
         id: Uuid::new_v4().to_string(),
         workflow_id,
         source_step_id,
         target_step_id,
+        condition: condition.unwrap_or_default(),
     };
     repo.save_edge(&edge).await.map_err(AppError::from)?;
     Ok(edge)
@@ -167,3 +190,159 @@ pub async fn validate_workflow(
 ) -> Result<(), AppError> {
     engine.validate(&id).await.map_err(AppError::from)
 }
+
+#[tauri::command]
+pub async fn simulate_workflow(
+    engine: State<'_, Arc<WorkflowEngine>>,
+    id: String,
+) -> Result<WorkflowSimulation, AppError> {
+    engine.simulate(&id).await.map_err(AppError::from)
+}
+
+// --- Shared workflow templates ---
+
+#[tauri::command]
+pub async fn list_workflow_templates(
+    templates: State<'_, Arc<WorkflowTemplateStore>>,
+) -> Result<Vec<WorkflowTemplate>, AppError> {
+    templates.list().map_err(AppError::Process)
+}
+
+/// Save an existing workflow as a template shared across projects.
+#[tauri::command]
+pub async fn save_workflow_template(
+    templates: State<'_, Arc<WorkflowTemplateStore>>,
+    repo: State<'_, WorkflowRepo>,
+    workflow_id: String,
+    name: Option<String>,
+) -> Result<WorkflowTemplate, AppError> {
+    templates
+        .save_from_workflow(repo.inner().as_ref(), &workflow_id, name)
+        .await
+        .map_err(AppError::Process)
+}
+
+#[tauri::command]
+pub async fn delete_workflow_template(
+    templates: State<'_, Arc<WorkflowTemplateStore>>,
+    id: String,
+) -> Result<(), AppError> {
+    templates.delete(&id).map_err(AppError::Process)
+}
+
+/// Create a workflow in the current project from a template, resolving
+/// agents by name against the project's `.claude/agents/`.
+#[tauri::command]
+pub async fn instantiate_workflow_template(
+    templates: State<'_, Arc<WorkflowTemplateStore>>,
+    repo: State<'_, WorkflowRepo>,
+    agent_manager: State<'_, Arc<AgentManager>>,
+    template_id: String,
+    name: Option<String>,
+) -> Result<TemplateInstantiation, AppError> {
+    let available: Vec<String> = agent_manager
+        .list_agents()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|a| a.name)
+        .collect();
+
+    templates
+        .instantiate(repo.inner().as_ref(), &template_id, name, &available)
+        .await
+        .map_err(AppError::Process)
+}
+
+// --- Step prompt templates ---
+
+#[tauri::command]
+pub async fn list_prompt_templates(
+    repo: State<'_, WorkflowRepo>,
+) -> Result<Vec<PromptTemplate>, AppError> {
+    repo.list_prompt_templates().await.map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn create_prompt_template(
+    repo: State<'_, WorkflowRepo>,
+    name: String,
+    body: String,
+    variables: Option<HashMap<String, String>>,
+) -> Result<PromptTemplate, AppError> {
+    let now = Utc::now().to_rfc3339();
+    let template = PromptTemplate {
+        id: Uuid::new_v4().to_string(),
+        name,
+        body,
+        variables: variables.unwrap_or_default(),
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    repo.save_prompt_template(&template)
+        .await
+        .map_err(AppError::from)?;
+    Ok(template)
+}
+
+/// Update a template. Every step referencing it picks up the change on its next run.
+#[tauri::command]
+pub async fn update_prompt_template(
+    repo: State<'_, WorkflowRepo>,
+    template: PromptTemplate,
+) -> Result<PromptTemplate, AppError> {
+    let template = PromptTemplate {
+        updated_at: Utc::now().to_rfc3339(),
+        ..template
+    };
+    repo.update_prompt_template(&template)
+        .await
+        .map_err(AppError::from)?;
+    Ok(template)
+}
+
+#[tauri::command]
+pub async fn delete_prompt_template(
+    repo: State<'_, WorkflowRepo>,
+    id: String,
+) -> Result<(), AppError> {
+    repo.delete_prompt_template(&id).await.map_err(AppError::from)
+}
+
+// --- Import from Claude Code custom commands ---
+
+/// Preview the project's `.claude/commands/*.md` files as workflow phases.
+#[tauri::command]
+pub async fn list_custom_commands(
+    session_manager: State<'_, Arc<SessionManager>>,
+) -> Result<Vec<CustomCommand>, AppError> {
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .ok_or_else(|| AppError::Process("No project directory set".into()))?;
+    command_importer::list_commands(&project_dir).map_err(AppError::Process)
+}
+
+/// Create a draft workflow from a custom command, one step per phase.
+#[tauri::command]
+pub async fn import_custom_command(
+    session_manager: State<'_, Arc<SessionManager>>,
+    repo: State<'_, WorkflowRepo>,
+    command_name: String,
+    agent_name: String,
+    model: Option<String>,
+) -> Result<Workflow, AppError> {
+    let project_dir = session_manager
+        .get_project_dir()
+        .await
+        .ok_or_else(|| AppError::Process("No project directory set".into()))?;
+    command_importer::import_command(
+        repo.inner().as_ref(),
+        &project_dir,
+        &command_name,
+        &agent_name,
+        model,
+    )
+    .await
+    .map_err(AppError::Process)
+}
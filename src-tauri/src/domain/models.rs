@@ -3,8 +3,10 @@ use std::collections::HashMap;
 
 // --- Spec ---
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(rename_all = "snake_case")]
+/// Built-in statuses drive the agent lifecycle. Projects can add their own
+/// (`blocked`, `qa`, ...) in their spec workflow; those load as `Custom`.
+/// Serialized as the plain snake_case id either way.
+#[derive(Debug, Clone, PartialEq)]
 pub enum SpecStatus {
     Draft,
     Assigned,
@@ -12,6 +14,32 @@ pub enum SpecStatus {
     Review,
     Done,
     Rejected,
+    Custom(String),
+}
+
+impl SpecStatus {
+    pub const BUILT_IN: [SpecStatus; 6] = [
+        SpecStatus::Draft,
+        SpecStatus::Assigned,
+        SpecStatus::InProgress,
+        SpecStatus::Review,
+        SpecStatus::Done,
+        SpecStatus::Rejected,
+    ];
+
+    /// Parse a status id (case-insensitive); anything unknown is `Custom`.
+    pub fn parse(id: &str) -> SpecStatus {
+        let id = id.trim().to_lowercase();
+        match id.as_str() {
+            "draft" => SpecStatus::Draft,
+            "assigned" => SpecStatus::Assigned,
+            "in_progress" => SpecStatus::InProgress,
+            "review" => SpecStatus::Review,
+            "done" => SpecStatus::Done,
+            "rejected" => SpecStatus::Rejected,
+            _ => SpecStatus::Custom(id),
+        }
+    }
 }
 
 impl std::fmt::Display for SpecStatus {
@@ -23,10 +51,24 @@ impl std::fmt::Display for SpecStatus {
             SpecStatus::Review => write!(f, "review"),
             SpecStatus::Done => write!(f, "done"),
             SpecStatus::Rejected => write!(f, "rejected"),
+            SpecStatus::Custom(id) => write!(f, "{id}"),
         }
     }
 }
 
+impl Serialize for SpecStatus {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SpecStatus {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Ok(SpecStatus::parse(&id))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum SpecPriority {
@@ -45,19 +87,333 @@ impl std::fmt::Display for SpecPriority {
     }
 }
 
+/// Body layout for a new spec.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecStructure {
+    /// Empty body.
+    #[default]
+    Blank,
+    /// Context, Requirements, Out of Scope and Acceptance Criteria sections.
+    Standard,
+}
+
+/// Size estimate: a t-shirt size (`S`, `M`, `L`, ...) or story points.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum SpecEstimate {
+    Points(f64),
+    Size(String),
+}
+
+impl SpecEstimate {
+    /// Points used for roll-ups. Sizes map to a Fibonacci-ish scale;
+    /// unknown sizes count as unestimated.
+    pub fn points(&self) -> Option<f64> {
+        match self {
+            SpecEstimate::Points(p) if p.is_finite() && *p >= 0.0 => Some(*p),
+            SpecEstimate::Points(_) => None,
+            SpecEstimate::Size(size) => match size.trim().to_uppercase().as_str() {
+                "XS" => Some(0.5),
+                "S" => Some(1.0),
+                "M" => Some(3.0),
+                "L" => Some(5.0),
+                "XL" => Some(8.0),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// A checkable acceptance criterion. Plain strings in older spec files are
+/// read as unchecked criteria.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(from = "CriterionRepr")]
+pub struct AcceptanceCriterion {
+    pub text: String,
+    pub done: bool,
+    /// Latest finding from a verification run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finding: Option<String>,
+}
+
+impl AcceptanceCriterion {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            done: false,
+            finding: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CriterionRepr {
+    Text(String),
+    Item {
+        text: String,
+        #[serde(default)]
+        done: bool,
+        #[serde(default)]
+        finding: Option<String>,
+    },
+}
+
+impl From<CriterionRepr> for AcceptanceCriterion {
+    fn from(repr: CriterionRepr) -> Self {
+        match repr {
+            CriterionRepr::Text(text) => Self::new(text),
+            CriterionRepr::Item {
+                text,
+                done,
+                finding,
+            } => Self {
+                text,
+                done,
+                finding,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStatus {
+    Running,
+    /// Every criterion passed.
+    Passed,
+    /// At least one criterion failed.
+    Failed,
+    /// The agent failed or didn't produce a readable report.
+    Error,
+}
+
+/// The latest verification run for a spec.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecVerification {
+    pub session_id: String,
+    pub status: VerificationStatus,
+    pub started_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Approved,
+    Rejected,
+}
+
+/// One entry in a spec's review log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecReview {
+    pub decision: ReviewDecision,
+    pub reviewer: String,
+    pub at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// One agent run on a spec. Usage is stored when the run ends, since
+/// sessions themselves aren't kept across restarts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecSessionRecord {
+    pub session_id: String,
+    pub agent_name: String,
+    pub started_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<String>,
+    /// Final agent status; `None` while the run is in progress.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<AgentStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_tokens: Option<u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Spec {
     pub title: String,
     pub priority: SpecPriority,
     pub status: SpecStatus,
-    pub acceptance_criteria: Vec<String>,
+    #[serde(default)]
+    pub estimate: Option<SpecEstimate>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub acceptance_criteria: Vec<AcceptanceCriterion>,
     pub assigned_agent: Option<String>,
     pub assigned_session_id: Option<String>,
+    /// Agents that work on this spec together: the first implements it, the
+    /// others review in turn (see `run_spec_pair`).
+    #[serde(default)]
+    pub assigned_agents: Vec<String>,
     pub parent_spec: Option<String>,
+    /// Agent proposed for this spec (e.g. by a breakdown); preselected when running.
+    #[serde(default)]
+    pub suggested_agent: Option<String>,
+    /// Specs that must be `done` first, by file path, file name or slug.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Position within its status column on the board (lower first).
+    #[serde(default)]
+    pub rank: Option<u32>,
+    /// `YYYY-MM-DD` (end of that day, local time) or an RFC 3339 timestamp.
+    #[serde(default)]
+    pub due_date: Option<String>,
+    /// Git branch holding this spec's work. Once it is merged (its pull
+    /// request, or a merge commit on the default branch) the spec is done.
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Source issue in an external tracker, e.g. `github:owner/repo#12`.
+    #[serde(default)]
+    pub external_id: Option<String>,
+    #[serde(default)]
+    pub external_url: Option<String>,
+    #[serde(default)]
+    pub verification: Option<SpecVerification>,
+    /// Approve/reject decisions, oldest first.
+    #[serde(default)]
+    pub reviews: Vec<SpecReview>,
+    /// Every agent run bound to this spec, oldest first.
+    #[serde(default)]
+    pub sessions: Vec<SpecSessionRecord>,
     pub created_at: String,
     pub updated_at: String,
     pub file_path: String,
     pub body: String,
+    /// Computed by `list_specs`, not stored: unfinished or missing dependencies.
+    #[serde(default)]
+    pub blocked_by: Vec<String>,
+    /// Computed by `list_specs`: number of specs whose `parent_spec` is this one.
+    #[serde(default)]
+    pub child_count: usize,
+    /// Computed by `list_specs`: how many of those children are `done`.
+    #[serde(default)]
+    pub children_done: usize,
+    /// File modification time (ms since epoch) when this spec was read.
+    /// Passed back as `SpecUpdate::expected_mtime` to detect concurrent edits.
+    #[serde(default)]
+    pub file_mtime: Option<u64>,
+    /// Computed from the file's location: its folder relative to `specs/`
+    /// (e.g. `auth/login`), or `None` for specs at the top level.
+    #[serde(default)]
+    pub epic: Option<String>,
+}
+
+/// A spec update refused because the file changed on disk since it was read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecConflict {
+    pub file_path: String,
+    /// The spec as it is on disk now.
+    pub current: Spec,
+    /// The spec that would have been written.
+    pub attempted: Spec,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticSeverity {
+    /// The file can't be loaded and is missing from the board.
+    Error,
+    /// The file loads, but a value is ignored or replaced by a default.
+    Warning,
+}
+
+/// A problem found in a spec file's frontmatter by `validate_specs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecDiagnostic {
+    pub file_path: String,
+    pub severity: DiagnosticSeverity,
+    /// Frontmatter field the problem is about, if any.
+    pub field: Option<String>,
+    pub message: String,
+}
+
+/// Criteria for `list_specs`; unset fields match everything.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SpecFilter {
+    /// Specs must carry every one of these labels (case-insensitive).
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub status: Option<SpecStatus>,
+    #[serde(default)]
+    pub priority: Option<SpecPriority>,
+    #[serde(default)]
+    pub assigned_agent: Option<String>,
+    /// Only specs in this folder (subfolders included).
+    #[serde(default)]
+    pub epic: Option<String>,
+}
+
+impl SpecFilter {
+    pub fn matches(&self, spec: &Spec) -> bool {
+        self.status.as_ref().is_none_or(|s| *s == spec.status)
+            && self.priority.as_ref().is_none_or(|p| *p == spec.priority)
+            && self
+                .assigned_agent
+                .as_ref()
+                .is_none_or(|a| spec.assigned_agent.as_ref() == Some(a))
+            && self.epic.as_deref().is_none_or(|wanted| {
+                spec.epic.as_deref().is_some_and(|epic| {
+                    epic == wanted
+                        || epic
+                            .strip_prefix(wanted)
+                            .is_some_and(|rest| rest.starts_with('/'))
+                })
+            })
+            && self.labels.iter().all(|wanted| {
+                spec.labels
+                    .iter()
+                    .any(|label| label.eq_ignore_ascii_case(wanted.trim()))
+            })
+    }
+}
+
+/// Spec count and estimated points for one priority or status.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpecStatBucket {
+    /// `p0`..`p2` or a status such as `in_progress`, as serialized on `Spec`.
+    pub key: String,
+    pub count: usize,
+    pub points: f64,
+}
+
+/// Backlog size roll-up. Specs with children contribute to counts but not
+/// points, so a parent's estimate isn't counted on top of its children's.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecStats {
+    pub total: usize,
+    /// Specs that aren't done or rejected.
+    pub open: usize,
+    pub open_points: f64,
+    /// Open leaf specs without a usable estimate.
+    pub unestimated: usize,
+    /// Open specs only.
+    pub by_priority: Vec<SpecStatBucket>,
+    /// All specs, in board order.
+    pub by_status: Vec<SpecStatBucket>,
+}
+
+/// An issue fetched from an external tracker, normalized for import as a spec.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalIssue {
+    /// Tracker-prefixed ID, e.g. `github:owner/repo#12`.
+    pub external_id: String,
+    pub url: Option<String>,
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>,
+    pub priority: SpecPriority,
+    /// Mapped tracker status, if the tracker has one worth keeping.
+    pub status: Option<SpecStatus>,
 }
 
 /// Fields that can be updated on a spec.
@@ -66,11 +422,20 @@ pub struct SpecUpdate {
     pub title: Option<String>,
     pub priority: Option<SpecPriority>,
     pub status: Option<SpecStatus>,
-    pub acceptance_criteria: Option<Vec<String>>,
+    pub estimate: Option<Option<SpecEstimate>>,
+    pub labels: Option<Vec<String>>,
+    pub acceptance_criteria: Option<Vec<AcceptanceCriterion>>,
     pub assigned_agent: Option<Option<String>>,
     pub assigned_session_id: Option<Option<String>>,
+    pub assigned_agents: Option<Vec<String>>,
     pub parent_spec: Option<Option<String>>,
+    pub suggested_agent: Option<Option<String>>,
+    pub depends_on: Option<Vec<String>>,
+    pub due_date: Option<Option<String>>,
+    pub branch: Option<Option<String>>,
     pub body: Option<String>,
+    /// Refuse the update if the file's mtime no longer matches.
+    pub expected_mtime: Option<u64>,
 }
 
 // --- Agent Config ---
@@ -80,12 +445,32 @@ pub struct AgentConfig {
     pub name: String,
     pub description: String,
     pub model: String,
+    /// Always a name from `agent_parser::AGENT_COLORS`.
     pub color: String,
+    /// Icon name from `agent_parser::AGENT_ICONS`, if the agent sets one.
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// Tools the agent may use (`tools:` frontmatter). `None` inherits all tools.
+    #[serde(default)]
+    pub tools: Option<Vec<String>>,
+    /// Agents this one is meant to collaborate with (`pairs_with:` frontmatter).
+    #[serde(default)]
+    pub pairs_with: Vec<String>,
     pub file_path: String,
     #[serde(default)]
     pub body: String,
 }
 
+/// A frontmatter problem found by `validate_agents`. Errors mean the agent
+/// can't be loaded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentDiagnostic {
+    pub file_path: String,
+    pub severity: DiagnosticSeverity,
+    pub field: Option<String>,
+    pub message: String,
+}
+
 /// Fields that can be updated on an agent config.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AgentConfigUpdate {
@@ -93,16 +478,72 @@ pub struct AgentConfigUpdate {
     pub description: Option<String>,
     pub model: Option<String>,
     pub color: Option<String>,
+    /// `Some(None)` removes the icon.
+    pub icon: Option<Option<String>>,
+    /// `Some(None)` removes the restriction so the agent inherits all tools.
+    pub tools: Option<Option<Vec<String>>>,
+    pub pairs_with: Option<Vec<String>>,
+    pub body: Option<String>,
+}
+
+/// A Claude Code slash command from `.claude/commands/`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashCommand {
+    /// Name as typed after `/`; subdirectories become `dir:name`.
+    pub name: String,
+    pub file_path: String,
+    pub description: Option<String>,
+    pub argument_hint: Option<String>,
+    /// Comma-separated, as in the frontmatter.
+    pub allowed_tools: Option<String>,
+    pub model: Option<String>,
+    pub body: String,
+}
+
+/// Fields that can be updated on a slash command. An empty string removes
+/// the frontmatter field.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SlashCommandUpdate {
+    pub description: Option<String>,
+    pub argument_hint: Option<String>,
+    pub allowed_tools: Option<String>,
+    pub model: Option<String>,
     pub body: Option<String>,
 }
 
-/// A relationship between two agents derived from workflow edges.
+/// A skill package: a `.claude/skills/<dir>/` folder with a `SKILL.md`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Skill {
+    pub name: String,
+    pub description: String,
+    pub dir_path: String,
+    /// Files in the package, relative to `dir_path`.
+    pub files: Vec<String>,
+    /// Names of the agents whose definition mentions this skill.
+    pub referenced_by: Vec<String>,
+}
+
+/// An approved agent definition in the current project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentApproval {
+    /// Path relative to the project root, `/`-separated.
+    pub file_path: String,
+    pub hash: String,
+    /// RFC 3339 timestamp.
+    pub approved_at: String,
+}
+
+/// A relationship between two agents: observed in workflow edges, declared
+/// with `pairs_with:` in the source agent's frontmatter, or both.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentRelationship {
     pub source_agent: String,
     pub target_agent: String,
     pub workflow_names: Vec<String>,
+    /// Workflow edges between the two; 0 for a pairing that is only declared.
     pub edge_count: usize,
+    #[serde(default)]
+    pub declared: bool,
 }
 
 // --- Agent Status ---
@@ -145,6 +586,146 @@ pub struct AgentSession {
     pub input_tokens: u64,
     pub output_tokens: u64,
     pub cost_usd: f64,
+    /// Project the session was started in; None when no project was open.
+    #[serde(default)]
+    pub project_dir: Option<String>,
+    /// Git branch created for the session's work.
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// `cost_usd` was estimated from tokens and the pricing table because
+    /// Claude Code reported none (API-key mode).
+    #[serde(default)]
+    pub cost_estimated: bool,
+    /// Claude profile the session ran under; None for the default one.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+/// Usage of one agent aggregated over its sessions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentStats {
+    pub agent_name: String,
+    pub run_count: u32,
+    pub completed_count: u32,
+    pub error_count: u32,
+    /// Share of finished runs that completed / errored (0.0-1.0). Runs still
+    /// in progress don't count; stopped runs count as finished.
+    pub success_rate: f64,
+    pub error_rate: f64,
+    pub avg_duration_secs: Option<f64>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub total_cost_usd: f64,
+    pub avg_cost_usd: f64,
+    pub last_used_at: Option<String>,
+}
+
+/// Outcome of a sandboxed agent test run. Never stored as a session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentTestRun {
+    pub agent_name: String,
+    pub model: String,
+    pub status: AgentStatus,
+    /// Final result text reported by the CLI.
+    pub result: Option<String>,
+    /// Every stream-json message, redacted, in order.
+    pub transcript: Vec<AgentTestMessage>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentTestMessage {
+    pub message_type: String,
+    pub content: String,
+}
+
+/// Which CLAUDE.md memory file: shared project memory, the user's private
+/// project memory (`CLAUDE.local.md`), or the user's global memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryScope {
+    Project,
+    ProjectLocal,
+    User,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryFile {
+    pub scope: MemoryScope,
+    pub path: String,
+    pub exists: bool,
+    /// Empty when the file doesn't exist yet.
+    pub content: String,
+}
+
+/// Where an MCP server is configured: the project's `.mcp.json` or the
+/// user's `~/.claude.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpScope {
+    Project,
+    User,
+}
+
+/// An entry of the `mcpServers` map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpServer {
+    pub name: String,
+    pub scope: McpScope,
+    /// `stdio`, `http` or `sse`.
+    pub transport: String,
+    pub command: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpHealthStatus {
+    Ok,
+    Error,
+    Timeout,
+    /// A project stdio server the user hasn't allowed to be launched.
+    Unapproved,
+}
+
+/// Result of trying to start (or reach) one MCP server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpHealth {
+    pub name: String,
+    pub scope: McpScope,
+    pub status: McpHealthStatus,
+    pub message: String,
+    pub latency_ms: u64,
+}
+
+/// Which Claude Code settings file: the shared `.claude/settings.json`, the
+/// git-ignored `.claude/settings.local.json`, or the user's
+/// `~/.claude/settings.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingsScope {
+    Project,
+    ProjectLocal,
+    User,
+}
+
+/// The `hooks` section of one settings file. `hooks` is the raw JSON so
+/// files that fail validation can still be shown and fixed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HooksSettings {
+    pub scope: SettingsScope,
+    pub path: String,
+    pub exists: bool,
+    pub hooks: serde_json::Value,
+    /// Schema problems in `hooks`, each prefixed with its JSON path.
+    pub errors: Vec<String>,
 }
 
 // --- Log Entry ---
@@ -156,6 +737,182 @@ pub struct LogEntry {
     pub message_type: String,
     pub content: String,
     pub timestamp: String,
+    /// Token usage reported on assistant messages.
+    #[serde(default)]
+    pub input_tokens: Option<u64>,
+    #[serde(default)]
+    pub output_tokens: Option<u64>,
+}
+
+/// Token usage reported on one assistant message.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MessageUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+/// One assistant turn's share of a session's cost.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnCost {
+    /// Id of the turn's first log entry.
+    pub log_id: u64,
+    pub timestamp: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// The session's reported cost apportioned by weighted token usage.
+    pub estimated_cost_usd: f64,
+    /// Tools the turn called, in order.
+    pub tools: Vec<String>,
+    pub text_preview: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionCostBreakdown {
+    pub session_id: String,
+    pub total_cost_usd: f64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub turns: Vec<TurnCost>,
+}
+
+/// What session costs are summed by.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CostGroupBy {
+    Workflow,
+    Agent,
+    Spec,
+}
+
+/// Total cost of one group's sessions, e.g. every run of a workflow.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CostGroup {
+    /// Workflow id, agent name or spec path; None for the sessions outside
+    /// any workflow or spec.
+    pub key: Option<String>,
+    pub label: String,
+    pub cost_usd: f64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub session_count: u32,
+}
+
+/// A stored session with the log entries that matched a search, newest
+/// first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSearchResult {
+    pub session_id: String,
+    pub agent_name: String,
+    pub model: String,
+    pub prompt: String,
+    pub started_at: String,
+    pub project_dir: Option<String>,
+    pub matches: Vec<LogEntry>,
+}
+
+/// Summary figures for one session's log, computed without loading it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionLogStats {
+    pub session_id: String,
+    pub total_entries: u64,
+    pub messages_by_type: HashMap<String, u64>,
+    pub tool_calls_by_name: HashMap<String, u64>,
+    /// Content size as written by the agent, before compression.
+    pub content_bytes: u64,
+    pub first_timestamp: Option<String>,
+    pub last_timestamp: Option<String>,
+    /// stderr lines, failed tool calls and error results.
+    pub error_count: u64,
+}
+
+/// How much space compression of large log entries is saving.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogCompressionStats {
+    pub total_entries: u64,
+    pub compressed_entries: u64,
+    /// Size of all content as written by the agents.
+    pub raw_bytes: u64,
+    /// Size actually stored, compressed entries counted at their gzip size.
+    pub stored_bytes: u64,
+}
+
+/// Narrows a log query. Empty `message_types` means every type; besides the
+/// stored types (`system`, `assistant`, `user`, `result`, `stderr`) it takes
+/// `tool_use` and `tool_result` for the messages carrying those blocks.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LogQueryFilter {
+    #[serde(default)]
+    pub message_types: Vec<String>,
+    /// Inclusive RFC 3339 bounds on the entry timestamp.
+    pub since: Option<String>,
+    pub until: Option<String>,
+    /// Only stderr, failed tool results and error results.
+    #[serde(default)]
+    pub errors_only: bool,
+}
+
+/// What kind of failure an error entry reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    RateLimit,
+    Overloaded,
+    Auth,
+    Network,
+    ToolFailure,
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorExample {
+    pub session_id: String,
+    pub timestamp: String,
+    pub message: String,
+}
+
+/// Errors that look alike once numbers, ids and paths are masked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorGroup {
+    pub category: ErrorCategory,
+    pub signature: String,
+    pub count: u64,
+    pub session_count: u64,
+    pub first_seen: String,
+    pub last_seen: String,
+    pub examples: Vec<ErrorExample>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorDigest {
+    pub since: Option<String>,
+    pub total_errors: u64,
+    /// Most frequent first.
+    pub groups: Vec<ErrorGroup>,
+}
+
+/// A message of a Claude Code transcript, converted to a log entry.
+#[derive(Debug, Clone)]
+pub struct TranscriptEntry {
+    pub message_type: String,
+    pub content: String,
+    pub timestamp: String,
+    pub usage: Option<MessageUsage>,
+}
+
+/// A session run directly in the terminal, read from its transcript.
+#[derive(Debug, Clone)]
+pub struct ImportedTranscript {
+    pub session: AgentSession,
+    pub entries: Vec<TranscriptEntry>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TranscriptImportReport {
+    pub imported: u32,
+    /// Transcripts already stored, or without any messages.
+    pub skipped: u32,
+    /// `<file>: <error>` for each transcript that could not be imported.
+    pub failed: Vec<String>,
 }
 
 // --- Workflows ---
@@ -206,6 +963,89 @@ impl std::fmt::Display for StepStatus {
     }
 }
 
+/// What the engine does when a step fails.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// Mark the whole workflow as failed (default).
+    #[default]
+    FailWorkflow,
+    /// Treat the failed step as done and keep running its dependents.
+    Continue,
+    /// Skip every step downstream of the failed one; run the rest.
+    SkipDescendants,
+}
+
+impl std::fmt::Display for FailurePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FailurePolicy::FailWorkflow => write!(f, "fail_workflow"),
+            FailurePolicy::Continue => write!(f, "continue"),
+            FailurePolicy::SkipDescendants => write!(f, "skip_descendants"),
+        }
+    }
+}
+
+/// How a step with several parents decides it is unblocked.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum JoinMode {
+    /// Wait for every parent (default).
+    #[default]
+    All,
+    /// Start as soon as any one parent is done.
+    Any,
+}
+
+impl std::fmt::Display for JoinMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JoinMode::All => write!(f, "all"),
+            JoinMode::Any => write!(f, "any"),
+        }
+    }
+}
+
+/// What a workflow step executes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StepKind {
+    /// Run a Claude Code agent (default).
+    #[default]
+    Agent,
+    /// Run a verification command (tests, linter); passes on exit code 0.
+    Gate,
+}
+
+impl std::fmt::Display for StepKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StepKind::Agent => write!(f, "agent"),
+            StepKind::Gate => write!(f, "gate"),
+        }
+    }
+}
+
+/// When an edge's target may run, relative to the source step's outcome.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeCondition {
+    /// Run after the source succeeds (default).
+    #[default]
+    OnSuccess,
+    /// Run only if the source fails, e.g. a fixer branch after a gate.
+    OnFailure,
+}
+
+impl std::fmt::Display for EdgeCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EdgeCondition::OnSuccess => write!(f, "on_success"),
+            EdgeCondition::OnFailure => write!(f, "on_failure"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Workflow {
     pub id: String,
@@ -214,6 +1054,10 @@ pub struct Workflow {
     pub status: WorkflowStatus,
     pub created_at: String,
     pub updated_at: String,
+    /// Most steps running at once; ready steps wait for a free slot, in
+    /// canvas order. No limit when None.
+    #[serde(default)]
+    pub max_parallel: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -231,6 +1075,25 @@ pub struct WorkflowStep {
     pub created_at: String,
     pub pass_context: bool,
     pub result_output: Option<String>,
+    #[serde(default)]
+    pub failure_policy: FailurePolicy,
+    #[serde(default)]
+    pub join_mode: JoinMode,
+    #[serde(default)]
+    pub kind: StepKind,
+    /// Command line run by gate steps, e.g. `cargo test`. Not run via a shell.
+    #[serde(default)]
+    pub gate_command: Option<String>,
+    /// Shared prompt template used instead of `prompt` when set.
+    #[serde(default)]
+    pub prompt_template_id: Option<String>,
+    /// Per-step values for the template's variables (override its defaults).
+    #[serde(default)]
+    pub template_vars: HashMap<String, String>,
+    /// When set, the working tree is committed after the step completes
+    /// with this message template; blank uses the default message.
+    #[serde(default)]
+    pub commit_message: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -239,6 +1102,97 @@ pub struct WorkflowEdge {
     pub workflow_id: String,
     pub source_step_id: String,
     pub target_step_id: String,
+    #[serde(default)]
+    pub condition: EdgeCondition,
+}
+
+/// A named step prompt with `{{variable}}` placeholders, shared across steps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub id: String,
+    pub name: String,
+    pub body: String,
+    /// Default variable values; steps override them via `template_vars`.
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A single step in a workflow dry-run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedStep {
+    pub step_id: String,
+    pub agent_name: String,
+    /// Zero-based parallel wave the step would start in.
+    pub wave: usize,
+    /// Average cost of this agent's past sessions, if any.
+    pub estimated_cost_usd: Option<f64>,
+    /// Prompt size with the parent output available today.
+    pub prompt_chars: usize,
+    /// Prompt size if every missing parent output hits the result cap.
+    pub max_prompt_chars: usize,
+    pub estimated_prompt_tokens: usize,
+}
+
+/// Result of simulating a workflow without spawning agents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowSimulation {
+    pub workflow_id: String,
+    pub execution_order: Vec<String>,
+    /// Step IDs grouped by wave; steps in the same wave run in parallel.
+    pub waves: Vec<Vec<String>>,
+    pub steps: Vec<SimulatedStep>,
+    /// Sum of the per-step estimates that have history.
+    pub estimated_cost_usd: f64,
+    pub warnings: Vec<String>,
+}
+
+/// What one planned agent run is expected to use, from the agent's past
+/// finished sessions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AgentRunEstimate {
+    pub agent_name: String,
+    /// Zero-based wave the run starts in; runs in a wave go in parallel.
+    pub wave: usize,
+    /// Past sessions the averages come from; zero leaves them unset.
+    pub sample_size: u32,
+    pub avg_cost_usd: Option<f64>,
+    pub avg_input_tokens: Option<u64>,
+    pub avg_output_tokens: Option<u64>,
+    pub avg_duration_secs: Option<f64>,
+}
+
+/// Predicted usage of a workflow or spec run, shown before starting it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RunEstimate {
+    pub runs: Vec<AgentRunEstimate>,
+    /// Sums over the runs with history.
+    pub cost_usd: f64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Wall-clock time: the slowest run of each wave, one wave after another.
+    pub duration_secs: f64,
+    /// Agents without finished sessions, whose runs aren't in the totals.
+    pub missing_history: Vec<String>,
+}
+
+// --- Tool Calls ---
+
+/// One tool invocation by an agent, paired with its result once it arrives.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCall {
+    pub id: u64,
+    pub session_id: String,
+    pub tool_use_id: String,
+    pub tool_name: String,
+    /// The salient input (command, file path, pattern…), redacted and truncated.
+    pub input_summary: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub duration_ms: Option<u64>,
+    /// `None` while the call is still running.
+    pub success: Option<bool>,
 }
 
 // --- File Changes ---
@@ -252,6 +1206,201 @@ pub struct FileChange {
     pub timestamp: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotPoint {
+    Start,
+    End,
+}
+
+/// A separate Claude Code configuration directory, e.g. one per account,
+/// passed to sessions as `CLAUDE_CONFIG_DIR`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClaudeProfile {
+    pub name: String,
+    /// `~` expands to the home directory.
+    pub config_dir: String,
+}
+
+impl ClaudeProfile {
+    pub fn config_path(&self) -> std::path::PathBuf {
+        match self.config_dir.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+            None => std::path::PathBuf::from(&self.config_dir),
+        }
+    }
+}
+
+/// What starting an agent does when the project has uncommitted changes.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DirtyTreePolicy {
+    /// Start as usual (default).
+    #[default]
+    Allow,
+    /// Start, noting the changes in the session's log.
+    Warn,
+    /// Refuse to start until the changes are committed or stashed.
+    Block,
+    /// Stash the changes, untracked files included, then start.
+    Stash,
+}
+
+/// Git tree hashes of the working tree (untracked files included) when a
+/// session started and ended.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub start_tree: Option<String>,
+    pub end_tree: Option<String>,
+}
+
+// --- Database ---
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DbTableInfo {
+    pub name: String,
+    pub rows: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DbInfo {
+    pub path: String,
+    /// Database plus WAL files on disk.
+    pub file_size_bytes: u64,
+    /// Space in free pages, reclaimable with a vacuum.
+    pub free_bytes: u64,
+    pub page_count: u64,
+    pub tables: Vec<DbTableInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DbVacuumResult {
+    pub size_before: u64,
+    pub size_after: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DbIntegrityReport {
+    pub ok: bool,
+    /// `PRAGMA integrity_check` output; empty when the file is sound.
+    pub problems: Vec<String>,
+}
+
+// --- Usage History ---
+
+/// Token counts and cost of one model, cumulative or over a period.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ModelUsage {
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Claude Code's all-time usage of a model as last seen on `date`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UsageSnapshot {
+    /// Local date, `YYYY-MM-DD`.
+    pub date: String,
+    /// Profile whose stats were read; None for the default one.
+    #[serde(default)]
+    pub profile: Option<String>,
+    #[serde(flatten)]
+    pub usage: ModelUsage,
+}
+
+/// Size of the periods usage history is grouped into.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageGranularity {
+    #[default]
+    Day,
+    /// Weeks starting on Monday.
+    Week,
+    Month,
+}
+
+/// Usage within one period of the history.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UsagePeriod {
+    /// First day of the period, `YYYY-MM-DD`.
+    pub start: String,
+    pub cost_usd: f64,
+    /// Per-model usage in the period, costliest first.
+    pub models: Vec<ModelUsage>,
+}
+
+/// USD per million tokens of one model, for estimating costs Claude Code
+/// doesn't report.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ModelPrice {
+    pub input_per_mtok: f64,
+    pub output_per_mtok: f64,
+}
+
+impl ModelPrice {
+    pub const fn new(input_per_mtok: f64, output_per_mtok: f64) -> Self {
+        Self {
+            input_per_mtok,
+            output_per_mtok,
+        }
+    }
+}
+
+/// A model offered by model pickers.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ModelOption {
+    /// Passed to `claude --model`.
+    pub id: String,
+    pub label: String,
+    /// A family alias following the latest release, rather than a dated ID.
+    pub alias: bool,
+}
+
+/// The models to pick from and the one preselected.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelCatalog {
+    pub models: Vec<ModelOption>,
+    pub default_model: String,
+}
+
+/// Inclusive range of local dates, `YYYY-MM-DD`; open-ended on a side that
+/// is None.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UsageRange {
+    #[serde(default)]
+    pub from: Option<String>,
+    #[serde(default)]
+    pub to: Option<String>,
+}
+
+impl UsageRange {
+    pub fn contains(&self, date: &str) -> bool {
+        self.from.as_deref().is_none_or(|from| date >= from)
+            && self.to.as_deref().is_none_or(|to| date <= to)
+    }
+}
+
+// --- Redaction ---
+
+/// Which secret patterns `redact_secrets` applies, on top of the defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RedactionConfig {
+    /// Names of built-in patterns to skip.
+    #[serde(default)]
+    pub disabled_builtins: Vec<String>,
+    /// Extra regexes, e.g. internal token formats or customer identifiers.
+    #[serde(default)]
+    pub custom_patterns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionPattern {
+    pub name: String,
+    pub pattern: String,
+}
+
 // --- Stream Messages ---
 
 /// A single message from Claude Code's stream-json output.
@@ -292,6 +1441,17 @@ pub struct ResultMessage {
     pub extra: HashMap<String, serde_json::Value>,
 }
 
+impl ResultMessage {
+    /// Cost of the run as Claude Code reports it: `total_cost_usd`, or
+    /// `cost_usd` from older versions.
+    pub fn cost_usd(&self) -> Option<f64> {
+        self.extra
+            .get("total_cost_usd")
+            .or_else(|| self.extra.get("cost_usd"))
+            .and_then(|v| v.as_f64())
+    }
+}
+
 impl StreamMessage {
     pub fn message_type(&self) -> &str {
         match self {
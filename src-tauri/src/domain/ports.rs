@@ -1,9 +1,11 @@
 use super::error::DomainError;
 use super::models::{
-    AgentSession, AgentStatus, LogEntry, StepStatus, Workflow, WorkflowEdge, WorkflowStatus,
-    WorkflowStep,
+    AgentSession, AgentStatus, FileChange, LogCompressionStats, LogEntry, LogQueryFilter,
+    MessageUsage, ModelUsage, PromptTemplate, SessionLogStats, SessionSearchResult, SessionSnapshot,
+    SnapshotPoint, StepStatus, ToolCall, TranscriptEntry, UsageSnapshot, Workflow, WorkflowEdge, WorkflowStatus, WorkflowStep,
 };
 use async_trait::async_trait;
+use std::collections::HashSet;
 
 // ---------------------------------------------------------------------------
 // Port: AgentRunner — mechanism for running agent processes
@@ -16,6 +18,14 @@ pub struct SpawnConfig {
     pub model: String,
     pub prompt: String,
     pub project_dir: String,
+    /// Claude config directory of the session's profile; the environment's
+    /// own when None.
+    pub config_dir: Option<String>,
+    /// Variables to pass on top of the runner's own allowlist.
+    pub extra_env: Vec<String>,
+    /// Run in plan permission mode: the agent can read the project but not
+    /// edit files or run commands.
+    pub read_only: bool,
 }
 
 /// Configuration for resuming an existing session.
@@ -23,6 +33,9 @@ pub struct ResumeConfig {
     pub session_id: String,
     pub prompt: String,
     pub project_dir: String,
+    pub config_dir: Option<String>,
+    pub extra_env: Vec<String>,
+    pub read_only: bool,
 }
 
 /// Port: mechanism for running agent processes.
@@ -78,10 +91,12 @@ pub struct UsageUpdateEvent {
     /// Actual cost in USD from Claude Code's result message. Zero for
     /// intermediate updates; set only when the final result arrives.
     pub cost_usd: f64,
+    /// The result reported no cost, so `cost_usd` is estimated from tokens.
+    pub cost_estimated: bool,
 }
 
 /// Domain event: Claude quota/rate-limit exceeded.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct RateLimitedEvent {
     pub session_id: String,
     /// ISO 8601 reset timestamp if parseable from the error message.
@@ -90,12 +105,27 @@ pub struct RateLimitedEvent {
     pub raw_message: String,
 }
 
+/// Domain event: a running session wrote a file another running session in
+/// the same project has also written.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FileConflictEvent {
+    /// Relative to the project when inside it.
+    pub path: String,
+    /// The session that just wrote the file.
+    pub session_id: String,
+    pub agent_name: String,
+    /// The session that wrote it first.
+    pub other_session_id: String,
+    pub other_agent_name: String,
+}
+
 /// Port: mechanism for emitting domain events to external consumers.
 pub trait EventEmitter: Send + Sync {
     fn emit_status_changed(&self, event: StatusChangedEvent) -> Result<(), DomainError>;
     fn emit_agent_message(&self, event: MessageEvent) -> Result<(), DomainError>;
     fn emit_usage_update(&self, event: UsageUpdateEvent) -> Result<(), DomainError>;
     fn emit_rate_limited(&self, event: RateLimitedEvent) -> Result<(), DomainError>;
+    fn emit_file_conflict(&self, event: FileConflictEvent) -> Result<(), DomainError>;
 }
 
 // ---------------------------------------------------------------------------
@@ -105,16 +135,19 @@ pub trait EventEmitter: Send + Sync {
 /// Port: log entry persistence and querying.
 #[async_trait]
 pub trait LogRepository: Send + Sync {
-    /// Buffer a log entry for batch persistence.
+    /// Buffer a log entry for batch persistence. Assistant messages carry
+    /// the token usage of their turn.
     async fn append(
         &self,
         session_id: &str,
         message_type: &str,
         content: &str,
         timestamp: &str,
+        usage: Option<MessageUsage>,
     );
 
-    /// Force-flush buffered entries to durable storage.
+    /// Force-flush buffered entries to durable storage, returning once they
+    /// are written.
     async fn flush(&self);
 
     /// Query log entries for a session with pagination.
@@ -125,9 +158,125 @@ pub trait LogRepository: Send + Sync {
         limit: u32,
     ) -> Result<Vec<LogEntry>, DomainError>;
 
+    /// Entries with an id greater than `after_id`, oldest first. Lets callers
+    /// poll incrementally from the last id they saw instead of by offset.
+    async fn query_logs_after(
+        &self,
+        session_id: &str,
+        after_id: u64,
+        limit: u32,
+    ) -> Result<Vec<LogEntry>, DomainError>;
+
+    /// The last `limit` entries of a session, oldest first.
+    async fn query_logs_tail(
+        &self,
+        session_id: &str,
+        limit: u32,
+    ) -> Result<Vec<LogEntry>, DomainError>;
+
+    /// Entries matching `filter` with an id greater than `after_id`, oldest
+    /// first. The filter is applied in the query, not after loading.
+    async fn query_logs_filtered(
+        &self,
+        session_id: &str,
+        filter: &LogQueryFilter,
+        after_id: u64,
+        limit: u32,
+    ) -> Result<Vec<LogEntry>, DomainError>;
+
+    /// `query_logs_filtered` across every session.
+    async fn query_all_logs_filtered(
+        &self,
+        filter: &LogQueryFilter,
+        after_id: u64,
+        limit: u32,
+    ) -> Result<Vec<LogEntry>, DomainError>;
+
+    /// Counts, sizes and time span of one session's log.
+    async fn session_stats(&self, session_id: &str) -> Result<SessionLogStats, DomainError>;
+
+    /// Storage totals across all sessions, before and after compression.
+    async fn compression_stats(&self) -> Result<LogCompressionStats, DomainError>;
+
     /// Get total log count for a session.
     async fn count_logs(&self, session_id: &str) -> Result<u64, DomainError>;
 
+    /// Record a tool call when its `tool_use` block is first seen. Repeats
+    /// (stream-json resends cumulative messages) are ignored.
+    async fn record_tool_call(
+        &self,
+        session_id: &str,
+        tool_use_id: &str,
+        tool_name: &str,
+        input_summary: &str,
+        started_at: &str,
+    ) -> Result<(), DomainError>;
+
+    /// Mark a tool call finished with its outcome. Only the first result
+    /// for a call counts.
+    async fn finish_tool_call(
+        &self,
+        session_id: &str,
+        tool_use_id: &str,
+        finished_at: &str,
+        success: bool,
+    ) -> Result<(), DomainError>;
+
+    /// Tool calls of a session in the order they were made.
+    async fn query_tool_calls(&self, session_id: &str) -> Result<Vec<ToolCall>, DomainError>;
+
+    /// Record a file touched by a tool call. Repeats of the same call are
+    /// ignored.
+    async fn record_file_change(
+        &self,
+        session_id: &str,
+        tool_use_id: &str,
+        file_path: &str,
+        operation: &str,
+        timestamp: &str,
+    ) -> Result<(), DomainError>;
+
+    /// Store a session's metadata, replacing any earlier copy.
+    async fn record_session(&self, session: &AgentSession) -> Result<(), DomainError>;
+
+    /// Ids of every stored session, including placeholder rows.
+    async fn known_session_ids(&self) -> Result<HashSet<String>, DomainError>;
+
+    /// Finished sessions recorded for `project_dir`, oldest first.
+    async fn list_stored_sessions(
+        &self,
+        project_dir: &str,
+    ) -> Result<Vec<AgentSession>, DomainError>;
+
+    /// Store a session together with its log entries in one transaction,
+    /// bypassing the append buffer.
+    async fn import_session(
+        &self,
+        session: &AgentSession,
+        entries: &[TranscriptEntry],
+    ) -> Result<(), DomainError>;
+
+    /// Entries containing `query` (case-insensitive) in sessions recorded
+    /// for `project_dir`, grouped by session. At most `limit` entries.
+    async fn search_logs(
+        &self,
+        project_dir: &str,
+        query: &str,
+        limit: u32,
+    ) -> Result<Vec<SessionSearchResult>, DomainError>;
+
+    /// Files a session touched, in the order they were changed.
+    async fn query_file_changes(&self, session_id: &str) -> Result<Vec<FileChange>, DomainError>;
+
+    /// Store the working-tree snapshot taken at `point` of a recorded session.
+    async fn record_snapshot(
+        &self,
+        session_id: &str,
+        point: SnapshotPoint,
+        tree: &str,
+    ) -> Result<(), DomainError>;
+
+    async fn get_snapshot(&self, session_id: &str) -> Result<SessionSnapshot, DomainError>;
 }
 
 // ---------------------------------------------------------------------------
@@ -152,7 +301,7 @@ pub trait SessionRepository: Send + Sync {
         input_tokens: u64,
         output_tokens: u64,
     ) -> (u64, u64);
-    async fn update_cost(&self, session_id: &str, cost_usd: f64);
+    async fn update_cost(&self, session_id: &str, cost_usd: f64, estimated: bool);
 }
 
 // ---------------------------------------------------------------------------
@@ -186,8 +335,36 @@ pub trait WorkflowRepository: Send + Sync {
     async fn delete_step(&self, id: &str) -> Result<(), DomainError>;
 
     async fn update_step_result(&self, id: &str, result_output: &str) -> Result<(), DomainError>;
+    /// Atomically move a step from pending to running. Returns false if the
+    /// step was not pending (already started, skipped, ...).
+    async fn claim_step(&self, id: &str) -> Result<bool, DomainError>;
 
     async fn save_edge(&self, edge: &WorkflowEdge) -> Result<(), DomainError>;
     async fn get_edges(&self, workflow_id: &str) -> Result<Vec<WorkflowEdge>, DomainError>;
     async fn delete_edge(&self, id: &str) -> Result<(), DomainError>;
+
+    async fn save_prompt_template(&self, template: &PromptTemplate) -> Result<(), DomainError>;
+    async fn get_prompt_template(&self, id: &str) -> Result<Option<PromptTemplate>, DomainError>;
+    async fn list_prompt_templates(&self) -> Result<Vec<PromptTemplate>, DomainError>;
+    async fn update_prompt_template(&self, template: &PromptTemplate) -> Result<(), DomainError>;
+    async fn delete_prompt_template(&self, id: &str) -> Result<(), DomainError>;
+}
+
+// ---------------------------------------------------------------------------
+// Port: UsageRepository — historical Claude usage
+// ---------------------------------------------------------------------------
+
+#[async_trait]
+pub trait UsageRepository: Send + Sync {
+    /// Store a profile's all-time per-model usage seen on `date` (None for
+    /// the default profile), replacing that day's earlier snapshot.
+    async fn record_usage_snapshot(
+        &self,
+        date: &str,
+        profile: Option<&str>,
+        models: &[ModelUsage],
+    ) -> Result<(), DomainError>;
+
+    /// Snapshots up to and including `until` (all when None), oldest first.
+    async fn usage_snapshots(&self, until: Option<&str>) -> Result<Vec<UsageSnapshot>, DomainError>;
 }
@@ -1,14 +1,31 @@
 use super::error::DomainError;
-use super::models::{AgentSession, AgentStatus};
+use super::models::{
+    AgentSession, AgentStatus, ClaudeProfile, DirtyTreePolicy, MessageUsage, ModelPrice,
+    SnapshotPoint,
+};
 use super::ports::{
-    AgentRunner, EventEmitter, LogRepository, MessageEvent, RateLimitedEvent, ResumeConfig,
-    SessionRepository, SpawnConfig, StatusChangedEvent, UsageUpdateEvent,
+    AgentRunner, EventEmitter, FileConflictEvent, LogRepository, MessageEvent, RateLimitedEvent,
+    ResumeConfig, SessionRepository, SpawnConfig, StatusChangedEvent, UsageUpdateEvent,
 };
+use super::stream_parser::{self, ToolResult, ToolUse};
+use crate::services::{git_service, pricing};
 use chrono::Utc;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+/// Which branch a new session works on.
+enum Branching<'a> {
+    /// A new branch named after the label, or the agent when None, if
+    /// auto-branching is on. Checks for uncommitted changes first.
+    New(Option<&'a str>),
+    /// The branch already checked out, recorded as the session's.
+    Keep(Option<String>),
+}
+
 /// Core domain service for agent session orchestration.
 ///
 /// SessionManager owns the business logic for starting, stopping, resuming agents
@@ -20,6 +37,25 @@ pub struct SessionManager {
     logs: Arc<dyn LogRepository>,
     sessions: Arc<dyn SessionRepository>,
     project_dir: RwLock<Option<String>>,
+    /// Give each new session its own git branch.
+    auto_branch: AtomicBool,
+    /// What to do with uncommitted changes when a session starts.
+    dirty_tree: Mutex<DirtyTreePolicy>,
+    /// Why new sessions are refused, while a spending budget is used up.
+    budget_stop: Mutex<Option<String>>,
+    /// User prices overriding the built-in table, for estimating costs
+    /// Claude Code doesn't report.
+    pricing: Mutex<HashMap<String, ModelPrice>>,
+    /// Claude profiles sessions can run under, and the one they use unless
+    /// started with another; None means Claude Code's own config directory.
+    profiles: Mutex<Vec<ClaudeProfile>>,
+    default_profile: Mutex<Option<String>>,
+    /// Variables the user allowed the project's agents to receive.
+    extra_env: Mutex<Vec<String>>,
+    /// Files written by each running session, from its tool calls.
+    written_files: RwLock<HashMap<String, HashSet<String>>>,
+    /// Sessions started read-only, which stay read-only when resumed.
+    read_only: Mutex<HashSet<String>>,
 }
 
 impl SessionManager {
@@ -34,6 +70,15 @@ impl SessionManager {
             logs,
             sessions,
             project_dir: RwLock::new(None),
+            auto_branch: AtomicBool::new(false),
+            dirty_tree: Mutex::new(DirtyTreePolicy::default()),
+            budget_stop: Mutex::new(None),
+            pricing: Mutex::new(HashMap::new()),
+            profiles: Mutex::new(Vec::new()),
+            default_profile: Mutex::new(None),
+            extra_env: Mutex::new(Vec::new()),
+            written_files: RwLock::new(HashMap::new()),
+            read_only: Mutex::new(HashSet::new()),
         }
     }
 
@@ -50,6 +95,58 @@ impl SessionManager {
         self.project_dir.read().await.clone()
     }
 
+    pub fn set_auto_branch(&self, enabled: bool) {
+        self.auto_branch.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn set_dirty_tree_policy(&self, policy: DirtyTreePolicy) {
+        *self.dirty_tree.lock().unwrap() = policy;
+    }
+
+    /// Refuse new and resumed sessions with `reason` until cleared with None.
+    pub fn set_budget_stop(&self, reason: Option<String>) {
+        *self.budget_stop.lock().unwrap_or_else(|e| e.into_inner()) = reason;
+    }
+
+    fn check_budget_stop(&self) -> Result<(), DomainError> {
+        match self.budget_stop.lock().unwrap_or_else(|e| e.into_inner()).clone() {
+            Some(reason) => Err(DomainError::Process(reason)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn set_pricing(&self, overrides: HashMap<String, ModelPrice>) {
+        *self.pricing.lock().unwrap() = overrides;
+    }
+
+    pub fn set_profiles(&self, profiles: Vec<ClaudeProfile>, default_profile: Option<String>) {
+        *self.profiles.lock().unwrap() = profiles;
+        *self.default_profile.lock().unwrap() = default_profile;
+    }
+
+    pub fn set_extra_env(&self, names: Vec<String>) {
+        *self.extra_env.lock().unwrap() = names;
+    }
+
+    /// Profile new sessions run under unless started with another.
+    pub fn default_profile(&self) -> Option<String> {
+        self.default_profile.lock().unwrap().clone()
+    }
+
+    /// Config directory of the named profile; None for the default one.
+    fn profile_dir(&self, profile: Option<&str>) -> Result<Option<String>, DomainError> {
+        let Some(name) = profile else {
+            return Ok(None);
+        };
+        self.profiles
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| Some(p.config_path().to_string_lossy().to_string()))
+            .ok_or_else(|| DomainError::Process(format!("Unknown Claude profile '{name}'")))
+    }
+
     /// Start a new agent session.
     pub async fn start_agent(
         &self,
@@ -57,14 +154,87 @@ impl SessionManager {
         model: String,
         prompt: String,
     ) -> Result<String, DomainError> {
-        let project_dir = self
-            .project_dir
-            .read()
+        self.start_agent_for(agent_name, model, prompt, None, None)
             .await
-            .clone()
-            .unwrap_or_else(|| ".".to_string());
+    }
+
+    /// Start a new agent session. With auto-branching on, the project first
+    /// switches to a new branch named after `branch_label` (e.g. the spec),
+    /// or the agent when there is none. It runs under `profile`, or the
+    /// default profile when None.
+    pub async fn start_agent_for(
+        &self,
+        agent_name: String,
+        model: String,
+        prompt: String,
+        branch_label: Option<&str>,
+        profile: Option<&str>,
+    ) -> Result<String, DomainError> {
+        let branching = Branching::New(branch_label);
+        self.start_session(agent_name, model, prompt, branching, profile, false)
+            .await
+    }
+
+    /// Start a session that continues a run of several, such as a workflow,
+    /// on the run's `branch` (None when it has none). There is no new branch
+    /// nor dirty-tree check: uncommitted changes are the run's own work.
+    pub async fn start_agent_on_branch(
+        &self,
+        agent_name: String,
+        model: String,
+        prompt: String,
+        branch: Option<String>,
+    ) -> Result<String, DomainError> {
+        let branching = Branching::Keep(branch);
+        self.start_session(agent_name, model, prompt, branching, None, false)
+            .await
+    }
+
+    /// Start a session in plan permission mode, which can read the project
+    /// but not change it. It works on the current tree as is: there is no
+    /// branching nor dirty-tree check.
+    pub async fn start_read_only_agent(
+        &self,
+        agent_name: String,
+        model: String,
+        prompt: String,
+    ) -> Result<String, DomainError> {
+        let branching = Branching::Keep(None);
+        self.start_session(agent_name, model, prompt, branching, None, true)
+            .await
+    }
+
+    async fn start_session(
+        &self,
+        agent_name: String,
+        model: String,
+        prompt: String,
+        branching: Branching<'_>,
+        profile: Option<&str>,
+        read_only: bool,
+    ) -> Result<String, DomainError> {
+        self.check_budget_stop()?;
+        let profile = profile.map(String::from).or_else(|| self.default_profile());
+        let config_dir = self.profile_dir(profile.as_deref())?;
+        let project = self.project_dir.read().await.clone();
+        let project_dir = project.clone().unwrap_or_else(|| ".".to_string());
 
         let session_id = Uuid::new_v4().to_string();
+        // Before branching, which would carry the changes along
+        let dirty_note = match (&project, &branching) {
+            (Some(dir), Branching::New(_)) => {
+                self.check_dirty_tree(dir, &agent_name, &session_id).await?
+            }
+            _ => None,
+        };
+        let branch = match (&project, branching) {
+            (Some(dir), Branching::New(label)) if self.auto_branch.load(Ordering::Relaxed) => {
+                let label = label.unwrap_or(&agent_name);
+                self.create_session_branch(dir, label, &session_id).await
+            }
+            (_, Branching::Keep(branch)) => branch,
+            _ => None,
+        };
 
         let session = AgentSession {
             id: session_id.clone(),
@@ -77,10 +247,25 @@ impl SessionManager {
             input_tokens: 0,
             output_tokens: 0,
             cost_usd: 0.0,
+            project_dir: project,
+            branch,
+            cost_estimated: false,
+            profile,
         };
 
         // Persist session state
         self.sessions.save(&session).await;
+        if let Err(e) = self.logs.record_session(&session).await {
+            eprintln!("Failed to record session {session_id}: {e}");
+        }
+        if let Some(dir) = &session.project_dir {
+            self.record_snapshot(&session_id, dir, SnapshotPoint::Start)
+                .await;
+        }
+        if let Some(note) = dirty_note {
+            self.on_agent_message(&session_id, "system", &note, &Utc::now().to_rfc3339(), None)
+                .await;
+        }
 
         // Emit starting status
         let _ = self.emitter.emit_status_changed(StatusChangedEvent {
@@ -105,12 +290,114 @@ impl SessionManager {
                 model,
                 prompt,
                 project_dir,
+                config_dir,
+                extra_env: self.extra_env.lock().unwrap().clone(),
+                read_only,
             })
             .await?;
+        if read_only {
+            self.read_only.lock().unwrap().insert(session_id.clone());
+        }
 
         Ok(session_id)
     }
 
+    /// Apply the project's dirty-tree policy before a session starts. Returns
+    /// a note for the session's log when the changes were warned about or
+    /// stashed; errors when the policy blocks the start. Skipped while other
+    /// agents are working in the project, whose changes these would be, and
+    /// for projects that aren't git repositories.
+    async fn check_dirty_tree(
+        &self,
+        project_dir: &str,
+        agent_name: &str,
+        session_id: &str,
+    ) -> Result<Option<String>, DomainError> {
+        let policy = *self.dirty_tree.lock().unwrap();
+        if policy == DirtyTreePolicy::Allow || self.project_busy(project_dir).await {
+            return Ok(None);
+        }
+        let Ok(count) = git_service::uncommitted_count(project_dir) else {
+            return Ok(None);
+        };
+        if count == 0 {
+            return Ok(None);
+        }
+        let files = format!("{count} file{}", if count == 1 { "" } else { "s" });
+        match policy {
+            DirtyTreePolicy::Allow => Ok(None),
+            DirtyTreePolicy::Warn => Ok(Some(format!(
+                "Started with uncommitted changes to {files}"
+            ))),
+            DirtyTreePolicy::Block => Err(DomainError::Process(format!(
+                "Working tree has uncommitted changes to {files}; commit or stash them first"
+            ))),
+            DirtyTreePolicy::Stash => {
+                let short_id = session_id.get(..8).unwrap_or(session_id);
+                let message = format!("clautron: before {agent_name} session {short_id}");
+                match git_service::stash_all(project_dir, &message) {
+                    Ok(Some(hash)) => Ok(Some(format!(
+                        "Stashed uncommitted changes to {files} as {hash} (\"{message}\")"
+                    ))),
+                    Ok(None) => Ok(None),
+                    Err(e) => Err(DomainError::Process(format!(
+                        "Failed to stash uncommitted changes: {e}"
+                    ))),
+                }
+            }
+        }
+    }
+
+    /// Switch the project to a new branch for a session. Skipped while other
+    /// agents are working in the project, since they would switch with it.
+    async fn create_session_branch(
+        &self,
+        project_dir: &str,
+        label: &str,
+        session_id: &str,
+    ) -> Option<String> {
+        if self.project_busy(project_dir).await {
+            eprintln!("Not branching for session {session_id}: other agents are running");
+            return None;
+        }
+        let name = git_service::session_branch_name(label, session_id);
+        match git_service::create_branch(project_dir, &name, true) {
+            Ok(()) => Some(name),
+            Err(e) => {
+                eprintln!("Failed to create branch {name}: {e}");
+                None
+            }
+        }
+    }
+
+    /// Whether any agent is starting or running in `project_dir`.
+    async fn project_busy(&self, project_dir: &str) -> bool {
+        self.sessions.list().await.iter().any(|s| {
+            matches!(s.status, AgentStatus::Starting | AgentStatus::Running)
+                && s.project_dir.as_deref() == Some(project_dir)
+        })
+    }
+
+    /// Snapshot the working tree as a git tree, so the session's own changes
+    /// can later be told apart from what was there before it started.
+    async fn record_snapshot(&self, session_id: &str, project_dir: &str, point: SnapshotPoint) {
+        // Hashes every changed file, which can take a while on big trees
+        let dir = project_dir.to_string();
+        let tree = match tokio::task::spawn_blocking(move || git_service::snapshot_tree(&dir))
+            .await
+            .unwrap_or_else(|e| Err(e.to_string()))
+        {
+            Ok(tree) => tree,
+            Err(e) => {
+                eprintln!("No working-tree snapshot for session {session_id}: {e}");
+                return;
+            }
+        };
+        if let Err(e) = self.logs.record_snapshot(session_id, point, &tree).await {
+            eprintln!("Failed to record snapshot for session {session_id}: {e}");
+        }
+    }
+
     /// Stop a running agent by session ID.
     pub async fn stop_agent(&self, session_id: &str) -> Result<(), DomainError> {
         let session = self
@@ -125,6 +412,7 @@ impl SessionManager {
             .ok_or_else(|| DomainError::Process("AgentRunner not initialized".into()))?;
 
         runner.kill(session_id).await?;
+        self.written_files.write().await.remove(session_id);
 
         let ended_at = Utc::now().to_rfc3339();
         self.sessions
@@ -149,6 +437,8 @@ impl SessionManager {
         session_id: String,
         prompt: String,
     ) -> Result<String, DomainError> {
+        // Otherwise the hard stop could be dodged by resuming old sessions
+        self.check_budget_stop()?;
         let project_dir = self
             .project_dir
             .read()
@@ -161,6 +451,7 @@ impl SessionManager {
             .get(&session_id)
             .await
             .ok_or_else(|| DomainError::SessionNotFound(session_id.clone()))?;
+        let config_dir = self.profile_dir(session.profile.as_deref())?;
 
         // Update status to running
         self.sessions
@@ -187,6 +478,9 @@ impl SessionManager {
                 session_id: session_id.clone(),
                 prompt,
                 project_dir,
+                config_dir,
+                extra_env: self.extra_env.lock().unwrap().clone(),
+                read_only: self.read_only.lock().unwrap().contains(&session_id),
             })
             .await?;
 
@@ -201,6 +495,16 @@ impl SessionManager {
         self.sessions.get(session_id).await
     }
 
+    /// Add stored sessions (e.g. imported transcripts) to the session list.
+    /// Sessions already listed keep their live state.
+    pub async fn restore_sessions(&self, sessions: Vec<AgentSession>) {
+        for session in sessions {
+            if self.sessions.get(&session.id).await.is_none() {
+                self.sessions.save(&session).await;
+            }
+        }
+    }
+
     /// Graceful shutdown: kill all running agents and flush logs.
     pub async fn shutdown_all(&self) {
         let runner = self.runner.read().await;
@@ -253,6 +557,7 @@ impl SessionManager {
         msg_type: &str,
         content: &str,
         timestamp: &str,
+        usage: Option<MessageUsage>,
     ) {
         // Emit to frontend
         let _ = self.emitter.emit_agent_message(MessageEvent {
@@ -264,10 +569,98 @@ impl SessionManager {
 
         // Persist to log store
         self.logs
-            .append(session_id, msg_type, content, timestamp)
+            .append(session_id, msg_type, content, timestamp, usage)
             .await;
     }
 
+    /// Called for each `tool_use` block seen in the agent's output.
+    pub async fn on_tool_use(&self, session_id: &str, tool_use: &ToolUse, timestamp: &str) {
+        let summary = stream_parser::summarize_tool_input(&tool_use.input);
+        if let Err(e) = self
+            .logs
+            .record_tool_call(
+                session_id,
+                &tool_use.id,
+                &tool_use.name,
+                &summary,
+                timestamp,
+            )
+            .await
+        {
+            eprintln!("Failed to record tool call {}: {e}", tool_use.id);
+        }
+
+        if let Some((path, operation)) = stream_parser::file_change(tool_use) {
+            // Stored relative to the project so it lines up with git paths
+            let project_dir = self.get_project_dir().await;
+            let path = project_dir
+                .as_deref()
+                .and_then(|dir| Path::new(path).strip_prefix(dir).ok())
+                .map_or(path.to_string(), |rel| rel.to_string_lossy().into_owned());
+            if let Err(e) = self
+                .logs
+                .record_file_change(session_id, &tool_use.id, &path, operation, timestamp)
+                .await
+            {
+                eprintln!("Failed to record file change {path}: {e}");
+            }
+            self.check_file_conflict(session_id, &path).await;
+        }
+    }
+
+    /// Remember that a session wrote `path`. The first time it writes a file
+    /// another running session in the same project has written, report it.
+    async fn check_file_conflict(&self, session_id: &str, path: &str) {
+        let others: Vec<String> = {
+            let mut written = self.written_files.write().await;
+            if !written
+                .entry(session_id.to_string())
+                .or_default()
+                .insert(path.to_string())
+            {
+                return;
+            }
+            written
+                .iter()
+                .filter(|(id, paths)| id.as_str() != session_id && paths.contains(path))
+                .map(|(id, _)| id.clone())
+                .collect()
+        };
+        if others.is_empty() {
+            return;
+        }
+        let Some(session) = self.sessions.get(session_id).await else {
+            return;
+        };
+        for other_id in others {
+            let Some(other) = self.sessions.get(&other_id).await else {
+                continue;
+            };
+            let running = matches!(other.status, AgentStatus::Starting | AgentStatus::Running);
+            if !running || other.project_dir != session.project_dir {
+                continue;
+            }
+            let _ = self.emitter.emit_file_conflict(FileConflictEvent {
+                path: path.to_string(),
+                session_id: session_id.to_string(),
+                agent_name: session.agent_name.clone(),
+                other_session_id: other.id,
+                other_agent_name: other.agent_name,
+            });
+        }
+    }
+
+    /// Called for each `tool_result` block seen in the agent's output.
+    pub async fn on_tool_result(&self, session_id: &str, result: &ToolResult, timestamp: &str) {
+        if let Err(e) = self
+            .logs
+            .finish_tool_call(session_id, &result.tool_use_id, timestamp, !result.is_error)
+            .await
+        {
+            eprintln!("Failed to record tool result {}: {e}", result.tool_use_id);
+        }
+    }
+
     /// Called when token usage is extracted from an intermediate assistant message.
     pub async fn on_agent_usage(
         &self,
@@ -285,12 +678,39 @@ impl SessionManager {
             input_tokens: total_in,
             output_tokens: total_out,
             cost_usd: 0.0,
+            cost_estimated: false,
         });
     }
 
     /// Called with the authoritative cost from the final Result message.
     pub async fn on_agent_cost(&self, session_id: &str, cost_usd: f64) {
-        self.sessions.update_cost(session_id, cost_usd).await;
+        self.set_cost(session_id, cost_usd, false).await;
+    }
+
+    /// Called when the final Result message reports no cost, as in API-key
+    /// mode: the cost is estimated from the session's tokens instead.
+    pub async fn on_agent_cost_unreported(&self, session_id: &str) {
+        let Some(session) = self.sessions.get(session_id).await else {
+            return;
+        };
+        let cost_usd = {
+            let overrides = self.pricing.lock().unwrap();
+            pricing::estimate_cost(
+                &session.model,
+                session.input_tokens,
+                session.output_tokens,
+                &overrides,
+            )
+        };
+        if cost_usd > 0.0 {
+            self.set_cost(session_id, cost_usd, true).await;
+        }
+    }
+
+    async fn set_cost(&self, session_id: &str, cost_usd: f64, estimated: bool) {
+        self.sessions
+            .update_cost(session_id, cost_usd, estimated)
+            .await;
 
         if let Some(session) = self.sessions.get(session_id).await {
             let _ = self.emitter.emit_usage_update(UsageUpdateEvent {
@@ -298,6 +718,7 @@ impl SessionManager {
                 input_tokens: session.input_tokens,
                 output_tokens: session.output_tokens,
                 cost_usd,
+                cost_estimated: estimated,
             });
         }
     }
@@ -318,6 +739,7 @@ impl SessionManager {
 
     /// Called when the agent process finishes (success, error, or stopped).
     pub async fn on_agent_finished(&self, session_id: &str, status: AgentStatus) {
+        self.written_files.write().await.remove(session_id);
         let ended_at = Utc::now().to_rfc3339();
         self.sessions
             .update_status(session_id, status.clone(), Some(ended_at.clone()))
@@ -326,6 +748,13 @@ impl SessionManager {
         self.logs.flush().await;
 
         if let Some(session) = self.sessions.get(session_id).await {
+            if let Err(e) = self.logs.record_session(&session).await {
+                eprintln!("Failed to record session {session_id}: {e}");
+            }
+            if let Some(dir) = &session.project_dir {
+                self.record_snapshot(session_id, dir, SnapshotPoint::End)
+                    .await;
+            }
             let _ = self.emitter.emit_status_changed(StatusChangedEvent {
                 session_id: session_id.to_string(),
                 agent_name: session.agent_name,
@@ -1,3 +1,5 @@
+use crate::domain::models::SpecConflict;
+use crate::services::spec_manager::SpecWriteError;
 use serde::Serialize;
 
 #[derive(Debug, thiserror::Error)]
@@ -11,6 +13,9 @@ pub enum AppError {
     #[error("Agent not found: {0}")]
     AgentNotFound(String),
 
+    #[error("Agent not approved: {0}")]
+    AgentNotApproved(String),
+
     #[error("Database error: {0}")]
     Database(String),
 
@@ -19,6 +24,19 @@ pub enum AppError {
 
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+
+    #[error("Spec was modified on disk since it was read: {}", .0.file_path)]
+    SpecConflict(Box<SpecConflict>),
+}
+
+/// Conflicts are sent as an object carrying both versions so the UI can
+/// offer to reload or overwrite; every other error is a plain string.
+#[derive(Serialize)]
+struct SpecConflictPayload<'a> {
+    kind: &'static str,
+    message: String,
+    #[serde(flatten)]
+    conflict: &'a SpecConflict,
 }
 
 impl Serialize for AppError {
@@ -26,7 +44,24 @@ impl Serialize for AppError {
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        match self {
+            AppError::SpecConflict(conflict) => SpecConflictPayload {
+                kind: "spec_conflict",
+                message: self.to_string(),
+                conflict,
+            }
+            .serialize(serializer),
+            _ => serializer.serialize_str(&self.to_string()),
+        }
+    }
+}
+
+impl From<SpecWriteError> for AppError {
+    fn from(e: SpecWriteError) -> Self {
+        match e {
+            SpecWriteError::Conflict(conflict) => AppError::SpecConflict(conflict),
+            SpecWriteError::Other(e) => AppError::Process(e),
+        }
     }
 }
 
@@ -6,31 +6,38 @@ mod error;
 use adapters::claude_cli_runner::ClaudeCliRunner;
 use adapters::in_memory_session_repository::InMemorySessionRepository;
 use adapters::sqlite_log_repository::SqliteLogRepository;
+use adapters::sqlite_maintenance::SqliteMaintenance;
+use adapters::sqlite_usage_repository::SqliteUsageRepository;
 use adapters::tauri_event_emitter::TauriEventEmitter;
+use adapters::webhook_event_emitter::WebhookEventEmitter;
 use adapters::sqlite_workflow_repository::SqliteWorkflowRepository;
-use commands::{agent_commands, config_commands, log_commands, quota_commands, review_commands, spec_commands, workflow_commands};
+use commands::http_api::HttpApi;
+use commands::{agent_commands, config_commands, db_commands, http_api, log_commands, quota_commands, review_commands, spec_commands, workflow_commands};
 use domain::ports::WorkflowRepository;
 use services::quota_service::{QuotaState, start_poller};
 use services::workflow_engine::WorkflowEngine;
+use services::workflow_templates::WorkflowTemplateStore;
 use domain::ports::LogRepository;
 use domain::session_manager::SessionManager;
+use services::agent_approvals::AgentApprovalStore;
 use services::agent_manager::AgentManager;
+use services::agent_versions::AgentVersionStore;
 use services::agent_watcher;
+use services::config_watcher;
 use services::config_store::ConfigStore;
+use services::log_mirror::LogMirror;
+use services::notification_rules;
+use services::linear_sync::{start_sync_task, LinearSync};
+use services::spec_autocomplete::{emit_status_changed, start_autocomplete_task, try_complete};
 use services::spec_manager::SpecManager;
+use services::spec_reminders::start_reminder_task;
 use std::sync::Arc;
-use tauri::{Emitter, Listener, Manager};
+use tauri::{Listener, Manager};
 use tokio::sync::RwLock;
 
 // Keep services module for config_store and agent_watcher (no trait needed)
 mod services;
 
-#[derive(Clone, serde::Serialize)]
-struct SpecStatusChangedPayload {
-    file_path: String,
-    status: String,
-}
-
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Set up app data directory
@@ -50,16 +57,45 @@ pub fn run() {
 
     // --- Adapter construction ---
 
+    // Config store (JSON) — no trait, concrete type
+    let config_store = Arc::new(ConfigStore::new());
+    let mut config = config_store.load();
+    // Team settings committed to the project override the user's own
+    config.reload_project_file();
+
+    // Optional JSONL copy of session logs, toggled per project
+    let log_mirror = Arc::new(LogMirror::new(data_dir.join("logs")));
+    log_mirror.set_enabled(config.project_settings().mirror_logs);
+    let auto_branch = config.project_settings().auto_branch;
+    let dirty_tree = config.project_settings().dirty_tree;
+    let pricing = config.pricing.clone();
+    let profiles = config.profiles.clone();
+    let default_profile = config.project_settings().profile;
+    let extra_env = config.project_settings().extra_env;
+    let webhook_config = config.integrations.webhook.clone();
+    let hold_on_conflict = config.project_settings().hold_on_conflict;
+    if let Err(e) = domain::stream_parser::set_redaction(&config.redaction()) {
+        eprintln!("Redaction patterns not applied: {e}");
+    }
+
     // Log repository (SQLite)
     let db_file = data_dir.join("data.db").to_string_lossy().to_string();
-    let log_repo = Arc::new(SqliteLogRepository::new(db_file));
+    let log_repo =
+        Arc::new(SqliteLogRepository::new(db_file).with_mirror(Arc::clone(&log_mirror)));
 
     // Session repository (in-memory)
     let session_repo = Arc::new(InMemorySessionRepository::new());
 
-    // Config store (JSON) — no trait, concrete type
-    let config_store = Arc::new(ConfigStore::new());
-    let config = config_store.load();
+    // Agent approvals, per project. Older configs kept them in one global map.
+    let agent_approvals = Arc::new(AgentApprovalStore::new(data_dir.clone()));
+    if !config.approved_agent_hashes.is_empty() {
+        let migrated = agent_approvals
+            .migrate_legacy(&config.approved_agent_hashes, config.project_path.as_deref());
+        if migrated.is_ok() {
+            config.approved_agent_hashes.clear();
+            let _ = config_store.save(&config);
+        }
+    }
 
     // --- Domain service construction ---
     // EventEmitter needs AppHandle, which is only available in setup().
@@ -71,12 +107,21 @@ pub fn run() {
     let log_repo_for_setup = Arc::clone(&log_repo);
     let log_repo_for_state: Arc<dyn LogRepository> = Arc::clone(&log_repo) as Arc<dyn LogRepository>;
     let log_repo_for_engine: Arc<dyn LogRepository> = Arc::clone(&log_repo) as Arc<dyn LogRepository>;
+    let log_repo_for_listener: Arc<dyn LogRepository> = Arc::clone(&log_repo) as Arc<dyn LogRepository>;
     let session_repo_for_state = Arc::clone(&session_repo);
 
-    // Restore project dir from saved config
+    // Restore project dir from saved config. Configs from before the known
+    // projects list get an entry for it here.
     let project_path_for_setup = config.project_path.clone();
+    if let Some(ref path) = project_path_for_setup {
+        if !config.projects.contains_key(path) {
+            config.open_project(path);
+            let _ = config_store.save(&config);
+        }
+    }
 
     let config_state: config_commands::ConfigState = Arc::new(RwLock::new(config));
+    let config_state_for_setup = Arc::clone(&config_state);
 
     // Spec manager
     let spec_manager = Arc::new(SpecManager::new());
@@ -91,7 +136,10 @@ pub fn run() {
     let spec_manager_for_state = Arc::clone(&spec_manager);
 
     // Agent manager
-    let agent_manager = Arc::new(AgentManager::new(Arc::clone(&config_store)));
+    let agent_manager = Arc::new(AgentManager::new(
+        Arc::clone(&agent_approvals),
+        AgentVersionStore::new(data_dir.join("agent-versions")),
+    ));
     if let Some(ref path) = project_path_for_setup {
         let am = Arc::clone(&agent_manager);
         let path = path.clone();
@@ -102,10 +150,24 @@ pub fn run() {
 
     // Workflow repository (SQLite)
     let workflow_db_path = data_dir.join("data.db").to_string_lossy().to_string();
-    let workflow_repo: Arc<dyn WorkflowRepository> =
-        Arc::new(SqliteWorkflowRepository::new(workflow_db_path));
+    let sqlite_workflow_repo = Arc::new(SqliteWorkflowRepository::new(workflow_db_path));
+    let workflow_repo_for_setup = Arc::clone(&sqlite_workflow_repo);
+    let workflow_repo: Arc<dyn WorkflowRepository> = sqlite_workflow_repo;
     let workflow_repo_for_state = Arc::clone(&workflow_repo);
 
+    // Daily usage snapshots (SQLite), migrated by the log repository
+    let usage_repo = Arc::new(SqliteUsageRepository::new(
+        data_dir.join("data.db").to_string_lossy().to_string(),
+    ));
+
+    // Vacuum / integrity checks on the shared database file
+    let db_maintenance = Arc::new(SqliteMaintenance::new(
+        data_dir.join("data.db").to_string_lossy().to_string(),
+    ));
+
+    // Workflow templates (JSON files shared across projects)
+    let workflow_templates = Arc::new(WorkflowTemplateStore::new(data_dir.join("templates")));
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
@@ -117,10 +179,17 @@ pub fn run() {
 
             // EventEmitter adapter (needs AppHandle)
             let emitter = Arc::new(TauriEventEmitter::new(app_handle.clone()));
+            emitter.start_batch_task();
+
+            // Also forwards events to the configured webhook
+            let webhook = Arc::new(WebhookEventEmitter::new(emitter));
+            webhook.set_config(webhook_config);
+            webhook.start_batch_task();
+            app.manage(Arc::clone(&webhook));
 
             // SessionManager (domain core)
             let session_manager = Arc::new(SessionManager::new(
-                emitter,
+                webhook,
                 log_repo_for_state,
                 session_repo_for_state,
             ));
@@ -135,6 +204,11 @@ pub fn run() {
             });
 
             // Restore project dir from saved config
+            session_manager.set_auto_branch(auto_branch);
+            session_manager.set_dirty_tree_policy(dirty_tree);
+            session_manager.set_pricing(pricing);
+            session_manager.set_profiles(profiles, default_profile);
+            session_manager.set_extra_env(extra_env);
             if let Some(ref project_path) = project_path_for_setup {
                 let sm = Arc::clone(&session_manager);
                 let path = project_path.clone();
@@ -143,16 +217,20 @@ pub fn run() {
                 });
             }
 
-            // Initialize SQLite and start periodic flush
+            // Migrate SQLite and start periodic flush
             let lr = Arc::clone(&log_repo_for_setup);
+            let wr = Arc::clone(&workflow_repo_for_setup);
             tauri::async_runtime::spawn(async move {
                 if let Err(e) = lr.init().await {
                     eprintln!("Failed to init log store: {e}");
                 }
+                if let Err(e) = wr.init().await {
+                    eprintln!("Failed to init workflow store: {e}");
+                }
                 lr.start_flush_task();
             });
 
-            // Start FS watchers for .claude/agents/ and specs/ if project is configured
+            // Start FS watchers for .claude/agents/, .claude/commands/, specs/ and the working tree if project is configured
             let sm_for_watcher = Arc::clone(&session_manager);
             let app_handle_for_watcher = app_handle.clone();
             tauri::async_runtime::spawn(async move {
@@ -165,41 +243,138 @@ pub fn run() {
                         std::mem::forget(watcher);
                     }
 
+                    let commands_dir = crate::services::slash_commands::commands_dir(&project_dir);
+                    if let Some(watcher) = crate::services::command_watcher::start_watching(
+                        app_handle_for_watcher.clone(),
+                        commands_dir,
+                    ) {
+                        std::mem::forget(watcher);
+                    }
+
                     let specs_dir =
                         std::path::PathBuf::from(&project_dir).join("specs");
                     if let Some(watcher) =
-                        crate::services::spec_watcher::start_watching(app_handle_for_watcher, specs_dir)
+                        crate::services::spec_watcher::start_watching(app_handle_for_watcher.clone(), specs_dir)
                     {
                         std::mem::forget(watcher);
                     }
+
+                    if let Some(watcher) = crate::services::git_watcher::start_watching(
+                        app_handle_for_watcher,
+                        std::path::PathBuf::from(&project_dir),
+                    ) {
+                        std::mem::forget(watcher);
+                    }
                 }
             });
 
-            // Workflow engine (needs session_manager + repo + logs)
+            // Workflow engine (needs session_manager + repo + logs + specs)
             let workflow_engine = Arc::new(WorkflowEngine::new(
                 Arc::clone(&workflow_repo),
                 Arc::clone(&session_manager),
                 log_repo_for_engine,
+                Arc::clone(&spec_manager),
             ));
+            workflow_engine.set_hold_on_conflict(hold_on_conflict);
+            let we = Arc::clone(&workflow_engine);
+            tauri::async_runtime::spawn(async move {
+                we.start_gate_listener();
+            });
+            let we = Arc::clone(&workflow_engine);
+            app.listen("conflict:detected", move |event| {
+                if let Ok(conflict) = serde_json::from_str::<domain::ports::FileConflictEvent>(event.payload()) {
+                    let we = Arc::clone(&we);
+                    tauri::async_runtime::spawn(async move {
+                        we.on_file_conflict(&conflict).await;
+                    });
+                }
+            });
             app.manage(workflow_engine);
 
-            // Quota poller (reads ~/.claude/.credentials.json, polls Anthropic OAuth endpoint)
-            let quota_state = Arc::new(QuotaState::new());
+            // Quota poller (reads ~/.claude/stats-cache.json, records daily
+            // usage snapshots, enforces spending budgets)
+            let quota_state = Arc::new(QuotaState::new(
+                usage_repo,
+                Arc::clone(&config_state_for_setup),
+                Arc::clone(&session_manager),
+            ));
             app.manage(Arc::clone(&quota_state));
+            let qs = Arc::clone(&quota_state);
+            let app_for_limits = app_handle.clone();
+            app.listen("agent:rate-limited", move |event| {
+                if let Ok(limited) = serde_json::from_str::<domain::ports::RateLimitedEvent>(event.payload()) {
+                    let qs = Arc::clone(&qs);
+                    let app_h = app_for_limits.clone();
+                    tauri::async_runtime::spawn(async move {
+                        qs.on_rate_limited(&app_h, &limited).await;
+                    });
+                }
+            });
             start_poller(app_handle.clone(), quota_state);
 
+            // Linear status sync (no-op until an API key is configured)
+            let linear_sync = Arc::new(LinearSync::new());
+            app.manage(Arc::clone(&linear_sync));
+            start_sync_task(
+                app_handle.clone(),
+                linear_sync,
+                Arc::clone(&config_state_for_setup),
+                Arc::clone(&spec_manager),
+            );
+
+            // Due-date reminders for open specs
+            start_reminder_task(
+                app_handle.clone(),
+                Arc::clone(&spec_manager),
+                Arc::clone(&config_state_for_setup),
+            );
+
+            // Specs in review finish once their criteria are checked or their branch merges
+            start_autocomplete_task(app_handle.clone(), Arc::clone(&spec_manager));
+
             // Register SessionManager as managed state
+            let sessions_for_events = Arc::clone(&session_manager);
             app.manage(session_manager);
 
+            // Desktop notifications for agent events, per the user's rules
+            notification_rules::start_listening(&app_handle, Arc::clone(&config_state_for_setup));
+
+            // Local HTTP API, when enabled
+            let api_config = Arc::clone(&config_state_for_setup);
+            let app_for_api = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let config = api_config.read().await.http_api.clone();
+                let api = app_for_api.state::<Arc<HttpApi>>();
+                if let Err(e) = api.apply(&app_for_api, &config).await {
+                    eprintln!("HTTP API not started: {e}");
+                }
+            });
+
+            // Apply edits made to config.json outside the app
+            let (config_tx, mut config_rx) = tokio::sync::mpsc::unbounded_channel();
+            let config_path = app.state::<Arc<ConfigStore>>().path().to_path_buf();
+            if let Some(watcher) = config_watcher::start_watching(config_path, config_tx) {
+                std::mem::forget(watcher);
+            }
+            let app_for_config = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                while config_rx.recv().await.is_some() {
+                    config_commands::reload_config(&app_for_config).await;
+                }
+            });
+
             // Lifecycle listeners: update specs and advance workflows on agent status changes
             let spec_mgr = Arc::clone(&spec_manager);
             let wf_engine = app.state::<Arc<WorkflowEngine>>().inner().clone();
             let app_for_events = app.handle().clone();
+            let logs_for_events = Arc::clone(&log_repo_for_listener);
             app.listen("agent:status-changed", move |event| {
                 if let Ok(status_event) = serde_json::from_str::<domain::ports::StatusChangedEvent>(event.payload()) {
                     let sm = Arc::clone(&spec_mgr);
                     let we = Arc::clone(&wf_engine);
                     let app_h = app_for_events.clone();
+                    let logs = Arc::clone(&logs_for_events);
+                    let sessions = Arc::clone(&sessions_for_events);
                     tauri::async_runtime::spawn(async move {
                         // Spec lifecycle
                         let spec_change = match status_event.status {
@@ -207,18 +382,75 @@ pub fn run() {
                                 sm.on_agent_started(&status_event.session_id).await
                             }
                             domain::models::AgentStatus::Completed => {
-                                sm.on_agent_completed(&status_event.session_id).await
+                                let running: Vec<String> = sessions
+                                    .list_sessions()
+                                    .await
+                                    .into_iter()
+                                    .filter(|s| {
+                                        matches!(
+                                            s.status,
+                                            domain::models::AgentStatus::Starting
+                                                | domain::models::AgentStatus::Running
+                                        )
+                                    })
+                                    .map(|s| s.id)
+                                    .collect();
+                                sm.on_agent_completed(&status_event.session_id, &running).await
                             }
                             _ => None,
                         };
                         if let Some(spec) = spec_change {
-                            let _ = app_h.emit("spec:status-changed", SpecStatusChangedPayload {
-                                file_path: spec.file_path,
-                                status: spec.status.to_string(),
-                            });
+                            // Parents follow their children (e.g. draft -> in_progress)
+                            let mut changed = sm.rollup_parents(&spec).await;
+                            changed.insert(0, spec.clone());
+                            emit_status_changed(&app_h, changed);
+                            try_complete(&app_h, &sm, &spec).await;
+                        }
+
+                        // Session history: keep the final usage of runs bound to a spec
+                        let ended = matches!(
+                            status_event.status,
+                            domain::models::AgentStatus::Completed
+                                | domain::models::AgentStatus::Error
+                                | domain::models::AgentStatus::Stopped
+                        );
+                        if ended {
+                            if let Some(mut session) = sessions.get_session(&status_event.session_id).await {
+                                session.status = status_event.status.clone();
+                                session.ended_at = session.ended_at.or(status_event.ended_at.clone());
+                                sm.record_session_end(&session).await;
+                            }
+                        }
+
+                        // Spec verification: apply the agent's per-criterion report
+                        let finished = matches!(
+                            status_event.status,
+                            domain::models::AgentStatus::Completed | domain::models::AgentStatus::Error
+                        );
+                        if finished {
+                            if let Some(spec) = sm.find_spec_by_verification(&status_event.session_id).await {
+                                let mut output = None;
+                                if status_event.status == domain::models::AgentStatus::Completed {
+                                    logs.flush().await;
+                                    if let Ok(entries) = logs.query_logs_tail(&status_event.session_id, 1000).await {
+                                        output = services::workflow_engine::extract_result_text(&entries);
+                                    }
+                                }
+                                match sm.finish_verification(&spec, output.as_deref()).await {
+                                    Ok(verified) => {
+                                        try_complete(&app_h, &sm, &verified).await;
+                                    }
+                                    Err(e) => {
+                                        eprintln!("Failed to record verification for {}: {e}", spec.file_path);
+                                    }
+                                }
+                            }
                         }
 
                         // Workflow lifecycle
+                        if ended {
+                            we.release_held(&status_event.session_id).await;
+                        }
                         match status_event.status {
                             domain::models::AgentStatus::Completed => {
                                 we.on_agent_completed(&status_event.session_id).await;
@@ -236,17 +468,25 @@ pub fn run() {
         })
         .manage(log_repo as Arc<dyn LogRepository>)
         .manage(config_store)
+        .manage(log_mirror)
+        .manage(agent_approvals)
         .manage(config_state)
         .manage(spec_manager_for_state)
         .manage(agent_manager)
         .manage(workflow_repo_for_state)
+        .manage(db_maintenance)
+        .manage(workflow_templates)
+        .manage(Arc::new(HttpApi::new()))
         .invoke_handler(tauri::generate_handler![
             agent_commands::start_agent,
             agent_commands::stop_agent,
             agent_commands::resume_agent,
             agent_commands::list_sessions,
             agent_commands::get_session,
+            agent_commands::get_agent_stats,
+            agent_commands::test_agent,
             agent_commands::list_agents,
+            agent_commands::validate_agents,
             agent_commands::set_project_dir,
             agent_commands::get_project_dir,
             agent_commands::check_claude_auth,
@@ -255,22 +495,100 @@ pub fn run() {
             agent_commands::create_agent_config,
             agent_commands::update_agent_config,
             agent_commands::delete_agent_config,
+            agent_commands::get_agent_versions,
+            agent_commands::restore_agent_version,
+            agent_commands::list_slash_commands,
+            agent_commands::get_slash_command,
+            agent_commands::create_slash_command,
+            agent_commands::update_slash_command,
+            agent_commands::delete_slash_command,
+            agent_commands::list_skills,
+            agent_commands::list_agent_templates,
+            agent_commands::install_agent_template,
             agent_commands::get_agent_relationships,
             agent_commands::generate_text,
+            agent_commands::generate_agent,
             log_commands::get_session_logs,
+            log_commands::get_session_logs_after,
+            log_commands::get_session_logs_tail,
+            log_commands::get_session_logs_filtered,
             log_commands::get_session_log_count,
+            log_commands::get_log_compression_stats,
+            log_commands::get_session_tool_calls,
+            log_commands::get_session_cost_breakdown,
+            log_commands::get_cost_breakdown,
+            log_commands::export_usage,
+            log_commands::estimate_cost,
+            log_commands::get_error_digest,
+            log_commands::get_session_stats,
+            log_commands::search_project_logs,
+            log_commands::import_transcripts,
+            db_commands::db_info,
+            db_commands::db_vacuum,
+            db_commands::db_integrity_check,
+            log_commands::get_session_file_changes,
+            log_commands::export_logs,
             config_commands::get_config,
             config_commands::save_config,
-            config_commands::set_project_path,
+            config_commands::open_project,
+            config_commands::list_projects,
+            config_commands::get_project_settings,
+            config_commands::list_models,
+            config_commands::export_settings,
+            config_commands::get_project_env,
+            config_commands::allow_project_env,
+            config_commands::revoke_project_env,
+            http_api::get_http_api,
+            http_api::set_http_api,
+            http_api::regenerate_http_api_token,
+            config_commands::import_settings,
+            config_commands::add_project,
+            config_commands::remove_project,
             config_commands::get_project_path,
+            config_commands::list_redaction_builtins,
+            config_commands::test_redaction,
             config_commands::check_agent_approval,
             config_commands::approve_agents,
+            config_commands::revoke_agent_approvals,
+            config_commands::list_agent_approvals,
+            config_commands::list_memory_files,
+            config_commands::get_memory_file,
+            config_commands::save_memory_file,
+            config_commands::list_hooks,
+            config_commands::save_hooks,
+            config_commands::list_mcp_servers,
+            config_commands::add_mcp_server,
+            config_commands::remove_mcp_server,
+            config_commands::check_mcp_servers,
+            config_commands::approve_mcp_server,
             spec_commands::list_specs,
             spec_commands::get_spec,
+            spec_commands::get_spec_sessions,
             spec_commands::create_spec,
             spec_commands::update_spec,
+            spec_commands::reorder_spec,
+            spec_commands::list_child_specs,
+            spec_commands::create_child_spec,
+            spec_commands::get_spec_stats,
+            spec_commands::get_spec_workflow,
+            spec_commands::validate_specs,
+            spec_commands::export_specs,
+            spec_commands::approve_spec,
+            spec_commands::reject_spec,
+            spec_commands::get_spec_history,
+            spec_commands::breakdown_spec,
+            spec_commands::apply_spec_breakdown,
             spec_commands::delete_spec,
             spec_commands::run_spec,
+            spec_commands::run_specs,
+            spec_commands::run_spec_pair,
+            spec_commands::verify_spec,
+            spec_commands::list_github_issues,
+            spec_commands::import_github_issues,
+            spec_commands::import_jira_issues,
+            spec_commands::import_linear_issues,
+            spec_commands::sync_linear,
+            spec_commands::list_linear_conflicts,
             workflow_commands::create_workflow,
             workflow_commands::get_workflow,
             workflow_commands::list_workflows,
@@ -285,9 +603,33 @@ pub fn run() {
             workflow_commands::start_workflow,
             workflow_commands::stop_workflow,
             workflow_commands::validate_workflow,
+            workflow_commands::simulate_workflow,
+            workflow_commands::list_workflow_templates,
+            workflow_commands::save_workflow_template,
+            workflow_commands::delete_workflow_template,
+            workflow_commands::instantiate_workflow_template,
+            workflow_commands::list_prompt_templates,
+            workflow_commands::create_prompt_template,
+            workflow_commands::update_prompt_template,
+            workflow_commands::delete_prompt_template,
+            workflow_commands::list_custom_commands,
+            workflow_commands::import_custom_command,
             review_commands::get_changed_files,
             review_commands::get_diff,
+            review_commands::get_file_diff,
+            review_commands::suggest_commit_message,
+            review_commands::get_session_diff,
+            review_commands::get_commits,
+            review_commands::get_blame,
+            review_commands::discard_file,
+            review_commands::discard_hunk,
+            review_commands::preview_patch,
+            review_commands::apply_patch,
+            review_commands::list_branches,
+            review_commands::create_branch,
+            review_commands::checkout_branch,
             quota_commands::refresh_quota,
+            quota_commands::get_usage_history,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
@@ -0,0 +1,424 @@
+use crate::domain::models::AgentApproval;
+use crate::services::{agent_parser, agent_watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ApprovalEntry {
+    hash: String,
+    approved_at: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ApprovalFile {
+    /// Keyed by canonical project directory, then by relative file path.
+    #[serde(default)]
+    projects: HashMap<String, HashMap<String, ApprovalEntry>>,
+}
+
+/// Every approval with its approved content, for the settings archive.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ApprovalExport {
+    #[serde(default)]
+    projects: HashMap<String, HashMap<String, ApprovalEntry>>,
+    /// Approved content by hash.
+    #[serde(default)]
+    copies: HashMap<String, String>,
+}
+
+/// Agent approvals, namespaced per project and keyed by canonical relative
+/// path, stored at `<dir>/agent-approvals.json` (0600). Approved content is
+/// kept under `<dir>/approved-agents/<hash>.md` so later edits can be diffed.
+pub struct AgentApprovalStore {
+    dir: PathBuf,
+    state: Mutex<ApprovalFile>,
+}
+
+impl AgentApprovalStore {
+    pub fn new(dir: PathBuf) -> Self {
+        let state = std::fs::read_to_string(dir.join("agent-approvals.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            dir,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// The approved hash for `file_path` in `project_dir`.
+    pub fn approved_hash(&self, project_dir: &str, file_path: &str) -> Option<String> {
+        let key = normalize_key(file_path)?;
+        let state = self.state.lock().ok()?;
+        state
+            .projects
+            .get(&project_key(project_dir))?
+            .get(&key)
+            .map(|entry| entry.hash.clone())
+    }
+
+    /// Refuse `agent_name` when a file under the project's `.claude/agents`
+    /// defines it, by frontmatter name or file name, and its content isn't
+    /// the approved one. Agents the project doesn't define, such as
+    /// user-level ones, pass.
+    pub fn check_agent(&self, project_dir: &str, agent_name: &str) -> Result<(), String> {
+        let agents_dir = Path::new(project_dir).join(".claude/agents");
+        for path in agent_watcher::collect_md_files(&agents_dir) {
+            let Ok(raw) = std::fs::read(&path) else {
+                continue;
+            };
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            let name = agent_parser::parse_agent(&String::from_utf8_lossy(&raw), "")
+                .ok()
+                .map(|agent| agent.name);
+            if stem != agent_name && name.as_deref() != Some(agent_name) {
+                continue;
+            }
+            let approved = relative_key(project_dir, &path)
+                .and_then(|key| self.approved_hash(project_dir, &key));
+            match approved {
+                Some(hash) if hash == agent_watcher::hash_bytes(&raw) => {}
+                Some(_) => return Err(format!("{agent_name} changed since it was approved")),
+                None => return Err(format!("{agent_name} has not been approved")),
+            }
+        }
+        Ok(())
+    }
+
+    /// Every approval recorded for `project_dir`, sorted by path.
+    pub fn list(&self, project_dir: &str) -> Vec<AgentApproval> {
+        let Ok(state) = self.state.lock() else {
+            return Vec::new();
+        };
+        let mut approvals: Vec<AgentApproval> = state
+            .projects
+            .get(&project_key(project_dir))
+            .into_iter()
+            .flatten()
+            .map(|(file_path, entry)| AgentApproval {
+                file_path: file_path.clone(),
+                hash: entry.hash.clone(),
+                approved_at: entry.approved_at.clone(),
+            })
+            .collect();
+        approvals.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        approvals
+    }
+
+    /// Approve `(file_path, hash)` pairs in one write. When a file on disk
+    /// still matches its hash, a copy is kept for future diffs.
+    pub fn approve(&self, project_dir: &str, agents: &[(String, String)]) -> Result<(), String> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut entries = Vec::with_capacity(agents.len());
+        for (file_path, hash) in agents {
+            let key = normalize_key(file_path)
+                .ok_or_else(|| format!("Invalid agent path: {file_path}"))?;
+            if let Ok(raw) = std::fs::read(Path::new(project_dir).join(&key)) {
+                if agent_watcher::hash_bytes(&raw) == *hash {
+                    self.save_copy(hash, &raw)?;
+                }
+            }
+            entries.push((key, hash.clone()));
+        }
+
+        let mut state = self.state.lock().map_err(|e| e.to_string())?;
+        let project = state.projects.entry(project_key(project_dir)).or_default();
+        for (key, hash) in entries {
+            project.insert(
+                key,
+                ApprovalEntry {
+                    hash,
+                    approved_at: now.clone(),
+                },
+            );
+        }
+        self.save(&state)
+    }
+
+    /// Approve a file's current content. `path` may be absolute or relative
+    /// to the project.
+    pub fn approve_file(&self, project_dir: &str, path: &Path) -> Result<(), String> {
+        let key = relative_key(project_dir, path)
+            .ok_or_else(|| format!("Agent is outside the project: {}", path.display()))?;
+        let raw = std::fs::read(Path::new(project_dir).join(&key)).map_err(|e| e.to_string())?;
+        self.approve(project_dir, &[(key, agent_watcher::hash_bytes(&raw))])
+    }
+
+    /// Remove approvals so the files are flagged again on the next check.
+    /// Returns how many were removed.
+    pub fn revoke(&self, project_dir: &str, file_paths: &[String]) -> Result<usize, String> {
+        let mut state = self.state.lock().map_err(|e| e.to_string())?;
+        let Some(project) = state.projects.get_mut(&project_key(project_dir)) else {
+            return Ok(0);
+        };
+        let removed = file_paths
+            .iter()
+            .filter_map(|path| normalize_key(path))
+            .filter(|key| project.remove(key).is_some())
+            .count();
+        if project.is_empty() {
+            state.projects.remove(&project_key(project_dir));
+        }
+        self.save(&state)?;
+        Ok(removed)
+    }
+
+    /// Fold the old global `approved_agent_hashes` map into per-project
+    /// entries. Absolute keys find their project from the `.claude/agents`
+    /// segment; relative ones are credited to `fallback_project`. Existing
+    /// entries win.
+    pub fn migrate_legacy(
+        &self,
+        legacy: &HashMap<String, String>,
+        fallback_project: Option<&str>,
+    ) -> Result<(), String> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut state = self.state.lock().map_err(|e| e.to_string())?;
+        for (path, hash) in legacy {
+            let (project_dir, rel) = match path.find("/.claude/agents/") {
+                Some(idx) if Path::new(path).is_absolute() => (&path[..idx], &path[idx + 1..]),
+                _ => match fallback_project {
+                    Some(project) => (project, path.as_str()),
+                    None => continue,
+                },
+            };
+            let Some(key) = normalize_key(rel) else {
+                continue;
+            };
+            state
+                .projects
+                .entry(project_key(project_dir))
+                .or_default()
+                .entry(key)
+                .or_insert_with(|| ApprovalEntry {
+                    hash: hash.clone(),
+                    approved_at: now.clone(),
+                });
+        }
+        self.save(&state)
+    }
+
+    /// Every approval and stored copy.
+    pub fn export(&self) -> Result<ApprovalExport, String> {
+        let state = self.state.lock().map_err(|e| e.to_string())?;
+        let copies = state
+            .projects
+            .values()
+            .flat_map(|files| files.values())
+            .filter_map(|entry| Some((entry.hash.clone(), self.load_copy(&entry.hash)?)))
+            .collect();
+        Ok(ApprovalExport {
+            projects: state.projects.clone(),
+            copies,
+        })
+    }
+
+    /// Add exported approvals and their copies. Existing entries win, and
+    /// copies whose content doesn't match their hash are dropped. Returns how
+    /// many approvals were added.
+    pub fn import(&self, export: &ApprovalExport) -> Result<usize, String> {
+        let mut added = 0;
+        {
+            let mut state = self.state.lock().map_err(|e| e.to_string())?;
+            for (project, files) in &export.projects {
+                let entries = state.projects.entry(project.clone()).or_default();
+                for (file_path, entry) in files {
+                    let Some(key) = normalize_key(file_path) else {
+                        continue;
+                    };
+                    if !entries.contains_key(&key) {
+                        entries.insert(key, entry.clone());
+                        added += 1;
+                    }
+                }
+            }
+            self.save(&state)?;
+        }
+        for (hash, content) in &export.copies {
+            // Otherwise an edited archive could pass off other content as
+            // what was approved
+            if agent_watcher::hash_bytes(content.as_bytes()) != *hash {
+                continue;
+            }
+            if self.load_copy(hash).is_none() {
+                self.save_copy(hash, content.as_bytes())?;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Keep a copy of approved content, keyed by its hash.
+    pub fn save_copy(&self, hash: &str, content: &[u8]) -> Result<(), String> {
+        let Some(path) = self.copy_path(hash) else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            create_private_dir(dir)?;
+        }
+        write_private(&path, content)
+    }
+
+    /// The approved content stored for `hash`, if any.
+    pub fn load_copy(&self, hash: &str) -> Option<String> {
+        std::fs::read_to_string(self.copy_path(hash)?).ok()
+    }
+
+    fn copy_path(&self, hash: &str) -> Option<PathBuf> {
+        // Hashes come back from the frontend; only hex is a safe file name
+        if hash.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        Some(self.dir.join("approved-agents").join(format!("{hash}.md")))
+    }
+
+    fn save(&self, state: &ApprovalFile) -> Result<(), String> {
+        create_private_dir(&self.dir)?;
+        let json = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+        let path = self.dir.join("agent-approvals.json");
+        let tmp = path.with_extension("json.tmp");
+        write_private(&tmp, json.as_bytes())?;
+        std::fs::rename(&tmp, &path).map_err(|e| e.to_string())
+    }
+}
+
+/// Approvals survive symlinked or `..`-laden project paths by keying on the
+/// canonical directory.
+fn project_key(project_dir: &str) -> String {
+    std::fs::canonicalize(project_dir)
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| project_dir.trim_end_matches('/').to_string())
+}
+
+/// The canonical `/`-separated key for a file inside `project_dir`, or
+/// `None` if it resolves outside the project.
+pub fn relative_key(project_dir: &str, path: &Path) -> Option<String> {
+    let root = std::fs::canonicalize(project_dir).ok()?;
+    let full = std::fs::canonicalize(Path::new(project_dir).join(path)).ok()?;
+    let rel = full.strip_prefix(&root).ok()?;
+    normalize_key(&rel.to_string_lossy())
+}
+
+/// Lexical normalization for keys coming from the frontend: `/` separators,
+/// no leading `./`, and nothing absolute or escaping via `..`.
+fn normalize_key(path: &str) -> Option<String> {
+    let path = path.replace('\\', "/");
+    let mut parts = Vec::new();
+    for component in Path::new(&path).components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().into_owned()),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+fn create_private_dir(dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700));
+    }
+    Ok(())
+}
+
+/// Write `content` to a file only the user can read, created that way so
+/// it's never readable by others, even briefly.
+pub fn write_private(path: &Path, content: &[u8]) -> Result<(), String> {
+    use std::io::Write;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path).map_err(|e| e.to_string())?;
+    // The mode only applies to new files
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))
+            .map_err(|e| e.to_string())?;
+    }
+    file.write_all(content).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approvals_are_per_project_and_keyed_by_relative_path() {
+        let root =
+            std::env::temp_dir().join(format!("clautron-approvals-{}", uuid::Uuid::new_v4()));
+        let project_a = root.join("a");
+        let project_b = root.join("b");
+        for project in [&project_a, &project_b] {
+            std::fs::create_dir_all(project.join(".claude/agents")).unwrap();
+            std::fs::write(
+                project.join(".claude/agents/dev.md"),
+                "---\nname: dev\n---\n",
+            )
+            .unwrap();
+        }
+        let a = project_a.to_string_lossy().to_string();
+        let b = project_b.to_string_lossy().to_string();
+        let store = AgentApprovalStore::new(root.join("data"));
+
+        // Absolute and relative paths land on the same key
+        store
+            .approve_file(&a, &project_a.join(".claude/agents/dev.md"))
+            .unwrap();
+        let hash = store.approved_hash(&a, "./.claude/agents/dev.md").unwrap();
+        assert!(store.load_copy(&hash).is_some());
+        assert!(store.approved_hash(&b, ".claude/agents/dev.md").is_none());
+        assert!(store.check_agent(&a, "dev").is_ok());
+        assert!(store.check_agent(&b, "dev").is_err());
+        // Not defined by the project, e.g. a user-level agent
+        assert!(store.check_agent(&b, "reviewer").is_ok());
+
+        // Reloading from disk keeps the namespacing
+        let store = AgentApprovalStore::new(root.join("data"));
+        assert_eq!(store.list(&a).len(), 1);
+        assert!(store.list(&b).is_empty());
+
+        assert!(store
+            .approve(
+                &a,
+                &[("../b/.claude/agents/dev.md".to_string(), hash.clone())]
+            )
+            .is_err());
+        assert_eq!(
+            store
+                .revoke(&a, &[".claude/agents/dev.md".to_string()])
+                .unwrap(),
+            1
+        );
+        assert!(store.list(&a).is_empty());
+
+        let legacy = HashMap::from([(format!("{b}/.claude/agents/dev.md"), hash.clone())]);
+        store.migrate_legacy(&legacy, None).unwrap();
+        assert_eq!(store.approved_hash(&b, ".claude/agents/dev.md"), Some(hash));
+        std::fs::write(
+            project_b.join(".claude/agents/dev.md"),
+            "---\nname: dev\n---\nEdited\n",
+        )
+        .unwrap();
+        assert!(store.check_agent(&b, "dev").unwrap_err().contains("changed"));
+
+        // Imported copies must match the hash they're filed under
+        let forged = agent_watcher::hash_bytes(b"---\nname: other\n---\n");
+        let export = ApprovalExport {
+            projects: HashMap::new(),
+            copies: HashMap::from([(forged.clone(), "---\nname: evil\n---\n".to_string())]),
+        };
+        store.import(&export).unwrap();
+        assert!(store.load_copy(&forged).is_none());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::services::agent_parser;
+use serde::Deserialize;
+
+const MODELS: &[&str] = &["opus", "sonnet", "haiku"];
+
+/// A complete agent definition generated by Claude, validated and ready to
+/// be written with `AgentManager::create_agent`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedAgent {
+    pub description: String,
+    pub model: String,
+    /// `None` grants every tool.
+    pub tools: Option<Vec<String>>,
+    pub system_prompt: String,
+}
+
+/// Shape of the model's answer.
+#[derive(Deserialize)]
+struct RawAgent {
+    description: String,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    tools: Option<Vec<String>>,
+    system_prompt: String,
+}
+
+/// Prompt asking Claude for a full agent definition as JSON.
+pub fn build_prompt(name: &str, goals: &str) -> String {
+    format!(
+        "Design a Claude Code subagent named \"{name}\".\n\n\
+         ## Goals\n\n{goals}\n\n\
+         ## Rules\n\n\
+         - description: one sentence saying when to use this agent\n\
+         - model: \"opus\" for hard reasoning, \"sonnet\" for most coding work, \"haiku\" for simple fast tasks\n\
+         - tools: the smallest set of tools the agent needs, chosen from {tools}, or null for all tools\n\
+         - system_prompt: markdown defining the agent's role, approach and key instructions, 200-500 words, \
+         no YAML frontmatter and no preamble\n\n\
+         Respond with only a JSON object, no prose, in this format:\n\
+         {{\"description\": \"...\", \"model\": \"sonnet\", \"tools\": [\"Read\", \"Grep\"], \"system_prompt\": \"...\"}}",
+        tools = agent_parser::KNOWN_TOOLS.join(", "),
+    )
+}
+
+/// Parse and validate the model's answer. Tolerates surrounding prose and
+/// code fences; an unknown model falls back to sonnet.
+pub fn parse_generated(output: &str) -> Result<GeneratedAgent, String> {
+    let (Some(start), Some(end)) = (output.find('{'), output.rfind('}')) else {
+        return Err("Claude did not return an agent definition".into());
+    };
+    if end < start {
+        return Err("Claude did not return an agent definition".into());
+    }
+    let raw: RawAgent = serde_json::from_str(&output[start..=end])
+        .map_err(|e| format!("Could not parse the agent definition: {e}"))?;
+
+    let description = raw.description.trim().to_string();
+    let system_prompt = raw.system_prompt.trim().to_string();
+    if description.is_empty() || system_prompt.is_empty() {
+        return Err("Claude returned an agent without a description or system prompt".into());
+    }
+    let model = raw
+        .model
+        .map(|m| m.trim().to_lowercase())
+        .filter(|m| MODELS.contains(&m.as_str()))
+        .unwrap_or_else(|| "sonnet".to_string());
+    let tools = match raw.tools {
+        Some(tools) => {
+            let tools: Vec<String> = tools
+                .into_iter()
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect();
+            agent_parser::validate_tools(&tools)?;
+            Some(tools)
+        }
+        None => None,
+    };
+
+    Ok(GeneratedAgent {
+        description,
+        model,
+        tools,
+        system_prompt,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fenced_object_and_validates_fields() {
+        let output = "```json\n{\"description\": \" Reviews PRs \", \"model\": \"Opus\", \"tools\": [\"Read\", \"Grep\"], \"system_prompt\": \"You review code.\"}\n```";
+        let agent = parse_generated(output).unwrap();
+        assert_eq!(agent.description, "Reviews PRs");
+        assert_eq!(agent.model, "opus");
+        assert_eq!(
+            agent.tools,
+            Some(vec!["Read".to_string(), "Grep".to_string()])
+        );
+
+        let unknown_model = "{\"description\": \"d\", \"model\": \"gpt\", \"tools\": null, \"system_prompt\": \"p\"}";
+        assert_eq!(parse_generated(unknown_model).unwrap().model, "sonnet");
+
+        assert!(parse_generated(
+            "{\"description\": \"d\", \"tools\": [\"Teleport\"], \"system_prompt\": \"p\"}"
+        )
+        .is_err());
+        assert!(parse_generated("{\"description\": \"\", \"system_prompt\": \"p\"}").is_err());
+        assert!(parse_generated("no json here").is_err());
+    }
+}
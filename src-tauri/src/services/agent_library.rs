@@ -0,0 +1,44 @@
+use crate::domain::models::AgentConfig;
+use crate::services::agent_parser;
+
+/// Curated agent definitions shipped with the app. Each agent's `name` is
+/// also its file stem when installed.
+const BUILT_IN: &[&str] = &[
+    include_str!("../../agent-templates/planner.md"),
+    include_str!("../../agent-templates/implementer.md"),
+    include_str!("../../agent-templates/reviewer.md"),
+    include_str!("../../agent-templates/test-writer.md"),
+    include_str!("../../agent-templates/doc-writer.md"),
+];
+
+/// The built-in agent templates, parsed for display. `file_path` is empty
+/// until a template is installed into a project.
+pub fn list_templates() -> Vec<AgentConfig> {
+    BUILT_IN
+        .iter()
+        .filter_map(|content| agent_parser::parse_agent(content, "").ok())
+        .collect()
+}
+
+/// The markdown of the built-in template called `name`.
+pub fn get_template(name: &str) -> Option<&'static str> {
+    BUILT_IN.iter().copied().find(|content| {
+        agent_parser::parse_agent(content, "").is_ok_and(|agent| agent.name == name)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_built_in_template_parses() {
+        let templates = list_templates();
+        assert_eq!(templates.len(), BUILT_IN.len());
+        for template in &templates {
+            assert!(!template.body.is_empty());
+            assert!(get_template(&template.name).is_some());
+        }
+        assert!(get_template("missing").is_none());
+    }
+}
@@ -1,7 +1,9 @@
-use crate::domain::models::{AgentConfig, AgentConfigUpdate};
+use crate::domain::models::{AgentConfig, AgentConfigUpdate, AgentDiagnostic};
+use crate::services::agent_approvals::{self, AgentApprovalStore};
+use crate::services::agent_library;
 use crate::services::agent_parser;
+use crate::services::agent_versions::{AgentVersion, AgentVersionStore};
 use crate::services::agent_watcher;
-use crate::services::config_store::ConfigStore;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -9,14 +11,16 @@ use tokio::sync::RwLock;
 /// Service for managing agent definition files on disk.
 pub struct AgentManager {
     project_dir: RwLock<Option<String>>,
-    config_store: Arc<ConfigStore>,
+    approvals: Arc<AgentApprovalStore>,
+    versions: AgentVersionStore,
 }
 
 impl AgentManager {
-    pub fn new(config_store: Arc<ConfigStore>) -> Self {
+    pub fn new(approvals: Arc<AgentApprovalStore>, versions: AgentVersionStore) -> Self {
         Self {
             project_dir: RwLock::new(None),
-            config_store,
+            approvals,
+            versions,
         }
     }
 
@@ -65,20 +69,50 @@ impl AgentManager {
         Ok(configs)
     }
 
+    /// Frontmatter problems across every agent file, including ones that
+    /// fail to parse and so are missing from `list_agents`.
+    pub async fn validate_agents(&self) -> Result<Vec<AgentDiagnostic>, String> {
+        let project_dir = self
+            .project_dir
+            .read()
+            .await
+            .clone()
+            .ok_or("No project directory set")?;
+
+        let agents_dir = Self::agents_dir(&project_dir);
+        let mut diagnostics = Vec::new();
+        for path in agent_watcher::collect_md_files(&agents_dir) {
+            if let Ok(content) = std::fs::read_to_string(&path) {
+                diagnostics.extend(agent_parser::validate_agent(
+                    &content,
+                    &path.to_string_lossy(),
+                ));
+            }
+        }
+        Ok(diagnostics)
+    }
+
     /// Get a single agent by file path.
     pub async fn get_agent(&self, file_path: &str) -> Result<AgentConfig, String> {
         let content = std::fs::read_to_string(file_path).map_err(|e| e.to_string())?;
         agent_parser::parse_agent(&content, file_path)
     }
 
-    /// Create a new agent definition file.
+    /// Create a new agent definition file. `tools: None` grants every tool.
     pub async fn create_agent(
         &self,
         name: String,
         model: String,
         description: String,
         color: String,
+        tools: Option<Vec<String>>,
+        body: String,
     ) -> Result<AgentConfig, String> {
+        if let Some(ref tools) = tools {
+            agent_parser::validate_tools(tools)?;
+        }
+        agent_parser::validate_color(&color)?;
+        let color = color.trim().to_lowercase();
         let project_dir = self
             .project_dir
             .read()
@@ -105,57 +139,124 @@ impl AgentManager {
             description,
             model,
             color,
+            icon: None,
+            tools,
+            pairs_with: Vec::new(),
             file_path: file_path.to_string_lossy().to_string(),
-            body: String::new(),
+            body,
         };
 
         let content = agent_parser::serialize_agent(&config, None);
         std::fs::write(&file_path, &content).map_err(|e| e.to_string())?;
 
         // Auto-approve the hash so the approval dialog doesn't fire
-        self.auto_approve_hash(&file_path)?;
+        self.auto_approve_hash(&file_path).await?;
 
         Ok(config)
     }
 
+    /// Write a built-in agent template into .claude/agents/ and approve it.
+    /// Refuses to overwrite an existing agent file.
+    pub async fn install_template(&self, name: &str) -> Result<AgentConfig, String> {
+        let project_dir = self
+            .project_dir
+            .read()
+            .await
+            .clone()
+            .ok_or("No project directory set")?;
+        let content = agent_library::get_template(name)
+            .ok_or_else(|| format!("Unknown agent template: {name}"))?;
+
+        let agents_dir = Self::agents_dir(&project_dir);
+        std::fs::create_dir_all(&agents_dir).map_err(|e| e.to_string())?;
+        let file_path = agents_dir.join(format!("{}.md", slugify(name)));
+        if file_path.exists() {
+            return Err(format!("Agent already exists: {}", file_path.display()));
+        }
+        std::fs::write(&file_path, content).map_err(|e| e.to_string())?;
+
+        // Auto-approve the hash so the approval dialog doesn't fire
+        self.auto_approve_hash(&file_path).await?;
+
+        agent_parser::parse_agent(content, &file_path.to_string_lossy())
+    }
+
     /// Update an existing agent definition.
     pub async fn update_agent(
         &self,
         file_path: &str,
         update: AgentConfigUpdate,
     ) -> Result<AgentConfig, String> {
+        if let Some(Some(ref tools)) = update.tools {
+            agent_parser::validate_tools(tools)?;
+        }
+        if let Some(ref color) = update.color {
+            agent_parser::validate_color(color)?;
+        }
+        if let Some(Some(ref icon)) = update.icon {
+            agent_parser::validate_icon(icon)?;
+        }
         let original_content = std::fs::read_to_string(file_path).map_err(|e| e.to_string())?;
         let current = agent_parser::parse_agent(&original_content, file_path)?;
         let updated = agent_parser::apply_update(&current, &update);
         let content = agent_parser::serialize_agent(&updated, Some(&original_content));
+        if content != original_content {
+            self.versions.record(file_path, &original_content)?;
+        }
         std::fs::write(file_path, &content).map_err(|e| e.to_string())?;
 
         // Auto-approve the new hash
         let path = std::path::Path::new(file_path);
-        self.auto_approve_hash(path)?;
+        self.auto_approve_hash(path).await?;
 
         Ok(updated)
     }
 
-    /// Delete an agent definition file.
-    pub async fn delete_agent(&self, file_path: &str) -> Result<(), String> {
-        std::fs::remove_file(file_path).map_err(|e| e.to_string())
+    /// Earlier saved versions of an agent file, newest first.
+    pub fn versions(&self, file_path: &str) -> Vec<AgentVersion> {
+        self.versions.list(file_path)
     }
 
-    /// Compute SHA-256 hash of the file and update approved hashes in ConfigStore.
-    fn auto_approve_hash(&self, path: &std::path::Path) -> Result<(), String> {
-        if let Some(hash) = agent_watcher::hash_file(path) {
-            let mut config = self.config_store.load();
-            let file_path_str = path.to_string_lossy().to_string();
-            config
-                .approved_agent_hashes
-                .insert(file_path_str, hash);
-            self.config_store
-                .save(&config)
-                .map_err(|e| e.to_string())?;
+    /// Overwrite an agent file with one of its earlier versions. The content
+    /// being replaced is kept as a version too, so a restore can be undone.
+    pub async fn restore_version(
+        &self,
+        file_path: &str,
+        version_id: &str,
+    ) -> Result<AgentConfig, String> {
+        let version = self.versions.get(file_path, version_id)?;
+        let restored = agent_parser::parse_agent(&version.content, file_path)?;
+        if let Ok(current) = std::fs::read_to_string(file_path) {
+            self.versions.record(file_path, &current)?;
+        }
+        std::fs::write(file_path, &version.content).map_err(|e| e.to_string())?;
+        self.auto_approve_hash(Path::new(file_path)).await?;
+        Ok(restored)
+    }
+
+    /// Delete an agent definition file.
+    pub async fn delete_agent(&self, file_path: &str) -> Result<(), String> {
+        let key = match self.project_dir.read().await.as_deref() {
+            Some(project_dir) => agent_approvals::relative_key(project_dir, Path::new(file_path))
+                .map(|key| (project_dir.to_string(), key)),
+            None => None,
+        };
+        std::fs::remove_file(file_path).map_err(|e| e.to_string())?;
+        // A file recreated at the same path has to be approved again
+        if let Some((project_dir, key)) = key {
+            self.approvals.revoke(&project_dir, &[key])?;
         }
         Ok(())
     }
+
+    /// Record the file's current content as approved for this project, so
+    /// edits made in-app don't trigger the approval dialog.
+    async fn auto_approve_hash(&self, path: &Path) -> Result<(), String> {
+        let Some(project_dir) = self.project_dir.read().await.clone() else {
+            return Ok(());
+        };
+        self.approvals.approve_file(&project_dir, path)
+    }
 }
 
 /// Convert a name to a URL-safe filename slug.
@@ -1,4 +1,4 @@
-use crate::domain::models::{AgentConfig, AgentConfigUpdate};
+use crate::domain::models::{AgentConfig, AgentConfigUpdate, AgentDiagnostic, DiagnosticSeverity};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -10,13 +10,137 @@ struct AgentFrontmatter {
     description: String,
     #[serde(default = "default_model")]
     model: String,
-    #[serde(default = "default_color")]
-    color: String,
+    /// Kept as a raw value so a typo or a number doesn't fail the whole parse.
+    #[serde(default, skip_serializing_if = "serde_yaml::Value::is_null")]
+    color: serde_yaml::Value,
+    #[serde(default, skip_serializing_if = "serde_yaml::Value::is_null")]
+    icon: serde_yaml::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tools: Option<ToolList>,
+    /// Names of agents this one is meant to work with (clautron-specific).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pairs_with: Option<ToolList>,
     /// Preserve unknown frontmatter fields (e.g. `memory: project`).
     #[serde(flatten)]
     extra: HashMap<String, serde_yaml::Value>,
 }
 
+/// `tools:` is a comma-separated string in Claude Code agent files; a YAML
+/// list is accepted too. Always written back as a string. `pairs_with:`
+/// reads the same way but is written back as a list.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum ToolList {
+    Csv(String),
+    List(Vec<String>),
+}
+
+impl ToolList {
+    fn into_vec(self) -> Vec<String> {
+        let tools: Vec<String> = match self {
+            ToolList::Csv(s) => s.split(',').map(String::from).collect(),
+            ToolList::List(list) => list,
+        };
+        tools
+            .into_iter()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect()
+    }
+}
+
+/// Tools built into Claude Code. MCP tools (`mcp__server__tool`) are also allowed.
+pub const KNOWN_TOOLS: &[&str] = &[
+    "Agent",
+    "Bash",
+    "BashOutput",
+    "Edit",
+    "ExitPlanMode",
+    "Glob",
+    "Grep",
+    "KillShell",
+    "LS",
+    "MultiEdit",
+    "NotebookEdit",
+    "NotebookRead",
+    "Read",
+    "SlashCommand",
+    "Task",
+    "TodoWrite",
+    "WebFetch",
+    "WebSearch",
+    "Write",
+];
+
+/// Reject tool names Claude Code doesn't know. MCP tools are accepted by prefix.
+pub fn validate_tools(tools: &[String]) -> Result<(), String> {
+    let unknown: Vec<&str> = tools
+        .iter()
+        .map(|t| t.trim())
+        .filter(|t| !KNOWN_TOOLS.contains(t) && !t.starts_with("mcp__"))
+        .collect();
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Unknown tools: {}", unknown.join(", ")))
+    }
+}
+
+/// Colors the board can render. Claude Code uses the same names.
+pub const AGENT_COLORS: &[&str] = &[
+    "blue", "red", "green", "yellow", "purple", "orange", "pink", "cyan", "gray",
+];
+
+/// Icon names the frontend maps to its icon set.
+pub const AGENT_ICONS: &[&str] = &[
+    "bot",
+    "book",
+    "bug",
+    "code",
+    "database",
+    "eye",
+    "flask",
+    "git-branch",
+    "pen",
+    "rocket",
+    "search",
+    "shield",
+    "sparkles",
+    "terminal",
+    "wrench",
+];
+
+/// Reject colors outside the palette. Case is ignored.
+pub fn validate_color(color: &str) -> Result<(), String> {
+    normalize_color(color).map(|_| ()).ok_or_else(|| {
+        format!(
+            "Unknown color `{color}`; expected one of: {}",
+            AGENT_COLORS.join(", ")
+        )
+    })
+}
+
+/// Reject icon names the frontend doesn't know.
+pub fn validate_icon(icon: &str) -> Result<(), String> {
+    normalize_icon(icon).map(|_| ()).ok_or_else(|| {
+        format!(
+            "Unknown icon `{icon}`; expected one of: {}",
+            AGENT_ICONS.join(", ")
+        )
+    })
+}
+
+fn normalize_color(color: &str) -> Option<&'static str> {
+    let lower = color.trim().to_lowercase();
+    let color = if lower == "grey" { "gray" } else { &lower };
+    AGENT_COLORS.iter().copied().find(|c| *c == color)
+}
+
+fn normalize_icon(icon: &str) -> Option<&'static str> {
+    let icon = icon.trim().to_lowercase();
+    AGENT_ICONS.iter().copied().find(|i| *i == icon)
+}
+
 fn default_model() -> String {
     "sonnet".to_string()
 }
@@ -27,6 +151,26 @@ fn default_color() -> String {
 
 /// Parse an agent markdown file into an AgentConfig.
 pub fn parse_agent(content: &str, file_path: &str) -> Result<AgentConfig, String> {
+    let (fm, body) = split_agent(content)?;
+
+    Ok(AgentConfig {
+        name: fm.name,
+        description: fm.description,
+        model: fm.model,
+        color: fm
+            .color
+            .as_str()
+            .and_then(normalize_color)
+            .map_or_else(default_color, String::from),
+        icon: fm.icon.as_str().and_then(normalize_icon).map(String::from),
+        tools: fm.tools.map(ToolList::into_vec),
+        pairs_with: fm.pairs_with.map(ToolList::into_vec).unwrap_or_default(),
+        file_path: file_path.to_string(),
+        body,
+    })
+}
+
+fn split_agent(content: &str) -> Result<(AgentFrontmatter, String), String> {
     let content = content.trim();
     if !content.starts_with("---") {
         return Err("Agent file must start with YAML frontmatter (---)".into());
@@ -41,15 +185,7 @@ pub fn parse_agent(content: &str, file_path: &str) -> Result<AgentConfig, String
 
     let fm: AgentFrontmatter =
         serde_yaml::from_str(frontmatter_str).map_err(|e| format!("YAML parse error: {e}"))?;
-
-    Ok(AgentConfig {
-        name: fm.name,
-        description: fm.description,
-        model: fm.model,
-        color: fm.color,
-        file_path: file_path.to_string(),
-        body,
-    })
+    Ok((fm, body))
 }
 
 /// Serialize an AgentConfig back to markdown with YAML frontmatter.
@@ -66,7 +202,14 @@ pub fn serialize_agent(config: &AgentConfig, original_content: Option<&str>) ->
         name: config.name.clone(),
         description: config.description.clone(),
         model: config.model.clone(),
-        color: config.color.clone(),
+        color: serde_yaml::Value::String(config.color.clone()),
+        icon: config
+            .icon
+            .clone()
+            .map_or(serde_yaml::Value::Null, serde_yaml::Value::String),
+        tools: config.tools.as_ref().map(|t| ToolList::Csv(t.join(", "))),
+        pairs_with: (!config.pairs_with.is_empty())
+            .then(|| ToolList::List(config.pairs_with.clone())),
         extra,
     };
 
@@ -100,12 +243,86 @@ pub fn apply_update(config: &AgentConfig, update: &AgentConfigUpdate) -> AgentCo
     if let Some(ref color) = update.color {
         updated.color = color.clone();
     }
+    if let Some(ref icon) = update.icon {
+        updated.icon = icon.clone();
+    }
+    if let Some(ref tools) = update.tools {
+        updated.tools = tools.clone();
+    }
+    if let Some(ref pairs_with) = update.pairs_with {
+        updated.pairs_with = pairs_with.clone();
+    }
     if let Some(ref body) = update.body {
         updated.body = body.clone();
     }
     updated
 }
 
+/// Check an agent file's frontmatter. Unlike `parse_agent`, which falls
+/// back to defaults, this reports every problem it finds.
+pub fn validate_agent(content: &str, file_path: &str) -> Vec<AgentDiagnostic> {
+    let diagnostic = |severity, field: Option<&str>, message: String| AgentDiagnostic {
+        file_path: file_path.to_string(),
+        severity,
+        field: field.map(String::from),
+        message,
+    };
+
+    let fm = match split_agent(content) {
+        Ok((fm, _)) => fm,
+        Err(e) => return vec![diagnostic(DiagnosticSeverity::Error, None, e)],
+    };
+
+    let mut diagnostics = Vec::new();
+    if fm.name.trim().is_empty() {
+        diagnostics.push(diagnostic(
+            DiagnosticSeverity::Error,
+            Some("name"),
+            "Name is empty".into(),
+        ));
+    }
+    match &fm.color {
+        serde_yaml::Value::Null => {}
+        serde_yaml::Value::String(color) => {
+            if let Err(e) = validate_color(color) {
+                diagnostics.push(diagnostic(
+                    DiagnosticSeverity::Warning,
+                    Some("color"),
+                    format!("{e} (shown as {})", default_color()),
+                ));
+            }
+        }
+        _ => diagnostics.push(diagnostic(
+            DiagnosticSeverity::Warning,
+            Some("color"),
+            format!("Color must be a string (shown as {})", default_color()),
+        )),
+    }
+    match &fm.icon {
+        serde_yaml::Value::Null => {}
+        serde_yaml::Value::String(icon) => {
+            if let Err(e) = validate_icon(icon) {
+                diagnostics.push(diagnostic(
+                    DiagnosticSeverity::Warning,
+                    Some("icon"),
+                    format!("{e} (ignored)"),
+                ));
+            }
+        }
+        _ => diagnostics.push(diagnostic(
+            DiagnosticSeverity::Warning,
+            Some("icon"),
+            "Icon must be a string (ignored)".into(),
+        )),
+    }
+    if let Some(tools) = fm.tools {
+        if let Err(e) = validate_tools(&tools.into_vec()) {
+            diagnostics.push(diagnostic(DiagnosticSeverity::Warning, Some("tools"), e));
+        }
+    }
+    diagnostics
+}
+
 /// Extract extra (non-standard) frontmatter fields from content.
 fn parse_extra_fields(content: &str) -> Option<HashMap<String, serde_yaml::Value>> {
     let content = content.trim();
@@ -119,3 +336,55 @@ fn parse_extra_fields(content: &str) -> Option<HashMap<String, serde_yaml::Value
     let fm: AgentFrontmatter = serde_yaml::from_str(frontmatter_str).ok()?;
     Some(fm.extra)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tools_parse_from_string_or_list_and_write_back_as_string() {
+        let agent = parse_agent(
+            "---\nname: reviewer\ntools: Read, Grep,  mcp__github__get_pr\n---\nReview.",
+            "reviewer.md",
+        )
+        .unwrap();
+        assert_eq!(
+            agent.tools.as_deref(),
+            Some(&["Read".into(), "Grep".into(), "mcp__github__get_pr".into()][..])
+        );
+        assert!(serialize_agent(&agent, None).contains("tools: Read, Grep, mcp__github__get_pr"));
+
+        let listed = parse_agent("---\nname: a\ntools: [Bash, Edit]\n---\n", "a.md").unwrap();
+        assert_eq!(listed.tools, Some(vec!["Bash".into(), "Edit".into()]));
+        assert_eq!(
+            parse_agent("---\nname: a\n---\n", "a.md").unwrap().tools,
+            None
+        );
+
+        assert!(validate_tools(&["Bash".into(), "mcp__x__y".into()]).is_ok());
+        assert_eq!(
+            validate_tools(&["Bash".into(), "Shell".into()]).unwrap_err(),
+            "Unknown tools: Shell"
+        );
+    }
+
+    #[test]
+    fn off_palette_colors_and_icons_fall_back_with_diagnostics() {
+        let content = "---\nname: a\ncolor: Magenta\nicon: 42\n---\n";
+        let agent = parse_agent(content, "a.md").unwrap();
+        assert_eq!(agent.color, "gray");
+        assert_eq!(agent.icon, None);
+
+        let fields: Vec<_> = validate_agent(content, "a.md")
+            .into_iter()
+            .map(|d| d.field.unwrap())
+            .collect();
+        assert_eq!(fields, ["color", "icon"]);
+
+        let agent = parse_agent("---\nname: a\ncolor: Blue\nicon: bug\n---\n", "a.md").unwrap();
+        assert_eq!(agent.color, "blue");
+        assert_eq!(agent.icon.as_deref(), Some("bug"));
+        assert!(serialize_agent(&agent, None).contains("icon: bug"));
+        assert!(validate_agent("---\nname: a\n---\n", "a.md").is_empty());
+    }
+}
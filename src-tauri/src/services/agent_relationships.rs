@@ -0,0 +1,83 @@
+use crate::domain::models::{AgentConfig, AgentRelationship};
+
+/// Fold `pairs_with:` declarations into the relationships observed in
+/// workflows. A pairing that is also observed marks the existing entry as
+/// declared; pairings naming an agent that doesn't exist are skipped.
+pub fn merge_declared(
+    mut relationships: Vec<AgentRelationship>,
+    agents: &[AgentConfig],
+) -> Vec<AgentRelationship> {
+    for agent in agents {
+        for target in &agent.pairs_with {
+            if target == &agent.name || !agents.iter().any(|a| &a.name == target) {
+                continue;
+            }
+            match relationships
+                .iter_mut()
+                .find(|r| r.source_agent == agent.name && &r.target_agent == target)
+            {
+                Some(existing) => existing.declared = true,
+                None => relationships.push(AgentRelationship {
+                    source_agent: agent.name.clone(),
+                    target_agent: target.clone(),
+                    workflow_names: Vec::new(),
+                    edge_count: 0,
+                    declared: true,
+                }),
+            }
+        }
+    }
+    relationships.sort_by(|a, b| {
+        (&a.source_agent, &a.target_agent).cmp(&(&b.source_agent, &b.target_agent))
+    });
+    relationships
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn declared_pairings_merge_with_observed_edges() {
+        let agent = |name: &str, pairs_with: &[&str]| AgentConfig {
+            name: name.into(),
+            description: String::new(),
+            model: "sonnet".into(),
+            color: "blue".into(),
+            icon: None,
+            tools: None,
+            pairs_with: pairs_with.iter().map(|p| p.to_string()).collect(),
+            file_path: format!("{name}.md"),
+            body: String::new(),
+        };
+        let agents = [
+            agent("dev", &["reviewer", "missing", "dev"]),
+            agent("planner", &["dev"]),
+            agent("reviewer", &[]),
+        ];
+        let observed = vec![AgentRelationship {
+            source_agent: "planner".into(),
+            target_agent: "dev".into(),
+            workflow_names: vec!["ship".into()],
+            edge_count: 2,
+            declared: false,
+        }];
+
+        let merged = merge_declared(observed, &agents);
+        let summary: Vec<_> = merged
+            .iter()
+            .map(|r| {
+                (
+                    r.source_agent.as_str(),
+                    r.target_agent.as_str(),
+                    r.edge_count,
+                    r.declared,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [("dev", "reviewer", 0, true), ("planner", "dev", 2, true)]
+        );
+    }
+}
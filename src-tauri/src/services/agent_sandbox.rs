@@ -0,0 +1,182 @@
+//! Throwaway agent runs for iterating on system prompts: the agent runs in an
+//! empty temp directory in plan (read-only) permission mode, and nothing is
+//! recorded in session history.
+
+use crate::adapters::claude_cli_runner::ENV_ALLOWLIST;
+use crate::domain::models::{AgentStatus, AgentTestMessage, AgentTestRun, StreamMessage};
+use crate::domain::stream_parser;
+use crate::services::agent_parser;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+
+const TEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Run the agent defined in `agent_file` once against `sample_prompt`.
+pub async fn test_agent(agent_file: &str, sample_prompt: &str) -> Result<AgentTestRun, String> {
+    let prompt = sample_prompt.trim();
+    if prompt.is_empty() {
+        return Err("Sample prompt is empty".into());
+    }
+    if prompt.starts_with('-') {
+        return Err("Sample prompt must not start with '-'".into());
+    }
+    let content = std::fs::read_to_string(agent_file).map_err(|e| e.to_string())?;
+    let agent = agent_parser::parse_agent(&content, agent_file)?;
+
+    let sandbox = create_sandbox(&content)?;
+    let result = run(&sandbox, &agent.name, &agent.model, prompt).await;
+    let _ = std::fs::remove_dir_all(&sandbox);
+    result
+}
+
+/// A private temp directory holding only the agent definition, so the CLI
+/// resolves `--agent` without seeing the real project.
+fn create_sandbox(content: &str) -> Result<PathBuf, String> {
+    let dir = std::env::temp_dir().join(format!("clautron-agent-test-{}", uuid::Uuid::new_v4()));
+    let agents_dir = dir.join(".claude").join("agents");
+    std::fs::create_dir_all(&agents_dir).map_err(|e| e.to_string())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700));
+    }
+    std::fs::write(agents_dir.join("agent.md"), content).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+async fn run(
+    dir: &Path,
+    agent_name: &str,
+    model: &str,
+    prompt: &str,
+) -> Result<AgentTestRun, String> {
+    // P0 Security: args array, never shell interpolation
+    let mut cmd = Command::new("claude");
+    cmd.args([
+        "--print",
+        "--output-format",
+        "stream-json",
+        "--verbose",
+        "--permission-mode",
+        "plan",
+        "--agent",
+        agent_name,
+        "--model",
+        model,
+        prompt,
+    ])
+    .current_dir(dir)
+    .stdout(Stdio::piped())
+    .stderr(Stdio::null())
+    .stdin(Stdio::null())
+    .kill_on_drop(true)
+    .env_clear();
+    for key in ENV_ALLOWLIST {
+        if let Ok(val) = std::env::var(key) {
+            cmd.env(key, val);
+        }
+    }
+
+    let started = Instant::now();
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to run claude: {e}"))?;
+    let stdout = child.stdout.take().ok_or("No stdout from claude")?;
+
+    let mut test_run = AgentTestRun {
+        agent_name: agent_name.to_string(),
+        model: model.to_string(),
+        status: AgentStatus::Completed,
+        result: None,
+        transcript: Vec::new(),
+        input_tokens: 0,
+        output_tokens: 0,
+        cost_usd: 0.0,
+        duration_ms: 0,
+    };
+    let read = async {
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            record_line(&mut test_run, &line);
+        }
+        let _ = child.wait().await;
+    };
+    if tokio::time::timeout(TEST_TIMEOUT, read).await.is_err() {
+        return Err(format!(
+            "Test run timed out after {}s",
+            TEST_TIMEOUT.as_secs()
+        ));
+    }
+    test_run.duration_ms = started.elapsed().as_millis() as u64;
+    Ok(test_run)
+}
+
+/// Fold one stream-json line into the run: transcript, usage and result.
+fn record_line(run: &mut AgentTestRun, line: &str) {
+    let Some(msg) = stream_parser::parse_stream_line(line) else {
+        return;
+    };
+    match &msg {
+        StreamMessage::Assistant(a) => {
+            if let Some(usage) = a.message.as_ref().and_then(|m| m.get("usage")) {
+                let tokens = |key: &str| usage.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+                run.input_tokens += tokens("input_tokens");
+                run.output_tokens += tokens("output_tokens");
+            }
+        }
+        StreamMessage::Result(r) => {
+            if r.subtype.as_deref() == Some("error") {
+                run.status = AgentStatus::Error;
+            }
+            run.result = r
+                .extra
+                .get("result")
+                .and_then(|v| v.as_str())
+                .map(stream_parser::redact_secrets);
+            run.cost_usd = r.cost_usd().unwrap_or(0.0);
+        }
+        _ => {}
+    }
+    run.transcript.push(AgentTestMessage {
+        message_type: msg.message_type().to_string(),
+        content: stream_parser::redact_secrets(line),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_line_collects_usage_and_result() {
+        let mut run = AgentTestRun {
+            agent_name: "a".into(),
+            model: "sonnet".into(),
+            status: AgentStatus::Completed,
+            result: None,
+            transcript: Vec::new(),
+            input_tokens: 0,
+            output_tokens: 0,
+            cost_usd: 0.0,
+            duration_ms: 0,
+        };
+        record_line(&mut run, "not json");
+        record_line(
+            &mut run,
+            r#"{"type":"assistant","message":{"usage":{"input_tokens":12,"output_tokens":3}}}"#,
+        );
+        record_line(
+            &mut run,
+            r#"{"type":"result","subtype":"error","result":"boom","total_cost_usd":0.02}"#,
+        );
+        assert_eq!(run.transcript.len(), 2);
+        assert_eq!(run.input_tokens, 12);
+        assert_eq!(run.output_tokens, 3);
+        assert_eq!(run.status, AgentStatus::Error);
+        assert_eq!(run.result.as_deref(), Some("boom"));
+        assert_eq!(run.cost_usd, 0.02);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::domain::models::{AgentSession, AgentStats, AgentStatus};
+use chrono::DateTime;
+use std::collections::HashMap;
+
+/// Aggregate sessions per agent, most used first.
+pub fn agent_stats(sessions: &[AgentSession]) -> Vec<AgentStats> {
+    let mut by_agent: HashMap<&str, Vec<&AgentSession>> = HashMap::new();
+    for session in sessions {
+        by_agent
+            .entry(session.agent_name.as_str())
+            .or_default()
+            .push(session);
+    }
+
+    let mut stats: Vec<AgentStats> = by_agent
+        .into_iter()
+        .map(|(agent_name, runs)| {
+            let count = |status: AgentStatus| runs.iter().filter(|s| s.status == status).count();
+            let completed = count(AgentStatus::Completed);
+            let errors = count(AgentStatus::Error);
+            let finished = completed + errors + count(AgentStatus::Stopped);
+            let rate = |n: usize| {
+                if finished == 0 {
+                    0.0
+                } else {
+                    n as f64 / finished as f64
+                }
+            };
+
+            let durations: Vec<f64> = runs.iter().filter_map(|s| duration_secs(s)).collect();
+            let total_cost_usd: f64 = runs.iter().map(|s| s.cost_usd).sum();
+            AgentStats {
+                agent_name: agent_name.to_string(),
+                run_count: runs.len() as u32,
+                completed_count: completed as u32,
+                error_count: errors as u32,
+                success_rate: rate(completed),
+                error_rate: rate(errors),
+                avg_duration_secs: (!durations.is_empty())
+                    .then(|| durations.iter().sum::<f64>() / durations.len() as f64),
+                input_tokens: runs.iter().map(|s| s.input_tokens).sum(),
+                output_tokens: runs.iter().map(|s| s.output_tokens).sum(),
+                total_cost_usd,
+                avg_cost_usd: total_cost_usd / runs.len() as f64,
+                last_used_at: runs.iter().map(|s| s.started_at.clone()).max(),
+            }
+        })
+        .collect();
+
+    stats.sort_by(|a, b| {
+        b.run_count
+            .cmp(&a.run_count)
+            .then_with(|| a.agent_name.cmp(&b.agent_name))
+    });
+    stats
+}
+
+pub(crate) fn duration_secs(session: &AgentSession) -> Option<f64> {
+    let start = DateTime::parse_from_rfc3339(&session.started_at).ok()?;
+    let end = DateTime::parse_from_rfc3339(session.ended_at.as_deref()?).ok()?;
+    Some((end - start).num_milliseconds() as f64 / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(
+        agent: &str,
+        status: AgentStatus,
+        start: &str,
+        end: Option<&str>,
+        cost: f64,
+    ) -> AgentSession {
+        AgentSession {
+            id: format!("{agent}-{start}"),
+            agent_name: agent.into(),
+            model: "sonnet".into(),
+            status,
+            prompt: String::new(),
+            started_at: start.into(),
+            ended_at: end.map(String::from),
+            input_tokens: 100,
+            output_tokens: 10,
+            cost_usd: cost,
+            project_dir: None,
+            branch: None,
+            cost_estimated: false,
+            profile: None,
+        }
+    }
+
+    #[test]
+    fn aggregates_runs_per_agent() {
+        let sessions = vec![
+            session(
+                "coder",
+                AgentStatus::Completed,
+                "2024-01-01T10:00:00Z",
+                Some("2024-01-01T10:01:00Z"),
+                0.5,
+            ),
+            session(
+                "coder",
+                AgentStatus::Error,
+                "2024-01-02T10:00:00Z",
+                Some("2024-01-02T10:03:00Z"),
+                0.25,
+            ),
+            session(
+                "coder",
+                AgentStatus::Running,
+                "2024-01-03T10:00:00Z",
+                None,
+                0.0,
+            ),
+            session(
+                "docs",
+                AgentStatus::Completed,
+                "2024-01-01T09:00:00Z",
+                None,
+                0.1,
+            ),
+        ];
+        let stats = agent_stats(&sessions);
+        assert_eq!(stats[0].agent_name, "coder");
+        assert_eq!(stats[0].run_count, 3);
+        assert_eq!(stats[0].success_rate, 0.5);
+        assert_eq!(stats[0].error_rate, 0.5);
+        assert_eq!(stats[0].avg_duration_secs, Some(120.0));
+        assert_eq!(stats[0].total_cost_usd, 0.75);
+        assert_eq!(stats[0].input_tokens, 300);
+        assert_eq!(
+            stats[0].last_used_at.as_deref(),
+            Some("2024-01-03T10:00:00Z")
+        );
+        assert_eq!(stats[1].avg_duration_secs, None);
+        assert_eq!(stats[1].success_rate, 1.0);
+    }
+}
@@ -0,0 +1,139 @@
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Versions kept per agent file; older ones are pruned.
+const MAX_VERSIONS: usize = 50;
+
+/// A saved copy of an agent definition, taken just before it was overwritten.
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentVersion {
+    pub id: String,
+    pub saved_at: String,
+    pub content: String,
+}
+
+/// Previous versions of agent files, stored as plain copies under
+/// `~/.clautron/agent-versions/<hash of the agent path>/<id>.md`.
+pub struct AgentVersionStore {
+    dir: PathBuf,
+}
+
+impl AgentVersionStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Save `content` as the newest version of `file_path`, unless it is the
+    /// same as the newest one already stored.
+    pub fn record(&self, file_path: &str, content: &str) -> Result<(), String> {
+        let dir = self.agent_dir(file_path);
+        let ids = list_ids(&dir);
+        if let Some(latest) = ids.last() {
+            if std::fs::read_to_string(dir.join(format!("{latest}.md"))).is_ok_and(|c| c == content)
+            {
+                return Ok(());
+            }
+        }
+
+        create_private_dir(&dir)?;
+        let mut millis = chrono::Utc::now().timestamp_millis();
+        while dir.join(format!("{millis:013}.md")).exists() {
+            millis += 1;
+        }
+        let path = dir.join(format!("{millis:013}.md"));
+        std::fs::write(&path, content).map_err(|e| e.to_string())?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+        }
+
+        for old in ids.iter().rev().skip(MAX_VERSIONS - 1) {
+            let _ = std::fs::remove_file(dir.join(format!("{old}.md")));
+        }
+        Ok(())
+    }
+
+    /// Stored versions of `file_path`, newest first.
+    pub fn list(&self, file_path: &str) -> Vec<AgentVersion> {
+        let dir = self.agent_dir(file_path);
+        list_ids(&dir)
+            .into_iter()
+            .rev()
+            .filter_map(|id| self.get(file_path, &id).ok())
+            .collect()
+    }
+
+    pub fn get(&self, file_path: &str, id: &str) -> Result<AgentVersion, String> {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("Invalid version id: {id}"));
+        }
+        let content = std::fs::read_to_string(self.agent_dir(file_path).join(format!("{id}.md")))
+            .map_err(|_| format!("Version not found: {id}"))?;
+        let saved_at = id
+            .parse::<i64>()
+            .ok()
+            .and_then(chrono::DateTime::from_timestamp_millis)
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default();
+        Ok(AgentVersion {
+            id: id.to_string(),
+            saved_at,
+            content,
+        })
+    }
+
+    fn agent_dir(&self, file_path: &str) -> PathBuf {
+        let hash = hex::encode(Sha256::digest(file_path.as_bytes()));
+        self.dir.join(&hash[..16])
+    }
+}
+
+/// Version ids in `dir`, oldest first.
+fn list_ids(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut ids: Vec<String> = entries
+        .flatten()
+        .filter_map(|e| {
+            let path = e.path();
+            (path.extension()? == "md").then(|| path.file_stem()?.to_str().map(String::from))?
+        })
+        .collect();
+    ids.sort();
+    ids
+}
+
+fn create_private_dir(dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_distinct_versions_newest_first() {
+        let dir =
+            std::env::temp_dir().join(format!("clautron-agent-versions-{}", uuid::Uuid::new_v4()));
+        let store = AgentVersionStore::new(dir.clone());
+        store.record("/p/.claude/agents/a.md", "one").unwrap();
+        store.record("/p/.claude/agents/a.md", "one").unwrap();
+        store.record("/p/.claude/agents/a.md", "two").unwrap();
+        store.record("/p/.claude/agents/b.md", "other").unwrap();
+
+        let versions = store.list("/p/.claude/agents/a.md");
+        let contents: Vec<&str> = versions.iter().map(|v| v.content.as_str()).collect();
+        assert_eq!(contents, vec!["two", "one"]);
+        assert!(store.get("/p/.claude/agents/a.md", "../x").is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
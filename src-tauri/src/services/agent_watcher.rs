@@ -1,3 +1,4 @@
+use crate::services::git_service::DiffHunk;
 use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
 use sha2::{Digest, Sha256};
 use std::path::PathBuf;
@@ -18,14 +19,23 @@ pub struct UnapprovedAgent {
     pub model: String,
     pub description: String,
     pub hash: String,
+    /// Whether an earlier version of this file was approved.
+    pub previously_approved: bool,
+    /// Changes since the last approved version, when its content is on record.
+    pub diff: Option<Vec<DiffHunk>>,
 }
 
 /// Compute SHA-256 hash of file contents.
 pub fn hash_file(path: &std::path::Path) -> Option<String> {
     let content = std::fs::read(path).ok()?;
+    Some(hash_bytes(&content))
+}
+
+/// Hex-encoded SHA-256 of `content`, the hash agent approvals are keyed on.
+pub fn hash_bytes(content: &[u8]) -> String {
     let mut hasher = Sha256::new();
-    hasher.update(&content);
-    Some(hex::encode(hasher.finalize()))
+    hasher.update(content);
+    hex::encode(hasher.finalize())
 }
 
 /// Recursively collect all `.md` files under a directory.
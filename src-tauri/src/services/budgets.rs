@@ -0,0 +1,176 @@
+//! Spending budgets: recorded spend for the current day, week and month
+//! against the limits in `BudgetConfig`.
+
+use crate::domain::models::{UsageGranularity, UsagePeriod};
+use crate::services::config_store::BudgetConfig;
+use crate::services::usage_history::period_start;
+use chrono::NaiveDate;
+use serde::Serialize;
+
+const DEFAULT_WARN_PERCENT: f64 = 80.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl BudgetPeriod {
+    fn granularity(self) -> UsageGranularity {
+        match self {
+            BudgetPeriod::Daily => UsageGranularity::Day,
+            BudgetPeriod::Weekly => UsageGranularity::Week,
+            BudgetPeriod::Monthly => UsageGranularity::Month,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BudgetPeriod::Daily => "daily",
+            BudgetPeriod::Weekly => "weekly",
+            BudgetPeriod::Monthly => "monthly",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetLevel {
+    /// Past the warning percentage.
+    Warning,
+    /// At or past the limit.
+    Exceeded,
+}
+
+/// One configured budget in its current period. Also the payload of
+/// `quota:budget-warning` and `quota:budget-exceeded`.
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetStatus {
+    pub period: BudgetPeriod,
+    /// First day of the current period, `YYYY-MM-DD`.
+    pub start: String,
+    pub limit_usd: f64,
+    pub spent_usd: f64,
+    /// None while under the warning percentage.
+    pub level: Option<BudgetLevel>,
+}
+
+/// First day the daily history must cover for `budget_statuses` on `today`.
+pub fn history_start(today: NaiveDate) -> String {
+    let today = today.format("%Y-%m-%d").to_string();
+    let week = period_start(&today, UsageGranularity::Week);
+    let month = period_start(&today, UsageGranularity::Month);
+    week.min(month)
+}
+
+/// Status of each configured budget on `today`, from `days` of daily usage
+/// going back to at least `history_start(today)`.
+pub fn budget_statuses(
+    config: &BudgetConfig,
+    days: &[UsagePeriod],
+    today: NaiveDate,
+) -> Vec<BudgetStatus> {
+    let today = today.format("%Y-%m-%d").to_string();
+    let warn_fraction = config.warn_percent.unwrap_or(DEFAULT_WARN_PERCENT) / 100.0;
+    [
+        (BudgetPeriod::Daily, config.daily_usd),
+        (BudgetPeriod::Weekly, config.weekly_usd),
+        (BudgetPeriod::Monthly, config.monthly_usd),
+    ]
+    .into_iter()
+    .filter_map(|(period, limit)| {
+        let limit_usd = limit.filter(|l| *l > 0.0)?;
+        let start = period_start(&today, period.granularity());
+        let spent_usd: f64 = days
+            .iter()
+            .filter(|d| d.start >= start && d.start <= today)
+            .map(|d| d.cost_usd)
+            .sum();
+        let level = if spent_usd >= limit_usd {
+            Some(BudgetLevel::Exceeded)
+        } else if spent_usd >= limit_usd * warn_fraction {
+            Some(BudgetLevel::Warning)
+        } else {
+            None
+        };
+        Some(BudgetStatus {
+            period,
+            start,
+            limit_usd,
+            spent_usd,
+            level,
+        })
+    })
+    .collect()
+}
+
+/// Why new sessions should be refused: the hard stop is on and a budget is
+/// used up. None otherwise.
+pub fn stop_reason(config: &BudgetConfig, statuses: &[BudgetStatus]) -> Option<String> {
+    if !config.hard_stop {
+        return None;
+    }
+    let exceeded = statuses
+        .iter()
+        .find(|s| s.level == Some(BudgetLevel::Exceeded))?;
+    Some(format!(
+        "The {} budget of ${:.2} is used up (${:.2} spent since {})",
+        exceeded.period.label(),
+        exceeded.limit_usd,
+        exceeded.spent_usd,
+        exceeded.start
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(start: &str, cost_usd: f64) -> UsagePeriod {
+        UsagePeriod {
+            start: start.into(),
+            cost_usd,
+            models: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn budgets_sum_spend_in_their_current_period() {
+        let config = BudgetConfig {
+            daily_usd: Some(5.0),
+            weekly_usd: Some(20.0),
+            monthly_usd: Some(100.0),
+            warn_percent: None,
+            hard_stop: true,
+        };
+        // Wednesday; the week starts in the previous month
+        let today = NaiveDate::from_ymd_opt(2026, 4, 1).unwrap();
+        assert_eq!(history_start(today), "2026-03-30");
+
+        let days = [
+            day("2026-03-30", 10.0),
+            day("2026-03-31", 6.0),
+            day("2026-04-01", 4.5),
+        ];
+        let statuses = budget_statuses(&config, &days, today);
+        let levels: Vec<_> = statuses.iter().map(|s| (s.period, s.level)).collect();
+        assert_eq!(
+            levels,
+            [
+                (BudgetPeriod::Daily, Some(BudgetLevel::Warning)),
+                (BudgetPeriod::Weekly, Some(BudgetLevel::Exceeded)),
+                (BudgetPeriod::Monthly, None),
+            ]
+        );
+        assert_eq!(statuses[2].spent_usd, 4.5);
+        assert!(stop_reason(&config, &statuses).unwrap().contains("weekly"));
+
+        let soft = BudgetConfig {
+            hard_stop: false,
+            ..config
+        };
+        assert_eq!(stop_reason(&soft, &statuses), None);
+    }
+}
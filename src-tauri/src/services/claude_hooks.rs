@@ -0,0 +1,257 @@
+use crate::domain::models::{HooksSettings, SettingsScope};
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+
+/// Hook events Claude Code fires, in lifecycle order.
+pub const HOOK_EVENTS: &[&str] = &[
+    "SessionStart",
+    "UserPromptSubmit",
+    "PreToolUse",
+    "PostToolUse",
+    "Notification",
+    "Stop",
+    "SubagentStop",
+    "PreCompact",
+    "SessionEnd",
+];
+
+/// Events whose `matcher` is a regex over tool names.
+const TOOL_EVENTS: &[&str] = &["PreToolUse", "PostToolUse"];
+
+pub fn settings_path(scope: SettingsScope, project_dir: Option<&str>) -> Result<PathBuf, String> {
+    let project = || {
+        project_dir
+            .map(|dir| Path::new(dir).join(".claude"))
+            .ok_or_else(|| "No project directory set".to_string())
+    };
+    match scope {
+        SettingsScope::Project => Ok(project()?.join("settings.json")),
+        SettingsScope::ProjectLocal => Ok(project()?.join("settings.local.json")),
+        SettingsScope::User => dirs::home_dir()
+            .map(|h| h.join(".claude/settings.json"))
+            .ok_or_else(|| "No home directory".to_string()),
+    }
+}
+
+/// The hooks of every settings file that can be resolved.
+pub fn list_hooks(project_dir: Option<&str>) -> Result<Vec<HooksSettings>, String> {
+    [
+        SettingsScope::Project,
+        SettingsScope::ProjectLocal,
+        SettingsScope::User,
+    ]
+    .into_iter()
+    .filter(|scope| settings_path(*scope, project_dir).is_ok())
+    .map(|scope| read_hooks(scope, project_dir))
+    .collect()
+}
+
+pub fn read_hooks(
+    scope: SettingsScope,
+    project_dir: Option<&str>,
+) -> Result<HooksSettings, String> {
+    let path = settings_path(scope, project_dir)?;
+    let root = read_root(&path)?;
+    let hooks = root.get("hooks").cloned().unwrap_or_else(|| json!({}));
+    Ok(HooksSettings {
+        scope,
+        path: path.to_string_lossy().to_string(),
+        exists: path.exists(),
+        errors: validate_hooks(&hooks),
+        hooks,
+    })
+}
+
+/// Replace the `hooks` section, leaving every other setting untouched. An
+/// empty object removes the section.
+pub fn write_hooks(
+    scope: SettingsScope,
+    project_dir: Option<&str>,
+    hooks: Value,
+) -> Result<HooksSettings, String> {
+    let errors = validate_hooks(&hooks);
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+    let path = settings_path(scope, project_dir)?;
+    let mut root = read_root(&path)?;
+    let settings = root
+        .as_object_mut()
+        .ok_or_else(|| format!("{} is not a JSON object", path.display()))?;
+    if hooks.as_object().is_some_and(Map::is_empty) {
+        settings.remove("hooks");
+    } else {
+        settings.insert("hooks".into(), hooks);
+    }
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    // Temp file and rename, so a running `claude` never reads half a file
+    let json = serde_json::to_string_pretty(&root).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, format!("{json}\n")).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp, &path).map_err(|e| e.to_string())?;
+
+    read_hooks(scope, project_dir)
+}
+
+/// Check a `hooks` value against the settings schema:
+/// `{ <event>: [{ matcher?: string, hooks: [{ type, command | prompt, timeout? }] }] }`.
+/// Returns every problem found, each prefixed with its JSON path.
+pub fn validate_hooks(hooks: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    let Some(events) = hooks.as_object() else {
+        return vec!["hooks: must be an object keyed by event name".into()];
+    };
+
+    for (event, groups) in events {
+        let at = format!("hooks.{event}");
+        if !HOOK_EVENTS.contains(&event.as_str()) {
+            errors.push(format!(
+                "{at}: unknown event; expected one of: {}",
+                HOOK_EVENTS.join(", ")
+            ));
+            continue;
+        }
+        let Some(groups) = groups.as_array() else {
+            errors.push(format!("{at}: must be an array of matcher groups"));
+            continue;
+        };
+        for (i, group) in groups.iter().enumerate() {
+            validate_group(event, group, &format!("{at}[{i}]"), &mut errors);
+        }
+    }
+    errors
+}
+
+fn validate_group(event: &str, group: &Value, at: &str, errors: &mut Vec<String>) {
+    let Some(group) = group.as_object() else {
+        errors.push(format!("{at}: must be an object"));
+        return;
+    };
+    for key in group.keys() {
+        if key != "matcher" && key != "hooks" {
+            errors.push(format!("{at}.{key}: unknown field"));
+        }
+    }
+    match group.get("matcher") {
+        // An empty matcher or `*` matches every tool
+        Some(Value::String(matcher))
+            if TOOL_EVENTS.contains(&event) && !matcher.is_empty() && matcher != "*" =>
+        {
+            if let Err(e) = regex::Regex::new(matcher) {
+                errors.push(format!("{at}.matcher: invalid regex: {e}"));
+            }
+        }
+        None | Some(Value::String(_)) => {}
+        Some(_) => errors.push(format!("{at}.matcher: must be a string")),
+    }
+    let Some(hooks) = group.get("hooks").and_then(Value::as_array) else {
+        errors.push(format!("{at}.hooks: must be an array of hooks"));
+        return;
+    };
+    if hooks.is_empty() {
+        errors.push(format!("{at}.hooks: must not be empty"));
+    }
+    for (i, hook) in hooks.iter().enumerate() {
+        validate_hook(hook, &format!("{at}.hooks[{i}]"), errors);
+    }
+}
+
+fn validate_hook(hook: &Value, at: &str, errors: &mut Vec<String>) {
+    let Some(hook) = hook.as_object() else {
+        errors.push(format!("{at}: must be an object"));
+        return;
+    };
+    let field = match hook.get("type").and_then(Value::as_str) {
+        Some("command") => "command",
+        Some("prompt") => "prompt",
+        Some(other) => {
+            errors.push(format!(
+                "{at}.type: unknown hook type `{other}`; expected command or prompt"
+            ));
+            return;
+        }
+        None => {
+            errors.push(format!("{at}.type: required"));
+            return;
+        }
+    };
+    match hook.get(field).and_then(Value::as_str) {
+        Some(value) if !value.trim().is_empty() => {}
+        _ => errors.push(format!("{at}.{field}: required non-empty string")),
+    }
+    if let Some(timeout) = hook.get("timeout") {
+        if !timeout.as_f64().is_some_and(|t| t > 0.0) {
+            errors.push(format!(
+                "{at}.timeout: must be a positive number of seconds"
+            ));
+        }
+    }
+    for key in hook.keys() {
+        if !matches!(key.as_str(), "type" | "command" | "prompt" | "timeout") {
+            errors.push(format!("{at}.{key}: unknown field"));
+        }
+    }
+}
+
+fn read_root(path: &Path) -> Result<Value, String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid JSON in {}: {e}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(json!({})),
+        Err(e) => Err(format!("Failed to read {}: {e}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation_reports_paths_and_writes_keep_other_settings() {
+        let errors = validate_hooks(&json!({
+            "PreToolUse": [{ "matcher": "Edit|(", "hooks": [{ "type": "command", "command": "" }] }],
+            "OnSave": [],
+            "Stop": [{ "hooks": [{ "type": "command", "command": "say done", "timeout": -1 }] }],
+        }));
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0].starts_with("hooks.OnSave: unknown event"));
+        assert!(errors[1].starts_with("hooks.PreToolUse[0].matcher: invalid regex"));
+        assert_eq!(
+            errors[2],
+            "hooks.PreToolUse[0].hooks[0].command: required non-empty string"
+        );
+        assert_eq!(
+            errors[3],
+            "hooks.Stop[0].hooks[0].timeout: must be a positive number of seconds"
+        );
+
+        let dir = std::env::temp_dir().join(format!("clautron-hooks-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join(".claude")).unwrap();
+        std::fs::write(
+            dir.join(".claude/settings.json"),
+            r#"{"permissions": {"allow": ["Bash(ls)"]}}"#,
+        )
+        .unwrap();
+        let project = dir.to_str();
+
+        let hooks = json!({
+            "PostToolUse": [{ "matcher": "Edit|Write", "hooks": [{ "type": "command", "command": "cargo fmt" }] }]
+        });
+        assert!(write_hooks(SettingsScope::Project, project, json!({ "Stop": {} })).is_err());
+        let saved = write_hooks(SettingsScope::Project, project, hooks.clone()).unwrap();
+        assert_eq!(saved.hooks, hooks);
+        assert!(saved.errors.is_empty());
+
+        let root = read_root(&dir.join(".claude/settings.json")).unwrap();
+        assert_eq!(root["permissions"]["allow"][0], "Bash(ls)");
+
+        write_hooks(SettingsScope::Project, project, json!({})).unwrap();
+        let root = read_root(&dir.join(".claude/settings.json")).unwrap();
+        assert!(root.get("hooks").is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,119 @@
+//! CLAUDE.md memory files, which Claude Code loads into every agent's context.
+
+use crate::domain::models::{MemoryFile, MemoryScope};
+use std::path::{Path, PathBuf};
+
+const SCOPES: [MemoryScope; 3] = [
+    MemoryScope::Project,
+    MemoryScope::ProjectLocal,
+    MemoryScope::User,
+];
+
+/// Where the memory file for `scope` lives. Project scopes need a project.
+pub fn memory_path(scope: MemoryScope, project_dir: Option<&str>) -> Result<PathBuf, String> {
+    let project = || project_dir.map(Path::new).ok_or("No project directory set");
+    match scope {
+        MemoryScope::Project => Ok(project()?.join("CLAUDE.md")),
+        MemoryScope::ProjectLocal => Ok(project()?.join("CLAUDE.local.md")),
+        MemoryScope::User => dirs::home_dir()
+            .map(|h| h.join(".claude").join("CLAUDE.md"))
+            .ok_or_else(|| "No home directory".to_string()),
+    }
+}
+
+/// Every memory file that applies to the project, existing or not.
+pub fn list_memory_files(project_dir: Option<&str>) -> Vec<MemoryFile> {
+    SCOPES
+        .iter()
+        .filter_map(|scope| read_memory(*scope, project_dir).ok())
+        .collect()
+}
+
+pub fn read_memory(scope: MemoryScope, project_dir: Option<&str>) -> Result<MemoryFile, String> {
+    let path = memory_path(scope, project_dir)?;
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(format!("Failed to read {}: {e}", path.display())),
+    };
+    Ok(MemoryFile {
+        scope,
+        path: path.to_string_lossy().to_string(),
+        exists: content.is_some(),
+        content: content.unwrap_or_default(),
+    })
+}
+
+/// Write (creating if needed) the memory file for `scope`. A new
+/// `CLAUDE.local.md` is added to the project's `.gitignore`, since it holds
+/// personal notes.
+pub fn write_memory(
+    scope: MemoryScope,
+    project_dir: Option<&str>,
+    content: &str,
+) -> Result<MemoryFile, String> {
+    let path = memory_path(scope, project_dir)?;
+    let created = !path.exists();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, content).map_err(|e| e.to_string())?;
+
+    if created && scope == MemoryScope::ProjectLocal {
+        if let Some(dir) = project_dir {
+            ignore_in_git(Path::new(dir), "CLAUDE.local.md")?;
+        }
+    }
+    read_memory(scope, project_dir)
+}
+
+/// Append `entry` to the repository's `.gitignore` unless it's listed already.
+fn ignore_in_git(project_dir: &Path, entry: &str) -> Result<(), String> {
+    if !project_dir.join(".git").exists() {
+        return Ok(());
+    }
+    let gitignore = project_dir.join(".gitignore");
+    let mut content = std::fs::read_to_string(&gitignore).unwrap_or_default();
+    let listed = content.lines().any(|line| {
+        let line = line.trim();
+        line == entry || line.strip_prefix('/') == Some(entry)
+    });
+    if listed {
+        return Ok(());
+    }
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(entry);
+    content.push('\n');
+    std::fs::write(&gitignore, content).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creating_local_memory_ignores_it_in_git() {
+        let dir = std::env::temp_dir().join(format!("clautron-memory-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::write(dir.join(".gitignore"), "target").unwrap();
+        let project = dir.to_str();
+
+        assert!(
+            !read_memory(MemoryScope::ProjectLocal, project)
+                .unwrap()
+                .exists
+        );
+        let file = write_memory(MemoryScope::ProjectLocal, project, "# Notes\n").unwrap();
+        assert!(file.exists);
+        assert_eq!(file.content, "# Notes\n");
+        write_memory(MemoryScope::ProjectLocal, project, "# More\n").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.join(".gitignore")).unwrap(),
+            "target\nCLAUDE.local.md\n"
+        );
+        assert!(memory_path(MemoryScope::Project, None).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
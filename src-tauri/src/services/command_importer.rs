@@ -0,0 +1,286 @@
+use crate::domain::models::{StepStatus, Workflow, WorkflowEdge, WorkflowStatus, WorkflowStep};
+use crate::domain::ports::WorkflowRepository;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use uuid::Uuid;
+
+/// `## Phase 1: Plan`, `### Step 2 - Build`, `## 3. Test`
+static HEADING_PHASE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^#{1,6}\s+(?:(?:phase|step)\s+)?(\d+)\s*[.:)\-]?\s*(.*)$").unwrap()
+});
+
+/// `1. Plan the change`, `2) Write tests` (top level only, no indentation)
+static LIST_PHASE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\d+)[.)]\s+(.+)$").unwrap());
+
+/// Vertical gap between imported steps on the canvas.
+const STEP_SPACING_Y: f64 = 150.0;
+
+/// A single phase of a custom command, becoming one workflow step.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommandPhase {
+    pub title: String,
+    /// Full step prompt: shared command preamble plus this phase's instructions.
+    pub prompt: String,
+}
+
+/// A Claude Code custom slash command parsed from `.claude/commands/`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomCommand {
+    /// Command name as typed after `/`; subdirectories become `dir:name`.
+    pub name: String,
+    pub file_path: String,
+    pub description: Option<String>,
+    pub model: Option<String>,
+    pub phases: Vec<CommandPhase>,
+}
+
+/// Frontmatter fields used by the importer; others are ignored.
+#[derive(Debug, Default, Deserialize)]
+struct CommandFrontmatter {
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    model: Option<String>,
+}
+
+/// List and preview every custom command in the project.
+pub fn list_commands(project_dir: &str) -> Result<Vec<CustomCommand>, String> {
+    let root = PathBuf::from(project_dir).join(".claude/commands");
+    if !root.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut files = Vec::new();
+    collect_markdown_files(&root, &mut files);
+
+    let mut commands = Vec::new();
+    for path in files {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let name = path
+            .strip_prefix(&root)
+            .unwrap_or(&path)
+            .with_extension("")
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join(":");
+        commands.push(parse_command(&name, &path.to_string_lossy(), &content));
+    }
+
+    commands.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(commands)
+}
+
+/// Convert a custom command into a draft workflow: one step per phase,
+/// chained in order, each receiving the previous phase's output as context.
+pub async fn import_command(
+    repo: &dyn WorkflowRepository,
+    project_dir: &str,
+    command_name: &str,
+    agent_name: &str,
+    model: Option<String>,
+) -> Result<Workflow, String> {
+    let command = list_commands(project_dir)?
+        .into_iter()
+        .find(|c| c.name == command_name)
+        .ok_or_else(|| format!("Custom command not found: /{command_name}"))?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let workflow = Workflow {
+        id: Uuid::new_v4().to_string(),
+        name: format!("/{}", command.name),
+        description: command.description.clone(),
+        status: WorkflowStatus::Draft,
+        created_at: now.clone(),
+        updated_at: now.clone(),
+        max_parallel: None,
+    };
+    repo.save_workflow(&workflow).await.map_err(|e| e.to_string())?;
+
+    let model = model
+        .or(command.model.clone())
+        .unwrap_or_else(|| "sonnet".to_string());
+    let mut previous: Option<String> = None;
+    for (i, phase) in command.phases.iter().enumerate() {
+        let step = WorkflowStep {
+            id: Uuid::new_v4().to_string(),
+            workflow_id: workflow.id.clone(),
+            agent_name: agent_name.to_string(),
+            model: model.clone(),
+            prompt: phase.prompt.clone(),
+            spec_path: None,
+            status: StepStatus::Pending,
+            session_id: None,
+            position_x: 0.0,
+            position_y: i as f64 * STEP_SPACING_Y,
+            created_at: now.clone(),
+            pass_context: previous.is_some(),
+            result_output: None,
+            failure_policy: Default::default(),
+            join_mode: Default::default(),
+            kind: Default::default(),
+            gate_command: None,
+            prompt_template_id: None,
+            template_vars: Default::default(),
+            commit_message: None,
+        };
+        repo.save_step(&step).await.map_err(|e| e.to_string())?;
+
+        if let Some(source) = previous.take() {
+            let edge = WorkflowEdge {
+                id: Uuid::new_v4().to_string(),
+                workflow_id: workflow.id.clone(),
+                source_step_id: source,
+                target_step_id: step.id.clone(),
+                condition: Default::default(),
+            };
+            repo.save_edge(&edge).await.map_err(|e| e.to_string())?;
+        }
+        previous = Some(step.id);
+    }
+
+    Ok(workflow)
+}
+
+fn collect_markdown_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_markdown_files(&path, out);
+        } else if path.extension().and_then(|e| e.to_str()) == Some("md") {
+            out.push(path);
+        }
+    }
+}
+
+/// Parse a command file. Frontmatter is optional. Phases come from numbered
+/// headings if present, else top-level numbered list items; a command with
+/// fewer than two phases becomes a single step with the whole body.
+fn parse_command(name: &str, file_path: &str, content: &str) -> CustomCommand {
+    let (frontmatter, body) = split_frontmatter(content);
+    let fm: CommandFrontmatter = frontmatter
+        .and_then(|f| serde_yaml::from_str(f).ok())
+        .unwrap_or_default();
+
+    CustomCommand {
+        name: name.to_string(),
+        file_path: file_path.to_string(),
+        description: fm.description,
+        model: fm.model,
+        phases: split_phases(name, body.trim()),
+    }
+}
+
+fn split_frontmatter(content: &str) -> (Option<&str>, &str) {
+    let trimmed = content.trim_start();
+    let Some(after_first) = trimmed.strip_prefix("---") else {
+        return (None, content);
+    };
+    match after_first.find("\n---") {
+        Some(end) => {
+            let rest = &after_first[end + 4..];
+            (Some(&after_first[..end]), rest.strip_prefix('\n').unwrap_or(rest))
+        }
+        None => (None, content),
+    }
+}
+
+fn split_phases(name: &str, body: &str) -> Vec<CommandPhase> {
+    let lines: Vec<&str> = body.lines().collect();
+
+    // Ignore anything that looks like a phase inside fenced code blocks
+    let mut in_fence = false;
+    let mut headings = Vec::new();
+    let mut items = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        if let Some(cap) = HEADING_PHASE_RE.captures(line) {
+            headings.push((i, cap[2].to_string()));
+        } else if let Some(cap) = LIST_PHASE_RE.captures(line) {
+            items.push((i, cap[2].to_string()));
+        }
+    }
+
+    let starts = if headings.len() >= 2 { headings } else { items };
+    if starts.len() < 2 {
+        return vec![CommandPhase {
+            title: name.to_string(),
+            prompt: body.to_string(),
+        }];
+    }
+
+    let preamble = lines[..starts[0].0].join("\n").trim().to_string();
+    let total = starts.len();
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, (start, title))| {
+            let end = starts.get(n + 1).map(|(s, _)| *s).unwrap_or(lines.len());
+            let title = title.replace("**", "").trim().to_string();
+            let details = lines[start + 1..end].join("\n").trim().to_string();
+
+            let mut prompt = String::new();
+            if !preamble.is_empty() {
+                prompt.push_str(&preamble);
+                prompt.push_str("\n\n");
+            }
+            prompt.push_str(&format!("## Phase {} of {}: {}", n + 1, total, title));
+            if !details.is_empty() {
+                prompt.push_str("\n\n");
+                prompt.push_str(&details);
+            }
+            CommandPhase { title, prompt }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_numbered_list_into_phases_with_preamble() {
+        let content = "---\ndescription: Ship a feature\nmodel: opus\n---\nWork on $ARGUMENTS.\n\n1. **Plan** the change\n   - read the code\n2. Implement it\n\n```\n3. not a phase\n```\n";
+        let cmd = parse_command("ship", "ship.md", content);
+        assert_eq!(cmd.description.as_deref(), Some("Ship a feature"));
+        assert_eq!(cmd.model.as_deref(), Some("opus"));
+        assert_eq!(cmd.phases.len(), 2);
+        assert_eq!(cmd.phases[0].title, "Plan the change");
+        assert!(cmd.phases[0]
+            .prompt
+            .starts_with("Work on $ARGUMENTS.\n\n## Phase 1 of 2:"));
+        assert!(cmd.phases[0].prompt.ends_with("- read the code"));
+        assert!(cmd.phases[1].prompt.contains("3. not a phase"));
+    }
+
+    #[test]
+    fn headings_take_precedence_and_single_phase_fallback() {
+        let content = "## Phase 1: Plan\n1. a\n2. b\n## Phase 2: Build\nDo it";
+        let cmd = parse_command("x", "x.md", content);
+        let titles: Vec<&str> = cmd.phases.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, vec!["Plan", "Build"]);
+
+        let cmd = parse_command("review", "review.md", "Review the diff.");
+        assert_eq!(
+            cmd.phases,
+            vec![CommandPhase {
+                title: "review".into(),
+                prompt: "Review the diff.".into()
+            }]
+        );
+    }
+}
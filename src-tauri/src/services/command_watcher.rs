@@ -0,0 +1,53 @@
+use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Event emitted when slash command files change on disk.
+#[derive(Clone, serde::Serialize)]
+pub struct CommandsChangedEvent {
+    /// Files that were added, modified or removed.
+    pub changed_files: Vec<String>,
+}
+
+/// Start watching .claude/commands/ for changes.
+/// Debounced at 500ms. Emits `commands:changed` on file changes.
+pub fn start_watching(
+    app: AppHandle,
+    commands_dir: PathBuf,
+) -> Option<notify_debouncer_mini::Debouncer<notify::RecommendedWatcher>> {
+    if !commands_dir.exists() {
+        return None;
+    }
+
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(500),
+        move |events: Result<Vec<notify_debouncer_mini::DebouncedEvent>, notify::Error>| {
+            if let Ok(events) = events {
+                let changed: Vec<String> = events
+                    .iter()
+                    .filter(|e| e.kind == DebouncedEventKind::Any)
+                    .filter(|e| e.path.extension().and_then(|ext| ext.to_str()) == Some("md"))
+                    .map(|e| e.path.to_string_lossy().to_string())
+                    .collect();
+
+                if !changed.is_empty() {
+                    let _ = app.emit(
+                        "commands:changed",
+                        CommandsChangedEvent {
+                            changed_files: changed,
+                        },
+                    );
+                }
+            }
+        },
+    )
+    .ok()?;
+
+    debouncer
+        .watcher()
+        .watch(&commands_dir, notify::RecursiveMode::Recursive)
+        .ok()?;
+
+    Some(debouncer)
+}
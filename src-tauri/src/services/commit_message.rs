@@ -0,0 +1,144 @@
+use crate::services::git_service::FileDiff;
+
+/// Diff text sent to Claude is cut at this many characters; the file list
+/// is always complete.
+const MAX_DIFF_CHARS: usize = 24_000;
+
+const TYPES: &[&str] = &[
+    "feat", "fix", "refactor", "perf", "docs", "test", "build", "ci", "chore", "style",
+];
+
+/// Prompt asking Claude for a conventional-commit message describing `diffs`.
+pub fn build_prompt(diffs: &[FileDiff]) -> String {
+    let files: Vec<String> = diffs
+        .iter()
+        .map(|d| format!("- {} ({})", d.path, d.change_type))
+        .collect();
+
+    let mut diff_text = String::new();
+    let mut omitted = 0;
+    for diff in diffs {
+        let text = render_diff(diff);
+        if diff_text.len() + text.len() > MAX_DIFF_CHARS {
+            omitted += 1;
+            continue;
+        }
+        diff_text.push_str(&text);
+    }
+    if omitted > 0 {
+        diff_text.push_str(&format!("\n[{omitted} more file(s) not shown]\n"));
+    }
+
+    format!(
+        "Write a git commit message for the changes below.\n\n\
+         ## Rules\n\n\
+         - Conventional Commits: `<type>(<optional scope>): <summary>`, type one of {types}\n\
+         - Summary in the imperative mood, lower case, no trailing period, at most 72 characters\n\
+         - Then a blank line and a short body explaining what changed and why, wrapped at 72 \
+         characters; leave it out for trivial changes\n\
+         - Respond with only the commit message, no preamble and no code fences\n\n\
+         ## Files\n\n{files}\n\n\
+         ## Diff\n\n{diff_text}",
+        types = TYPES.join(", "),
+        files = files.join("\n"),
+    )
+}
+
+/// Clean up Claude's answer: drop code fences, surrounding quotes and any
+/// `Commit message:` lead-in, and trim trailing whitespace on every line.
+pub fn parse_message(output: &str) -> Result<String, String> {
+    let mut text = output.trim();
+    if let Some(inner) = text.strip_prefix("```") {
+        // Skip the fence's language tag, if any
+        let inner = inner.split_once('\n').map_or("", |(_, rest)| rest);
+        text = inner.trim_end().strip_suffix("```").unwrap_or(inner).trim();
+    }
+    for lead in ["Commit message:", "commit message:"] {
+        if let Some(rest) = text.strip_prefix(lead) {
+            text = rest.trim();
+        }
+    }
+    if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+        text = text[1..text.len() - 1].trim();
+    }
+
+    let message = text
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if message.is_empty() {
+        return Err("Claude returned an empty commit message".into());
+    }
+    Ok(message)
+}
+
+fn render_diff(diff: &FileDiff) -> String {
+    let mut text = format!("--- {} ({})\n", diff.path, diff.change_type);
+    for hunk in &diff.hunks {
+        text.push_str(&hunk.header);
+        text.push('\n');
+        for line in &hunk.lines {
+            let prefix = match line.line_type.as_str() {
+                "add" => '+',
+                "remove" => '-',
+                _ => ' ',
+            };
+            text.push(prefix);
+            text.push_str(&line.content);
+            text.push('\n');
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git_service::{DiffHunk, DiffLine};
+
+    #[test]
+    fn prompt_carries_the_diff_and_answers_are_cleaned() {
+        let diff = FileDiff {
+            path: "src/lib.rs".into(),
+            change_type: "modified".into(),
+            hunks: vec![DiffHunk {
+                header: "@@ -1,1 +1,1 @@".into(),
+                lines: vec![
+                    DiffLine {
+                        line_type: "remove".into(),
+                        content: "let a = 1;".into(),
+                        old_line: Some(1),
+                        new_line: None,
+                        segments: None,
+                    },
+                    DiffLine {
+                        line_type: "add".into(),
+                        content: "let a = 2;".into(),
+                        old_line: None,
+                        new_line: Some(1),
+                        segments: None,
+                    },
+                ],
+            }],
+            old_path: None,
+            is_binary: false,
+            old_size: None,
+            new_size: None,
+            staged: false,
+        };
+        let prompt = build_prompt(&[diff]);
+        assert!(prompt.contains("- src/lib.rs (modified)"));
+        assert!(prompt.contains("@@ -1,1 +1,1 @@\n-let a = 1;\n+let a = 2;\n"));
+
+        assert_eq!(
+            parse_message("```text\nfix(parser): handle empty input  \n\nBody.\n```").unwrap(),
+            "fix(parser): handle empty input\n\nBody."
+        );
+        assert_eq!(
+            parse_message("Commit message: \"feat: add export\"").unwrap(),
+            "feat: add export"
+        );
+        assert!(parse_message("```\n```").is_err());
+    }
+}
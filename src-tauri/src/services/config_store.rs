@@ -1,3 +1,5 @@
+use crate::adapters::claude_cli_runner::{ENV_ALLOWLIST, ENV_DENYLIST};
+use crate::domain::models::{ClaudeProfile, DirtyTreePolicy, ModelPrice, RedactionConfig};
 use crate::error::AppError;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -5,13 +7,572 @@ use std::path::PathBuf;
 /// Persistent app configuration stored at ~/.clautron/config.json
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppConfig {
+    /// The open project, one of the keys of `projects`.
     pub project_path: Option<String>,
     pub window_width: Option<f64>,
     pub window_height: Option<f64>,
-    /// SHA-256 hashes of approved agent definition files.
-    /// Key: file path relative to project, Value: hex-encoded SHA-256 hash.
-    #[serde(default)]
+    /// Legacy global approval map, migrated into `AgentApprovalStore` on
+    /// startup and left empty afterwards.
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
     pub approved_agent_hashes: std::collections::HashMap<String, String>,
+    /// Credentials for issue tracker imports.
+    #[serde(default)]
+    pub integrations: IntegrationsConfig,
+    /// Show a desktop notification when a spec becomes due soon or overdue.
+    #[serde(default)]
+    pub notify_spec_due: bool,
+    /// Secret redaction for every project; merged with the project's own.
+    #[serde(default)]
+    pub redaction: RedactionConfig,
+    /// Spending limits, checked against Claude Code's recorded usage.
+    #[serde(default)]
+    pub budgets: BudgetConfig,
+    /// Expected capacity of the subscription's rolling usage window.
+    #[serde(default)]
+    pub rate_window: RateWindowConfig,
+    /// Desktop notifications for spend and usage thresholds.
+    #[serde(default)]
+    pub quota_notifications: QuotaNotifyConfig,
+    /// Desktop notifications for agent events.
+    #[serde(default)]
+    pub agent_notifications: AgentNotifyConfig,
+    /// Local HTTP API for scripts and editors.
+    #[serde(default)]
+    pub http_api: HttpApiConfig,
+    /// Prices overriding the built-in table used to estimate costs Claude
+    /// Code doesn't report, keyed by model name or a part of it (`"opus"`).
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub pricing: std::collections::HashMap<String, ModelPrice>,
+    /// Model preselected when starting an agent that doesn't name one;
+    /// a project's `default_model` takes precedence.
+    #[serde(default)]
+    pub default_model: Option<String>,
+    /// Claude config directories besides the default one, e.g. one per
+    /// account. Usage is read from each and sessions can pick one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<ClaudeProfile>,
+    /// Every known project with its settings, keyed by project path. Also
+    /// the recent projects list.
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub projects: std::collections::HashMap<String, ProjectSettings>,
+    /// The open project's own config file, applied over the settings above.
+    /// Never written to config.json.
+    #[serde(skip)]
+    pub project_file: Option<ProjectFileConfig>,
+}
+
+impl AppConfig {
+    /// Settings of the current project, or the defaults when none is open.
+    pub fn project_settings(&self) -> ProjectSettings {
+        let mut settings = self
+            .project_path
+            .as_ref()
+            .and_then(|path| self.projects.get(path))
+            .cloned()
+            .unwrap_or_default();
+        if let Some(file) = &self.project_file {
+            file.apply(&mut settings);
+        }
+        settings
+    }
+
+    /// Variables the project's file requests that the user hasn't allowed
+    /// and could allow.
+    pub fn pending_project_env(&self) -> Vec<String> {
+        let allowed = self.project_settings().extra_env;
+        self.requested_project_env(|name| {
+            !allowed.iter().any(|a| a == name)
+                && !ENV_ALLOWLIST.contains(&name)
+                && !ENV_DENYLIST.contains(&name)
+        })
+    }
+
+    /// Variables the project's file requests that are never passed to agents.
+    pub fn refused_project_env(&self) -> Vec<String> {
+        self.requested_project_env(|name| ENV_DENYLIST.contains(&name))
+    }
+
+    fn requested_project_env(&self, keep: impl Fn(&str) -> bool) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .project_file
+            .iter()
+            .flat_map(|file| file.env.iter())
+            .filter(|name| keep(name))
+            .cloned()
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Pass `names` to the open project's agents from now on.
+    pub fn allow_project_env(&mut self, names: &[String]) -> Result<(), AppError> {
+        for name in names {
+            check_env_name(name)?;
+        }
+        let settings = self.open_project_settings()?;
+        for name in names {
+            if !settings.extra_env.contains(name) && !ENV_ALLOWLIST.contains(&name.as_str()) {
+                settings.extra_env.push(name.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Stop passing `names` to the open project's agents.
+    pub fn revoke_project_env(&mut self, names: &[String]) -> Result<(), AppError> {
+        self.open_project_settings()?
+            .extra_env
+            .retain(|name| !names.contains(name));
+        Ok(())
+    }
+
+    /// Let the health check launch the open project's MCP server `name`
+    /// while its launch hash stays `hash`.
+    pub fn approve_mcp_server(&mut self, name: &str, hash: String) -> Result<(), AppError> {
+        self.open_project_settings()?
+            .approved_mcp_servers
+            .insert(name.to_string(), hash);
+        Ok(())
+    }
+
+    /// The open project's stored settings, without its file applied.
+    fn open_project_settings(&mut self) -> Result<&mut ProjectSettings, AppError> {
+        let path = self
+            .project_path
+            .clone()
+            .ok_or_else(|| AppError::Process("No project open".into()))?;
+        Ok(self.projects.entry(path).or_default())
+    }
+
+    /// The project's default model, else the user's.
+    pub fn default_model(&self) -> Option<String> {
+        self.project_settings()
+            .default_model
+            .or_else(|| self.default_model.clone())
+    }
+
+    /// Spending limits: the user's, tightened by the project's config file.
+    /// A project can add or lower limits and turn on the hard stop, but
+    /// never loosen what the user set.
+    pub fn budgets(&self) -> BudgetConfig {
+        let project = self.project_file.as_ref().and_then(|f| f.budgets.as_ref());
+        match project {
+            Some(project) => self.budgets.tightened_by(project),
+            None => self.budgets.clone(),
+        }
+    }
+
+    /// Load the open project's config file into `project_file`. A file that
+    /// can't be read is reported and left out.
+    pub fn reload_project_file(&mut self) {
+        self.project_file = match self.project_path.as_deref().map(load_project_file) {
+            Some(Ok(file)) => file,
+            Some(Err(e)) => {
+                eprintln!("Project config not applied: {e}");
+                None
+            }
+            None => None,
+        };
+    }
+
+    /// Known projects, most recently opened first.
+    pub fn known_projects(&self) -> Vec<KnownProject> {
+        let mut projects: Vec<KnownProject> = self
+            .projects
+            .iter()
+            .map(|(path, settings)| KnownProject {
+                path: path.clone(),
+                name: settings.name.clone().unwrap_or_else(|| folder_name(path)),
+                last_opened: settings.last_opened.clone(),
+                exists: std::path::Path::new(path).is_dir(),
+            })
+            .collect();
+        // Fixed-width UTC timestamps sort chronologically as strings
+        projects.sort_by(|a, b| {
+            b.last_opened
+                .cmp(&a.last_opened)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        projects
+    }
+
+    /// Add a project without opening it. A known project keeps its settings
+    /// and only takes the new name, if one is given.
+    pub fn add_project(&mut self, path: &str, name: Option<String>) {
+        let settings = self.projects.entry(path.to_string()).or_default();
+        if name.is_some() {
+            settings.name = name;
+        }
+    }
+
+    /// Make `path` the open project, adding it if it's new.
+    pub fn open_project(&mut self, path: &str) {
+        self.add_project(path, None);
+        if let Some(settings) = self.projects.get_mut(path) {
+            settings.last_opened = Some(
+                chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            );
+        }
+        self.project_path = Some(path.to_string());
+    }
+
+    /// Forget a project and its settings. The open project can't be removed.
+    pub fn remove_project(&mut self, path: &str) -> Result<(), AppError> {
+        if self.project_path.as_deref() == Some(path) {
+            return Err(AppError::Process(
+                "Open another project before removing this one".into(),
+            ));
+        }
+        self.projects.remove(path);
+        Ok(())
+    }
+
+    /// User-level redaction combined with the current project's: a built-in
+    /// disabled in either is off, and both sets of custom patterns apply.
+    pub fn redaction(&self) -> RedactionConfig {
+        let mut merged = self.redaction.clone();
+        let project = self.project_settings().redaction;
+        for name in project.disabled_builtins {
+            if !merged.disabled_builtins.contains(&name) {
+                merged.disabled_builtins.push(name);
+            }
+        }
+        merged.custom_patterns.extend(project.custom_patterns);
+        merged
+    }
+}
+
+/// Where a project keeps its config file, relative to its root; the first
+/// that exists is used.
+const PROJECT_FILES: &[&str] = &[".clautron.json", ".clautron/config.json"];
+
+/// Team settings committed to a project, overriding each user's own for
+/// that project. Unset fields leave the user's setting alone.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ProjectFileConfig {
+    #[serde(default)]
+    pub default_model: Option<String>,
+    /// Name of one of the user's Claude profiles.
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Only tightens the user's limits; see `AppConfig::budgets`.
+    #[serde(default)]
+    pub budgets: Option<BudgetConfig>,
+    #[serde(default)]
+    pub auto_branch: Option<bool>,
+    #[serde(default)]
+    pub hold_on_conflict: Option<bool>,
+    #[serde(default)]
+    pub dirty_tree: Option<DirtyTreePolicy>,
+    /// Extra secret patterns. Built-in patterns can't be turned off from a
+    /// project's file.
+    #[serde(default)]
+    pub redaction_patterns: Vec<String>,
+    /// Variables the project's agents need. Only requested: each is passed
+    /// once the user allows it (`extra_env`).
+    #[serde(default)]
+    pub env: Vec<String>,
+}
+
+impl ProjectFileConfig {
+    fn apply(&self, settings: &mut ProjectSettings) {
+        if self.default_model.is_some() {
+            settings.default_model = self.default_model.clone();
+        }
+        if self.profile.is_some() {
+            settings.profile = self.profile.clone();
+        }
+        if let Some(auto_branch) = self.auto_branch {
+            settings.auto_branch = auto_branch;
+        }
+        if let Some(hold) = self.hold_on_conflict {
+            settings.hold_on_conflict = hold;
+        }
+        if let Some(policy) = self.dirty_tree {
+            settings.dirty_tree = policy;
+        }
+        settings
+            .redaction
+            .custom_patterns
+            .extend(self.redaction_patterns.iter().cloned());
+    }
+}
+
+/// A variable name that may be passed to agents: letters, digits and `_`,
+/// and none of the names that can inject code into the process.
+fn check_env_name(name: &str) -> Result<(), AppError> {
+    let valid = name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name.chars().next().is_some_and(|c| !c.is_ascii_digit());
+    if !valid {
+        return Err(AppError::Process(format!(
+            "'{name}' is not a valid variable name"
+        )));
+    }
+    if ENV_DENYLIST.contains(&name) {
+        return Err(AppError::Process(format!(
+            "{name} can't be passed to agents"
+        )));
+    }
+    Ok(())
+}
+
+/// The config file of the project at `project_dir`; None when it has none.
+pub fn load_project_file(project_dir: &str) -> Result<Option<ProjectFileConfig>, String> {
+    let Some(path) = PROJECT_FILES
+        .iter()
+        .map(|name| std::path::Path::new(project_dir).join(name))
+        .find(|path| path.is_file())
+    else {
+        return Ok(None);
+    };
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Cannot parse {}: {e}", path.display()))
+}
+
+/// Entry of the recent projects list.
+#[derive(Debug, Clone, Serialize)]
+pub struct KnownProject {
+    pub path: String,
+    /// The configured name, else the folder name.
+    pub name: String,
+    pub last_opened: Option<String>,
+    /// False once the folder has been moved or deleted.
+    pub exists: bool,
+}
+
+fn folder_name(path: &str) -> String {
+    std::path::Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ProjectSettings {
+    /// Shown in the recent projects list instead of the folder name.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// RFC 3339 time the project was last opened.
+    #[serde(default)]
+    pub last_opened: Option<String>,
+    /// Also write session logs to `~/.clautron/logs/<session_id>.jsonl`.
+    #[serde(default)]
+    pub mirror_logs: bool,
+    #[serde(default)]
+    pub redaction: RedactionConfig,
+    /// Start each agent on a new branch named after its spec or agent.
+    #[serde(default)]
+    pub auto_branch: bool,
+    /// Pause a workflow step's agent when it writes a file another running
+    /// agent has written, until that agent finishes.
+    #[serde(default)]
+    pub hold_on_conflict: bool,
+    /// Guard against starting agents on top of uncommitted changes.
+    #[serde(default)]
+    pub dirty_tree: DirtyTreePolicy,
+    /// Profile the project's sessions run under unless started with
+    /// another; Claude Code's own config directory when None.
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Model preselected when starting an agent that doesn't name one.
+    #[serde(default)]
+    pub default_model: Option<String>,
+    /// Variables passed to the project's agents on top of the runner's
+    /// allowlist. Only the user adds these, never a project's file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_env: Vec<String>,
+    /// `.mcp.json` stdio servers the health check may launch, by name, with
+    /// the `launch_hash` the user approved. Only the user adds these.
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub approved_mcp_servers: std::collections::HashMap<String, String>,
+}
+
+/// USD spending limits per calendar day, week (from Monday) and month.
+/// Unset limits aren't checked.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BudgetConfig {
+    #[serde(default)]
+    pub daily_usd: Option<f64>,
+    #[serde(default)]
+    pub weekly_usd: Option<f64>,
+    #[serde(default)]
+    pub monthly_usd: Option<f64>,
+    /// Percentage of a limit at which to warn; 80 when unset.
+    #[serde(default)]
+    pub warn_percent: Option<f64>,
+    /// Refuse to start new sessions while any limit is used up.
+    #[serde(default)]
+    pub hard_stop: bool,
+}
+
+impl BudgetConfig {
+    /// The stricter of `self` and `other`, field by field.
+    fn tightened_by(&self, other: &BudgetConfig) -> BudgetConfig {
+        let lower = |a: Option<f64>, b: Option<f64>| match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        BudgetConfig {
+            daily_usd: lower(self.daily_usd, other.daily_usd),
+            weekly_usd: lower(self.weekly_usd, other.weekly_usd),
+            monthly_usd: lower(self.monthly_usd, other.monthly_usd),
+            warn_percent: lower(self.warn_percent, other.warn_percent),
+            hard_stop: self.hard_stop || other.hard_stop,
+        }
+    }
+}
+
+/// Claude subscriptions limit usage per 5-hour window, but don't publish
+/// the limit, so headroom is only estimated when one is configured here.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RateWindowConfig {
+    /// Input plus output tokens the window is expected to allow.
+    #[serde(default)]
+    pub token_limit: Option<u64>,
+}
+
+/// Which quota thresholds show a desktop notification; all off by default.
+/// The in-app events are emitted either way.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct QuotaNotifyConfig {
+    /// A budget passed its `warn_percent`.
+    #[serde(default)]
+    pub budget_warning: bool,
+    /// A budget is used up.
+    #[serde(default)]
+    pub budget_exceeded: bool,
+    /// A session hit Claude's usage limit.
+    #[serde(default)]
+    pub rate_limited: bool,
+    /// Percentage of `rate_window.token_limit` used in the current window at
+    /// which to notify, once per window.
+    #[serde(default)]
+    pub window_percent: Option<f64>,
+    /// The limit a session hit, or a window past `window_percent`, has reset.
+    #[serde(default)]
+    pub window_reset: bool,
+}
+
+/// The local HTTP API; off by default. Clients send `token` as a bearer
+/// token.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct HttpApiConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Port on 127.0.0.1; 7777 when unset.
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+/// Which agent events show a desktop notification: the first rule matching
+/// an event decides, and events no rule matches show none.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AgentNotifyConfig {
+    #[serde(default)]
+    pub rules: Vec<NotificationRule>,
+    /// Local times between which nothing is shown, e.g. `22:00`-`07:00`.
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHours>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationRule {
+    pub event: AgentNotifyEvent,
+    /// Only sessions of these agents; every agent when empty.
+    #[serde(default)]
+    pub agents: Vec<String>,
+    /// False mutes matching events, e.g. to exclude one agent from a later,
+    /// broader rule.
+    #[serde(default = "default_true")]
+    pub notify: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentNotifyEvent {
+    Started,
+    Completed,
+    Failed,
+    Stopped,
+    FileConflict,
+}
+
+/// `HH:MM` local times; `end` before `start` spans midnight.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: String,
+    pub end: String,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Issue tracker credentials. Stored in config.json, which is 0600.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct IntegrationsConfig {
+    /// GitHub token; when unset, the `gh` CLI's own auth is used instead.
+    #[serde(default)]
+    pub github_token: Option<String>,
+    #[serde(default)]
+    pub jira: Option<JiraConfig>,
+    /// Personal API key from Linear settings; enables the background status sync.
+    #[serde(default)]
+    pub linear_api_key: Option<String>,
+    /// Endpoint notified of agent events.
+    #[serde(default)]
+    pub webhook: Option<WebhookConfig>,
+}
+
+/// Agent events POSTed as JSON batches, `{"source": "clautron", "events":
+/// [...]}`, to `url`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Signs each request with HMAC-SHA256 over `<timestamp>.<body>`, sent
+    /// as `X-Clautron-Signature: sha256=<hex>` next to
+    /// `X-Clautron-Timestamp: <unix seconds>`.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Events to send. Messages are left out by default: there's one per
+    /// line of agent output.
+    #[serde(default = "default_webhook_events")]
+    pub events: Vec<WebhookEventKind>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventKind {
+    Status,
+    Message,
+    Usage,
+    RateLimited,
+    FileConflict,
+}
+
+fn default_webhook_events() -> Vec<WebhookEventKind> {
+    vec![
+        WebhookEventKind::Status,
+        WebhookEventKind::Usage,
+        WebhookEventKind::RateLimited,
+        WebhookEventKind::FileConflict,
+    ]
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraConfig {
+    /// e.g. `https://your-team.atlassian.net`
+    pub base_url: String,
+    /// Account email for Jira Cloud; leave unset to use a personal access token.
+    #[serde(default)]
+    pub email: Option<String>,
+    pub api_token: String,
 }
 
 pub struct ConfigStore {
@@ -29,6 +590,10 @@ impl ConfigStore {
         }
     }
 
+    pub fn path(&self) -> &std::path::Path {
+        &self.config_path
+    }
+
     /// Load config from disk. Returns default if file doesn't exist.
     pub fn load(&self) -> AppConfig {
         match std::fs::read_to_string(&self.config_path) {
@@ -37,6 +602,14 @@ impl ConfigStore {
         }
     }
 
+    /// Load config from disk, failing rather than falling back to the
+    /// defaults when the file is missing or invalid.
+    pub fn try_load(&self) -> Result<AppConfig, String> {
+        let content = std::fs::read_to_string(&self.config_path)
+            .map_err(|e| format!("Cannot read config.json: {e}"))?;
+        serde_json::from_str(&content).map_err(|e| format!("Cannot parse config.json: {e}"))
+    }
+
     /// Save config to disk with 0600 permissions (P0 Security #6).
     pub fn save(&self, config: &AppConfig) -> Result<(), AppError> {
         if let Some(parent) = self.config_path.parent() {
@@ -68,3 +641,113 @@ impl ConfigStore {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opened_projects_come_first_and_keep_their_settings() {
+        let mut config = AppConfig::default();
+        config.add_project("/work/api", Some("API".into()));
+        config.open_project("/work/web");
+        config.projects.get_mut("/work/web").unwrap().mirror_logs = true;
+        config.open_project("/work/api");
+
+        let names: Vec<_> = config.known_projects().into_iter().map(|p| p.name).collect();
+        assert_eq!(names, ["API", "web"]);
+        assert_eq!(config.project_path.as_deref(), Some("/work/api"));
+
+        config.open_project("/work/web");
+        assert!(config.project_settings().mirror_logs);
+        assert!(config.remove_project("/work/web").is_err());
+        config.remove_project("/work/api").unwrap();
+        assert_eq!(config.known_projects().len(), 1);
+    }
+
+    #[test]
+    fn project_file_overrides_only_what_it_sets() {
+        let mut config = AppConfig::default();
+        config.open_project("/work/api");
+        let settings = config.projects.get_mut("/work/api").unwrap();
+        settings.auto_branch = true;
+        settings.hold_on_conflict = true;
+        config.budgets.daily_usd = Some(5.0);
+
+        config.project_file = Some(
+            serde_json::from_str(
+                r#"{"auto_branch": false, "default_model": "sonnet", "redaction_patterns": ["acme_[a-z0-9]+"]}"#,
+            )
+            .unwrap(),
+        );
+        let settings = config.project_settings();
+        assert!(!settings.auto_branch);
+        assert!(settings.hold_on_conflict);
+        assert_eq!(settings.default_model.as_deref(), Some("sonnet"));
+        assert_eq!(config.redaction().custom_patterns, ["acme_[a-z0-9]+"]);
+        assert_eq!(config.budgets().daily_usd, Some(5.0));
+    }
+
+    #[test]
+    fn project_budgets_only_tighten_the_users() {
+        let mut config = AppConfig::default();
+        config.budgets = BudgetConfig {
+            daily_usd: Some(5.0),
+            weekly_usd: Some(20.0),
+            monthly_usd: None,
+            warn_percent: Some(70.0),
+            hard_stop: true,
+        };
+        // Looser daily limit, stricter weekly one, a new monthly one and no
+        // hard stop
+        config.project_file = Some(
+            serde_json::from_str(
+                r#"{"budgets": {"daily_usd": 50.0, "weekly_usd": 10.0, "monthly_usd": 100.0, "warn_percent": 90.0}}"#,
+            )
+            .unwrap(),
+        );
+        let budgets = config.budgets();
+        assert_eq!(budgets.daily_usd, Some(5.0));
+        assert_eq!(budgets.weekly_usd, Some(10.0));
+        assert_eq!(budgets.monthly_usd, Some(100.0));
+        assert_eq!(budgets.warn_percent, Some(70.0));
+        assert!(budgets.hard_stop);
+
+        config.budgets.hard_stop = false;
+        config.project_file =
+            Some(serde_json::from_str(r#"{"budgets": {"hard_stop": true}}"#).unwrap());
+        assert!(config.budgets().hard_stop);
+        assert_eq!(config.budgets().daily_usd, Some(5.0));
+    }
+
+    #[test]
+    fn project_env_is_requested_by_the_file_and_allowed_by_the_user() {
+        let mut config = AppConfig::default();
+        assert!(config.allow_project_env(&["TEST_VAR".into()]).is_err());
+        config.open_project("/work/api");
+        config.project_file = Some(
+            serde_json::from_str(
+                r#"{"env": ["TEST_DATABASE_URL", "TEST_VAR", "PATH", "NODE_OPTIONS"]}"#,
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            config.pending_project_env(),
+            ["TEST_DATABASE_URL", "TEST_VAR"]
+        );
+        assert_eq!(config.refused_project_env(), ["NODE_OPTIONS"]);
+
+        config
+            .allow_project_env(&["TEST_VAR".into(), "PATH".into()])
+            .unwrap();
+        assert_eq!(config.project_settings().extra_env, ["TEST_VAR"]);
+        assert_eq!(config.pending_project_env(), ["TEST_DATABASE_URL"]);
+        assert!(config.allow_project_env(&["LD_PRELOAD".into()]).is_err());
+        assert!(config.allow_project_env(&["NODE_OPTIONS".into()]).is_err());
+        assert!(config.allow_project_env(&["GIT_SSH_COMMAND".into()]).is_err());
+        assert!(config.allow_project_env(&["BAD-NAME".into()]).is_err());
+
+        config.revoke_project_env(&["TEST_VAR".into()]).unwrap();
+        assert!(config.project_settings().extra_env.is_empty());
+    }
+}
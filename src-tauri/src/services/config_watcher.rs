@@ -0,0 +1,44 @@
+//! Watches `~/.clautron/config.json` for edits made outside the app, e.g. by
+//! scripts or another instance, so they apply without a restart.
+
+use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Send on `changed` whenever the config file is written or replaced.
+/// Debounced at 500ms. The directory is watched rather than the file, as
+/// editors often replace files instead of writing to them. None when the
+/// directory doesn't exist.
+pub fn start_watching(
+    config_path: PathBuf,
+    changed: mpsc::UnboundedSender<()>,
+) -> Option<notify_debouncer_mini::Debouncer<notify::RecommendedWatcher>> {
+    let config_dir = config_path.parent()?.to_path_buf();
+    if !config_dir.exists() {
+        return None;
+    }
+
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(500),
+        move |events: Result<Vec<notify_debouncer_mini::DebouncedEvent>, notify::Error>| {
+            if let Ok(events) = events {
+                let touched = events.iter().any(|e| {
+                    e.kind == DebouncedEventKind::Any
+                        && e.path.file_name() == config_path.file_name()
+                });
+                if touched {
+                    let _ = changed.send(());
+                }
+            }
+        },
+    )
+    .ok()?;
+
+    debouncer
+        .watcher()
+        .watch(&config_dir, notify::RecursiveMode::NonRecursive)
+        .ok()?;
+
+    Some(debouncer)
+}
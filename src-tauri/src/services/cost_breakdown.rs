@@ -0,0 +1,202 @@
+use crate::domain::models::{AgentSession, CostGroup, LogEntry, TurnCost};
+use serde_json::Value;
+
+/// Output tokens cost five times input tokens on every current Claude model,
+/// so turns are weighted `input + 5 * output` when sharing out the cost.
+const OUTPUT_TOKEN_WEIGHT: f64 = 5.0;
+
+/// Split a session's total cost across its assistant turns by token
+/// usage. Claude Code emits one assistant message per content block, each
+/// repeating the turn's usage, so entries are grouped by `message.id`.
+pub fn breakdown(entries: &[LogEntry], total_cost_usd: f64) -> Vec<TurnCost> {
+    let mut turns: Vec<TurnCost> = Vec::new();
+    let mut turn_ids: Vec<Option<String>> = Vec::new();
+
+    for entry in entries {
+        if entry.message_type != "assistant" {
+            continue;
+        }
+        let parsed: Value = serde_json::from_str(&entry.content).unwrap_or(Value::Null);
+        let message_id = parsed
+            .pointer("/message/id")
+            .and_then(Value::as_str)
+            .map(String::from);
+        let blocks = parsed
+            .pointer("/message/content")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        let idx = match turn_ids
+            .iter()
+            .position(|id| id.is_some() && *id == message_id)
+        {
+            Some(idx) => idx,
+            None => {
+                turn_ids.push(message_id);
+                turns.push(TurnCost {
+                    log_id: entry.id,
+                    timestamp: entry.timestamp.clone(),
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    estimated_cost_usd: 0.0,
+                    tools: Vec::new(),
+                    text_preview: String::new(),
+                });
+                turns.len() - 1
+            }
+        };
+        let turn = &mut turns[idx];
+        turn.input_tokens = turn.input_tokens.max(entry.input_tokens.unwrap_or(0));
+        turn.output_tokens = turn.output_tokens.max(entry.output_tokens.unwrap_or(0));
+        for block in blocks {
+            match block.get("type").and_then(Value::as_str) {
+                Some("tool_use") => {
+                    if let Some(name) = block.get("name").and_then(Value::as_str) {
+                        turn.tools.push(name.to_string());
+                    }
+                }
+                Some("text") if turn.text_preview.is_empty() => {
+                    let text = block.get("text").and_then(Value::as_str).unwrap_or("");
+                    turn.text_preview = text.trim().chars().take(120).collect();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let weight =
+        |t: &TurnCost| t.input_tokens as f64 + OUTPUT_TOKEN_WEIGHT * t.output_tokens as f64;
+    let total_weight: f64 = turns.iter().map(weight).sum();
+    if total_weight > 0.0 {
+        for turn in &mut turns {
+            turn.estimated_cost_usd = total_cost_usd * weight(turn) / total_weight;
+        }
+    }
+    turns
+}
+
+/// Sum session costs per group, costliest first. `group_of` gives a
+/// session's group key and label; sessions without one are summed under
+/// `ungrouped`.
+pub fn group_costs(
+    sessions: &[AgentSession],
+    group_of: impl Fn(&AgentSession) -> Option<(String, String)>,
+    ungrouped: &str,
+) -> Vec<CostGroup> {
+    let mut groups: Vec<CostGroup> = Vec::new();
+    for session in sessions {
+        let (key, label) = match group_of(session) {
+            Some((key, label)) => (Some(key), label),
+            None => (None, ungrouped.to_string()),
+        };
+        let idx = match groups.iter().position(|g| g.key == key) {
+            Some(idx) => idx,
+            None => {
+                groups.push(CostGroup {
+                    key,
+                    label,
+                    cost_usd: 0.0,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    session_count: 0,
+                });
+                groups.len() - 1
+            }
+        };
+        let group = &mut groups[idx];
+        group.cost_usd += session.cost_usd;
+        group.input_tokens += session.input_tokens;
+        group.output_tokens += session.output_tokens;
+        group.session_count += 1;
+    }
+    groups.sort_by(|a, b| {
+        b.cost_usd
+            .partial_cmp(&a.cost_usd)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assistant(id: u64, message_id: &str, block: &str, usage: (u64, u64)) -> LogEntry {
+        LogEntry {
+            id,
+            session_id: "s1".into(),
+            message_type: "assistant".into(),
+            content: format!(
+                r#"{{"type":"assistant","message":{{"id":"{message_id}","content":[{block}]}}}}"#
+            ),
+            timestamp: "2026-01-01T00:00:00Z".into(),
+            input_tokens: Some(usage.0),
+            output_tokens: Some(usage.1),
+        }
+    }
+
+    #[test]
+    fn cost_is_shared_by_weighted_tokens_per_turn() {
+        let entries = vec![
+            assistant(
+                1,
+                "m1",
+                r#"{"type":"text","text":"Let me look"}"#,
+                (100, 20),
+            ),
+            assistant(
+                2,
+                "m1",
+                r#"{"type":"tool_use","id":"t1","name":"Grep","input":{}}"#,
+                (100, 20),
+            ),
+            assistant(3, "m2", r#"{"type":"text","text":"Done"}"#, (300, 100)),
+        ];
+        let turns = breakdown(&entries, 1.0);
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].tools, ["Grep"]);
+        assert_eq!(turns[0].text_preview, "Let me look");
+        assert_eq!((turns[0].input_tokens, turns[0].output_tokens), (100, 20));
+        // 200 vs 800 weighted tokens
+        assert!((turns[0].estimated_cost_usd - 0.2).abs() < 1e-9);
+        assert!((turns[1].estimated_cost_usd - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn session_costs_sum_per_group() {
+        let session = |id: &str, agent: &str, cost_usd: f64| AgentSession {
+            id: id.into(),
+            agent_name: agent.into(),
+            model: "opus".into(),
+            status: crate::domain::models::AgentStatus::Completed,
+            prompt: String::new(),
+            started_at: String::new(),
+            ended_at: None,
+            input_tokens: 10,
+            output_tokens: 5,
+            cost_usd,
+            project_dir: None,
+            branch: None,
+            cost_estimated: false,
+            profile: None,
+        };
+        let sessions = [
+            session("a", "writer", 0.5),
+            session("b", "reviewer", 2.0),
+            session("c", "writer", 1.0),
+        ];
+        // Only "b" belongs to a workflow
+        let groups = group_costs(
+            &sessions,
+            |s| (s.id == "b").then(|| ("wf1".to_string(), "Release".to_string())),
+            "No workflow",
+        );
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].key.as_deref(), Some("wf1"));
+        assert_eq!(groups[1].label, "No workflow");
+        assert_eq!(groups[1].cost_usd, 1.5);
+        assert_eq!(groups[1].session_count, 2);
+        assert_eq!(groups[1].input_tokens, 20);
+    }
+}
@@ -0,0 +1,287 @@
+use crate::domain::models::{ErrorCategory, ErrorDigest, ErrorExample, ErrorGroup, LogEntry};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+/// Example messages kept per group.
+const MAX_EXAMPLES: usize = 3;
+
+/// Longest signature, in characters, after masking.
+const MAX_SIGNATURE_CHARS: usize = 120;
+
+/// Substrings that identify a category, checked in order on the lowercased
+/// message. Anything else from a failed tool result is a tool failure.
+const CATEGORY_MARKERS: &[(ErrorCategory, &[&str])] = &[
+    (
+        ErrorCategory::RateLimit,
+        &[
+            "rate limit",
+            "rate_limit",
+            "429",
+            "too many requests",
+            "usage limit",
+        ],
+    ),
+    (ErrorCategory::Overloaded, &["overloaded", "529"]),
+    (
+        ErrorCategory::Auth,
+        &[
+            "401",
+            "unauthorized",
+            "authentication",
+            "invalid api key",
+            "invalid x-api-key",
+            "/login",
+            "oauth token",
+        ],
+    ),
+    (
+        ErrorCategory::Network,
+        &[
+            "econnreset",
+            "econnrefused",
+            "etimedout",
+            "enotfound",
+            "connection refused",
+            "network error",
+            "socket hang up",
+        ],
+    ),
+];
+
+static MASKS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    vec![
+        // Paths before numbers, so `/tmp/x1` masks as a whole
+        (Regex::new(r"(?:~|\.{0,2})/[^\s:'`]+").unwrap(), "<path>"),
+        (
+            Regex::new(r"\b[0-9a-fA-F]{8}-[0-9a-fA-F-]{27}\b|\b[0-9a-fA-F_-]{16,}\b").unwrap(),
+            "<id>",
+        ),
+        (Regex::new(r"\d+").unwrap(), "N"),
+    ]
+});
+
+/// Group the error entries (stderr, failed tool results, error results) into
+/// recurring failure modes, most frequent first.
+pub fn digest(entries: &[LogEntry], since: Option<String>) -> ErrorDigest {
+    let mut groups: Vec<ErrorGroup> = Vec::new();
+    let mut index: HashMap<(ErrorCategory, String), usize> = HashMap::new();
+    let mut sessions: Vec<HashSet<String>> = Vec::new();
+    let mut total_errors = 0;
+
+    for entry in entries {
+        for (category, message) in errors_in(entry) {
+            total_errors += 1;
+            let signature = signature(&message);
+            let idx = *index
+                .entry((category, signature.clone()))
+                .or_insert_with(|| {
+                    groups.push(ErrorGroup {
+                        category,
+                        signature,
+                        count: 0,
+                        session_count: 0,
+                        first_seen: entry.timestamp.clone(),
+                        last_seen: entry.timestamp.clone(),
+                        examples: Vec::new(),
+                    });
+                    sessions.push(HashSet::new());
+                    groups.len() - 1
+                });
+            let group = &mut groups[idx];
+            group.count += 1;
+            if entry.timestamp < group.first_seen {
+                group.first_seen = entry.timestamp.clone();
+            }
+            if entry.timestamp > group.last_seen {
+                group.last_seen = entry.timestamp.clone();
+            }
+            if sessions[idx].insert(entry.session_id.clone()) {
+                group.session_count += 1;
+            }
+            if group.examples.len() < MAX_EXAMPLES
+                && !group.examples.iter().any(|e| e.message == message)
+            {
+                group.examples.push(ErrorExample {
+                    session_id: entry.session_id.clone(),
+                    timestamp: entry.timestamp.clone(),
+                    message,
+                });
+            }
+        }
+    }
+
+    groups.sort_by(|a, b| b.count.cmp(&a.count).then(b.last_seen.cmp(&a.last_seen)));
+    ErrorDigest {
+        since,
+        total_errors,
+        groups,
+    }
+}
+
+/// The error messages one entry carries, with their category.
+fn errors_in(entry: &LogEntry) -> Vec<(ErrorCategory, String)> {
+    if entry.message_type == "stderr" {
+        let text = entry.content.trim();
+        return if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![(categorize(text, ErrorCategory::Other), text.to_string())]
+        };
+    }
+    let Ok(parsed) = serde_json::from_str::<Value>(&entry.content) else {
+        return Vec::new();
+    };
+    match entry.message_type.as_str() {
+        "result" => {
+            let failed = parsed.get("is_error").and_then(Value::as_bool) == Some(true)
+                || parsed
+                    .get("subtype")
+                    .and_then(Value::as_str)
+                    .is_some_and(|s| s.starts_with("error"));
+            if !failed {
+                return Vec::new();
+            }
+            let text = parsed
+                .get("result")
+                .and_then(Value::as_str)
+                .filter(|r| !r.trim().is_empty())
+                .or_else(|| parsed.get("subtype").and_then(Value::as_str))
+                .unwrap_or("error");
+            vec![(
+                categorize(text, ErrorCategory::Other),
+                text.trim().to_string(),
+            )]
+        }
+        "user" => parsed
+            .pointer("/message/content")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter(|block| {
+                block.get("type").and_then(Value::as_str) == Some("tool_result")
+                    && block.get("is_error").and_then(Value::as_bool) == Some(true)
+            })
+            .map(|block| {
+                let text = tool_result_text(block.get("content"));
+                (categorize(&text, ErrorCategory::ToolFailure), text)
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn tool_result_text(content: Option<&Value>) -> String {
+    match content {
+        Some(Value::String(text)) => text.trim().to_string(),
+        Some(Value::Array(blocks)) => blocks
+            .iter()
+            .filter_map(|b| b.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string(),
+        _ => String::new(),
+    }
+}
+
+fn categorize(message: &str, fallback: ErrorCategory) -> ErrorCategory {
+    let lower = message.to_lowercase();
+    CATEGORY_MARKERS
+        .iter()
+        .find(|(_, markers)| markers.iter().any(|m| lower.contains(m)))
+        .map_or(fallback, |(category, _)| *category)
+}
+
+/// The first line with paths, ids and numbers masked, so messages that
+/// differ only in those group together.
+fn signature(message: &str) -> String {
+    let mut line = message.lines().next().unwrap_or("").trim().to_string();
+    for (pattern, mask) in MASKS.iter() {
+        line = pattern.replace_all(&line, *mask).to_string();
+    }
+    match line.char_indices().nth(MAX_SIGNATURE_CHARS) {
+        Some((idx, _)) => format!("{}…", &line[..idx]),
+        None => line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(session_id: &str, message_type: &str, content: &str, timestamp: &str) -> LogEntry {
+        LogEntry {
+            id: 0,
+            session_id: session_id.into(),
+            message_type: message_type.into(),
+            content: content.into(),
+            timestamp: timestamp.into(),
+            input_tokens: None,
+            output_tokens: None,
+        }
+    }
+
+    #[test]
+    fn similar_errors_group_across_sessions() {
+        let tool_failure = |path: &str| {
+            format!(
+                r#"{{"type":"user","message":{{"content":[{{"type":"tool_result","tool_use_id":"t","is_error":true,"content":"File does not exist: {path}"}}]}}}}"#
+            )
+        };
+        let entries = vec![
+            entry(
+                "s1",
+                "stderr",
+                "API Error: 429 rate_limit_error",
+                "2026-01-01T00:00:00Z",
+            ),
+            entry(
+                "s1",
+                "user",
+                &tool_failure("/src/a.rs"),
+                "2026-01-01T00:01:00Z",
+            ),
+            entry(
+                "s2",
+                "user",
+                &tool_failure("/src/b.rs"),
+                "2026-01-02T00:00:00Z",
+            ),
+            entry(
+                "s2",
+                "result",
+                r#"{"type":"result","subtype":"success","is_error":false,"result":"ok"}"#,
+                "2026-01-02T00:01:00Z",
+            ),
+            entry(
+                "s3",
+                "result",
+                r#"{"type":"result","subtype":"error_max_turns","is_error":true}"#,
+                "2026-01-03T00:00:00Z",
+            ),
+        ];
+        let digest = digest(&entries, None);
+        assert_eq!(digest.total_errors, 4);
+        assert_eq!(digest.groups.len(), 3);
+
+        let tools = &digest.groups[0];
+        assert_eq!(tools.category, ErrorCategory::ToolFailure);
+        assert_eq!(tools.signature, "File does not exist: <path>");
+        assert_eq!((tools.count, tools.session_count), (2, 2));
+        assert_eq!(tools.examples.len(), 2);
+        assert_eq!(tools.first_seen, "2026-01-01T00:01:00Z");
+        assert_eq!(tools.last_seen, "2026-01-02T00:00:00Z");
+
+        assert!(digest
+            .groups
+            .iter()
+            .any(|g| g.category == ErrorCategory::RateLimit
+                && g.signature == "API Error: N rate_limit_error"));
+        assert!(digest
+            .groups
+            .iter()
+            .any(|g| g.category == ErrorCategory::Other && g.signature == "error_max_turns"));
+    }
+}
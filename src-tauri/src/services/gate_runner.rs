@@ -0,0 +1,199 @@
+use crate::domain::stream_parser::redact_secrets;
+use std::time::Duration;
+use tokio::process::Command;
+
+/// Hard limit for a single gate command (10 minutes).
+const GATE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Env vars a gate command inherits: the agents' allowlist without the
+/// Claude credentials and config directory, which a project's test or lint
+/// command has no use for.
+const GATE_ENV_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LOGNAME",
+    "SHELL",
+    "TMPDIR",
+    "LANG",
+    "LC_ALL",
+    "XDG_CONFIG_HOME",
+    "XDG_DATA_HOME",
+    "TERM",
+];
+
+/// Result of running a gate command.
+pub struct GateResult {
+    /// True only if the command exited with status 0.
+    pub success: bool,
+    /// Combined stdout + stderr, redacted and truncated to the tail.
+    pub output: String,
+}
+
+/// Run a gate command in the project directory.
+/// The command line is split into argv and executed directly, never through a
+/// shell (P0 Security #1), with only allowlisted env vars (P0 Security #3).
+pub async fn run_gate(command_line: &str, project_dir: &str, max_output: usize) -> GateResult {
+    let argv = match split_command_line(command_line) {
+        Ok(argv) if !argv.is_empty() => argv,
+        Ok(_) => return failure("Gate step has no command configured".into()),
+        Err(e) => return failure(format!("Invalid gate command: {e}")),
+    };
+
+    let mut cmd = Command::new(&argv[0]);
+    cmd.args(&argv[1..])
+        .current_dir(project_dir)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .env_clear();
+    for key in GATE_ENV_ALLOWLIST {
+        if let Ok(val) = std::env::var(key) {
+            cmd.env(key, val);
+        }
+    }
+
+    let output = match tokio::time::timeout(GATE_TIMEOUT, cmd.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => return failure(format!("Failed to run `{}`: {e}", argv[0])),
+        Err(_) => {
+            return failure(format!(
+                "`{command_line}` timed out after {}s",
+                GATE_TIMEOUT.as_secs()
+            ))
+        }
+    };
+
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.is_empty() {
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(&stderr);
+    }
+    let status_line = match output.status.code() {
+        Some(code) => format!("`{command_line}` exited with code {code}"),
+        None => format!("`{command_line}` was terminated by a signal"),
+    };
+
+    GateResult {
+        success: output.status.success(),
+        output: format!("{}\n\n{}", tail(&redact_secrets(&text), max_output), status_line),
+    }
+}
+
+fn failure(output: String) -> GateResult {
+    GateResult {
+        success: false,
+        output,
+    }
+}
+
+/// Keep the last `max_len` bytes of `s` (test failures are usually at the end),
+/// cutting on a char boundary.
+fn tail(s: &str, max_len: usize) -> String {
+    if s.len() <= max_len {
+        return s.to_string();
+    }
+    let mut start = s.len() - max_len;
+    while !s.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("[truncated] ...{}", &s[start..])
+}
+
+/// Split a command line into arguments. Supports single quotes (literal),
+/// double quotes and backslash escapes. No variables, globs or operators.
+fn split_command_line(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_arg = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err("unterminated single quote".into()),
+                    }
+                }
+            }
+            '"' => {
+                in_arg = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) => current.push(c),
+                            None => return Err("trailing backslash".into()),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err("unterminated double quote".into()),
+                    }
+                }
+            }
+            '\\' => {
+                in_arg = true;
+                match chars.next() {
+                    Some(c) => current.push(c),
+                    None => return Err("trailing backslash".into()),
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            c => {
+                in_arg = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_plain_and_quoted_args() {
+        assert_eq!(
+            split_command_line(r#"cargo test -p "my crate" -- 'a b' c\ d"#).unwrap(),
+            vec!["cargo", "test", "-p", "my crate", "--", "a b", "c d"]
+        );
+        assert_eq!(split_command_line(r#"echo """#).unwrap(), vec!["echo", ""]);
+        assert!(split_command_line("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_unterminated_quotes() {
+        assert!(split_command_line("npm run 'lint").is_err());
+        assert!(split_command_line("npm run \"lint").is_err());
+    }
+
+    #[test]
+    fn gates_get_no_claude_credentials() {
+        use crate::adapters::claude_cli_runner::ENV_ALLOWLIST;
+        for key in GATE_ENV_ALLOWLIST {
+            assert!(ENV_ALLOWLIST.contains(key), "{key}");
+            assert!(!key.ends_with("_API_KEY") && !key.starts_with("CLAUDE_"), "{key}");
+        }
+    }
+
+    #[test]
+    fn tail_keeps_end_on_char_boundary() {
+        assert_eq!(tail("short", 10), "short");
+        assert_eq!(tail("abcdef", 3), "[truncated] ...def");
+        assert_eq!(tail("aé", 1), "[truncated] ...");
+    }
+}
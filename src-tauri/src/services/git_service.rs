@@ -1,4 +1,10 @@
+//! Git operations for the review and branch views. Every call runs the `git`
+//! CLI through `git_command` and parses its porcelain and diff text into
+//! `ChangedFile` and `FileDiff`; nothing here links libgit2 or gitoxide.
+
+use crate::services::text_diff;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::process::Command;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -6,6 +12,21 @@ pub struct FileDiff {
     pub path: String,
     pub change_type: String, // "modified", "added", "deleted", "renamed"
     pub hunks: Vec<DiffHunk>,
+    /// Path before a rename.
+    #[serde(default)]
+    pub old_path: Option<String>,
+    /// Binary contents, which git doesn't diff; never has hunks.
+    #[serde(default)]
+    pub is_binary: bool,
+    /// Sizes in bytes of the two sides, filled in when the contents aren't
+    /// shown (binary files and untracked files over the size cap).
+    #[serde(default)]
+    pub old_size: Option<u64>,
+    #[serde(default)]
+    pub new_size: Option<u64>,
+    /// Changes in the index against HEAD, rather than in the working tree.
+    #[serde(default)]
+    pub staged: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,36 +41,784 @@ pub struct DiffLine {
     pub content: String,
     pub old_line: Option<u32>,
     pub new_line: Option<u32>,
+    /// For a removed or added line paired with its counterpart, the line
+    /// split into unchanged and changed runs so the exact edit can be
+    /// highlighted. None for whole-line changes and context.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segments: Option<Vec<WordSegment>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordSegment {
+    pub text: String,
+    pub changed: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChangedFile {
     pub path: String,
     pub status: String, // "M", "A", "D", "R", "?"
+    /// Lines added and removed, staged and unstaged changes together. None
+    /// for binary files and untracked files over the size cap.
+    #[serde(default)]
+    pub insertions: Option<u32>,
+    #[serde(default)]
+    pub deletions: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitBranch {
+    pub name: String,
+    /// Checked out in the working tree.
+    pub current: bool,
+    pub upstream: Option<String>,
+    /// Abbreviated hash and subject of the tip commit.
+    pub commit: String,
+    pub subject: String,
 }
 
 /// Get list of changed files in the working tree.
 pub fn get_changed_files(project_dir: &str) -> Result<Vec<ChangedFile>, String> {
     // Get staged + unstaged + untracked
-    let output = Command::new("git")
-        .args(["status", "--porcelain"])
+    let output = git_command()
+        .args(["status", "--porcelain=v1", "-z"])
         .current_dir(project_dir)
         .output()
         .map_err(|e| format!("Failed to run git status: {e}"))?;
+    let mut files = parse_status(&String::from_utf8_lossy(&output.stdout));
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut stats: HashMap<String, (Option<u32>, Option<u32>)> = HashMap::new();
+    for args in [
+        &["diff", "-M", "--numstat", "-z"][..],
+        &["diff", "--cached", "-M", "--numstat", "-z"],
+    ] {
+        // Fails outside a repository, where status has listed nothing
+        let Ok(stdout) = run_git(project_dir, args) else {
+            continue;
+        };
+        for (path, insertions, deletions) in parse_numstat(&stdout) {
+            let entry = stats.entry(path).or_insert((Some(0), Some(0)));
+            entry.0 = entry.0.zip(insertions).map(|(a, b)| a + b);
+            entry.1 = entry.1.zip(deletions).map(|(a, b)| a + b);
+        }
+    }
+    for file in &mut files {
+        if let Some(&(insertions, deletions)) = stats.get(&file.path) {
+            (file.insertions, file.deletions) = (insertions, deletions);
+        } else if file.status == "??" {
+            file.insertions = untracked_line_count(project_dir, &file.path);
+            file.deletions = file.insertions.map(|_| 0);
+        }
+    }
+    Ok(files)
+}
+
+/// Parse `git diff --numstat -z`: `added\tdeleted\tpath` records separated
+/// by NUL, counts `-` for binary files. A rename leaves the path empty and
+/// is followed by the old and the new path as records of their own.
+fn parse_numstat(stdout: &str) -> Vec<(String, Option<u32>, Option<u32>)> {
+    let mut stats = Vec::new();
+    let mut records = stdout.split('\0');
+    while let Some(record) = records.next() {
+        let mut fields = record.splitn(3, '\t');
+        let (Some(insertions), Some(deletions), Some(mut path)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if path.is_empty() {
+            records.next();
+            let Some(new_path) = records.next() else {
+                break;
+            };
+            path = new_path;
+        }
+        stats.push((
+            path.to_string(),
+            insertions.parse().ok(),
+            deletions.parse().ok(),
+        ));
+    }
+    stats
+}
+
+/// Lines in an untracked file, counted the way its added-file diff shows
+/// them. None for binary files and files over the size cap.
+fn untracked_line_count(project_dir: &str, path: &str) -> Option<u32> {
+    let full = std::path::Path::new(project_dir).join(path);
+    if std::fs::metadata(&full).ok()?.len() > MAX_UNTRACKED_DIFF_BYTES {
+        return None;
+    }
+    let content = std::fs::read(&full).ok()?;
+    if content.iter().take(8000).any(|&b| b == 0) {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&content).lines().count() as u32)
+}
+
+/// Parse `git status --porcelain=v1 -z`: `XY path` records separated by NUL,
+/// paths unquoted. Renames and copies carry the original path as an extra
+/// record, which is skipped.
+fn parse_status(stdout: &str) -> Vec<ChangedFile> {
     let mut files = Vec::new();
+    let mut records = stdout.split('\0');
+    while let Some(record) = records.next() {
+        let (Some(xy), Some(path)) = (record.get(..2), record.get(3..)) else {
+            continue;
+        };
+        if xy.contains(['R', 'C']) {
+            records.next();
+        }
+        files.push(ChangedFile {
+            path: path.to_string(),
+            status: xy.trim().to_string(),
+            insertions: None,
+            deletions: None,
+        });
+    }
+    files
+}
 
-    for line in stdout.lines() {
-        if line.len() < 4 {
+/// `git` with paths printed as-is rather than C-quoted, so non-ASCII file
+/// names come through intact.
+fn git_command() -> Command {
+    let mut cmd = Command::new("git");
+    cmd.args(["-c", "core.quotePath=false"]);
+    cmd
+}
+
+/// `user.name` from the project's git config, if set.
+pub fn get_user_name(project_dir: &str) -> Option<String> {
+    let output = git_command()
+        .args(["config", "user.name"])
+        .current_dir(project_dir)
+        .output()
+        .ok()?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !name.is_empty()).then_some(name)
+}
+
+/// Local branches, most recently committed first.
+pub fn list_branches(project_dir: &str) -> Result<Vec<GitBranch>, String> {
+    let stdout = run_git(
+        project_dir,
+        &[
+            "for-each-ref",
+            "--sort=-committerdate",
+            "--format=%(HEAD)%1f%(refname:short)%1f%(upstream:short)%1f%(objectname:short)%1f%(subject)",
+            "refs/heads",
+        ],
+    )?;
+    Ok(stdout
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\u{1f}').collect();
+            if fields.len() < 5 {
+                return None;
+            }
+            Some(GitBranch {
+                name: fields[1].to_string(),
+                current: fields[0] == "*",
+                upstream: Some(fields[2].to_string()).filter(|u| !u.is_empty()),
+                commit: fields[3].to_string(),
+                subject: fields[4..].join("\u{1f}"),
+            })
+        })
+        .collect())
+}
+
+/// Create `name` at HEAD, optionally switching to it. Uncommitted changes
+/// carry over to the new branch.
+pub fn create_branch(project_dir: &str, name: &str, checkout: bool) -> Result<(), String> {
+    validate_branch_name(project_dir, name)?;
+    if checkout {
+        run_git(project_dir, &["switch", "-c", name])?;
+    } else {
+        run_git(project_dir, &["branch", name])?;
+    }
+    Ok(())
+}
+
+/// Switch to an existing branch. Git refuses when uncommitted changes
+/// would be overwritten.
+pub fn checkout_branch(project_dir: &str, name: &str) -> Result<(), String> {
+    validate_branch_name(project_dir, name)?;
+    run_git(project_dir, &["switch", name])?;
+    Ok(())
+}
+
+/// Branch name for an agent's work: `clautron/<label>-<first 8 of the id>`,
+/// the label lower-cased with runs of other characters turned into `-`.
+pub fn session_branch_name(label: &str, session_id: &str) -> String {
+    let mut slug = String::new();
+    for c in label.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug: String = slug.trim_end_matches('-').chars().take(40).collect();
+    let slug = slug.trim_end_matches('-');
+    let short_id: String = session_id.chars().take(8).collect();
+    if slug.is_empty() {
+        format!("clautron/{short_id}")
+    } else {
+        format!("clautron/{slug}-{short_id}")
+    }
+}
+
+/// Stage everything (untracked files too, ignored ones not) and commit it.
+/// Returns the new commit's abbreviated hash, or None when there was
+/// nothing to commit.
+pub fn commit_all(project_dir: &str, message: &str) -> Result<Option<String>, String> {
+    run_git(project_dir, &["add", "--all"])?;
+    if run_git(project_dir, &["diff", "--cached", "--quiet"]).is_ok() {
+        return Ok(None);
+    }
+    run_git(project_dir, &["commit", "--quiet", "-m", message])?;
+    run_git(project_dir, &["rev-parse", "--short", "HEAD"])
+        .map(|hash| Some(hash.trim().to_string()))
+}
+
+/// Number of changed paths (staged, unstaged or untracked) in the working
+/// tree. Fails outside a repository.
+pub fn uncommitted_count(project_dir: &str) -> Result<usize, String> {
+    let stdout = run_git(project_dir, &["status", "--porcelain=v1", "-z"])?;
+    Ok(parse_status(&stdout).len())
+}
+
+/// Stash every change, untracked files included, under `message`. Returns
+/// the stash's commit hash, or None when there was nothing to stash.
+pub fn stash_all(project_dir: &str, message: &str) -> Result<Option<String>, String> {
+    if uncommitted_count(project_dir)? == 0 {
+        return Ok(None);
+    }
+    run_git(
+        project_dir,
+        &["stash", "push", "--include-untracked", "--quiet", "-m", message],
+    )?;
+    run_git(project_dir, &["rev-parse", "--short", "stash@{0}"])
+        .map(|hash| Some(hash.trim().to_string()))
+}
+
+/// Throw away every change to `path`, staged or not. Untracked files are
+/// deleted.
+pub fn discard_file(project_dir: &str, path: &str) -> Result<(), String> {
+    check_repo_path(path)?;
+    let tracked = run_git(project_dir, &["ls-files", "--error-unmatch", "--", path]).is_ok();
+    let in_head = run_git(project_dir, &["cat-file", "-e", &format!("HEAD:{path}")]).is_ok();
+    if tracked || in_head {
+        run_git(
+            project_dir,
+            &[
+                "restore",
+                "--source=HEAD",
+                "--staged",
+                "--worktree",
+                "--",
+                path,
+            ],
+        )?;
+    } else {
+        std::fs::remove_file(std::path::Path::new(project_dir).join(path))
+            .map_err(|e| format!("Failed to delete {path}: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Revert one hunk of `path`, identified by its `@@` header as returned by
+/// `get_diff` for the `staged` or unstaged side. An unstaged hunk is
+/// reverted in the working tree. A staged one is reverted in the index,
+/// then in the working tree unless later edits there touch the same lines,
+/// which leaves the hunk unstaged and is reported as an error.
+pub fn discard_hunk(
+    project_dir: &str,
+    path: &str,
+    hunk_header: &str,
+    staged: bool,
+) -> Result<(), String> {
+    check_repo_path(path)?;
+    let args: &[&str] = if staged {
+        &["diff", "--cached", "--", path]
+    } else {
+        &["diff", "--", path]
+    };
+    let diff = run_git(project_dir, args)?;
+    let patch = extract_hunk_patch(&diff, hunk_header)
+        .ok_or_else(|| format!("Hunk {hunk_header} not found in {path}; refresh the diff"))?;
+    if !staged {
+        return git_apply(project_dir, &patch, &["-R"]);
+    }
+    git_apply(project_dir, &patch, &["-R", "--cached"])?;
+    git_apply(project_dir, &patch, &["-R"]).map_err(|e| {
+        format!(
+            "Unstaged the hunk, but the working tree has other edits to those lines, \
+             so it was left as is: {e}"
+        )
+    })
+}
+
+/// A patch holding just the file header and the hunk starting with
+/// `hunk_header`, from the diff of a single file.
+fn extract_hunk_patch(diff: &str, hunk_header: &str) -> Option<String> {
+    let mut header = String::new();
+    let mut hunk = String::new();
+    let mut seen_hunk = false;
+    for line in diff.split_inclusive('\n') {
+        let text = line.trim_end_matches('\n');
+        if text.starts_with("@@") {
+            if !hunk.is_empty() {
+                break;
+            }
+            seen_hunk = true;
+            if text == hunk_header {
+                hunk.push_str(line);
+            }
+        } else if !hunk.is_empty() {
+            hunk.push_str(line);
+        } else if !seen_hunk {
+            // diff --git, index, --- and +++ lines
+            header.push_str(line);
+        }
+    }
+    if hunk.is_empty() {
+        return None;
+    }
+    if !hunk.ends_with('\n') {
+        hunk.push('\n');
+    }
+    Some(header + &hunk)
+}
+
+/// Which of `paths` (relative to the project) `.gitignore` rules exclude.
+/// Empty when git can't tell, so nothing is wrongly left out.
+pub fn ignored_paths(project_dir: &str, paths: &[String]) -> Vec<String> {
+    use std::io::Write;
+
+    if paths.is_empty() {
+        return Vec::new();
+    }
+    let Ok(mut child) = git_command()
+        .args(["check-ignore", "-z", "--stdin"])
+        .current_dir(project_dir)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+    else {
+        return Vec::new();
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(paths.join("\0").as_bytes());
+    }
+    // Exits 1 when nothing is ignored
+    let Ok(output) = child.wait_with_output() else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// A file's part of a patch, with its headers.
+struct PatchSection {
+    path: String,
+    /// Path before a rename or the deleted file's path; None when unchanged.
+    old_path: Option<String>,
+    created: bool,
+    deleted: bool,
+    text: String,
+}
+
+/// Check a patch (e.g. one an agent wrote in plan mode) against the working
+/// tree and apply the parts touching `paths`, or all of it. `hunks` narrows
+/// a file down to some of its hunks, by their index in `preview_patch`;
+/// files it doesn't list keep every hunk. Nothing is applied unless every
+/// selected file applies cleanly. Returns the paths changed.
+pub fn apply_patch(
+    project_dir: &str,
+    diff_text: &str,
+    paths: Option<&[String]>,
+    hunks: Option<&HashMap<String, Vec<usize>>>,
+) -> Result<Vec<String>, String> {
+    let sections = split_patch(diff_text)?;
+    let mut patch = String::new();
+    let mut changed = Vec::new();
+    for section in &sections {
+        if paths.is_some_and(|paths| !paths.contains(&section.path)) {
             continue;
         }
-        let status = line[..2].trim().to_string();
-        let path = line[3..].trim().to_string();
-        files.push(ChangedFile { path, status });
+        match hunks.and_then(|hunks| hunks.get(&section.path)) {
+            Some(keep) if keep.is_empty() => continue,
+            Some(keep) => patch.push_str(&select_hunks(&section.text, keep)),
+            None => patch.push_str(&section.text),
+        }
+        changed.push(section.path.clone());
     }
+    if changed.is_empty() {
+        return Err("None of the selected files are in the patch".into());
+    }
+    git_apply(project_dir, &patch, &["--check"])?;
+    git_apply(project_dir, &patch, &[])?;
+    Ok(changed)
+}
 
-    Ok(files)
+/// A section from `split_patch` with only the hunks at `keep`. The new-side
+/// start of each kept hunk is moved to account for the hunks left out.
+fn select_hunks(text: &str, keep: &[usize]) -> String {
+    let mut out = String::new();
+    let mut index: Option<usize> = None;
+    // Lines the kept hunks so far add, less the ones they remove
+    let mut shift: i64 = 0;
+    for line in text.lines() {
+        if line.starts_with("@@") {
+            index = Some(index.map_or(0, |i| i + 1));
+        }
+        let Some(i) = index else {
+            // File header
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        };
+        if !keep.contains(&i) {
+            continue;
+        }
+        // `@@ -4,2 +4,3 @@ tail`, as `recount_hunks` writes it
+        let header = line.strip_prefix("@@ -").and_then(|rest| {
+            let (ranges, tail) = rest.split_once(" @@")?;
+            let (old, new) = ranges.split_once(" +")?;
+            let (old_start, old_count) = old.split_once(',')?;
+            let (_, new_count) = new.split_once(',')?;
+            let old_start: i64 = old_start.parse().ok()?;
+            let old_count: i64 = old_count.parse().ok()?;
+            let new_count: i64 = new_count.parse().ok()?;
+            Some((old_start, old_count, new_count, tail))
+        });
+        match header {
+            Some((old_start, old_count, new_count, tail)) => {
+                // A pure insertion's old start is the line before it
+                let new_start = old_start + shift + i64::from(old_count == 0);
+                out.push_str(&format!(
+                    "@@ -{old_start},{old_count} +{new_start},{new_count} @@{tail}\n"
+                ));
+                shift += new_count - old_count;
+            }
+            None => {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+/// What a patch would change, per file, for choosing which files to apply.
+pub fn preview_patch(diff_text: &str) -> Result<Vec<FileDiff>, String> {
+    let mut diffs = Vec::new();
+    for section in split_patch(diff_text)? {
+        let text = if section.text.starts_with("diff --git ") {
+            section.text
+        } else {
+            let old = section.old_path.as_deref().unwrap_or(&section.path);
+            format!("diff --git a/{old} b/{}\n{}", section.path, section.text)
+        };
+        let Some(mut diff) = parse_unified_diff(&text).pop() else {
+            continue;
+        };
+        diff.path = section.path;
+        if section.created {
+            diff.change_type = "added".into();
+        } else if section.deleted {
+            diff.change_type = "deleted".into();
+        } else if section.old_path.is_some() {
+            diff.change_type = "renamed".into();
+            diff.old_path = section.old_path;
+        }
+        diffs.push(diff);
+    }
+    Ok(diffs)
+}
+
+/// Split a unified diff into per-file sections. Patches from agents often
+/// come in a code fence and without `diff --git` lines, so a section also
+/// starts at `---`, `+++` and `@@` lines in a row, and `a/`/`b/` prefixes
+/// are added where they're missing. Text after a hunk is dropped and hunk
+/// line counts, which hand-written hunks often get wrong, are recounted.
+/// Every path must stay inside the repository.
+fn split_patch(diff_text: &str) -> Result<Vec<PatchSection>, String> {
+    let lines: Vec<&str> = diff_text
+        .lines()
+        .filter(|l| !l.starts_with("```"))
+        .collect();
+    let mut sections: Vec<PatchSection> = Vec::new();
+    // Whether the current section has reached its hunks, and whether it
+    // started with a `diff --git` line (whose header has its own ---/+++)
+    let (mut in_hunks, mut git_header) = (false, false);
+    // Prose after the current section's hunks
+    let mut trailing_text = false;
+
+    for (i, line) in lines.iter().enumerate() {
+        let plain_header = (in_hunks || !git_header)
+            && line.starts_with("--- ")
+            && lines.get(i + 1).is_some_and(|l| l.starts_with("+++ "))
+            && lines.get(i + 2).is_some_and(|l| l.starts_with("@@"));
+        if line.starts_with("diff --git ") || plain_header {
+            git_header = line.starts_with("diff --git ");
+            in_hunks = false;
+            trailing_text = false;
+            sections.push(PatchSection {
+                path: String::new(),
+                old_path: None,
+                created: false,
+                deleted: false,
+                text: String::new(),
+            });
+        }
+        let Some(section) = sections.last_mut() else {
+            // Prose before the first file
+            continue;
+        };
+        if line.starts_with("@@") {
+            in_hunks = true;
+        } else if in_hunks && !line.is_empty() && !line.starts_with([' ', '+', '-', '\\']) {
+            trailing_text = true;
+        }
+        if trailing_text {
+            continue;
+        }
+
+        let mut line = line.to_string();
+        if in_hunks && line.is_empty() {
+            // Editors and chat output strip the space of blank context lines
+            line = " ".to_string();
+        } else if !in_hunks {
+            if let Some(rest) = line.strip_prefix("--- ") {
+                let old = header_path(rest, "a/");
+                if old.is_none() {
+                    section.created = true;
+                }
+                section.old_path = old.clone();
+                if !rest.starts_with("a/") && old.is_some() {
+                    line = format!("--- a/{rest}");
+                }
+            } else if let Some(rest) = line.strip_prefix("+++ ") {
+                match header_path(rest, "b/") {
+                    Some(path) => section.path = path,
+                    None => section.deleted = true,
+                }
+                if !rest.starts_with("b/") && !section.deleted {
+                    line = format!("+++ b/{rest}");
+                }
+            } else if let Some(from) = line.strip_prefix("rename from ") {
+                section.old_path = Some(from.to_string());
+            } else if let Some(to) = line.strip_prefix("rename to ") {
+                section.path = to.to_string();
+            }
+        }
+        section.text.push_str(&line);
+        section.text.push('\n');
+    }
+
+    for section in &mut sections {
+        section.text = recount_hunks(&section.text);
+        if section.path.is_empty() {
+            // A deletion, or a `diff --git` header with no ---/+++ lines
+            section.path = match (&section.old_path, section.text.lines().next()) {
+                (Some(old), _) => old.clone(),
+                (None, Some(first)) => first
+                    .rsplit_once(" b/")
+                    .map(|(_, path)| path.to_string())
+                    .unwrap_or_default(),
+                (None, None) => String::new(),
+            };
+        }
+        if section.old_path.as_ref() == Some(&section.path) {
+            section.old_path = None;
+        }
+        check_repo_path(&section.path)?;
+        if let Some(old) = &section.old_path {
+            check_repo_path(old)?;
+        }
+    }
+    if sections.is_empty() {
+        return Err("No file changes found in the patch".into());
+    }
+    Ok(sections)
+}
+
+/// Rewrite each hunk header with the line counts its body really has.
+/// Blank lines at the end of a hunk are dropped.
+fn recount_hunks(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        i += 1;
+        let mut end = i;
+        while line.starts_with("@@") && end < lines.len() && !lines[end].starts_with("@@") {
+            end += 1;
+        }
+        // `@@ -1,3 +1,4 @@ fn main() {`
+        let starts = line.strip_prefix("@@ -").and_then(|rest| {
+            let (ranges, tail) = rest.split_once(" @@")?;
+            let (old, new) = ranges.split_once(" +")?;
+            let start = |range: &str| range.split(',').next().unwrap_or(range).to_string();
+            Some((start(old), start(new), tail))
+        });
+        let Some((old_start, new_start, tail)) = starts else {
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        };
+
+        let mut body = &lines[i..end];
+        while let Some((last, rest)) = body.split_last() {
+            if !last.trim().is_empty() {
+                break;
+            }
+            body = rest;
+        }
+        let count = |sides: [char; 2]| body.iter().filter(|l| l.starts_with(sides)).count();
+        out.push_str(&format!(
+            "@@ -{old_start},{} +{new_start},{} @@{tail}\n",
+            count([' ', '-']),
+            count([' ', '+'])
+        ));
+        for l in body {
+            out.push_str(l);
+            out.push('\n');
+        }
+        i = end;
+    }
+    out
+}
+
+/// The path in a `---`/`+++` header, without its `a/`/`b/` prefix and any
+/// trailing tab and timestamp. None for `/dev/null`.
+fn header_path(rest: &str, prefix: &str) -> Option<String> {
+    let path = rest.split('\t').next().unwrap_or(rest).trim_end();
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(path.strip_prefix(prefix).unwrap_or(path).to_string())
+}
+
+/// `git apply` with `args`, reading the patch from stdin.
+fn git_apply(project_dir: &str, patch: &str, args: &[&str]) -> Result<(), String> {
+    use std::io::Write;
+    let mut child = git_command()
+        .args(["apply", "--whitespace=nowarn"])
+        .args(args)
+        .current_dir(project_dir)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run git apply: {e}"))?;
+    child
+        .stdin
+        .take()
+        .ok_or("No stdin for git apply")?
+        .write_all(patch.as_bytes())
+        .map_err(|e| format!("Failed to write patch: {e}"))?;
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run git apply: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git apply failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Paths from the frontend must stay inside the repository.
+fn check_repo_path(path: &str) -> Result<(), String> {
+    let p = std::path::Path::new(path);
+    let escapes = p.components().any(|c| {
+        !matches!(
+            c,
+            std::path::Component::Normal(_) | std::path::Component::CurDir
+        )
+    });
+    if path.is_empty() || escapes {
+        return Err(format!("Invalid path: {path}"));
+    }
+    Ok(())
+}
+
+fn validate_branch_name(project_dir: &str, name: &str) -> Result<(), String> {
+    // Names starting with `-` would be read as options
+    if name.starts_with('-')
+        || run_git(project_dir, &["check-ref-format", "--branch", name]).is_err()
+    {
+        return Err(format!("Invalid branch name: {name}"));
+    }
+    Ok(())
+}
+
+fn run_git(project_dir: &str, args: &[&str]) -> Result<String, String> {
+    let output = git_command()
+        .args(args)
+        .current_dir(project_dir)
+        .output()
+        .map_err(|e| format!("Failed to run git {}: {e}", args[0]))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Whether `branch` was merged into the default branch with a merge commit:
+/// its tip is a second parent of a recent merge. Fast-forward and squash
+/// merges aren't detected here (the pull request state covers those).
+pub fn is_branch_merged(project_dir: &str, branch: &str) -> bool {
+    if branch.is_empty() || branch.starts_with('-') {
+        return false;
+    }
+    let git = |args: &[&str]| -> Option<String> {
+        let output = git_command()
+            .args(args)
+            .current_dir(project_dir)
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let Some(tip) = git(&[
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("{branch}^{{commit}}"),
+    ]) else {
+        return false;
+    };
+    let default = git(&["symbolic-ref", "--short", "refs/remotes/origin/HEAD"]).or_else(|| {
+        ["main", "master"]
+            .into_iter()
+            .find(|b| git(&["rev-parse", "--verify", "--quiet", b]).is_some())
+            .map(String::from)
+    });
+    let Some(default) = default.filter(|d| d.as_str() != branch) else {
+        return false;
+    };
+    let Some(parents) = git(&["log", &default, "--merges", "--format=%P", "-n", "500"]) else {
+        return false;
+    };
+    parents
+        .lines()
+        .any(|line| line.split_whitespace().skip(1).any(|p| p == tip))
 }
 
 /// Get unified diff for specific files or all changes.
@@ -57,43 +826,484 @@ pub fn get_diff(
     project_dir: &str,
     paths: Option<Vec<String>>,
 ) -> Result<Vec<FileDiff>, String> {
-    let mut diffs = Vec::new();
+    let paths = paths.as_deref();
+    let mut diffs = tracked_diffs(project_dir, false, paths)?;
+    diffs.extend(tracked_diffs(project_dir, true, paths)?);
+    diffs.extend(untracked_diffs(project_dir, paths)?);
+    Ok(diffs)
+}
 
-    // Get unstaged diffs
+/// Diff of one file, so large changesets can be loaded a file at a time:
+/// the index against HEAD when `staged`, otherwise the working tree against
+/// the index, with an untracked file shown as added. None when that side
+/// has no changes. Only `path` is diffed, so a rename shows up as an added
+/// file.
+pub fn get_file_diff(
+    project_dir: &str,
+    path: &str,
+    staged: bool,
+) -> Result<Option<FileDiff>, String> {
+    check_repo_path(path)?;
+    let paths = [path.to_string()];
+    let mut diffs = tracked_diffs(project_dir, staged, Some(&paths))?;
+    if diffs.is_empty() && !staged {
+        diffs = untracked_diffs(project_dir, Some(&paths))?;
+    }
+    Ok(diffs.into_iter().next())
+}
+
+/// Staged (index against HEAD) or unstaged (working tree against the index)
+/// changes to tracked files.
+fn tracked_diffs(
+    project_dir: &str,
+    staged: bool,
+    paths: Option<&[String]>,
+) -> Result<Vec<FileDiff>, String> {
     let mut args = vec!["diff".to_string()];
-    if let Some(ref paths) = paths {
+    if staged {
+        args.push("--cached".to_string());
+    }
+    args.push("-M".to_string());
+    if let Some(paths) = paths {
         args.push("--".to_string());
-        args.extend(paths.clone());
+        args.extend_from_slice(paths);
     }
 
-    let output = Command::new("git")
+    let output = git_command()
         .args(&args)
         .current_dir(project_dir)
         .output()
         .map_err(|e| format!("Failed to run git diff: {e}"))?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    diffs.extend(parse_unified_diff(&stdout));
+    let mut diffs = parse_unified_diff(&stdout);
+    if staged {
+        for diff in &mut diffs {
+            diff.staged = true;
+        }
+        set_binary_sizes(project_dir, &mut diffs, Side::Tree("HEAD"), Side::Index);
+    } else {
+        set_binary_sizes(project_dir, &mut diffs, Side::Index, Side::WorkTree);
+    }
+    Ok(diffs)
+}
 
-    // Get staged diffs
-    let mut args = vec!["diff".to_string(), "--cached".to_string()];
-    if let Some(ref paths) = paths {
+/// Untracked files aren't in either diff; show them as added.
+fn untracked_diffs(project_dir: &str, paths: Option<&[String]>) -> Result<Vec<FileDiff>, String> {
+    let mut args = vec![
+        "ls-files".to_string(),
+        "--others".to_string(),
+        "--exclude-standard".to_string(),
+        "-z".to_string(),
+    ];
+    if let Some(paths) = paths {
         args.push("--".to_string());
-        args.extend(paths.clone());
+        args.extend_from_slice(paths);
     }
 
-    let output = Command::new("git")
+    let output = git_command()
         .args(&args)
         .current_dir(project_dir)
         .output()
-        .map_err(|e| format!("Failed to run git diff --cached: {e}"))?;
+        .map_err(|e| format!("Failed to run git ls-files: {e}"))?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    diffs.extend(parse_unified_diff(&stdout));
+    let mut diffs = Vec::new();
+    for path in stdout.split('\0').filter(|p| !p.is_empty()) {
+        let full = std::path::Path::new(project_dir).join(path);
+        let Ok(meta) = std::fs::metadata(&full) else {
+            continue;
+        };
+        let content = if meta.len() > MAX_UNTRACKED_DIFF_BYTES {
+            None
+        } else {
+            std::fs::read(&full).ok()
+        };
+        diffs.push(untracked_diff(path, meta.len(), content.as_deref()));
+    }
+    Ok(diffs)
+}
 
+/// Record the whole working tree, untracked files included (ignored ones
+/// not), as a git tree and return its hash. Works on a copy of the index,
+/// so nothing the user has staged changes.
+pub fn snapshot_tree(project_dir: &str) -> Result<String, String> {
+    let index = run_git(project_dir, &["rev-parse", "--git-path", "index"])?;
+    let index = std::path::Path::new(project_dir).join(index.trim());
+    let tmp_index = std::env::temp_dir().join(format!("clautron-index-{}", uuid::Uuid::new_v4()));
+    let result = (|| {
+        if index.exists() {
+            // Starting from the real index keeps git's stat cache, so only
+            // changed files are hashed
+            std::fs::copy(&index, &tmp_index).map_err(|e| format!("Failed to copy index: {e}"))?;
+        }
+        let git = |args: &[&str]| -> Result<String, String> {
+            let output = git_command()
+                .args(args)
+                .env("GIT_INDEX_FILE", &tmp_index)
+                .current_dir(project_dir)
+                .output()
+                .map_err(|e| format!("Failed to run git {}: {e}", args[0]))?;
+            if !output.status.success() {
+                return Err(format!(
+                    "git {} failed: {}",
+                    args[0],
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+        git(&["add", "--all", "--", "."])?;
+        git(&["write-tree"])
+    })();
+    let _ = std::fs::remove_file(&tmp_index);
+    result
+}
+
+/// Changes between two trees from `snapshot_tree`, optionally limited to
+/// `paths`.
+pub fn diff_trees(
+    project_dir: &str,
+    from: &str,
+    to: &str,
+    paths: Option<Vec<String>>,
+) -> Result<Vec<FileDiff>, String> {
+    let mut args = vec![
+        "diff".to_string(),
+        "-M".to_string(),
+        from.to_string(),
+        to.to_string(),
+    ];
+    if let Some(paths) = paths {
+        args.push("--".to_string());
+        args.extend(paths);
+    }
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let mut diffs = parse_unified_diff(&run_git(project_dir, &args)?);
+    set_binary_sizes(project_dir, &mut diffs, Side::Tree(from), Side::Tree(to));
     Ok(diffs)
 }
 
+/// Where one side of a diff is read from.
+enum Side<'a> {
+    Tree(&'a str),
+    Index,
+    WorkTree,
+}
+
+/// Look up the sizes of binary files, which the diff text doesn't carry.
+fn set_binary_sizes(project_dir: &str, diffs: &mut [FileDiff], old: Side, new: Side) {
+    let size = |side: &Side, path: &str| -> Option<u64> {
+        let spec = match side {
+            Side::Tree(tree) => format!("{tree}:{path}"),
+            Side::Index => format!(":{path}"),
+            Side::WorkTree => {
+                return std::fs::metadata(std::path::Path::new(project_dir).join(path))
+                    .ok()
+                    .map(|m| m.len());
+            }
+        };
+        run_git(project_dir, &["cat-file", "-s", &spec])
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+    };
+    for diff in diffs.iter_mut().filter(|d| d.is_binary) {
+        if diff.change_type != "added" {
+            diff.old_size = size(&old, diff.old_path.as_deref().unwrap_or(&diff.path));
+        }
+        if diff.change_type != "deleted" {
+            diff.new_size = size(&new, &diff.path);
+        }
+    }
+}
+
+/// Untracked files larger than this are listed without their contents.
+const MAX_UNTRACKED_DIFF_BYTES: u64 = 256 * 1024;
+
+/// An added-file diff for an untracked file. Binary files (a NUL byte in
+/// the first 8000 bytes, as git checks) and files over the size cap, passed
+/// as `None`, get no hunks and report their `size` instead.
+fn untracked_diff(path: &str, size: u64, content: Option<&[u8]>) -> FileDiff {
+    let mut diff = FileDiff {
+        path: path.to_string(),
+        change_type: "added".to_string(),
+        hunks: Vec::new(),
+        old_path: None,
+        is_binary: false,
+        old_size: None,
+        new_size: None,
+        staged: false,
+    };
+    let Some(content) = content else {
+        diff.new_size = Some(size);
+        return diff;
+    };
+    if content.iter().take(8000).any(|&b| b == 0) {
+        diff.is_binary = true;
+        diff.new_size = Some(size);
+        return diff;
+    }
+    if content.is_empty() {
+        return diff;
+    }
+    let text = String::from_utf8_lossy(content);
+    let lines: Vec<DiffLine> = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| DiffLine {
+            line_type: "add".to_string(),
+            content: line.to_string(),
+            old_line: None,
+            new_line: Some(i as u32 + 1),
+            segments: None,
+        })
+        .collect();
+    diff.hunks.push(DiffHunk {
+        header: format!("@@ -0,0 +1,{} @@", lines.len()),
+        lines,
+    });
+    diff
+}
+
+/// One commit that touched a file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileRevision {
+    pub commit: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// ISO 8601 author date.
+    pub date: String,
+    pub subject: String,
+    /// Repo-relative path at this commit (follows renames).
+    pub path: String,
+    /// File contents at this commit; empty if the commit deleted it.
+    pub content: String,
+    /// Changes this commit made to the file.
+    pub diff: Vec<FileDiff>,
+}
+
+/// A commit from `get_commits`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitCommit {
+    pub hash: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// ISO 8601 committer date.
+    pub date: String,
+    pub subject: String,
+    /// The session that was running in the project when it was committed.
+    #[serde(default)]
+    pub session_id: Option<String>,
+}
+
+/// Commits on any branch committed between `since` and `until` (any date
+/// git understands, e.g. RFC 3339), newest first. `author` matches name
+/// or email as `git log --author` does.
+pub fn get_commits(
+    project_dir: &str,
+    since: &str,
+    until: Option<&str>,
+    author: Option<&str>,
+) -> Result<Vec<GitCommit>, String> {
+    let mut args = vec![
+        "log".to_string(),
+        "--all".to_string(),
+        format!("--since={since}"),
+        "--format=%x1e%H%x1f%an%x1f%ae%x1f%cI%x1f%s".to_string(),
+    ];
+    if let Some(until) = until {
+        args.push(format!("--until={until}"));
+    }
+    if let Some(author) = author.filter(|a| !a.is_empty()) {
+        args.push(format!("--author={author}"));
+    }
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    Ok(parse_commits(&run_git(project_dir, &args)?))
+}
+
+/// Parse `git log` records: RS-separated, fields split by US.
+fn parse_commits(stdout: &str) -> Vec<GitCommit> {
+    stdout
+        .split('\u{1e}')
+        .filter_map(|record| {
+            let fields: Vec<&str> = record.trim_end().split('\u{1f}').collect();
+            if fields.len() < 5 {
+                return None;
+            }
+            Some(GitCommit {
+                hash: fields[0].to_string(),
+                author_name: fields[1].to_string(),
+                author_email: fields[2].to_string(),
+                date: fields[3].to_string(),
+                subject: fields[4..].join("\u{1f}"),
+                session_id: None,
+            })
+        })
+        .collect()
+}
+
+/// Who last changed one line, from `get_blame`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlameLine {
+    /// Line number in the blamed revision.
+    pub line: u32,
+    pub commit: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// ISO 8601 author date.
+    pub date: String,
+    pub summary: String,
+    pub content: String,
+    /// Changed in the working tree and not committed yet.
+    pub uncommitted: bool,
+}
+
+/// Blame lines `start..=end` of `path` at `revision`, or in the working
+/// tree when None. Blaming `HEAD` at a diff's old line numbers shows who
+/// wrote the code a change replaced.
+pub fn get_blame(
+    project_dir: &str,
+    path: &str,
+    start: u32,
+    end: u32,
+    revision: Option<&str>,
+) -> Result<Vec<BlameLine>, String> {
+    check_repo_path(path)?;
+    if start == 0 || end < start {
+        return Err(format!("Invalid line range {start}-{end}"));
+    }
+    let range = format!("{start},{end}");
+    let mut args = vec!["blame", "--line-porcelain", "-L", &range];
+    if let Some(revision) = revision {
+        args.push(revision);
+    }
+    args.extend(["--", path]);
+    Ok(parse_blame(&run_git(project_dir, &args)?))
+}
+
+/// Parse `git blame --line-porcelain`: per line a `<hash> <orig> <final>`
+/// header, `key value` fields, then the content prefixed with a tab.
+fn parse_blame(stdout: &str) -> Vec<BlameLine> {
+    let mut lines = Vec::new();
+    let mut current: Option<BlameLine> = None;
+    let mut author_time = None;
+    for row in stdout.lines() {
+        if let Some(content) = row.strip_prefix('\t') {
+            if let Some(mut line) = current.take() {
+                line.content = content.to_string();
+                line.date = author_time
+                    .take()
+                    .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+                    .map(|d| d.to_rfc3339())
+                    .unwrap_or_default();
+                lines.push(line);
+            }
+            continue;
+        }
+        let (key, value) = row.split_once(' ').unwrap_or((row, ""));
+        match current.as_mut() {
+            None => {
+                let mut fields = value.split(' ');
+                let line = fields.nth(1).and_then(|n| n.parse().ok()).unwrap_or(0);
+                current = Some(BlameLine {
+                    line,
+                    commit: key.to_string(),
+                    author_name: String::new(),
+                    author_email: String::new(),
+                    date: String::new(),
+                    summary: String::new(),
+                    content: String::new(),
+                    uncommitted: key.bytes().all(|b| b == b'0'),
+                });
+            }
+            Some(line) => match key {
+                "author" => line.author_name = value.to_string(),
+                "author-mail" => {
+                    line.author_email = value.trim_matches(|c| c == '<' || c == '>').to_string()
+                }
+                "author-time" => author_time = value.parse().ok(),
+                "summary" => line.summary = value.to_string(),
+                _ => {}
+            },
+        }
+    }
+    lines
+}
+
+/// Commits that touched `path`, newest first, with the file's contents and
+/// diff at each one.
+pub fn get_file_history(
+    project_dir: &str,
+    path: &str,
+    limit: usize,
+) -> Result<Vec<FileRevision>, String> {
+    // Records are separated by RS, fields by US, followed by the file name
+    let limit = format!("-n{limit}");
+    let output = git_command()
+        .args([
+            "log",
+            "--follow",
+            &limit,
+            "--name-only",
+            "--format=%x1e%H%x1f%an%x1f%ae%x1f%aI%x1f%s",
+            "--",
+            path,
+        ])
+        .current_dir(project_dir)
+        .output()
+        .map_err(|e| format!("Failed to run git log: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git log failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut revisions = Vec::new();
+    for record in stdout.split('\u{1e}').filter(|r| !r.trim().is_empty()) {
+        let mut lines = record.lines();
+        let header = lines.next().unwrap_or_default();
+        let fields: Vec<&str> = header.split('\u{1f}').collect();
+        if fields.len() < 5 {
+            continue;
+        }
+        let commit = fields[0].to_string();
+        let path_at_commit = lines
+            .map(str::trim)
+            .find(|l| !l.is_empty())
+            .unwrap_or(path)
+            .to_string();
+
+        let content = git_command()
+            .args(["show", &format!("{commit}:{path_at_commit}")])
+            .current_dir(project_dir)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+            .unwrap_or_default();
+        let diff = git_command()
+            .args(["show", "--format=", &commit, "--", &path_at_commit])
+            .current_dir(project_dir)
+            .output()
+            .map(|o| parse_unified_diff(&String::from_utf8_lossy(&o.stdout)))
+            .unwrap_or_default();
+
+        revisions.push(FileRevision {
+            commit,
+            author_name: fields[1].to_string(),
+            author_email: fields[2].to_string(),
+            date: fields[3].to_string(),
+            subject: fields[4..].join("\u{1f}"),
+            path: path_at_commit,
+            content,
+            diff,
+        });
+    }
+    Ok(revisions)
+}
+
 /// Parse unified diff output into structured FileDiff objects.
 fn parse_unified_diff(diff_output: &str) -> Vec<FileDiff> {
     let mut files = Vec::new();
@@ -124,7 +1334,25 @@ fn parse_unified_diff(diff_output: &str) -> Vec<FileDiff> {
                 path,
                 change_type: "modified".to_string(),
                 hunks: Vec::new(),
+                old_path: None,
+                is_binary: false,
+                old_size: None,
+                new_size: None,
+                staged: false,
             });
+        } else if current_hunk.is_none() && (line.starts_with("+++ ") || line.starts_with("--- ")) {
+            // Exact even when the path has spaces, which the `diff --git`
+            // line can't tell apart; git ends such names with a tab
+            if let Some(ref mut file) = current_file {
+                let path = match line.strip_prefix("+++ b/") {
+                    Some(path) => Some(path),
+                    None if file.change_type == "deleted" => line.strip_prefix("--- a/"),
+                    None => None,
+                };
+                if let Some(path) = path {
+                    file.path = path.trim_end_matches('\t').to_string();
+                }
+            }
         } else if line.starts_with("new file") {
             if let Some(ref mut file) = current_file {
                 file.change_type = "added".to_string();
@@ -136,6 +1364,16 @@ fn parse_unified_diff(diff_output: &str) -> Vec<FileDiff> {
         } else if line.starts_with("rename") {
             if let Some(ref mut file) = current_file {
                 file.change_type = "renamed".to_string();
+                if let Some(from) = line.strip_prefix("rename from ") {
+                    file.old_path = Some(from.to_string());
+                } else if let Some(to) = line.strip_prefix("rename to ") {
+                    // A pure rename has no ---/+++ lines to take the path from
+                    file.path = to.to_string();
+                }
+            }
+        } else if current_hunk.is_none() && line.starts_with("Binary files ") {
+            if let Some(ref mut file) = current_file {
+                file.is_binary = true;
             }
         } else if line.starts_with("@@") {
             // Save previous hunk
@@ -156,20 +1394,23 @@ fn parse_unified_diff(diff_output: &str) -> Vec<FileDiff> {
                 lines: Vec::new(),
             });
         } else if let Some(ref mut hunk) = current_hunk {
-            if line.starts_with('+') && !line.starts_with("+++") {
+            // File headers are handled above, so `+++`/`---` here are content
+            if let Some(content) = line.strip_prefix('+') {
                 hunk.lines.push(DiffLine {
                     line_type: "add".to_string(),
-                    content: line[1..].to_string(),
+                    content: content.to_string(),
                     old_line: None,
                     new_line: Some(new_line),
+                    segments: None,
                 });
                 new_line += 1;
-            } else if line.starts_with('-') && !line.starts_with("---") {
+            } else if let Some(content) = line.strip_prefix('-') {
                 hunk.lines.push(DiffLine {
                     line_type: "remove".to_string(),
-                    content: line[1..].to_string(),
+                    content: content.to_string(),
                     old_line: Some(old_line),
                     new_line: None,
+                    segments: None,
                 });
                 old_line += 1;
             } else if line.starts_with(' ') || line.is_empty() {
@@ -183,6 +1424,7 @@ fn parse_unified_diff(diff_output: &str) -> Vec<FileDiff> {
                     content,
                     old_line: Some(old_line),
                     new_line: Some(new_line),
+                    segments: None,
                 });
                 old_line += 1;
                 new_line += 1;
@@ -198,6 +1440,9 @@ fn parse_unified_diff(diff_output: &str) -> Vec<FileDiff> {
         files.push(file);
     }
 
+    for hunk in files.iter_mut().flat_map(|f| f.hunks.iter_mut()) {
+        text_diff::mark_word_changes(&mut hunk.lines);
+    }
     files
 }
 
@@ -221,3 +1466,214 @@ fn parse_hunk_header(line: &str) -> Option<(u32, u32)> {
         .ok()?;
     Some((old_start, new_start))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_branches_are_named_after_the_label() {
+        let id = "3f2a9c1e-0000-4000-8000-000000000000";
+        assert_eq!(
+            session_branch_name("Add OAuth login (v2)!", id),
+            "clautron/add-oauth-login-v2-3f2a9c1e"
+        );
+        assert_eq!(session_branch_name("---", id), "clautron/3f2a9c1e");
+        assert!(session_branch_name(&"x".repeat(100), id).len() <= "clautron/".len() + 40 + 9);
+    }
+
+    #[test]
+    fn paths_with_spaces_and_renames_parse() {
+        let files = parse_status(" M src/a.rs\0R  new name.rs\0old name.rs\0?? docs/ünïcode.md\0");
+        let parsed: Vec<(&str, &str)> = files
+            .iter()
+            .map(|f| (f.status.as_str(), f.path.as_str()))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("M", "src/a.rs"),
+                ("R", "new name.rs"),
+                ("??", "docs/ünïcode.md")
+            ]
+        );
+
+        let diff = "diff --git a/my notes.md b/my notes.md\n\
+                    deleted file mode 100644\n\
+                    --- a/my notes.md\t\n\
+                    +++ /dev/null\n\
+                    @@ -1 +0,0 @@\n\
+                    --- a/not a header\n";
+        let files = parse_unified_diff(diff);
+        assert_eq!(files[0].path, "my notes.md");
+        assert_eq!(files[0].change_type, "deleted");
+        assert_eq!(files[0].hunks[0].lines[0].content, "-- a/not a header");
+
+        let diff = "diff --git a/old name.png b/new name.png\n\
+                    similarity index 100%\n\
+                    rename from old name.png\n\
+                    rename to new name.png\n\
+                    diff --git a/icon.png b/icon.png\n\
+                    index 1a2b3c4..5d6e7f8 100644\n\
+                    Binary files a/icon.png and b/icon.png differ\n";
+        let files = parse_unified_diff(diff);
+        assert_eq!(files[0].change_type, "renamed");
+        assert_eq!(files[0].path, "new name.png");
+        assert_eq!(files[0].old_path.as_deref(), Some("old name.png"));
+        assert!(!files[0].is_binary);
+        assert!(files[1].is_binary && files[1].hunks.is_empty());
+    }
+
+    #[test]
+    fn agent_patches_split_into_files() {
+        let patch = "Here is the fix:\n\
+                     ```diff\n\
+                     --- src/lib.rs\t2026-01-01\n\
+                     +++ src/lib.rs\n\
+                     @@ -1,2 +1,2 @@\n\
+                     -let a = 1;\n\
+                     +let a = 2;\n\
+                     --- not a header\n\
+                     --- /dev/null\n\
+                     +++ b/docs/new.md\n\
+                     @@ -0,0 +1 @@\n\
+                     +# New\n\
+                     diff --git a/old.rs b/new.rs\n\
+                     similarity index 100%\n\
+                     rename from old.rs\n\
+                     rename to new.rs\n\
+                     ```\n";
+        let sections = split_patch(patch).unwrap();
+        let paths: Vec<&str> = sections.iter().map(|s| s.path.as_str()).collect();
+        assert_eq!(paths, ["src/lib.rs", "docs/new.md", "new.rs"]);
+        assert!(sections[0]
+            .text
+            .starts_with("--- a/src/lib.rs\t2026-01-01\n+++ b/src/lib.rs\n"));
+        assert!(sections[0].text.ends_with("--- not a header\n"));
+        assert!(sections[1].created && sections[1].old_path.is_none());
+        assert_eq!(sections[2].old_path.as_deref(), Some("old.rs"));
+
+        let previews = preview_patch(patch).unwrap();
+        assert_eq!(previews[0].hunks[0].lines.len(), 3);
+        assert_eq!(previews[1].change_type, "added");
+        assert_eq!(previews[2].change_type, "renamed");
+
+        assert!(split_patch("--- a/x\n+++ b/../x\n@@ -1 +1 @@\n").is_err());
+        assert!(split_patch("No changes needed.").is_err());
+
+        // Leaving out the first hunk moves the second one's new start back
+        let two_hunks = "--- a/f\n+++ b/f\n\
+                         @@ -1,1 +1,2 @@\n a\n+b\n\
+                         @@ -9,1 +10,1 @@ fn x\n-c\n+d\n";
+        assert_eq!(
+            select_hunks(two_hunks, &[1]),
+            "--- a/f\n+++ b/f\n@@ -9,1 +9,1 @@ fn x\n-c\n+d\n"
+        );
+    }
+
+    #[test]
+    fn numstat_counts_per_file() {
+        let stats = parse_numstat(
+            "3\t1\tsrc/a b.rs\0-\t-\ticon.png\0\
+             2\t0\t\0old.rs\0new.rs\0",
+        );
+        assert_eq!(
+            stats,
+            [
+                ("src/a b.rs".to_string(), Some(3), Some(1)),
+                ("icon.png".to_string(), None, None),
+                ("new.rs".to_string(), Some(2), Some(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn commit_records_parse() {
+        let stdout = "\u{1e}abc123\u{1f}Ada\u{1f}ada@example.com\u{1f}2026-01-02T10:00:00+00:00\u{1f}fix: parser\n\
+                      \u{1e}def456\u{1f}Bob\u{1f}bob@example.com\u{1f}2026-01-01T09:00:00+00:00\u{1f}feat: a\u{1f}b\n";
+        let commits = parse_commits(stdout);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].hash, "abc123");
+        assert_eq!(commits[0].subject, "fix: parser");
+        assert_eq!(commits[1].author_email, "bob@example.com");
+        assert_eq!(commits[1].subject, "feat: a\u{1f}b");
+        assert!(parse_commits("").is_empty());
+    }
+
+    #[test]
+    fn blame_porcelain_parses_per_line() {
+        let stdout = "abc1230000000000000000000000000000000000 3 10 1\n\
+                      author Ada\n\
+                      author-mail <ada@example.com>\n\
+                      author-time 1767261600\n\
+                      author-tz +0000\n\
+                      summary fix: parser\n\
+                      filename src/lib.rs\n\
+                      \tlet a = 1;\n\
+                      0000000000000000000000000000000000000000 11 11 1\n\
+                      author Not Committed Yet\n\
+                      author-mail <not.committed.yet>\n\
+                      author-time 1767265200\n\
+                      summary Version of src/lib.rs from src/lib.rs\n\
+                      filename src/lib.rs\n\
+                      \t\n";
+        let lines = parse_blame(stdout);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].line, 10);
+        assert_eq!(lines[0].author_email, "ada@example.com");
+        assert_eq!(lines[0].date, "2026-01-01T10:00:00+00:00");
+        assert_eq!(lines[0].summary, "fix: parser");
+        assert_eq!(lines[0].content, "let a = 1;");
+        assert!(!lines[0].uncommitted);
+        assert!(lines[1].uncommitted);
+        assert_eq!(lines[1].content, "");
+    }
+
+    #[test]
+    fn untracked_files_diff_as_added() {
+        let diff = untracked_diff("notes.md", 14, Some(b"# Notes\n\nDone\n"));
+        assert_eq!(diff.change_type, "added");
+        assert_eq!(diff.hunks[0].header, "@@ -0,0 +1,3 @@");
+        let lines: Vec<(&str, Option<u32>)> = diff.hunks[0]
+            .lines
+            .iter()
+            .map(|l| (l.content.as_str(), l.new_line))
+            .collect();
+        assert_eq!(
+            lines,
+            [("# Notes", Some(1)), ("", Some(2)), ("Done", Some(3))]
+        );
+
+        let image = untracked_diff("logo.png", 6, Some(b"\x89PNG\0\0"));
+        assert!(image.is_binary && image.hunks.is_empty());
+        assert_eq!(image.new_size, Some(6));
+        let huge = untracked_diff("huge.log", 1 << 20, None);
+        assert!(!huge.is_binary && huge.hunks.is_empty());
+        assert_eq!(huge.new_size, Some(1 << 20));
+    }
+
+    #[test]
+    fn hunk_patches_keep_the_file_header() {
+        let diff = "diff --git a/src/a.rs b/src/a.rs\n\
+                    index 1111111..2222222 100644\n\
+                    --- a/src/a.rs\n\
+                    +++ b/src/a.rs\n\
+                    @@ -1,2 +1,2 @@\n\
+                    -one\n\
+                    +uno\n \
+                    two\n\
+                    @@ -10,1 +10,2 @@ fn main()\n \
+                    ten\n\
+                    +eleven\n";
+        let patch = extract_hunk_patch(diff, "@@ -10,1 +10,2 @@ fn main()").unwrap();
+        assert_eq!(
+            patch,
+            "diff --git a/src/a.rs b/src/a.rs\nindex 1111111..2222222 100644\n\
+             --- a/src/a.rs\n+++ b/src/a.rs\n@@ -10,1 +10,2 @@ fn main()\n ten\n+eleven\n"
+        );
+        assert!(extract_hunk_patch(diff, "@@ -5,1 +5,1 @@").is_none());
+        assert!(check_repo_path("src/a.rs").is_ok());
+        assert!(check_repo_path("../outside").is_err());
+        assert!(check_repo_path("/etc/passwd").is_err());
+    }
+}
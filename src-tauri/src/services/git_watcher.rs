@@ -0,0 +1,102 @@
+use crate::services::git_service;
+use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Event emitted when files in the project's working tree change.
+#[derive(Clone, Serialize)]
+pub struct WorkingTreeChangedEvent {
+    /// Paths relative to the project; ignored files are left out.
+    pub changed_files: Vec<String>,
+}
+
+/// Start watching the project's working tree, so views showing its diff
+/// can refresh while agents edit files. Debounced at 500ms. Emits
+/// `git:working-tree-changed` unless every change was to an ignored file.
+pub fn start_watching(
+    app: AppHandle,
+    project_dir: PathBuf,
+) -> Option<notify_debouncer_mini::Debouncer<notify::RecommendedWatcher>> {
+    if !project_dir.join(".git").exists() {
+        return None;
+    }
+
+    // Events may carry the resolved path (e.g. /private/var on macOS)
+    let root = std::fs::canonicalize(&project_dir).unwrap_or_else(|_| project_dir.clone());
+    let watch_dir = root.clone();
+    let dir = project_dir.to_string_lossy().to_string();
+
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(500),
+        move |events: Result<Vec<notify_debouncer_mini::DebouncedEvent>, notify::Error>| {
+            if let Ok(events) = events {
+                let mut paths: Vec<String> = events
+                    .iter()
+                    .filter(|e| e.kind == DebouncedEventKind::Any)
+                    .filter_map(|e| {
+                        e.path
+                            .strip_prefix(&root)
+                            .or_else(|_| e.path.strip_prefix(&project_dir))
+                            .ok()
+                    })
+                    .filter(|path| affects_status(path))
+                    .map(|path| path.to_string_lossy().to_string())
+                    .collect();
+                paths.sort();
+                paths.dedup();
+
+                let (git_files, files): (Vec<String>, Vec<String>) =
+                    paths.into_iter().partition(|p| p.starts_with(".git/"));
+                let ignored = git_service::ignored_paths(&dir, &files);
+                let mut changed: Vec<String> =
+                    files.into_iter().filter(|p| !ignored.contains(p)).collect();
+                changed.extend(git_files);
+
+                if !changed.is_empty() {
+                    let _ = app.emit(
+                        "git:working-tree-changed",
+                        WorkingTreeChangedEvent {
+                            changed_files: changed,
+                        },
+                    );
+                }
+            }
+        },
+    )
+    .ok()?;
+
+    debouncer
+        .watcher()
+        .watch(&watch_dir, notify::RecursiveMode::Recursive)
+        .ok()?;
+
+    Some(debouncer)
+}
+
+/// Whether a change to `path` (relative to the project) can change what
+/// the review diff shows. Inside `.git/` only the index (staging) and HEAD
+/// (checkouts) count; objects, logs and lock files churn on every command.
+fn affects_status(path: &Path) -> bool {
+    let mut components = path.components();
+    if components.next() != Some(Component::Normal(".git".as_ref())) {
+        return true;
+    }
+    matches!(components.as_path().to_str(), Some("index") | Some("HEAD"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_index_and_head_count_inside_git_dir() {
+        assert!(affects_status(Path::new("src/main.rs")));
+        assert!(affects_status(Path::new(".github/workflows/ci.yml")));
+        assert!(affects_status(Path::new(".git/index")));
+        assert!(affects_status(Path::new(".git/HEAD")));
+        assert!(!affects_status(Path::new(".git/index.lock")));
+        assert!(!affects_status(Path::new(".git/objects/ab/cdef")));
+    }
+}
@@ -0,0 +1,195 @@
+use crate::domain::models::ExternalIssue;
+use crate::services::spec_manager::priority_from_labels;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::process::Command;
+
+const API_BASE: &str = "https://api.github.com";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Issue fields shared by the REST API and `gh --json` output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GithubIssue {
+    pub number: u64,
+    pub title: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<GithubLabel>,
+    /// `html_url` in the REST API, `url` in `gh` output.
+    #[serde(alias = "html_url", default)]
+    pub url: Option<String>,
+    /// Present only for pull requests in the REST issues endpoint.
+    #[serde(default, skip_serializing)]
+    pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GithubLabel {
+    pub name: String,
+}
+
+/// List open issues in `repo` (`owner/name`) so the user can pick which to import.
+pub async fn list_issues(
+    repo: &str,
+    token: Option<&str>,
+    limit: u32,
+) -> Result<Vec<GithubIssue>, String> {
+    validate_repo(repo)?;
+    let issues: Vec<GithubIssue> = match token {
+        Some(token) => {
+            let per_page = limit.clamp(1, 100);
+            api_get(
+                &format!("{API_BASE}/repos/{repo}/issues?state=open&per_page={per_page}"),
+                token,
+            )
+            .await?
+        }
+        None => {
+            let limit = limit.to_string();
+            gh_json(&[
+                "issue",
+                "list",
+                "--repo",
+                repo,
+                "--state",
+                "open",
+                "--limit",
+                &limit,
+                "--json",
+                "number,title,body,labels,url",
+            ])
+            .await?
+        }
+    };
+    // The REST endpoint also returns pull requests
+    Ok(issues
+        .into_iter()
+        .filter(|i| i.pull_request.is_none())
+        .collect())
+}
+
+/// Fetch specific issues and normalize them for import.
+pub async fn fetch_issues(
+    repo: &str,
+    numbers: &[u64],
+    token: Option<&str>,
+) -> Result<Vec<ExternalIssue>, String> {
+    validate_repo(repo)?;
+    let mut issues = Vec::new();
+    for number in numbers {
+        let issue: GithubIssue = match token {
+            Some(token) => {
+                api_get(&format!("{API_BASE}/repos/{repo}/issues/{number}"), token).await?
+            }
+            None => {
+                let number = number.to_string();
+                gh_json(&[
+                    "issue",
+                    "view",
+                    &number,
+                    "--repo",
+                    repo,
+                    "--json",
+                    "number,title,body,labels,url",
+                ])
+                .await?
+            }
+        };
+        issues.push(to_external(repo, issue));
+    }
+    Ok(issues)
+}
+
+/// State of the pull request for `branch` in the project's repository
+/// (`OPEN`, `CLOSED` or `MERGED`), via `gh`.
+pub async fn pull_request_state(project_dir: &str, branch: &str) -> Result<String, String> {
+    if branch.is_empty() || branch.starts_with('-') {
+        return Err(format!("Invalid branch name: {branch}"));
+    }
+    #[derive(Deserialize)]
+    struct PrState {
+        state: String,
+    }
+    let output = Command::new("gh")
+        .args(["pr", "view", branch, "--json", "state"])
+        .current_dir(project_dir)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "gh failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let pr: PrState =
+        serde_json::from_slice(&output.stdout).map_err(|e| format!("Invalid gh output: {e}"))?;
+    Ok(pr.state)
+}
+
+fn to_external(repo: &str, issue: GithubIssue) -> ExternalIssue {
+    let labels: Vec<String> = issue.labels.into_iter().map(|l| l.name).collect();
+    ExternalIssue {
+        external_id: format!("github:{}#{}", repo, issue.number),
+        url: issue.url,
+        title: issue.title,
+        body: issue.body.unwrap_or_default(),
+        priority: priority_from_labels(&labels),
+        labels,
+        status: None,
+    }
+}
+
+/// Only `owner/name` with GitHub's allowed characters; keeps the value safe
+/// to interpolate into API paths and CLI args.
+fn validate_repo(repo: &str) -> Result<(), String> {
+    let valid_part = |p: &str| {
+        !p.is_empty()
+            && p.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    match repo.split_once('/') {
+        Some((owner, name)) if valid_part(owner) && valid_part(name) => Ok(()),
+        _ => Err(format!(
+            "Invalid GitHub repository (expected owner/name): {repo}"
+        )),
+    }
+}
+
+async fn api_get<T: serde::de::DeserializeOwned>(url: &str, token: &str) -> Result<T, String> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .get(url)
+        .bearer_auth(token)
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "clautron")
+        .send()
+        .await
+        .map_err(|e| format!("GitHub request failed: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("GitHub API returned {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid GitHub response: {e}"))
+}
+
+/// Run `gh` with an args array (no shell) and parse its JSON output.
+async fn gh_json<T: serde::de::DeserializeOwned>(args: &[&str]) -> Result<T, String> {
+    let output =
+        Command::new("gh").args(args).output().await.map_err(|e| {
+            format!("Failed to run gh (install it or configure a GitHub token): {e}")
+        })?;
+    if !output.status.success() {
+        return Err(format!(
+            "gh failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| format!("Invalid gh output: {e}"))
+}
@@ -0,0 +1,239 @@
+use crate::domain::models::{ExternalIssue, SpecPriority, SpecStatus};
+use crate::services::config_store::JiraConfig;
+use serde::Deserialize;
+use std::time::Duration;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Most issues Jira returns per page.
+const PAGE_SIZE: u32 = 100;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResponse {
+    #[serde(default)]
+    issues: Vec<JiraIssue>,
+    /// Cursor of the next page (v3 `search/jql`); None on the last page.
+    #[serde(default)]
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssue {
+    key: String,
+    fields: JiraFields,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraFields {
+    summary: String,
+    /// Plain text / wiki markup in REST API v2, an Atlassian Document
+    /// Format tree in v3.
+    #[serde(default)]
+    description: Option<serde_json::Value>,
+    #[serde(default)]
+    priority: Option<Named>,
+    #[serde(default)]
+    status: Option<JiraStatus>,
+    #[serde(default)]
+    labels: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Named {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraStatus {
+    #[serde(default)]
+    status_category: Option<StatusCategory>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusCategory {
+    key: String,
+}
+
+/// Run a JQL search and normalize the matching tickets for import.
+pub async fn search_issues(
+    config: &JiraConfig,
+    jql: &str,
+    limit: u32,
+) -> Result<Vec<ExternalIssue>, String> {
+    let base_url = config.base_url.trim_end_matches('/');
+    if !base_url.starts_with("https://") {
+        return Err("Jira base URL must use https://".into());
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    // Jira Cloud uses email + API token and only has the v3 `search/jql`
+    // endpoint; Server/Data Center uses a personal access token and v2.
+    let email = config.email.as_deref().filter(|e| !e.is_empty());
+    let url = match email {
+        Some(_) => format!("{base_url}/rest/api/3/search/jql"),
+        None => format!("{base_url}/rest/api/2/search"),
+    };
+
+    let limit = limit.max(1);
+    let mut issues: Vec<JiraIssue> = Vec::new();
+    let mut next_page_token: Option<String> = None;
+    loop {
+        let page_size = (limit - issues.len() as u32).min(PAGE_SIZE).to_string();
+        let mut query = vec![
+            ("jql", jql),
+            ("maxResults", page_size.as_str()),
+            ("fields", "summary,description,priority,status,labels"),
+        ];
+        if let Some(token) = next_page_token.as_deref() {
+            query.push(("nextPageToken", token));
+        }
+        let request = client
+            .get(&url)
+            .query(&query)
+            .header("Accept", "application/json");
+        let request = match email {
+            Some(email) => request.basic_auth(email, Some(&config.api_token)),
+            None => request.bearer_auth(&config.api_token),
+        };
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Jira request failed: {e}"))?;
+        if !response.status().is_success() {
+            return Err(format!("Jira API returned {}", response.status()));
+        }
+        let page: SearchResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Invalid Jira response: {e}"))?;
+        let empty = page.issues.is_empty();
+        issues.extend(page.issues);
+        // v2 has no cursor, so it stops after the first page
+        next_page_token = page.next_page_token;
+        if empty || next_page_token.is_none() || issues.len() as u32 >= limit {
+            break;
+        }
+    }
+    issues.truncate(limit as usize);
+
+    Ok(issues
+        .into_iter()
+        .map(|issue| ExternalIssue {
+            external_id: format!("jira:{}", issue.key),
+            url: Some(format!("{}/browse/{}", base_url, issue.key)),
+            title: issue.fields.summary,
+            body: issue
+                .fields
+                .description
+                .map(|d| description_text(&d))
+                .unwrap_or_default(),
+            priority: map_priority(issue.fields.priority.as_ref().map(|p| p.name.as_str())),
+            status: issue
+                .fields
+                .status
+                .and_then(|s| s.status_category)
+                .map(|c| map_status_category(&c.key)),
+            labels: issue.fields.labels,
+        })
+        .collect())
+}
+
+/// A v2 description as is, a v3 one converted from ADF to plain text.
+fn description_text(description: &serde_json::Value) -> String {
+    match description {
+        serde_json::Value::String(text) => text.clone(),
+        node => {
+            let mut out = String::new();
+            adf_text(node, &mut out);
+            out.trim_end().to_string()
+        }
+    }
+}
+
+/// Append the text of an ADF node: blocks end with a newline, list items
+/// start with `- `, and mentions, links and emoji keep their visible text.
+fn adf_text(node: &serde_json::Value, out: &mut String) {
+    let attr = |name: &str| node["attrs"][name].as_str().unwrap_or_default().to_string();
+    match node["type"].as_str().unwrap_or_default() {
+        "text" => out.push_str(node["text"].as_str().unwrap_or_default()),
+        "hardBreak" => out.push('\n'),
+        "mention" => out.push_str(&attr("text")),
+        "emoji" => out.push_str(&attr("shortName")),
+        "inlineCard" | "blockCard" => out.push_str(&attr("url")),
+        "rule" => out.push_str("---\n"),
+        kind => {
+            if kind == "listItem" {
+                out.push_str("- ");
+            }
+            if kind == "codeBlock" {
+                out.push_str("```\n");
+            }
+            for child in node["content"].as_array().into_iter().flatten() {
+                adf_text(child, out);
+            }
+            match kind {
+                "codeBlock" => out.push_str("\n```\n"),
+                "paragraph" | "heading" | "blockquote" => out.push('\n'),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Jira's default priority scheme: Highest/Blocker/Critical, High, Medium, Low, Lowest.
+fn map_priority(name: Option<&str>) -> SpecPriority {
+    match name.map(|n| n.to_lowercase()).as_deref() {
+        Some("highest" | "blocker" | "critical") => SpecPriority::P0,
+        Some("low" | "lowest" | "minor" | "trivial") => SpecPriority::P2,
+        _ => SpecPriority::P1,
+    }
+}
+
+/// Status categories are stable across custom Jira workflows, unlike status names.
+fn map_status_category(key: &str) -> SpecStatus {
+    match key {
+        "indeterminate" => SpecStatus::InProgress,
+        "done" => SpecStatus::Done,
+        _ => SpecStatus::Draft,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adf_descriptions_become_plain_text() {
+        let adf = serde_json::json!({
+            "type": "doc",
+            "version": 1,
+            "content": [
+                {"type": "paragraph", "content": [
+                    {"type": "text", "text": "Ask "},
+                    {"type": "mention", "attrs": {"id": "1", "text": "@Ana"}},
+                    {"type": "text", "text": " first."}
+                ]},
+                {"type": "bulletList", "content": [
+                    {"type": "listItem", "content": [
+                        {"type": "paragraph", "content": [{"type": "text", "text": "one"}]}
+                    ]},
+                    {"type": "listItem", "content": [
+                        {"type": "paragraph", "content": [{"type": "text", "text": "two"}]}
+                    ]}
+                ]},
+                {"type": "codeBlock", "content": [{"type": "text", "text": "cargo test"}]}
+            ]
+        });
+        assert_eq!(
+            description_text(&adf),
+            "Ask @Ana first.\n- one\n- two\n```\ncargo test\n```"
+        );
+        assert_eq!(description_text(&serde_json::json!("h1. Wiki")), "h1. Wiki");
+    }
+}
@@ -0,0 +1,494 @@
+//! Linear integration: import issues as specs and keep their status in sync.
+//!
+//! Specs are linked to Linear through `external_id: linear:ENG-123`. A
+//! background task compares each linked spec against its Linear issue every
+//! few minutes. Local transitions to in_progress / review / done are pushed to
+//! Linear, remote transitions are pulled into the spec, and when both sides
+//! changed since the last pass to different states a conflict is reported and
+//! neither side is overwritten.
+
+use crate::domain::models::{ExternalIssue, Spec, SpecPriority, SpecStatus, SpecUpdate};
+use crate::services::config_store::AppConfig;
+use crate::services::spec_manager::SpecManager;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{async_runtime, AppHandle, Emitter};
+use tokio::sync::{Mutex, RwLock};
+
+const API_URL: &str = "https://api.linear.app/graphql";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const SYNC_INTERVAL: Duration = Duration::from_secs(120);
+const ID_PREFIX: &str = "linear:";
+
+/// Both sides changed since the last sync and disagree.
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncConflict {
+    pub file_path: String,
+    pub external_id: String,
+    pub local_status: SpecStatus,
+    /// Name of the Linear workflow state, e.g. "Canceled".
+    pub remote_state: String,
+    pub detected_at: String,
+}
+
+/// Outcome of one sync pass. Paths are spec file paths.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncReport {
+    pub pushed: Vec<String>,
+    pub pulled: Vec<String>,
+    pub conflicts: Vec<SyncConflict>,
+    pub errors: Vec<String>,
+}
+
+impl SyncReport {
+    fn is_empty(&self) -> bool {
+        self.pushed.is_empty()
+            && self.pulled.is_empty()
+            && self.conflicts.is_empty()
+            && self.errors.is_empty()
+    }
+}
+
+/// What both sides looked like at the end of the last pass.
+struct SyncRecord {
+    local: SpecStatus,
+    remote_state_id: String,
+}
+
+#[derive(Default)]
+struct SyncState {
+    records: HashMap<String, SyncRecord>,
+    /// Open conflicts keyed by external_id; cleared once both sides agree.
+    conflicts: HashMap<String, SyncConflict>,
+}
+
+/// Sync bookkeeping lives in memory: after a restart the first pass only
+/// records a baseline.
+#[derive(Default)]
+pub struct LinearSync {
+    state: Mutex<SyncState>,
+}
+
+impl LinearSync {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Conflicts that haven't been resolved yet.
+    pub async fn conflicts(&self) -> Vec<SyncConflict> {
+        let mut conflicts: Vec<SyncConflict> = self
+            .state
+            .lock()
+            .await
+            .conflicts
+            .values()
+            .cloned()
+            .collect();
+        conflicts.sort_by(|a, b| a.external_id.cmp(&b.external_id));
+        conflicts
+    }
+
+    /// Run one sync pass over every spec linked to Linear. Passes are
+    /// serialized; a failure on one issue doesn't stop the others.
+    pub async fn sync(&self, api_key: &str, specs: &SpecManager) -> Result<SyncReport, String> {
+        let mut state = self.state.lock().await;
+        let mut report = SyncReport::default();
+
+        let linked: Vec<Spec> = specs
+            .list_specs()
+            .await?
+            .into_iter()
+            .filter(|s| {
+                s.external_id
+                    .as_deref()
+                    .is_some_and(|id| id.starts_with(ID_PREFIX))
+            })
+            .collect();
+
+        for spec in linked {
+            let external_id = spec.external_id.clone().unwrap_or_default();
+            if let Err(e) = self
+                .sync_spec(api_key, specs, &spec, &external_id, &mut state, &mut report)
+                .await
+            {
+                report.errors.push(format!("{external_id}: {e}"));
+            }
+        }
+        Ok(report)
+    }
+
+    async fn sync_spec(
+        &self,
+        api_key: &str,
+        specs: &SpecManager,
+        spec: &Spec,
+        external_id: &str,
+        state: &mut SyncState,
+        report: &mut SyncReport,
+    ) -> Result<(), String> {
+        let identifier = &external_id[ID_PREFIX.len()..];
+        let remote = fetch_issue_state(api_key, identifier).await?;
+        let remote_status = status_from_state(&remote.state);
+
+        let Some(record) = state.records.get(external_id) else {
+            state.records.insert(
+                external_id.to_string(),
+                SyncRecord {
+                    local: spec.status.clone(),
+                    remote_state_id: remote.state.id,
+                },
+            );
+            return Ok(());
+        };
+
+        let local_changed = spec.status != record.local;
+        let remote_changed = remote.state.id != record.remote_state_id;
+        let mut local = spec.status.clone();
+        let mut remote_state_id = remote.state.id.clone();
+
+        if remote_status == spec.status {
+            state.conflicts.remove(external_id);
+        } else if local_changed && remote_changed {
+            let conflict = SyncConflict {
+                file_path: spec.file_path.clone(),
+                external_id: external_id.to_string(),
+                local_status: spec.status.clone(),
+                remote_state: remote.state.name.clone(),
+                detected_at: chrono::Utc::now().to_rfc3339(),
+            };
+            report.conflicts.push(conflict.clone());
+            state.conflicts.insert(external_id.to_string(), conflict);
+        } else if local_changed {
+            if let Some(target) = target_state(&remote.team_states, &spec.status) {
+                update_issue_state(api_key, &remote.id, &target.id).await?;
+                remote_state_id = target.id.clone();
+                report.pushed.push(spec.file_path.clone());
+            }
+        } else if remote_changed {
+            let updated = specs
+                .update_spec(
+                    &spec.file_path,
+                    SpecUpdate {
+                        status: Some(remote_status.clone()),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+            specs.rollup_parents(&updated).await;
+            local = remote_status;
+            report.pulled.push(spec.file_path.clone());
+        }
+
+        state.records.insert(
+            external_id.to_string(),
+            SyncRecord {
+                local,
+                remote_state_id,
+            },
+        );
+        Ok(())
+    }
+}
+
+/// Sync every `SYNC_INTERVAL` while a Linear API key is configured and emit
+/// `linear:sync` with the report whenever something happened.
+pub fn start_sync_task(
+    app: AppHandle,
+    sync: Arc<LinearSync>,
+    config: Arc<RwLock<AppConfig>>,
+    specs: Arc<SpecManager>,
+) {
+    async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SYNC_INTERVAL).await;
+            let api_key = config.read().await.integrations.linear_api_key.clone();
+            let Some(api_key) = api_key else {
+                continue;
+            };
+            match sync.sync(&api_key, &specs).await {
+                Ok(report) if !report.is_empty() => {
+                    let _ = app.emit("linear:sync", report);
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Linear sync failed: {e}"),
+            }
+        }
+    });
+}
+
+// ---------------------------------------------------------------------------
+// GraphQL API
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Deserialize)]
+struct WorkflowState {
+    id: String,
+    name: String,
+    /// triage, backlog, unstarted, started, completed or canceled
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    position: f64,
+}
+
+struct RemoteIssue {
+    id: String,
+    state: WorkflowState,
+    team_states: Vec<WorkflowState>,
+}
+
+#[derive(Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct Named {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct IssueNode {
+    id: String,
+    identifier: String,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    url: String,
+    /// 0 = none, 1 = urgent, 2 = high, 3 = medium, 4 = low
+    #[serde(default)]
+    priority: u8,
+    labels: Nodes<Named>,
+    state: WorkflowState,
+    #[serde(default)]
+    team: Option<TeamNode>,
+}
+
+#[derive(Deserialize)]
+struct TeamNode {
+    states: Nodes<WorkflowState>,
+}
+
+/// List issues that aren't completed or canceled, optionally for one team key.
+pub async fn list_issues(
+    api_key: &str,
+    team_key: Option<&str>,
+    limit: u32,
+) -> Result<Vec<ExternalIssue>, String> {
+    #[derive(Deserialize)]
+    struct Data {
+        issues: Nodes<IssueNode>,
+    }
+
+    let mut filter = json!({ "state": { "type": { "nin": ["completed", "canceled"] } } });
+    if let Some(team_key) = team_key.filter(|k| !k.is_empty()) {
+        filter["team"] = json!({ "key": { "eq": team_key } });
+    }
+    let data: Data = graphql(
+        api_key,
+        "query($first: Int!, $filter: IssueFilter) {
+            issues(first: $first, filter: $filter) {
+                nodes { id identifier title description url priority
+                        labels { nodes { name } } state { id name type position } }
+            }
+        }",
+        json!({ "first": limit.clamp(1, 100), "filter": filter }),
+    )
+    .await?;
+
+    Ok(data
+        .issues
+        .nodes
+        .into_iter()
+        .map(|issue| ExternalIssue {
+            external_id: format!("{ID_PREFIX}{}", issue.identifier),
+            url: Some(issue.url),
+            title: issue.title,
+            body: issue.description.unwrap_or_default(),
+            labels: issue.labels.nodes.into_iter().map(|l| l.name).collect(),
+            priority: map_priority(issue.priority),
+            status: Some(status_from_state(&issue.state)),
+        })
+        .collect())
+}
+
+async fn fetch_issue_state(api_key: &str, identifier: &str) -> Result<RemoteIssue, String> {
+    #[derive(Deserialize)]
+    struct Data {
+        issue: IssueNode,
+    }
+
+    let data: Data = graphql(
+        api_key,
+        "query($id: String!) {
+            issue(id: $id) {
+                id identifier title url priority labels { nodes { name } }
+                state { id name type position }
+                team { states { nodes { id name type position } } }
+            }
+        }",
+        json!({ "id": identifier }),
+    )
+    .await?;
+
+    Ok(RemoteIssue {
+        id: data.issue.id,
+        state: data.issue.state,
+        team_states: data.issue.team.map(|t| t.states.nodes).unwrap_or_default(),
+    })
+}
+
+async fn update_issue_state(api_key: &str, issue_id: &str, state_id: &str) -> Result<(), String> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Data {
+        issue_update: Success,
+    }
+    #[derive(Deserialize)]
+    struct Success {
+        success: bool,
+    }
+
+    let data: Data = graphql(
+        api_key,
+        "mutation($id: String!, $stateId: String!) {
+            issueUpdate(id: $id, input: { stateId: $stateId }) { success }
+        }",
+        json!({ "id": issue_id, "stateId": state_id }),
+    )
+    .await?;
+    if data.issue_update.success {
+        Ok(())
+    } else {
+        Err("Linear rejected the status update".into())
+    }
+}
+
+async fn graphql<T: serde::de::DeserializeOwned>(
+    api_key: &str,
+    query: &str,
+    variables: serde_json::Value,
+) -> Result<T, String> {
+    #[derive(Deserialize)]
+    struct Response<T> {
+        data: Option<T>,
+        #[serde(default)]
+        errors: Vec<GraphqlError>,
+    }
+    #[derive(Deserialize)]
+    struct GraphqlError {
+        message: String,
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    // Personal API keys are sent as-is, without a Bearer prefix
+    let response = client
+        .post(API_URL)
+        .header("Authorization", api_key)
+        .json(&json!({ "query": query, "variables": variables }))
+        .send()
+        .await
+        .map_err(|e| format!("Linear request failed: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Linear API returned {}", response.status()));
+    }
+    let body: Response<T> = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid Linear response: {e}"))?;
+    if let Some(error) = body.errors.first() {
+        return Err(format!("Linear API error: {}", error.message));
+    }
+    body.data
+        .ok_or_else(|| "Linear response contained no data".to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Status mapping
+// ---------------------------------------------------------------------------
+
+fn map_priority(priority: u8) -> SpecPriority {
+    match priority {
+        1 | 2 => SpecPriority::P0,
+        4 => SpecPriority::P2,
+        _ => SpecPriority::P1,
+    }
+}
+
+/// Linear has no dedicated review type; a started state named like
+/// "In Review" counts as review.
+fn is_review_state(state: &WorkflowState) -> bool {
+    state.kind == "started" && state.name.to_lowercase().contains("review")
+}
+
+fn status_from_state(state: &WorkflowState) -> SpecStatus {
+    match state.kind.as_str() {
+        "started" if is_review_state(state) => SpecStatus::Review,
+        "started" => SpecStatus::InProgress,
+        "completed" => SpecStatus::Done,
+        "canceled" => SpecStatus::Rejected,
+        _ => SpecStatus::Draft,
+    }
+}
+
+/// The team state a local status should be pushed as, or `None` for
+/// statuses that stay local (draft, assigned, rejected). Review falls back to
+/// the first started state when the team has no review state.
+fn target_state<'a>(states: &'a [WorkflowState], status: &SpecStatus) -> Option<&'a WorkflowState> {
+    let first = |pred: &dyn Fn(&WorkflowState) -> bool| {
+        states
+            .iter()
+            .filter(|s| pred(s))
+            .min_by(|a, b| a.position.total_cmp(&b.position))
+    };
+    let in_progress = || first(&|s| s.kind == "started" && !is_review_state(s));
+    match status {
+        SpecStatus::InProgress => in_progress(),
+        SpecStatus::Review => first(&is_review_state).or_else(in_progress),
+        SpecStatus::Done => first(&|s| s.kind == "completed"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(id: &str, name: &str, kind: &str, position: f64) -> WorkflowState {
+        WorkflowState {
+            id: id.into(),
+            name: name.into(),
+            kind: kind.into(),
+            position,
+        }
+    }
+
+    #[test]
+    fn maps_statuses_to_team_states() {
+        let states = vec![
+            state("todo", "Todo", "unstarted", 0.0),
+            state("review", "In Review", "started", 2.0),
+            state("wip", "In Progress", "started", 1.0),
+            state("done", "Done", "completed", 3.0),
+            state("dup", "Duplicate", "canceled", 4.0),
+        ];
+        let target = |s| target_state(&states, &s).map(|s| s.id.as_str());
+        assert_eq!(target(SpecStatus::InProgress), Some("wip"));
+        assert_eq!(target(SpecStatus::Review), Some("review"));
+        assert_eq!(target(SpecStatus::Done), Some("done"));
+        assert_eq!(target(SpecStatus::Draft), None);
+
+        assert_eq!(
+            target_state(&states[2..3], &SpecStatus::Review).unwrap().id,
+            "wip"
+        );
+        assert_eq!(status_from_state(&states[1]), SpecStatus::Review);
+        assert_eq!(status_from_state(&states[4]), SpecStatus::Rejected);
+    }
+}
@@ -0,0 +1,466 @@
+use crate::domain::models::{AgentSession, LogEntry};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogExportFormat {
+    Markdown,
+    Html,
+    Jsonl,
+}
+
+/// Tool output beyond this many characters is cut in rendered documents.
+const MAX_TOOL_OUTPUT: usize = 4000;
+
+/// One readable step of a transcript.
+enum Item {
+    Text(String),
+    ToolCall {
+        id: Option<String>,
+        name: String,
+        input: String,
+    },
+    ToolResult {
+        id: Option<String>,
+        text: String,
+        is_error: bool,
+    },
+    Result {
+        text: String,
+        is_error: bool,
+    },
+    Stderr(String),
+}
+
+/// Render a session's stored log entries as a document. Markdown and HTML
+/// show assistant text inline with each tool call (and its result) folded
+/// into a `<details>` block; JSONL is the raw stream, one message per line.
+pub fn export_logs(
+    entries: &[LogEntry],
+    session: Option<&AgentSession>,
+    format: LogExportFormat,
+) -> String {
+    match format {
+        LogExportFormat::Jsonl => export_jsonl(entries),
+        LogExportFormat::Markdown => export_markdown(&transcript(entries), session),
+        LogExportFormat::Html => export_html(&transcript(entries), session),
+    }
+}
+
+fn export_jsonl(entries: &[LogEntry]) -> String {
+    let mut out = String::new();
+    for entry in entries {
+        out.push_str(&jsonl_line(
+            &entry.message_type,
+            &entry.content,
+            &entry.timestamp,
+        ));
+        out.push('\n');
+    }
+    out
+}
+
+/// One entry as a line of the raw stream.
+pub fn jsonl_line(message_type: &str, content: &str, timestamp: &str) -> String {
+    match serde_json::from_str::<Value>(content) {
+        Ok(value) => value.to_string(),
+        // stderr lines are stored as plain text
+        Err(_) => serde_json::json!({
+            "type": message_type,
+            "text": content,
+            "timestamp": timestamp,
+        })
+        .to_string(),
+    }
+}
+
+/// Stream-json assistant and user messages are cumulative snapshots, so
+/// only the last of each consecutive run of one type is kept (as
+/// `deduplicateStreamLogs` does in the frontend).
+fn transcript(entries: &[LogEntry]) -> Vec<Item> {
+    let mut items = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let next_type = entries.get(i + 1).map(|e| e.message_type.as_str());
+        if entry.message_type != "stderr" && next_type == Some(entry.message_type.as_str()) {
+            continue;
+        }
+        if entry.message_type == "stderr" {
+            items.push(Item::Stderr(entry.content.clone()));
+            continue;
+        }
+        let Ok(parsed) = serde_json::from_str::<Value>(&entry.content) else {
+            continue;
+        };
+        let blocks = parsed
+            .pointer("/message/content")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        match parsed.get("type").and_then(Value::as_str) {
+            Some("assistant") => {
+                for block in blocks {
+                    match block.get("type").and_then(Value::as_str) {
+                        Some("text") => {
+                            let text = block.get("text").and_then(Value::as_str).unwrap_or("");
+                            if !text.trim().is_empty() {
+                                items.push(Item::Text(text.to_string()));
+                            }
+                        }
+                        Some("tool_use") => items.push(Item::ToolCall {
+                            id: block.get("id").and_then(Value::as_str).map(String::from),
+                            name: block
+                                .get("name")
+                                .and_then(Value::as_str)
+                                .unwrap_or("unknown")
+                                .to_string(),
+                            input: block
+                                .get("input")
+                                .map(|v| serde_json::to_string_pretty(v).unwrap_or_default())
+                                .unwrap_or_default(),
+                        }),
+                        _ => {}
+                    }
+                }
+            }
+            Some("user") => {
+                for block in blocks {
+                    if block.get("type").and_then(Value::as_str) != Some("tool_result") {
+                        continue;
+                    }
+                    let text = match block.get("content") {
+                        Some(Value::String(s)) => s.clone(),
+                        Some(Value::Array(parts)) => parts
+                            .iter()
+                            .filter_map(|p| p.get("text").and_then(Value::as_str))
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                        Some(other) => other.to_string(),
+                        None => String::new(),
+                    };
+                    items.push(Item::ToolResult {
+                        id: block
+                            .get("tool_use_id")
+                            .and_then(Value::as_str)
+                            .map(String::from),
+                        text: truncate(&text),
+                        is_error: block.get("is_error").and_then(Value::as_bool) == Some(true),
+                    });
+                }
+            }
+            Some("result") => {
+                let is_error = parsed.get("subtype").and_then(Value::as_str) == Some("error")
+                    || parsed.get("is_error").and_then(Value::as_bool) == Some(true);
+                let text = parsed
+                    .get("result")
+                    .and_then(Value::as_str)
+                    .unwrap_or(if is_error {
+                        "Agent encountered an error"
+                    } else {
+                        ""
+                    });
+                if !text.is_empty() {
+                    items.push(Item::Result {
+                        text: text.to_string(),
+                        is_error,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    items
+}
+
+/// Results keyed by the call they answer, so each can be folded into its call.
+fn results_by_call(items: &[Item]) -> HashMap<&str, (&str, bool)> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Item::ToolResult {
+                id: Some(id),
+                text,
+                is_error,
+            } => Some((id.as_str(), (text.as_str(), *is_error))),
+            _ => None,
+        })
+        .collect()
+}
+
+fn call_ids(items: &[Item]) -> Vec<&str> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Item::ToolCall { id: Some(id), .. } => Some(id.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn header_fields(session: Option<&AgentSession>) -> Vec<(&'static str, String)> {
+    let Some(s) = session else {
+        return Vec::new();
+    };
+    let mut fields = vec![
+        ("Session", s.id.clone()),
+        ("Model", s.model.clone()),
+        ("Status", s.status.to_string()),
+        ("Started", s.started_at.clone()),
+    ];
+    if let Some(ended) = &s.ended_at {
+        fields.push(("Ended", ended.clone()));
+    }
+    fields.push((
+        "Tokens",
+        format!("{} in / {} out", s.input_tokens, s.output_tokens),
+    ));
+    if s.cost_usd > 0.0 {
+        fields.push(("Cost", format!("${:.4}", s.cost_usd)));
+    }
+    fields
+}
+
+fn export_markdown(items: &[Item], session: Option<&AgentSession>) -> String {
+    let results = results_by_call(items);
+    let calls = call_ids(items);
+    let mut out = String::new();
+    let title = session.map_or("Agent transcript".to_string(), |s| {
+        format!("Agent transcript: {}", s.agent_name)
+    });
+    let _ = writeln!(out, "# {title}\n");
+    for (label, value) in header_fields(session) {
+        let _ = writeln!(out, "- **{label}:** {value}");
+    }
+    if let Some(s) = session {
+        let _ = writeln!(out, "\n## Prompt\n\n{}", fenced(&s.prompt, ""));
+    }
+    let _ = writeln!(out, "\n## Transcript\n");
+
+    for item in items {
+        match item {
+            Item::Text(text) => {
+                let _ = writeln!(out, "{}\n", text.trim_end());
+            }
+            Item::ToolCall { id, name, input } => {
+                let _ = writeln!(out, "<details>\n<summary>Tool: {name}</summary>\n");
+                if !input.is_empty() {
+                    let _ = writeln!(out, "{}", fenced(input, "json"));
+                }
+                if let Some((text, is_error)) = id.as_deref().and_then(|id| results.get(id)) {
+                    let label = if *is_error { "Error" } else { "Result" };
+                    let _ = writeln!(out, "**{label}:**\n\n{}", fenced(text, ""));
+                }
+                let _ = writeln!(out, "</details>\n");
+            }
+            Item::ToolResult { id, text, is_error } => {
+                if id.as_deref().is_some_and(|id| calls.contains(&id)) {
+                    continue;
+                }
+                let label = if *is_error {
+                    "Tool error"
+                } else {
+                    "Tool result"
+                };
+                let _ = writeln!(
+                    out,
+                    "<details>\n<summary>{label}</summary>\n\n{}</details>\n",
+                    fenced(text, "")
+                );
+            }
+            Item::Result { text, is_error } => {
+                let heading = if *is_error { "Error" } else { "Result" };
+                let _ = writeln!(out, "## {heading}\n\n{}\n", text.trim_end());
+            }
+            Item::Stderr(text) => {
+                let _ = writeln!(out, "> stderr: `{}`\n", text.replace('`', "'"));
+            }
+        }
+    }
+    out
+}
+
+fn export_html(items: &[Item], session: Option<&AgentSession>) -> String {
+    let results = results_by_call(items);
+    let calls = call_ids(items);
+    let title = session.map_or("Agent transcript".to_string(), |s| {
+        format!("Agent transcript: {}", s.agent_name)
+    });
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n\
+         body {{ font-family: -apple-system, system-ui, sans-serif; max-width: 860px; margin: 2rem auto; padding: 0 1rem; line-height: 1.5; color: #222; }}\n\
+         pre {{ background: #f5f5f5; padding: .75rem; overflow-x: auto; white-space: pre-wrap; }}\n\
+         details {{ border: 1px solid #ddd; border-radius: 4px; padding: .25rem .75rem; margin: .5rem 0; }}\n\
+         summary {{ cursor: pointer; font-family: monospace; }}\n\
+         .error {{ color: #b00020; }}\n\
+         .stderr {{ color: #888; font-family: monospace; font-size: .85em; }}\n\
+         </style>\n</head>\n<body>\n<h1>{}</h1>",
+        escape_html(&title),
+        escape_html(&title)
+    );
+    let fields = header_fields(session);
+    if !fields.is_empty() {
+        out.push_str("<ul>\n");
+        for (label, value) in fields {
+            let _ = writeln!(
+                out,
+                "<li><strong>{label}:</strong> {}</li>",
+                escape_html(&value)
+            );
+        }
+        out.push_str("</ul>\n");
+    }
+    if let Some(s) = session {
+        let _ = writeln!(
+            out,
+            "<h2>Prompt</h2>\n<pre>{}</pre>",
+            escape_html(&s.prompt)
+        );
+    }
+    out.push_str("<h2>Transcript</h2>\n");
+
+    for item in items {
+        match item {
+            Item::Text(text) => {
+                let _ = writeln!(out, "<pre>{}</pre>", escape_html(text.trim_end()));
+            }
+            Item::ToolCall { id, name, input } => {
+                let _ = writeln!(
+                    out,
+                    "<details>\n<summary>Tool: {}</summary>",
+                    escape_html(name)
+                );
+                if !input.is_empty() {
+                    let _ = writeln!(out, "<pre>{}</pre>", escape_html(input));
+                }
+                if let Some((text, is_error)) = id.as_deref().and_then(|id| results.get(id)) {
+                    let class = if *is_error { " class=\"error\"" } else { "" };
+                    let _ = writeln!(out, "<pre{class}>{}</pre>", escape_html(text));
+                }
+                out.push_str("</details>\n");
+            }
+            Item::ToolResult { id, text, is_error } => {
+                if id.as_deref().is_some_and(|id| calls.contains(&id)) {
+                    continue;
+                }
+                let (label, class) = if *is_error {
+                    ("Tool error", " class=\"error\"")
+                } else {
+                    ("Tool result", "")
+                };
+                let _ = writeln!(
+                    out,
+                    "<details>\n<summary>{label}</summary>\n<pre{class}>{}</pre>\n</details>",
+                    escape_html(text)
+                );
+            }
+            Item::Result { text, is_error } => {
+                let (heading, class) = if *is_error {
+                    ("Error", " class=\"error\"")
+                } else {
+                    ("Result", "")
+                };
+                let _ = writeln!(
+                    out,
+                    "<h2{class}>{heading}</h2>\n<pre>{}</pre>",
+                    escape_html(text.trim_end())
+                );
+            }
+            Item::Stderr(text) => {
+                let _ = writeln!(out, "<div class=\"stderr\">{}</div>", escape_html(text));
+            }
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// A fenced code block whose fence outlasts any backtick run in `text`.
+fn fenced(text: &str, lang: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in text.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!("{fence}{lang}\n{}\n{fence}\n", text.trim_end())
+}
+
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(MAX_TOOL_OUTPUT) {
+        Some((idx, _)) => format!("{}\n… (truncated)", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(message_type: &str, content: &str) -> LogEntry {
+        LogEntry {
+            id: 0,
+            session_id: "s1".into(),
+            message_type: message_type.into(),
+            content: content.into(),
+            timestamp: "2026-01-01T00:00:00Z".into(),
+            input_tokens: None,
+            output_tokens: None,
+        }
+    }
+
+    #[test]
+    fn export_folds_tool_calls_and_keeps_last_cumulative_snapshot() {
+        let entries = vec![
+            entry("system", r#"{"type":"system","subtype":"init"}"#),
+            entry(
+                "assistant",
+                r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Draft"}]}}"#,
+            ),
+            entry(
+                "assistant",
+                r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Listing <files>"},{"type":"tool_use","id":"t1","name":"Bash","input":{"command":"ls"}}]}}"#,
+            ),
+            entry(
+                "user",
+                r#"{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"t1","content":"a.rs\nb.rs"}]}}"#,
+            ),
+            entry("stderr", "warning: slow"),
+            entry(
+                "result",
+                r#"{"type":"result","subtype":"success","result":"Done"}"#,
+            ),
+        ];
+
+        let md = export_logs(&entries, None, LogExportFormat::Markdown);
+        assert!(!md.contains("Draft"));
+        assert!(md.contains("Listing <files>\n"));
+        assert!(md.contains("<summary>Tool: Bash</summary>"));
+        assert!(md.contains("\"command\": \"ls\""));
+        assert_eq!(md.matches("a.rs\nb.rs").count(), 1);
+        assert!(md.contains("## Result\n\nDone"));
+
+        let html = export_logs(&entries, None, LogExportFormat::Html);
+        assert!(html.contains("Listing &lt;files&gt;"));
+        assert!(html.contains("<div class=\"stderr\">warning: slow</div>"));
+
+        let jsonl = export_logs(&entries, None, LogExportFormat::Jsonl);
+        let lines: Vec<&str> = jsonl.lines().collect();
+        assert_eq!(lines.len(), entries.len());
+        let stderr: Value = serde_json::from_str(lines[4]).unwrap();
+        assert_eq!(stderr["type"], "stderr");
+
+        assert_eq!(fenced("x ``` y", "").lines().next(), Some("````"));
+    }
+}
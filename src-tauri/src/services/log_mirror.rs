@@ -0,0 +1,104 @@
+use crate::services::log_export;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Tees appended log entries to `<dir>/<session_id>.jsonl`, one stream-json
+/// line per entry, for projects with `mirror_logs` turned on. The files are
+/// append-only, so they outlive a corrupted database.
+pub struct LogMirror {
+    dir: PathBuf,
+    enabled: AtomicBool,
+}
+
+impl LogMirror {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            enabled: AtomicBool::new(false),
+        }
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn path(&self, session_id: &str) -> Result<PathBuf, String> {
+        // Session ids are UUIDs; anything else must not escape the directory
+        if session_id.is_empty()
+            || !session_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!("Invalid session id: {session_id}"));
+        }
+        Ok(self.dir.join(format!("{session_id}.jsonl")))
+    }
+
+    /// Append one entry if mirroring is on. A no-op otherwise.
+    pub fn write(
+        &self,
+        session_id: &str,
+        message_type: &str,
+        content: &str,
+        timestamp: &str,
+    ) -> Result<(), String> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        let path = self.path(session_id)?;
+        if !self.dir.exists() {
+            std::fs::create_dir_all(&self.dir).map_err(|e| e.to_string())?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let _ = std::fs::set_permissions(&self.dir, std::fs::Permissions::from_mode(0o700));
+            }
+        }
+        let mut line = log_export::jsonl_line(message_type, content, timestamp);
+        line.push('\n');
+        // One write per line, so concurrent appends never interleave mid-line
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_one_line_per_entry_only_when_enabled() {
+        let dir = std::env::temp_dir().join(format!("clautron-mirror-{}", uuid::Uuid::new_v4()));
+        let mirror = LogMirror::new(dir.clone());
+
+        mirror
+            .write("s1", "assistant", r#"{"type":"assistant"}"#, "t0")
+            .unwrap();
+        assert!(!dir.exists());
+
+        mirror.set_enabled(true);
+        mirror
+            .write("s1", "assistant", r#"{"type":"assistant"}"#, "t1")
+            .unwrap();
+        mirror.write("s1", "stderr", "warning: slow", "t2").unwrap();
+        assert!(mirror.write("../s1", "stderr", "x", "t3").is_err());
+
+        let content = std::fs::read_to_string(dir.join("s1.jsonl")).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], r#"{"type":"assistant"}"#);
+        let stderr: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(stderr["text"], "warning: slow");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,587 @@
+//! MCP server entries in `.mcp.json` (project) and `~/.claude.json` (user),
+//! plus a health check that starts each server and waits for it to answer
+//! the MCP `initialize` handshake.
+
+use crate::adapters::claude_cli_runner::ENV_ALLOWLIST;
+use crate::domain::models::{McpHealth, McpHealthStatus, McpScope, McpServer};
+use crate::services::agent_watcher;
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How much of a server's stderr is kept for the error message.
+const STDERR_TAIL_BYTES: usize = 4096;
+
+/// `${VAR}` or `${VAR:-default}`, expanded by Claude Code in `.mcp.json`.
+static ENV_REF_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)(?::-([^}]*))?\}").unwrap());
+
+pub fn config_path(scope: McpScope, project_dir: Option<&str>) -> Result<PathBuf, String> {
+    match scope {
+        McpScope::Project => project_dir
+            .map(|dir| Path::new(dir).join(".mcp.json"))
+            .ok_or_else(|| "No project directory set".to_string()),
+        McpScope::User => dirs::home_dir()
+            .map(|h| h.join(".claude.json"))
+            .ok_or_else(|| "No home directory".to_string()),
+    }
+}
+
+/// Servers from both scopes; a missing config file has none.
+pub fn list_servers(project_dir: Option<&str>) -> Result<Vec<McpServer>, String> {
+    let mut servers = Vec::new();
+    for scope in [McpScope::Project, McpScope::User] {
+        let Ok(path) = config_path(scope, project_dir) else {
+            continue;
+        };
+        servers.extend(read_servers(&path, scope)?);
+    }
+    Ok(servers)
+}
+
+pub fn add_server(server: &McpServer, project_dir: Option<&str>) -> Result<(), String> {
+    validate(server)?;
+    let path = config_path(server.scope, project_dir)?;
+    let mut root = read_root(&path)?;
+    let servers = servers_map(&mut root)?;
+    if servers.contains_key(&server.name) {
+        return Err(format!("MCP server already exists: {}", server.name));
+    }
+    servers.insert(server.name.clone(), to_entry(server));
+    write_root(&path, &root, server.scope)
+}
+
+pub fn remove_server(name: &str, scope: McpScope, project_dir: Option<&str>) -> Result<(), String> {
+    let path = config_path(scope, project_dir)?;
+    let mut root = read_root(&path)?;
+    if servers_map(&mut root)?.remove(name).is_none() {
+        return Err(format!("MCP server not found: {name}"));
+    }
+    write_root(&path, &root, scope)
+}
+
+/// What the user has allowed for the open project's `.mcp.json`, which a
+/// cloned repository controls.
+#[derive(Debug, Clone, Default)]
+pub struct CheckPolicy {
+    /// Variables `${VAR}` may read besides `ENV_ALLOWLIST`.
+    pub extra_env: Vec<String>,
+    /// Stdio servers the check may launch, by name, with their `launch_hash`.
+    pub approved_servers: HashMap<String, String>,
+}
+
+impl CheckPolicy {
+    /// Whether `${name}` in `server` may read the app's environment. The
+    /// user's own `~/.claude.json` may read anything.
+    fn may_read(&self, server: &McpServer, name: &str) -> bool {
+        server.scope == McpScope::User
+            || ENV_ALLOWLIST.contains(&name)
+            || self.extra_env.iter().any(|allowed| allowed == name)
+    }
+
+    fn may_launch(&self, server: &McpServer) -> bool {
+        server.scope == McpScope::User
+            || self.approved_servers.get(&server.name) == Some(&launch_hash(server))
+    }
+}
+
+/// Hash of what launching a stdio server runs, so an approval lapses when
+/// the command, its args or its env change.
+pub fn launch_hash(server: &McpServer) -> String {
+    let env: BTreeMap<_, _> = server.env.iter().collect();
+    let launch = json!([server.command, server.args, env]);
+    agent_watcher::hash_bytes(launch.to_string().as_bytes())
+}
+
+/// Check every server concurrently.
+pub async fn check_servers(
+    project_dir: Option<&str>,
+    policy: CheckPolicy,
+) -> Result<Vec<McpHealth>, String> {
+    let work_dir = project_dir
+        .map(String::from)
+        .or_else(|| std::env::var("HOME").ok())
+        .unwrap_or_else(|| ".".to_string());
+    let policy = Arc::new(policy);
+    let handles: Vec<_> = list_servers(project_dir)?
+        .into_iter()
+        .map(|server| {
+            let work_dir = work_dir.clone();
+            let policy = Arc::clone(&policy);
+            tokio::spawn(async move { check_server(&server, &work_dir, &policy).await })
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    for handle in handles {
+        if let Ok(health) = handle.await {
+            results.push(health);
+        }
+    }
+    Ok(results)
+}
+
+pub async fn check_server(server: &McpServer, work_dir: &str, policy: &CheckPolicy) -> McpHealth {
+    let started = Instant::now();
+    if server.transport == "stdio" && !policy.may_launch(server) {
+        return McpHealth {
+            name: server.name.clone(),
+            scope: server.scope,
+            status: McpHealthStatus::Unapproved,
+            message: "Approve this server's command before it's launched".to_string(),
+            latency_ms: 0,
+        };
+    }
+    let expand = |value: &str| expand_env(value, |name| policy.may_read(server, name));
+    let outcome = tokio::time::timeout(HEALTH_TIMEOUT, async {
+        match server.transport.as_str() {
+            "stdio" => check_stdio(server, work_dir, &expand).await,
+            _ => check_remote(server, &expand).await,
+        }
+    })
+    .await;
+    let (status, message) = match outcome {
+        Ok(Ok(message)) => (McpHealthStatus::Ok, message),
+        Ok(Err(message)) => (McpHealthStatus::Error, message),
+        Err(_) => (
+            McpHealthStatus::Timeout,
+            format!("No response after {}s", HEALTH_TIMEOUT.as_secs()),
+        ),
+    };
+    McpHealth {
+        name: server.name.clone(),
+        scope: server.scope,
+        status,
+        message,
+        latency_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+fn initialize_request() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": { "name": "clautron", "version": env!("CARGO_PKG_VERSION") },
+        },
+    })
+}
+
+/// Launch the server (args array, allowlisted env plus its own) and wait for
+/// its reply to `initialize`.
+async fn check_stdio(
+    server: &McpServer,
+    work_dir: &str,
+    expand: &impl Fn(&str) -> String,
+) -> Result<String, String> {
+    let command = server
+        .command
+        .as_deref()
+        .map(expand)
+        .ok_or("No command configured")?;
+    let mut cmd = Command::new(&command);
+    cmd.args(server.args.iter().map(|a| expand(a)))
+        .current_dir(work_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .env_clear();
+    for key in ENV_ALLOWLIST {
+        if let Ok(val) = std::env::var(key) {
+            cmd.env(key, val);
+        }
+    }
+    for (key, value) in &server.env {
+        cmd.env(key, expand(value));
+    }
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to start `{command}`: {e}"))?;
+    let mut stdin = child.stdin.take().ok_or("No stdin")?;
+    let stdout = child.stdout.take().ok_or("No stdout")?;
+    let mut stderr = child.stderr.take().ok_or("No stderr")?;
+    // Drained alongside stdout, or a server chatty on stderr fills the pipe
+    // and stalls before it answers; only the tail is kept
+    let stderr_tail = tokio::spawn(async move {
+        let mut tail = Vec::new();
+        let mut chunk = [0u8; 4096];
+        while let Ok(n) = stderr.read(&mut chunk).await {
+            if n == 0 {
+                break;
+            }
+            tail.extend_from_slice(&chunk[..n]);
+            if tail.len() > STDERR_TAIL_BYTES {
+                tail.drain(..tail.len() - STDERR_TAIL_BYTES);
+            }
+        }
+        String::from_utf8_lossy(&tail).into_owned()
+    });
+
+    let request = format!("{}\n", initialize_request());
+    stdin
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to write to the server: {e}"))?;
+
+    let mut lines = BufReader::new(stdout).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if let Some(answer) = parse_initialize_response(&line) {
+            let _ = child.kill().await;
+            stderr_tail.abort();
+            return answer;
+        }
+    }
+
+    let err = stderr_tail.await.unwrap_or_default();
+    let status = child
+        .wait()
+        .await
+        .map(|s| s.to_string())
+        .unwrap_or_default();
+    let err = err.trim();
+    Err(if err.is_empty() {
+        format!("Exited before responding ({status})")
+    } else {
+        format!("Exited before responding ({status}): {}", tail(err, 500))
+    })
+}
+
+async fn check_remote(
+    server: &McpServer,
+    expand: &impl Fn(&str) -> String,
+) -> Result<String, String> {
+    let url = server
+        .url
+        .as_deref()
+        .map(expand)
+        .ok_or("No URL configured")?;
+    let client = reqwest::Client::builder()
+        .timeout(HEALTH_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let request = if server.transport == "sse" {
+        client.get(&url).header("Accept", "text/event-stream")
+    } else {
+        client
+            .post(&url)
+            .header("Accept", "application/json, text/event-stream")
+            .json(&initialize_request())
+    };
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(format!("Reachable (HTTP {})", status.as_u16()))
+    } else if status.as_u16() == 401 || status.as_u16() == 403 {
+        Ok(format!(
+            "Reachable, requires authentication (HTTP {})",
+            status.as_u16()
+        ))
+    } else {
+        Err(format!("Server returned HTTP {}", status.as_u16()))
+    }
+}
+
+/// `Some` once the line is the reply to request id 1.
+fn parse_initialize_response(line: &str) -> Option<Result<String, String>> {
+    let value: Value = serde_json::from_str(line.trim()).ok()?;
+    if value.get("id") != Some(&json!(1)) {
+        return None;
+    }
+    if let Some(error) = value.get("error") {
+        let message = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("unknown error");
+        return Some(Err(format!("initialize failed: {message}")));
+    }
+    let info = value.pointer("/result/serverInfo");
+    let name = info
+        .and_then(|i| i.get("name"))
+        .and_then(|n| n.as_str())
+        .unwrap_or("server");
+    Some(Ok(
+        match info.and_then(|i| i.get("version")).and_then(|v| v.as_str()) {
+            Some(version) => format!("{name} {version}"),
+            None => name.to_string(),
+        },
+    ))
+}
+
+/// Expand `${VAR}` references. Variables `readable` refuses are treated as
+/// unset, so a project's file can't smuggle the app's secrets into a URL or
+/// command line.
+fn expand_env(value: &str, readable: impl Fn(&str) -> bool) -> String {
+    ENV_REF_RE
+        .replace_all(value, |caps: &regex::Captures| {
+            Some(&caps[1])
+                .filter(|name| readable(name))
+                .and_then(|name| std::env::var(name).ok())
+                .or_else(|| caps.get(2).map(|d| d.as_str().to_string()))
+                .unwrap_or_default()
+        })
+        .into_owned()
+}
+
+fn tail(s: &str, max_len: usize) -> &str {
+    let mut start = s.len().saturating_sub(max_len);
+    while !s.is_char_boundary(start) {
+        start += 1;
+    }
+    &s[start..]
+}
+
+fn validate(server: &McpServer) -> Result<(), String> {
+    if server.name.is_empty()
+        || !server
+            .name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!("Invalid MCP server name: {}", server.name));
+    }
+    match server.transport.as_str() {
+        "stdio"
+            if server
+                .command
+                .as_deref()
+                .is_some_and(|c| !c.trim().is_empty()) =>
+        {
+            Ok(())
+        }
+        "stdio" => Err("A stdio server needs a command".into()),
+        "http" | "sse"
+            if server
+                .url
+                .as_deref()
+                .is_some_and(|u| u.starts_with("http://") || u.starts_with("https://")) =>
+        {
+            Ok(())
+        }
+        "http" | "sse" => Err("An http or sse server needs an http(s) URL".into()),
+        other => Err(format!("Unknown MCP transport: {other}")),
+    }
+}
+
+fn read_root(path: &Path) -> Result<Value, String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid JSON in {}: {e}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(json!({})),
+        Err(e) => Err(format!("Failed to read {}: {e}", path.display())),
+    }
+}
+
+fn servers_map(root: &mut Value) -> Result<&mut Map<String, Value>, String> {
+    let root = root
+        .as_object_mut()
+        .ok_or("MCP config is not a JSON object")?;
+    root.entry("mcpServers")
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .ok_or_else(|| "`mcpServers` is not a JSON object".to_string())
+}
+
+fn read_servers(path: &Path, scope: McpScope) -> Result<Vec<McpServer>, String> {
+    let mut root = read_root(path)?;
+    let Some(servers) = root.get_mut("mcpServers").and_then(|s| s.as_object_mut()) else {
+        return Ok(vec![]);
+    };
+    let mut list: Vec<McpServer> = servers
+        .iter()
+        .map(|(name, entry)| from_entry(name, scope, entry))
+        .collect();
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(list)
+}
+
+fn from_entry(name: &str, scope: McpScope, entry: &Value) -> McpServer {
+    let string = |key: &str| entry.get(key).and_then(|v| v.as_str()).map(String::from);
+    let url = string("url");
+    McpServer {
+        name: name.to_string(),
+        scope,
+        // `type` is optional for stdio servers
+        transport: string("type")
+            .unwrap_or_else(|| if url.is_some() { "http" } else { "stdio" }.to_string()),
+        command: string("command"),
+        args: entry
+            .get("args")
+            .and_then(|a| a.as_array())
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        env: entry
+            .get("env")
+            .and_then(|e| e.as_object())
+            .map(|e| {
+                e.iter()
+                    .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        url,
+    }
+}
+
+fn to_entry(server: &McpServer) -> Value {
+    let mut entry = Map::new();
+    entry.insert("type".into(), json!(server.transport));
+    if server.transport == "stdio" {
+        entry.insert("command".into(), json!(server.command));
+        entry.insert("args".into(), json!(server.args));
+        if !server.env.is_empty() {
+            entry.insert("env".into(), json!(server.env));
+        }
+    } else {
+        entry.insert("url".into(), json!(server.url));
+    }
+    Value::Object(entry)
+}
+
+/// Write via a temp file and rename, so a concurrent reader (e.g. a running
+/// `claude` reading `~/.claude.json`) never sees a half-written file.
+fn write_root(path: &Path, root: &Value, scope: McpScope) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(root).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, format!("{json}\n")).map_err(|e| e.to_string())?;
+    #[cfg(unix)]
+    if scope == McpScope::User {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600));
+    }
+    #[cfg(not(unix))]
+    let _ = scope;
+    std::fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_remove_preserve_other_keys() {
+        let dir = std::env::temp_dir().join(format!("clautron-mcp-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join(".mcp.json"),
+            r#"{"other": true, "mcpServers": {"docs": {"url": "https://example.com/mcp"}}}"#,
+        )
+        .unwrap();
+        let project = dir.to_str();
+
+        let server = McpServer {
+            name: "fs".into(),
+            scope: McpScope::Project,
+            transport: "stdio".into(),
+            command: Some("npx".into()),
+            args: vec!["-y".into(), "server-filesystem".into()],
+            env: Default::default(),
+            url: None,
+        };
+        add_server(&server, project).unwrap();
+        assert!(add_server(&server, project).is_err());
+
+        let servers = read_servers(&dir.join(".mcp.json"), McpScope::Project).unwrap();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers[0].name, "docs");
+        assert_eq!(servers[0].transport, "http");
+        assert_eq!(servers[1].args, vec!["-y", "server-filesystem"]);
+
+        remove_server("docs", McpScope::Project, project).unwrap();
+        let root = read_root(&dir.join(".mcp.json")).unwrap();
+        assert_eq!(root["other"], json!(true));
+        assert!(root["mcpServers"].get("docs").is_none());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn parses_initialize_reply() {
+        assert!(parse_initialize_response("{\"jsonrpc\":\"2.0\",\"method\":\"log\"}").is_none());
+        assert_eq!(
+            parse_initialize_response(
+                r#"{"jsonrpc":"2.0","id":1,"result":{"serverInfo":{"name":"fs","version":"1.2"}}}"#
+            ),
+            Some(Ok("fs 1.2".to_string()))
+        );
+        assert!(matches!(
+            parse_initialize_response(r#"{"id":1,"error":{"message":"bad"}}"#),
+            Some(Err(_))
+        ));
+    }
+
+    #[test]
+    fn project_servers_need_approval_and_only_read_allowed_env() {
+        std::env::set_var("CLAUTRON_MCP_TEST_SECRET", "secret");
+        std::env::set_var("CLAUTRON_MCP_TEST_TOKEN", "token");
+        let mut policy = CheckPolicy {
+            extra_env: vec!["CLAUTRON_MCP_TEST_TOKEN".into()],
+            ..Default::default()
+        };
+        let mut server = McpServer {
+            name: "fs".into(),
+            scope: McpScope::Project,
+            transport: "stdio".into(),
+            command: Some("definitely-not-installed".into()),
+            args: vec![],
+            env: Default::default(),
+            url: None,
+        };
+        let url =
+            "https://example.com/?k=${CLAUTRON_MCP_TEST_SECRET:-none}&t=${CLAUTRON_MCP_TEST_TOKEN}";
+        let expanded = |server: &McpServer| expand_env(url, |name| policy.may_read(server, name));
+        assert_eq!(expanded(&server), "https://example.com/?k=none&t=token");
+        server.scope = McpScope::User;
+        assert_eq!(expanded(&server), "https://example.com/?k=secret&t=token");
+
+        server.scope = McpScope::Project;
+        let check = |policy: &CheckPolicy, server: &McpServer| {
+            tauri::async_runtime::block_on(check_server(server, ".", policy)).status
+        };
+        assert_eq!(check(&policy, &server), McpHealthStatus::Unapproved);
+        policy
+            .approved_servers
+            .insert("fs".into(), launch_hash(&server));
+        assert_eq!(check(&policy, &server), McpHealthStatus::Error);
+        server.args.push("--evil".into());
+        assert_eq!(check(&policy, &server), McpHealthStatus::Unapproved);
+    }
+
+    #[test]
+    fn chatty_stderr_does_not_stall_the_handshake() {
+        let server = McpServer {
+            name: "chatty".into(),
+            scope: McpScope::User,
+            transport: "stdio".into(),
+            command: Some("sh".into()),
+            args: vec![
+                "-c".into(),
+                r#"head -c 200000 /dev/zero >&2; read line; echo '{"jsonrpc":"2.0","id":1,"result":{"serverInfo":{"name":"chatty"}}}'"#.into(),
+            ],
+            env: Default::default(),
+            url: None,
+        };
+        let health =
+            tauri::async_runtime::block_on(check_server(&server, ".", &CheckPolicy::default()));
+        assert_eq!(health.status, McpHealthStatus::Ok, "{}", health.message);
+        assert_eq!(health.message, "chatty");
+    }
+}
@@ -1,10 +1,54 @@
+pub mod agent_approvals;
+pub mod agent_generator;
+pub mod agent_library;
 pub mod agent_manager;
 pub mod agent_parser;
+pub mod agent_relationships;
+pub mod agent_sandbox;
+pub mod agent_stats;
+pub mod agent_versions;
 pub mod agent_watcher;
+pub mod budgets;
+pub mod claude_hooks;
+pub mod claude_memory;
+pub mod command_importer;
+pub mod command_watcher;
+pub mod commit_message;
 pub mod config_store;
+pub mod config_watcher;
+pub mod cost_breakdown;
+pub mod error_digest;
+pub mod gate_runner;
 pub mod git_service;
+pub mod git_watcher;
+pub mod github_issues;
+pub mod jira_import;
+pub mod linear_sync;
+pub mod log_export;
+pub mod log_mirror;
+pub mod mcp_registry;
+pub mod model_catalog;
+pub mod notification_rules;
+pub mod pricing;
 pub mod quota_service;
+pub mod rate_window;
+pub mod run_estimate;
+pub mod settings_bundle;
+pub mod skills;
+pub mod slash_commands;
+pub mod spec_autocomplete;
+pub mod spec_batch;
+pub mod spec_breakdown;
+pub mod spec_export;
 pub mod spec_manager;
 pub mod spec_parser;
+pub mod spec_reminders;
+pub mod spec_verifier;
 pub mod spec_watcher;
+pub mod spec_workflow;
+pub mod text_diff;
+pub mod transcript_import;
+pub mod usage_export;
+pub mod usage_history;
 pub mod workflow_engine;
+pub mod workflow_templates;
@@ -0,0 +1,84 @@
+//! Models offered by the start-agent and workflow step forms, so model
+//! names are picked rather than typed.
+
+use crate::domain::models::{ModelCatalog, ModelOption};
+
+/// Preselected when neither the project nor the user configured a default.
+pub const FALLBACK_MODEL: &str = "opus";
+
+/// Aliases first, then dated IDs newest first within each family.
+const CURATED: &[(&str, &str, bool)] = &[
+    ("opus", "Opus (latest)", true),
+    ("sonnet", "Sonnet (latest)", true),
+    ("haiku", "Haiku (latest)", true),
+    ("claude-opus-4-1-20250805", "Claude Opus 4.1", false),
+    ("claude-opus-4-20250514", "Claude Opus 4", false),
+    ("claude-sonnet-4-5-20250929", "Claude Sonnet 4.5", false),
+    ("claude-sonnet-4-20250514", "Claude Sonnet 4", false),
+    ("claude-3-7-sonnet-20250219", "Claude Sonnet 3.7", false),
+    ("claude-haiku-4-5-20251001", "Claude Haiku 4.5", false),
+    ("claude-3-5-haiku-20241022", "Claude Haiku 3.5", false),
+];
+
+/// The curated models, followed by models in `used` (agent definitions,
+/// past sessions, the default) that aren't among them, so existing choices
+/// stay selectable.
+pub fn model_catalog<'a>(
+    used: impl IntoIterator<Item = &'a str>,
+    default_model: Option<&str>,
+) -> ModelCatalog {
+    let mut models: Vec<ModelOption> = CURATED
+        .iter()
+        .map(|(id, label, alias)| ModelOption {
+            id: id.to_string(),
+            label: label.to_string(),
+            alias: *alias,
+        })
+        .collect();
+    let default_model = default_model
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .unwrap_or(FALLBACK_MODEL);
+    for id in used.into_iter().chain([default_model]) {
+        let id = id.trim();
+        if !id.is_empty() && !models.iter().any(|m| m.id == id) {
+            models.push(ModelOption {
+                id: id.to_string(),
+                label: id.to_string(),
+                alias: false,
+            });
+        }
+    }
+    ModelCatalog {
+        models,
+        default_model: default_model.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn used_models_are_appended_once() {
+        let catalog = model_catalog(
+            [
+                "sonnet",
+                "claude-sonnet-4-5-20250929",
+                "my-proxy-model",
+                "my-proxy-model",
+                "",
+            ],
+            Some("claude-opus-4-5"),
+        );
+        let extra: Vec<&str> = catalog.models[CURATED.len()..]
+            .iter()
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(extra, ["my-proxy-model", "claude-opus-4-5"]);
+        assert_eq!(catalog.default_model, "claude-opus-4-5");
+        assert!(catalog.models[0].alias);
+
+        assert_eq!(model_catalog([], None).default_model, FALLBACK_MODEL);
+    }
+}
@@ -0,0 +1,174 @@
+//! Desktop notifications for agent events, so long runs can be followed
+//! from another app. `AgentNotifyConfig` decides which events show one.
+
+use crate::domain::models::AgentStatus;
+use crate::domain::ports::{FileConflictEvent, StatusChangedEvent};
+use crate::services::config_store::{AgentNotifyConfig, AgentNotifyEvent, AppConfig, QuietHours};
+use chrono::{Local, NaiveTime};
+use std::sync::Arc;
+use tauri::{AppHandle, Listener};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::RwLock;
+
+/// Longest part of the prompt shown in a notification.
+const PROMPT_PREVIEW_CHARS: usize = 80;
+
+/// Whether `event` of a session of `agent` shows a notification at `now`.
+pub fn should_notify(
+    config: &AgentNotifyConfig,
+    event: AgentNotifyEvent,
+    agent: &str,
+    now: NaiveTime,
+) -> bool {
+    if config
+        .quiet_hours
+        .as_ref()
+        .is_some_and(|quiet| in_quiet_hours(quiet, now))
+    {
+        return false;
+    }
+    config
+        .rules
+        .iter()
+        .find(|rule| {
+            rule.event == event
+                && (rule.agents.is_empty() || rule.agents.iter().any(|a| a == agent))
+        })
+        .is_some_and(|rule| rule.notify)
+}
+
+/// Whether `now` is within `quiet`; never when either time doesn't parse.
+fn in_quiet_hours(quiet: &QuietHours, now: NaiveTime) -> bool {
+    let parse = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M").ok();
+    let (Some(start), Some(end)) = (parse(&quiet.start), parse(&quiet.end)) else {
+        return false;
+    };
+    if start <= end {
+        start <= now && now < end
+    } else {
+        now >= start || now < end
+    }
+}
+
+/// Show notifications for status changes and file conflicts per the rules
+/// in `config`, read at each event.
+pub fn start_listening(app: &AppHandle, config: Arc<RwLock<AppConfig>>) {
+    let app_for_status = app.clone();
+    let config_for_status = Arc::clone(&config);
+    app.listen("agent:status-changed", move |event| {
+        let Ok(status) = serde_json::from_str::<StatusChangedEvent>(event.payload()) else {
+            return;
+        };
+        let kind = match status.status {
+            AgentStatus::Running => AgentNotifyEvent::Started,
+            AgentStatus::Completed => AgentNotifyEvent::Completed,
+            AgentStatus::Error => AgentNotifyEvent::Failed,
+            AgentStatus::Stopped => AgentNotifyEvent::Stopped,
+            AgentStatus::Idle | AgentStatus::Starting => return,
+        };
+        let app = app_for_status.clone();
+        let config = Arc::clone(&config_for_status);
+        tauri::async_runtime::spawn(async move {
+            let rules = config.read().await.agent_notifications.clone();
+            if !should_notify(&rules, kind, &status.agent_name, Local::now().time()) {
+                return;
+            }
+            let title = match kind {
+                AgentNotifyEvent::Started => format!("{} started", status.agent_name),
+                AgentNotifyEvent::Completed => format!("{} finished", status.agent_name),
+                AgentNotifyEvent::Failed => format!("{} failed", status.agent_name),
+                AgentNotifyEvent::Stopped | AgentNotifyEvent::FileConflict => {
+                    format!("{} was stopped", status.agent_name)
+                }
+            };
+            show_notification(&app, &title, prompt_preview(&status.prompt));
+        });
+    });
+
+    let app_for_conflicts = app.clone();
+    app.listen("conflict:detected", move |event| {
+        let Ok(conflict) = serde_json::from_str::<FileConflictEvent>(event.payload()) else {
+            return;
+        };
+        let app = app_for_conflicts.clone();
+        let config = Arc::clone(&config);
+        tauri::async_runtime::spawn(async move {
+            let rules = config.read().await.agent_notifications.clone();
+            let now = Local::now().time();
+            let notify = [&conflict.agent_name, &conflict.other_agent_name]
+                .iter()
+                .any(|agent| should_notify(&rules, AgentNotifyEvent::FileConflict, agent, now));
+            if notify {
+                show_notification(
+                    &app,
+                    "File conflict",
+                    format!(
+                        "{} and {} both wrote {}",
+                        conflict.other_agent_name, conflict.agent_name, conflict.path
+                    ),
+                );
+            }
+        });
+    });
+}
+
+fn prompt_preview(prompt: &str) -> String {
+    let line = prompt.lines().next().unwrap_or_default().trim();
+    if line.chars().count() > PROMPT_PREVIEW_CHARS {
+        let cut: String = line.chars().take(PROMPT_PREVIEW_CHARS).collect();
+        format!("{cut}…")
+    } else {
+        line.to_string()
+    }
+}
+
+fn show_notification(app: &AppHandle, title: &str, body: String) {
+    let _ = app.notification().builder().title(title).body(body).show();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::config_store::NotificationRule;
+
+    fn rule(event: AgentNotifyEvent, agents: &[&str], notify: bool) -> NotificationRule {
+        NotificationRule {
+            event,
+            agents: agents.iter().map(|a| a.to_string()).collect(),
+            notify,
+        }
+    }
+
+    fn at(time: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(time, "%H:%M").unwrap()
+    }
+
+    #[test]
+    fn first_matching_rule_decides_outside_quiet_hours() {
+        use AgentNotifyEvent::{Completed, Failed, Started};
+        let mut config = AgentNotifyConfig {
+            rules: vec![
+                rule(Completed, &["scratch"], false),
+                rule(Completed, &[], true),
+                rule(Failed, &["dev"], true),
+            ],
+            quiet_hours: None,
+        };
+        let notified = |config: &AgentNotifyConfig, event, agent, time| {
+            should_notify(config, event, agent, at(time))
+        };
+        assert!(notified(&config, Completed, "dev", "12:00"));
+        assert!(!notified(&config, Completed, "scratch", "12:00"));
+        assert!(notified(&config, Failed, "dev", "12:00"));
+        assert!(!notified(&config, Failed, "docs", "12:00"));
+        assert!(!notified(&config, Started, "dev", "12:00"));
+
+        config.quiet_hours = Some(QuietHours {
+            start: "22:00".into(),
+            end: "07:00".into(),
+        });
+        assert!(!notified(&config, Completed, "dev", "23:30"));
+        assert!(!notified(&config, Completed, "dev", "06:59"));
+        assert!(notified(&config, Completed, "dev", "07:00"));
+    }
+}
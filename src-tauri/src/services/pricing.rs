@@ -0,0 +1,85 @@
+//! Cost estimates from token counts, for sessions whose result message
+//! reports no cost (Claude Code run with an API key).
+
+use crate::domain::models::ModelPrice;
+use std::collections::HashMap;
+
+const SONNET: ModelPrice = ModelPrice::new(3.0, 15.0);
+
+/// USD per million tokens, by model family. Matched against the model name,
+/// so `opus`, `claude-opus-4-1` and `claude-3-opus-latest` all price as Opus.
+const BUILTIN_PRICES: &[(&str, ModelPrice)] = &[
+    ("opus", ModelPrice::new(15.0, 75.0)),
+    ("sonnet", SONNET),
+    ("haiku", ModelPrice::new(0.25, 1.25)),
+];
+
+/// Models matching no entry are priced as Sonnet, Claude Code's default.
+const FALLBACK_FAMILY: &str = "sonnet";
+
+/// Price of `model`: the longest key in `overrides` or the built-in table
+/// that the model name contains, so `"opus-4-5"` can override `"opus"`.
+pub fn price_for(model: &str, overrides: &HashMap<String, ModelPrice>) -> ModelPrice {
+    matching_price(&model.to_lowercase(), overrides)
+        .or_else(|| matching_price(FALLBACK_FAMILY, overrides))
+        .unwrap_or(SONNET)
+}
+
+fn matching_price(model: &str, overrides: &HashMap<String, ModelPrice>) -> Option<ModelPrice> {
+    // Overrides come last, so they win ties: `max_by_key` keeps the last maximum
+    BUILTIN_PRICES
+        .iter()
+        .map(|(key, price)| (key.to_string(), *price))
+        .chain(
+            overrides
+                .iter()
+                .map(|(key, price)| (key.to_lowercase(), *price)),
+        )
+        .filter(|(key, _)| !key.is_empty() && model.contains(key.as_str()))
+        .max_by_key(|(key, _)| key.len())
+        .map(|(_, price)| price)
+}
+
+/// Estimated USD cost of a session's tokens.
+pub fn estimate_cost(
+    model: &str,
+    input_tokens: u64,
+    output_tokens: u64,
+    overrides: &HashMap<String, ModelPrice>,
+) -> f64 {
+    let price = price_for(model, overrides);
+    (input_tokens as f64 * price.input_per_mtok + output_tokens as f64 * price.output_per_mtok)
+        / 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn longest_matching_key_prices_the_model() {
+        let overrides = HashMap::from([
+            ("opus-4-5".to_string(), ModelPrice::new(5.0, 25.0)),
+            ("Haiku".to_string(), ModelPrice::new(1.0, 5.0)),
+        ]);
+
+        assert_eq!(
+            estimate_cost("claude-opus-4-1", 1_000_000, 100_000, &overrides),
+            22.5
+        );
+        assert_eq!(
+            estimate_cost("claude-opus-4-5-20251101", 1_000_000, 100_000, &overrides),
+            7.5
+        );
+        assert_eq!(
+            price_for("claude-haiku-4-5", &overrides),
+            ModelPrice::new(1.0, 5.0)
+        );
+        // Unknown and unset models fall back to Sonnet
+        assert_eq!(price_for("", &overrides), ModelPrice::new(3.0, 15.0));
+        assert_eq!(
+            price_for("gpt-5", &HashMap::new()),
+            ModelPrice::new(3.0, 15.0)
+        );
+    }
+}
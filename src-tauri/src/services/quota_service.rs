@@ -5,17 +5,43 @@
 //! that Claude Code maintains itself after every session. It contains per-model
 //! token counts, USD cost, and daily activity. No network, no auth required.
 //!
-//! The file is re-read every 60 seconds and on explicit refresh requests.
+//! The file is re-read whenever it changes on disk, on explicit refresh
+//! requests, and by a slow fallback poll in case file events are missed.
+//! Each read also stores the day's per-model totals, so spend can be charted
+//! over time (see `usage_history`) and checked against spending budgets,
+//! emitting `quota:budget-warning` / `quota:budget-exceeded` once per period
+//! when a budget reaches that level. Every read also emits
+//! `quota:window-update` with the usage in the current 5-hour window.
+//! Budget levels, a window's token threshold, rate limits and their resets
+//! also show desktop notifications when enabled in `QuotaNotifyConfig`.
+//!
+//! Each configured Claude profile keeps its own stats file in its config
+//! directory. All of them are read, reported per profile and summed into
+//! the totals.
 
-use chrono::Local;
+use crate::domain::models::{ClaudeProfile, ModelUsage, UsageGranularity, UsagePeriod};
+use crate::domain::ports::{RateLimitedEvent, UsageRepository};
+use crate::domain::session_manager::SessionManager;
+use crate::services::budgets::{self, BudgetLevel, BudgetPeriod, BudgetStatus};
+use crate::services::config_store::AppConfig;
+use crate::services::rate_window::{self, RateWindow};
+use crate::services::usage_history;
+use chrono::{DateTime, Local, Utc};
+use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tauri::{async_runtime, AppHandle, Emitter};
-use tokio::sync::RwLock;
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::{mpsc, Mutex, RwLock};
 
-const POLL_INTERVAL_SECS: u64 = 60;
+/// How often to check the stats file when no change event arrived. The
+/// check is skipped when the file is unchanged and the day hasn't rolled
+/// over.
+const FALLBACK_POLL_SECS: u64 = 300;
+const STATS_CACHE_FILE: &str = "stats-cache.json";
 
 // ---------------------------------------------------------------------------
 // stats-cache.json types
@@ -78,18 +104,33 @@ pub struct DailyStats {
     pub message_count: u32,
 }
 
+/// Usage read from one profile's stats file.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileUsage {
+    /// None for Claude Code's default config directory.
+    pub profile: Option<String>,
+    pub models: Vec<ModelUsageEntry>,
+    pub total_cost_usd: f64,
+    pub available: bool,
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct QuotaUpdateEvent {
-    /// Per-model breakdown across all time.
+    /// Per-model breakdown across all time and profiles.
     pub models: Vec<ModelUsageEntry>,
     /// Today's activity stats.
     pub today: Option<DailyStats>,
     /// Total all-time cost in USD.
     pub total_cost_usd: f64,
+    /// The same usage for each profile on its own.
+    pub profiles: Vec<ProfileUsage>,
     pub fetched_at: String,
     /// False when the stats file could not be read.
     pub available: bool,
     pub error: Option<String>,
+    /// Each configured spending budget in its current period.
+    pub budgets: Vec<BudgetStatus>,
 }
 
 // ---------------------------------------------------------------------------
@@ -97,14 +138,273 @@ pub struct QuotaUpdateEvent {
 // ---------------------------------------------------------------------------
 
 pub struct QuotaState {
-    _private: RwLock<()>,
+    usage: Arc<dyn UsageRepository>,
+    config: Arc<RwLock<AppConfig>>,
+    /// Refuses new sessions while a budget with a hard stop is used up.
+    sessions: Arc<SessionManager>,
+    /// Budget levels already reported, with the period start they were in.
+    reported: Mutex<HashMap<BudgetPeriod, (String, BudgetLevel)>>,
+    /// Start of the window last notified as past `window_percent`.
+    window_alerted: Mutex<Option<String>>,
+    /// Reset time (or message) of the rate limit last notified.
+    rate_limit_alerted: Mutex<Option<String>>,
+    /// Reset time with a notification already scheduled.
+    reset_scheduled: Mutex<Option<String>>,
 }
 
 impl QuotaState {
-    pub fn new() -> Self {
+    pub fn new(
+        usage: Arc<dyn UsageRepository>,
+        config: Arc<RwLock<AppConfig>>,
+        sessions: Arc<SessionManager>,
+    ) -> Self {
         Self {
-            _private: RwLock::new(()),
+            usage,
+            config,
+            sessions,
+            reported: Mutex::new(HashMap::new()),
+            window_alerted: Mutex::new(None),
+            rate_limit_alerted: Mutex::new(None),
+            reset_scheduled: Mutex::new(None),
+        }
+    }
+
+    /// Spend per period between `from` and `to` (inclusive `YYYY-MM-DD`
+    /// dates), from the stored daily snapshots.
+    pub async fn usage_history(
+        &self,
+        from: Option<&str>,
+        to: Option<&str>,
+        granularity: UsageGranularity,
+    ) -> Result<Vec<UsagePeriod>, String> {
+        let snapshots = self
+            .usage
+            .usage_snapshots(to)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(usage_history::usage_history(&snapshots, from, to, granularity))
+    }
+
+    /// Store today's totals of a profile, replacing an earlier read from the
+    /// same day.
+    async fn record_snapshot(&self, profile: Option<&str>, models: &[ModelUsageEntry]) {
+        if models.is_empty() {
+            return;
+        }
+        let date = Local::now().format("%Y-%m-%d").to_string();
+        let models: Vec<ModelUsage> = models
+            .iter()
+            .map(|m| ModelUsage {
+                model: m.model.clone(),
+                input_tokens: m.input_tokens,
+                output_tokens: m.output_tokens,
+                cache_read_tokens: m.cache_read_tokens,
+                cache_creation_tokens: m.cache_creation_tokens,
+                cost_usd: m.cost_usd,
+            })
+            .collect();
+        if let Err(e) = self
+            .usage
+            .record_usage_snapshot(&date, profile, &models)
+            .await
+        {
+            eprintln!("Failed to record usage snapshot: {e}");
+        }
+    }
+}
+
+impl QuotaState {
+    /// Check spend against the configured budgets, applying the hard stop
+    /// and emitting each budget's warning or excess once per period.
+    async fn check_budgets(&self, app: &AppHandle) -> Vec<BudgetStatus> {
+        let (config, notify) = {
+            let config = self.config.read().await;
+            (config.budgets(), config.quota_notifications.clone())
+        };
+        let today = Local::now().date_naive();
+        let from = budgets::history_start(today);
+        let days = match self
+            .usage_history(Some(&from), None, UsageGranularity::Day)
+            .await
+        {
+            Ok(days) => days,
+            Err(e) => {
+                eprintln!("Budgets not checked: {e}");
+                return Vec::new();
+            }
+        };
+        let statuses = budgets::budget_statuses(&config, &days, today);
+        self.sessions
+            .set_budget_stop(budgets::stop_reason(&config, &statuses));
+
+        let mut reported = self.reported.lock().await;
+        let mut still_reached = HashMap::new();
+        for status in &statuses {
+            let Some(level) = status.level else {
+                continue;
+            };
+            let key = (status.start.clone(), level);
+            if reported.get(&status.period) != Some(&key) {
+                let event = match level {
+                    BudgetLevel::Warning => "quota:budget-warning",
+                    BudgetLevel::Exceeded => "quota:budget-exceeded",
+                };
+                let _ = app.emit(event, status);
+                let (enabled, title) = match level {
+                    BudgetLevel::Warning => (notify.budget_warning, "Budget Warning"),
+                    BudgetLevel::Exceeded => (notify.budget_exceeded, "Budget Exceeded"),
+                };
+                if enabled {
+                    show_notification(
+                        app,
+                        title,
+                        format!(
+                            "${:.2} of the {} ${:.2} budget spent",
+                            status.spent_usd,
+                            status.period.label(),
+                            status.limit_usd
+                        ),
+                    );
+                }
+            }
+            still_reached.insert(status.period, key);
         }
+        *reported = still_reached;
+        statuses
+    }
+}
+
+impl QuotaState {
+    /// Stats files to read: the default config directory's, then each
+    /// configured profile's.
+    async fn stats_sources(&self) -> Vec<StatsSource> {
+        let profiles = self.config.read().await.profiles.clone();
+        stats_sources(&profiles)
+    }
+
+    /// Usage in the current rolling window, from this app's sessions under
+    /// the project's default profile: each account has a window of its own.
+    pub async fn rate_window(&self) -> RateWindow {
+        let token_limit = self.config.read().await.rate_window.token_limit;
+        let profile = self.sessions.default_profile();
+        let sessions: Vec<_> = self
+            .sessions
+            .list_sessions()
+            .await
+            .into_iter()
+            .filter(|s| s.profile == profile)
+            .collect();
+        rate_window::current_window(&sessions, chrono::Utc::now(), token_limit)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Notifications
+// ---------------------------------------------------------------------------
+
+impl QuotaState {
+    /// Notify once per window when it passes the configured share of its
+    /// token limit.
+    async fn check_window(&self, app: &AppHandle, window: &RateWindow) {
+        let notify = self.config.read().await.quota_notifications.clone();
+        let (Some(threshold), Some(used), Some(started_at)) =
+            (notify.window_percent, window.used_percent(), &window.started_at)
+        else {
+            return;
+        };
+        if used < threshold {
+            return;
+        }
+        {
+            let mut alerted = self.window_alerted.lock().await;
+            if alerted.as_ref() == Some(started_at) {
+                return;
+            }
+            *alerted = Some(started_at.clone());
+        }
+        let resets = window
+            .resets_at
+            .as_deref()
+            .map(|at| format!("; resets at {}", local_time(at)))
+            .unwrap_or_default();
+        show_notification(
+            app,
+            "Usage Window",
+            format!("{used:.0}% of the 5-hour window's tokens used{resets}"),
+        );
+        if notify.window_reset {
+            if let Some(resets_at) = &window.resets_at {
+                self.schedule_reset_notification(app, resets_at).await;
+            }
+        }
+    }
+
+    /// Notify that a session hit the usage limit, once per limit when
+    /// several sessions hit it together.
+    pub async fn on_rate_limited(&self, app: &AppHandle, event: &RateLimitedEvent) {
+        let notify = self.config.read().await.quota_notifications.clone();
+        let key = event
+            .reset_at
+            .clone()
+            .unwrap_or_else(|| event.raw_message.clone());
+        {
+            let mut alerted = self.rate_limit_alerted.lock().await;
+            if alerted.as_ref() == Some(&key) {
+                return;
+            }
+            *alerted = Some(key);
+        }
+        if notify.rate_limited {
+            let body = match &event.reset_at {
+                Some(at) => format!("Claude's usage limit was hit; it resets at {}", local_time(at)),
+                None => event.raw_message.clone(),
+            };
+            show_notification(app, "Usage Limit Reached", body);
+        }
+        if notify.window_reset {
+            if let Some(reset_at) = &event.reset_at {
+                self.schedule_reset_notification(app, reset_at).await;
+            }
+        }
+    }
+
+    /// Notify when `resets_at` (RFC 3339) arrives. Times in the past or not
+    /// parseable are ignored.
+    async fn schedule_reset_notification(&self, app: &AppHandle, resets_at: &str) {
+        let Some(delay) = DateTime::parse_from_rfc3339(resets_at)
+            .ok()
+            .and_then(|at| (at.with_timezone(&Utc) - Utc::now()).to_std().ok())
+        else {
+            return;
+        };
+        {
+            let mut scheduled = self.reset_scheduled.lock().await;
+            if scheduled.as_deref() == Some(resets_at) {
+                return;
+            }
+            *scheduled = Some(resets_at.to_string());
+        }
+        let app = app.clone();
+        async_runtime::spawn(async move {
+            tokio::time::sleep(delay).await;
+            show_notification(
+                &app,
+                "Usage Window Reset",
+                "Claude usage is available again".to_string(),
+            );
+        });
+    }
+}
+
+fn show_notification(app: &AppHandle, title: &str, body: String) {
+    let _ = app.notification().builder().title(title).body(body).show();
+}
+
+/// Local clock time of an RFC 3339 timestamp, or the timestamp itself.
+fn local_time(timestamp: &str) -> String {
+    match DateTime::parse_from_rfc3339(timestamp) {
+        Ok(t) => t.with_timezone(&Local).format("%H:%M").to_string(),
+        Err(_) => timestamp.to_string(),
     }
 }
 
@@ -112,13 +412,50 @@ impl QuotaState {
 // File reading
 // ---------------------------------------------------------------------------
 
-fn stats_cache_path() -> Option<std::path::PathBuf> {
-    dirs::home_dir().map(|h| h.join(".claude").join("stats-cache.json"))
+/// A profile's stats file; `profile` is None for the default one.
+#[derive(Debug, Clone, PartialEq)]
+struct StatsSource {
+    profile: Option<String>,
+    path: PathBuf,
 }
 
-fn read_stats() -> Result<QuotaUpdateEvent, String> {
-    let path = stats_cache_path().ok_or("Cannot determine home directory")?;
-    let content = std::fs::read_to_string(&path)
+/// Claude Code's own config directory: `CLAUDE_CONFIG_DIR` when this app was
+/// started with it, as sessions inherit it, else `~/.claude`.
+fn default_config_dir() -> Option<PathBuf> {
+    match std::env::var_os("CLAUDE_CONFIG_DIR") {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => dirs::home_dir().map(|h| h.join(".claude")),
+    }
+}
+
+/// Profiles pointing at the default directory are left out, so its usage
+/// isn't counted twice.
+fn stats_sources(profiles: &[ClaudeProfile]) -> Vec<StatsSource> {
+    let default_dir = default_config_dir();
+    let mut sources: Vec<StatsSource> = default_dir
+        .iter()
+        .map(|dir| StatsSource {
+            profile: None,
+            path: dir.join(STATS_CACHE_FILE),
+        })
+        .collect();
+    for profile in profiles {
+        let dir = profile.config_path();
+        if default_dir.as_ref() == Some(&dir) {
+            continue;
+        }
+        sources.push(StatsSource {
+            profile: Some(profile.name.clone()),
+            path: dir.join(STATS_CACHE_FILE),
+        });
+    }
+    sources
+}
+
+fn read_stats_file(
+    path: &std::path::Path,
+) -> Result<(Vec<ModelUsageEntry>, Option<DailyStats>), String> {
+    let content = std::fs::read_to_string(path)
         .map_err(|e| format!("Cannot read stats-cache.json: {e}"))?;
     let cache: StatsCache =
         serde_json::from_str(&content).map_err(|e| format!("Cannot parse stats-cache.json: {e}"))?;
@@ -143,19 +480,83 @@ fn read_stats() -> Result<QuotaUpdateEvent, String> {
             cost_usd: stats.cost_usd,
         })
         .collect();
+    sort_by_cost(&mut models);
+    Ok((models, today))
+}
 
-    // Sort by cost descending so the most-used model appears first
+/// Sort by cost descending so the most-used model appears first
+fn sort_by_cost(models: &mut [ModelUsageEntry]) {
     models.sort_by(|a, b| b.cost_usd.partial_cmp(&a.cost_usd).unwrap_or(std::cmp::Ordering::Equal));
+}
+
+/// Read every source and sum them. Fails only when none could be read.
+fn read_stats(sources: &[StatsSource]) -> Result<QuotaUpdateEvent, String> {
+    if sources.is_empty() {
+        return Err("Cannot determine home directory".into());
+    }
 
-    let total_cost_usd = models.iter().map(|m| m.cost_usd).sum();
+    let mut profiles = Vec::new();
+    let mut combined: Vec<ModelUsageEntry> = Vec::new();
+    let mut today: Option<DailyStats> = None;
+    let mut first_error = None;
+    for source in sources {
+        let (models, profile_today) = match read_stats_file(&source.path) {
+            Ok(stats) => stats,
+            Err(e) => {
+                first_error.get_or_insert_with(|| e.clone());
+                profiles.push(ProfileUsage {
+                    profile: source.profile.clone(),
+                    models: Vec::new(),
+                    total_cost_usd: 0.0,
+                    available: false,
+                    error: Some(e),
+                });
+                continue;
+            }
+        };
+        for entry in &models {
+            match combined.iter_mut().find(|m| m.model == entry.model) {
+                Some(m) => {
+                    m.input_tokens += entry.input_tokens;
+                    m.output_tokens += entry.output_tokens;
+                    m.cache_read_tokens += entry.cache_read_tokens;
+                    m.cache_creation_tokens += entry.cache_creation_tokens;
+                    m.cost_usd += entry.cost_usd;
+                }
+                None => combined.push(entry.clone()),
+            }
+        }
+        if let Some(day) = profile_today {
+            match today.as_mut() {
+                Some(t) => {
+                    t.session_count += day.session_count;
+                    t.message_count += day.message_count;
+                }
+                None => today = Some(day),
+            }
+        }
+        profiles.push(ProfileUsage {
+            profile: source.profile.clone(),
+            total_cost_usd: models.iter().map(|m| m.cost_usd).sum(),
+            models,
+            available: true,
+            error: None,
+        });
+    }
+    if profiles.iter().all(|p| !p.available) {
+        return Err(first_error.unwrap_or_default());
+    }
+    sort_by_cost(&mut combined);
 
     Ok(QuotaUpdateEvent {
-        models,
+        total_cost_usd: combined.iter().map(|m| m.cost_usd).sum(),
+        models: combined,
         today,
-        total_cost_usd,
+        profiles,
         fetched_at: chrono::Utc::now().to_rfc3339(),
         available: true,
         error: None,
+        budgets: Vec::new(),
     })
 }
 
@@ -166,9 +567,11 @@ fn emit_unavailable(app: &AppHandle, reason: &str) {
             models: vec![],
             today: None,
             total_cost_usd: 0.0,
+            profiles: vec![],
             fetched_at: chrono::Utc::now().to_rfc3339(),
             available: false,
             error: Some(reason.to_string()),
+            budgets: vec![],
         },
     );
 }
@@ -177,26 +580,104 @@ fn emit_unavailable(app: &AppHandle, reason: &str) {
 // Polling
 // ---------------------------------------------------------------------------
 
-pub fn start_poller(app: AppHandle, _state: Arc<QuotaState>) {
+/// Re-read the stats files within a second of Claude Code rewriting them,
+/// with a slow fallback poll for platforms or setups where events don't
+/// arrive. Directories are watched as configured at startup; profiles added
+/// later are read on refreshes and by the fallback poll.
+pub fn start_poller(app: AppHandle, state: Arc<QuotaState>) {
     async_runtime::spawn(async move {
+        let (changed_tx, mut changed_rx) = mpsc::unbounded_channel();
+        let dirs: Vec<PathBuf> = state
+            .stats_sources()
+            .await
+            .iter()
+            .filter_map(|s| s.path.parent().map(|p| p.to_path_buf()))
+            .collect();
+        if let Some(watcher) = watch_stats_cache(&dirs, changed_tx) {
+            std::mem::forget(watcher);
+        }
+
+        let mut last_seen = stats_fingerprint(&state.stats_sources().await);
+        poll_once(&app, &state).await;
         loop {
-            poll_once_inner(&app);
-            tokio::time::sleep(Duration::from_secs(POLL_INTERVAL_SECS)).await;
+            tokio::select! {
+                Some(()) = changed_rx.recv() => {}
+                _ = tokio::time::sleep(Duration::from_secs(FALLBACK_POLL_SECS)) => {
+                    if stats_fingerprint(&state.stats_sources().await) == last_seen {
+                        continue;
+                    }
+                }
+            }
+            last_seen = stats_fingerprint(&state.stats_sources().await);
+            poll_once(&app, &state).await;
         }
     });
 }
 
-pub async fn poll_once(app: &AppHandle, _state: &QuotaState) {
-    poll_once_inner(app);
+/// Watch the config directories for a stats file being written or replaced.
+/// Debounced at 1s, as Claude Code may write it several times at the end of
+/// a session. Directories that don't exist yet are skipped; None when none
+/// could be watched.
+fn watch_stats_cache(
+    dirs: &[PathBuf],
+    changed: mpsc::UnboundedSender<()>,
+) -> Option<notify_debouncer_mini::Debouncer<notify::RecommendedWatcher>> {
+    let mut debouncer = new_debouncer(
+        Duration::from_secs(1),
+        move |events: Result<Vec<notify_debouncer_mini::DebouncedEvent>, notify::Error>| {
+            if let Ok(events) = events {
+                let touched = events.iter().any(|e| {
+                    e.kind == DebouncedEventKind::Any
+                        && e.path.file_name().and_then(|n| n.to_str()) == Some(STATS_CACHE_FILE)
+                });
+                if touched {
+                    let _ = changed.send(());
+                }
+            }
+        },
+    )
+    .ok()?;
+
+    let mut watching = false;
+    for dir in dirs.iter().filter(|d| d.exists()) {
+        watching |= debouncer
+            .watcher()
+            .watch(dir, notify::RecursiveMode::NonRecursive)
+            .is_ok();
+    }
+    watching.then_some(debouncer)
+}
+
+/// Each stats file's modification time and today's date: a poll with the
+/// same fingerprint as the last one would find nothing new.
+fn stats_fingerprint(sources: &[StatsSource]) -> (Vec<Option<std::time::SystemTime>>, String) {
+    let modified = sources
+        .iter()
+        .map(|source| {
+            std::fs::metadata(&source.path)
+                .ok()
+                .and_then(|meta| meta.modified().ok())
+        })
+        .collect();
+    (modified, Local::now().format("%Y-%m-%d").to_string())
 }
 
-fn poll_once_inner(app: &AppHandle) {
-    match read_stats() {
-        Ok(event) => {
+pub async fn poll_once(app: &AppHandle, state: &QuotaState) {
+    match read_stats(&state.stats_sources().await) {
+        Ok(mut event) => {
+            for profile in event.profiles.iter().filter(|p| p.available) {
+                state
+                    .record_snapshot(profile.profile.as_deref(), &profile.models)
+                    .await;
+            }
+            event.budgets = state.check_budgets(app).await;
             let _ = app.emit("quota:update", event);
         }
         Err(e) => {
             emit_unavailable(app, &e);
         }
     }
+    let window = state.rate_window().await;
+    state.check_window(app, &window).await;
+    let _ = app.emit("quota:window-update", window);
 }
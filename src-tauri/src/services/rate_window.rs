@@ -0,0 +1,145 @@
+//! The subscription's rolling usage window: Claude limits usage per 5 hours,
+//! counted from the first message after the previous window ran out. The
+//! window is reconstructed from when this app's sessions started, so usage
+//! from outside the app isn't counted.
+
+use crate::domain::models::AgentSession;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+const WINDOW_HOURS: i64 = 5;
+
+/// Usage in the current window. Payload of `quota:window-update`.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct RateWindow {
+    /// RFC 3339 start of the window; None when no window is open, i.e. the
+    /// next session starts a fresh one.
+    pub started_at: Option<String>,
+    pub resets_at: Option<String>,
+    pub resets_in_secs: Option<i64>,
+    pub session_count: u32,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+    /// Configured capacity of a window, and what's left of it.
+    pub token_limit: Option<u64>,
+    pub remaining_tokens: Option<u64>,
+}
+
+impl RateWindow {
+    /// Share of the token limit used, in percent; None without a limit.
+    pub fn used_percent(&self) -> Option<f64> {
+        let limit = self.token_limit.filter(|l| *l > 0)?;
+        Some((self.input_tokens + self.output_tokens) as f64 * 100.0 / limit as f64)
+    }
+}
+
+/// The window open at `now`, with the usage of sessions started in it.
+pub fn current_window(
+    sessions: &[AgentSession],
+    now: DateTime<Utc>,
+    token_limit: Option<u64>,
+) -> RateWindow {
+    let window = Duration::hours(WINDOW_HOURS);
+    let mut starts: Vec<(DateTime<Utc>, &AgentSession)> = sessions
+        .iter()
+        .filter_map(|s| {
+            let started = DateTime::parse_from_rfc3339(&s.started_at).ok()?;
+            Some((started.with_timezone(&Utc), s))
+        })
+        .filter(|(started, _)| *started <= now)
+        .collect();
+    starts.sort_by_key(|(started, _)| *started);
+
+    // Each start after a window ran out opens the next one
+    let mut window_start: Option<DateTime<Utc>> = None;
+    for (started, _) in &starts {
+        if window_start.is_none_or(|w| *started >= w + window) {
+            window_start = Some(*started);
+        }
+    }
+    let Some(start) = window_start.filter(|w| *w + window > now) else {
+        return RateWindow {
+            token_limit,
+            remaining_tokens: token_limit,
+            ..Default::default()
+        };
+    };
+
+    let resets_at = start + window;
+    let mut current = RateWindow {
+        started_at: Some(start.to_rfc3339()),
+        resets_at: Some(resets_at.to_rfc3339()),
+        resets_in_secs: Some((resets_at - now).num_seconds()),
+        token_limit,
+        ..Default::default()
+    };
+    for (_, session) in starts.iter().filter(|(started, _)| *started >= start) {
+        current.session_count += 1;
+        current.input_tokens += session.input_tokens;
+        current.output_tokens += session.output_tokens;
+        current.cost_usd += session.cost_usd;
+    }
+    let used = current.input_tokens + current.output_tokens;
+    current.remaining_tokens = token_limit.map(|limit| limit.saturating_sub(used));
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::AgentStatus;
+
+    fn session(started_at: &str, output_tokens: u64) -> AgentSession {
+        AgentSession {
+            id: started_at.into(),
+            agent_name: "dev".into(),
+            model: "opus".into(),
+            status: AgentStatus::Completed,
+            prompt: String::new(),
+            started_at: started_at.into(),
+            ended_at: None,
+            input_tokens: 0,
+            output_tokens,
+            cost_usd: 0.0,
+            project_dir: None,
+            branch: None,
+            cost_estimated: false,
+            profile: None,
+        }
+    }
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(timestamp).unwrap().into()
+    }
+
+    #[test]
+    fn window_opens_with_the_first_start_after_the_last_one_ran_out() {
+        let sessions = [
+            session("2026-03-02T08:00:00Z", 500),
+            session("2026-03-02T12:00:00Z", 700),
+            // 08:00's window ran out at 13:00, so this opens a new one
+            session("2026-03-02T13:30:00Z", 100),
+            session("2026-03-02T15:00:00Z", 200),
+        ];
+
+        let window = current_window(&sessions, at("2026-03-02T16:00:00Z"), Some(1000));
+        assert_eq!(
+            window.started_at.as_deref(),
+            Some("2026-03-02T13:30:00+00:00")
+        );
+        assert_eq!(window.resets_in_secs, Some(150 * 60));
+        assert_eq!(window.session_count, 2);
+        assert_eq!(window.remaining_tokens, Some(700));
+        assert_eq!(window.used_percent(), Some(30.0));
+
+        let idle = current_window(&sessions, at("2026-03-02T19:00:00Z"), Some(1000));
+        assert_eq!(idle.started_at, None);
+        assert_eq!(idle.session_count, 0);
+        assert_eq!(idle.remaining_tokens, Some(1000));
+        assert_eq!(
+            current_window(&sessions, at("2026-03-02T16:00:00Z"), None).used_percent(),
+            None
+        );
+    }
+}
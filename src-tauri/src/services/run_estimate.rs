@@ -0,0 +1,106 @@
+use crate::domain::models::{AgentRunEstimate, AgentSession, RunEstimate};
+use crate::services::agent_stats::duration_secs;
+
+/// Predict a run of `planned` agents, each with the wave it starts in, from
+/// the finished sessions in `history`.
+pub fn estimate_run(planned: &[(usize, String)], history: &[AgentSession]) -> RunEstimate {
+    let runs: Vec<AgentRunEstimate> = planned
+        .iter()
+        .map(|(wave, agent)| agent_estimate(agent, *wave, history))
+        .collect();
+
+    let mut missing_history: Vec<String> = Vec::new();
+    for run in runs.iter().filter(|r| r.sample_size == 0) {
+        if !missing_history.contains(&run.agent_name) {
+            missing_history.push(run.agent_name.clone());
+        }
+    }
+
+    let wave_count = runs.iter().map(|r| r.wave + 1).max().unwrap_or(0);
+    let duration_secs = (0..wave_count)
+        .map(|wave| {
+            runs.iter()
+                .filter(|r| r.wave == wave)
+                .filter_map(|r| r.avg_duration_secs)
+                .fold(0.0, f64::max)
+        })
+        .sum();
+
+    RunEstimate {
+        cost_usd: runs.iter().filter_map(|r| r.avg_cost_usd).sum(),
+        input_tokens: runs.iter().filter_map(|r| r.avg_input_tokens).sum(),
+        output_tokens: runs.iter().filter_map(|r| r.avg_output_tokens).sum(),
+        duration_secs,
+        missing_history,
+        runs,
+    }
+}
+
+fn agent_estimate(agent_name: &str, wave: usize, history: &[AgentSession]) -> AgentRunEstimate {
+    let past: Vec<&AgentSession> = history
+        .iter()
+        .filter(|s| s.agent_name == agent_name && s.ended_at.is_some())
+        .collect();
+    let count = past.len();
+    let avg = |total: f64| (count > 0).then(|| total / count as f64);
+    let durations: Vec<f64> = past.iter().filter_map(|s| duration_secs(s)).collect();
+
+    AgentRunEstimate {
+        agent_name: agent_name.to_string(),
+        wave,
+        sample_size: count as u32,
+        avg_cost_usd: avg(past.iter().map(|s| s.cost_usd).sum()),
+        avg_input_tokens: avg(past.iter().map(|s| s.input_tokens as f64).sum()).map(|t| t as u64),
+        avg_output_tokens: avg(past.iter().map(|s| s.output_tokens as f64).sum()).map(|t| t as u64),
+        avg_duration_secs: (!durations.is_empty())
+            .then(|| durations.iter().sum::<f64>() / durations.len() as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::AgentStatus;
+
+    fn session(agent: &str, minutes: i64, cost_usd: f64) -> AgentSession {
+        AgentSession {
+            id: format!("{agent}-{minutes}"),
+            agent_name: agent.into(),
+            model: "sonnet".into(),
+            status: AgentStatus::Completed,
+            prompt: String::new(),
+            started_at: "2026-03-02T10:00:00Z".into(),
+            ended_at: Some(format!("2026-03-02T10:{minutes:02}:00Z")),
+            input_tokens: 1000,
+            output_tokens: 100,
+            cost_usd,
+            project_dir: None,
+            branch: None,
+            cost_estimated: false,
+            profile: None,
+        }
+    }
+
+    #[test]
+    fn waves_run_in_parallel_and_agents_average_their_history() {
+        let history = [
+            session("dev", 10, 1.0),
+            session("dev", 20, 2.0),
+            session("reviewer", 5, 0.5),
+        ];
+        let planned = [
+            (0, "dev".to_string()),
+            (0, "reviewer".to_string()),
+            (1, "reviewer".to_string()),
+            (1, "docs".to_string()),
+        ];
+
+        let estimate = estimate_run(&planned, &history);
+        assert_eq!(estimate.runs[0].avg_cost_usd, Some(1.5));
+        assert_eq!(estimate.cost_usd, 2.5);
+        assert_eq!(estimate.input_tokens, 3000);
+        // Slowest of wave 0 (15 min), then of wave 1 (5 min)
+        assert_eq!(estimate.duration_secs, 20.0 * 60.0);
+        assert_eq!(estimate.missing_history, ["docs"]);
+    }
+}
@@ -0,0 +1,92 @@
+//! Settings archive for moving to a new machine: the app config (with its
+//! Claude profiles and credentials), agent approvals and workflow templates
+//! in one gzipped JSON file.
+
+use crate::services::agent_approvals::ApprovalExport;
+use crate::services::config_store::AppConfig;
+use crate::services::workflow_templates::WorkflowTemplate;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+/// Archives from a newer app version are refused.
+const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub version: u32,
+    pub exported_at: String,
+    pub config: AppConfig,
+    #[serde(default)]
+    pub agent_approvals: ApprovalExport,
+    #[serde(default)]
+    pub workflow_templates: Vec<WorkflowTemplate>,
+}
+
+/// What `import_settings` added next to the replaced config.
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsImportReport {
+    pub agent_approvals: usize,
+    pub workflow_templates: usize,
+}
+
+impl SettingsBundle {
+    pub fn new(
+        config: AppConfig,
+        agent_approvals: ApprovalExport,
+        workflow_templates: Vec<WorkflowTemplate>,
+    ) -> Self {
+        Self {
+            version: BUNDLE_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            config,
+            agent_approvals,
+            workflow_templates,
+        }
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>, String> {
+        let json = serde_json::to_vec(self).map_err(|e| e.to_string())?;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&json).map_err(|e| e.to_string())?;
+        encoder.finish().map_err(|e| e.to_string())
+    }
+
+    pub fn decode(archive: &[u8]) -> Result<Self, String> {
+        let mut json = String::new();
+        flate2::read::GzDecoder::new(archive)
+            .read_to_string(&mut json)
+            .map_err(|e| format!("Not a settings archive: {e}"))?;
+        let bundle: Self =
+            serde_json::from_str(&json).map_err(|e| format!("Invalid settings archive: {e}"))?;
+        if bundle.version > BUNDLE_VERSION {
+            return Err(format!(
+                "The archive is from a newer version of the app (format {})",
+                bundle.version
+            ));
+        }
+        Ok(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundles_round_trip_and_newer_formats_are_refused() {
+        let mut config = AppConfig::default();
+        config.default_model = Some("sonnet".into());
+        let bundle = SettingsBundle::new(config, ApprovalExport::default(), Vec::new());
+
+        let archive = bundle.encode().unwrap();
+        let decoded = SettingsBundle::decode(&archive).unwrap();
+        assert_eq!(decoded.config.default_model.as_deref(), Some("sonnet"));
+
+        let newer = SettingsBundle {
+            version: BUNDLE_VERSION + 1,
+            ..decoded
+        };
+        assert!(SettingsBundle::decode(&newer.encode().unwrap()).is_err());
+        assert!(SettingsBundle::decode(b"{}").is_err());
+    }
+}
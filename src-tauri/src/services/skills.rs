@@ -0,0 +1,143 @@
+use crate::domain::models::{AgentConfig, Skill};
+use regex::Regex;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Cap on files listed per skill, in case a package vendors a large tree.
+const MAX_FILES: usize = 200;
+
+#[derive(Debug, Default, Deserialize)]
+struct SkillFrontmatter {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+pub fn skills_dir(project_dir: &str) -> PathBuf {
+    Path::new(project_dir).join(".claude/skills")
+}
+
+/// Every skill package in the project, sorted by name, with the agents that
+/// mention it.
+pub fn list_skills(project_dir: &str, agents: &[AgentConfig]) -> Vec<Skill> {
+    let Ok(entries) = std::fs::read_dir(skills_dir(project_dir)) else {
+        return vec![];
+    };
+    // Frontmatter isn't part of AgentConfig, so read the files themselves
+    let agent_files: Vec<(&str, String)> = agents
+        .iter()
+        .map(|a| {
+            let content = std::fs::read_to_string(&a.file_path).unwrap_or_else(|_| a.body.clone());
+            (a.name.as_str(), content)
+        })
+        .collect();
+
+    let mut skills: Vec<Skill> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|dir| dir.join("SKILL.md").is_file())
+        .map(|dir| {
+            let content = std::fs::read_to_string(dir.join("SKILL.md")).unwrap_or_default();
+            let fm = parse_frontmatter(&content);
+            let dir_name = dir
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let name = fm.name.filter(|n| !n.trim().is_empty()).unwrap_or(dir_name);
+            let mut files = Vec::new();
+            collect_files(&dir, &dir, &mut files);
+            files.sort();
+            Skill {
+                referenced_by: agent_files
+                    .iter()
+                    .filter(|(_, content)| mentions(content, &name))
+                    .map(|(agent, _)| agent.to_string())
+                    .collect(),
+                name,
+                description: fm.description.unwrap_or_default(),
+                dir_path: dir.to_string_lossy().to_string(),
+                files,
+            }
+        })
+        .collect();
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    skills
+}
+
+fn parse_frontmatter(content: &str) -> SkillFrontmatter {
+    content
+        .trim_start()
+        .strip_prefix("---")
+        .and_then(|rest| rest.find("\n---").map(|end| &rest[..end]))
+        .and_then(|yaml| serde_yaml::from_str(yaml).ok())
+        .unwrap_or_default()
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        if files.len() >= MAX_FILES {
+            return;
+        }
+        if path.is_dir() {
+            collect_files(root, &path, files);
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            files.push(relative.to_string_lossy().to_string());
+        }
+    }
+}
+
+/// Whether `content` names the skill as a whole word (case-insensitive).
+fn mentions(content: &str, skill: &str) -> bool {
+    Regex::new(&format!(
+        r"(?i)(^|[^\w-]){}($|[^\w-])",
+        regex::escape(skill)
+    ))
+    .is_ok_and(|re| re.is_match(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_skills_with_referencing_agents() {
+        let dir = std::env::temp_dir().join(format!("clautron-skills-{}", uuid::Uuid::new_v4()));
+        let skill = dir.join(".claude/skills/pdf");
+        std::fs::create_dir_all(skill.join("scripts")).unwrap();
+        std::fs::write(
+            skill.join("SKILL.md"),
+            "---\nname: pdf-tools\ndescription: Fill PDF forms\n---\nSteps",
+        )
+        .unwrap();
+        std::fs::write(skill.join("scripts/fill.py"), "").unwrap();
+        std::fs::create_dir_all(dir.join(".claude/skills/empty")).unwrap();
+
+        let agent = |name: &str, body: &str| AgentConfig {
+            name: name.into(),
+            description: String::new(),
+            model: "sonnet".into(),
+            color: "blue".into(),
+            icon: None,
+            tools: None,
+            pairs_with: Vec::new(),
+            file_path: String::new(),
+            body: body.into(),
+        };
+        let agents = vec![
+            agent("forms", "Use the PDF-Tools skill for forms."),
+            agent("other", "Nothing about pdf-tools-extra here."),
+        ];
+
+        let skills = list_skills(dir.to_str().unwrap(), &agents);
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].name, "pdf-tools");
+        assert_eq!(skills[0].description, "Fill PDF forms");
+        assert_eq!(skills[0].files, vec!["SKILL.md", "scripts/fill.py"]);
+        assert_eq!(skills[0].referenced_by, vec!["forms"]);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::domain::models::{SlashCommand, SlashCommandUpdate};
+use crate::services::agent_watcher;
+use serde_yaml::{Mapping, Value};
+use std::path::{Path, PathBuf};
+
+/// Frontmatter keys managed by the editor, in the order of `SlashCommand`.
+/// Other keys are preserved untouched.
+const KEYS: [&str; 4] = ["description", "argument-hint", "allowed-tools", "model"];
+
+pub fn commands_dir(project_dir: &str) -> PathBuf {
+    Path::new(project_dir).join(".claude/commands")
+}
+
+/// Every slash command in the project, sorted by name.
+pub fn list_commands(project_dir: &str) -> Result<Vec<SlashCommand>, String> {
+    let root = commands_dir(project_dir);
+    let mut commands: Vec<SlashCommand> = agent_watcher::collect_md_files(&root)
+        .into_iter()
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(&path).ok()?;
+            Some(parse_command(&command_name(&root, &path), &path, &content))
+        })
+        .collect();
+    commands.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(commands)
+}
+
+pub fn get_command(project_dir: &str, file_path: &str) -> Result<SlashCommand, String> {
+    let path = resolve(project_dir, file_path)?;
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    Ok(parse_command(
+        &command_name(&commands_dir(project_dir), &path),
+        &path,
+        &content,
+    ))
+}
+
+/// Create `.claude/commands/<name>.md`; `dir:name` creates it in a
+/// subdirectory. Refuses to overwrite an existing command.
+pub fn create_command(
+    project_dir: &str,
+    name: &str,
+    description: Option<String>,
+) -> Result<SlashCommand, String> {
+    let segments: Vec<&str> = name.trim().trim_start_matches('/').split(':').collect();
+    let valid = |s: &&str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    };
+    if !segments.iter().all(valid) {
+        return Err(format!(
+            "Invalid command name (letters, digits, - and _, with : for folders): {name}"
+        ));
+    }
+    let mut path = commands_dir(project_dir);
+    for segment in &segments {
+        path.push(segment);
+    }
+    path.set_extension("md");
+    if path.exists() {
+        return Err(format!("Command already exists: /{}", segments.join(":")));
+    }
+
+    let command = SlashCommand {
+        name: segments.join(":"),
+        file_path: path.to_string_lossy().to_string(),
+        description: description.filter(|d| !d.trim().is_empty()),
+        argument_hint: None,
+        allowed_tools: None,
+        model: None,
+        body: String::new(),
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, serialize_command(&command, None)).map_err(|e| e.to_string())?;
+    Ok(command)
+}
+
+pub fn update_command(
+    project_dir: &str,
+    file_path: &str,
+    update: SlashCommandUpdate,
+) -> Result<SlashCommand, String> {
+    let path = resolve(project_dir, file_path)?;
+    let original = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let mut command = parse_command(
+        &command_name(&commands_dir(project_dir), &path),
+        &path,
+        &original,
+    );
+    let set = |field: &mut Option<String>, value: Option<String>| {
+        if let Some(value) = value {
+            *field = Some(value.trim().to_string()).filter(|v| !v.is_empty());
+        }
+    };
+    set(&mut command.description, update.description);
+    set(&mut command.argument_hint, update.argument_hint);
+    set(&mut command.allowed_tools, update.allowed_tools);
+    set(&mut command.model, update.model);
+    if let Some(body) = update.body {
+        command.body = body;
+    }
+    std::fs::write(&path, serialize_command(&command, Some(&original)))
+        .map_err(|e| e.to_string())?;
+    Ok(command)
+}
+
+pub fn delete_command(project_dir: &str, file_path: &str) -> Result<(), String> {
+    let path = resolve(project_dir, file_path)?;
+    std::fs::remove_file(path).map_err(|e| e.to_string())
+}
+
+/// The file, if it's a markdown file inside the project's commands directory.
+fn resolve(project_dir: &str, file_path: &str) -> Result<PathBuf, String> {
+    let not_a_command = || format!("Not a slash command file: {file_path}");
+    let root = commands_dir(project_dir)
+        .canonicalize()
+        .map_err(|_| not_a_command())?;
+    let path = Path::new(file_path)
+        .canonicalize()
+        .map_err(|_| not_a_command())?;
+    if !path.starts_with(&root) || path.extension().and_then(|e| e.to_str()) != Some("md") {
+        return Err(not_a_command());
+    }
+    Ok(path)
+}
+
+fn command_name(root: &Path, path: &Path) -> String {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    path.strip_prefix(&root)
+        .or_else(|_| path.strip_prefix(root.as_path()))
+        .unwrap_or(path)
+        .with_extension("")
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn split_frontmatter(content: &str) -> (Mapping, &str) {
+    let Some(rest) = content.trim_start().strip_prefix("---") else {
+        return (Mapping::new(), content);
+    };
+    match rest.find("\n---") {
+        Some(end) => {
+            let body = &rest[end + 4..];
+            let fm = serde_yaml::from_str(&rest[..end]).unwrap_or_default();
+            (fm, body.strip_prefix('\n').unwrap_or(body))
+        }
+        None => (Mapping::new(), content),
+    }
+}
+
+fn parse_command(name: &str, path: &Path, content: &str) -> SlashCommand {
+    let (fm, body) = split_frontmatter(content);
+    let field = |key: &str| match fm.get(key) {
+        Some(Value::String(s)) => Some(s.clone()),
+        // `allowed-tools` may be written as a YAML list
+        Some(Value::Sequence(items)) => Some(
+            items
+                .iter()
+                .filter_map(|v| v.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        Some(Value::Number(n)) => Some(n.to_string()),
+        _ => None,
+    };
+    SlashCommand {
+        name: name.to_string(),
+        file_path: path.to_string_lossy().to_string(),
+        description: field("description"),
+        argument_hint: field("argument-hint"),
+        allowed_tools: field("allowed-tools"),
+        model: field("model"),
+        body: body.trim().to_string(),
+    }
+}
+
+/// Render a command file, keeping any frontmatter keys the editor doesn't
+/// manage from `original_content`.
+fn serialize_command(command: &SlashCommand, original_content: Option<&str>) -> String {
+    let mut fm = original_content
+        .map(|c| split_frontmatter(c).0)
+        .unwrap_or_default();
+    let values = [
+        &command.description,
+        &command.argument_hint,
+        &command.allowed_tools,
+        &command.model,
+    ];
+    for (key, value) in KEYS.iter().zip(values) {
+        match value {
+            Some(v) => {
+                fm.insert(Value::from(*key), Value::from(v.as_str()));
+            }
+            None => {
+                fm.remove(*key);
+            }
+        }
+    }
+
+    let mut out = String::new();
+    if !fm.is_empty() {
+        let yaml = serde_yaml::to_string(&fm).unwrap_or_default();
+        out.push_str("---\n");
+        out.push_str(yaml.trim());
+        out.push_str("\n---\n");
+        if !command.body.is_empty() {
+            out.push('\n');
+        }
+    }
+    if !command.body.is_empty() {
+        out.push_str(&command.body);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_keeps_unknown_keys_and_drops_cleared_fields() {
+        let original = "---\ndescription: Old\ndisable-model-invocation: true\nallowed-tools: [Read, Grep]\n---\n\nDo $ARGUMENTS\n";
+        let mut command = parse_command("review", Path::new("review.md"), original);
+        assert_eq!(command.allowed_tools.as_deref(), Some("Read, Grep"));
+        assert_eq!(command.body, "Do $ARGUMENTS");
+
+        command.description = None;
+        command.argument_hint = Some("[file]".into());
+        let out = serialize_command(&command, Some(original));
+        let reparsed = parse_command("review", Path::new("review.md"), &out);
+        assert!(out.contains("disable-model-invocation: true"));
+        assert_eq!(reparsed.description, None);
+        assert_eq!(reparsed.argument_hint.as_deref(), Some("[file]"));
+        assert_eq!(reparsed.body, "Do $ARGUMENTS");
+    }
+}
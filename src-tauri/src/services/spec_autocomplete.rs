@@ -0,0 +1,125 @@
+//! Auto-completion: specs waiting in review (the workflow's
+//! `on_agent_completed` status) move to `done` on their own once every
+//! acceptance criterion is checked or their linked branch is merged.
+
+use crate::domain::models::{Spec, SpecStatus, SpecUpdate};
+use crate::services::spec_manager::SpecManager;
+use crate::services::{git_service, github_issues};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{async_runtime, AppHandle, Emitter};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(120);
+
+/// Payload of `spec:status-changed`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecStatusChangedPayload {
+    pub file_path: String,
+    pub status: String,
+}
+
+/// Emit `spec:status-changed` for each spec.
+pub fn emit_status_changed(app: &AppHandle, specs: Vec<Spec>) {
+    for spec in specs {
+        let _ = app.emit(
+            "spec:status-changed",
+            SpecStatusChangedPayload {
+                file_path: spec.file_path,
+                status: spec.status.to_string(),
+            },
+        );
+    }
+}
+
+/// Whether the spec has acceptance criteria and all of them are checked.
+pub fn criteria_complete(spec: &Spec) -> bool {
+    !spec.acceptance_criteria.is_empty() && spec.acceptance_criteria.iter().all(|c| c.done)
+}
+
+/// Whether the spec's linked branch has been merged: its pull request if
+/// `gh` knows one, otherwise a merge commit on the default branch.
+async fn branch_merged(project_dir: &str, branch: &str) -> bool {
+    match github_issues::pull_request_state(project_dir, branch).await {
+        Ok(state) => state == "MERGED",
+        Err(_) => {
+            let (dir, branch) = (project_dir.to_string(), branch.to_string());
+            async_runtime::spawn_blocking(move || git_service::is_branch_merged(&dir, &branch))
+                .await
+                .unwrap_or(false)
+        }
+    }
+}
+
+/// Move `spec` to `done` if it is in review and complete, then roll up its
+/// parents and emit `spec:status-changed`. Returns whether it moved.
+pub async fn try_complete(app: &AppHandle, specs: &SpecManager, spec: &Spec) -> bool {
+    let workflow = specs.workflow().await;
+    if spec.status != workflow.on_agent_completed
+        || !workflow.allows(&spec.status, &SpecStatus::Done)
+    {
+        return false;
+    }
+    let complete = criteria_complete(spec)
+        || match (&spec.branch, specs.get_project_dir().await) {
+            (Some(branch), Some(dir)) => branch_merged(&dir, branch).await,
+            _ => false,
+        };
+    if !complete {
+        return false;
+    }
+
+    let update = SpecUpdate {
+        status: Some(SpecStatus::Done),
+        ..Default::default()
+    };
+    match specs.update_spec(&spec.file_path, update).await {
+        Ok(done) => {
+            let mut changed = specs.rollup_parents(&done).await;
+            changed.insert(0, done);
+            emit_status_changed(app, changed);
+            true
+        }
+        Err(e) => {
+            eprintln!("Failed to auto-complete {}: {e}", spec.file_path);
+            false
+        }
+    }
+}
+
+pub fn start_autocomplete_task(app: AppHandle, specs: Arc<SpecManager>) {
+    async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            let Ok(all) = specs.list_specs().await else {
+                continue;
+            };
+            let on_completed = specs.workflow().await.on_agent_completed;
+            for spec in all.iter().filter(|s| s.status == on_completed) {
+                try_complete(&app, &specs, spec).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::spec_parser::parse_spec;
+
+    #[test]
+    fn criteria_complete_requires_every_checked_criterion() {
+        let spec = |criteria: &str| {
+            parse_spec(
+                &format!("---\ntitle: t\nstatus: review\nacceptance_criteria: {criteria}\n---\n"),
+                "t.md",
+            )
+            .unwrap()
+        };
+        assert!(!criteria_complete(&spec("[]")));
+        assert!(!criteria_complete(&spec(
+            "[{text: a, done: true}, {text: b, done: false}]"
+        )));
+        assert!(criteria_complete(&spec("[{text: a, done: true}]")));
+    }
+}
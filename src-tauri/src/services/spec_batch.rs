@@ -0,0 +1,242 @@
+use crate::domain::models::{
+    FailurePolicy, Spec, StepStatus, Workflow, WorkflowEdge, WorkflowStatus, WorkflowStep,
+};
+use crate::domain::ports::WorkflowRepository;
+use crate::services::spec_manager::spec_matches_ref;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Canvas spacing between columns and between rows of steps.
+const STEP_SPACING_X: f64 = 300.0;
+const STEP_SPACING_Y: f64 = 150.0;
+
+/// Concurrent specs for a parallel batch when no limit is given.
+const DEFAULT_MAX_PARALLEL: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RunSpecsMode {
+    /// One spec at a time, in the given order.
+    Sequential,
+    /// Up to `max_parallel` specs at a time (default `DEFAULT_MAX_PARALLEL`).
+    Parallel {
+        #[serde(default)]
+        max_parallel: Option<usize>,
+    },
+}
+
+/// `depends_on` links between the specs of a batch, as (dependency,
+/// dependent) indexes into `specs`.
+pub fn batch_dependencies(specs: &[Spec]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    for (dependent, spec) in specs.iter().enumerate() {
+        for reference in &spec.depends_on {
+            if let Some(dependency) = specs
+                .iter()
+                .position(|s| spec_matches_ref(&s.file_path, reference))
+                .filter(|&i| i != dependent)
+            {
+                pairs.push((dependency, dependent));
+            }
+        }
+    }
+    pairs
+}
+
+/// Create an ad-hoc workflow with one spec-bound step per path, laid out in
+/// the given order. The workflow's `max_parallel` caps how many run at once
+/// (one when sequential), and the engine starts the next ready spec as a
+/// slot frees up. Each `dependencies` pair (see `batch_dependencies`) becomes
+/// an edge, so a spec waits for the ones it depends on, and is skipped if
+/// one of them fails. Other failures don't stop the batch.
+pub async fn create_batch_workflow(
+    repo: &dyn WorkflowRepository,
+    spec_paths: &[String],
+    dependencies: &[(usize, usize)],
+    agent_name: &str,
+    model: &str,
+    mode: &RunSpecsMode,
+) -> Result<Workflow, String> {
+    if spec_paths.is_empty() {
+        return Err("No specs selected".into());
+    }
+    let max_parallel = match mode {
+        RunSpecsMode::Sequential => 1,
+        RunSpecsMode::Parallel { max_parallel } => max_parallel
+            .unwrap_or(DEFAULT_MAX_PARALLEL)
+            .clamp(1, spec_paths.len()),
+    };
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let workflow = Workflow {
+        id: Uuid::new_v4().to_string(),
+        name: format!("Batch: {} specs", spec_paths.len()),
+        description: Some(format!(
+            "{} run of {} specs with {}",
+            match mode {
+                RunSpecsMode::Sequential => "Sequential".to_string(),
+                RunSpecsMode::Parallel { .. } => format!("Parallel (max {max_parallel})"),
+            },
+            spec_paths.len(),
+            agent_name
+        )),
+        status: WorkflowStatus::Draft,
+        created_at: now.clone(),
+        updated_at: now.clone(),
+        max_parallel: Some(max_parallel as u32),
+    };
+    repo.save_workflow(&workflow)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Row-major grid, so the engine's top-to-bottom start order is the
+    // given order.
+    let columns = max_parallel;
+    let mut step_ids = Vec::with_capacity(spec_paths.len());
+    for (i, spec_path) in spec_paths.iter().enumerate() {
+        let step = WorkflowStep {
+            id: Uuid::new_v4().to_string(),
+            workflow_id: workflow.id.clone(),
+            agent_name: agent_name.to_string(),
+            model: model.to_string(),
+            prompt: String::new(),
+            spec_path: Some(spec_path.clone()),
+            status: StepStatus::Pending,
+            session_id: None,
+            position_x: (i % columns) as f64 * STEP_SPACING_X,
+            position_y: (i / columns) as f64 * STEP_SPACING_Y,
+            created_at: now.clone(),
+            pass_context: false,
+            result_output: None,
+            failure_policy: FailurePolicy::SkipDescendants,
+            join_mode: Default::default(),
+            kind: Default::default(),
+            gate_command: None,
+            prompt_template_id: None,
+            template_vars: Default::default(),
+            commit_message: None,
+        };
+        repo.save_step(&step).await.map_err(|e| e.to_string())?;
+        step_ids.push(step.id);
+    }
+
+    for &(dependency, dependent) in dependencies {
+        let edge = WorkflowEdge {
+            id: Uuid::new_v4().to_string(),
+            workflow_id: workflow.id.clone(),
+            source_step_id: step_ids[dependency].clone(),
+            target_step_id: step_ids[dependent].clone(),
+            condition: Default::default(),
+        };
+        repo.save_edge(&edge).await.map_err(|e| e.to_string())?;
+    }
+
+    Ok(workflow)
+}
+
+/// Create an ad-hoc workflow for a spec's assignees: the first agent
+/// implements the spec, then each other agent reviews the previous one's
+/// work (receiving its output) and fixes what it finds. Every step is bound
+/// to the spec.
+pub async fn create_pair_workflow(
+    repo: &dyn WorkflowRepository,
+    spec_path: &str,
+    spec_title: &str,
+    agents: &[String],
+    model: &str,
+) -> Result<Workflow, String> {
+    let [implementer, reviewers @ ..] = agents else {
+        return Err("Spec has no assigned agents".into());
+    };
+    if reviewers.is_empty() {
+        return Err("A paired run needs at least two assigned agents".into());
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let workflow = Workflow {
+        id: Uuid::new_v4().to_string(),
+        name: format!("Pair: {spec_title}"),
+        description: Some(format!(
+            "{implementer} implements, reviewed by {}",
+            reviewers.join(", ")
+        )),
+        status: WorkflowStatus::Draft,
+        created_at: now.clone(),
+        updated_at: now.clone(),
+        max_parallel: None,
+    };
+    repo.save_workflow(&workflow)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut previous: Option<(String, &str)> = None;
+    for (i, agent) in agents.iter().enumerate() {
+        let prompt = match previous {
+            Some((_, author)) => format!(
+                "You are reviewing the changes {author} made for this spec. Check the work \
+                 against every acceptance criterion, fix any problems you find, and finish \
+                 with a short summary of what you checked and changed."
+            ),
+            None => String::new(),
+        };
+        let step = WorkflowStep {
+            id: Uuid::new_v4().to_string(),
+            workflow_id: workflow.id.clone(),
+            agent_name: agent.clone(),
+            model: model.to_string(),
+            prompt,
+            spec_path: Some(spec_path.to_string()),
+            status: StepStatus::Pending,
+            session_id: None,
+            position_x: 0.0,
+            position_y: i as f64 * STEP_SPACING_Y,
+            created_at: now.clone(),
+            pass_context: previous.is_some(),
+            result_output: None,
+            failure_policy: FailurePolicy::FailWorkflow,
+            join_mode: Default::default(),
+            kind: Default::default(),
+            gate_command: None,
+            prompt_template_id: None,
+            template_vars: Default::default(),
+            commit_message: None,
+        };
+        repo.save_step(&step).await.map_err(|e| e.to_string())?;
+
+        if let Some((source, _)) = previous.take() {
+            let edge = WorkflowEdge {
+                id: Uuid::new_v4().to_string(),
+                workflow_id: workflow.id.clone(),
+                source_step_id: source,
+                target_step_id: step.id.clone(),
+                condition: Default::default(),
+            };
+            repo.save_edge(&edge).await.map_err(|e| e.to_string())?;
+        }
+        previous = Some((step.id, agent.as_str()));
+    }
+
+    Ok(workflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::spec_parser;
+
+    fn spec(path: &str, depends_on: &[&str]) -> Spec {
+        let mut spec = spec_parser::parse_spec("---\ntitle: t\n---\n", path).unwrap();
+        spec.depends_on = depends_on.iter().map(|d| d.to_string()).collect();
+        spec
+    }
+
+    #[test]
+    fn dependencies_within_the_batch_become_pairs() {
+        let specs = [
+            spec("specs/api.md", &[]),
+            spec("specs/ui.md", &["api", "specs/elsewhere.md"]),
+            spec("specs/docs.md", &["specs/ui.md", "docs"]),
+        ];
+        assert_eq!(batch_dependencies(&specs), [(0, 1), (1, 2)]);
+    }
+}
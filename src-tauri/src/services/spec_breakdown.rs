@@ -0,0 +1,131 @@
+use crate::domain::models::{AgentConfig, Spec, SpecPriority};
+use serde::{Deserialize, Serialize};
+
+/// A child spec proposed by a breakdown, shown for confirmation before any
+/// file is written.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposedSpec {
+    pub title: String,
+    pub priority: SpecPriority,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub acceptance_criteria: Vec<String>,
+    #[serde(default)]
+    pub suggested_agent: Option<String>,
+}
+
+/// Loosely-typed shape of the model's answer; normalized into `ProposedSpec`.
+#[derive(Deserialize)]
+struct RawProposal {
+    title: String,
+    #[serde(default)]
+    priority: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    acceptance_criteria: Vec<String>,
+    #[serde(default)]
+    agent: Option<String>,
+}
+
+/// Prompt asking Claude to split a spec into independently runnable child specs.
+pub fn build_prompt(spec: &Spec, agents: &[AgentConfig]) -> String {
+    let mut prompt = format!(
+        "Break the following spec down into 2-8 smaller child specs. Each child \
+         must be independently implementable by a single coding agent in one \
+         session and have concrete, checkable acceptance criteria. Together the \
+         children must cover the whole spec.\n\n# {}\n\n{}\n",
+        spec.title, spec.body
+    );
+    if !spec.acceptance_criteria.is_empty() {
+        prompt.push_str("\n## Acceptance Criteria\n\n");
+        for criterion in &spec.acceptance_criteria {
+            prompt.push_str(&format!("- {}\n", criterion.text));
+        }
+    }
+    if !agents.is_empty() {
+        prompt.push_str("\n## Available agents\n\n");
+        for agent in agents {
+            prompt.push_str(&format!("- {}: {}\n", agent.name, agent.description));
+        }
+    }
+    prompt.push_str(
+        "\nRespond with only a JSON array, no prose, in this format:\n\
+         [{\"title\": \"...\", \"priority\": \"P0|P1|P2\", \"description\": \"...\", \
+         \"acceptance_criteria\": [\"...\"], \"agent\": \"name of the best-suited available agent or null\"}]",
+    );
+    prompt
+}
+
+/// Parse the model's answer. Tolerates surrounding prose and code fences;
+/// agents that don't exist in the project are dropped.
+pub fn parse_proposals(output: &str, agents: &[AgentConfig]) -> Result<Vec<ProposedSpec>, String> {
+    let (Some(start), Some(end)) = (output.find('['), output.rfind(']')) else {
+        return Err("Claude did not return a list of child specs".into());
+    };
+    if end < start {
+        return Err("Claude did not return a list of child specs".into());
+    }
+    let raw: Vec<RawProposal> = serde_json::from_str(&output[start..=end])
+        .map_err(|e| format!("Could not parse the breakdown: {e}"))?;
+
+    let proposals: Vec<ProposedSpec> = raw
+        .into_iter()
+        .filter(|p| !p.title.trim().is_empty())
+        .map(|p| ProposedSpec {
+            title: p.title.trim().to_string(),
+            priority: match p.priority.as_deref().map(str::to_uppercase).as_deref() {
+                Some("P0") => SpecPriority::P0,
+                Some("P2") => SpecPriority::P2,
+                _ => SpecPriority::P1,
+            },
+            body: p.description.unwrap_or_default().trim().to_string(),
+            acceptance_criteria: p
+                .acceptance_criteria
+                .into_iter()
+                .map(|c| c.trim().to_string())
+                .filter(|c| !c.is_empty())
+                .collect(),
+            suggested_agent: p
+                .agent
+                .filter(|name| agents.iter().any(|a| &a.name == name)),
+        })
+        .collect();
+
+    if proposals.is_empty() {
+        return Err("Claude returned an empty breakdown".into());
+    }
+    Ok(proposals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fenced_array_and_normalizes_fields() {
+        let agents = vec![AgentConfig {
+            name: "backend".into(),
+            description: "API work".into(),
+            model: "sonnet".into(),
+            color: "blue".into(),
+            icon: None,
+            tools: None,
+            pairs_with: Vec::new(),
+            file_path: "backend.md".into(),
+            body: String::new(),
+        }];
+        let output = "Here you go:\n```json\n[{\"title\": \" Add endpoint \", \"priority\": \"P0\", \"acceptance_criteria\": [\"returns 200\", \" \"], \"agent\": \"backend\"}, {\"title\": \"Docs\", \"agent\": \"writer\"}]\n```";
+        let proposals = parse_proposals(output, &agents).unwrap();
+        assert_eq!(proposals.len(), 2);
+        assert_eq!(proposals[0].title, "Add endpoint");
+        assert_eq!(proposals[0].priority, SpecPriority::P0);
+        assert_eq!(proposals[0].acceptance_criteria, vec!["returns 200"]);
+        assert_eq!(proposals[0].suggested_agent.as_deref(), Some("backend"));
+        assert_eq!(proposals[1].priority, SpecPriority::P1);
+        assert_eq!(proposals[1].suggested_agent, None);
+
+        assert!(parse_proposals("I can't do that", &agents).is_err());
+    }
+}
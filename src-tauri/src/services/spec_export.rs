@@ -0,0 +1,141 @@
+use crate::domain::models::Spec;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// One spec flattened for reporting.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecExportRow {
+    pub file_path: String,
+    pub title: String,
+    pub status: String,
+    pub priority: String,
+    pub epic: Option<String>,
+    pub labels: Vec<String>,
+    pub assigned_agent: Option<String>,
+    pub parent_spec: Option<String>,
+    pub estimate_points: Option<f64>,
+    pub due_date: Option<String>,
+    pub criteria_total: usize,
+    pub criteria_done: usize,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+const CSV_HEADER: &[&str] = &[
+    "file_path",
+    "title",
+    "status",
+    "priority",
+    "epic",
+    "labels",
+    "assigned_agent",
+    "parent_spec",
+    "estimate_points",
+    "due_date",
+    "criteria_total",
+    "criteria_done",
+    "created_at",
+    "updated_at",
+];
+
+impl From<&Spec> for SpecExportRow {
+    fn from(spec: &Spec) -> Self {
+        Self {
+            file_path: spec.file_path.clone(),
+            title: spec.title.clone(),
+            status: spec.status.to_string(),
+            priority: spec.priority.to_string(),
+            epic: spec.epic.clone(),
+            labels: spec.labels.clone(),
+            assigned_agent: spec.assigned_agent.clone(),
+            parent_spec: spec.parent_spec.clone(),
+            estimate_points: spec.estimate.as_ref().and_then(|e| e.points()),
+            due_date: spec.due_date.clone(),
+            criteria_total: spec.acceptance_criteria.len(),
+            criteria_done: spec.acceptance_criteria.iter().filter(|c| c.done).count(),
+            created_at: spec.created_at.clone(),
+            updated_at: spec.updated_at.clone(),
+        }
+    }
+}
+
+/// Render specs as CSV (one row per spec, labels joined with `;`) or as a
+/// JSON array of rows.
+pub fn export_specs(specs: &[Spec], format: ExportFormat) -> Result<String, String> {
+    let rows: Vec<SpecExportRow> = specs.iter().map(SpecExportRow::from).collect();
+    match format {
+        ExportFormat::Json => serde_json::to_string_pretty(&rows).map_err(|e| e.to_string()),
+        ExportFormat::Csv => {
+            let mut out = CSV_HEADER.join(",");
+            out.push_str("\r\n");
+            for row in &rows {
+                let opt = |v: &Option<String>| v.clone().unwrap_or_default();
+                let fields = [
+                    row.file_path.clone(),
+                    row.title.clone(),
+                    row.status.clone(),
+                    row.priority.clone(),
+                    opt(&row.epic),
+                    row.labels.join(";"),
+                    opt(&row.assigned_agent),
+                    opt(&row.parent_spec),
+                    row.estimate_points
+                        .map(|p| p.to_string())
+                        .unwrap_or_default(),
+                    opt(&row.due_date),
+                    row.criteria_total.to_string(),
+                    row.criteria_done.to_string(),
+                    row.created_at.clone(),
+                    row.updated_at.clone(),
+                ];
+                let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                out.push_str(&fields.join(","));
+                out.push_str("\r\n");
+            }
+            Ok(out)
+        }
+    }
+}
+
+/// Quote a CSV field when needed. Values starting with a formula character
+/// are prefixed with `'` so spreadsheets don't evaluate spec titles.
+pub(crate) fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::spec_parser::parse_spec;
+
+    #[test]
+    fn csv_escapes_fields_and_counts_criteria() {
+        let spec = parse_spec(
+            "---\ntitle: 'Fix \"login\", again'\nlabels: [auth, ui]\nestimate: M\nacceptance_criteria:\n  - text: a\n    done: true\n  - b\ncreated_at: c\nupdated_at: u\n---\n",
+            "specs/a.md",
+        )
+        .unwrap();
+        let csv = export_specs(&[spec], ExportFormat::Csv).unwrap();
+        let row = csv.lines().nth(1).unwrap();
+        assert_eq!(
+            row,
+            "specs/a.md,\"Fix \"\"login\"\", again\",draft,P1,,auth;ui,,,3,,2,1,c,u"
+        );
+        assert_eq!(csv_field("=HYPERLINK()"), "'=HYPERLINK()");
+    }
+}
@@ -1,6 +1,67 @@
-use crate::domain::models::{Spec, SpecPriority, SpecStatus, SpecUpdate};
-use crate::services::spec_parser;
+use crate::domain::models::{
+    AcceptanceCriterion, AgentSession, DiagnosticSeverity, ExternalIssue, ReviewDecision, Spec,
+    SpecConflict, SpecDiagnostic, SpecPriority, SpecReview, SpecSessionRecord, SpecStatBucket,
+    SpecStats, SpecStatus, SpecStructure, SpecUpdate, SpecVerification, VerificationStatus,
+};
+use crate::services::git_service::{self, FileRevision};
+use crate::services::spec_workflow::SpecWorkflow;
+use crate::services::{spec_parser, spec_verifier};
+use regex::Regex;
+use serde::Serialize;
 use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+static HTML_COMMENT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<!--.*?-->").unwrap());
+
+/// Max number of ancestors updated by a single roll-up.
+const MAX_ROLLUP_DEPTH: usize = 16;
+
+/// A committed version of a spec, with the fields most worth comparing
+/// across versions parsed out of it.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecRevision {
+    #[serde(flatten)]
+    pub revision: FileRevision,
+    /// `None` when this version didn't parse (or the commit deleted the file).
+    pub status: Option<SpecStatus>,
+    pub assigned_agent: Option<String>,
+    pub acceptance_criteria: Vec<AcceptanceCriterion>,
+}
+
+/// Why a spec write failed.
+#[derive(Debug)]
+pub enum SpecWriteError {
+    /// The file changed on disk since it was read.
+    Conflict(Box<SpecConflict>),
+    Other(String),
+}
+
+impl std::fmt::Display for SpecWriteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpecWriteError::Conflict(c) => {
+                write!(
+                    f,
+                    "Spec was modified on disk since it was read: {}",
+                    c.file_path
+                )
+            }
+            SpecWriteError::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<String> for SpecWriteError {
+    fn from(e: String) -> Self {
+        SpecWriteError::Other(e)
+    }
+}
+
+impl From<SpecWriteError> for String {
+    fn from(e: SpecWriteError) -> Self {
+        e.to_string()
+    }
+}
 
 /// Service for managing spec markdown files on disk.
 pub struct SpecManager {
@@ -26,7 +87,39 @@ impl SpecManager {
         Path::new(project_dir).join("specs")
     }
 
-    /// List all specs in the project's specs/ directory.
+    /// The project's status workflow, or the built-in one if its config is
+    /// missing or invalid.
+    pub async fn workflow(&self) -> SpecWorkflow {
+        let Some(project_dir) = self.get_project_dir().await else {
+            return SpecWorkflow::default();
+        };
+        SpecWorkflow::load(&project_dir).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            SpecWorkflow::default()
+        })
+    }
+
+    /// Fail unless the project's workflow lets this spec move to `to`.
+    /// Lifecycle hooks skip this check; user-initiated changes go through it.
+    pub async fn check_transition(&self, file_path: &str, to: &SpecStatus) -> Result<(), String> {
+        let spec = self.get_spec(file_path).await?;
+        if &spec.status == to {
+            return Ok(());
+        }
+        let workflow = self.workflow().await;
+        if !workflow.is_defined(to) {
+            return Err(format!("Unknown spec status: {to}"));
+        }
+        if !workflow.allows(&spec.status, to) {
+            return Err(format!(
+                "This project's workflow doesn't allow moving a spec from {} to {to}",
+                spec.status
+            ));
+        }
+        Ok(())
+    }
+
+    /// List all specs in the project's specs/ directory and its subfolders.
     pub async fn list_specs(&self) -> Result<Vec<Spec>, String> {
         let project_dir = self
             .project_dir
@@ -40,37 +133,145 @@ impl SpecManager {
             return Ok(vec![]);
         }
 
+        let mut files = Vec::new();
+        collect_spec_files(&specs_dir, &mut files);
+
         let mut specs = Vec::new();
-        let entries = std::fs::read_dir(&specs_dir).map_err(|e| e.to_string())?;
-
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.extension().and_then(|e| e.to_str()) == Some("md") {
-                if let Ok(content) = std::fs::read_to_string(&path) {
-                    let file_path = path.to_string_lossy().to_string();
-                    match spec_parser::parse_spec(&content, &file_path) {
-                        Ok(spec) => specs.push(spec),
-                        Err(e) => {
-                            eprintln!("Failed to parse spec {}: {}", file_path, e);
-                        }
-                    }
+        for path in files {
+            match read_spec(&path) {
+                Ok(mut spec) => {
+                    spec.epic = epic_of(&specs_dir, &path);
+                    specs.push(spec);
+                }
+                Err(e) => {
+                    eprintln!("Failed to parse spec {}: {}", path.display(), e);
                 }
             }
         }
 
-        // Sort by updated_at descending
-        specs.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        let computed: Vec<(Vec<String>, usize, usize)> = specs
+            .iter()
+            .map(|s| {
+                let children = children_of(s, &specs);
+                let done = children.iter().filter(|c| c.status == SpecStatus::Done).count();
+                (blockers_of(s, &specs), children.len(), done)
+            })
+            .collect();
+        for (spec, (blocked_by, child_count, children_done)) in specs.iter_mut().zip(computed) {
+            spec.blocked_by = blocked_by;
+            spec.child_count = child_count;
+            spec.children_done = children_done;
+        }
+
+        // Board order: status column, then rank (unranked last), then most recently updated
+        let workflow = self.workflow().await;
+        specs.sort_by(|a, b| {
+            workflow
+                .order(&a.status)
+                .cmp(&workflow.order(&b.status))
+                .then_with(|| match (a.rank, b.rank) {
+                    (Some(x), Some(y)) => x.cmp(&y),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Equal,
+                })
+                .then_with(|| b.updated_at.cmp(&a.updated_at))
+        });
         Ok(specs)
     }
 
+    /// Previous committed versions of a spec, newest first.
+    pub async fn history(
+        &self,
+        file_path: &str,
+        limit: usize,
+    ) -> Result<Vec<SpecRevision>, String> {
+        let project_dir = self
+            .get_project_dir()
+            .await
+            .ok_or("No project directory set")?;
+        let revisions = git_service::get_file_history(&project_dir, file_path, limit)?;
+        Ok(revisions
+            .into_iter()
+            .map(|revision| {
+                let parsed = spec_parser::parse_spec(&revision.content, file_path).ok();
+                SpecRevision {
+                    status: parsed.as_ref().map(|s| s.status.clone()),
+                    assigned_agent: parsed.as_ref().and_then(|s| s.assigned_agent.clone()),
+                    acceptance_criteria: parsed.map(|s| s.acceptance_criteria).unwrap_or_default(),
+                    revision,
+                }
+            })
+            .collect())
+    }
+
+    /// Check every spec file against the frontmatter schema, including the
+    /// ones `list_specs` skips because they don't parse.
+    pub async fn validate_specs(&self) -> Result<Vec<SpecDiagnostic>, String> {
+        let project_dir = self
+            .project_dir
+            .read()
+            .await
+            .clone()
+            .ok_or("No project directory set")?;
+
+        let mut files = Vec::new();
+        collect_spec_files(&Self::specs_dir(&project_dir), &mut files);
+        files.sort();
+        let workflow = self.workflow().await;
+
+        let mut diagnostics = Vec::new();
+        for path in files {
+            let file_path = path.to_string_lossy().to_string();
+            match std::fs::read_to_string(&path) {
+                Ok(content) => {
+                    diagnostics.extend(spec_parser::validate_spec(&content, &file_path, &workflow))
+                }
+                Err(e) => diagnostics.push(SpecDiagnostic {
+                    file_path,
+                    severity: DiagnosticSeverity::Error,
+                    field: None,
+                    message: format!("Failed to read file: {e}"),
+                }),
+            }
+        }
+        Ok(diagnostics)
+    }
+
+    /// Backlog size roll-up across all specs.
+    pub async fn stats(&self) -> Result<SpecStats, String> {
+        let specs = self.list_specs().await?;
+        Ok(compute_stats(&specs, &self.workflow().await))
+    }
+
+    /// Dependencies of a spec that aren't `done` yet (or don't exist).
+    pub async fn unmet_dependencies(&self, spec: &Spec) -> Result<Vec<String>, String> {
+        if spec.depends_on.is_empty() {
+            return Ok(vec![]);
+        }
+        let specs = self.list_specs().await?;
+        Ok(blockers_of(spec, &specs))
+    }
+
     /// Get a single spec by file path.
     pub async fn get_spec(&self, file_path: &str) -> Result<Spec, String> {
-        let content = std::fs::read_to_string(file_path).map_err(|e| e.to_string())?;
-        spec_parser::parse_spec(&content, file_path)
+        let path = Path::new(file_path);
+        let mut spec = read_spec(path)?;
+        if let Some(project_dir) = self.get_project_dir().await {
+            spec.epic = epic_of(&Self::specs_dir(&project_dir), path);
+        }
+        Ok(spec)
     }
 
-    /// Create a new spec file.
-    pub async fn create_spec(&self, title: String, priority: SpecPriority) -> Result<Spec, String> {
+    /// Create a new spec file, inside the `epic` folder of specs/ if given,
+    /// with its body pre-filled according to `structure`.
+    pub async fn create_spec(
+        &self,
+        title: String,
+        priority: SpecPriority,
+        epic: Option<String>,
+        structure: SpecStructure,
+    ) -> Result<Spec, String> {
         let project_dir = self
             .project_dir
             .read()
@@ -78,7 +279,14 @@ impl SpecManager {
             .clone()
             .ok_or("No project directory set")?;
 
-        let specs_dir = Self::specs_dir(&project_dir);
+        let epic = epic
+            .map(|e| validate_epic(&e))
+            .transpose()?
+            .filter(|e| !e.is_empty());
+        let specs_dir = match &epic {
+            Some(epic) => Self::specs_dir(&project_dir).join(epic),
+            None => Self::specs_dir(&project_dir),
+        };
         std::fs::create_dir_all(&specs_dir).map_err(|e| e.to_string())?;
 
         // Generate filename from title
@@ -94,39 +302,237 @@ impl SpecManager {
         };
 
         let now = chrono::Utc::now().to_rfc3339();
-        let spec = Spec {
+        let mut spec = Spec {
             title,
             priority,
             status: SpecStatus::Draft,
+            estimate: None,
+            labels: vec![],
             acceptance_criteria: vec![],
             assigned_agent: None,
             assigned_session_id: None,
+            assigned_agents: vec![],
             parent_spec: None,
+            suggested_agent: None,
+            depends_on: vec![],
+            rank: None,
+            due_date: None,
+            branch: None,
+            external_id: None,
+            external_url: None,
+            verification: None,
+            reviews: vec![],
+            sessions: vec![],
             created_at: now.clone(),
             updated_at: now,
             file_path: file_path.to_string_lossy().to_string(),
-            body: String::new(),
+            body: scaffold_body(structure),
+            blocked_by: vec![],
+            child_count: 0,
+            children_done: 0,
+            file_mtime: None,
+            epic,
         };
 
-        let content = spec_parser::serialize_spec(&spec);
-        std::fs::write(&file_path, &content).map_err(|e| e.to_string())?;
-
+        write_spec(&mut spec, None)?;
         Ok(spec)
     }
 
     /// Update an existing spec.
-    pub async fn update_spec(
+    pub async fn update_spec(&self, file_path: &str, update: SpecUpdate) -> Result<Spec, String> {
+        self.update_spec_checked(file_path, update)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Update a spec without clobbering concurrent edits: fails with a
+    /// conflict if the file changed since `update.expected_mtime` (when set)
+    /// or while the update was being applied.
+    pub async fn update_spec_checked(
         &self,
         file_path: &str,
         update: SpecUpdate,
-    ) -> Result<Spec, String> {
+    ) -> Result<Spec, SpecWriteError> {
         let current = self.get_spec(file_path).await?;
-        let updated = spec_parser::apply_update(&current, &update);
-        let content = spec_parser::serialize_spec(&updated);
-        std::fs::write(file_path, &content).map_err(|e| e.to_string())?;
+        let mut updated = spec_parser::apply_update(&current, &update);
+        let expected = update.expected_mtime.or(current.file_mtime);
+        if update.expected_mtime.is_some() && current.file_mtime != update.expected_mtime {
+            return Err(SpecWriteError::Conflict(Box::new(SpecConflict {
+                file_path: file_path.to_string(),
+                current,
+                attempted: updated,
+            })));
+        }
+        write_spec(&mut updated, expected)?;
         Ok(updated)
     }
 
+    /// List the direct children of a spec.
+    pub async fn list_children(&self, file_path: &str) -> Result<Vec<Spec>, String> {
+        let specs = self.list_specs().await?;
+        let Some(parent) = specs.iter().find(|s| s.file_path == file_path) else {
+            return Err(format!("Spec not found: {file_path}"));
+        };
+        Ok(children_of(parent, &specs).into_iter().cloned().collect())
+    }
+
+    /// Create a new spec as a child of an existing one.
+    pub async fn create_child_spec(
+        &self,
+        parent_path: &str,
+        title: String,
+        priority: SpecPriority,
+    ) -> Result<Spec, String> {
+        let parent = self.get_spec(parent_path).await?;
+        // Children live next to their parent
+        let child = self
+            .create_spec(title, priority, parent.epic.clone(), SpecStructure::Blank)
+            .await?;
+
+        // Reference the parent relative to the project so the link survives moves
+        let project_dir = self.get_project_dir().await.unwrap_or_default();
+        let parent_ref = Path::new(&parent.file_path)
+            .strip_prefix(&project_dir)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or(parent.file_path);
+
+        self.update_spec(
+            &child.file_path,
+            SpecUpdate {
+                parent_spec: Some(Some(parent_ref)),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Roll a spec's status up into its ancestors: a parent moves to
+    /// `in_progress` once any child has started, and to `review` once all
+    /// (non-rejected) children are done. Returns the parents that changed.
+    pub async fn rollup_parents(&self, spec: &Spec) -> Vec<Spec> {
+        let mut changed = Vec::new();
+        let mut current = spec.clone();
+        // Bounded walk up the tree, in case of a parent_spec cycle
+        for _ in 0..MAX_ROLLUP_DEPTH {
+            let Some(parent_ref) = current.parent_spec.clone() else {
+                break;
+            };
+            let Ok(specs) = self.list_specs().await else {
+                break;
+            };
+            let Some(parent) = specs
+                .iter()
+                .find(|s| spec_matches_ref(&s.file_path, &parent_ref))
+            else {
+                break;
+            };
+            let Some(status) = rollup_status(parent, &children_of(parent, &specs)) else {
+                break;
+            };
+            match self
+                .update_spec(
+                    &parent.file_path,
+                    SpecUpdate {
+                        status: Some(status),
+                        ..Default::default()
+                    },
+                )
+                .await
+            {
+                Ok(updated) => {
+                    changed.push(updated.clone());
+                    current = updated;
+                }
+                Err(e) => {
+                    eprintln!("Failed to roll up parent spec {}: {}", parent.file_path, e);
+                    break;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Move a spec to `index` within the `status` column (changing its status
+    /// if needed) and renumber that column's ranks. Only the moved spec gets a
+    /// new `updated_at`; neighbours just have their rank rewritten.
+    pub async fn reorder_spec(
+        &self,
+        file_path: &str,
+        status: SpecStatus,
+        index: usize,
+    ) -> Result<Spec, String> {
+        let specs = self.list_specs().await?;
+        let mut moved = specs
+            .iter()
+            .find(|s| s.file_path == file_path)
+            .cloned()
+            .ok_or_else(|| format!("Spec not found: {file_path}"))?;
+        if moved.status != status {
+            moved = self
+                .update_spec(
+                    file_path,
+                    SpecUpdate {
+                        status: Some(status.clone()),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+        }
+
+        let mut column: Vec<Spec> = specs
+            .into_iter()
+            .filter(|s| s.status == status && s.file_path != file_path)
+            .collect();
+        column.insert(index.min(column.len()), moved);
+
+        let mut result = None;
+        for (rank, mut spec) in column.into_iter().enumerate() {
+            let rank = Some(rank as u32);
+            if spec.rank != rank {
+                spec.rank = rank;
+                let expected = spec.file_mtime;
+                write_spec(&mut spec, expected)?;
+            }
+            if spec.file_path == file_path {
+                result = Some(spec);
+            }
+        }
+        result.ok_or_else(|| format!("Spec not found: {file_path}"))
+    }
+
+    /// Import an external issue as a new spec. If a spec with the same
+    /// `external_id` already exists it is returned unchanged, so re-importing
+    /// never clobbers local edits.
+    pub async fn import_issue(&self, issue: &ExternalIssue) -> Result<Spec, String> {
+        let specs = self.list_specs().await?;
+        if let Some(existing) = specs
+            .into_iter()
+            .find(|s| s.external_id.as_deref() == Some(issue.external_id.as_str()))
+        {
+            return Ok(existing);
+        }
+
+        let mut spec = self
+            .create_spec(
+                issue.title.clone(),
+                issue.priority.clone(),
+                None,
+                SpecStructure::Blank,
+            )
+            .await?;
+        spec.body = issue.body.trim().to_string();
+        spec.acceptance_criteria = checklist_items(&issue.body);
+        spec.labels = issue.labels.clone();
+        spec.external_id = Some(issue.external_id.clone());
+        spec.external_url = issue.url.clone();
+        if let Some(ref status) = issue.status {
+            spec.status = status.clone();
+        }
+        let expected = spec.file_mtime;
+        write_spec(&mut spec, expected)?;
+        Ok(spec)
+    }
+
     /// Delete a spec file.
     pub async fn delete_spec(&self, file_path: &str) -> Result<(), String> {
         std::fs::remove_file(file_path).map_err(|e| e.to_string())
@@ -138,35 +544,126 @@ impl SpecManager {
     pub fn build_prompt_from_spec(spec: &Spec) -> String {
         let mut prompt = String::new();
         prompt.push_str(&format!("# {}\n\n", spec.title));
-        prompt.push_str(&spec.body);
+        prompt.push_str(&strip_empty_sections(&spec.body));
 
         if !spec.acceptance_criteria.is_empty() {
             prompt.push_str("\n\n## Acceptance Criteria\n\n");
             for (i, criterion) in spec.acceptance_criteria.iter().enumerate() {
-                prompt.push_str(&format!("{}. {}\n", i + 1, criterion));
+                prompt.push_str(&format!("{}. {}\n", i + 1, criterion.text));
+            }
+        }
+
+        // A re-run after rejection should address the reviewer's feedback
+        if let Some(review) = spec.reviews.last() {
+            if review.decision == ReviewDecision::Rejected {
+                if let Some(comment) = &review.comment {
+                    prompt.push_str(&format!(
+                        "\n\n## Review Feedback\n\nA previous attempt was rejected by {}. Address this feedback:\n\n{}\n",
+                        review.reviewer, comment
+                    ));
+                }
             }
         }
 
         prompt
     }
 
-    /// Assign a spec to an agent and session.
+    /// Record a review decision: move the spec to `done` or `rejected` and
+    /// append the reviewer, time and comment to its review log.
+    pub async fn review_spec(
+        &self,
+        file_path: &str,
+        decision: ReviewDecision,
+        comment: Option<String>,
+    ) -> Result<Spec, String> {
+        let to = match decision {
+            ReviewDecision::Approved => SpecStatus::Done,
+            ReviewDecision::Rejected => SpecStatus::Rejected,
+        };
+        self.check_transition(file_path, &to).await?;
+        let mut spec = self.get_spec(file_path).await?;
+        let reviewer = match self.get_project_dir().await {
+            Some(dir) => git_service::get_user_name(&dir),
+            None => None,
+        }
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .unwrap_or_else(|| "reviewer".to_string());
+
+        let now = chrono::Utc::now().to_rfc3339();
+        spec.status = to;
+        spec.reviews.push(SpecReview {
+            decision,
+            reviewer,
+            at: now.clone(),
+            comment: comment
+                .map(|c| c.trim().to_string())
+                .filter(|c| !c.is_empty()),
+        });
+        spec.updated_at = now;
+        let expected = spec.file_mtime;
+        write_spec(&mut spec, expected)?;
+        Ok(spec)
+    }
+
+    /// Assign a spec to an agent and session, and add the run to its
+    /// session history.
     pub async fn assign_to_agent(
         &self,
         file_path: &str,
         agent_name: &str,
         session_id: &str,
     ) -> Result<Spec, String> {
-        self.update_spec(
-            file_path,
-            SpecUpdate {
-                status: Some(SpecStatus::Assigned),
-                assigned_agent: Some(Some(agent_name.to_string())),
-                assigned_session_id: Some(Some(session_id.to_string())),
-                ..Default::default()
-            },
-        )
-        .await
+        let mut spec = self.get_spec(file_path).await?;
+        let now = chrono::Utc::now().to_rfc3339();
+        spec.status = SpecStatus::Assigned;
+        spec.assigned_agent = Some(agent_name.to_string());
+        spec.assigned_session_id = Some(session_id.to_string());
+        if !spec.sessions.iter().any(|s| s.session_id == session_id) {
+            spec.sessions.push(SpecSessionRecord {
+                session_id: session_id.to_string(),
+                agent_name: agent_name.to_string(),
+                started_at: now.clone(),
+                ended_at: None,
+                outcome: None,
+                cost_usd: None,
+                input_tokens: None,
+                output_tokens: None,
+            });
+        }
+        spec.updated_at = now;
+        let expected = spec.file_mtime;
+        write_spec(&mut spec, expected)?;
+        Ok(spec)
+    }
+
+    /// Store the final status and usage of a finished run in the history of
+    /// the spec it was bound to. Does nothing for sessions not on any spec.
+    pub async fn record_session_end(&self, session: &AgentSession) -> Option<Spec> {
+        let specs = self.list_specs().await.ok()?;
+        let mut spec = specs
+            .into_iter()
+            .find(|s| s.sessions.iter().any(|r| r.session_id == session.id))?;
+        let record = spec
+            .sessions
+            .iter_mut()
+            .find(|r| r.session_id == session.id)?;
+        record.ended_at = Some(
+            session
+                .ended_at
+                .clone()
+                .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+        );
+        record.outcome = Some(session.status.clone());
+        record.cost_usd = Some(session.cost_usd);
+        record.input_tokens = Some(session.input_tokens);
+        record.output_tokens = Some(session.output_tokens);
+        let expected = spec.file_mtime;
+        if let Err(e) = write_spec(&mut spec, expected) {
+            eprintln!("Failed to record session for {}: {e}", spec.file_path);
+            return None;
+        }
+        Some(spec)
     }
 
     /// Called when agent starts running. Moves spec to in_progress.
@@ -190,15 +687,29 @@ impl SpecManager {
         None
     }
 
-    /// Called when agent completes. Moves spec to review.
-    pub async fn on_agent_completed(&self, session_id: &str) -> Option<Spec> {
+    /// Called when agent completes. Moves spec to review (or the workflow's
+    /// `on_agent_completed` status), unless another of `running_sessions` is
+    /// still working on the spec.
+    pub async fn on_agent_completed(
+        &self,
+        session_id: &str,
+        running_sessions: &[String],
+    ) -> Option<Spec> {
         if let Some(spec) = self.find_spec_by_session(session_id).await {
+            let others_running = spec
+                .sessions
+                .iter()
+                .any(|r| r.session_id != session_id && running_sessions.contains(&r.session_id));
+            if others_running {
+                return None;
+            }
             if spec.status == SpecStatus::InProgress || spec.status == SpecStatus::Assigned {
+                let next = self.workflow().await.on_agent_completed;
                 if let Ok(updated) = self
                     .update_spec(
                         &spec.file_path,
                         SpecUpdate {
-                            status: Some(SpecStatus::Review),
+                            status: Some(next),
                             ..Default::default()
                         },
                     )
@@ -211,15 +722,365 @@ impl SpecManager {
         None
     }
 
-    /// Find a spec that is bound to a given session ID.
+    // --- Verification ---
+
+    /// Record that a verification agent is checking this spec.
+    pub async fn start_verification(
+        &self,
+        file_path: &str,
+        session_id: &str,
+    ) -> Result<Spec, String> {
+        let mut spec = self.get_spec(file_path).await?;
+        spec.verification = Some(SpecVerification {
+            session_id: session_id.to_string(),
+            status: VerificationStatus::Running,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            finished_at: None,
+            summary: None,
+        });
+        spec.updated_at = chrono::Utc::now().to_rfc3339();
+        let expected = spec.file_mtime;
+        write_spec(&mut spec, expected)?;
+        Ok(spec)
+    }
+
+    /// Find the spec whose running verification uses this session.
+    pub async fn find_spec_by_verification(&self, session_id: &str) -> Option<Spec> {
+        let specs = self.list_specs().await.ok()?;
+        specs.into_iter().find(|s| {
+            s.verification.as_ref().is_some_and(|v| {
+                v.session_id == session_id && v.status == VerificationStatus::Running
+            })
+        })
+    }
+
+    /// Apply the verification agent's final output: check off passing
+    /// criteria and attach findings. `None` means the agent failed.
+    pub async fn finish_verification(
+        &self,
+        spec: &Spec,
+        output: Option<&str>,
+    ) -> Result<Spec, String> {
+        let mut spec = spec.clone();
+        let report = output.and_then(spec_verifier::parse_report);
+        let (status, summary) = match report {
+            Some(report) => spec_verifier::apply_report(&mut spec.acceptance_criteria, &report),
+            None if output.is_some() => (
+                VerificationStatus::Error,
+                "Verification agent did not return a readable report".to_string(),
+            ),
+            None => (VerificationStatus::Error, "Verification agent failed".to_string()),
+        };
+        let now = chrono::Utc::now().to_rfc3339();
+        if let Some(ref mut verification) = spec.verification {
+            verification.status = status;
+            verification.finished_at = Some(now.clone());
+            verification.summary = Some(summary);
+        }
+        spec.updated_at = now;
+        let expected = spec.file_mtime;
+        write_spec(&mut spec, expected)?;
+        Ok(spec)
+    }
+
+    /// Find a spec that is bound to a given session ID: its latest session,
+    /// or any run of it still in progress (e.g. one of several assignees).
     pub async fn find_spec_by_session(&self, session_id: &str) -> Option<Spec> {
         let specs = self.list_specs().await.ok()?;
-        specs
-            .into_iter()
-            .find(|s| s.assigned_session_id.as_deref() == Some(session_id))
+        specs.into_iter().find(|s| {
+            s.assigned_session_id.as_deref() == Some(session_id)
+                || s.sessions
+                    .iter()
+                    .any(|r| r.session_id == session_id && r.outcome.is_none())
+        })
+    }
+}
+
+/// Whether a spec file is the one a `depends_on`/`parent_spec` reference
+/// points to. References may be a full path, a path relative to the project
+/// (`specs/api.md`), a file name (`api.md`) or a slug (`api`).
+pub fn spec_matches_ref(file_path: &str, reference: &str) -> bool {
+    let reference = reference.trim();
+    if reference.is_empty() {
+        return false;
+    }
+    let path = Path::new(file_path);
+    file_path == reference
+        || path.ends_with(reference)
+        || path.file_stem().and_then(|s| s.to_str()) == Some(reference)
+}
+
+/// Map tracker labels to a spec priority (`p0`, `critical`, `priority: low`, ...).
+pub fn priority_from_labels(labels: &[String]) -> SpecPriority {
+    let labels: Vec<String> = labels.iter().map(|l| l.to_lowercase()).collect();
+    let has = |words: &[&str]| {
+        labels
+            .iter()
+            .any(|l| words.iter().any(|w| l == w || l.ends_with(&format!(": {w}"))))
+    };
+    if has(&["p0", "critical", "urgent", "blocker"]) {
+        SpecPriority::P0
+    } else if has(&["p2", "low", "minor"]) {
+        SpecPriority::P2
+    } else {
+        SpecPriority::P1
+    }
+}
+
+/// Markdown task-list items (`- [ ] ...`) become acceptance criteria.
+fn checklist_items(body: &str) -> Vec<AcceptanceCriterion> {
+    body.lines()
+        .filter_map(|line| {
+            let line = line.trim_start();
+            let rest = line.strip_prefix("- ").or_else(|| line.strip_prefix("* "))?;
+            let (item, done) = match rest.strip_prefix("[ ] ") {
+                Some(item) => (item, false),
+                None => (
+                    rest.strip_prefix("[x] ")
+                        .or_else(|| rest.strip_prefix("[X] "))?,
+                    true,
+                ),
+            };
+            let item = item.trim();
+            (!item.is_empty()).then(|| AcceptanceCriterion {
+                done,
+                ..AcceptanceCriterion::new(item)
+            })
+        })
+        .collect()
+}
+
+/// Sections of a `SpecStructure::Standard` body, with the hint shown under each.
+const STANDARD_SECTIONS: &[(&str, &str)] = &[
+    (
+        "Context",
+        "Why this is needed: background, links, current behavior.",
+    ),
+    ("Requirements", "What must be built or changed."),
+    ("Out of Scope", "What this spec deliberately leaves out."),
+    (
+        "Acceptance Criteria",
+        "Checkable criteria live in the sidebar; add notes on verifying them here.",
+    ),
+];
+
+fn scaffold_body(structure: SpecStructure) -> String {
+    match structure {
+        SpecStructure::Blank => String::new(),
+        SpecStructure::Standard => STANDARD_SECTIONS
+            .iter()
+            .map(|(heading, hint)| format!("## {heading}\n\n<!-- {hint} -->\n"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+/// Drop HTML comments and `##` sections left empty, so unfilled scaffold
+/// sections don't reach the agent's prompt.
+fn strip_empty_sections(body: &str) -> String {
+    let without_comments = HTML_COMMENT_RE.replace_all(body, "");
+    let mut out: Vec<String> = Vec::new();
+    let mut section: Vec<&str> = Vec::new();
+    let flush = |section: &mut Vec<&str>, out: &mut Vec<String>| {
+        let is_heading = section.first().is_some_and(|l| l.starts_with("## "));
+        let has_content = section.iter().skip(1).any(|l| !l.trim().is_empty());
+        if !section.is_empty() && (!is_heading || has_content) {
+            out.push(section.join("\n").trim().to_string());
+        }
+        section.clear();
+    };
+    for line in without_comments.lines() {
+        if line.starts_with("## ") {
+            flush(&mut section, &mut out);
+        }
+        section.push(line);
+    }
+    flush(&mut section, &mut out);
+    out.retain(|s| !s.is_empty());
+    out.join("\n\n")
+}
+
+/// Recursively collect `.md` files, skipping hidden files and folders.
+pub(crate) fn collect_spec_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            collect_spec_files(&path, out);
+        } else if path.extension().and_then(|e| e.to_str()) == Some("md") {
+            out.push(path);
+        }
+    }
+}
+
+/// Folder of a spec relative to specs/, with `/` separators.
+fn epic_of(specs_dir: &Path, path: &Path) -> Option<String> {
+    let folder = path.parent()?.strip_prefix(specs_dir).ok()?;
+    let parts: Vec<String> = folder
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// Normalize an epic folder name, rejecting anything that could escape
+/// specs/ (absolute paths, `..`, hidden folders).
+fn validate_epic(epic: &str) -> Result<String, String> {
+    let parts: Vec<&str> = epic
+        .split(['/', '\\'])
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+    if parts
+        .iter()
+        .any(|p| p.starts_with('.') || p.chars().any(|c| c.is_control() || c == ':'))
+    {
+        return Err(format!("Invalid spec folder: {epic}"));
+    }
+    Ok(parts.join("/"))
+}
+
+/// Modification time in ms since the epoch, if the file exists.
+fn file_mtime(path: &Path) -> Option<u64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let ms = modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_millis();
+    u64::try_from(ms).ok()
+}
+
+/// Read and parse a spec file, recording its mtime.
+fn read_spec(path: &Path) -> Result<Spec, String> {
+    let file_path = path.to_string_lossy();
+    let mtime = file_mtime(path);
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut spec = spec_parser::parse_spec(&content, &file_path)?;
+    spec.file_mtime = mtime;
+    Ok(spec)
+}
+
+/// Write a spec atomically: serialize to a temp file in the same directory,
+/// then rename it over the original, so readers never see a partial file.
+/// With `expected_mtime`, the write is abandoned with a conflict if the file
+/// changed since it was read (the check runs right before the rename).
+fn write_spec(spec: &mut Spec, expected_mtime: Option<u64>) -> Result<(), SpecWriteError> {
+    let path = PathBuf::from(&spec.file_path);
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let tmp = dir.join(format!(".{}.{}.tmp", file_name, uuid::Uuid::new_v4()));
+    std::fs::write(&tmp, spec_parser::serialize_spec(spec)).map_err(|e| e.to_string())?;
+
+    if expected_mtime.is_some() && file_mtime(&path) != expected_mtime {
+        let _ = std::fs::remove_file(&tmp);
+        let current = read_spec(&path)?;
+        return Err(SpecWriteError::Conflict(Box::new(SpecConflict {
+            file_path: spec.file_path.clone(),
+            current,
+            attempted: spec.clone(),
+        })));
+    }
+    if let Err(e) = std::fs::rename(&tmp, &path) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(SpecWriteError::Other(e.to_string()));
+    }
+    spec.file_mtime = file_mtime(&path);
+    Ok(())
+}
+
+fn compute_stats(specs: &[Spec], workflow: &SpecWorkflow) -> SpecStats {
+    let is_open = |s: &Spec| !matches!(s.status, SpecStatus::Done | SpecStatus::Rejected);
+    // Parents are sized by their children
+    let points = |s: &Spec| {
+        if s.child_count > 0 {
+            Some(0.0)
+        } else {
+            s.estimate.as_ref().and_then(|e| e.points())
+        }
+    };
+    let bucket = |key: String, matching: &[&Spec]| SpecStatBucket {
+        key,
+        count: matching.len(),
+        points: matching.iter().filter_map(|s| points(s)).sum(),
+    };
+
+    let open: Vec<&Spec> = specs.iter().filter(|s| is_open(s)).collect();
+    let by_priority = [SpecPriority::P0, SpecPriority::P1, SpecPriority::P2]
+        .into_iter()
+        .map(|p| {
+            let matching: Vec<&Spec> = open.iter().copied().filter(|s| s.priority == p).collect();
+            bucket(p.to_string().to_lowercase(), &matching)
+        })
+        .collect();
+    let by_status = workflow
+        .statuses
+        .iter()
+        .map(|st| {
+            let matching: Vec<&Spec> = specs.iter().filter(|s| s.status == st.id).collect();
+            bucket(st.id.to_string(), &matching)
+        })
+        .collect();
+
+    SpecStats {
+        total: specs.len(),
+        open: open.len(),
+        open_points: open.iter().filter_map(|s| points(s)).sum(),
+        unestimated: open.iter().filter(|s| points(s).is_none()).count(),
+        by_priority,
+        by_status,
     }
 }
 
+/// Direct children of `parent` among `all`.
+fn children_of<'a>(parent: &Spec, all: &'a [Spec]) -> Vec<&'a Spec> {
+    all.iter()
+        .filter(|s| {
+            s.parent_spec
+                .as_deref()
+                .is_some_and(|p| spec_matches_ref(&parent.file_path, p))
+        })
+        .collect()
+}
+
+/// The status a parent should move to given its children, if any.
+fn rollup_status(parent: &Spec, children: &[&Spec]) -> Option<SpecStatus> {
+    let active: Vec<&&Spec> = children
+        .iter()
+        .filter(|c| c.status != SpecStatus::Rejected)
+        .collect();
+    if active.is_empty() {
+        return None;
+    }
+    let all_done = active.iter().all(|c| c.status == SpecStatus::Done);
+    let any_started = active.iter().any(|c| c.status != SpecStatus::Draft);
+    match parent.status {
+        SpecStatus::Review | SpecStatus::Done | SpecStatus::Rejected => None,
+        _ if all_done => Some(SpecStatus::Review),
+        SpecStatus::Draft | SpecStatus::Assigned if any_started => Some(SpecStatus::InProgress),
+        _ => None,
+    }
+}
+
+/// Describe each of `spec`'s dependencies that isn't `done`.
+fn blockers_of(spec: &Spec, all: &[Spec]) -> Vec<String> {
+    spec.depends_on
+        .iter()
+        .filter_map(|dep| match all.iter().find(|s| spec_matches_ref(&s.file_path, dep)) {
+            Some(s) if s.status == SpecStatus::Done => None,
+            Some(s) => Some(format!("{} ({})", s.title, s.status)),
+            None => Some(format!("{dep} (not found)")),
+        })
+        .collect()
+}
+
 /// Convert a title to a URL-safe filename slug.
 fn slugify(title: &str) -> String {
     title
@@ -232,3 +1093,135 @@ fn slugify(title: &str) -> String {
         .collect::<Vec<&str>>()
         .join("-")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::SpecEstimate;
+
+    fn spec(priority: SpecPriority, status: SpecStatus, estimate: Option<SpecEstimate>) -> Spec {
+        let mut spec = spec_parser::parse_spec("---\ntitle: t\n---\n", "t.md").unwrap();
+        spec.priority = priority;
+        spec.status = status;
+        spec.estimate = estimate;
+        spec
+    }
+
+    #[test]
+    fn stats_roll_up_open_points() {
+        let mut parent = spec(
+            SpecPriority::P0,
+            SpecStatus::InProgress,
+            Some(SpecEstimate::Points(13.0)),
+        );
+        parent.child_count = 2;
+        let specs = vec![
+            parent,
+            spec(
+                SpecPriority::P0,
+                SpecStatus::Draft,
+                Some(SpecEstimate::Size("m".into())),
+            ),
+            spec(
+                SpecPriority::P1,
+                SpecStatus::InProgress,
+                Some(SpecEstimate::Points(2.0)),
+            ),
+            spec(SpecPriority::P1, SpecStatus::Draft, None),
+            spec(
+                SpecPriority::P2,
+                SpecStatus::Done,
+                Some(SpecEstimate::Points(5.0)),
+            ),
+        ];
+        let stats = compute_stats(&specs, &SpecWorkflow::default());
+        assert_eq!(stats.total, 5);
+        assert_eq!(stats.open, 4);
+        assert_eq!(stats.open_points, 5.0);
+        assert_eq!(stats.unestimated, 1);
+        assert_eq!(
+            stats.by_priority[0],
+            SpecStatBucket {
+                key: "p0".into(),
+                count: 2,
+                points: 3.0
+            }
+        );
+        assert_eq!(stats.by_priority[2].count, 0);
+        let done = stats.by_status.iter().find(|b| b.key == "done").unwrap();
+        assert_eq!((done.count, done.points), (1, 5.0));
+    }
+    #[test]
+    fn write_reports_conflict_when_file_changed_since_read() {
+        let dir = std::env::temp_dir().join(format!("clautron-spec-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("a.md");
+        std::fs::write(&path, "---\ntitle: original\n---\n").unwrap();
+
+        let mut mine = read_spec(&path).unwrap();
+        let stale = mine.file_mtime.map(|m| m - 1000);
+        mine.title = "mine".into();
+        match write_spec(&mut mine, stale) {
+            Err(SpecWriteError::Conflict(c)) => {
+                assert_eq!(c.current.title, "original");
+                assert_eq!(c.attempted.title, "mine");
+            }
+            _ => panic!("expected a conflict"),
+        }
+        assert_eq!(read_spec(&path).unwrap().title, "original");
+
+        let expected = read_spec(&path).unwrap().file_mtime;
+        write_spec(&mut mine, expected).unwrap();
+        assert_eq!(read_spec(&path).unwrap().title, "mine");
+        // No temp files left behind
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rerun_prompt_includes_latest_rejection_comment() {
+        let mut spec = spec(SpecPriority::P1, SpecStatus::Rejected, None);
+        let review = |decision, comment: &str| SpecReview {
+            decision,
+            reviewer: "ana".into(),
+            at: String::new(),
+            comment: Some(comment.into()),
+        };
+        spec.reviews = vec![review(ReviewDecision::Rejected, "Handle empty input")];
+        let prompt = SpecManager::build_prompt_from_spec(&spec);
+        assert!(prompt.contains("## Review Feedback"));
+        assert!(prompt.ends_with("Handle empty input\n"));
+
+        spec.reviews.push(review(ReviewDecision::Approved, "LGTM"));
+        assert!(!SpecManager::build_prompt_from_spec(&spec).contains("Review Feedback"));
+    }
+
+    #[test]
+    fn epics_follow_folders_under_specs() {
+        let specs_dir = Path::new("/p/specs");
+        assert_eq!(epic_of(specs_dir, Path::new("/p/specs/a.md")), None);
+        assert_eq!(
+            epic_of(specs_dir, Path::new("/p/specs/auth/login/a.md")).as_deref(),
+            Some("auth/login")
+        );
+        assert_eq!(validate_epic(" auth//login/ ").unwrap(), "auth/login");
+        assert!(validate_epic("../outside").is_err());
+        assert!(validate_epic("auth/.git").is_err());
+    }
+
+    #[test]
+    fn unfilled_scaffold_sections_are_left_out_of_prompts() {
+        let mut body = scaffold_body(SpecStructure::Standard);
+        assert!(body.starts_with("## Context\n\n<!--"));
+        assert_eq!(strip_empty_sections(&body), "");
+
+        body = body.replace(
+            "## Requirements\n",
+            "## Requirements\n\n- Rate limit by IP\n",
+        );
+        assert_eq!(
+            strip_empty_sections(&format!("Intro\n\n{body}")),
+            "Intro\n\n## Requirements\n\n- Rate limit by IP"
+        );
+    }
+}
@@ -1,4 +1,8 @@
-use crate::domain::models::{Spec, SpecPriority, SpecStatus, SpecUpdate};
+use crate::domain::models::{
+    AcceptanceCriterion, DiagnosticSeverity, Spec, SpecDiagnostic, SpecEstimate, SpecPriority,
+    SpecReview, SpecSessionRecord, SpecStatus, SpecUpdate, SpecVerification,
+};
+use crate::services::spec_workflow::SpecWorkflow;
 use serde::{Deserialize, Serialize};
 
 /// YAML frontmatter structure for spec files.
@@ -9,20 +13,72 @@ struct SpecFrontmatter {
     priority: String,
     #[serde(default = "default_status")]
     status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    estimate: Option<SpecEstimate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    labels: Vec<String>,
     #[serde(default)]
-    acceptance_criteria: Vec<String>,
+    acceptance_criteria: Vec<AcceptanceCriterion>,
     #[serde(default)]
     assigned_agent: Option<String>,
     #[serde(default)]
     assigned_session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    assigned_agents: Vec<String>,
     #[serde(default)]
     parent_spec: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    suggested_agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    depends_on: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rank: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    external_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    external_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    verification: Option<SpecVerification>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    reviews: Vec<SpecReview>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    sessions: Vec<SpecSessionRecord>,
     #[serde(default = "default_timestamp")]
     created_at: String,
     #[serde(default = "default_timestamp")]
     updated_at: String,
 }
 
+/// Every key `SpecFrontmatter` understands; keep in sync with the struct.
+const KNOWN_FIELDS: &[&str] = &[
+    "title",
+    "priority",
+    "status",
+    "estimate",
+    "labels",
+    "acceptance_criteria",
+    "assigned_agent",
+    "assigned_session_id",
+    "assigned_agents",
+    "parent_spec",
+    "suggested_agent",
+    "depends_on",
+    "rank",
+    "due_date",
+    "branch",
+    "external_id",
+    "external_url",
+    "verification",
+    "reviews",
+    "sessions",
+    "created_at",
+    "updated_at",
+];
+
 fn default_priority() -> String {
     "P1".to_string()
 }
@@ -43,19 +99,18 @@ fn parse_priority(s: &str) -> SpecPriority {
     }
 }
 
+/// Unknown ids are kept as custom statuses so they survive a save;
+/// `validate_spec` reports the ones the project's workflow doesn't define.
 fn parse_status(s: &str) -> SpecStatus {
-    match s.to_lowercase().as_str() {
-        "assigned" => SpecStatus::Assigned,
-        "in_progress" => SpecStatus::InProgress,
-        "review" => SpecStatus::Review,
-        "done" => SpecStatus::Done,
-        "rejected" => SpecStatus::Rejected,
-        _ => SpecStatus::Draft,
+    if s.trim().is_empty() {
+        SpecStatus::Draft
+    } else {
+        SpecStatus::parse(s)
     }
 }
 
-/// Parse a spec markdown file into a Spec struct.
-pub fn parse_spec(content: &str, file_path: &str) -> Result<Spec, String> {
+/// Split a spec file into its raw frontmatter and trimmed body.
+fn split_frontmatter(content: &str) -> Result<(&str, &str), String> {
     let content = content.trim();
     if !content.starts_with("---") {
         return Err("Spec file must start with YAML frontmatter (---)".into());
@@ -65,8 +120,13 @@ pub fn parse_spec(content: &str, file_path: &str) -> Result<Spec, String> {
     let end_idx = after_first
         .find("---")
         .ok_or("Missing closing --- for frontmatter")?;
-    let frontmatter_str = &after_first[..end_idx];
-    let body = after_first[end_idx + 3..].trim().to_string();
+    Ok((&after_first[..end_idx], after_first[end_idx + 3..].trim()))
+}
+
+/// Parse a spec markdown file into a Spec struct.
+pub fn parse_spec(content: &str, file_path: &str) -> Result<Spec, String> {
+    let (frontmatter_str, body) = split_frontmatter(content)?;
+    let body = body.to_string();
 
     let fm: SpecFrontmatter =
         serde_yaml::from_str(frontmatter_str).map_err(|e| format!("YAML parse error: {e}"))?;
@@ -75,27 +135,192 @@ pub fn parse_spec(content: &str, file_path: &str) -> Result<Spec, String> {
         title: fm.title,
         priority: parse_priority(&fm.priority),
         status: parse_status(&fm.status),
+        estimate: fm.estimate,
+        labels: fm.labels,
         acceptance_criteria: fm.acceptance_criteria,
         assigned_agent: fm.assigned_agent,
         assigned_session_id: fm.assigned_session_id,
+        assigned_agents: fm.assigned_agents,
         parent_spec: fm.parent_spec,
+        suggested_agent: fm.suggested_agent,
+        depends_on: fm.depends_on,
+        rank: fm.rank,
+        due_date: fm.due_date,
+        branch: fm.branch,
+        external_id: fm.external_id,
+        external_url: fm.external_url,
+        verification: fm.verification,
+        reviews: fm.reviews,
+        sessions: fm.sessions,
         created_at: fm.created_at,
         updated_at: fm.updated_at,
         file_path: file_path.to_string(),
         body,
+        blocked_by: vec![],
+        child_count: 0,
+        children_done: 0,
+        file_mtime: None,
+        epic: None,
     })
 }
 
+/// Check a spec file against the frontmatter schema and the project's
+/// status workflow. Unlike `parse_spec`, which falls back to defaults, this
+/// reports every problem it finds.
+pub fn validate_spec(
+    content: &str,
+    file_path: &str,
+    workflow: &SpecWorkflow,
+) -> Vec<SpecDiagnostic> {
+    let diagnostic = |severity, field: Option<&str>, message: String| SpecDiagnostic {
+        file_path: file_path.to_string(),
+        severity,
+        field: field.map(String::from),
+        message,
+    };
+
+    let frontmatter = match split_frontmatter(content) {
+        Ok((frontmatter, _)) => frontmatter,
+        Err(e) => return vec![diagnostic(DiagnosticSeverity::Error, None, e)],
+    };
+    let value: serde_yaml::Value = match serde_yaml::from_str(frontmatter) {
+        Ok(value) => value,
+        Err(e) => {
+            return vec![diagnostic(
+                DiagnosticSeverity::Error,
+                None,
+                format!("YAML parse error: {e}"),
+            )]
+        }
+    };
+    let Some(map) = value.as_mapping() else {
+        return vec![diagnostic(
+            DiagnosticSeverity::Error,
+            None,
+            "Frontmatter must be a mapping of fields".into(),
+        )];
+    };
+
+    let mut diagnostics = Vec::new();
+    let field_str = |name: &str| map.get(name).and_then(|v| v.as_str());
+
+    match map.get("title") {
+        None => diagnostics.push(diagnostic(
+            DiagnosticSeverity::Error,
+            Some("title"),
+            "Missing required field `title`".into(),
+        )),
+        Some(v) if v.as_str().is_some_and(|t| t.trim().is_empty()) => diagnostics.push(diagnostic(
+            DiagnosticSeverity::Warning,
+            Some("title"),
+            "Title is empty".into(),
+        )),
+        _ => {}
+    }
+    if let Some(status) = field_str("status") {
+        if !workflow.is_defined(&SpecStatus::parse(status)) {
+            let known: Vec<String> = workflow.statuses.iter().map(|s| s.id.to_string()).collect();
+            diagnostics.push(diagnostic(
+                DiagnosticSeverity::Warning,
+                Some("status"),
+                format!(
+                    "Status `{status}` is not defined in the project's workflow; expected one of: {}",
+                    known.join(", ")
+                ),
+            ));
+        }
+    }
+    if let Some(priority) = field_str("priority") {
+        if !matches!(priority.to_uppercase().as_str(), "P0" | "P1" | "P2") {
+            diagnostics.push(diagnostic(
+                DiagnosticSeverity::Warning,
+                Some("priority"),
+                format!("Invalid priority `{priority}` (treated as P1); expected P0, P1 or P2"),
+            ));
+        }
+    }
+    for field in ["created_at", "updated_at"] {
+        if let Some(date) = field_str(field) {
+            if chrono::DateTime::parse_from_rfc3339(date).is_err() {
+                diagnostics.push(diagnostic(
+                    DiagnosticSeverity::Warning,
+                    Some(field),
+                    format!("Malformed date `{date}`; expected RFC 3339 (2024-01-31T12:00:00Z)"),
+                ));
+            }
+        }
+    }
+    if let Some(due) = field_str("due_date") {
+        if parse_due_date(due).is_none() {
+            diagnostics.push(diagnostic(
+                DiagnosticSeverity::Warning,
+                Some("due_date"),
+                format!("Malformed due date `{due}`; expected YYYY-MM-DD or RFC 3339"),
+            ));
+        }
+    }
+    for key in map.keys() {
+        let name = key.as_str().unwrap_or_default();
+        if !KNOWN_FIELDS.contains(&name) {
+            diagnostics.push(diagnostic(
+                DiagnosticSeverity::Warning,
+                Some(name),
+                format!("Unknown field `{name}` is ignored and dropped on the next save"),
+            ));
+        }
+    }
+
+    // Type errors (labels not a list, rank not a number, ...). Skipped when
+    // the title is missing since serde would only repeat that.
+    if map.contains_key("title") {
+        if let Err(e) = serde_yaml::from_str::<SpecFrontmatter>(frontmatter) {
+            diagnostics.push(diagnostic(
+                DiagnosticSeverity::Error,
+                None,
+                format!("YAML parse error: {e}"),
+            ));
+        }
+    }
+    diagnostics
+}
+
+/// When a `due_date` value falls due: a bare date means the end of that day
+/// in local time.
+pub fn parse_due_date(value: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let value = value.trim();
+    if let Ok(at) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(at.with_timezone(&chrono::Utc));
+    }
+    let date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    date.and_hms_opt(23, 59, 59)?
+        .and_local_timezone(chrono::Local)
+        .earliest()
+        .map(|at| at.with_timezone(&chrono::Utc))
+}
+
 /// Serialize a Spec back to markdown with YAML frontmatter.
 pub fn serialize_spec(spec: &Spec) -> String {
     let fm = SpecFrontmatter {
         title: spec.title.clone(),
         priority: spec.priority.to_string(),
         status: spec.status.to_string(),
+        estimate: spec.estimate.clone(),
+        labels: spec.labels.clone(),
         acceptance_criteria: spec.acceptance_criteria.clone(),
         assigned_agent: spec.assigned_agent.clone(),
         assigned_session_id: spec.assigned_session_id.clone(),
+        assigned_agents: spec.assigned_agents.clone(),
         parent_spec: spec.parent_spec.clone(),
+        suggested_agent: spec.suggested_agent.clone(),
+        depends_on: spec.depends_on.clone(),
+        rank: spec.rank,
+        due_date: spec.due_date.clone(),
+        branch: spec.branch.clone(),
+        external_id: spec.external_id.clone(),
+        external_url: spec.external_url.clone(),
+        verification: spec.verification.clone(),
+        reviews: spec.reviews.clone(),
+        sessions: spec.sessions.clone(),
         created_at: spec.created_at.clone(),
         updated_at: spec.updated_at.clone(),
     };
@@ -128,6 +353,12 @@ pub fn apply_update(spec: &Spec, update: &SpecUpdate) -> Spec {
     if let Some(ref status) = update.status {
         updated.status = status.clone();
     }
+    if let Some(ref estimate) = update.estimate {
+        updated.estimate = estimate.clone();
+    }
+    if let Some(ref labels) = update.labels {
+        updated.labels = labels.clone();
+    }
     if let Some(ref criteria) = update.acceptance_criteria {
         updated.acceptance_criteria = criteria.clone();
     }
@@ -137,12 +368,70 @@ pub fn apply_update(spec: &Spec, update: &SpecUpdate) -> Spec {
     if let Some(ref session_id) = update.assigned_session_id {
         updated.assigned_session_id = session_id.clone();
     }
+    if let Some(ref agents) = update.assigned_agents {
+        updated.assigned_agents = agents
+            .iter()
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty())
+            .collect();
+    }
     if let Some(ref parent) = update.parent_spec {
         updated.parent_spec = parent.clone();
     }
+    if let Some(ref agent) = update.suggested_agent {
+        updated.suggested_agent = agent.clone();
+    }
+    if let Some(ref deps) = update.depends_on {
+        updated.depends_on = deps.clone();
+    }
+    if let Some(ref due) = update.due_date {
+        updated.due_date = due.clone();
+    }
+    if let Some(ref branch) = update.branch {
+        updated.branch = branch.clone();
+    }
     if let Some(ref body) = update.body {
         updated.body = body.clone();
     }
     updated.updated_at = chrono::Utc::now().to_rfc3339();
     updated
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(content: &str) -> Vec<(DiagnosticSeverity, Option<String>)> {
+        validate_spec(content, "a.md", &SpecWorkflow::default())
+            .into_iter()
+            .map(|d| (d.severity, d.field))
+            .collect()
+    }
+
+    #[test]
+    fn valid_spec_has_no_diagnostics() {
+        let spec = parse_spec("---\ntitle: A\nlabels: [x]\n---\nBody", "a.md").unwrap();
+        assert!(validate_spec(&serialize_spec(&spec), "a.md", &SpecWorkflow::default()).is_empty());
+    }
+
+    #[test]
+    fn reports_schema_problems() {
+        assert_eq!(
+            problems("---\nstatus: doing\ncreated_at: yesterday\nowner: me\n---\n"),
+            vec![
+                (DiagnosticSeverity::Error, Some("title".into())),
+                (DiagnosticSeverity::Warning, Some("status".into())),
+                (DiagnosticSeverity::Warning, Some("created_at".into())),
+                (DiagnosticSeverity::Warning, Some("owner".into())),
+            ]
+        );
+        assert_eq!(
+            problems("---\ntitle: A\nrank: first\n---\n"),
+            vec![(DiagnosticSeverity::Error, None)]
+        );
+        assert_eq!(
+            problems("no frontmatter"),
+            vec![(DiagnosticSeverity::Error, None)]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Due-date reminders: a background task that checks open specs every few
+//! minutes and emits `spec:due-soon` / `spec:overdue` once per spec when it
+//! enters that state (again after its due date changes).
+
+use crate::domain::models::{Spec, SpecStatus};
+use crate::services::config_store::AppConfig;
+use crate::services::spec_manager::SpecManager;
+use crate::services::spec_parser::parse_due_date;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{async_runtime, AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::RwLock;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(300);
+/// How far ahead a due date counts as "due soon".
+const DUE_SOON_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DueState {
+    DueSoon,
+    Overdue,
+}
+
+/// Payload of `spec:due-soon` and `spec:overdue`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecDueEvent {
+    pub file_path: String,
+    pub title: String,
+    pub due_date: String,
+    pub state: DueState,
+}
+
+/// Whether an open spec is due soon or overdue at `now`. Finished specs and
+/// specs without a (parseable) due date never are.
+pub fn due_state(spec: &Spec, now: DateTime<Utc>) -> Option<DueState> {
+    if matches!(spec.status, SpecStatus::Done | SpecStatus::Rejected) {
+        return None;
+    }
+    let due = parse_due_date(spec.due_date.as_deref()?)?;
+    if due <= now {
+        Some(DueState::Overdue)
+    } else if due - now <= chrono::Duration::hours(DUE_SOON_HOURS) {
+        Some(DueState::DueSoon)
+    } else {
+        None
+    }
+}
+
+pub fn start_reminder_task(
+    app: AppHandle,
+    specs: Arc<SpecManager>,
+    config: Arc<RwLock<AppConfig>>,
+) {
+    async_runtime::spawn(async move {
+        // file path -> (due date, state) already reported
+        let mut reported: HashMap<String, (String, DueState)> = HashMap::new();
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            let Ok(all) = specs.list_specs().await else {
+                continue;
+            };
+            let notify = config.read().await.notify_spec_due;
+            let now = Utc::now();
+
+            let mut still_due = HashMap::new();
+            for spec in &all {
+                let (Some(state), Some(due_date)) = (due_state(spec, now), &spec.due_date) else {
+                    continue;
+                };
+                let key = (due_date.clone(), state);
+                if reported.get(&spec.file_path) != Some(&key) {
+                    emit_due(&app, spec, due_date, state, notify);
+                }
+                still_due.insert(spec.file_path.clone(), key);
+            }
+            reported = still_due;
+        }
+    });
+}
+
+fn emit_due(app: &AppHandle, spec: &Spec, due_date: &str, state: DueState, notify: bool) {
+    let event = match state {
+        DueState::DueSoon => "spec:due-soon",
+        DueState::Overdue => "spec:overdue",
+    };
+    let _ = app.emit(
+        event,
+        SpecDueEvent {
+            file_path: spec.file_path.clone(),
+            title: spec.title.clone(),
+            due_date: due_date.to_string(),
+            state,
+        },
+    );
+    if notify {
+        let title = match state {
+            DueState::DueSoon => "Spec Due Soon",
+            DueState::Overdue => "Spec Overdue",
+        };
+        let _ = app
+            .notification()
+            .builder()
+            .title(title)
+            .body(format!("{} (due {due_date})", spec.title))
+            .show();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::spec_parser::parse_spec;
+
+    #[test]
+    fn classifies_open_specs_by_due_date() {
+        let now = DateTime::parse_from_rfc3339("2024-05-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let spec = |status: &str, due: &str| {
+            parse_spec(
+                &format!("---\ntitle: t\nstatus: {status}\ndue_date: \"{due}\"\n---\n"),
+                "t.md",
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            due_state(&spec("draft", "2024-05-10T11:00:00Z"), now),
+            Some(DueState::Overdue)
+        );
+        assert_eq!(
+            due_state(&spec("in_progress", "2024-05-11T06:00:00Z"), now),
+            Some(DueState::DueSoon)
+        );
+        assert_eq!(due_state(&spec("draft", "2024-06-01"), now), None);
+        assert_eq!(due_state(&spec("done", "2024-05-01"), now), None);
+        assert_eq!(due_state(&spec("draft", "next week"), now), None);
+    }
+}
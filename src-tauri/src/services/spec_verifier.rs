@@ -0,0 +1,128 @@
+use crate::domain::models::{AcceptanceCriterion, Spec, VerificationStatus};
+use regex::Regex;
+use serde::Deserialize;
+use std::sync::LazyLock;
+
+/// Fenced code blocks, optionally tagged `json`.
+static FENCED_JSON_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)```(?:json)?\s*(\{.*?\})\s*```").unwrap());
+
+/// The JSON block a verification agent ends its answer with.
+#[derive(Debug, Deserialize)]
+pub struct VerificationReport {
+    pub criteria: Vec<CriterionResult>,
+    #[serde(default)]
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CriterionResult {
+    /// 1-based, matching the numbering in the prompt.
+    pub index: usize,
+    pub pass: bool,
+    #[serde(default)]
+    pub finding: Option<String>,
+}
+
+/// Prompt for an agent that checks the current project against each
+/// acceptance criterion without changing anything.
+pub fn build_prompt(spec: &Spec) -> String {
+    let mut prompt = format!(
+        "# Verify: {}\n\n\
+         Check whether the current state of the project meets each acceptance \
+         criterion of the spec below by reading the code. This session is read-only: \
+         it can't run commands or modify files.\n\n\
+         ## Spec\n\n{}\n\n## Acceptance Criteria\n\n",
+        spec.title, spec.body
+    );
+    for (i, criterion) in spec.acceptance_criteria.iter().enumerate() {
+        prompt.push_str(&format!("{}. {}\n", i + 1, criterion.text));
+    }
+    prompt.push_str(
+        "\nEnd your answer with a single fenced JSON block in exactly this format, \
+         with one entry per criterion:\n\n\
+         ```json\n\
+         {\"criteria\": [{\"index\": 1, \"pass\": true, \"finding\": \"what you checked and found\"}], \
+         \"summary\": \"one-sentence overall result\"}\n\
+         ```\n",
+    );
+    prompt
+}
+
+/// Find the last fenced JSON block in the agent's output that parses as a report.
+pub fn parse_report(output: &str) -> Option<VerificationReport> {
+    FENCED_JSON_RE
+        .captures_iter(output)
+        .filter_map(|cap| serde_json::from_str(&cap[1]).ok())
+        .last()
+}
+
+/// Check off passing criteria, uncheck failing ones and attach findings.
+/// Criteria missing from the report keep their state but fail the run.
+pub fn apply_report(
+    criteria: &mut [AcceptanceCriterion],
+    report: &VerificationReport,
+) -> (VerificationStatus, String) {
+    let mut reported = vec![false; criteria.len()];
+    for result in &report.criteria {
+        let Some(criterion) = result
+            .index
+            .checked_sub(1)
+            .and_then(|i| criteria.get_mut(i))
+        else {
+            continue;
+        };
+        criterion.done = result.pass;
+        criterion.finding = result.finding.clone().filter(|f| !f.trim().is_empty());
+        reported[result.index - 1] = true;
+    }
+    for (criterion, _) in criteria.iter_mut().zip(&reported).filter(|(_, r)| !**r) {
+        criterion.finding = Some("Not covered by the verification report".into());
+    }
+
+    let passed = criteria
+        .iter()
+        .zip(&reported)
+        .filter(|(c, r)| **r && c.done)
+        .count();
+    let status = if passed == criteria.len() {
+        VerificationStatus::Passed
+    } else {
+        VerificationStatus::Failed
+    };
+    let mut summary = format!("{}/{} criteria passed", passed, criteria.len());
+    if let Some(ref text) = report.summary {
+        summary.push_str(": ");
+        summary.push_str(text.trim());
+    }
+    (status, summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_last_report_and_applies_it() {
+        let output = "Example:\n```json\n{\"criteria\": []}\n```\nResults:\n```json\n{\"criteria\": [{\"index\": 1, \"pass\": true, \"finding\": \"tests pass\"}, {\"index\": 9, \"pass\": true}], \"summary\": \"mostly done\"}\n```";
+        let report = parse_report(output).unwrap();
+        assert_eq!(report.criteria.len(), 2);
+
+        let mut criteria = vec![
+            AcceptanceCriterion::new("tests pass"),
+            AcceptanceCriterion {
+                done: true,
+                ..AcceptanceCriterion::new("docs updated")
+            },
+        ];
+        let (status, summary) = apply_report(&mut criteria, &report);
+        assert_eq!(status, VerificationStatus::Failed);
+        assert_eq!(summary, "1/2 criteria passed: mostly done");
+        assert!(criteria[0].done);
+        assert_eq!(criteria[0].finding.as_deref(), Some("tests pass"));
+        assert!(criteria[1].done);
+        assert!(criteria[1].finding.is_some());
+
+        assert!(parse_report("no report here").is_none());
+    }
+}
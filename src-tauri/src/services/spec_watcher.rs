@@ -1,15 +1,56 @@
+use crate::domain::models::{Spec, SpecStatus};
+use crate::services::spec_manager::collect_spec_files;
+use crate::services::spec_parser;
 use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
-use std::path::PathBuf;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tauri::{AppHandle, Emitter};
 
+/// Spec fields not stored in the file, or bumped on every write; never
+/// reported as changed.
+const IGNORED_FIELDS: &[&str] = &[
+    "updated_at",
+    "file_path",
+    "blocked_by",
+    "child_count",
+    "children_done",
+    "file_mtime",
+    "epic",
+];
+
 /// Event emitted when spec files change on disk.
-#[derive(Clone, serde::Serialize)]
+#[derive(Clone, Serialize)]
 pub struct SpecsChangedEvent {
     pub changed_files: Vec<String>,
+    /// What changed in each spec, compared with the last version seen.
+    pub changes: Vec<SpecFileChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecChangeKind {
+    Created,
+    Modified,
+    Deleted,
+    /// The file exists but can't be parsed as a spec.
+    Invalid,
 }
 
-/// Start watching specs/ directory for changes.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecFileChange {
+    pub file_path: String,
+    pub kind: SpecChangeKind,
+    /// Current title, or the last known one for deleted and invalid files.
+    pub title: Option<String>,
+    pub old_status: Option<SpecStatus>,
+    pub new_status: Option<SpecStatus>,
+    /// Frontmatter fields (and `body`) that differ from the previous version.
+    pub changed_fields: Vec<String>,
+}
+
+/// Start watching specs/ directory (including epic subfolders) for changes.
 /// Debounced at 500ms. Emits `specs:changed` on file changes.
 pub fn start_watching(
     app: AppHandle,
@@ -23,21 +64,36 @@ pub fn start_watching(
     let app_handle = app.clone();
     let watch_dir = specs_dir.clone();
 
+    // Last parsed version of every spec, to describe what each change did
+    let mut known: HashMap<PathBuf, Spec> = {
+        let mut files = Vec::new();
+        collect_spec_files(&specs_dir, &mut files);
+        files
+            .into_iter()
+            .filter_map(|path| Some((path.clone(), read(&path).ok()?)))
+            .collect()
+    };
+
     let mut debouncer = new_debouncer(
         Duration::from_millis(500),
         move |events: Result<Vec<notify_debouncer_mini::DebouncedEvent>, notify::Error>| {
             if let Ok(events) = events {
-                let changed: Vec<String> = events
+                let mut paths: Vec<&PathBuf> = events
                     .iter()
                     .filter(|e| e.kind == DebouncedEventKind::Any)
-                    .filter_map(|e| {
-                        let path = &e.path;
-                        if path.extension().and_then(|ext| ext.to_str()) == Some("md") {
-                            Some(path.to_string_lossy().to_string())
-                        } else {
-                            None
-                        }
-                    })
+                    .map(|e| &e.path)
+                    .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("md"))
+                    .collect();
+                paths.sort();
+                paths.dedup();
+
+                let changed: Vec<String> = paths
+                    .iter()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .collect();
+                let changes: Vec<SpecFileChange> = paths
+                    .into_iter()
+                    .filter_map(|path| describe_change(&mut known, path))
                     .collect();
 
                 if !changed.is_empty() {
@@ -45,6 +101,7 @@ pub fn start_watching(
                         "specs:changed",
                         SpecsChangedEvent {
                             changed_files: changed,
+                            changes,
                         },
                     );
                 }
@@ -55,8 +112,100 @@ pub fn start_watching(
 
     debouncer
         .watcher()
-        .watch(&watch_dir, notify::RecursiveMode::NonRecursive)
+        .watch(&watch_dir, notify::RecursiveMode::Recursive)
         .ok()?;
 
     Some(debouncer)
 }
+
+fn read(path: &Path) -> Result<Spec, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    spec_parser::parse_spec(&content, &path.to_string_lossy())
+}
+
+/// Compare the file on disk with its last known version and update the
+/// snapshot. `None` if nothing visible changed (e.g. only `updated_at`).
+fn describe_change(known: &mut HashMap<PathBuf, Spec>, path: &Path) -> Option<SpecFileChange> {
+    let file_path = path.to_string_lossy().to_string();
+    let previous = known.remove(path);
+    let exists = path.exists();
+    let current = exists.then(|| read(path));
+
+    let (kind, title, changed_fields) = match (&previous, &current) {
+        (None, None) => return None,
+        (Some(old), None) => (SpecChangeKind::Deleted, Some(old.title.clone()), vec![]),
+        (old, Some(Err(_))) => (
+            SpecChangeKind::Invalid,
+            old.as_ref().map(|s| s.title.clone()),
+            vec![],
+        ),
+        (None, Some(Ok(new))) => (SpecChangeKind::Created, Some(new.title.clone()), vec![]),
+        (Some(old), Some(Ok(new))) => {
+            let fields = changed_fields(old, new);
+            if fields.is_empty() {
+                known.insert(path.to_path_buf(), new.clone());
+                return None;
+            }
+            (SpecChangeKind::Modified, Some(new.title.clone()), fields)
+        }
+    };
+
+    let new_status = match &current {
+        Some(Ok(spec)) => Some(spec.status.clone()),
+        _ => None,
+    };
+    let change = SpecFileChange {
+        file_path,
+        kind,
+        title,
+        old_status: previous.map(|s| s.status),
+        new_status,
+        changed_fields,
+    };
+    if let Some(Ok(spec)) = current {
+        known.insert(path.to_path_buf(), spec);
+    }
+    Some(change)
+}
+
+/// Names of the stored fields that differ between two versions of a spec.
+fn changed_fields(old: &Spec, new: &Spec) -> Vec<String> {
+    let (Ok(serde_json::Value::Object(old)), Ok(serde_json::Value::Object(new))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    else {
+        return vec![];
+    };
+    let mut fields: Vec<String> = old
+        .keys()
+        .chain(new.keys().filter(|k| !old.contains_key(*k)))
+        .filter(|k| !IGNORED_FIELDS.contains(&k.as_str()))
+        .filter(|k| old.get(*k) != new.get(*k))
+        .cloned()
+        .collect();
+    fields.sort();
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::spec_parser::parse_spec;
+
+    #[test]
+    fn changed_fields_skips_timestamps_and_computed_fields() {
+        let spec = |fields: &str| {
+            parse_spec(
+                &format!(
+                    "---\ntitle: Login\ncreated_at: \"2024-01-01T00:00:00Z\"\n{fields}\n---\nBody"
+                ),
+                "a.md",
+            )
+            .unwrap()
+        };
+        let old = spec("status: in_progress\nupdated_at: \"2024-01-01T00:00:00Z\"");
+        let mut new = spec("status: review\nlabels: [auth]\nupdated_at: \"2024-01-02T00:00:00Z\"");
+        new.child_count = 3;
+        assert_eq!(changed_fields(&old, &new), vec!["labels", "status"]);
+        assert!(changed_fields(&old, &old).is_empty());
+    }
+}
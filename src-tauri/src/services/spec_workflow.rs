@@ -0,0 +1,153 @@
+//! Project-defined spec statuses and transitions, read from
+//! `.clautron/spec-workflow.json` in the project:
+//!
+//! ```json
+//! {
+//!   "statuses": [{ "id": "blocked", "label": "Blocked", "color": "#f97316" }, { "id": "qa" }],
+//!   "transitions": { "review": ["qa", "rejected"], "qa": ["done", "rejected"] },
+//!   "on_agent_completed": "qa"
+//! }
+//! ```
+//!
+//! `statuses` lists the board columns in order. Built-in statuses left out of
+//! the list are appended, since the agent lifecycle relies on them. A status
+//! with an entry in `transitions` may only move to the listed statuses;
+//! statuses without one may move anywhere.
+
+use crate::domain::models::SpecStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+const WORKFLOW_FILE: &str = ".clautron/spec-workflow.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusDefinition {
+    pub id: SpecStatus,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct WorkflowFile {
+    #[serde(default)]
+    statuses: Vec<StatusDefinition>,
+    #[serde(default)]
+    transitions: HashMap<String, Vec<SpecStatus>>,
+    #[serde(default)]
+    on_agent_completed: Option<SpecStatus>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecWorkflow {
+    /// Every valid status in board order.
+    pub statuses: Vec<StatusDefinition>,
+    /// Allowed next statuses by status id.
+    pub transitions: HashMap<String, Vec<SpecStatus>>,
+    /// Where a spec goes when its agent finishes (`review` by default).
+    pub on_agent_completed: SpecStatus,
+}
+
+impl Default for SpecWorkflow {
+    fn default() -> Self {
+        Self::from_file(WorkflowFile::default())
+    }
+}
+
+impl SpecWorkflow {
+    /// Load the project's workflow; the built-in one when there's no file.
+    pub fn load(project_dir: &str) -> Result<Self, String> {
+        let path = Path::new(project_dir).join(WORKFLOW_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let file: WorkflowFile =
+            serde_json::from_str(&content).map_err(|e| format!("Invalid {WORKFLOW_FILE}: {e}"))?;
+        let workflow = Self::from_file(file);
+        if !workflow.is_defined(&workflow.on_agent_completed) {
+            return Err(format!(
+                "Invalid {WORKFLOW_FILE}: on_agent_completed status `{}` is not defined",
+                workflow.on_agent_completed
+            ));
+        }
+        Ok(workflow)
+    }
+
+    fn from_file(file: WorkflowFile) -> Self {
+        let mut statuses: Vec<StatusDefinition> = Vec::new();
+        for definition in file.statuses {
+            if !statuses.iter().any(|s| s.id == definition.id) {
+                statuses.push(definition);
+            }
+        }
+        for id in SpecStatus::BUILT_IN {
+            if !statuses.iter().any(|s| s.id == id) {
+                statuses.push(StatusDefinition {
+                    id,
+                    label: None,
+                    color: None,
+                });
+            }
+        }
+        Self {
+            statuses,
+            transitions: file
+                .transitions
+                .into_iter()
+                .map(|(from, to)| (from.trim().to_lowercase(), to))
+                .collect(),
+            on_agent_completed: file.on_agent_completed.unwrap_or(SpecStatus::Review),
+        }
+    }
+
+    pub fn is_defined(&self, status: &SpecStatus) -> bool {
+        self.statuses.iter().any(|s| &s.id == status)
+    }
+
+    /// Whether a spec may move from `from` to `to`.
+    pub fn allows(&self, from: &SpecStatus, to: &SpecStatus) -> bool {
+        from == to
+            || self
+                .transitions
+                .get(&from.to_string())
+                .is_none_or(|allowed| allowed.contains(to))
+    }
+
+    /// Board column index; undefined statuses sort last.
+    pub fn order(&self, status: &SpecStatus) -> usize {
+        self.statuses
+            .iter()
+            .position(|s| &s.id == status)
+            .unwrap_or(self.statuses.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_statuses_and_transitions() {
+        let file: WorkflowFile = serde_json::from_str(
+            r#"{"statuses": [{"id": "draft"}, {"id": "blocked"}, {"id": "qa"}],
+                "transitions": {"review": ["qa", "rejected"]},
+                "on_agent_completed": "qa"}"#,
+        )
+        .unwrap();
+        let workflow = SpecWorkflow::from_file(file);
+        let qa = SpecStatus::Custom("qa".into());
+        assert_eq!(workflow.order(&SpecStatus::Draft), 0);
+        assert_eq!(workflow.order(&qa), 2);
+        // Built-ins left out are appended
+        assert_eq!(workflow.statuses.len(), 8);
+        assert!(!workflow.is_defined(&SpecStatus::Custom("typo".into())));
+        assert_eq!(workflow.on_agent_completed, qa);
+
+        assert!(workflow.allows(&SpecStatus::Review, &qa));
+        assert!(!workflow.allows(&SpecStatus::Review, &SpecStatus::Done));
+        assert!(workflow.allows(&SpecStatus::Draft, &SpecStatus::Done));
+    }
+}
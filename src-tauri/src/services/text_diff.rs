@@ -0,0 +1,312 @@
+use crate::services::git_service::{DiffHunk, DiffLine, WordSegment};
+
+/// Inputs larger than this (in lines, per side) skip the LCS table and are
+/// reported as a whole-file replacement.
+const MAX_LINES: usize = 4000;
+
+/// Lines with more tokens than this (per side) keep the whole-line
+/// highlight rather than a word diff.
+const MAX_LINE_TOKENS: usize = 500;
+
+/// Line diff of two texts as unified-diff hunks with `context` lines of
+/// surrounding context, in the same shape `git_service::get_diff` returns.
+pub fn diff_lines(old: &str, new: &str, context: usize) -> Vec<DiffHunk> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let ops = if old.len() > MAX_LINES || new.len() > MAX_LINES {
+        old.iter()
+            .map(|l| Op::Remove(l))
+            .chain(new.iter().map(|l| Op::Add(l)))
+            .collect()
+    } else {
+        edit_script(&old, &new)
+    };
+
+    // Number every line, then keep only the changes and their context.
+    let mut lines = Vec::with_capacity(ops.len());
+    let (mut old_no, mut new_no) = (1u32, 1u32);
+    for op in ops {
+        let line = match op {
+            Op::Keep(content) => {
+                old_no += 1;
+                new_no += 1;
+                DiffLine {
+                    line_type: "context".to_string(),
+                    content: content.to_string(),
+                    old_line: Some(old_no - 1),
+                    new_line: Some(new_no - 1),
+                    segments: None,
+                }
+            }
+            Op::Remove(content) => {
+                old_no += 1;
+                DiffLine {
+                    line_type: "remove".to_string(),
+                    content: content.to_string(),
+                    old_line: Some(old_no - 1),
+                    new_line: None,
+                    segments: None,
+                }
+            }
+            Op::Add(content) => {
+                new_no += 1;
+                DiffLine {
+                    line_type: "add".to_string(),
+                    content: content.to_string(),
+                    old_line: None,
+                    new_line: Some(new_no - 1),
+                    segments: None,
+                }
+            }
+        };
+        lines.push(line);
+    }
+    mark_word_changes(&mut lines);
+
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.line_type != "context")
+        .map(|(i, _)| i)
+        .collect();
+    let mut hunks = Vec::new();
+    let mut i = 0;
+    while i < changed.len() {
+        let start = changed[i].saturating_sub(context);
+        let mut end = changed[i];
+        while i < changed.len() && changed[i] <= end + 2 * context + 1 {
+            end = changed[i];
+            i += 1;
+        }
+        let end = (end + context + 1).min(lines.len());
+        let hunk_lines = lines[start..end].to_vec();
+        hunks.push(DiffHunk {
+            header: hunk_header(&hunk_lines),
+            lines: hunk_lines,
+        });
+    }
+    hunks
+}
+
+/// Fill in `segments` for modified lines: within each block of removed
+/// lines followed by added lines, the n-th removal is paired with the n-th
+/// addition and the two are diffed word by word. Pairs with little in
+/// common are left as whole-line changes.
+pub fn mark_word_changes(lines: &mut [DiffLine]) {
+    let mut i = 0;
+    while i < lines.len() {
+        let removed = i;
+        while i < lines.len() && lines[i].line_type == "remove" {
+            i += 1;
+        }
+        let added = i;
+        while i < lines.len() && lines[i].line_type == "add" {
+            i += 1;
+        }
+        if removed == added || added == i {
+            i = i.max(removed + 1);
+            continue;
+        }
+        for n in 0..(added - removed).min(i - added) {
+            let (old, new) = (removed + n, added + n);
+            if let Some((old_segments, new_segments)) =
+                word_diff(&lines[old].content, &lines[new].content)
+            {
+                lines[old].segments = Some(old_segments);
+                lines[new].segments = Some(new_segments);
+            }
+        }
+    }
+}
+
+/// Word diff of a line pair as (old, new) segments. None when either line
+/// is too long or less than half of the longer line is unchanged.
+fn word_diff(old: &str, new: &str) -> Option<(Vec<WordSegment>, Vec<WordSegment>)> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    if old_tokens.len() > MAX_LINE_TOKENS || new_tokens.len() > MAX_LINE_TOKENS {
+        return None;
+    }
+
+    let (mut old_segments, mut new_segments) = (Vec::new(), Vec::new());
+    let mut unchanged = 0;
+    for op in edit_script(&old_tokens, &new_tokens) {
+        match op {
+            Op::Keep(token) => {
+                unchanged += token.len();
+                push_segment(&mut old_segments, token, false);
+                push_segment(&mut new_segments, token, false);
+            }
+            Op::Remove(token) => push_segment(&mut old_segments, token, true),
+            Op::Add(token) => push_segment(&mut new_segments, token, true),
+        }
+    }
+    if unchanged * 2 < old.len().max(new.len()) {
+        return None;
+    }
+    Some((old_segments, new_segments))
+}
+
+/// Split a line into words (letters, digits and `_`), whitespace runs and
+/// single punctuation characters.
+fn tokenize(line: &str) -> Vec<&str> {
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            0
+        } else if c.is_whitespace() {
+            1
+        } else {
+            2
+        }
+    };
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut chars = line.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        let kind = class(c);
+        if let Some(&(next, next_char)) = chars.peek() {
+            if kind == 2 || class(next_char) != kind {
+                tokens.push(&line[start..next]);
+                start = next;
+            }
+        }
+    }
+    if start < line.len() {
+        tokens.push(&line[start..]);
+    }
+    tokens
+}
+
+fn push_segment(segments: &mut Vec<WordSegment>, text: &str, changed: bool) {
+    match segments.last_mut() {
+        Some(last) if last.changed == changed => last.text.push_str(text),
+        _ => segments.push(WordSegment {
+            text: text.to_string(),
+            changed,
+        }),
+    }
+}
+
+enum Op<'a> {
+    Keep(&'a str),
+    Remove(&'a str),
+    Add(&'a str),
+}
+
+/// Shortest edit script via a longest-common-subsequence table.
+fn edit_script<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Op<'a>> {
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push(Op::Keep(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(Op::Remove(old[i]));
+            i += 1;
+        } else {
+            ops.push(Op::Add(new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|l| Op::Remove(l)));
+    ops.extend(new[j..].iter().map(|l| Op::Add(l)));
+    ops
+}
+
+fn hunk_header(lines: &[DiffLine]) -> String {
+    let old_count = lines.iter().filter(|l| l.old_line.is_some()).count();
+    let new_count = lines.iter().filter(|l| l.new_line.is_some()).count();
+    // An empty side starts at the line before the hunk, as git prints it
+    let old_start = lines.iter().find_map(|l| l.old_line).unwrap_or_else(|| {
+        lines
+            .iter()
+            .find_map(|l| l.new_line)
+            .map_or(0, |n| n.saturating_sub(1))
+    });
+    let new_start = lines.iter().find_map(|l| l.new_line).unwrap_or_else(|| {
+        lines
+            .iter()
+            .find_map(|l| l.old_line)
+            .map_or(0, |n| n.saturating_sub(1))
+    });
+    format!("@@ -{old_start},{old_count} +{new_start},{new_count} @@")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_lines_groups_changes_into_hunks_with_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\n";
+        let hunks = diff_lines(old, new, 1);
+        assert_eq!(hunks.len(), 2);
+
+        assert_eq!(hunks[0].header, "@@ -1,3 +1,3 @@");
+        let kinds: Vec<&str> = hunks[0]
+            .lines
+            .iter()
+            .map(|l| l.line_type.as_str())
+            .collect();
+        assert_eq!(kinds, ["context", "remove", "add", "context"]);
+        assert_eq!(hunks[0].lines[1].content, "b");
+        assert_eq!(hunks[0].lines[2].content, "B");
+
+        assert_eq!(hunks[1].header, "@@ -10,1 +10,2 @@");
+        assert_eq!(hunks[1].lines.last().unwrap().content, "k");
+        assert_eq!(hunks[1].lines.last().unwrap().new_line, Some(11));
+
+        assert!(diff_lines(old, old, 3).is_empty());
+    }
+
+    #[test]
+    fn modified_lines_get_word_segments() {
+        let hunks = diff_lines(
+            "let total = price * quantity;\nfn old() {}\n",
+            "let total = price * count + tax;\nstruct Unrelated;\n",
+            0,
+        );
+        let lines = &hunks[0].lines;
+        let segments = |i: usize| -> Vec<(&str, bool)> {
+            lines[i]
+                .segments
+                .iter()
+                .flatten()
+                .map(|s| (s.text.as_str(), s.changed))
+                .collect()
+        };
+        assert_eq!(
+            segments(0),
+            [
+                ("let total = price * ", false),
+                ("quantity", true),
+                (";", false)
+            ]
+        );
+        assert_eq!(
+            segments(2),
+            [
+                ("let total = price * ", false),
+                ("count + tax", true),
+                (";", false)
+            ]
+        );
+        // Nothing in common: left as a whole-line change
+        assert!(lines[1].segments.is_none() && lines[3].segments.is_none());
+    }
+}
@@ -0,0 +1,250 @@
+use crate::domain::models::{
+    AgentSession, AgentStatus, ImportedTranscript, MessageUsage, TranscriptEntry,
+    TranscriptImportReport,
+};
+use crate::domain::ports::LogRepository;
+use crate::domain::stream_parser;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+/// Agent name given to sessions run directly in the terminal.
+pub const TERMINAL_AGENT: &str = "terminal";
+
+/// Where Claude Code keeps the transcripts of a project: the project path
+/// with every character other than letters and digits replaced by `-`.
+pub fn transcripts_dir(project_dir: &str) -> Result<PathBuf, String> {
+    let name: String = project_dir
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    dirs::home_dir()
+        .map(|h| h.join(".claude/projects").join(name))
+        .ok_or_else(|| "No home directory".to_string())
+}
+
+/// Import every transcript of the project not yet in the log store. Files
+/// named after a stored session (including those clautron launched) are
+/// skipped, so running it again only picks up new terminal sessions.
+pub async fn import_project(
+    logs: &dyn LogRepository,
+    project_dir: &str,
+) -> Result<TranscriptImportReport, String> {
+    let dir = transcripts_dir(project_dir)?;
+    let mut report = TranscriptImportReport::default();
+    let files = match std::fs::read_dir(&dir) {
+        Ok(files) => files,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(report),
+        Err(e) => return Err(format!("Failed to read {}: {e}", dir.display())),
+    };
+
+    let known = logs.known_session_ids().await.map_err(|e| e.to_string())?;
+    let mut paths: Vec<PathBuf> = files
+        .filter_map(|f| f.ok().map(|f| f.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let file = path.file_name().unwrap_or_default().to_string_lossy();
+        let Some(session_id) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if known.contains(session_id) {
+            report.skipped += 1;
+            continue;
+        }
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                report.failed.push(format!("{file}: {e}"));
+                continue;
+            }
+        };
+        let Some(transcript) = parse_transcript(session_id, &content, project_dir) else {
+            report.skipped += 1;
+            continue;
+        };
+        match logs
+            .import_session(&transcript.session, &transcript.entries)
+            .await
+        {
+            Ok(()) => report.imported += 1,
+            Err(e) => report.failed.push(format!("{file}: {e}")),
+        }
+    }
+    Ok(report)
+}
+
+/// Convert a transcript into a finished session and its log entries. Each
+/// user and assistant line becomes an entry shaped like the stream-json
+/// message clautron would have logged; summaries, sidechains (subagent
+/// turns) and unparsable lines are left out. None when nothing remains.
+pub fn parse_transcript(
+    session_id: &str,
+    content: &str,
+    project_dir: &str,
+) -> Option<ImportedTranscript> {
+    let mut entries = Vec::new();
+    let mut model = None;
+    let mut prompt = None;
+    let (mut input_tokens, mut output_tokens) = (0, 0);
+    let mut counted: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        let Ok(line) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let message_type = match line.get("type").and_then(Value::as_str) {
+            Some(t @ ("user" | "assistant")) => t,
+            _ => continue,
+        };
+        if line.get("isSidechain").and_then(Value::as_bool) == Some(true) {
+            continue;
+        }
+        let Some(message) = line.get("message") else {
+            continue;
+        };
+        let timestamp = line
+            .get("timestamp")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+
+        let mut usage = None;
+        if message_type == "assistant" {
+            if model.is_none() {
+                model = message
+                    .get("model")
+                    .and_then(Value::as_str)
+                    .map(model_alias);
+            }
+            let tokens = |key: &str| {
+                message
+                    .pointer(&format!("/usage/{key}"))
+                    .and_then(Value::as_u64)
+                    .unwrap_or(0)
+            };
+            let (input, output) = (tokens("input_tokens"), tokens("output_tokens"));
+            if input > 0 || output > 0 {
+                usage = Some(MessageUsage {
+                    input_tokens: input,
+                    output_tokens: output,
+                });
+                // Every content block of a message repeats its usage
+                let id = message.get("id").and_then(Value::as_str).unwrap_or("");
+                if id.is_empty() || !counted.iter().any(|c| c == id) {
+                    counted.push(id.to_string());
+                    input_tokens += input;
+                    output_tokens += output;
+                }
+            }
+        } else if prompt.is_none() && line.get("isMeta").and_then(Value::as_bool) != Some(true) {
+            prompt = prompt_text(message);
+        }
+
+        let entry = json!({
+            "type": message_type,
+            "message": message,
+            "session_id": session_id,
+        });
+        entries.push(TranscriptEntry {
+            message_type: message_type.to_string(),
+            content: stream_parser::redact_secrets(&entry.to_string()),
+            timestamp,
+            usage,
+        });
+    }
+
+    let first = entries.first()?.timestamp.clone();
+    let last = entries.last()?.timestamp.clone();
+    Some(ImportedTranscript {
+        session: AgentSession {
+            id: session_id.to_string(),
+            agent_name: TERMINAL_AGENT.to_string(),
+            model: model.unwrap_or_default(),
+            status: AgentStatus::Completed,
+            prompt: prompt.unwrap_or_default(),
+            started_at: first,
+            ended_at: Some(last),
+            input_tokens,
+            output_tokens,
+            // Transcripts don't record cost
+            cost_usd: 0.0,
+            project_dir: Some(project_dir.to_string()),
+            branch: None,
+            cost_estimated: false,
+            profile: None,
+        },
+        entries,
+    })
+}
+
+/// `claude-sonnet-4-5-20250929` -> `sonnet`, matching agent configs.
+fn model_alias(model: &str) -> String {
+    ["opus", "sonnet", "haiku"]
+        .into_iter()
+        .find(|alias| model.contains(alias))
+        .unwrap_or(model)
+        .to_string()
+}
+
+/// The text of a user message, which is a plain string or a list of blocks.
+/// Messages carrying only tool results have none.
+fn prompt_text(message: &Value) -> Option<String> {
+    let text = match message.get("content")? {
+        Value::String(text) => text.clone(),
+        Value::Array(blocks) => blocks
+            .iter()
+            .filter(|b| b.get("type").and_then(Value::as_str) == Some("text"))
+            .filter_map(|b| b.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => return None,
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| stream_parser::redact_secrets(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transcripts_become_finished_sessions() {
+        let transcript = [
+            r#"{"type":"summary","summary":"Fix the parser","leafUuid":"u9"}"#,
+            r#"{"type":"user","isMeta":true,"message":{"role":"user","content":"<command-name>/clear</command-name>"},"timestamp":"2026-01-01T10:00:00Z"}"#,
+            r#"{"type":"user","message":{"role":"user","content":"Fix the parser"},"timestamp":"2026-01-01T10:00:01Z"}"#,
+            r#"{"type":"assistant","message":{"id":"m1","model":"claude-sonnet-4-5-20250929","content":[{"type":"text","text":"Looking"}],"usage":{"input_tokens":100,"output_tokens":20}},"timestamp":"2026-01-01T10:00:02Z"}"#,
+            r#"{"type":"assistant","message":{"id":"m1","model":"claude-sonnet-4-5-20250929","content":[{"type":"tool_use","id":"t1","name":"Read","input":{}}],"usage":{"input_tokens":100,"output_tokens":20}},"timestamp":"2026-01-01T10:00:03Z"}"#,
+            r#"{"type":"assistant","isSidechain":true,"message":{"id":"m2","content":[],"usage":{"input_tokens":999,"output_tokens":999}},"timestamp":"2026-01-01T10:00:04Z"}"#,
+            r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"fn main() {}"}]},"timestamp":"2026-01-01T10:00:05Z"}"#,
+            "not json",
+        ]
+        .join("\n");
+
+        let imported = parse_transcript("s1", &transcript, "/work/app").unwrap();
+        let session = &imported.session;
+        assert_eq!(session.model, "sonnet");
+        assert_eq!(session.prompt, "Fix the parser");
+        assert_eq!((session.input_tokens, session.output_tokens), (100, 20));
+        assert_eq!(session.started_at, "2026-01-01T10:00:00Z");
+        assert_eq!(session.ended_at.as_deref(), Some("2026-01-01T10:00:05Z"));
+        assert_eq!(session.project_dir.as_deref(), Some("/work/app"));
+
+        let types: Vec<&str> = imported
+            .entries
+            .iter()
+            .map(|e| e.message_type.as_str())
+            .collect();
+        assert_eq!(types, ["user", "user", "assistant", "assistant", "user"]);
+        let last: Value = serde_json::from_str(&imported.entries[4].content).unwrap();
+        assert_eq!(last["session_id"], "s1");
+        assert_eq!(last["message"]["content"][0]["tool_use_id"], "t1");
+
+        assert!(parse_transcript("s2", r#"{"type":"summary"}"#, "/work/app").is_none());
+        assert!(transcripts_dir("/work/my.app")
+            .unwrap()
+            .ends_with(".claude/projects/-work-my-app"));
+    }
+}
@@ -0,0 +1,122 @@
+//! Usage export for expense reporting: session tokens and cost summed per
+//! day, model and agent.
+
+use crate::domain::models::{AgentSession, UsageRange};
+use crate::services::spec_export::csv_field;
+use std::collections::BTreeMap;
+
+const CSV_HEADER: &[&str] = &[
+    "date",
+    "model",
+    "agent",
+    "sessions",
+    "input_tokens",
+    "output_tokens",
+    "cost_usd",
+];
+
+#[derive(Debug, Default, PartialEq)]
+struct UsageRow {
+    sessions: u32,
+    input_tokens: u64,
+    output_tokens: u64,
+    cost_usd: f64,
+}
+
+/// Render sessions started within `range` as CSV, one row per local day,
+/// model and agent, oldest first.
+pub fn export_usage_csv(sessions: &[AgentSession], range: &UsageRange) -> String {
+    let mut rows: BTreeMap<(String, &str, &str), UsageRow> = BTreeMap::new();
+    for session in sessions {
+        let date = local_date(&session.started_at);
+        if !range.contains(&date) {
+            continue;
+        }
+        let row = rows
+            .entry((date, session.model.as_str(), session.agent_name.as_str()))
+            .or_default();
+        row.sessions += 1;
+        row.input_tokens += session.input_tokens;
+        row.output_tokens += session.output_tokens;
+        row.cost_usd += session.cost_usd;
+    }
+
+    let mut out = CSV_HEADER.join(",");
+    out.push_str("\r\n");
+    for ((date, model, agent), row) in &rows {
+        let fields = [
+            csv_field(date),
+            csv_field(model),
+            csv_field(agent),
+            row.sessions.to_string(),
+            row.input_tokens.to_string(),
+            row.output_tokens.to_string(),
+            format!("{:.4}", row.cost_usd),
+        ];
+        out.push_str(&fields.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Local date of an RFC 3339 timestamp, matching the dates usage snapshots
+/// are recorded under. Anything else keeps its leading date part.
+fn local_date(timestamp: &str) -> String {
+    match chrono::DateTime::parse_from_rfc3339(timestamp) {
+        Ok(t) => t
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d")
+            .to_string(),
+        Err(_) => timestamp.chars().take(10).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::AgentStatus;
+
+    fn session(agent: &str, model: &str, started_at: &str, cost_usd: f64) -> AgentSession {
+        AgentSession {
+            id: format!("{agent}-{started_at}"),
+            agent_name: agent.into(),
+            model: model.into(),
+            status: AgentStatus::Completed,
+            prompt: String::new(),
+            started_at: started_at.into(),
+            ended_at: None,
+            input_tokens: 100,
+            output_tokens: 10,
+            cost_usd,
+            project_dir: None,
+            branch: None,
+            cost_estimated: false,
+            profile: None,
+        }
+    }
+
+    #[test]
+    fn usage_rows_sum_per_day_model_and_agent() {
+        let sessions = [
+            session("reviewer", "opus", "2026-03-02T12:00:00Z", 1.0),
+            session("reviewer", "opus", "2026-03-02T12:30:00Z", 0.5),
+            session("=cmd", "haiku", "2026-03-02T12:00:00Z", 0.25),
+            session("reviewer", "opus", "2026-03-04T12:00:00Z", 2.0),
+        ];
+        let range = UsageRange {
+            from: None,
+            to: Some("2026-03-03".into()),
+        };
+
+        let csv = export_usage_csv(&sessions, &range);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "date,model,agent,sessions,input_tokens,output_tokens,cost_usd",
+                "2026-03-02,haiku,'=cmd,1,100,10,0.2500",
+                "2026-03-02,opus,reviewer,2,200,20,1.5000",
+            ]
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Spend over time from daily snapshots of Claude Code's usage totals.
+//!
+//! `stats-cache.json` only holds all-time totals per model, so the quota
+//! poller stores them once a day and a day's usage is the growth since the
+//! model's previous snapshot. The first day ever recorded is the baseline
+//! and has no usage of its own; a model that first shows up later counts
+//! its whole total on that day. Each Claude profile keeps its own totals, so
+//! growth is tracked per profile and model, then summed per model.
+
+use crate::domain::models::{ModelUsage, UsageGranularity, UsagePeriod, UsageSnapshot};
+use chrono::{Datelike, NaiveDate};
+use std::collections::BTreeMap;
+
+/// Usage per period between `from` and `to` (inclusive `YYYY-MM-DD` dates,
+/// open-ended when None), oldest first. Every recorded day in range
+/// contributes a period, so idle days show as zero spend.
+pub fn usage_history(
+    snapshots: &[UsageSnapshot],
+    from: Option<&str>,
+    to: Option<&str>,
+    granularity: UsageGranularity,
+) -> Vec<UsagePeriod> {
+    let Some(first_day) = snapshots.iter().map(|s| s.date.as_str()).min() else {
+        return Vec::new();
+    };
+    let in_range =
+        |date: &str| from.is_none_or(|from| date >= from) && to.is_none_or(|to| date <= to);
+
+    let mut by_model: BTreeMap<(Option<&str>, &str), Vec<&UsageSnapshot>> = BTreeMap::new();
+    for snapshot in snapshots {
+        by_model
+            .entry((snapshot.profile.as_deref(), snapshot.usage.model.as_str()))
+            .or_default()
+            .push(snapshot);
+    }
+
+    let mut periods: BTreeMap<String, BTreeMap<String, ModelUsage>> = BTreeMap::new();
+    for snapshot in snapshots {
+        if snapshot.date.as_str() != first_day && in_range(&snapshot.date) {
+            periods
+                .entry(period_start(&snapshot.date, granularity))
+                .or_default();
+        }
+    }
+    for ((_, model), mut days) in by_model {
+        days.sort_by(|a, b| a.date.cmp(&b.date));
+        let mut previous: Option<&ModelUsage> = None;
+        for day in days {
+            let usage = match previous {
+                Some(prev) => growth(prev, &day.usage),
+                None if day.date.as_str() == first_day => None,
+                None => Some(day.usage.clone()),
+            };
+            previous = Some(&day.usage);
+            let Some(usage) = usage.filter(|_| in_range(&day.date)) else {
+                continue;
+            };
+            let total = periods
+                .entry(period_start(&day.date, granularity))
+                .or_default()
+                .entry(model.to_string())
+                .or_insert_with(|| ModelUsage {
+                    model: model.to_string(),
+                    ..Default::default()
+                });
+            total.input_tokens += usage.input_tokens;
+            total.output_tokens += usage.output_tokens;
+            total.cache_read_tokens += usage.cache_read_tokens;
+            total.cache_creation_tokens += usage.cache_creation_tokens;
+            total.cost_usd += usage.cost_usd;
+        }
+    }
+
+    periods
+        .into_iter()
+        .map(|(start, models)| {
+            let mut models: Vec<ModelUsage> = models.into_values().collect();
+            models.sort_by(|a, b| {
+                b.cost_usd
+                    .partial_cmp(&a.cost_usd)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            UsagePeriod {
+                start,
+                cost_usd: models.iter().map(|m| m.cost_usd).sum(),
+                models,
+            }
+        })
+        .collect()
+}
+
+/// Usage between two snapshots of a model, None when nothing changed. Totals
+/// that went down mean the stats cache was reset, so everything in the
+/// newer one is new.
+fn growth(prev: &ModelUsage, cur: &ModelUsage) -> Option<ModelUsage> {
+    let reset = cur.input_tokens < prev.input_tokens
+        || cur.output_tokens < prev.output_tokens
+        || cur.cost_usd < prev.cost_usd;
+    let usage = if reset {
+        cur.clone()
+    } else {
+        ModelUsage {
+            model: cur.model.clone(),
+            input_tokens: cur.input_tokens - prev.input_tokens,
+            output_tokens: cur.output_tokens - prev.output_tokens,
+            cache_read_tokens: cur.cache_read_tokens.saturating_sub(prev.cache_read_tokens),
+            cache_creation_tokens: cur
+                .cache_creation_tokens
+                .saturating_sub(prev.cache_creation_tokens),
+            cost_usd: cur.cost_usd - prev.cost_usd,
+        }
+    };
+    let unchanged = usage.input_tokens == 0
+        && usage.output_tokens == 0
+        && usage.cache_read_tokens == 0
+        && usage.cache_creation_tokens == 0;
+    (!unchanged).then_some(usage)
+}
+
+/// First day of the period `date` falls in. Unparseable dates are their
+/// own period.
+pub fn period_start(date: &str, granularity: UsageGranularity) -> String {
+    let Ok(day) = NaiveDate::parse_from_str(date, "%Y-%m-%d") else {
+        return date.to_string();
+    };
+    let start = match granularity {
+        UsageGranularity::Day => day,
+        UsageGranularity::Week => {
+            day - chrono::Duration::days(day.weekday().num_days_from_monday() as i64)
+        }
+        UsageGranularity::Month => day.with_day(1).unwrap_or(day),
+    };
+    start.format("%Y-%m-%d").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(date: &str, model: &str, output_tokens: u64, cost_usd: f64) -> UsageSnapshot {
+        UsageSnapshot {
+            date: date.into(),
+            profile: None,
+            usage: ModelUsage {
+                model: model.into(),
+                output_tokens,
+                cost_usd,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn days_count_growth_since_the_previous_snapshot() {
+        let snapshots = [
+            snapshot("2026-03-02", "opus", 100, 1.0),
+            snapshot("2026-03-03", "opus", 150, 1.5),
+            // Not seen on the baseline day: all of it is new
+            snapshot("2026-03-03", "haiku", 40, 0.1),
+            snapshot("2026-03-04", "opus", 150, 1.5),
+            // Cache reset: totals restart from zero
+            snapshot("2026-03-09", "opus", 30, 0.25),
+        ];
+
+        let days = usage_history(&snapshots, None, None, UsageGranularity::Day);
+        let starts: Vec<&str> = days.iter().map(|p| p.start.as_str()).collect();
+        assert_eq!(starts, ["2026-03-03", "2026-03-04", "2026-03-09"]);
+        assert_eq!(days[0].cost_usd, 0.6);
+        assert_eq!(days[0].models[0].model, "opus");
+        assert_eq!(days[0].models[0].output_tokens, 50);
+        assert!(days[1].models.is_empty());
+        assert_eq!(days[2].cost_usd, 0.25);
+
+        let weeks = usage_history(&snapshots, None, None, UsageGranularity::Week);
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(weeks[0].start, "2026-03-02");
+        assert_eq!(weeks[1].start, "2026-03-09");
+
+        let months = usage_history(
+            &snapshots,
+            Some("2026-03-04"),
+            None,
+            UsageGranularity::Month,
+        );
+        assert_eq!(months.len(), 1);
+        assert_eq!(months[0].start, "2026-03-01");
+        assert_eq!(months[0].cost_usd, 0.25);
+    }
+
+    #[test]
+    fn profiles_grow_separately_and_sum_per_model() {
+        let work = |date: &str, output_tokens, cost_usd| UsageSnapshot {
+            profile: Some("work".into()),
+            ..snapshot(date, "opus", output_tokens, cost_usd)
+        };
+        let snapshots = [
+            snapshot("2026-03-02", "opus", 100, 1.0),
+            work("2026-03-02", 500, 5.0),
+            snapshot("2026-03-03", "opus", 120, 1.25),
+            work("2026-03-03", 530, 5.5),
+        ];
+
+        let days = usage_history(&snapshots, None, None, UsageGranularity::Day);
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].models.len(), 1);
+        assert_eq!(days[0].models[0].output_tokens, 50);
+        assert_eq!(days[0].cost_usd, 0.75);
+    }
+}
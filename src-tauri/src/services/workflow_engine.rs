@@ -1,19 +1,66 @@
 use crate::domain::error::DomainError;
 use crate::domain::models::*;
-use crate::domain::ports::{LogRepository, WorkflowRepository};
+use crate::domain::ports::{FileConflictEvent, LogRepository, WorkflowRepository};
 use crate::domain::session_manager::SessionManager;
-use std::collections::{HashMap, HashSet};
+use crate::services::gate_runner::{run_gate, GateResult};
+use crate::services::spec_manager::SpecManager;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
 
 /// Max size for captured result output (50KB) to prevent context explosion.
 const MAX_RESULT_OUTPUT_LEN: usize = 50 * 1024;
 
+/// Rough chars-per-token ratio used for prompt size estimates.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Sent to a held step's agent when it is resumed.
+const HELD_RESUME_PROMPT: &str = "You were paused because another agent was editing the same \
+    files. It has finished; re-read any file you were working on before changing it, then \
+    continue your task.";
+
+/// Commit message for auto-committing steps that leave theirs blank.
+const DEFAULT_COMMIT_MESSAGE: &str = "{{workflow}}: {{step}}";
+
+/// Outcome of a gate command, reported back to the engine by its task.
+struct GateOutcome {
+    workflow_id: String,
+    step_id: String,
+    result: GateResult,
+}
+
+/// Whether an edge currently lets its target run.
+#[derive(PartialEq)]
+enum EdgeState {
+    Satisfied,
+    Waiting,
+    /// The source finished in a way that will never satisfy this edge.
+    Dead,
+}
+
 /// Workflow execution engine. Resolves DAG dependencies and launches
-/// agent steps in the correct order (parallel when possible).
+/// agent and gate steps in the correct order (parallel when possible).
 pub struct WorkflowEngine {
     repo: Arc<dyn WorkflowRepository>,
     session_manager: Arc<SessionManager>,
     logs: Arc<dyn LogRepository>,
+    spec_manager: Arc<SpecManager>,
+    gate_tx: mpsc::UnboundedSender<GateOutcome>,
+    gate_rx: Mutex<Option<mpsc::UnboundedReceiver<GateOutcome>>>,
+    /// Running gate commands by step ID, so `stop` can kill them.
+    gate_tasks: Mutex<HashMap<String, tokio::task::AbortHandle>>,
+    /// Per-workflow locks serializing state transitions and `advance`, so
+    /// near-simultaneous completions can't double-start children.
+    workflow_locks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+    /// Stop step agents that write a file another session is writing.
+    hold_on_conflict: AtomicBool,
+    /// Stopped step sessions, each with the session it waits for.
+    held_sessions: Mutex<HashMap<String, String>>,
+    /// Branch of each running workflow, set when its first step starts (None
+    /// when that step didn't branch). Later steps run on it rather than
+    /// branching again.
+    run_branches: Mutex<HashMap<String, Option<String>>>,
 }
 
 impl WorkflowEngine {
@@ -21,14 +68,69 @@ impl WorkflowEngine {
         repo: Arc<dyn WorkflowRepository>,
         session_manager: Arc<SessionManager>,
         logs: Arc<dyn LogRepository>,
+        spec_manager: Arc<SpecManager>,
     ) -> Self {
+        let (gate_tx, gate_rx) = mpsc::unbounded_channel();
         Self {
             repo,
             session_manager,
             logs,
+            spec_manager,
+            gate_tx,
+            gate_rx: Mutex::new(Some(gate_rx)),
+            gate_tasks: Mutex::new(HashMap::new()),
+            workflow_locks: Mutex::new(HashMap::new()),
+            hold_on_conflict: AtomicBool::new(false),
+            held_sessions: Mutex::new(HashMap::new()),
+            run_branches: Mutex::new(HashMap::new()),
         }
     }
 
+    pub fn set_hold_on_conflict(&self, enabled: bool) {
+        self.hold_on_conflict.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Acquire the lock for a workflow. Held across the whole transition
+    /// (status update + `advance`); `advance` itself never locks.
+    async fn lock_workflow(&self, workflow_id: &str) -> tokio::sync::OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = self.workflow_locks.lock().await;
+            Arc::clone(locks.entry(workflow_id.to_string()).or_default())
+        };
+        lock.lock_owned().await
+    }
+
+    /// Whether the step is still running in a running workflow. Re-checked
+    /// under the workflow lock, since duplicate or late events are possible.
+    async fn is_step_running(&self, workflow_id: &str, step_id: &str) -> bool {
+        let Ok(Some(wf)) = self.repo.get_workflow(workflow_id).await else {
+            return false;
+        };
+        if wf.status != WorkflowStatus::Running {
+            return false;
+        }
+        let Ok(steps) = self.repo.get_steps(workflow_id).await else {
+            return false;
+        };
+        steps
+            .iter()
+            .any(|s| s.id == step_id && s.status == StepStatus::Running)
+    }
+
+    /// Start the task that receives gate results and advances their workflows.
+    pub fn start_gate_listener(self: &Arc<Self>) {
+        let engine = Arc::clone(self);
+        tokio::spawn(async move {
+            let Some(mut rx) = engine.gate_rx.lock().await.take() else {
+                return;
+            };
+            while let Some(outcome) = rx.recv().await {
+                engine.gate_tasks.lock().await.remove(&outcome.step_id);
+                engine.on_gate_finished(outcome).await;
+            }
+        });
+    }
+
     /// Validate a workflow DAG: check for cycles via topological sort.
     pub async fn validate(&self, workflow_id: &str) -> Result<(), DomainError> {
         let steps = self.repo.get_steps(workflow_id).await?;
@@ -38,57 +140,176 @@ impl WorkflowEngine {
             return Err(DomainError::Process("Workflow has no steps".into()));
         }
 
-        // Build adjacency list and in-degree map
-        let mut in_degree: HashMap<String, usize> = HashMap::new();
-        let mut adj: HashMap<String, Vec<String>> = HashMap::new();
+        if topological_order(&steps, &edges).is_none() {
+            return Err(DomainError::Process(
+                "Workflow contains a cycle".into(),
+            ));
+        }
 
-        for step in &steps {
-            in_degree.entry(step.id.clone()).or_insert(0);
-            adj.entry(step.id.clone()).or_default();
+        if let Some(step) = steps.iter().find(|s| {
+            s.kind == StepKind::Gate && s.gate_command.as_deref().is_none_or(|c| c.trim().is_empty())
+        }) {
+            return Err(DomainError::Process(format!(
+                "Gate step {} has no command",
+                step.id
+            )));
         }
 
-        for edge in &edges {
-            *in_degree.entry(edge.target_step_id.clone()).or_insert(0) += 1;
-            adj.entry(edge.source_step_id.clone())
-                .or_default()
-                .push(edge.target_step_id.clone());
+        Ok(())
+    }
+
+    /// Dry-run a workflow: compute execution order, parallel waves, cost and
+    /// prompt-size estimates without spawning any processes.
+    pub async fn simulate(&self, workflow_id: &str) -> Result<WorkflowSimulation, DomainError> {
+        self.validate(workflow_id).await?;
+        let steps = self.repo.get_steps(workflow_id).await?;
+        let edges = self.repo.get_edges(workflow_id).await?;
+        let order = topological_order(&steps, &edges)
+            .ok_or_else(|| DomainError::Process("Workflow contains a cycle".into()))?;
+
+        // A step runs one wave after its parents: the latest parent for
+        // `all` joins, the earliest for `any` joins.
+        let mut wave_of: HashMap<String, usize> = HashMap::new();
+        for id in &order {
+            let Some(step) = steps.iter().find(|s| &s.id == id) else {
+                continue;
+            };
+            let parent_waves = edges
+                .iter()
+                .filter(|e| &e.target_step_id == id)
+                .filter_map(|e| wave_of.get(&e.source_step_id).copied());
+            let wave = match step.join_mode {
+                JoinMode::All => parent_waves.max().map(|w| w + 1),
+                JoinMode::Any => parent_waves.min().map(|w| w + 1),
+            }
+            .unwrap_or(0);
+            wave_of.insert(id.clone(), wave);
+        }
+
+        let wave_count = wave_of.values().max().map(|w| w + 1).unwrap_or(0);
+        let mut waves: Vec<Vec<String>> = vec![Vec::new(); wave_count];
+        for id in &order {
+            waves[wave_of[id]].push(id.clone());
+        }
+        let execution_order: Vec<String> = waves.iter().flatten().cloned().collect();
+
+        // Historical average cost per agent from the project's finished
+        // sessions, including those of earlier runs of the app
+        let history = match self.session_manager.get_project_dir().await {
+            Some(dir) => self.logs.list_stored_sessions(&dir).await?,
+            None => Vec::new(),
+        };
+        let mut cost_totals: HashMap<String, (f64, u32)> = HashMap::new();
+        for session in history {
+            if session.cost_usd > 0.0 {
+                let entry = cost_totals.entry(session.agent_name).or_insert((0.0, 0));
+                entry.0 += session.cost_usd;
+                entry.1 += 1;
+            }
         }
 
-        // Kahn's algorithm
-        let mut queue: Vec<String> = in_degree
+        // Parents without output yet are assumed to hit the result cap.
+        let worst_case_steps: Vec<WorkflowStep> = steps
             .iter()
-            .filter(|(_, &deg)| deg == 0)
-            .map(|(id, _)| id.clone())
+            .cloned()
+            .map(|mut s| {
+                if s.result_output.is_none() {
+                    s.result_output = Some("x".repeat(MAX_RESULT_OUTPUT_LEN));
+                }
+                s
+            })
             .collect();
 
-        let mut visited = 0;
-        while let Some(node) = queue.pop() {
-            visited += 1;
-            if let Some(neighbors) = adj.get(&node) {
-                for next in neighbors {
-                    if let Some(deg) = in_degree.get_mut(next) {
-                        *deg -= 1;
-                        if *deg == 0 {
-                            queue.push(next.clone());
-                        }
+        let mut warnings = Vec::new();
+        let mut planned = Vec::new();
+        for id in &execution_order {
+            let Some(step) = steps.iter().find(|s| &s.id == id) else {
+                continue;
+            };
+            if step.kind == StepKind::Gate {
+                planned.push(SimulatedStep {
+                    step_id: step.id.clone(),
+                    agent_name: step.agent_name.clone(),
+                    wave: wave_of[id],
+                    estimated_cost_usd: Some(0.0),
+                    prompt_chars: 0,
+                    max_prompt_chars: 0,
+                    estimated_prompt_tokens: 0,
+                });
+                continue;
+            }
+            let deps: Vec<&WorkflowEdge> =
+                edges.iter().filter(|e| &e.target_step_id == id).collect();
+            let base_prompt = match self.resolve_step_prompt(step).await {
+                Ok(p) => p,
+                Err(e) => {
+                    warnings.push(e.to_string());
+                    step.prompt.clone()
+                }
+            };
+            if let Some(spec_path) = step_spec_path(step) {
+                if let Ok(spec) = self.spec_manager.get_spec(spec_path).await {
+                    let unmet = self
+                        .spec_manager
+                        .unmet_dependencies(&spec)
+                        .await
+                        .unwrap_or_default();
+                    if !unmet.is_empty() {
+                        warnings.push(format!(
+                            "Spec '{}' is blocked by: {}",
+                            spec.title,
+                            unmet.join(", ")
+                        ));
                     }
                 }
             }
-        }
+            let prompt_chars = build_effective_prompt(step, &base_prompt, &deps, &steps)
+                .chars()
+                .count();
+            let max_prompt_chars =
+                build_effective_prompt(step, &base_prompt, &deps, &worst_case_steps)
+                    .chars()
+                    .count();
 
-        if visited != steps.len() {
-            return Err(DomainError::Process(
-                "Workflow contains a cycle".into(),
-            ));
+            let estimated_cost_usd = cost_totals
+                .get(&step.agent_name)
+                .map(|(total, count)| total / *count as f64);
+            if estimated_cost_usd.is_none() {
+                let warning = format!("No cost history for agent '{}'", step.agent_name);
+                if !warnings.contains(&warning) {
+                    warnings.push(warning);
+                }
+            }
+
+            planned.push(SimulatedStep {
+                step_id: step.id.clone(),
+                agent_name: step.agent_name.clone(),
+                wave: wave_of[id],
+                estimated_cost_usd,
+                prompt_chars,
+                max_prompt_chars,
+                estimated_prompt_tokens: max_prompt_chars / CHARS_PER_TOKEN,
+            });
         }
 
-        Ok(())
+        let estimated_cost_usd = planned.iter().filter_map(|s| s.estimated_cost_usd).sum();
+
+        Ok(WorkflowSimulation {
+            workflow_id: workflow_id.to_string(),
+            execution_order,
+            waves,
+            steps: planned,
+            estimated_cost_usd,
+            warnings,
+        })
     }
 
     /// Start executing a workflow.
     pub async fn start(&self, workflow_id: &str) -> Result<(), DomainError> {
         self.validate(workflow_id).await?;
+        let _guard = self.lock_workflow(workflow_id).await;
 
+        self.run_branches.lock().await.remove(workflow_id);
         self.repo
             .update_workflow_status(workflow_id, WorkflowStatus::Running)
             .await?;
@@ -101,6 +322,7 @@ impl WorkflowEngine {
 
     /// Stop a running workflow.
     pub async fn stop(&self, workflow_id: &str) -> Result<(), DomainError> {
+        let _guard = self.lock_workflow(workflow_id).await;
         let steps = self.repo.get_steps(workflow_id).await?;
 
         // Kill running steps
@@ -109,6 +331,9 @@ impl WorkflowEngine {
                 if let Some(ref sid) = step.session_id {
                     let _ = self.session_manager.stop_agent(sid).await;
                 }
+                if let Some(task) = self.gate_tasks.lock().await.remove(&step.id) {
+                    task.abort();
+                }
                 self.repo
                     .update_step_status(&step.id, StepStatus::Skipped, None)
                     .await?;
@@ -122,10 +347,96 @@ impl WorkflowEngine {
         self.repo
             .update_workflow_status(workflow_id, WorkflowStatus::Cancelled)
             .await?;
+        self.run_branches.lock().await.remove(workflow_id);
 
         Ok(())
     }
 
+    /// With holding on, stop a workflow step's agent that wrote a file
+    /// another running session has written. The step stays running and
+    /// `release_held` resumes the agent once the other session ends.
+    pub async fn on_file_conflict(&self, event: &FileConflictEvent) {
+        if !self.hold_on_conflict.load(Ordering::Relaxed)
+            || !self.is_step_session(&event.session_id).await
+        {
+            return;
+        }
+        {
+            let mut held = self.held_sessions.lock().await;
+            if held.contains_key(&event.session_id) {
+                return;
+            }
+            held.insert(event.session_id.clone(), event.other_session_id.clone());
+        }
+        if let Err(e) = self.session_manager.stop_agent(&event.session_id).await {
+            eprintln!("Failed to hold session {}: {e}", event.session_id);
+            self.held_sessions.lock().await.remove(&event.session_id);
+            return;
+        }
+        // The other session may have ended before the hold was in place
+        let other_running = self
+            .session_manager
+            .get_session(&event.other_session_id)
+            .await
+            .is_some_and(|s| matches!(s.status, AgentStatus::Starting | AgentStatus::Running));
+        if !other_running {
+            self.release_held(&event.other_session_id).await;
+        }
+    }
+
+    /// Resume the step agents held until `session_id` ended. Steps stopped
+    /// with their workflow in the meantime stay stopped.
+    pub async fn release_held(&self, session_id: &str) {
+        let released: Vec<String> = {
+            let mut held = self.held_sessions.lock().await;
+            let ids: Vec<String> = held
+                .iter()
+                .filter(|(_, other)| other.as_str() == session_id)
+                .map(|(id, _)| id.clone())
+                .collect();
+            for id in &ids {
+                held.remove(id);
+            }
+            ids
+        };
+        for id in released {
+            if !self.is_step_session(&id).await {
+                continue;
+            }
+            if let Err(e) = self
+                .session_manager
+                .resume_agent(id.clone(), HELD_RESUME_PROMPT.to_string())
+                .await
+            {
+                // e.g. refused by the budget hard stop; fail the step rather
+                // than leave it running with no agent
+                eprintln!("Failed to resume held session {id}: {e}");
+                self.on_agent_failed(&id).await;
+            }
+        }
+    }
+
+    /// Whether the session belongs to a running step of a running workflow.
+    async fn is_step_session(&self, session_id: &str) -> bool {
+        let Ok(workflows) = self.repo.list_workflows().await else {
+            return false;
+        };
+        for wf in workflows
+            .iter()
+            .filter(|w| w.status == WorkflowStatus::Running)
+        {
+            let Ok(steps) = self.repo.get_steps(&wf.id).await else {
+                continue;
+            };
+            if steps.iter().any(|s| {
+                s.session_id.as_deref() == Some(session_id) && s.status == StepStatus::Running
+            }) {
+                return true;
+            }
+        }
+        false
+    }
+
     /// Called when an agent session completes. Maps session_id -> workflow step,
     /// updates step status, and advances the workflow.
     pub async fn on_agent_completed(&self, session_id: &str) -> Option<String> {
@@ -140,6 +451,10 @@ impl WorkflowEngine {
                 if step.session_id.as_deref() == Some(session_id)
                     && step.status == StepStatus::Running
                 {
+                    let _guard = self.lock_workflow(&wf.id).await;
+                    if !self.is_step_running(&wf.id, &step.id).await {
+                        return Some(wf.id.clone());
+                    }
                     let _ = self
                         .repo
                         .update_step_status(&step.id, StepStatus::Completed, None)
@@ -147,11 +462,12 @@ impl WorkflowEngine {
 
                     // Capture result output for context passing
                     self.logs.flush().await;
-                    if let Ok(logs) = self.logs.query_logs(session_id, 0, 1000).await {
+                    if let Ok(logs) = self.logs.query_logs_tail(session_id, 1000).await {
                         if let Some(output) = extract_result_text(&logs) {
                             let _ = self.repo.update_step_result(&step.id, &output).await;
                         }
                     }
+                    self.commit_step(wf, step).await;
 
                     let _ = self.advance(&wf.id).await;
                     return Some(wf.id.clone());
@@ -161,7 +477,8 @@ impl WorkflowEngine {
         None
     }
 
-    /// Called when an agent session fails.
+    /// Called when an agent session fails. Runs the step's `on_failure` branch
+    /// if it has one, otherwise applies the step's failure policy.
     pub async fn on_agent_failed(&self, session_id: &str) -> Option<String> {
         let workflows = self.repo.list_workflows().await.ok()?;
         for wf in &workflows {
@@ -173,15 +490,26 @@ impl WorkflowEngine {
                 if step.session_id.as_deref() == Some(session_id)
                     && step.status == StepStatus::Running
                 {
+                    let _guard = self.lock_workflow(&wf.id).await;
+                    if !self.is_step_running(&wf.id, &step.id).await {
+                        return Some(wf.id.clone());
+                    }
                     let _ = self
                         .repo
                         .update_step_status(&step.id, StepStatus::Failed, None)
                         .await;
-                    // Mark workflow as failed
-                    let _ = self
-                        .repo
-                        .update_workflow_status(&wf.id, WorkflowStatus::Failed)
-                        .await;
+
+                    // Capture output so an on_failure branch can see what went wrong
+                    self.logs.flush().await;
+                    if let Ok(logs) = self.logs.query_logs_tail(session_id, 1000).await {
+                        if let Some(output) = extract_result_text(&logs) {
+                            let _ = self.repo.update_step_result(&step.id, &output).await;
+                        }
+                    }
+
+                    if let Ok(false) = self.handle_step_failure(&wf.id, step).await {
+                        let _ = self.advance(&wf.id).await;
+                    }
                     return Some(wf.id.clone());
                 }
             }
@@ -189,116 +517,519 @@ impl WorkflowEngine {
         None
     }
 
-    /// Advance the workflow: find unblocked pending steps and start them.
-    async fn advance(&self, workflow_id: &str) -> Result<(), DomainError> {
-        let steps = self.repo.get_steps(workflow_id).await?;
-        let edges = self.repo.get_edges(workflow_id).await?;
+    /// Record a gate result and advance the workflow. Ignored if the step was
+    /// stopped in the meantime.
+    async fn on_gate_finished(&self, outcome: GateOutcome) {
+        let _guard = self.lock_workflow(&outcome.workflow_id).await;
+        if !self.is_step_running(&outcome.workflow_id, &outcome.step_id).await {
+            return;
+        }
+        let Ok(steps) = self.repo.get_steps(&outcome.workflow_id).await else {
+            return;
+        };
+        let Some(step) = steps.iter().find(|s| s.id == outcome.step_id) else {
+            return;
+        };
+        let wf_id = outcome.workflow_id.as_str();
 
-        // Build set of completed step IDs
-        let completed: HashSet<String> = steps
+        let _ = self
+            .repo
+            .update_step_result(&step.id, &outcome.result.output)
+            .await;
+        if outcome.result.success {
+            let _ = self
+                .repo
+                .update_step_status(&step.id, StepStatus::Completed, None)
+                .await;
+            if let Ok(Some(workflow)) = self.repo.get_workflow(wf_id).await {
+                self.commit_step(&workflow, step).await;
+            }
+            let _ = self.advance(wf_id).await;
+        } else {
+            let _ = self
+                .repo
+                .update_step_status(&step.id, StepStatus::Failed, None)
+                .await;
+            if let Ok(false) = self.handle_step_failure(wf_id, step).await {
+                let _ = self.advance(wf_id).await;
+            }
+        }
+    }
+
+    /// Commit the working tree for a completed step that asks for it. Runs
+    /// before its children start, so the commit holds what the step (and
+    /// any step running beside it) changed. Failures are logged, not fatal.
+    async fn commit_step(&self, workflow: &Workflow, step: &WorkflowStep) {
+        let Some(template) = step.commit_message.as_deref() else {
+            return;
+        };
+        let template = if template.trim().is_empty() {
+            DEFAULT_COMMIT_MESSAGE
+        } else {
+            template
+        };
+        let message = render_template(template, &commit_vars(workflow, step));
+        let project_dir = self
+            .session_manager
+            .get_project_dir()
+            .await
+            .unwrap_or_else(|| ".".to_string());
+        if let Err(e) = crate::services::git_service::commit_all(&project_dir, &message) {
+            eprintln!("Failed to commit workflow step {}: {e}", step.id);
+        }
+    }
+
+    /// Handle a step that just failed. A step with `on_failure` edges has its
+    /// failure handled by that branch; otherwise its failure policy applies.
+    /// Returns true if the workflow itself was marked as failed.
+    async fn handle_step_failure(
+        &self,
+        workflow_id: &str,
+        step: &WorkflowStep,
+    ) -> Result<bool, DomainError> {
+        let edges = self.repo.get_edges(workflow_id).await?;
+        let has_failure_branch = edges
             .iter()
-            .filter(|s| s.status == StepStatus::Completed)
-            .map(|s| s.id.clone())
-            .collect();
+            .any(|e| e.source_step_id == step.id && e.condition == EdgeCondition::OnFailure);
+        if has_failure_branch {
+            return Ok(false);
+        }
 
-        // Find pending steps whose dependencies are all completed
-        let mut started_any = false;
-        for step in &steps {
-            if step.status != StepStatus::Pending {
-                continue;
+        match step.failure_policy {
+            FailurePolicy::FailWorkflow => {
+                self.repo
+                    .update_workflow_status(workflow_id, WorkflowStatus::Failed)
+                    .await?;
+                self.run_branches.lock().await.remove(workflow_id);
+                Ok(true)
             }
+            // Dependents treat the failed step as satisfied, and
+            // `skip_descendants` leaves them unreachable; `advance` handles both.
+            FailurePolicy::Continue | FailurePolicy::SkipDescendants => Ok(false),
+        }
+    }
 
-            let deps: Vec<&WorkflowEdge> = edges
-                .iter()
-                .filter(|e| e.target_step_id == step.id)
-                .collect();
+    /// Launch a claimed gate step's command in the background. The result
+    /// comes back through the gate listener.
+    async fn start_gate(&self, workflow_id: &str, step: &WorkflowStep) {
+        let command = step.gate_command.clone().unwrap_or_default();
+        let project_dir = self.session_manager.get_project_dir().await;
+        let tx = self.gate_tx.clone();
+        let workflow_id = workflow_id.to_string();
+        let step_id = step.id.clone();
+        let task = tokio::spawn(async move {
+            let result = match project_dir {
+                Some(dir) => run_gate(&command, &dir, MAX_RESULT_OUTPUT_LEN).await,
+                None => GateResult {
+                    success: false,
+                    output: "No project directory set".into(),
+                },
+            };
+            let _ = tx.send(GateOutcome {
+                workflow_id,
+                step_id,
+                result,
+            });
+        });
+        self.gate_tasks
+            .lock()
+            .await
+            .insert(step.id.clone(), task.abort_handle());
+    }
+
+    /// Resolve a step's base prompt. Steps bound to a spec use the spec's
+    /// prompt, with the step's own prompt appended as extra instructions.
+    async fn resolve_step_prompt(&self, step: &WorkflowStep) -> Result<String, DomainError> {
+        let own_prompt = self.render_step_template(step).await?;
+        let Some(spec_path) = step_spec_path(step) else {
+            return Ok(own_prompt);
+        };
+        let spec = self
+            .spec_manager
+            .get_spec(spec_path)
+            .await
+            .map_err(|e| DomainError::Process(format!("Failed to load spec {spec_path}: {e}")))?;
+        let spec_prompt = SpecManager::build_prompt_from_spec(&spec);
+        if own_prompt.trim().is_empty() {
+            Ok(spec_prompt)
+        } else {
+            Ok(format!(
+                "{}\n\n## Additional Instructions\n\n{}",
+                spec_prompt, own_prompt
+            ))
+        }
+    }
+
+    /// The step's own prompt: its prompt template rendered with the step's
+    /// variables (followed by any step prompt), or just the step prompt.
+    async fn render_step_template(&self, step: &WorkflowStep) -> Result<String, DomainError> {
+        let Some(template_id) = step.prompt_template_id.as_deref().filter(|id| !id.is_empty())
+        else {
+            return Ok(step.prompt.clone());
+        };
+        let template = self
+            .repo
+            .get_prompt_template(template_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::Process(format!("Prompt template not found: {template_id}"))
+            })?;
+
+        // Built-ins < template defaults < step overrides
+        let mut vars: HashMap<String, String> = HashMap::new();
+        vars.insert("agent_name".into(), step.agent_name.clone());
+        vars.insert("model".into(), step.model.clone());
+        vars.insert(
+            "spec_path".into(),
+            step_spec_path(step).unwrap_or_default().to_string(),
+        );
+        vars.extend(template.variables);
+        vars.extend(step.template_vars.clone());
+
+        let rendered = render_template(&template.body, &vars);
+        if step.prompt.trim().is_empty() {
+            Ok(rendered)
+        } else {
+            Ok(format!("{}\n\n{}", rendered, step.prompt))
+        }
+    }
 
-            let all_deps_met = deps
+    /// Advance the workflow: find unblocked pending steps and start them.
+    /// Callers must hold the workflow lock.
+    async fn advance(&self, workflow_id: &str) -> Result<(), DomainError> {
+        let workflow = self.repo.get_workflow(workflow_id).await?;
+        let max_parallel = workflow
+            .as_ref()
+            .and_then(|wf| wf.max_parallel)
+            .map(|n| n.max(1) as usize);
+
+        // Skipping an unreachable step or failing to start one may unblock
+        // or skip other steps, so re-evaluate until nothing changes.
+        loop {
+            let steps = self.repo.get_steps(workflow_id).await?;
+            let edges = self.repo.get_edges(workflow_id).await?;
+
+            // Agent steps past `max_parallel` wait for a running one to
+            // finish, which calls back in here. Ready steps start in canvas
+            // order, top to bottom, so the wait order is stable.
+            let mut running = steps
                 .iter()
-                .all(|e| completed.contains(&e.source_step_id));
+                .filter(|s| s.kind != StepKind::Gate && s.status == StepStatus::Running)
+                .count();
+            let mut ordered: Vec<&WorkflowStep> = steps.iter().collect();
+            ordered.sort_by(|a, b| {
+                a.position_y
+                    .total_cmp(&b.position_y)
+                    .then(a.position_x.total_cmp(&b.position_x))
+            });
 
-            if all_deps_met {
-                // Build effective prompt, injecting parent context if enabled
-                let effective_prompt = if step.pass_context {
-                    let parent_steps: Vec<&WorkflowStep> = deps
-                        .iter()
-                        .filter_map(|e| steps.iter().find(|s| s.id == e.source_step_id))
-                        .collect();
-                    let context_parts: Vec<String> = parent_steps
-                        .iter()
-                        .filter_map(|ps| {
-                            ps.result_output.as_ref().map(|out| {
-                                format!("=== Output from '{}' ===\n{}", ps.agent_name, out)
-                            })
-                        })
-                        .collect();
-                    if context_parts.is_empty() {
-                        step.prompt.clone()
-                    } else {
-                        format!(
-                            "Context from previous workflow steps:\n\n{}\n\n---\n\nYour task:\n{}",
-                            context_parts.join("\n\n"),
-                            step.prompt
-                        )
-                    }
-                } else {
-                    step.prompt.clone()
+            // Find pending steps whose dependencies are met (per join mode)
+            let mut started_any = false;
+            let mut reevaluate = false;
+            for step in ordered {
+                if step.status != StepStatus::Pending {
+                    continue;
+                }
+
+                let deps: Vec<&WorkflowEdge> = edges
+                    .iter()
+                    .filter(|e| e.target_step_id == step.id)
+                    .collect();
+
+                let states: Vec<EdgeState> =
+                    deps.iter().map(|e| edge_state(e, &steps)).collect();
+                let (deps_met, unreachable) = match step.join_mode {
+                    JoinMode::All => (
+                        states.iter().all(|s| *s == EdgeState::Satisfied),
+                        states.contains(&EdgeState::Dead),
+                    ),
+                    JoinMode::Any => (
+                        deps.is_empty() || states.contains(&EdgeState::Satisfied),
+                        !deps.is_empty() && states.iter().all(|s| *s == EdgeState::Dead),
+                    ),
                 };
 
-                // Start this step
-                match self
-                    .session_manager
-                    .start_agent(
-                        step.agent_name.clone(),
-                        step.model.clone(),
-                        effective_prompt,
-                    )
-                    .await
-                {
-                    Ok(session_id) => {
-                        self.repo
-                            .update_step_status(
-                                &step.id,
-                                StepStatus::Running,
-                                Some(session_id),
-                            )
-                            .await?;
-                        started_any = true;
+                if unreachable {
+                    self.repo
+                        .update_step_status(&step.id, StepStatus::Skipped, None)
+                        .await?;
+                    reevaluate = true;
+                } else if deps_met {
+                    let is_gate = step.kind == StepKind::Gate;
+                    if !is_gate && max_parallel.is_some_and(|max| running >= max) {
+                        continue;
+                    }
+                    // Claim the step first so it can never be started twice
+                    if !self.repo.claim_step(&step.id).await? {
+                        continue;
                     }
-                    Err(e) => {
-                        eprintln!("Failed to start workflow step {}: {}", step.id, e);
-                        self.repo
-                            .update_step_status(&step.id, StepStatus::Failed, None)
-                            .await?;
-                        self.repo
-                            .update_workflow_status(workflow_id, WorkflowStatus::Failed)
-                            .await?;
-                        return Ok(());
+                    started_any = true;
+                    if is_gate {
+                        self.start_gate(workflow_id, step).await;
+                        continue;
                     }
+                    running += 1;
+
+                    // Start the agent. The run's first step branches (when
+                    // auto-branching is on); the others join its branch.
+                    let run_branch = self.run_branches.lock().await.get(workflow_id).cloned();
+                    let started = match self.resolve_step_prompt(step).await {
+                        Ok(base_prompt) => {
+                            let effective_prompt =
+                                build_effective_prompt(step, &base_prompt, &deps, &steps);
+                            match run_branch.clone() {
+                                Some(branch) => {
+                                    self.session_manager
+                                        .start_agent_on_branch(
+                                            step.agent_name.clone(),
+                                            step.model.clone(),
+                                            effective_prompt,
+                                            branch,
+                                        )
+                                        .await
+                                }
+                                None => {
+                                    let label = workflow.as_ref().map(|wf| wf.name.as_str());
+                                    self.session_manager
+                                        .start_agent_for(
+                                            step.agent_name.clone(),
+                                            step.model.clone(),
+                                            effective_prompt,
+                                            label,
+                                            None,
+                                        )
+                                        .await
+                                }
+                            }
+                        }
+                        Err(e) => Err(e),
+                    };
+
+                    match started {
+                        Ok(session_id) => {
+                            if run_branch.is_none() {
+                                let branch = self
+                                    .session_manager
+                                    .get_session(&session_id)
+                                    .await
+                                    .and_then(|s| s.branch);
+                                self.run_branches
+                                    .lock()
+                                    .await
+                                    .insert(workflow_id.to_string(), branch);
+                            }
+                            // Bind the spec so its lifecycle follows the session
+                            // (assigned -> in_progress -> review).
+                            if let Some(spec_path) = step_spec_path(step) {
+                                if let Err(e) = self
+                                    .spec_manager
+                                    .assign_to_agent(spec_path, &step.agent_name, &session_id)
+                                    .await
+                                {
+                                    eprintln!("Failed to assign spec {spec_path}: {e}");
+                                }
+                            }
+                            self.repo
+                                .update_step_status(
+                                    &step.id,
+                                    StepStatus::Running,
+                                    Some(session_id),
+                                )
+                                .await?;
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to start workflow step {}: {}", step.id, e);
+                            self.repo
+                                .update_step_status(&step.id, StepStatus::Failed, None)
+                                .await?;
+                            if self.handle_step_failure(workflow_id, step).await? {
+                                return Ok(());
+                            }
+                            reevaluate = true;
+                        }
+                    }
+                }
+            }
+
+            if reevaluate {
+                continue;
+            }
+
+            // Check if workflow is complete (every step reached a terminal state)
+            if !started_any {
+                let all_done = steps.iter().all(|s| {
+                    matches!(
+                        s.status,
+                        StepStatus::Completed | StepStatus::Failed | StepStatus::Skipped
+                    )
+                });
+                if all_done && !steps.is_empty() {
+                    self.repo
+                        .update_workflow_status(workflow_id, WorkflowStatus::Completed)
+                        .await?;
+                    self.run_branches.lock().await.remove(workflow_id);
                 }
             }
+
+            return Ok(());
         }
+    }
+}
 
-        // Check if workflow is complete (all steps completed)
-        if !started_any {
-            let all_done = steps
-                .iter()
-                .all(|s| s.status == StepStatus::Completed);
-            if all_done && !steps.is_empty() {
-                self.repo
-                    .update_workflow_status(workflow_id, WorkflowStatus::Completed)
-                    .await?;
+/// Topologically sort steps with Kahn's algorithm, preserving the repository
+/// order among independent steps. Returns None if the graph has a cycle.
+fn topological_order(steps: &[WorkflowStep], edges: &[WorkflowEdge]) -> Option<Vec<String>> {
+    let mut in_degree: HashMap<&str, usize> =
+        steps.iter().map(|s| (s.id.as_str(), 0)).collect();
+    for edge in edges {
+        if let Some(deg) = in_degree.get_mut(edge.target_step_id.as_str()) {
+            *deg += 1;
+        }
+    }
+
+    let mut queue: std::collections::VecDeque<&str> = steps
+        .iter()
+        .map(|s| s.id.as_str())
+        .filter(|id| in_degree[id] == 0)
+        .collect();
+
+    let mut order = Vec::with_capacity(steps.len());
+    while let Some(node) = queue.pop_front() {
+        order.push(node.to_string());
+        for edge in edges.iter().filter(|e| e.source_step_id == node) {
+            if let Some(deg) = in_degree.get_mut(edge.target_step_id.as_str()) {
+                *deg -= 1;
+                if *deg == 0 {
+                    queue.push_back(edge.target_step_id.as_str());
+                }
             }
         }
+    }
 
-        Ok(())
+    if order.len() == steps.len() {
+        Some(order)
+    } else {
+        None
+    }
+}
+
+/// Evaluate an edge against its source step's current status.
+/// `on_success` edges also accept a failed source with the `continue` policy.
+fn edge_state(edge: &WorkflowEdge, steps: &[WorkflowStep]) -> EdgeState {
+    let Some(source) = steps.iter().find(|s| s.id == edge.source_step_id) else {
+        return EdgeState::Dead;
+    };
+    match (&edge.condition, &source.status) {
+        (_, StepStatus::Pending | StepStatus::Running) => EdgeState::Waiting,
+        (_, StepStatus::Skipped) => EdgeState::Dead,
+        (EdgeCondition::OnSuccess, StepStatus::Completed) => EdgeState::Satisfied,
+        (EdgeCondition::OnSuccess, StepStatus::Failed) => {
+            if source.failure_policy == FailurePolicy::Continue {
+                EdgeState::Satisfied
+            } else {
+                EdgeState::Dead
+            }
+        }
+        (EdgeCondition::OnFailure, StepStatus::Failed) => EdgeState::Satisfied,
+        (EdgeCondition::OnFailure, StepStatus::Completed) => EdgeState::Dead,
+    }
+}
+
+/// Display label for a step in injected context.
+fn step_label(step: &WorkflowStep) -> &str {
+    match step.kind {
+        StepKind::Gate => step.gate_command.as_deref().unwrap_or("gate"),
+        StepKind::Agent => &step.agent_name,
+    }
+}
+
+/// Replace `{{name}}` placeholders (whitespace inside the braces allowed).
+/// Unknown placeholders are left as-is so mistakes stay visible.
+fn render_template(body: &str, vars: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(body.len());
+    let mut rest = body;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        match vars.get(after[..end].trim()) {
+            Some(value) => out.push_str(value),
+            None => out.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Variables for a step's commit message template.
+fn commit_vars(workflow: &Workflow, step: &WorkflowStep) -> HashMap<String, String> {
+    let spec_path = step_spec_path(step).unwrap_or_default();
+    let spec = std::path::Path::new(spec_path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    HashMap::from([
+        ("workflow".to_string(), workflow.name.clone()),
+        ("step".to_string(), step_label(step).to_string()),
+        ("agent_name".to_string(), step.agent_name.clone()),
+        ("model".to_string(), step.model.clone()),
+        ("spec".to_string(), spec.to_string()),
+        ("spec_path".to_string(), spec_path.to_string()),
+    ])
+}
+
+/// The step's spec path, if it is bound to one.
+fn step_spec_path(step: &WorkflowStep) -> Option<&str> {
+    step.spec_path.as_deref().filter(|p| !p.is_empty())
+}
+
+/// Build a step's effective prompt, injecting parent context if enabled.
+/// Output from failed parents on `on_failure` edges is always injected.
+fn build_effective_prompt(
+    step: &WorkflowStep,
+    base_prompt: &str,
+    deps: &[&WorkflowEdge],
+    steps: &[WorkflowStep],
+) -> String {
+    let context_parts: Vec<String> = deps
+        .iter()
+        .filter_map(|e| {
+            let ps = steps.iter().find(|s| s.id == e.source_step_id)?;
+            let out = ps.result_output.as_ref()?;
+            match e.condition {
+                EdgeCondition::OnFailure if ps.status != StepStatus::Completed => Some(format!(
+                    "=== Failure output from '{}' ===\n{}",
+                    step_label(ps),
+                    out
+                )),
+                EdgeCondition::OnSuccess if step.pass_context => Some(format!(
+                    "=== Output from '{}' ===\n{}",
+                    step_label(ps),
+                    out
+                )),
+                _ => None,
+            }
+        })
+        .collect();
+    if context_parts.is_empty() {
+        base_prompt.to_string()
+    } else {
+        format!(
+            "Context from previous workflow steps:\n\n{}\n\n---\n\nYour task:\n{}",
+            context_parts.join("\n\n"),
+            base_prompt
+        )
     }
 }
 
 /// Extract the final result text from a session's log entries.
 /// Searches in reverse for a `result` message first, falling back to the last `assistant` message.
 /// Truncates to MAX_RESULT_OUTPUT_LEN to prevent context explosion.
-fn extract_result_text(logs: &[LogEntry]) -> Option<String> {
+pub(crate) fn extract_result_text(logs: &[LogEntry]) -> Option<String> {
     // Try to find the last result message
     for log in logs.iter().rev() {
         if log.message_type == "result" {
@@ -353,3 +1084,21 @@ fn truncate_str(s: &str, max_len: usize) -> String {
         format!("{}... [truncated]", &s[..max_len])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_template_substitutes_known_vars_only() {
+        let vars = HashMap::from([
+            ("spec_path".to_string(), "specs/auth.md".to_string()),
+            ("lang".to_string(), "Rust".to_string()),
+        ]);
+        assert_eq!(
+            render_template("Implement {{spec_path}} in {{ lang }}, keep {{unknown}}.", &vars),
+            "Implement specs/auth.md in Rust, keep {{unknown}}."
+        );
+        assert_eq!(render_template("open {{lang", &vars), "open {{lang");
+    }
+}
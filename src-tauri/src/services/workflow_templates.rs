@@ -0,0 +1,397 @@
+use crate::domain::models::{
+    EdgeCondition, FailurePolicy, JoinMode, StepKind, StepStatus, Workflow, WorkflowEdge,
+    WorkflowStatus, WorkflowStep,
+};
+use crate::domain::ports::WorkflowRepository;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// A step definition inside a template. `key` is local to the template and
+/// only used to wire up edges.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateStep {
+    pub key: String,
+    pub agent_name: String,
+    pub model: String,
+    pub prompt: String,
+    #[serde(default)]
+    pub position_x: f64,
+    #[serde(default)]
+    pub position_y: f64,
+    #[serde(default)]
+    pub pass_context: bool,
+    #[serde(default)]
+    pub failure_policy: FailurePolicy,
+    #[serde(default)]
+    pub join_mode: JoinMode,
+    #[serde(default)]
+    pub kind: StepKind,
+    #[serde(default)]
+    pub gate_command: Option<String>,
+    #[serde(default)]
+    pub prompt_template_id: Option<String>,
+    #[serde(default)]
+    pub template_vars: HashMap<String, String>,
+    #[serde(default)]
+    pub commit_message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateEdge {
+    pub source: String,
+    pub target: String,
+    #[serde(default)]
+    pub condition: EdgeCondition,
+}
+
+/// A reusable, project-independent workflow definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowTemplate {
+    /// File stem of the template on disk. Always taken from the file name on load.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub steps: Vec<TemplateStep>,
+    #[serde(default)]
+    pub edges: Vec<TemplateEdge>,
+}
+
+/// Result of instantiating a template into the current project.
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateInstantiation {
+    pub workflow: Workflow,
+    /// Agent names referenced by the template that don't exist in this project.
+    pub missing_agents: Vec<String>,
+}
+
+/// Workflow templates stored as JSON files under ~/.clautron/templates/,
+/// shared across all projects.
+pub struct WorkflowTemplateStore {
+    dir: PathBuf,
+}
+
+impl WorkflowTemplateStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// List all templates, sorted by name.
+    pub fn list(&self) -> Result<Vec<WorkflowTemplate>, String> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut templates = Vec::new();
+        let entries = std::fs::read_dir(&self.dir).map_err(|e| e.to_string())?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let id = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(stem) => stem.to_string(),
+                None => continue,
+            };
+            match self.get(&id) {
+                Ok(template) => templates.push(template),
+                Err(e) => eprintln!("Failed to parse template {}: {}", path.display(), e),
+            }
+        }
+
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(templates)
+    }
+
+    /// Load a single template by ID.
+    pub fn get(&self, id: &str) -> Result<WorkflowTemplate, String> {
+        let path = self.template_path(id)?;
+        let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let mut template: WorkflowTemplate =
+            serde_json::from_str(&content).map_err(|e| format!("Invalid template: {e}"))?;
+        template.id = id.to_string();
+        Ok(template)
+    }
+
+    /// Snapshot an existing workflow's steps and edges as a new template.
+    pub async fn save_from_workflow(
+        &self,
+        repo: &dyn WorkflowRepository,
+        workflow_id: &str,
+        name: Option<String>,
+    ) -> Result<WorkflowTemplate, String> {
+        let workflow = repo
+            .get_workflow(workflow_id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Workflow not found: {workflow_id}"))?;
+        let steps = repo.get_steps(workflow_id).await.map_err(|e| e.to_string())?;
+        let edges = repo.get_edges(workflow_id).await.map_err(|e| e.to_string())?;
+
+        // Step IDs are replaced by short positional keys so templates stay readable.
+        let keys: HashMap<&str, String> = steps
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.id.as_str(), format!("step-{}", i + 1)))
+            .collect();
+
+        let mut template = WorkflowTemplate {
+            id: String::new(),
+            name: name.unwrap_or(workflow.name),
+            description: workflow.description,
+            steps: steps
+                .iter()
+                .map(|s| TemplateStep {
+                    key: keys[s.id.as_str()].clone(),
+                    agent_name: s.agent_name.clone(),
+                    model: s.model.clone(),
+                    prompt: s.prompt.clone(),
+                    position_x: s.position_x,
+                    position_y: s.position_y,
+                    pass_context: s.pass_context,
+                    failure_policy: s.failure_policy.clone(),
+                    join_mode: s.join_mode.clone(),
+                    kind: s.kind.clone(),
+                    gate_command: s.gate_command.clone(),
+                    prompt_template_id: s.prompt_template_id.clone(),
+                    template_vars: s.template_vars.clone(),
+                    commit_message: s.commit_message.clone(),
+                })
+                .collect(),
+            edges: edges
+                .iter()
+                .filter_map(|e| {
+                    Some(TemplateEdge {
+                        source: keys.get(e.source_step_id.as_str())?.clone(),
+                        target: keys.get(e.target_step_id.as_str())?.clone(),
+                        condition: e.condition.clone(),
+                    })
+                })
+                .collect(),
+        };
+
+        template.id = self.write_new(&template)?;
+        Ok(template)
+    }
+
+    /// Delete a template by ID.
+    pub fn delete(&self, id: &str) -> Result<(), String> {
+        let path = self.template_path(id)?;
+        std::fs::remove_file(path).map_err(|e| e.to_string())
+    }
+
+    /// Create a concrete workflow in the current project from a template.
+    /// Steps keep their agent names even if the agent is missing; missing
+    /// names are reported so the user can fix them before running.
+    pub async fn instantiate(
+        &self,
+        repo: &dyn WorkflowRepository,
+        template_id: &str,
+        name: Option<String>,
+        available_agents: &[String],
+    ) -> Result<TemplateInstantiation, String> {
+        let template = self.get(template_id)?;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let workflow = Workflow {
+            id: Uuid::new_v4().to_string(),
+            name: name.unwrap_or_else(|| template.name.clone()),
+            description: template.description.clone(),
+            status: WorkflowStatus::Draft,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+            max_parallel: None,
+        };
+        repo.save_workflow(&workflow).await.map_err(|e| e.to_string())?;
+
+        let mut step_ids: HashMap<&str, String> = HashMap::new();
+        let mut missing_agents = Vec::new();
+        for ts in &template.steps {
+            if ts.kind == StepKind::Agent
+                && !available_agents.contains(&ts.agent_name)
+                && !missing_agents.contains(&ts.agent_name)
+            {
+                missing_agents.push(ts.agent_name.clone());
+            }
+
+            let step = WorkflowStep {
+                id: Uuid::new_v4().to_string(),
+                workflow_id: workflow.id.clone(),
+                agent_name: ts.agent_name.clone(),
+                model: ts.model.clone(),
+                prompt: ts.prompt.clone(),
+                spec_path: None,
+                status: StepStatus::Pending,
+                session_id: None,
+                position_x: ts.position_x,
+                position_y: ts.position_y,
+                created_at: now.clone(),
+                pass_context: ts.pass_context,
+                result_output: None,
+                failure_policy: ts.failure_policy.clone(),
+                join_mode: ts.join_mode.clone(),
+                kind: ts.kind.clone(),
+                gate_command: ts.gate_command.clone(),
+                prompt_template_id: ts.prompt_template_id.clone(),
+                template_vars: ts.template_vars.clone(),
+                commit_message: ts.commit_message.clone(),
+            };
+            repo.save_step(&step).await.map_err(|e| e.to_string())?;
+            step_ids.insert(ts.key.as_str(), step.id);
+        }
+
+        for te in &template.edges {
+            let (Some(source), Some(target)) =
+                (step_ids.get(te.source.as_str()), step_ids.get(te.target.as_str()))
+            else {
+                eprintln!(
+                    "Template {} has an edge to an unknown step: {} -> {}",
+                    template_id, te.source, te.target
+                );
+                continue;
+            };
+            let edge = WorkflowEdge {
+                id: Uuid::new_v4().to_string(),
+                workflow_id: workflow.id.clone(),
+                source_step_id: source.clone(),
+                target_step_id: target.clone(),
+                condition: te.condition.clone(),
+            };
+            repo.save_edge(&edge).await.map_err(|e| e.to_string())?;
+        }
+
+        Ok(TemplateInstantiation {
+            workflow,
+            missing_agents,
+        })
+    }
+
+    /// Resolve a template ID to its file, rejecting anything that could
+    /// escape the templates directory.
+    fn template_path(&self, id: &str) -> Result<PathBuf, String> {
+        if id.is_empty() || !id.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+            return Err(format!("Invalid template id: {id}"));
+        }
+        Ok(self.dir.join(format!("{id}.json")))
+    }
+
+    /// Add templates from another machine, skipping any whose name is
+    /// already taken. Returns how many were added.
+    pub fn import(&self, templates: &[WorkflowTemplate]) -> Result<usize, String> {
+        let existing: Vec<String> = self.list()?.into_iter().map(|t| t.name).collect();
+        let mut added = 0;
+        for template in templates.iter().filter(|t| !existing.contains(&t.name)) {
+            self.write_new(template)?;
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    /// Write a template under a fresh, unique file name. Returns its ID.
+    fn write_new(&self, template: &WorkflowTemplate) -> Result<String, String> {
+        if !self.dir.exists() {
+            std::fs::create_dir_all(&self.dir).map_err(|e| e.to_string())?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let _ = std::fs::set_permissions(&self.dir, std::fs::Permissions::from_mode(0o700));
+            }
+        }
+
+        let base = slugify(&template.name);
+        let base = if base.is_empty() { "template".to_string() } else { base };
+        let json = serde_json::to_string_pretty(template).map_err(|e| e.to_string())?;
+
+        // `{base}`, then `{base}-2`, `{base}-3`, ... Creating the file claims
+        // its name, so two saves under the same name never share a file.
+        let mut n = 1;
+        loop {
+            let id = if n == 1 { base.clone() } else { format!("{base}-{n}") };
+            let mut options = std::fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt;
+                options.mode(0o600);
+            }
+            match options.open(self.dir.join(format!("{id}.json"))) {
+                Ok(mut file) => {
+                    use std::io::Write;
+                    file.write_all(json.as_bytes()).map_err(|e| e.to_string())?;
+                    return Ok(id);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => n += 1,
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+    }
+}
+
+/// Convert a name to a URL-safe filename slug.
+fn slugify(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::sqlite_workflow_repository::SqliteWorkflowRepository;
+
+    #[test]
+    fn templates_round_trip_and_never_share_a_file() {
+        let root = std::env::temp_dir().join(format!("clautron-templates-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        let store = WorkflowTemplateStore::new(root.join("templates"));
+        let repo = SqliteWorkflowRepository::new(root.join("db.sqlite").to_string_lossy().into());
+        let template: WorkflowTemplate = serde_json::from_str(
+            r#"{"name": "Build & review", "steps": [
+                {"key": "build", "agent_name": "dev", "model": "sonnet", "prompt": "Build it"},
+                {"key": "review", "agent_name": "reviewer", "model": "opus", "prompt": "Review it"}
+            ], "edges": [{"source": "build", "target": "review"}]}"#,
+        )
+        .unwrap();
+
+        tauri::async_runtime::block_on(async {
+            repo.init().await.unwrap();
+            let id = store.write_new(&template).unwrap();
+            assert_eq!(id, "build-review");
+
+            let created = store
+                .instantiate(&repo, &id, None, &["dev".to_string()])
+                .await
+                .unwrap();
+            assert_eq!(created.workflow.name, "Build & review");
+            assert_eq!(created.missing_agents, ["reviewer"]);
+            let steps = repo.get_steps(&created.workflow.id).await.unwrap();
+            assert_eq!(steps.len(), 2);
+            assert!(steps.iter().any(|s| s.agent_name == "reviewer"));
+            assert_eq!(repo.get_edges(&created.workflow.id).await.unwrap().len(), 1);
+
+            // Saving it back under the same name takes the next free ID
+            let saved = store
+                .save_from_workflow(&repo, &created.workflow.id, None)
+                .await
+                .unwrap();
+            assert_eq!(saved.id, "build-review-2");
+            assert_eq!(saved.steps.len(), 2);
+            assert_eq!(saved.edges.len(), 1);
+        });
+        assert_eq!(store.write_new(&template).unwrap(), "build-review-3");
+
+        let mut ids: Vec<String> = store.list().unwrap().into_iter().map(|t| t.id).collect();
+        ids.sort();
+        assert_eq!(ids, ["build-review", "build-review-2", "build-review-3"]);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -21,9 +21,10 @@ import { StartAgentDialog } from "./components/dashboard/StartAgentDialog";
 import { SpecsView } from "./components/specs/SpecsView";
 import { WorkflowsView } from "./components/workflow/WorkflowsView";
 import { AgentTemplatesView } from "./components/agents/AgentTemplatesView";
+import { SettingsView } from "./components/settings/SettingsView";
 import { Toaster, toast } from "sonner";
 import * as tauri from "./lib/tauri";
-import type { AgentConfigChangedEvent, UnapprovedAgent } from "./lib/types";
+import type { AgentConfigChangedEvent, AppConfig, UnapprovedAgent } from "./lib/types";
 
 function App() {
   useAgentEvents();
@@ -106,18 +107,32 @@ function App() {
     return () => unlisten?.();
   }, [loadConfigs]);
 
+  // config.json edited outside the app (scripts, another instance)
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<AppConfig>("config:changed", () => {
+      toast.info("Settings reloaded from config.json");
+    }).then((fn) => {
+      unlisten = fn;
+    });
+    return () => unlisten?.();
+  }, []);
+
   const handleProjectSelected = useCallback(async (path: string) => {
-    await tauri.setProjectPath(path);
-    setProjectPath(path);
+    try {
+      await tauri.openProject(path);
+      setProjectPath(path);
+    } catch (err) {
+      toast.error(`Failed to open project: ${err}`);
+    }
   }, []);
 
   const handleChangeProject = useCallback(async () => {
     const selected = await open({ directory: true, multiple: false });
     if (selected) {
-      await tauri.setProjectPath(selected as string);
-      setProjectPath(selected as string);
+      await handleProjectSelected(selected as string);
     }
-  }, []);
+  }, [handleProjectSelected]);
 
   const handleAuthenticated = useCallback(() => {
     setIsAuthenticated(true);
@@ -184,6 +199,7 @@ function App() {
         onViewChange={setActiveView}
         projectPath={projectPath}
         onChangeProject={handleChangeProject}
+        onOpenProject={handleProjectSelected}
       />
 
       <main className="flex flex-1 flex-col overflow-hidden">
@@ -202,9 +218,7 @@ function App() {
         ) : activeView === "costs" ? (
           <CostDashboard />
         ) : activeView === "settings" ? (
-          <div className="flex flex-1 items-center justify-center text-zinc-500">
-            <p className="text-sm">Settings — coming soon</p>
-          </div>
+          <SettingsView />
         ) : null}
       </main>
 
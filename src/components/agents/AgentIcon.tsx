@@ -0,0 +1,46 @@
+import {
+  Book,
+  Bot,
+  Bug,
+  Code,
+  Database,
+  Eye,
+  FlaskConical,
+  GitBranch,
+  Pen,
+  Rocket,
+  Search,
+  Shield,
+  Sparkles,
+  SquareTerminal,
+  Wrench,
+  type LucideIcon,
+} from "lucide-react";
+
+/** Mirrors `agent_parser::AGENT_ICONS` on the Rust side. */
+export const AGENT_ICONS: Record<string, LucideIcon> = {
+  bot: Bot,
+  book: Book,
+  bug: Bug,
+  code: Code,
+  database: Database,
+  eye: Eye,
+  flask: FlaskConical,
+  "git-branch": GitBranch,
+  pen: Pen,
+  rocket: Rocket,
+  search: Search,
+  shield: Shield,
+  sparkles: Sparkles,
+  terminal: SquareTerminal,
+  wrench: Wrench,
+};
+
+/** The agent's icon in its color, or a plain color dot when it has none. */
+export function AgentIcon({ icon, color, size = 12 }: { icon: string | null; color: string; size?: number }) {
+  const Icon = icon ? AGENT_ICONS[icon] : undefined;
+  if (!Icon) {
+    return <div className="flex-shrink-0 rounded-full" style={{ width: size, height: size, backgroundColor: color }} />;
+  }
+  return <Icon size={size + 2} className="flex-shrink-0" style={{ color }} />;
+}
@@ -60,21 +60,24 @@ function layoutNodesInCircle(
   });
 }
 
+// Observed edges are solid; declared-only pairings are dashed; both is blue
 function relationshipsToEdges(relationships: AgentRelationship[]): Edge[] {
   return relationships.map((rel, i) => {
     const isMulti = rel.edge_count > 1;
+    const observed = rel.edge_count > 0;
     return {
       id: `rel-${i}`,
       source: rel.source_agent,
       target: rel.target_agent,
       animated: false,
-      label: rel.workflow_names.join(", "),
+      label: observed ? rel.workflow_names.join(", ") : "pairs with",
       labelStyle: { fontSize: 10, fill: "#a1a1aa" },
       labelBgStyle: { fill: "#18181b", fillOpacity: 0.9 },
       labelBgPadding: [4, 2] as [number, number],
       style: {
-        stroke: "#52525b",
+        stroke: rel.declared && observed ? "#3b82f6" : "#52525b",
         strokeWidth: isMulti ? 3 : 2,
+        strokeDasharray: observed ? undefined : "6 4",
       },
     };
   });
@@ -98,9 +101,10 @@ export function AgentRelationshipDiagram() {
     }
   }, []);
 
+  // Refetch when agents change, since `pairs_with:` lives in their files
   useEffect(() => {
     fetchRelationships();
-  }, [fetchRelationships]);
+  }, [fetchRelationships, configs]);
 
   const sessionMap = useMemo(() => {
     const map = new Map<string, { agent_name: string; status: string }>();
@@ -140,6 +144,11 @@ export function AgentRelationshipDiagram() {
           {relationships.length > 0 &&
             ` \u00b7 ${relationships.length} relationship${relationships.length !== 1 ? "s" : ""}`}
         </span>
+        {relationships.some((r) => r.declared) && (
+          <span className="text-[11px] text-zinc-500">
+            solid: in workflows · dashed: declared · blue: both
+          </span>
+        )}
         <div className="flex-1" />
         <button
           onClick={fetchRelationships}
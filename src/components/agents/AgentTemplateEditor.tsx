@@ -4,17 +4,21 @@ import { EditorView, keymap } from "@codemirror/view";
 import { markdown } from "@codemirror/lang-markdown";
 import { oneDark } from "@codemirror/theme-one-dark";
 import { defaultKeymap, history, historyKeymap } from "@codemirror/commands";
-import { Save, X, Sparkles, Loader2 } from "lucide-react";
+import { Save, X, Loader2, FlaskConical, History } from "lucide-react";
 import { useAgentStore } from "../../stores/agentStore";
-import type { AgentConfig, AgentConfigUpdate } from "../../lib/types";
-import { AGENT_COLOR_OPTIONS, AGENT_COLORS } from "../../lib/types";
-import { generateText } from "../../lib/tauri";
+import type { AgentConfig, AgentConfigUpdate, AgentStats, AgentTestRun, AgentVersion } from "../../lib/types";
+import { AGENT_COLOR_OPTIONS, AGENT_COLORS, KNOWN_AGENT_TOOLS } from "../../lib/types";
+import { getAgentStats, getAgentVersions, testAgent } from "../../lib/tauri";
+import { AGENT_ICONS } from "./AgentIcon";
 
 export function AgentTemplateEditor() {
   const configs = useAgentStore((s) => s.configs);
   const selectedAgentPath = useAgentStore((s) => s.selectedAgentPath);
   const updateAgent = useAgentStore((s) => s.updateAgent);
   const selectAgent = useAgentStore((s) => s.selectAgent);
+  const restoreAgentVersion = useAgentStore((s) => s.restoreAgentVersion);
+  // Bumped after a restore so the editor remounts with the restored content
+  const [revision, setRevision] = useState(0);
 
   const config = useMemo(
     () => configs.find((c) => c.file_path === selectedAgentPath) ?? null,
@@ -31,9 +35,13 @@ export function AgentTemplateEditor() {
 
   return (
     <AgentEditorInner
-      key={config.file_path}
+      key={`${config.file_path}:${revision}`}
       config={config}
       updateAgent={updateAgent}
+      onRestore={async (versionId) => {
+        await restoreAgentVersion(config.file_path, versionId);
+        setRevision((r) => r + 1);
+      }}
       onClose={() => selectAgent(null)}
     />
   );
@@ -46,10 +54,12 @@ function getColorHex(color: string): string {
 function AgentEditorInner({
   config,
   updateAgent,
+  onRestore,
   onClose,
 }: {
   config: AgentConfig;
   updateAgent: (filePath: string, update: AgentConfigUpdate) => Promise<void>;
+  onRestore: (versionId: string) => Promise<void>;
   onClose: () => void;
 }) {
   const editorRef = useRef<HTMLDivElement>(null);
@@ -60,12 +70,24 @@ function AgentEditorInner({
   const [description, setDescription] = useState(config.description);
   const [model, setModel] = useState(config.model);
   const [color, setColor] = useState(config.color);
+  const [icon, setIcon] = useState<string | null>(config.icon);
+  const [tools, setTools] = useState<string[] | null>(config.tools);
+  const [pairsWith, setPairsWith] = useState<string[]>(config.pairs_with);
+  const otherAgents = useAgentStore((s) => s.configs).filter((c) => c.name !== config.name);
+  const [mcpTool, setMcpTool] = useState("");
+  const [stats, setStats] = useState<AgentStats | null>(null);
   const [dirty, setDirty] = useState(false);
 
-  const [showGenerate, setShowGenerate] = useState(false);
-  const [genDescription, setGenDescription] = useState("");
-  const [generating, setGenerating] = useState(false);
-  const [genError, setGenError] = useState<string | null>(null);
+
+  const [showTest, setShowTest] = useState(false);
+  const [testPrompt, setTestPrompt] = useState("");
+  const [testing, setTesting] = useState(false);
+  const [testRun, setTestRun] = useState<AgentTestRun | null>(null);
+  const [testError, setTestError] = useState<string | null>(null);
+
+  const [showHistory, setShowHistory] = useState(false);
+  const [versions, setVersions] = useState<AgentVersion[]>([]);
+  const [historyError, setHistoryError] = useState<string | null>(null);
 
   // Debounced save
   const debouncedSave = useCallback(
@@ -86,47 +108,78 @@ function AgentEditorInner({
         description,
         model,
         color,
+        icon,
         ...overrides,
       };
       setDirty(true);
       debouncedSave(update);
     },
-    [name, description, model, color, debouncedSave],
+    [name, description, model, color, icon, debouncedSave],
   );
 
-  const handleGenerate = useCallback(async () => {
-    const desc = genDescription.trim();
-    if (!desc || generating) return;
-    setGenerating(true);
-    setGenError(null);
+  function togglePair(agentName: string) {
+    const next = pairsWith.includes(agentName)
+      ? pairsWith.filter((n) => n !== agentName)
+      : [...pairsWith, agentName];
+    setPairsWith(next);
+    saveFields({ pairs_with: next });
+  }
+
+  function saveTools(next: string[] | null) {
+    setTools(next);
+    saveFields({ tools: next });
+  }
+
+  function toggleTool(tool: string) {
+    const current = tools ?? [];
+    saveTools(current.includes(tool) ? current.filter((t) => t !== tool) : [...current, tool]);
+  }
+
+  function addMcpTool() {
+    const tool = mcpTool.trim();
+    if (!tool.startsWith("mcp__") || tools?.includes(tool)) return;
+    saveTools([...(tools ?? []), tool]);
+    setMcpTool("");
+  }
+
+  useEffect(() => {
+    if (!showHistory) return;
+    getAgentVersions(config.file_path)
+      .then(setVersions)
+      .catch((e) => setHistoryError(String(e)));
+  }, [showHistory, config.file_path]);
+
+  async function handleRestore(version: AgentVersion) {
+    if (!confirm(`Restore the version saved ${new Date(version.saved_at).toLocaleString()}?`)) return;
+    setHistoryError(null);
     try {
-      const metaPrompt =
-        `Generate a system prompt body for a Claude Code agent.\n\n` +
-        `Agent purpose: ${desc}\n\n` +
-        `Rules:\n` +
-        `- Output ONLY the system prompt body in markdown\n` +
-        `- No YAML frontmatter, no preamble, no "Here is..." intro\n` +
-        `- Define the agent's role, approach, capabilities, and key instructions\n` +
-        `- Use markdown headers and bullet lists where helpful\n` +
-        `- Be specific and actionable, 200-500 words`;
-      const result = await generateText(metaPrompt);
-      // Insert result into the CodeMirror editor
-      const view = viewRef.current;
-      if (view) {
-        view.dispatch({
-          changes: { from: 0, to: view.state.doc.length, insert: result },
-        });
-      }
-      setShowGenerate(false);
-      setGenDescription("");
+      await onRestore(version.id);
     } catch (e) {
-      setGenError(e instanceof Error ? e.message : String(e));
+      setHistoryError(e instanceof Error ? e.message : String(e));
+    }
+  }
+
+  const handleTest = useCallback(async () => {
+    const prompt = testPrompt.trim();
+    if (!prompt || testing) return;
+    setTesting(true);
+    setTestError(null);
+    try {
+      setTestRun(await testAgent(config.file_path, prompt));
+    } catch (e) {
+      setTestError(e instanceof Error ? e.message : String(e));
     } finally {
-      setGenerating(false);
+      setTesting(false);
     }
-  }, [genDescription, generating]);
+  }, [config.file_path, testPrompt, testing]);
 
   // CodeMirror setup
+  useEffect(() => {
+    getAgentStats()
+      .then((all) => setStats(all.find((s) => s.agent_name === config.name) ?? null))
+      .catch(() => setStats(null));
+  }, [config.name]);
+
   useEffect(() => {
     if (!editorRef.current) return;
 
@@ -192,21 +245,28 @@ function AgentEditorInner({
         </div>
         <div className="flex items-center gap-2">
           <button
-            onClick={() => {
-              setShowGenerate((v) => {
-                if (!v) setGenDescription(description); // seed from sidebar field
-                return !v;
-              });
-              setGenError(null);
-            }}
+            onClick={() => setShowTest((v) => !v)}
+            title="Run this agent once in a read-only sandbox; not saved to history"
+            className={`flex items-center gap-1.5 rounded-md px-2.5 py-1 text-xs font-medium transition-colors ${
+              showTest
+                ? "bg-emerald-600 text-white"
+                : "border border-zinc-700 text-zinc-400 hover:border-emerald-500 hover:text-emerald-300"
+            }`}
+          >
+            <FlaskConical size={12} />
+            Test
+          </button>
+          <button
+            onClick={() => setShowHistory((v) => !v)}
+            title="Earlier saved versions of this agent"
             className={`flex items-center gap-1.5 rounded-md px-2.5 py-1 text-xs font-medium transition-colors ${
-              showGenerate
-                ? "bg-violet-600 text-white"
-                : "border border-zinc-700 text-zinc-400 hover:border-violet-500 hover:text-violet-300"
+              showHistory
+                ? "bg-blue-600 text-white"
+                : "border border-zinc-700 text-zinc-400 hover:border-blue-500 hover:text-blue-300"
             }`}
           >
-            <Sparkles size={12} />
-            Generate
+            <History size={12} />
+            History
           </button>
           <button
             onClick={onClose}
@@ -217,46 +277,79 @@ function AgentEditorInner({
         </div>
       </div>
 
-      {/* AI Generate panel */}
-      {showGenerate && (
-        <div className="flex-shrink-0 border-b border-zinc-800 bg-violet-950/20 px-4 py-3">
-          <p className="mb-2 text-[11px] text-violet-300/70">
-            Refine the description if needed — Claude will write the system prompt body.
+      {/* Version history panel */}
+      {showHistory && (
+        <div className="flex-shrink-0 border-b border-zinc-800 bg-blue-950/20 px-4 py-3">
+          {versions.length === 0 ? (
+            <p className="text-[11px] text-zinc-500">No earlier versions saved yet.</p>
+          ) : (
+            <ul className="max-h-48 space-y-1 overflow-y-auto">
+              {versions.map((v) => (
+                <li key={v.id} className="flex items-center gap-2 text-xs">
+                  <span className="text-zinc-300">{new Date(v.saved_at).toLocaleString()}</span>
+                  <span className="flex-1 truncate text-zinc-600">
+                    {v.content.length.toLocaleString()} chars
+                  </span>
+                  <button
+                    onClick={() => handleRestore(v)}
+                    className="rounded px-2 py-0.5 text-blue-400 hover:bg-blue-500/10 hover:text-blue-300"
+                  >
+                    Restore
+                  </button>
+                </li>
+              ))}
+            </ul>
+          )}
+          {historyError && (
+            <p className="mt-2 text-[11px] text-red-400">{historyError}</p>
+          )}
+        </div>
+      )}
+
+      {/* Test-run panel */}
+      {showTest && (
+        <div className="flex-shrink-0 border-b border-zinc-800 bg-emerald-950/20 px-4 py-3">
+          <p className="mb-2 text-[11px] text-emerald-300/70">
+            Runs the saved agent in an empty temp directory with read-only (plan) permissions.
+            Nothing is added to session history.
           </p>
           <div className="flex gap-2">
             <textarea
-              value={genDescription}
-              onChange={(e) => setGenDescription(e.target.value)}
-              onKeyDown={(e) => { if (e.key === "Enter" && !e.shiftKey) { e.preventDefault(); handleGenerate(); } }}
-              placeholder="e.g. An agent that reviews pull requests for security vulnerabilities..."
+              value={testPrompt}
+              onChange={(e) => setTestPrompt(e.target.value)}
+              onKeyDown={(e) => { if (e.key === "Enter" && !e.shiftKey) { e.preventDefault(); handleTest(); } }}
+              placeholder="Sample prompt, e.g. Explain how you would approach a flaky test..."
               rows={2}
-              disabled={generating}
-              className="flex-1 resize-none rounded border border-zinc-700 bg-surface-0 px-3 py-2 text-sm text-zinc-200 placeholder-zinc-600 outline-none focus:border-violet-500 disabled:opacity-50"
-              autoFocus
+              disabled={testing}
+              className="flex-1 resize-none rounded border border-zinc-700 bg-surface-0 px-3 py-2 text-sm text-zinc-200 placeholder-zinc-600 outline-none focus:border-emerald-500 disabled:opacity-50"
             />
-            <div className="flex flex-col gap-1.5">
-              <button
-                onClick={handleGenerate}
-                disabled={!genDescription.trim() || generating}
-                className="flex items-center gap-1.5 rounded-md bg-violet-600 px-3 py-1.5 text-xs font-medium text-white transition-colors hover:bg-violet-500 disabled:opacity-40"
-              >
-                {generating ? (
-                  <><Loader2 size={11} className="animate-spin" /> Generating…</>
-                ) : (
-                  <><Sparkles size={11} /> Generate</>
-                )}
-              </button>
-              <button
-                onClick={() => { setShowGenerate(false); setGenError(null); }}
-                disabled={generating}
-                className="rounded-md px-3 py-1.5 text-xs text-zinc-500 hover:text-zinc-300 disabled:opacity-40"
-              >
-                Cancel
-              </button>
-            </div>
+            <button
+              onClick={handleTest}
+              disabled={!testPrompt.trim() || testing || dirty}
+              title={dirty ? "Wait for pending changes to save" : undefined}
+              className="flex h-fit items-center gap-1.5 rounded-md bg-emerald-600 px-3 py-1.5 text-xs font-medium text-white transition-colors hover:bg-emerald-500 disabled:opacity-40"
+            >
+              {testing ? (
+                <><Loader2 size={11} className="animate-spin" /> Running…</>
+              ) : (
+                <><FlaskConical size={11} /> Run</>
+              )}
+            </button>
           </div>
-          {genError && (
-            <p className="mt-2 text-[11px] text-red-400">{genError}</p>
+          {testError && (
+            <p className="mt-2 text-[11px] text-red-400">{testError}</p>
+          )}
+          {testRun && (
+            <div className="mt-2 space-y-1">
+              <p className="text-[11px] text-zinc-500">
+                {testRun.status} · {(testRun.duration_ms / 1000).toFixed(1)}s ·{" "}
+                {(testRun.input_tokens + testRun.output_tokens).toLocaleString()} tokens · $
+                {testRun.cost_usd.toFixed(4)} · {testRun.transcript.length} messages
+              </p>
+              <pre className="max-h-48 overflow-y-auto whitespace-pre-wrap rounded border border-zinc-800 bg-surface-0 p-2 text-xs text-zinc-300">
+                {testRun.result ?? "(no result)"}
+              </pre>
+            </div>
           )}
         </div>
       )}
@@ -341,6 +434,155 @@ function AgentEditorInner({
               </div>
             </div>
 
+            {/* Icon */}
+            <div>
+              <label className="mb-1 block text-[11px] font-medium uppercase tracking-wider text-zinc-500">
+                Icon
+              </label>
+              <div className="flex flex-wrap gap-1">
+                <button
+                  onClick={() => {
+                    setIcon(null);
+                    saveFields({ icon: null });
+                  }}
+                  className={`rounded px-1.5 py-1 text-[10px] ${
+                    icon === null ? "bg-surface-3 text-zinc-100" : "text-zinc-500 hover:text-zinc-300"
+                  }`}
+                >
+                  None
+                </button>
+                {Object.entries(AGENT_ICONS).map(([value, Icon]) => (
+                  <button
+                    key={value}
+                    onClick={() => {
+                      setIcon(value);
+                      saveFields({ icon: value });
+                    }}
+                    title={value}
+                    className={`rounded p-1 ${
+                      icon === value ? "bg-surface-3 text-zinc-100" : "text-zinc-500 hover:text-zinc-300"
+                    }`}
+                  >
+                    <Icon size={14} />
+                  </button>
+                ))}
+              </div>
+            </div>
+
+            {/* Usage */}
+            {stats && (
+              <div>
+                <label className="mb-1 block text-[11px] font-medium uppercase tracking-wider text-zinc-500">
+                  Usage
+                </label>
+                <div className="grid grid-cols-2 gap-x-3 gap-y-0.5 text-xs text-zinc-400">
+                  <span>Runs</span>
+                  <span className="text-zinc-200">{stats.run_count}</span>
+                  <span>Success</span>
+                  <span className="text-zinc-200">{Math.round(stats.success_rate * 100)}%</span>
+                  <span>Errors</span>
+                  <span className="text-zinc-200">{Math.round(stats.error_rate * 100)}%</span>
+                  <span>Avg duration</span>
+                  <span className="text-zinc-200">
+                    {stats.avg_duration_secs != null ? `${Math.round(stats.avg_duration_secs)}s` : "-"}
+                  </span>
+                  <span>Tokens</span>
+                  <span className="text-zinc-200">
+                    {(stats.input_tokens + stats.output_tokens).toLocaleString()}
+                  </span>
+                  <span>Cost</span>
+                  <span className="text-zinc-200">
+                    ${stats.total_cost_usd.toFixed(2)} (avg ${stats.avg_cost_usd.toFixed(2)})
+                  </span>
+                  <span>Last used</span>
+                  <span className="text-zinc-200">
+                    {stats.last_used_at ? new Date(stats.last_used_at).toLocaleString() : "-"}
+                  </span>
+                </div>
+              </div>
+            )}
+
+            {/* Pairs with */}
+            {(otherAgents.length > 0 || pairsWith.length > 0) && (
+              <div>
+                <label className="mb-1 block text-[11px] font-medium uppercase tracking-wider text-zinc-500">
+                  Pairs with
+                </label>
+                <div className="flex flex-wrap gap-1">
+                  {otherAgents.map((a) => (
+                    <button
+                      key={a.file_path}
+                      onClick={() => togglePair(a.name)}
+                      className={`rounded px-2 py-0.5 text-[11px] ${
+                        pairsWith.includes(a.name)
+                          ? "bg-blue-600/30 text-blue-300"
+                          : "bg-surface-2 text-zinc-500 hover:text-zinc-300"
+                      }`}
+                    >
+                      {a.name}
+                    </button>
+                  ))}
+                  {pairsWith
+                    .filter((n) => !otherAgents.some((a) => a.name === n))
+                    .map((n) => (
+                      <button
+                        key={n}
+                        onClick={() => togglePair(n)}
+                        title="No agent with this name; click to remove"
+                        className="rounded bg-surface-2 px-2 py-0.5 text-[11px] text-amber-400 line-through"
+                      >
+                        {n}
+                      </button>
+                    ))}
+                </div>
+              </div>
+            )}
+
+            {/* Tools */}
+            <div>
+              <label className="mb-1 block text-[11px] font-medium uppercase tracking-wider text-zinc-500">
+                Tools
+              </label>
+              <label className="mb-2 flex items-center gap-1.5 text-xs text-zinc-300">
+                <input
+                  type="checkbox"
+                  checked={tools === null}
+                  onChange={(e) => saveTools(e.target.checked ? null : ["Read", "Grep", "Glob"])}
+                />
+                All tools
+              </label>
+              {tools !== null && (
+                <div className="space-y-1">
+                  <div className="grid grid-cols-2 gap-x-2 gap-y-0.5">
+                    {KNOWN_AGENT_TOOLS.map((tool) => (
+                      <label key={tool} className="flex items-center gap-1.5 text-[11px] text-zinc-400">
+                        <input type="checkbox" checked={tools.includes(tool)} onChange={() => toggleTool(tool)} />
+                        {tool}
+                      </label>
+                    ))}
+                  </div>
+                  {tools
+                    .filter((t) => t.startsWith("mcp__"))
+                    .map((tool) => (
+                      <label key={tool} className="flex items-center gap-1.5 break-all text-[11px] text-zinc-400">
+                        <input type="checkbox" checked onChange={() => toggleTool(tool)} />
+                        {tool}
+                      </label>
+                    ))}
+                  <input
+                    type="text"
+                    value={mcpTool}
+                    onChange={(e) => setMcpTool(e.target.value)}
+                    onKeyDown={(e) => {
+                      if (e.key === "Enter") addMcpTool();
+                    }}
+                    placeholder="mcp__server__tool"
+                    className="w-full rounded border border-zinc-700 bg-surface-2 px-2 py-1 font-mono text-[11px] text-zinc-300 outline-none focus:border-blue-500"
+                  />
+                </div>
+              )}
+            </div>
+
             {/* File path */}
             <div className="space-y-1 pt-2 text-[11px] text-zinc-500">
               <div className="font-medium uppercase tracking-wider">File</div>
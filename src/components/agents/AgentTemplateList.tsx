@@ -1,8 +1,10 @@
-import { useState } from "react";
-import { Plus, Bot, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import { Plus, Bot, Trash2, Library, Sparkles, Loader2, AlertTriangle } from "lucide-react";
 import { useAgentStore } from "../../stores/agentStore";
-import type { AgentConfig } from "../../lib/types";
+import * as tauri from "../../lib/tauri";
+import type { AgentConfig, AgentDiagnostic } from "../../lib/types";
 import { AGENT_COLORS, AGENT_COLOR_OPTIONS } from "../../lib/types";
+import { AgentIcon } from "./AgentIcon";
 
 export function AgentTemplateList() {
   const configs = useAgentStore((s) => s.configs);
@@ -10,12 +12,44 @@ export function AgentTemplateList() {
   const selectAgent = useAgentStore((s) => s.selectAgent);
   const createAgent = useAgentStore((s) => s.createAgent);
   const deleteAgent = useAgentStore((s) => s.deleteAgent);
+  const installTemplate = useAgentStore((s) => s.installTemplate);
+  const generateAgent = useAgentStore((s) => s.generateAgent);
 
   const [creating, setCreating] = useState(false);
   const [newName, setNewName] = useState("");
   const [newModel, setNewModel] = useState("sonnet");
   const [newDescription, setNewDescription] = useState("");
   const [newColor, setNewColor] = useState("blue");
+  const [library, setLibrary] = useState<AgentConfig[]>([]);
+  const [showLibrary, setShowLibrary] = useState(false);
+  const [installError, setInstallError] = useState<string | null>(null);
+  const [generating, setGenerating] = useState(false);
+  const [genError, setGenError] = useState<string | null>(null);
+  const [diagnostics, setDiagnostics] = useState<AgentDiagnostic[]>([]);
+
+  useEffect(() => {
+    tauri.listAgentTemplates().then(setLibrary).catch(() => setLibrary([]));
+  }, []);
+
+  useEffect(() => {
+    tauri.validateAgents().then(setDiagnostics).catch(() => setDiagnostics([]));
+  }, [configs]);
+
+  // Files that fail to parse are missing from `configs`; surface them here
+  const loaded = new Set(configs.map((c) => c.file_path));
+  const broken = diagnostics.filter((d) => d.severity === "error" && !loaded.has(d.file_path));
+
+  const installed = new Set(configs.map((c) => c.name));
+  const available = library.filter((t) => !installed.has(t.name));
+
+  async function handleInstall(name: string) {
+    setInstallError(null);
+    try {
+      await installTemplate(name);
+    } catch (e) {
+      setInstallError(String(e));
+    }
+  }
 
   async function handleCreate() {
     if (!newName.trim()) return;
@@ -25,6 +59,23 @@ export function AgentTemplateList() {
     setCreating(false);
   }
 
+  // Claude writes the description, model, tools and system prompt from the goals
+  async function handleGenerate() {
+    if (!newName.trim() || !newDescription.trim() || generating) return;
+    setGenerating(true);
+    setGenError(null);
+    try {
+      await generateAgent(newName.trim(), newDescription.trim(), newColor);
+      setNewName("");
+      setNewDescription("");
+      setCreating(false);
+    } catch (e) {
+      setGenError(String(e));
+    } finally {
+      setGenerating(false);
+    }
+  }
+
   function getColorHex(color: string): string {
     return AGENT_COLORS[color] ?? AGENT_COLOR_OPTIONS.find((c) => c.value === color)?.hex ?? "#71717a";
   }
@@ -33,15 +84,52 @@ export function AgentTemplateList() {
     <div className="flex h-full flex-col">
       <div className="flex items-center justify-between border-b border-zinc-800 px-4 py-3">
         <h2 className="text-sm font-semibold text-zinc-100">Agent Templates</h2>
-        <button
-          onClick={() => setCreating(true)}
-          className="flex items-center gap-1 rounded-md bg-blue-600 px-2.5 py-1 text-xs font-medium text-white transition-colors hover:bg-blue-500"
-        >
-          <Plus size={12} />
-          New Agent
-        </button>
+        <div className="flex items-center gap-1">
+          {available.length > 0 && (
+            <button
+              onClick={() => setShowLibrary((v) => !v)}
+              title="Install a built-in agent"
+              className="flex items-center gap-1 rounded-md px-2 py-1 text-xs text-zinc-400 transition-colors hover:bg-surface-2 hover:text-zinc-200"
+            >
+              <Library size={12} />
+              Library
+            </button>
+          )}
+          <button
+            onClick={() => setCreating(true)}
+            className="flex items-center gap-1 rounded-md bg-blue-600 px-2.5 py-1 text-xs font-medium text-white transition-colors hover:bg-blue-500"
+          >
+            <Plus size={12} />
+            New Agent
+          </button>
+        </div>
       </div>
 
+      {/* Built-in agents, shown by default while the project has none */}
+      {(showLibrary || configs.length === 0) && available.length > 0 && (
+        <div className="border-b border-zinc-800 px-4 py-3">
+          <div className="mb-2 text-[11px] font-medium uppercase tracking-wider text-zinc-500">Built-in agents</div>
+          <div className="space-y-1">
+            {available.map((t) => (
+              <div key={t.name} className="flex items-center gap-2">
+                <AgentIcon icon={t.icon} color={getColorHex(t.color)} size={10} />
+                <div className="min-w-0 flex-1">
+                  <div className="text-xs font-medium text-zinc-200">{t.name}</div>
+                  <div className="truncate text-[11px] text-zinc-500">{t.description}</div>
+                </div>
+                <button
+                  onClick={() => handleInstall(t.name)}
+                  className="rounded bg-surface-2 px-2 py-0.5 text-[11px] text-zinc-300 hover:bg-surface-3"
+                >
+                  Install
+                </button>
+              </div>
+            ))}
+          </div>
+          {installError && <div className="mt-2 text-[11px] text-red-400">{installError}</div>}
+        </div>
+      )}
+
       {/* New agent form */}
       {creating && (
         <div className="border-b border-zinc-800 px-4 py-3 space-y-2">
@@ -61,7 +149,7 @@ export function AgentTemplateList() {
             type="text"
             value={newDescription}
             onChange={(e) => setNewDescription(e.target.value)}
-            placeholder="Description (optional)..."
+            placeholder="Description, or goals to generate from..."
             className="w-full rounded border border-zinc-700 bg-surface-2 px-3 py-1.5 text-sm text-zinc-100 outline-none focus:border-blue-500"
           />
           <div className="flex items-center gap-2">
@@ -97,6 +185,18 @@ export function AgentTemplateList() {
             >
               Create
             </button>
+            <button
+              onClick={handleGenerate}
+              disabled={!newName.trim() || !newDescription.trim() || generating}
+              title="Let Claude write the description, model, tools and system prompt from the goals"
+              className="flex items-center gap-1 rounded bg-violet-600 px-3 py-1 text-xs font-medium text-white hover:bg-violet-500 disabled:opacity-40"
+            >
+              {generating ? (
+                <><Loader2 size={11} className="animate-spin" /> Generating…</>
+              ) : (
+                <><Sparkles size={11} /> Generate</>
+              )}
+            </button>
             <button
               onClick={() => setCreating(false)}
               className="rounded px-3 py-1 text-xs text-zinc-400 hover:text-zinc-200"
@@ -104,6 +204,7 @@ export function AgentTemplateList() {
               Cancel
             </button>
           </div>
+          {genError && <div className="text-[11px] text-red-400">{genError}</div>}
         </div>
       )}
 
@@ -125,8 +226,18 @@ export function AgentTemplateList() {
             onSelect={() => selectAgent(config.file_path)}
             onDelete={() => deleteAgent(config.file_path)}
             getColorHex={getColorHex}
+            diagnostics={diagnostics.filter((d) => d.file_path === config.file_path)}
           />
         ))}
+        {broken.map((d) => (
+          <div key={d.file_path} className="flex items-start gap-3 px-4 py-2.5" title={d.file_path}>
+            <AlertTriangle size={14} className="mt-0.5 flex-shrink-0 text-red-400" />
+            <div className="min-w-0 flex-1">
+              <div className="truncate font-mono text-xs text-zinc-400">{d.file_path.split("/").pop()}</div>
+              <div className="text-[11px] text-red-400">{d.message}</div>
+            </div>
+          </div>
+        ))}
       </div>
     </div>
   );
@@ -138,12 +249,14 @@ function AgentRow({
   onSelect,
   onDelete,
   getColorHex,
+  diagnostics,
 }: {
   config: AgentConfig;
   isSelected: boolean;
   onSelect: () => void;
   onDelete: () => void;
   getColorHex: (color: string) => string;
+  diagnostics: AgentDiagnostic[];
 }) {
   return (
     <button
@@ -154,16 +267,18 @@ function AgentRow({
           : "text-zinc-300 hover:bg-surface-1"
       }`}
     >
-      <div
-        className="h-3 w-3 flex-shrink-0 rounded-full"
-        style={{ backgroundColor: getColorHex(config.color) }}
-      />
+      <AgentIcon icon={config.icon} color={getColorHex(config.color)} />
       <div className="min-w-0 flex-1">
         <div className="flex items-center gap-2">
           <span className="truncate text-sm font-medium">{config.name}</span>
           <span className="flex-shrink-0 rounded bg-surface-2 px-1.5 py-0.5 text-[10px] font-medium text-zinc-400">
             {config.model}
           </span>
+          {diagnostics.length > 0 && (
+            <span title={diagnostics.map((d) => d.message).join("\n")}>
+              <AlertTriangle size={12} className="flex-shrink-0 text-amber-400" />
+            </span>
+          )}
         </div>
         {config.description && (
           <div className="mt-0.5 truncate text-[11px] text-zinc-500">
@@ -3,8 +3,13 @@ import { Panel, PanelGroup, PanelResizeHandle } from "react-resizable-panels";
 import { AgentTemplateList } from "./AgentTemplateList";
 import { AgentTemplateEditor } from "./AgentTemplateEditor";
 import { AgentRelationshipDiagram } from "./AgentRelationshipDiagram";
+import { HooksEditor } from "./HooksEditor";
+import { McpServersView } from "./McpServersView";
+import { MemoryEditor } from "./MemoryEditor";
+import { SkillsView } from "./SkillsView";
+import { SlashCommandsView } from "./SlashCommandsView";
 
-type Tab = "templates" | "relationships";
+type Tab = "templates" | "relationships" | "commands" | "skills" | "mcp" | "hooks" | "memory";
 
 export function AgentTemplatesView() {
   const [activeTab, setActiveTab] = useState<Tab>("templates");
@@ -23,6 +28,31 @@ export function AgentTemplatesView() {
           isActive={activeTab === "relationships"}
           onClick={() => setActiveTab("relationships")}
         />
+        <TabButton
+          label="Commands"
+          isActive={activeTab === "commands"}
+          onClick={() => setActiveTab("commands")}
+        />
+        <TabButton
+          label="Skills"
+          isActive={activeTab === "skills"}
+          onClick={() => setActiveTab("skills")}
+        />
+        <TabButton
+          label="MCP"
+          isActive={activeTab === "mcp"}
+          onClick={() => setActiveTab("mcp")}
+        />
+        <TabButton
+          label="Hooks"
+          isActive={activeTab === "hooks"}
+          onClick={() => setActiveTab("hooks")}
+        />
+        <TabButton
+          label="Memory"
+          isActive={activeTab === "memory"}
+          onClick={() => setActiveTab("memory")}
+        />
       </div>
 
       {/* Content */}
@@ -37,6 +67,16 @@ export function AgentTemplatesView() {
               <AgentTemplateEditor />
             </Panel>
           </PanelGroup>
+        ) : activeTab === "commands" ? (
+          <SlashCommandsView />
+        ) : activeTab === "skills" ? (
+          <SkillsView />
+        ) : activeTab === "mcp" ? (
+          <McpServersView />
+        ) : activeTab === "hooks" ? (
+          <HooksEditor />
+        ) : activeTab === "memory" ? (
+          <MemoryEditor />
         ) : (
           <AgentRelationshipDiagram />
         )}
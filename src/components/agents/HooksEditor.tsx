@@ -0,0 +1,159 @@
+import { useEffect, useMemo, useState } from "react";
+import { AlertTriangle, Save } from "lucide-react";
+import * as tauri from "../../lib/tauri";
+import type { HooksConfig, HooksSettings, SettingsScope } from "../../lib/types";
+import { HOOK_EVENTS } from "../../lib/types";
+
+const SCOPE_LABELS: Record<SettingsScope, { label: string; hint: string }> = {
+  project: { label: "Project", hint: ".claude/settings.json — shared with the team" },
+  project_local: { label: "Project (local)", hint: ".claude/settings.local.json — only you" },
+  user: { label: "User", hint: "~/.claude/settings.json — every project" },
+};
+
+const EXAMPLE = `{
+  "PostToolUse": [
+    {
+      "matcher": "Edit|Write",
+      "hooks": [{ "type": "command", "command": "npm run lint --silent", "timeout": 60 }]
+    }
+  ]
+}`;
+
+/** The `hooks` section of Claude Code's settings files, edited as JSON. */
+export function HooksEditor() {
+  const [files, setFiles] = useState<HooksSettings[]>([]);
+  const [scope, setScope] = useState<SettingsScope>("project");
+  const [text, setText] = useState("");
+  const [dirty, setDirty] = useState(false);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    tauri
+      .listHooks()
+      .then((all) => {
+        setFiles(all);
+        if (all.length > 0 && !all.some((f) => f.scope === scope)) setScope(all[0].scope);
+      })
+      .catch((e) => setError(String(e)));
+  }, []);
+
+  const current = files.find((f) => f.scope === scope) ?? null;
+
+  useEffect(() => {
+    const hooks = current?.hooks ?? {};
+    setText(Object.keys(hooks).length > 0 ? JSON.stringify(hooks, null, 2) : "");
+    setDirty(false);
+    setError(null);
+  }, [current?.scope, current?.path]);
+
+  // Parsed on every keystroke for the summary; the backend does the schema check on save
+  const parsed = useMemo((): { hooks: HooksConfig | null; jsonError: string | null } => {
+    if (!text.trim()) return { hooks: {}, jsonError: null };
+    try {
+      return { hooks: JSON.parse(text), jsonError: null };
+    } catch (e) {
+      return { hooks: null, jsonError: String(e) };
+    }
+  }, [text]);
+
+  async function handleSave() {
+    if (!parsed.hooks) return;
+    setSaving(true);
+    setError(null);
+    try {
+      const saved = await tauri.saveHooks(scope, parsed.hooks);
+      setFiles((all) => all.map((f) => (f.scope === saved.scope ? saved : f)));
+      setDirty(false);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setSaving(false);
+    }
+  }
+
+  const problems = error ? error.split("\n") : !dirty ? (current?.errors ?? []) : [];
+
+  return (
+    <div className="flex h-full flex-col">
+      <div className="flex items-center gap-2 border-b border-zinc-800 px-4 py-2">
+        {files.map((f) => (
+          <button
+            key={f.scope}
+            onClick={() => {
+              if (dirty && !confirm("Discard unsaved changes?")) return;
+              setScope(f.scope);
+            }}
+            title={SCOPE_LABELS[f.scope].hint}
+            className={`rounded-md px-2.5 py-1 text-xs transition-colors ${
+              f.scope === scope ? "bg-surface-2 text-zinc-100" : "text-zinc-500 hover:text-zinc-300"
+            }`}
+          >
+            {SCOPE_LABELS[f.scope].label}
+            {f.errors.length > 0 && <AlertTriangle size={10} className="ml-1 inline text-amber-400" />}
+          </button>
+        ))}
+        <div className="flex-1" />
+        {current && (
+          <span className="truncate text-[11px] text-zinc-600" title={current.path}>
+            {current.path}
+          </span>
+        )}
+        <button
+          onClick={handleSave}
+          disabled={!current || saving || !dirty || !parsed.hooks}
+          className="flex items-center gap-1 rounded-md bg-blue-600 px-2.5 py-1 text-xs font-medium text-white transition-colors hover:bg-blue-500 disabled:opacity-40"
+        >
+          <Save size={12} />
+          Save
+        </button>
+      </div>
+
+      {(parsed.jsonError || problems.length > 0) && (
+        <div className="space-y-0.5 border-b border-zinc-800 px-4 py-2 font-mono text-[11px] text-red-400">
+          {parsed.jsonError ? <div>{parsed.jsonError}</div> : problems.map((p, i) => <div key={i}>{p}</div>)}
+        </div>
+      )}
+
+      <div className="flex flex-1 overflow-hidden">
+        <textarea
+          value={text}
+          onChange={(e) => {
+            setText(e.target.value);
+            setDirty(true);
+            setError(null);
+          }}
+          placeholder={current ? `${SCOPE_LABELS[current.scope].hint}\n\n${EXAMPLE}` : ""}
+          spellCheck={false}
+          className="flex-1 resize-none bg-surface-0 p-4 font-mono text-[13px] text-zinc-200 placeholder-zinc-600 outline-none"
+        />
+        <div className="w-64 flex-shrink-0 overflow-y-auto border-l border-zinc-800 p-3">
+          <div className="mb-2 text-[11px] font-medium uppercase tracking-wider text-zinc-500">Configured</div>
+          {HOOK_EVENTS.map((event) => {
+            const groups = parsed.hooks?.[event];
+            if (!Array.isArray(groups) || groups.length === 0) return null;
+            return (
+              <div key={event} className="mb-3">
+                <div className="text-xs font-medium text-zinc-300">{event}</div>
+                {groups.map((group, i) => (
+                  <div key={i} className="mt-1 border-l border-zinc-700 pl-2">
+                    {group.matcher && <div className="font-mono text-[10px] text-blue-400">{group.matcher}</div>}
+                    {(Array.isArray(group.hooks) ? group.hooks : []).map((hook, j) => (
+                      <div key={j} className="truncate font-mono text-[10px] text-zinc-500" title={hook.command ?? hook.prompt}>
+                        {hook.command ?? hook.prompt}
+                        {hook.timeout ? ` (${hook.timeout}s)` : ""}
+                      </div>
+                    ))}
+                  </div>
+                ))}
+              </div>
+            );
+          })}
+          {parsed.hooks && Object.keys(parsed.hooks).length === 0 && (
+            <p className="text-[11px] text-zinc-600">No hooks in this file</p>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
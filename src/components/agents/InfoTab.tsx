@@ -1,4 +1,4 @@
-import { useMemo } from "react";
+import { useEffect, useMemo, useState } from "react";
 import {
   Clock,
   Cpu,
@@ -7,7 +7,8 @@ import {
   MessageSquare,
   Wrench,
 } from "lucide-react";
-import type { AgentSession } from "../../lib/types";
+import type { AgentSession, SessionCostBreakdown } from "../../lib/types";
+import * as tauri from "../../lib/tauri";
 import { useAgentStore } from "../../stores/agentStore";
 import { formatTokens } from "../../lib/formatters";
 import { extractToolCalls, extractFileActivity } from "../../lib/logParser";
@@ -16,7 +17,7 @@ interface Props {
   session: AgentSession;
 }
 
-// Model pricing per million tokens (approximate, configurable later)
+// Model pricing per million tokens, for a running session's cost so far
 const MODEL_PRICING: Record<string, { input: number; output: number }> = {
   opus: { input: 15, output: 75 },
   sonnet: { input: 3, output: 15 },
@@ -88,7 +89,27 @@ export function InfoTab({ session }: Props) {
     [sessionLogs],
   );
 
-  const cost = estimateCost(session.model, session.input_tokens, session.output_tokens);
+  const [breakdown, setBreakdown] = useState<SessionCostBreakdown | null>(null);
+  useEffect(() => {
+    tauri
+      .getSessionCostBreakdown(session.id)
+      .then(setBreakdown)
+      .catch(() => setBreakdown(null));
+  }, [session.id, session.status, session.cost_usd]);
+  const costliestTurns = useMemo(
+    () =>
+      [...(breakdown?.turns ?? [])]
+        .sort((a, b) => b.estimated_cost_usd - a.estimated_cost_usd)
+        .slice(0, 10),
+    [breakdown],
+  );
+
+  // The final cost, reported or estimated by the backend, once the result arrives
+  const hasCost = session.cost_usd > 0;
+  const cost = hasCost
+    ? session.cost_usd
+    : estimateCost(session.model, session.input_tokens, session.output_tokens);
+  const costEstimated = !hasCost || !!session.cost_estimated;
   const totalTokens = session.input_tokens + session.output_tokens;
 
   return (
@@ -109,9 +130,9 @@ export function InfoTab({ session }: Props) {
         />
         <StatCard
           icon={DollarSign}
-          label="Estimated Cost"
-          value={`$${cost.toFixed(4)}`}
-          subValue={`${session.model} pricing`}
+          label={costEstimated ? "Estimated Cost" : "Cost"}
+          value={`${costEstimated ? "~" : ""}$${cost.toFixed(4)}`}
+          subValue={costEstimated ? `From tokens at ${session.model} pricing` : "Reported by Claude Code"}
         />
         <StatCard
           icon={Wrench}
@@ -132,6 +153,33 @@ export function InfoTab({ session }: Props) {
         />
       </div>
 
+      {/* Cost by turn */}
+      {costliestTurns.length > 0 && (
+        <div className="mb-6 rounded-lg border border-zinc-800 bg-surface-1 p-4">
+          <h3 className="mb-3 text-xs font-medium uppercase tracking-wider text-zinc-500">
+            Costliest Turns
+          </h3>
+          <ul className="space-y-1.5 text-xs">
+            {costliestTurns.map((turn) => (
+              <li key={turn.log_id} className="flex items-baseline gap-3">
+                <span className="w-16 flex-shrink-0 text-right font-mono text-zinc-300">
+                  ${turn.estimated_cost_usd.toFixed(4)}
+                </span>
+                <span className="w-24 flex-shrink-0 text-zinc-500">
+                  {formatTokens(turn.input_tokens)} / {formatTokens(turn.output_tokens)}
+                </span>
+                <span className="min-w-0 flex-1 truncate text-zinc-400" title={turn.text_preview}>
+                  {turn.tools.length > 0 && (
+                    <span className="mr-1.5 font-mono text-blue-400">{turn.tools.join(", ")}</span>
+                  )}
+                  {turn.text_preview}
+                </span>
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+
       {/* Session metadata */}
       <div className="rounded-lg border border-zinc-800 bg-surface-1 p-4">
         <h3 className="mb-3 text-xs font-medium uppercase tracking-wider text-zinc-500">
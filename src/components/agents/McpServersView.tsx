@@ -0,0 +1,206 @@
+import { useCallback, useEffect, useState } from "react";
+import { Activity, Loader2, Plus, Server, Trash2 } from "lucide-react";
+import * as tauri from "../../lib/tauri";
+import type { McpHealth, McpScope, McpServer } from "../../lib/types";
+
+const HEALTH_COLORS: Record<McpHealth["status"], string> = {
+  ok: "text-green-400",
+  error: "text-red-400",
+  timeout: "text-amber-400",
+  unapproved: "text-amber-400",
+};
+
+/** MCP servers from `.mcp.json` and `~/.claude.json`, with a health check. */
+export function McpServersView() {
+  const [servers, setServers] = useState<McpServer[]>([]);
+  const [health, setHealth] = useState<Record<string, McpHealth>>({});
+  const [checking, setChecking] = useState(false);
+  const [adding, setAdding] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const [name, setName] = useState("");
+  const [scope, setScope] = useState<McpScope>("project");
+  const [transport, setTransport] = useState<McpServer["transport"]>("stdio");
+  const [commandLine, setCommandLine] = useState("");
+  const [url, setUrl] = useState("");
+
+  const load = useCallback(() => {
+    tauri
+      .listMcpServers()
+      .then(setServers)
+      .catch((e) => setError(String(e)));
+  }, []);
+
+  useEffect(load, [load]);
+
+  const key = (s: { scope: McpScope; name: string }) => `${s.scope}:${s.name}`;
+
+  async function handleCheck() {
+    setChecking(true);
+    setError(null);
+    try {
+      const results = await tauri.checkMcpServers();
+      setHealth(Object.fromEntries(results.map((h) => [key(h), h])));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setChecking(false);
+    }
+  }
+
+  async function handleAdd() {
+    // Split on whitespace; quote-free on purpose, like the CLI's `claude mcp add`
+    const [command, ...args] = commandLine.trim().split(/\s+/).filter(Boolean);
+    setError(null);
+    try {
+      await tauri.addMcpServer({
+        name: name.trim(),
+        scope,
+        transport,
+        command: transport === "stdio" ? (command ?? null) : null,
+        args: transport === "stdio" ? args : [],
+        env: {},
+        url: transport === "stdio" ? null : url.trim(),
+      });
+      setName("");
+      setCommandLine("");
+      setUrl("");
+      setAdding(false);
+      load();
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
+  async function handleApprove(server: McpServer) {
+    const commandLine = [server.command, ...server.args].join(" ");
+    if (!confirm(`Allow the health check to run "${commandLine}" from .mcp.json?`)) return;
+    setError(null);
+    try {
+      await tauri.approveMcpServer(server.name);
+      await handleCheck();
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
+  async function handleRemove(server: McpServer) {
+    if (!confirm(`Remove MCP server "${server.name}" from ${server.scope === "project" ? ".mcp.json" : "~/.claude.json"}?`)) return;
+    setError(null);
+    try {
+      await tauri.removeMcpServer(server.name, server.scope);
+      load();
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
+  const input = "rounded border border-zinc-700 bg-surface-2 px-2 py-1.5 text-sm text-zinc-100 outline-none focus:border-blue-500";
+
+  return (
+    <div className="flex h-full flex-col">
+      <div className="flex items-center justify-between border-b border-zinc-800 px-4 py-3">
+        <h2 className="text-sm font-semibold text-zinc-100">MCP Servers</h2>
+        <div className="flex items-center gap-2">
+          <button
+            onClick={handleCheck}
+            disabled={checking || servers.length === 0}
+            className="flex items-center gap-1 rounded-md border border-zinc-700 px-2.5 py-1 text-xs text-zinc-300 hover:border-zinc-500 disabled:opacity-40"
+          >
+            {checking ? <Loader2 size={12} className="animate-spin" /> : <Activity size={12} />}
+            Check health
+          </button>
+          <button
+            onClick={() => setAdding(true)}
+            className="flex items-center gap-1 rounded-md bg-blue-600 px-2.5 py-1 text-xs font-medium text-white hover:bg-blue-500"
+          >
+            <Plus size={12} />
+            Add
+          </button>
+        </div>
+      </div>
+
+      {adding && (
+        <div className="flex flex-wrap items-center gap-2 border-b border-zinc-800 px-4 py-3">
+          <input value={name} onChange={(e) => setName(e.target.value)} placeholder="name" className={`${input} w-32`} autoFocus />
+          <select value={scope} onChange={(e) => setScope(e.target.value as McpScope)} className={input}>
+            <option value="project">Project (.mcp.json)</option>
+            <option value="user">User (~/.claude.json)</option>
+          </select>
+          <select value={transport} onChange={(e) => setTransport(e.target.value as McpServer["transport"])} className={input}>
+            <option value="stdio">stdio</option>
+            <option value="http">http</option>
+            <option value="sse">sse</option>
+          </select>
+          {transport === "stdio" ? (
+            <input
+              value={commandLine}
+              onChange={(e) => setCommandLine(e.target.value)}
+              placeholder="npx -y @modelcontextprotocol/server-filesystem ."
+              className={`${input} min-w-64 flex-1 font-mono`}
+            />
+          ) : (
+            <input
+              value={url}
+              onChange={(e) => setUrl(e.target.value)}
+              placeholder="https://example.com/mcp"
+              className={`${input} min-w-64 flex-1`}
+            />
+          )}
+          <button onClick={handleAdd} className="rounded bg-blue-600 px-3 py-1 text-xs font-medium text-white hover:bg-blue-500">
+            Save
+          </button>
+          <button onClick={() => setAdding(false)} className="rounded px-3 py-1 text-xs text-zinc-400 hover:text-zinc-200">
+            Cancel
+          </button>
+        </div>
+      )}
+      {error && <div className="px-4 py-2 text-[11px] text-red-400">{error}</div>}
+
+      <div className="flex-1 overflow-y-auto">
+        {servers.length === 0 && !adding && (
+          <div className="flex flex-col items-center justify-center py-12 text-zinc-500">
+            <Server size={32} className="mb-2 opacity-50" />
+            <p className="text-sm">No MCP servers configured</p>
+          </div>
+        )}
+        {servers.map((s) => {
+          const h = health[key(s)];
+          return (
+            <div key={key(s)} className="group flex items-center gap-3 border-b border-zinc-800/50 px-4 py-2.5">
+              <div className="min-w-0 flex-1">
+                <div className="flex items-center gap-2">
+                  <span className="text-sm text-zinc-200">{s.name}</span>
+                  <span className="rounded bg-surface-2 px-1.5 text-[10px] text-zinc-500">{s.transport}</span>
+                  <span className="text-[10px] text-zinc-600">{s.scope}</span>
+                </div>
+                <div className="truncate font-mono text-[11px] text-zinc-500">
+                  {s.transport === "stdio" ? [s.command, ...s.args].join(" ") : s.url}
+                </div>
+                {h && (
+                  <div className={`truncate text-[11px] ${HEALTH_COLORS[h.status]}`} title={h.message}>
+                    {h.status} · {h.message} · {h.latency_ms}ms
+                  </div>
+                )}
+              </div>
+              {h?.status === "unapproved" && (
+                <button
+                  onClick={() => handleApprove(s)}
+                  className="rounded border border-zinc-700 px-2 py-0.5 text-[11px] text-zinc-300 hover:border-zinc-500"
+                >
+                  Approve
+                </button>
+              )}
+              <button
+                onClick={() => handleRemove(s)}
+                className="rounded p-1 text-zinc-600 opacity-0 hover:text-red-400 group-hover:opacity-100"
+              >
+                <Trash2 size={12} />
+              </button>
+            </div>
+          );
+        })}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,101 @@
+import { useEffect, useState } from "react";
+import { Save, FilePlus } from "lucide-react";
+import * as tauri from "../../lib/tauri";
+import type { MemoryFile, MemoryScope } from "../../lib/types";
+
+const SCOPE_LABELS: Record<MemoryScope, { label: string; hint: string }> = {
+  project: { label: "Project", hint: "CLAUDE.md — shared with the team" },
+  project_local: { label: "Project (local)", hint: "CLAUDE.local.md — only you, git-ignored" },
+  user: { label: "User", hint: "~/.claude/CLAUDE.md — every project" },
+};
+
+/** Read and edit the CLAUDE.md memory files every agent loads. */
+export function MemoryEditor() {
+  const [files, setFiles] = useState<MemoryFile[]>([]);
+  const [scope, setScope] = useState<MemoryScope>("project");
+  const [content, setContent] = useState("");
+  const [dirty, setDirty] = useState(false);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    tauri
+      .listMemoryFiles()
+      .then((all) => {
+        setFiles(all);
+        if (all.length > 0 && !all.some((f) => f.scope === scope)) setScope(all[0].scope);
+      })
+      .catch((e) => setError(String(e)));
+  }, []);
+
+  const current = files.find((f) => f.scope === scope) ?? null;
+
+  useEffect(() => {
+    setContent(current?.content ?? "");
+    setDirty(false);
+  }, [current?.scope, current?.path]);
+
+  async function handleSave() {
+    setSaving(true);
+    setError(null);
+    try {
+      const saved = await tauri.saveMemoryFile(scope, content);
+      setFiles((all) => all.map((f) => (f.scope === saved.scope ? saved : f)));
+      setDirty(false);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setSaving(false);
+    }
+  }
+
+  return (
+    <div className="flex h-full flex-col">
+      <div className="flex items-center gap-2 border-b border-zinc-800 px-4 py-2">
+        {files.map((f) => (
+          <button
+            key={f.scope}
+            onClick={() => {
+              if (dirty && !confirm("Discard unsaved changes?")) return;
+              setScope(f.scope);
+            }}
+            title={SCOPE_LABELS[f.scope].hint}
+            className={`rounded-md px-2.5 py-1 text-xs transition-colors ${
+              f.scope === scope
+                ? "bg-surface-2 text-zinc-100"
+                : "text-zinc-500 hover:text-zinc-300"
+            }`}
+          >
+            {SCOPE_LABELS[f.scope].label}
+            {!f.exists && <span className="ml-1 text-zinc-600">(none)</span>}
+          </button>
+        ))}
+        <div className="flex-1" />
+        {current && (
+          <span className="truncate text-[11px] text-zinc-600" title={current.path}>
+            {current.path}
+          </span>
+        )}
+        <button
+          onClick={handleSave}
+          disabled={!current || saving || (current.exists && !dirty)}
+          className="flex items-center gap-1 rounded-md bg-blue-600 px-2.5 py-1 text-xs font-medium text-white transition-colors hover:bg-blue-500 disabled:opacity-40"
+        >
+          {current?.exists ? <Save size={12} /> : <FilePlus size={12} />}
+          {current?.exists ? "Save" : "Create"}
+        </button>
+      </div>
+      {error && <div className="px-4 py-1 text-[11px] text-red-400">{error}</div>}
+      <textarea
+        value={content}
+        onChange={(e) => {
+          setContent(e.target.value);
+          setDirty(true);
+        }}
+        placeholder={current ? `${SCOPE_LABELS[current.scope].hint}\n\nInstructions every agent should follow...` : ""}
+        spellCheck={false}
+        className="flex-1 resize-none bg-surface-0 p-4 font-mono text-[13px] text-zinc-200 placeholder-zinc-600 outline-none"
+      />
+    </div>
+  );
+}
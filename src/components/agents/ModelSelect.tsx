@@ -0,0 +1,50 @@
+import { useEffect, useState } from "react";
+import * as tauri from "../../lib/tauri";
+import type { ModelCatalog } from "../../lib/types";
+
+interface Props {
+  value: string;
+  onChange: (model: string) => void;
+  className?: string;
+}
+
+/** Picker over `listModels()`: aliases, dated IDs, and models already in use. */
+export function ModelSelect({ value, onChange, className }: Props) {
+  const catalog = useModelCatalog();
+  const models = catalog?.models ?? [];
+  const aliases = models.filter((m) => m.alias);
+  const dated = models.filter((m) => !m.alias);
+
+  return (
+    <select value={value} onChange={(e) => onChange(e.target.value)} className={className}>
+      {/* Keep a value the catalog doesn't know (or hasn't loaded yet) selectable */}
+      {value && !models.some((m) => m.id === value) && <option value={value}>{value}</option>}
+      <optgroup label="Latest">
+        {aliases.map((m) => (
+          <option key={m.id} value={m.id}>
+            {m.label}
+          </option>
+        ))}
+      </optgroup>
+      <optgroup label="Pinned versions">
+        {dated.map((m) => (
+          <option key={m.id} value={m.id}>
+            {m.label}
+          </option>
+        ))}
+      </optgroup>
+    </select>
+  );
+}
+
+/** The model catalog, fetched once per mount. */
+export function useModelCatalog(): ModelCatalog | null {
+  const [catalog, setCatalog] = useState<ModelCatalog | null>(null);
+  useEffect(() => {
+    tauri
+      .listModels()
+      .then(setCatalog)
+      .catch(() => setCatalog(null));
+  }, []);
+  return catalog;
+}
@@ -0,0 +1,65 @@
+import { useEffect, useState } from "react";
+import { Puzzle } from "lucide-react";
+import * as tauri from "../../lib/tauri";
+import type { Skill } from "../../lib/types";
+
+/** Skill packages in `.claude/skills/` and the agents that use them. */
+export function SkillsView() {
+  const [skills, setSkills] = useState<Skill[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [expanded, setExpanded] = useState<string | null>(null);
+
+  useEffect(() => {
+    tauri.listSkills().then(setSkills).catch((e) => setError(String(e)));
+  }, []);
+
+  return (
+    <div className="flex h-full flex-col">
+      <div className="border-b border-zinc-800 px-4 py-3">
+        <h2 className="text-sm font-semibold text-zinc-100">Skills</h2>
+      </div>
+      {error && <div className="px-4 py-2 text-[11px] text-red-400">{error}</div>}
+      <div className="flex-1 overflow-y-auto">
+        {skills.length === 0 && !error && (
+          <div className="flex flex-col items-center justify-center py-12 text-zinc-500">
+            <Puzzle size={32} className="mb-2 opacity-50" />
+            <p className="text-sm">No skills</p>
+            <p className="text-xs">Add folders with a SKILL.md to .claude/skills/</p>
+          </div>
+        )}
+        {skills.map((skill) => (
+          <div key={skill.dir_path} className="border-b border-zinc-800/50 px-4 py-2.5">
+            <button
+              onClick={() => setExpanded((v) => (v === skill.dir_path ? null : skill.dir_path))}
+              className="w-full text-left"
+            >
+              <div className="flex items-center gap-2">
+                <span className="text-sm text-zinc-200">{skill.name}</span>
+                <span className="text-[10px] text-zinc-600">{skill.files.length} files</span>
+              </div>
+              {skill.description && <div className="text-[11px] text-zinc-500">{skill.description}</div>}
+            </button>
+            <div className="mt-1 flex flex-wrap gap-1">
+              {skill.referenced_by.length === 0 ? (
+                <span className="text-[10px] text-zinc-600">Not referenced by any agent</span>
+              ) : (
+                skill.referenced_by.map((agent) => (
+                  <span key={agent} className="rounded bg-surface-2 px-1.5 text-[10px] text-zinc-400">
+                    {agent}
+                  </span>
+                ))
+              )}
+            </div>
+            {expanded === skill.dir_path && (
+              <ul className="mt-2 space-y-0.5 font-mono text-[11px] text-zinc-500">
+                {skill.files.map((f) => (
+                  <li key={f}>{f}</li>
+                ))}
+              </ul>
+            )}
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
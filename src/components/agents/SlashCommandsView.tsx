@@ -0,0 +1,253 @@
+import { useCallback, useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { Plus, Save, Terminal, Trash2 } from "lucide-react";
+import * as tauri from "../../lib/tauri";
+import type { SlashCommand } from "../../lib/types";
+
+/** List and edit the project's `.claude/commands/*.md` slash commands. */
+export function SlashCommandsView() {
+  const [commands, setCommands] = useState<SlashCommand[]>([]);
+  const [selectedPath, setSelectedPath] = useState<string | null>(null);
+  const [creating, setCreating] = useState(false);
+  const [newName, setNewName] = useState("");
+  const [newDescription, setNewDescription] = useState("");
+  const [error, setError] = useState<string | null>(null);
+
+  const load = useCallback(() => {
+    tauri
+      .listSlashCommands()
+      .then(setCommands)
+      .catch((e) => setError(String(e)));
+  }, []);
+
+  useEffect(() => {
+    load();
+    let unlisten: (() => void) | undefined;
+    listen("commands:changed", load).then((fn) => {
+      unlisten = fn;
+    });
+    return () => unlisten?.();
+  }, [load]);
+
+  async function handleCreate() {
+    if (!newName.trim()) return;
+    setError(null);
+    try {
+      const created = await tauri.createSlashCommand(newName.trim(), newDescription.trim() || undefined);
+      setNewName("");
+      setNewDescription("");
+      setCreating(false);
+      load();
+      setSelectedPath(created.file_path);
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
+  async function handleDelete(command: SlashCommand) {
+    if (!confirm(`Delete /${command.name}?`)) return;
+    setError(null);
+    try {
+      await tauri.deleteSlashCommand(command.file_path);
+      if (selectedPath === command.file_path) setSelectedPath(null);
+      load();
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
+  const selected = commands.find((c) => c.file_path === selectedPath) ?? null;
+
+  return (
+    <div className="flex h-full">
+      <div className="flex w-72 flex-shrink-0 flex-col border-r border-zinc-800">
+        <div className="flex items-center justify-between border-b border-zinc-800 px-4 py-3">
+          <h2 className="text-sm font-semibold text-zinc-100">Slash Commands</h2>
+          <button
+            onClick={() => setCreating(true)}
+            className="flex items-center gap-1 rounded-md bg-blue-600 px-2.5 py-1 text-xs font-medium text-white transition-colors hover:bg-blue-500"
+          >
+            <Plus size={12} />
+            New
+          </button>
+        </div>
+
+        {creating && (
+          <div className="space-y-2 border-b border-zinc-800 px-4 py-3">
+            <input
+              type="text"
+              value={newName}
+              onChange={(e) => setNewName(e.target.value)}
+              placeholder="Name, e.g. review or git:commit"
+              className="w-full rounded border border-zinc-700 bg-surface-2 px-3 py-1.5 text-sm text-zinc-100 outline-none focus:border-blue-500"
+              autoFocus
+              onKeyDown={(e) => {
+                if (e.key === "Enter") handleCreate();
+                if (e.key === "Escape") setCreating(false);
+              }}
+            />
+            <input
+              type="text"
+              value={newDescription}
+              onChange={(e) => setNewDescription(e.target.value)}
+              placeholder="Description (optional)..."
+              className="w-full rounded border border-zinc-700 bg-surface-2 px-3 py-1.5 text-sm text-zinc-100 outline-none focus:border-blue-500"
+            />
+            <div className="flex gap-2">
+              <button
+                onClick={handleCreate}
+                className="rounded bg-blue-600 px-3 py-1 text-xs font-medium text-white hover:bg-blue-500"
+              >
+                Create
+              </button>
+              <button
+                onClick={() => setCreating(false)}
+                className="rounded px-3 py-1 text-xs text-zinc-400 hover:text-zinc-200"
+              >
+                Cancel
+              </button>
+            </div>
+          </div>
+        )}
+        {error && <div className="px-4 py-2 text-[11px] text-red-400">{error}</div>}
+
+        <div className="flex-1 overflow-y-auto">
+          {commands.length === 0 && !creating && (
+            <div className="flex flex-col items-center justify-center py-12 text-zinc-500">
+              <Terminal size={32} className="mb-2 opacity-50" />
+              <p className="text-sm">No slash commands</p>
+              <p className="text-xs">.claude/commands/ is empty</p>
+            </div>
+          )}
+          {commands.map((c) => (
+            <div
+              key={c.file_path}
+              onClick={() => setSelectedPath(c.file_path)}
+              className={`group flex cursor-pointer items-center gap-2 px-4 py-2.5 ${
+                c.file_path === selectedPath ? "bg-surface-2" : "hover:bg-surface-1"
+              }`}
+            >
+              <div className="min-w-0 flex-1">
+                <div className="font-mono text-xs text-zinc-200">/{c.name}</div>
+                {c.description && <div className="truncate text-[11px] text-zinc-500">{c.description}</div>}
+              </div>
+              <button
+                onClick={(e) => {
+                  e.stopPropagation();
+                  handleDelete(c);
+                }}
+                className="rounded p-1 text-zinc-600 opacity-0 hover:text-red-400 group-hover:opacity-100"
+              >
+                <Trash2 size={12} />
+              </button>
+            </div>
+          ))}
+        </div>
+      </div>
+
+      <div className="flex-1 overflow-hidden">
+        {selected ? (
+          <SlashCommandEditor key={selected.file_path} command={selected} onSaved={load} />
+        ) : (
+          <div className="flex h-full items-center justify-center text-zinc-500">
+            <p className="text-sm">Select a command to edit</p>
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
+
+function SlashCommandEditor({ command, onSaved }: { command: SlashCommand; onSaved: () => void }) {
+  const [description, setDescription] = useState(command.description ?? "");
+  const [argumentHint, setArgumentHint] = useState(command.argument_hint ?? "");
+  const [allowedTools, setAllowedTools] = useState(command.allowed_tools ?? "");
+  const [model, setModel] = useState(command.model ?? "");
+  const [body, setBody] = useState(command.body);
+  const [dirty, setDirty] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  function edit<T>(setter: (value: T) => void) {
+    return (value: T) => {
+      setter(value);
+      setDirty(true);
+    };
+  }
+
+  async function handleSave() {
+    setError(null);
+    try {
+      await tauri.updateSlashCommand(command.file_path, {
+        description,
+        argument_hint: argumentHint,
+        allowed_tools: allowedTools,
+        model,
+        body,
+      });
+      setDirty(false);
+      onSaved();
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
+  const field = "w-full rounded border border-zinc-700 bg-surface-2 px-2 py-1.5 text-sm text-zinc-100 outline-none focus:border-blue-500";
+  const label = "mb-1 block text-[11px] font-medium uppercase tracking-wider text-zinc-500";
+
+  return (
+    <div className="flex h-full flex-col">
+      <div className="flex items-center justify-between border-b border-zinc-800 px-4 py-3">
+        <span className="font-mono text-sm text-zinc-100">/{command.name}</span>
+        <button
+          onClick={handleSave}
+          disabled={!dirty}
+          className="flex items-center gap-1 rounded-md bg-blue-600 px-2.5 py-1 text-xs font-medium text-white transition-colors hover:bg-blue-500 disabled:opacity-40"
+        >
+          <Save size={12} />
+          Save
+        </button>
+      </div>
+      {error && <div className="px-4 py-1 text-[11px] text-red-400">{error}</div>}
+      <div className="grid grid-cols-2 gap-3 border-b border-zinc-800 p-4">
+        <div className="col-span-2">
+          <label className={label}>Description</label>
+          <input value={description} onChange={(e) => edit(setDescription)(e.target.value)} className={field} />
+        </div>
+        <div>
+          <label className={label}>Argument hint</label>
+          <input
+            value={argumentHint}
+            onChange={(e) => edit(setArgumentHint)(e.target.value)}
+            placeholder="[file] [focus]"
+            className={field}
+          />
+        </div>
+        <div>
+          <label className={label}>Model</label>
+          <select value={model} onChange={(e) => edit(setModel)(e.target.value)} className={field}>
+            <option value="">Inherit</option>
+            <option value="opus">Opus</option>
+            <option value="sonnet">Sonnet</option>
+            <option value="haiku">Haiku</option>
+          </select>
+        </div>
+        <div className="col-span-2">
+          <label className={label}>Allowed tools</label>
+          <input
+            value={allowedTools}
+            onChange={(e) => edit(setAllowedTools)(e.target.value)}
+            placeholder="Bash(git status:*), Read"
+            className={field}
+          />
+        </div>
+      </div>
+      <textarea
+        value={body}
+        onChange={(e) => edit(setBody)(e.target.value)}
+        placeholder="Prompt. Use $ARGUMENTS for the text typed after the command."
+        spellCheck={false}
+        className="flex-1 resize-none bg-surface-0 p-4 font-mono text-[13px] text-zinc-200 placeholder-zinc-600 outline-none"
+      />
+    </div>
+  );
+}
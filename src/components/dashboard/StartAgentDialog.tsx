@@ -1,7 +1,9 @@
-import { useState } from "react";
+import { useEffect, useState } from "react";
 import * as Dialog from "@radix-ui/react-dialog";
 import { Play, X } from "lucide-react";
-import type { AgentConfig } from "../../lib/types";
+import type { AgentConfig, ClaudeProfile } from "../../lib/types";
+import * as tauri from "../../lib/tauri";
+import { ModelSelect, useModelCatalog } from "../agents/ModelSelect";
 import { useAgentStore } from "../../stores/agentStore";
 import { toast } from "sonner";
 
@@ -18,13 +20,27 @@ export function StartAgentDialog({ open, onOpenChange }: Props) {
   const [prompt, setPrompt] = useState("");
   const [model, setModel] = useState("opus");
   const [isStarting, setIsStarting] = useState(false);
+  const [profiles, setProfiles] = useState<ClaudeProfile[]>([]);
+  // Empty = the project's default profile
+  const [profile, setProfile] = useState("");
+
+  useEffect(() => {
+    if (!open) return;
+    tauri
+      .getConfig()
+      .then((config) => setProfiles(config.profiles ?? []))
+      .catch(() => setProfiles([]));
+  }, [open]);
+
+  const catalog = useModelCatalog();
+  const defaultModel = catalog?.default_model ?? "opus";
 
   const handleStart = async () => {
     if (!selectedAgent || !prompt.trim()) return;
 
     setIsStarting(true);
     try {
-      await startAgent(selectedAgent.name, model, prompt.trim());
+      await startAgent(selectedAgent.name, model, prompt.trim(), profile || null);
       toast.success(`Agent "${selectedAgent.name}" started`);
       setPrompt("");
       setSelectedAgent(null);
@@ -69,7 +85,7 @@ export function StartAgentDialog({ open, onOpenChange }: Props) {
                     key={config.name}
                     onClick={() => {
                       setSelectedAgent(config);
-                      setModel(config.model || "opus");
+                      setModel(config.model || defaultModel);
                     }}
                     className={`flex items-center gap-2 rounded-lg border p-3 text-left text-sm transition-colors ${
                       selectedAgent?.name === config.name
@@ -102,17 +118,34 @@ export function StartAgentDialog({ open, onOpenChange }: Props) {
             <label className="mb-1.5 block text-xs font-medium text-zinc-400">
               Model
             </label>
-            <select
+            <ModelSelect
               value={model}
-              onChange={(e) => setModel(e.target.value)}
+              onChange={setModel}
               className="w-full rounded-lg border border-zinc-700 bg-surface-2 px-3 py-2 text-sm text-zinc-100 outline-none focus:border-zinc-500"
-            >
-              <option value="opus">Claude Opus</option>
-              <option value="sonnet">Claude Sonnet</option>
-              <option value="haiku">Claude Haiku</option>
-            </select>
+            />
           </div>
 
+          {/* Profile selector, when several Claude accounts are configured */}
+          {profiles.length > 0 && (
+            <div className="mb-4">
+              <label className="mb-1.5 block text-xs font-medium text-zinc-400">
+                Profile
+              </label>
+              <select
+                value={profile}
+                onChange={(e) => setProfile(e.target.value)}
+                className="w-full rounded-lg border border-zinc-700 bg-surface-2 px-3 py-2 text-sm text-zinc-100 outline-none focus:border-zinc-500"
+              >
+                <option value="">Project default</option>
+                {profiles.map((p) => (
+                  <option key={p.name} value={p.name}>
+                    {p.name}
+                  </option>
+                ))}
+              </select>
+            </div>
+          )}
+
           {/* Prompt */}
           <div className="mb-5">
             <label className="mb-1.5 block text-xs font-medium text-zinc-400">
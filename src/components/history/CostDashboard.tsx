@@ -1,8 +1,12 @@
-import { useMemo } from "react";
-import { DollarSign, Cpu, ArrowUpRight, ArrowDownRight } from "lucide-react";
+import { useEffect, useMemo, useState } from "react";
+import { DollarSign, Cpu, ArrowUpRight, ArrowDownRight, Download } from "lucide-react";
+import { save } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
 import { useAgentStore } from "../../stores/agentStore";
 import { formatTokens } from "../../lib/formatters";
+import { exportUsage, getCostBreakdown } from "../../lib/tauri";
 import { AGENT_COLORS } from "../../lib/types";
+import type { CostGroup, CostGroupBy } from "../../lib/types";
 
 function getAgentColor(name: string): string {
   if (name.includes("architect")) return AGENT_COLORS.red;
@@ -85,13 +89,54 @@ export function CostDashboard() {
       .sort((a, b) => b[1].cost - a[1].cost);
   }, [allSessions]);
 
+  const [exportFrom, setExportFrom] = useState("");
+  const [exportTo, setExportTo] = useState("");
+
+  async function handleExport() {
+    const path = await save({
+      defaultPath: "usage.csv",
+      filters: [{ name: "CSV", extensions: ["csv"] }],
+    });
+    if (!path) return;
+    try {
+      await exportUsage({ from: exportFrom || null, to: exportTo || null }, path);
+      toast.success("Usage exported");
+    } catch (e) {
+      toast.error(`Export failed: ${e}`);
+    }
+  }
+
   // Max cost for bar chart scaling
   const maxAgentCost = byAgent.length > 0 ? byAgent[0][1].cost : 1;
 
   return (
     <div className="flex flex-1 flex-col overflow-hidden">
-      <header className="flex h-12 flex-shrink-0 items-center border-b border-zinc-800 px-6">
+      <header className="flex h-12 flex-shrink-0 items-center justify-between border-b border-zinc-800 px-6">
         <h2 className="text-sm font-medium text-zinc-300">Cost Dashboard</h2>
+        <div className="flex items-center gap-1.5 text-[11px] text-zinc-500">
+          <input
+            type="date"
+            value={exportFrom}
+            onChange={(e) => setExportFrom(e.target.value)}
+            title="First day to export"
+            className="rounded border border-zinc-700 bg-surface-2 px-1.5 py-0.5 text-[11px] text-zinc-300 outline-none focus:border-blue-500"
+          />
+          <span>to</span>
+          <input
+            type="date"
+            value={exportTo}
+            onChange={(e) => setExportTo(e.target.value)}
+            title="Last day to export"
+            className="rounded border border-zinc-700 bg-surface-2 px-1.5 py-0.5 text-[11px] text-zinc-300 outline-none focus:border-blue-500"
+          />
+          <button
+            onClick={handleExport}
+            title="Export usage per day, model and agent as CSV"
+            className="rounded-md p-1 text-zinc-500 transition-colors hover:bg-surface-2 hover:text-zinc-300"
+          >
+            <Download size={14} />
+          </button>
+        </div>
       </header>
 
       <div className="flex-1 overflow-y-auto p-6">
@@ -209,6 +254,8 @@ export function CostDashboard() {
           </div>
         </div>
 
+        <GroupedCosts sessionCount={totals.sessionCount} totalCost={totals.totalCost} />
+
         <p className="mt-6 text-[10px] text-zinc-600">
           Costs reported directly by Claude Code from session result messages.
         </p>
@@ -217,6 +264,76 @@ export function CostDashboard() {
   );
 }
 
+/** Costs per workflow or spec, from stored sessions joined with their runs. */
+function GroupedCosts({ sessionCount, totalCost }: { sessionCount: number; totalCost: number }) {
+  const [groupBy, setGroupBy] = useState<CostGroupBy>("workflow");
+  const [groups, setGroups] = useState<CostGroup[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  // Reload as sessions finish and costs change
+  useEffect(() => {
+    getCostBreakdown(groupBy)
+      .then((g) => {
+        setGroups(g);
+        setError(null);
+      })
+      .catch((e) => setError(String(e)));
+  }, [groupBy, sessionCount, totalCost]);
+
+  const maxCost = groups.length > 0 ? groups[0].cost_usd : 1;
+
+  return (
+    <div className="mt-6 rounded-lg border border-zinc-800 bg-surface-1 p-4">
+      <div className="mb-4 flex items-center justify-between">
+        <h3 className="text-sm font-medium text-zinc-200">
+          Cost by {groupBy === "workflow" ? "Workflow" : "Spec"}
+        </h3>
+        <div className="flex gap-1">
+          {(["workflow", "spec"] as const).map((g) => (
+            <button
+              key={g}
+              onClick={() => setGroupBy(g)}
+              className={`rounded px-2 py-1 text-[11px] capitalize transition-colors ${
+                groupBy === g ? "bg-surface-2 text-zinc-200" : "text-zinc-500 hover:text-zinc-300"
+              }`}
+            >
+              {g}
+            </button>
+          ))}
+        </div>
+      </div>
+      {error ? (
+        <p className="text-xs text-red-400">{error}</p>
+      ) : groups.length === 0 ? (
+        <p className="text-xs text-zinc-500">No data yet</p>
+      ) : (
+        <div className="flex flex-col gap-3">
+          {groups.map((group) => (
+            <div key={group.key ?? ""} title={group.key ?? undefined}>
+              <div className="mb-1 flex items-center justify-between">
+                <span
+                  className={`truncate text-xs ${group.key ? "text-zinc-300" : "italic text-zinc-500"}`}
+                >
+                  {group.label}
+                </span>
+                <span className="text-xs text-zinc-400">
+                  ${group.cost_usd.toFixed(2)} ({group.session_count} runs)
+                </span>
+              </div>
+              <div className="h-2 w-full overflow-hidden rounded-full bg-zinc-800">
+                <div
+                  className="h-full rounded-full bg-blue-500 transition-all"
+                  style={{ width: `${maxCost > 0 ? (group.cost_usd / maxCost) * 100 : 0}%` }}
+                />
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
+
 function StatCard({
   label,
   value,
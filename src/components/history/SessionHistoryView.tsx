@@ -1,5 +1,6 @@
-import { useMemo, useState } from "react";
-import { Clock, Eye, RotateCcw, Search, X } from "lucide-react";
+import { useEffect, useMemo, useState } from "react";
+import { Clock, Download, Eye, FileText, RotateCcw, Search, X } from "lucide-react";
+import * as tauri from "../../lib/tauri";
 import { useAgentStore } from "../../stores/agentStore";
 import { AgentStatusBadge } from "../dashboard/AgentStatusBadge";
 import { formatElapsed, formatTokens } from "../../lib/formatters";
@@ -12,12 +13,49 @@ type SortDir = "asc" | "desc";
 export function SessionHistoryView() {
   const sessions = useAgentStore((s) => s.sessions);
   const openDetail = useAgentStore((s) => s.openDetail);
+  const loadSessions = useAgentStore((s) => s.loadSessions);
 
   const [search, setSearch] = useState("");
   const [statusFilter, setStatusFilter] = useState<AgentStatus | "all">("all");
   const [agentFilter, setAgentFilter] = useState<string>("all");
   const [sortField, setSortField] = useState<SortField>("started");
   const [sortDir, setSortDir] = useState<SortDir>("desc");
+  // Also match log contents of this project's sessions, via the backend
+  const [searchLogs, setSearchLogs] = useState(false);
+  const [logMatches, setLogMatches] = useState<Map<string, number> | null>(null);
+  const [importing, setImporting] = useState(false);
+  const [importResult, setImportResult] = useState<string | null>(null);
+
+  async function handleImport() {
+    setImporting(true);
+    try {
+      const report = await tauri.importTranscripts();
+      await loadSessions();
+      const failed = report.failed.length > 0 ? `, ${report.failed.length} failed` : "";
+      setImportResult(`Imported ${report.imported} terminal session(s)${failed}`);
+    } catch (e) {
+      setImportResult(String(e));
+    } finally {
+      setImporting(false);
+    }
+  }
+
+  useEffect(() => {
+    const q = search.trim();
+    if (!searchLogs || q.length < 2) {
+      setLogMatches(null);
+      return;
+    }
+    const timer = setTimeout(() => {
+      tauri
+        .searchProjectLogs(q)
+        .then((results) =>
+          setLogMatches(new Map(results.map((r) => [r.session_id, r.matches.length]))),
+        )
+        .catch(() => setLogMatches(new Map()));
+    }, 300);
+    return () => clearTimeout(timer);
+  }, [search, searchLogs]);
 
   const allSessions = useMemo(
     () => Array.from(sessions.values()),
@@ -60,12 +98,13 @@ export function SessionHistoryView() {
         (s) =>
           s.prompt.toLowerCase().includes(q) ||
           s.agent_name.toLowerCase().includes(q) ||
-          s.id.toLowerCase().includes(q),
+          s.id.toLowerCase().includes(q) ||
+          (logMatches?.has(s.id) ?? false),
       );
     }
 
     return result;
-  }, [terminalSessions, statusFilter, agentFilter, search]);
+  }, [terminalSessions, statusFilter, agentFilter, search, logMatches]);
 
   // Sort
   const sorted = useMemo(() => {
@@ -160,7 +199,7 @@ export function SessionHistoryView() {
               type="text"
               value={search}
               onChange={(e) => setSearch(e.target.value)}
-              placeholder="Search prompts, agents, IDs..."
+              placeholder={searchLogs ? "Search prompts, agents, IDs, logs..." : "Search prompts, agents, IDs..."}
               className="w-full rounded-lg border border-zinc-700 bg-surface-0 py-1.5 pl-8 pr-8 text-xs text-zinc-100 placeholder:text-zinc-600 focus:border-blue-600 focus:outline-none"
             />
             {search && (
@@ -173,6 +212,29 @@ export function SessionHistoryView() {
             )}
           </div>
 
+          <button
+            onClick={() => setSearchLogs((v) => !v)}
+            title="Also search log contents of this project's sessions"
+            className={`flex items-center gap-1 rounded-lg border px-2 py-1.5 text-xs transition-colors ${
+              searchLogs
+                ? "border-blue-600 bg-blue-600/10 text-blue-300"
+                : "border-zinc-700 text-zinc-400 hover:text-zinc-200"
+            }`}
+          >
+            <FileText size={12} />
+            Logs
+          </button>
+
+          <button
+            onClick={handleImport}
+            disabled={importing}
+            title={importResult ?? "Import sessions run with claude in the terminal for this project"}
+            className="flex items-center gap-1 rounded-lg border border-zinc-700 px-2 py-1.5 text-xs text-zinc-400 transition-colors hover:text-zinc-200 disabled:opacity-40"
+          >
+            <Download size={12} />
+            {importing ? "Importing..." : "Import"}
+          </button>
+
           {/* Status filter */}
           <select
             value={statusFilter}
@@ -283,6 +345,11 @@ export function SessionHistoryView() {
                     <AgentStatusBadge status={session.status} />
                   </td>
                   <td className="max-w-xs truncate px-4 py-3 text-xs text-zinc-400">
+                    {logMatches?.has(session.id) && (
+                      <span className="mr-1.5 rounded bg-blue-600/20 px-1 text-[10px] text-blue-300">
+                        {logMatches.get(session.id)} in logs
+                      </span>
+                    )}
                     {session.prompt}
                   </td>
                   <td className="px-4 py-3 text-xs text-zinc-400">
@@ -1,7 +1,7 @@
 import { useState } from "react";
 import * as Dialog from "@radix-ui/react-dialog";
-import { ShieldAlert, Check, X } from "lucide-react";
-import type { UnapprovedAgent } from "../../lib/types";
+import { ShieldAlert, Check, X, ChevronRight, ChevronDown } from "lucide-react";
+import type { DiffHunk, UnapprovedAgent } from "../../lib/types";
 
 interface AgentApprovalDialogProps {
   agents: UnapprovedAgent[];
@@ -17,6 +17,7 @@ export function AgentApprovalDialog({
   const [selected, setSelected] = useState<Set<string>>(
     new Set(agents.map((a) => a.file_path)),
   );
+  const [expanded, setExpanded] = useState<string | null>(null);
 
   function toggleAgent(filePath: string) {
     setSelected((prev) => {
@@ -41,7 +42,7 @@ export function AgentApprovalDialog({
     <Dialog.Root open={true}>
       <Dialog.Portal>
         <Dialog.Overlay className="fixed inset-0 bg-black/60" />
-        <Dialog.Content className="fixed left-1/2 top-1/2 w-full max-w-lg -translate-x-1/2 -translate-y-1/2 rounded-xl border border-zinc-700 bg-surface-1 p-6 shadow-xl">
+        <Dialog.Content className="fixed left-1/2 top-1/2 w-full max-w-2xl -translate-x-1/2 -translate-y-1/2 rounded-xl border border-zinc-700 bg-surface-1 p-6 shadow-xl">
           <div className="flex items-center gap-3 text-amber-400">
             <ShieldAlert size={24} />
             <Dialog.Title className="text-lg font-semibold text-zinc-100">
@@ -54,37 +55,61 @@ export function AgentApprovalDialog({
             and approve them before they can be used.
           </Dialog.Description>
 
-          <div className="mt-4 max-h-64 space-y-2 overflow-y-auto">
+          <div className="mt-4 max-h-96 space-y-2 overflow-y-auto">
             {agents.map((agent) => (
-              <label
-                key={agent.file_path}
-                className="flex cursor-pointer items-start gap-3 rounded-lg border border-zinc-700 p-3 transition-colors hover:bg-surface-2"
-              >
-                <input
-                  type="checkbox"
-                  checked={selected.has(agent.file_path)}
-                  onChange={() => toggleAgent(agent.file_path)}
-                  className="mt-0.5 h-4 w-4 rounded border-zinc-600 bg-surface-0 text-blue-500 accent-blue-500"
-                />
-                <div className="flex-1 min-w-0">
-                  <div className="flex items-center gap-2">
-                    <span className="text-sm font-medium text-zinc-100">
-                      {agent.name}
-                    </span>
-                    <span className="rounded bg-surface-2 px-1.5 py-0.5 text-xs text-zinc-400">
-                      {agent.model}
-                    </span>
-                  </div>
-                  {agent.description && (
-                    <p className="mt-0.5 text-xs text-zinc-500 truncate">
-                      {agent.description}
+              <div key={agent.file_path} className="rounded-lg border border-zinc-700">
+                <label className="flex cursor-pointer items-start gap-3 rounded-lg p-3 transition-colors hover:bg-surface-2">
+                  <input
+                    type="checkbox"
+                    checked={selected.has(agent.file_path)}
+                    onChange={() => toggleAgent(agent.file_path)}
+                    className="mt-0.5 h-4 w-4 rounded border-zinc-600 bg-surface-0 text-blue-500 accent-blue-500"
+                  />
+                  <div className="flex-1 min-w-0">
+                    <div className="flex items-center gap-2">
+                      <span className="text-sm font-medium text-zinc-100">
+                        {agent.name}
+                      </span>
+                      <span className="rounded bg-surface-2 px-1.5 py-0.5 text-xs text-zinc-400">
+                        {agent.model}
+                      </span>
+                      <span
+                        className={`text-xs ${agent.previously_approved ? "text-amber-400" : "text-zinc-500"}`}
+                      >
+                        {agent.previously_approved ? "modified" : "new"}
+                      </span>
+                    </div>
+                    {agent.description && (
+                      <p className="mt-0.5 text-xs text-zinc-500 truncate">
+                        {agent.description}
+                      </p>
+                    )}
+                    <p className="mt-1 text-xs font-mono text-zinc-600 truncate">
+                      {agent.file_path}
                     </p>
-                  )}
-                  <p className="mt-1 text-xs font-mono text-zinc-600 truncate">
-                    {agent.file_path}
-                  </p>
-                </div>
-              </label>
+                  </div>
+                </label>
+                {agent.previously_approved && (
+                  <div className="border-t border-zinc-800 px-3 py-1.5">
+                    {agent.diff ? (
+                      <button
+                        onClick={() =>
+                          setExpanded(expanded === agent.file_path ? null : agent.file_path)
+                        }
+                        className="flex items-center gap-1 text-xs text-zinc-400 hover:text-zinc-200"
+                      >
+                        {expanded === agent.file_path ? <ChevronDown size={12} /> : <ChevronRight size={12} />}
+                        Changes since last approval
+                      </button>
+                    ) : (
+                      <p className="text-xs text-zinc-600">
+                        Changed since last approval; the approved version is not on record.
+                      </p>
+                    )}
+                    {expanded === agent.file_path && agent.diff && <ApprovalDiff hunks={agent.diff} />}
+                  </div>
+                )}
+              </div>
             ))}
           </div>
 
@@ -110,3 +135,33 @@ export function AgentApprovalDialog({
     </Dialog.Root>
   );
 }
+
+function ApprovalDiff({ hunks }: { hunks: DiffHunk[] }) {
+  if (hunks.length === 0) {
+    return <p className="mt-1 text-xs text-zinc-600">No line changes.</p>;
+  }
+  return (
+    <div className="mt-1.5 max-h-60 overflow-auto rounded bg-surface-0 font-mono text-[11px]">
+      {hunks.map((hunk, i) => (
+        <div key={i}>
+          <div className="bg-surface-2 px-2 text-zinc-500">{hunk.header}</div>
+          {hunk.lines.map((line, j) => (
+            <div
+              key={j}
+              className={`whitespace-pre px-2 ${
+                line.line_type === "add"
+                  ? "bg-green-500/10 text-green-400"
+                  : line.line_type === "remove"
+                    ? "bg-red-500/10 text-red-400"
+                    : "text-zinc-500"
+              }`}
+            >
+              {line.line_type === "add" ? "+" : line.line_type === "remove" ? "-" : " "}
+              {line.content}
+            </div>
+          ))}
+        </div>
+      ))}
+    </div>
+  );
+}
@@ -1,7 +1,12 @@
-import { useCallback, useState } from "react";
+import { useCallback, useEffect, useState } from "react";
 import { RefreshCw, WifiOff } from "lucide-react";
 import { useQuotaStore } from "../../stores/quotaStore";
-import { refreshQuota } from "../../lib/tauri";
+import { getUsageHistory, refreshQuota } from "../../lib/tauri";
+import { formatTokens } from "../../lib/formatters";
+import type { UsagePeriod } from "../../lib/types";
+
+/** Weeks of spend shown in the history bars. */
+const HISTORY_WEEKS = 8;
 
 /** Shorten a full Claude model ID to a readable label. */
 function modelLabel(model: string): string {
@@ -11,10 +16,38 @@ function modelLabel(model: string): string {
   return model.split("-").slice(0, 2).join("-");
 }
 
+/** Time left until `resetsAt`, e.g. "2h 05m". */
+function countdown(resetsAt: string, now: number): string {
+  const minutes = Math.max(Math.ceil((new Date(resetsAt).getTime() - now) / 60_000), 0);
+  const hours = Math.floor(minutes / 60);
+  return hours > 0 ? `${hours}h ${String(minutes % 60).padStart(2, "0")}m` : `${minutes}m`;
+}
+
 export function QuotaWidget() {
-  const { models, today, totalCostUsd, available, fetchedAt, error } =
+  const { models, today, totalCostUsd, profiles, available, fetchedAt, error, budgets, window } =
     useQuotaStore();
   const [refreshing, setRefreshing] = useState(false);
+  const [weeks, setWeeks] = useState<UsagePeriod[]>([]);
+  const [now, setNow] = useState(Date.now());
+
+  // Keep the window's reset countdown moving between polls
+  useEffect(() => {
+    if (!window?.resets_at) return;
+    setNow(Date.now());
+    const timer = setInterval(() => setNow(Date.now()), 30_000);
+    return () => clearInterval(timer);
+  }, [window?.resets_at]);
+
+  // Each poll stores a snapshot, so reload whenever new stats arrive
+  useEffect(() => {
+    if (!fetchedAt) return;
+    const from = new Date(Date.now() - HISTORY_WEEKS * 7 * 86_400_000);
+    const pad = (n: number) => String(n).padStart(2, "0");
+    const fromDate = `${from.getFullYear()}-${pad(from.getMonth() + 1)}-${pad(from.getDate())}`;
+    getUsageHistory(fromDate, undefined, "week")
+      .then(setWeeks)
+      .catch(() => setWeeks([]));
+  }, [fetchedAt]);
 
   const handleRefresh = useCallback(async () => {
     setRefreshing(true);
@@ -52,6 +85,7 @@ export function QuotaWidget() {
   }
 
   const modelsWithCost = (models ?? []).filter((m) => m.cost_usd > 0);
+  const maxWeekCost = Math.max(...weeks.map((w) => w.cost_usd), 0);
 
   return (
     <div className="mb-2">
@@ -80,6 +114,21 @@ export function QuotaWidget() {
         </span>
       </div>
 
+      {/* Per-profile cost, once more than the default profile is configured */}
+      {profiles.length > 1 &&
+        profiles.map((p) => (
+          <div
+            key={p.profile ?? ""}
+            className="mb-1 flex items-baseline justify-between pl-2"
+            title={p.error ?? undefined}
+          >
+            <span className="text-[10px] text-zinc-600">{p.profile ?? "default"}</span>
+            <span className="text-[10px] tabular-nums text-zinc-500">
+              {p.available ? `$${p.total_cost_usd.toFixed(2)}` : "unavailable"}
+            </span>
+          </div>
+        ))}
+
       {/* Today's activity */}
       {today && (
         <div className="mb-1.5 flex items-baseline justify-between">
@@ -90,6 +139,61 @@ export function QuotaWidget() {
         </div>
       )}
 
+      {/* Rolling 5-hour window */}
+      {window && (
+        <div
+          className="mb-1.5 flex items-baseline justify-between"
+          title={
+            window.started_at
+              ? `${window.session_count} sessions since ${new Date(window.started_at).toLocaleTimeString()}`
+              : "The next session starts a new window"
+          }
+        >
+          <span className="text-[10px] text-zinc-500">5h window</span>
+          <span className="text-[10px] tabular-nums text-zinc-400">
+            {window.resets_at
+              ? `${formatTokens(window.input_tokens + window.output_tokens)}${
+                  window.remaining_tokens !== null
+                    ? ` · ${formatTokens(window.remaining_tokens)} left`
+                    : ""
+                } · resets ${countdown(window.resets_at, now)}`
+              : "Not started"}
+          </span>
+        </div>
+      )}
+
+      {/* Spending budgets */}
+      {budgets.map((b) => (
+        <div key={b.period} className="mb-1.5 flex items-baseline justify-between">
+          <span className="text-[10px] capitalize text-zinc-500">{b.period} budget</span>
+          <span
+            className={`text-[10px] tabular-nums ${
+              b.level === "exceeded"
+                ? "text-red-400"
+                : b.level === "warning"
+                  ? "text-amber-400"
+                  : "text-zinc-400"
+            }`}
+          >
+            ${b.spent_usd.toFixed(2)} / ${b.limit_usd.toFixed(2)}
+          </span>
+        </div>
+      ))}
+
+      {/* Weekly spend */}
+      {maxWeekCost > 0 && (
+        <div className="mb-1.5 flex h-6 items-end gap-0.5">
+          {weeks.map((w) => (
+            <div
+              key={w.start}
+              className="flex-1 rounded-sm bg-blue-500/50"
+              style={{ height: `${Math.max((w.cost_usd / maxWeekCost) * 100, 4)}%` }}
+              title={`Week of ${w.start}: $${w.cost_usd.toFixed(2)}`}
+            />
+          ))}
+        </div>
+      )}
+
       {/* Per-model cost breakdown */}
       {modelsWithCost.length > 0 && (
         <div className="flex flex-col gap-1 mt-1">
@@ -1,15 +1,18 @@
-import { LayoutDashboard, Settings, History, FolderOpen, FileText, GitFork, DollarSign, Bot, AlertCircle, X } from "lucide-react";
+import { LayoutDashboard, Settings, History, FolderOpen, FileText, GitFork, DollarSign, Bot, AlertCircle, X, ChevronDown } from "lucide-react";
 import { useEffect, useState } from "react";
 import { useAgentStore, type RateLimitStatus } from "../../stores/agentStore";
 import { useSpecStore } from "../../stores/specStore";
 import { useWorkflowStore } from "../../stores/workflowStore";
 import { QuotaWidget } from "./QuotaWidget";
+import * as tauri from "../../lib/tauri";
+import type { KnownProject } from "../../lib/types";
 
 interface SidebarProps {
   activeView: string;
   onViewChange: (view: string) => void;
   projectPath: string | null;
   onChangeProject: () => void;
+  onOpenProject: (path: string) => void;
 }
 
 const NAV_ITEMS = [
@@ -27,6 +30,7 @@ export function Sidebar({
   onViewChange,
   projectPath,
   onChangeProject,
+  onOpenProject,
 }: SidebarProps) {
   const configs = useAgentStore((s) => s.configs);
   const sessions = useAgentStore((s) => s.sessions);
@@ -43,6 +47,19 @@ export function Sidebar({
     ? projectPath.split("/").filter(Boolean).pop() ?? "Project"
     : null;
 
+  // Recent projects, loaded when the switcher opens
+  const [recent, setRecent] = useState<KnownProject[] | null>(null);
+  const toggleSwitcher = () => {
+    if (recent) {
+      setRecent(null);
+      return;
+    }
+    tauri
+      .listProjects()
+      .then((projects) => setRecent(projects.filter((p) => p.path !== projectPath)))
+      .catch(() => setRecent([]));
+  };
+
   return (
     <aside className="flex w-56 flex-shrink-0 flex-col border-r border-zinc-800 bg-surface-1">
       <div className="flex h-12 items-center border-b border-zinc-800 px-4">
@@ -59,7 +76,7 @@ export function Sidebar({
       {projectName && (
         <div className="border-b border-zinc-800 px-3 py-2">
           <button
-            onClick={onChangeProject}
+            onClick={toggleSwitcher}
             className="flex w-full items-center gap-2 rounded-md px-2 py-1.5 text-left transition-colors hover:bg-surface-2"
           >
             <FolderOpen size={14} className="flex-shrink-0 text-zinc-500" />
@@ -71,7 +88,35 @@ export function Sidebar({
                 {projectPath}
               </div>
             </div>
+            <ChevronDown size={12} className="flex-shrink-0 text-zinc-600" />
           </button>
+          {recent && (
+            <div className="mt-1 space-y-0.5">
+              {recent.map((project) => (
+                <button
+                  key={project.path}
+                  onClick={() => {
+                    setRecent(null);
+                    onOpenProject(project.path);
+                  }}
+                  disabled={!project.exists}
+                  title={project.path}
+                  className="block w-full truncate rounded-md px-2 py-1 text-left text-[11px] text-zinc-400 transition-colors hover:bg-surface-2 hover:text-zinc-200 disabled:opacity-40"
+                >
+                  {project.name}
+                </button>
+              ))}
+              <button
+                onClick={() => {
+                  setRecent(null);
+                  onChangeProject();
+                }}
+                className="block w-full rounded-md px-2 py-1 text-left text-[11px] text-blue-400 transition-colors hover:bg-surface-2"
+              >
+                Open folder...
+              </button>
+            </div>
+          )}
         </div>
       )}
 
@@ -1,6 +1,8 @@
-import { useState } from "react";
-import { FolderOpen, Rocket } from "lucide-react";
+import { useEffect, useState } from "react";
+import { FolderOpen, Rocket, X } from "lucide-react";
 import { open } from "@tauri-apps/plugin-dialog";
+import * as tauri from "../../lib/tauri";
+import type { KnownProject } from "../../lib/types";
 
 interface WelcomeScreenProps {
   onProjectSelected: (path: string) => void;
@@ -8,6 +10,16 @@ interface WelcomeScreenProps {
 
 export function WelcomeScreen({ onProjectSelected }: WelcomeScreenProps) {
   const [loading, setLoading] = useState(false);
+  const [recent, setRecent] = useState<KnownProject[]>([]);
+
+  useEffect(() => {
+    tauri.listProjects().then(setRecent).catch(() => setRecent([]));
+  }, []);
+
+  async function handleRemove(path: string) {
+    await tauri.removeProject(path);
+    setRecent((projects) => projects.filter((p) => p.path !== path));
+  }
 
   async function handlePickFolder() {
     setLoading(true);
@@ -56,6 +68,39 @@ export function WelcomeScreen({ onProjectSelected }: WelcomeScreenProps) {
             folder with agent definitions.
           </p>
         </div>
+
+        {recent.length > 0 && (
+          <div className="w-full space-y-2 text-left">
+            <p className="text-xs font-medium uppercase tracking-wider text-zinc-500">
+              Recent projects
+            </p>
+            {recent.map((project) => (
+              <div
+                key={project.path}
+                className="group flex items-center gap-2 rounded-lg border border-zinc-800 bg-surface-1 px-3 py-2"
+              >
+                <button
+                  onClick={() => onProjectSelected(project.path)}
+                  disabled={!project.exists}
+                  title={project.exists ? project.path : "Folder not found"}
+                  className="min-w-0 flex-1 text-left disabled:cursor-not-allowed disabled:opacity-50"
+                >
+                  <div className="truncate text-sm font-medium text-zinc-200">
+                    {project.name}
+                  </div>
+                  <div className="truncate text-[11px] text-zinc-500">{project.path}</div>
+                </button>
+                <button
+                  onClick={() => handleRemove(project.path)}
+                  title="Remove from list"
+                  className="rounded p-1 text-zinc-600 opacity-0 transition-opacity hover:text-zinc-300 group-hover:opacity-100"
+                >
+                  <X size={12} />
+                </button>
+              </div>
+            ))}
+          </div>
+        )}
       </div>
     </div>
   );
@@ -1,10 +1,13 @@
 import { useRef, useEffect, useState, useMemo, useCallback } from "react";
 import { useVirtualizer } from "@tanstack/react-virtual";
 import { ArrowDown } from "lucide-react";
+import { save } from "@tauri-apps/plugin-dialog";
 import { useAgentStore } from "../../stores/agentStore";
 import { LogEntryRow } from "./LogEntryRow";
 import { LogFilter, type LogFilterState } from "./LogFilter";
 import { deduplicateStreamLogs } from "../../lib/logParser";
+import * as tauri from "../../lib/tauri";
+import type { LogEntry } from "../../lib/types";
 
 interface Props {
   sessionId: string;
@@ -25,15 +28,43 @@ export function AgentLogViewer({ sessionId }: Props) {
   const [filter, setFilter] = useState<LogFilterState>({
     types: new Set(ALL_TYPES),
     search: "",
+    errorsOnly: false,
   });
+  // With "errors only" the entries come from the log store, fetched
+  // incrementally by id as new output arrives
+  const [errorLogs, setErrorLogs] = useState<LogEntry[] | null>(null);
+  const lastErrorId = useRef(0);
 
   const [isAtBottom, setIsAtBottom] = useState(true);
   const scrollRef = useRef<HTMLDivElement>(null);
 
+  useEffect(() => {
+    lastErrorId.current = 0;
+    setErrorLogs(null);
+  }, [sessionId, filter.errorsOnly]);
+
+  useEffect(() => {
+    if (!filter.errorsOnly) return;
+    let cancelled = false;
+    tauri
+      .getSessionLogsFiltered(sessionId, { errors_only: true }, lastErrorId.current)
+      .then((entries) => {
+        if (cancelled) return;
+        if (entries.length > 0) lastErrorId.current = entries[entries.length - 1].id;
+        setErrorLogs((prev) => [...(prev ?? []), ...entries]);
+      })
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+    };
+  }, [sessionId, filter.errorsOnly, sessionLogs.length]);
+
   // Deduplicate cumulative stream-json messages, then filter
   const filteredLogs = useMemo(() => {
-    const deduped = deduplicateStreamLogs(sessionLogs);
-    return deduped.filter((log) => {
+    const source: typeof sessionLogs = filter.errorsOnly
+      ? (errorLogs ?? [])
+      : deduplicateStreamLogs(sessionLogs);
+    return source.filter((log) => {
       if (!filter.types.has(log.message_type)) return false;
       if (
         filter.search &&
@@ -43,7 +74,7 @@ export function AgentLogViewer({ sessionId }: Props) {
       }
       return true;
     });
-  }, [sessionLogs, filter]);
+  }, [sessionLogs, errorLogs, filter]);
 
   const virtualizer = useVirtualizer({
     count: filteredLogs.length,
@@ -76,9 +107,29 @@ export function AgentLogViewer({ sessionId }: Props) {
     setIsAtBottom(true);
   };
 
+  const handleExport = async () => {
+    const path = await save({
+      defaultPath: `transcript-${sessionId.slice(0, 8)}.md`,
+      filters: [
+        { name: "Markdown", extensions: ["md"] },
+        { name: "HTML", extensions: ["html"] },
+        { name: "JSON Lines", extensions: ["jsonl"] },
+      ],
+    });
+    if (!path) return;
+    const lower = path.toLowerCase();
+    const format = lower.endsWith(".html") || lower.endsWith(".htm")
+      ? "html"
+      : lower.endsWith(".jsonl")
+        ? "jsonl"
+        : "markdown";
+    await tauri.exportLogs(sessionId, format, path);
+  };
+
   return (
     <div className="flex h-full flex-col">
       <LogFilter
+        onExport={handleExport}
         filter={filter}
         onFilterChange={setFilter}
         totalCount={sessionLogs.length}
@@ -1,9 +1,11 @@
 import { memo } from "react";
-import { Search, X } from "lucide-react";
+import { Download, Search, X } from "lucide-react";
 
 export interface LogFilterState {
   types: Set<string>;
   search: string;
+  /** Errors are queried from the log store rather than filtered in memory */
+  errorsOnly: boolean;
 }
 
 const MESSAGE_TYPES = [
@@ -19,6 +21,7 @@ interface Props {
   onFilterChange: (filter: LogFilterState) => void;
   totalCount: number;
   filteredCount: number;
+  onExport?: () => void;
 }
 
 export const LogFilter = memo(function LogFilter({
@@ -26,6 +29,7 @@ export const LogFilter = memo(function LogFilter({
   onFilterChange,
   totalCount,
   filteredCount,
+  onExport,
 }: Props) {
   const toggleType = (type: string) => {
     const next = new Set(filter.types);
@@ -64,6 +68,18 @@ export const LogFilter = memo(function LogFilter({
         })}
       </div>
 
+      <button
+        onClick={() => onFilterChange({ ...filter, errorsOnly: !filter.errorsOnly })}
+        className={`rounded px-1.5 py-0.5 text-[10px] font-medium transition-colors ${
+          filter.errorsOnly
+            ? "bg-red-950/50 text-red-400"
+            : "text-zinc-600 hover:text-zinc-400"
+        }`}
+        title="Only stderr, failed tool calls and error results"
+      >
+        Errors only
+      </button>
+
       {/* Separator */}
       <div className="h-4 w-px bg-zinc-800" />
 
@@ -93,6 +109,17 @@ export const LogFilter = memo(function LogFilter({
           ? `${totalCount}`
           : `${filteredCount}/${totalCount}`}
       </span>
+
+      {onExport && (
+        <button
+          onClick={onExport}
+          disabled={totalCount === 0}
+          className="flex-shrink-0 text-zinc-600 hover:text-zinc-400 disabled:opacity-40"
+          title="Export transcript"
+        >
+          <Download size={12} />
+        </button>
+      )}
     </div>
   );
 });
@@ -0,0 +1,170 @@
+import { useState } from "react";
+import * as Dialog from "@radix-ui/react-dialog";
+import { FileDiff as FileDiffIcon, X } from "lucide-react";
+import type { FileDiff } from "../../lib/types";
+import { applyPatch, previewPatch } from "../../lib/tauri";
+import { DiffViewer } from "./DiffViewer";
+import { toast } from "sonner";
+
+interface Props {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  onApplied: () => void;
+}
+
+/** Paste a patch (e.g. from a plan-mode agent), pick its files and hunks, apply them. */
+export function ApplyPatchDialog({ open, onOpenChange, onApplied }: Props) {
+  const [diffText, setDiffText] = useState("");
+  // Set once previewed; null while editing the text
+  const [files, setFiles] = useState<FileDiff[] | null>(null);
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  // Hunk indexes left out, per file
+  const [skipped, setSkipped] = useState<Record<string, Set<number>>>({});
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const reset = () => {
+    setDiffText("");
+    setFiles(null);
+    setError(null);
+  };
+
+  const handlePreview = async () => {
+    setBusy(true);
+    setError(null);
+    try {
+      const preview = await previewPatch(diffText);
+      setFiles(preview);
+      setSelected(new Set(preview.map((f) => f.path)));
+      setSkipped({});
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleApply = async () => {
+    if (!files) return;
+    setBusy(true);
+    setError(null);
+    try {
+      const all = selected.size === files.length;
+      const hunks: Record<string, number[]> = {};
+      for (const file of files) {
+        const left = skipped[file.path];
+        if (!left?.size) continue;
+        hunks[file.path] = file.hunks.map((_, i) => i).filter((i) => !left.has(i));
+      }
+      const applied = await applyPatch(
+        diffText,
+        all ? undefined : Array.from(selected),
+        Object.keys(hunks).length > 0 ? hunks : undefined,
+      );
+      toast.success(`Applied changes to ${applied.length} file${applied.length !== 1 ? "s" : ""}`);
+      reset();
+      onOpenChange(false);
+      onApplied();
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const toggle = (path: string) => {
+    const next = new Set(selected);
+    if (!next.delete(path)) next.add(path);
+    setSelected(next);
+  };
+
+  const toggleHunk = (file: FileDiff, index: number) => {
+    const next = new Set(skipped[file.path]);
+    if (!next.delete(index)) next.add(index);
+    setSkipped({ ...skipped, [file.path]: next });
+  };
+
+  return (
+    <Dialog.Root open={open} onOpenChange={onOpenChange}>
+      <Dialog.Portal>
+        <Dialog.Overlay className="fixed inset-0 bg-black/60 data-[state=open]:animate-in data-[state=open]:fade-in-0" />
+        <Dialog.Content className="fixed left-1/2 top-1/2 flex max-h-[85vh] w-full max-w-3xl -translate-x-1/2 -translate-y-1/2 flex-col rounded-xl border border-zinc-700 bg-surface-1 p-6 shadow-2xl">
+          <div className="mb-4 flex items-center justify-between">
+            <Dialog.Title className="text-base font-semibold text-zinc-100">
+              Apply Patch
+            </Dialog.Title>
+            <Dialog.Close className="rounded p-1 text-zinc-500 hover:bg-surface-2 hover:text-zinc-300">
+              <X size={16} />
+            </Dialog.Close>
+          </div>
+
+          {files === null ? (
+            <textarea
+              value={diffText}
+              onChange={(e) => setDiffText(e.target.value)}
+              placeholder="Paste a unified diff..."
+              rows={16}
+              spellCheck={false}
+              className="w-full resize-none rounded-lg border border-zinc-700 bg-surface-0 px-3 py-2 font-mono text-xs text-zinc-100 placeholder:text-zinc-600 outline-none focus:border-zinc-500"
+            />
+          ) : (
+            <div className="flex min-h-0 flex-1 flex-col gap-2 overflow-y-auto">
+              {files.map((file) => (
+                <div key={file.path} className="flex items-start gap-2">
+                  <input
+                    type="checkbox"
+                    checked={selected.has(file.path)}
+                    onChange={() => toggle(file.path)}
+                    className="mt-2.5 accent-blue-600"
+                    title="Apply this file's changes"
+                  />
+                  <div className="min-w-0 flex-1">
+                    <DiffViewer
+                      diff={file}
+                      defaultExpanded={files.length === 1}
+                      selectedHunks={
+                        new Set(file.hunks.map((_, i) => i).filter((i) => !skipped[file.path]?.has(i)))
+                      }
+                      onToggleHunk={file.hunks.length > 1 ? (i) => toggleHunk(file, i) : undefined}
+                    />
+                  </div>
+                </div>
+              ))}
+            </div>
+          )}
+
+          {error && <p className="mt-3 text-xs text-red-400">{error}</p>}
+
+          <div className="mt-4 flex justify-end gap-2">
+            {files === null ? (
+              <Dialog.Close className="rounded-lg px-4 py-2 text-sm text-zinc-400 hover:bg-surface-2 hover:text-zinc-200">
+                Cancel
+              </Dialog.Close>
+            ) : (
+              <button
+                onClick={() => setFiles(null)}
+                className="rounded-lg px-4 py-2 text-sm text-zinc-400 hover:bg-surface-2 hover:text-zinc-200"
+              >
+                Back
+              </button>
+            )}
+            <button
+              onClick={files === null ? handlePreview : handleApply}
+              disabled={busy || !diffText.trim() || (files !== null && selected.size === 0)}
+              className="flex items-center gap-2 rounded-lg bg-blue-600 px-4 py-2 text-sm font-medium text-white transition-colors hover:bg-blue-500 disabled:cursor-not-allowed disabled:opacity-50"
+            >
+              <FileDiffIcon size={14} />
+              {files === null
+                ? busy
+                  ? "Reading..."
+                  : "Preview"
+                : busy
+                  ? "Applying..."
+                  : `Apply ${selected.size} file${selected.size !== 1 ? "s" : ""}`}
+            </button>
+          </div>
+        </Dialog.Content>
+      </Dialog.Portal>
+    </Dialog.Root>
+  );
+}
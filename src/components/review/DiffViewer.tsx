@@ -1,11 +1,28 @@
 import { memo, useState } from "react";
-import { ChevronDown, ChevronRight, FilePlus, FileMinus, FileEdit, FileSymlink } from "lucide-react";
-import type { FileDiff, DiffLine } from "../../lib/types";
+import {
+  ChevronDown,
+  ChevronRight,
+  FilePlus,
+  FileMinus,
+  FileEdit,
+  FileSymlink,
+  Undo2,
+  UserSearch,
+} from "lucide-react";
+import type { FileDiff, DiffLine, BlameLine } from "../../lib/types";
 import { CHANGE_TYPE_COLORS } from "../../lib/types";
 
 interface Props {
   diff: FileDiff;
   defaultExpanded?: boolean;
+  /** Revert the whole file / one hunk; the buttons only show when given. */
+  onDiscardFile?: () => void;
+  onDiscardHunk?: (hunkHeader: string) => void;
+  /** Blame of the old side's lines `start..=end`; the button only shows when given. */
+  onBlame?: (startLine: number, endLine: number) => Promise<BlameLine[]>;
+  /** Hunks picked by index; the checkboxes only show when `onToggleHunk` is given. */
+  selectedHunks?: Set<number>;
+  onToggleHunk?: (index: number) => void;
 }
 
 const CHANGE_ICONS: Record<string, typeof FileEdit> = {
@@ -15,7 +32,28 @@ const CHANGE_ICONS: Record<string, typeof FileEdit> = {
   renamed: FileSymlink,
 };
 
-function DiffLineRow({ line }: { line: DiffLine }) {
+function formatSize(bytes: number | null): string {
+  if (bytes === null) return "?";
+  if (bytes < 1024) return `${bytes} B`;
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
+
+/** What to show in place of hunks: binaries and oversized files carry sizes. */
+function emptyDiffText(diff: FileDiff): string {
+  const sizes =
+    diff.change_type === "added"
+      ? formatSize(diff.new_size)
+      : diff.change_type === "deleted"
+        ? formatSize(diff.old_size)
+        : `${formatSize(diff.old_size)} → ${formatSize(diff.new_size)}`;
+  if (diff.is_binary) return `Binary file (${sizes})`;
+  if (diff.new_size !== null) return `Too large to show (${sizes})`;
+  if (diff.change_type === "renamed") return "Renamed without changes";
+  return "No diff content";
+}
+
+function DiffLineRow({ line, blame }: { line: DiffLine; blame?: BlameLine | null }) {
   const bgClass =
     line.line_type === "add"
       ? "bg-green-950/40"
@@ -33,6 +71,9 @@ function DiffLineRow({ line }: { line: DiffLine }) {
   const prefix =
     line.line_type === "add" ? "+" : line.line_type === "remove" ? "-" : " ";
 
+  const changedClass =
+    line.line_type === "add" ? "bg-green-700/50 text-green-200" : "bg-red-700/50 text-red-200";
+
   return (
     <div className={`flex font-mono text-[12px] leading-5 ${bgClass}`}>
       <span className="inline-block w-12 flex-shrink-0 select-none pr-2 text-right text-zinc-600">
@@ -41,9 +82,30 @@ function DiffLineRow({ line }: { line: DiffLine }) {
       <span className="inline-block w-12 flex-shrink-0 select-none pr-2 text-right text-zinc-600">
         {line.new_line ?? ""}
       </span>
+      {blame !== undefined && (
+        <span
+          className="inline-block w-40 flex-shrink-0 select-none truncate pr-2 text-[11px] text-zinc-500"
+          title={blame ? `${blame.commit.slice(0, 8)} ${blame.summary}\n${blame.author_email}` : undefined}
+        >
+          {blame &&
+            (blame.uncommitted
+              ? "uncommitted"
+              : `${blame.author_name} · ${new Date(blame.date).toLocaleDateString()}`)}
+        </span>
+      )}
       <span className={`flex-1 whitespace-pre-wrap break-all px-2 ${textClass}`}>
         {prefix}
-        {line.content}
+        {line.segments
+          ? line.segments.map((segment, i) =>
+              segment.changed ? (
+                <span key={i} className={`rounded-sm ${changedClass}`}>
+                  {segment.text}
+                </span>
+              ) : (
+                <span key={i}>{segment.text}</span>
+              ),
+            )
+          : line.content}
       </span>
     </div>
   );
@@ -52,8 +114,32 @@ function DiffLineRow({ line }: { line: DiffLine }) {
 export const DiffViewer = memo(function DiffViewer({
   diff,
   defaultExpanded = true,
+  onDiscardFile,
+  onDiscardHunk,
+  onBlame,
+  selectedHunks,
+  onToggleHunk,
 }: Props) {
   const [expanded, setExpanded] = useState(defaultExpanded);
+  // Blame by hunk index, keyed by old line number
+  const [blame, setBlame] = useState<Record<number, Map<number, BlameLine>>>({});
+
+  const toggleBlame = async (index: number, lines: DiffLine[]) => {
+    if (!onBlame) return;
+    if (blame[index]) {
+      const { [index]: _, ...rest } = blame;
+      setBlame(rest);
+      return;
+    }
+    const oldLines = lines.map((l) => l.old_line).filter((n): n is number => n !== null);
+    if (oldLines.length === 0) return;
+    try {
+      const result = await onBlame(Math.min(...oldLines), Math.max(...oldLines));
+      setBlame((b) => ({ ...b, [index]: new Map(result.map((l) => [l.line, l])) }));
+    } catch (e) {
+      console.error("Blame failed:", e);
+    }
+  };
 
   const Icon = CHANGE_ICONS[diff.change_type] ?? FileEdit;
   const color = CHANGE_TYPE_COLORS[diff.change_type] ?? "#f59e0b";
@@ -70,44 +156,93 @@ export const DiffViewer = memo(function DiffViewer({
   return (
     <div className="overflow-hidden rounded-lg border border-zinc-800">
       {/* File header */}
-      <button
-        onClick={() => setExpanded(!expanded)}
-        className="flex w-full items-center gap-2 bg-surface-2 px-3 py-2 text-left transition-colors hover:bg-zinc-800/80"
-      >
-        {expanded ? (
-          <ChevronDown size={14} className="text-zinc-500" />
-        ) : (
-          <ChevronRight size={14} className="text-zinc-500" />
-        )}
-        <Icon size={14} style={{ color }} />
-        <span className="flex-1 truncate font-mono text-xs text-zinc-200">
-          {diff.path}
-        </span>
-        <span className="text-[11px] text-zinc-500">{diff.change_type}</span>
-        {additions > 0 && (
-          <span className="text-[11px] text-green-500">+{additions}</span>
-        )}
-        {deletions > 0 && (
-          <span className="text-[11px] text-red-500">-{deletions}</span>
+      <div className="flex items-center bg-surface-2">
+        <button
+          onClick={() => setExpanded(!expanded)}
+          className="flex min-w-0 flex-1 items-center gap-2 px-3 py-2 text-left transition-colors hover:bg-zinc-800/80"
+        >
+          {expanded ? (
+            <ChevronDown size={14} className="text-zinc-500" />
+          ) : (
+            <ChevronRight size={14} className="text-zinc-500" />
+          )}
+          <Icon size={14} style={{ color }} />
+          <span className="flex-1 truncate font-mono text-xs text-zinc-200">
+            {diff.old_path && <span className="text-zinc-500">{diff.old_path} → </span>}
+            {diff.path}
+          </span>
+          <span className="text-[11px] text-zinc-500">{diff.change_type}</span>
+          {additions > 0 && (
+            <span className="text-[11px] text-green-500">+{additions}</span>
+          )}
+          {deletions > 0 && (
+            <span className="text-[11px] text-red-500">-{deletions}</span>
+          )}
+        </button>
+        {onDiscardFile && (
+          <button
+            onClick={onDiscardFile}
+            title="Discard all changes to this file"
+            className="px-2 py-2 text-zinc-500 transition-colors hover:text-red-400"
+          >
+            <Undo2 size={13} />
+          </button>
         )}
-      </button>
+      </div>
 
       {/* Hunks */}
       {expanded && (
         <div className="max-h-[600px] overflow-auto bg-surface-0">
           {diff.hunks.map((hunk, i) => (
             <div key={i}>
-              <div className="bg-blue-950/30 px-4 py-1 font-mono text-[11px] text-blue-400">
-                {hunk.header}
+              <div className="flex items-center bg-blue-950/30 px-4 py-1 font-mono text-[11px] text-blue-400">
+                {onToggleHunk && (
+                  <input
+                    type="checkbox"
+                    checked={selectedHunks?.has(i) ?? true}
+                    onChange={() => onToggleHunk(i)}
+                    className="mr-2 accent-blue-600"
+                    title="Apply this hunk"
+                  />
+                )}
+                <span className="flex-1">{hunk.header}</span>
+                {onBlame && hunk.lines.some((l) => l.old_line !== null) && (
+                  <button
+                    onClick={() => toggleBlame(i, hunk.lines)}
+                    title="Who wrote the lines this hunk changes"
+                    className={`mr-3 flex items-center gap-1 font-sans transition-colors hover:text-zinc-300 ${
+                      blame[i] ? "text-zinc-300" : "text-zinc-500"
+                    }`}
+                  >
+                    <UserSearch size={11} />
+                    Blame
+                  </button>
+                )}
+                {onDiscardHunk && (
+                  <button
+                    onClick={() => onDiscardHunk(hunk.header)}
+                    title="Discard this hunk"
+                    className="flex items-center gap-1 font-sans text-zinc-500 transition-colors hover:text-red-400"
+                  >
+                    <Undo2 size={11} />
+                    Discard
+                  </button>
+                )}
               </div>
               {hunk.lines.map((line, j) => (
-                <DiffLineRow key={j} line={line} />
+                <DiffLineRow
+                  key={j}
+                  line={line}
+                  blame={
+                    blame[i] ? (line.old_line !== null ? blame[i].get(line.old_line) ?? null : null) : undefined
+                  }
+                />
               ))}
             </div>
           ))}
           {diff.hunks.length === 0 && (
             <div className="px-4 py-3 text-xs text-zinc-500">
-              No diff content (binary file or empty change)
+              {emptyDiffText(diff)}
             </div>
           )}
         </div>
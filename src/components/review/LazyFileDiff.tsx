@@ -0,0 +1,79 @@
+import { useState, type ReactNode } from "react";
+import { ChevronRight, FileEdit, Loader2 } from "lucide-react";
+import type { ChangedFile, FileDiff } from "../../lib/types";
+import { getFileDiff } from "../../lib/tauri";
+
+interface Props {
+  file: ChangedFile;
+  /** Render one of the file's diffs (staged and unstaged) once loaded. */
+  renderDiff: (diff: FileDiff) => ReactNode;
+}
+
+/** A changed file whose diff is only fetched when it's opened. */
+export function LazyFileDiff({ file, renderDiff }: Props) {
+  const [diffs, setDiffs] = useState<FileDiff[] | null>(null);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = async () => {
+    setLoading(true);
+    setError(null);
+    try {
+      const [staged, unstaged] = await Promise.all([
+        getFileDiff(file.path, true),
+        getFileDiff(file.path, false),
+      ]);
+      setDiffs([staged, unstaged].filter((d): d is FileDiff => d !== null));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  if (diffs) {
+    return (
+      <>
+        {diffs.map((diff, i) => (
+          <div key={i}>{renderDiff(diff)}</div>
+        ))}
+      </>
+    );
+  }
+
+  return (
+    <div className="overflow-hidden rounded-lg border border-zinc-800">
+      <button
+        onClick={load}
+        disabled={loading}
+        className="flex w-full items-center gap-2 bg-surface-2 px-3 py-2 text-left transition-colors hover:bg-zinc-800/80"
+      >
+        {loading ? (
+          <Loader2 size={14} className="animate-spin text-zinc-500" />
+        ) : (
+          <ChevronRight size={14} className="text-zinc-500" />
+        )}
+        <FileEdit size={14} className="text-zinc-500" />
+        <span className="flex-1 truncate font-mono text-xs text-zinc-200">{file.path}</span>
+        {error && (
+          <span className="truncate text-[11px] text-red-400" title={error}>
+            {error}
+          </span>
+        )}
+        <span className="font-mono text-[11px] text-zinc-500">{file.status}</span>
+        {file.insertions === null ? (
+          <span className="text-[11px] text-zinc-500">binary</span>
+        ) : (
+          <>
+            {file.insertions > 0 && (
+              <span className="text-[11px] text-green-500">+{file.insertions}</span>
+            )}
+            {(file.deletions ?? 0) > 0 && (
+              <span className="text-[11px] text-red-500">-{file.deletions}</span>
+            )}
+          </>
+        )}
+      </button>
+    </div>
+  );
+}
@@ -1,4 +1,5 @@
 import { useState, useEffect, useCallback } from "react";
+import { listen } from "@tauri-apps/api/event";
 import {
   RefreshCw,
   CheckCircle2,
@@ -6,10 +7,35 @@ import {
   MessageSquarePlus,
   Loader2,
   GitBranch,
+  Bot,
+  Sparkles,
+  Copy,
+  History,
+  GitCommitHorizontal,
+  FileDiff as FileDiffIcon,
 } from "lucide-react";
-import type { AgentSession, FileDiff, ChangedFile } from "../../lib/types";
-import { getChangedFiles, getDiff } from "../../lib/tauri";
+import type {
+  AgentSession,
+  FileDiff,
+  ChangedFile,
+  FileChange,
+  GitCommit,
+  WorkingTreeChangedEvent,
+} from "../../lib/types";
+import {
+  discardFile,
+  discardHunk,
+  getBlame,
+  getChangedFiles,
+  getCommits,
+  getDiff,
+  getSessionDiff,
+  getSessionFileChanges,
+  suggestCommitMessage,
+} from "../../lib/tauri";
+import { ApplyPatchDialog } from "./ApplyPatchDialog";
 import { DiffViewer } from "./DiffViewer";
+import { LazyFileDiff } from "./LazyFileDiff";
 import { useSpecStore } from "../../stores/specStore";
 import { useAgentStore } from "../../stores/agentStore";
 
@@ -17,13 +43,27 @@ interface Props {
   session: AgentSession;
 }
 
+/** Past this many changed files, diffs are loaded a file at a time. */
+const LAZY_DIFF_FILES = 50;
+
 export function ReviewPanel({ session }: Props) {
   const [changedFiles, setChangedFiles] = useState<ChangedFile[]>([]);
   const [diffs, setDiffs] = useState<FileDiff[]>([]);
+  // Too many files to diff up front; each loads when opened
+  const [lazy, setLazy] = useState(false);
+  const [touched, setTouched] = useState<FileChange[]>([]);
+  const [commits, setCommits] = useState<GitCommit[]>([]);
+  const [onlyTouched, setOnlyTouched] = useState(false);
+  // Diff the session's start/end snapshots instead of the working tree
+  const [sessionOnly, setSessionOnly] = useState(false);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
   const [revisionText, setRevisionText] = useState("");
   const [showRevisionInput, setShowRevisionInput] = useState(false);
+  const [commitMessage, setCommitMessage] = useState("");
+  const [suggesting, setSuggesting] = useState(false);
+  const [suggestError, setSuggestError] = useState<string | null>(null);
+  const [showApplyPatch, setShowApplyPatch] = useState(false);
 
   const specs = useSpecStore((s) => s.specs);
   const updateSpec = useSpecStore((s) => s.updateSpec);
@@ -33,46 +73,77 @@ export function ReviewPanel({ session }: Props) {
     (s) => s.assigned_session_id === session.id,
   );
 
-  const loadDiffs = useCallback(async () => {
-    setLoading(true);
+  // Quiet reloads (on working-tree changes) keep the current view until done
+  const loadDiffs = useCallback(async (quiet = false) => {
+    if (!quiet) setLoading(true);
     setError(null);
     try {
-      const [files, diffData] = await Promise.all([
-        getChangedFiles(),
-        getDiff(),
+      const files = await getChangedFiles();
+      const lazyDiffs = !sessionOnly && files.length > LAZY_DIFF_FILES;
+      const [diffData, fileChanges, sessionCommits] = await Promise.all([
+        sessionOnly ? getSessionDiff(session.id) : lazyDiffs ? [] : getDiff(),
+        getSessionFileChanges(session.id),
+        // Not being a git repo shows up in the diffs already
+        getCommits(session.started_at, session.ended_at).catch(() => []),
       ]);
       setChangedFiles(files);
+      setLazy(lazyDiffs);
       setDiffs(diffData);
+      setTouched(fileChanges);
+      setCommits(sessionCommits.filter((c) => c.session_id === session.id));
     } catch (e) {
       setError(String(e));
     } finally {
       setLoading(false);
     }
-  }, []);
+  }, [session.id, session.started_at, session.ended_at, sessionOnly]);
 
   useEffect(() => {
     loadDiffs();
   }, [loadDiffs]);
 
-  const totalAdditions = diffs.reduce(
-    (sum, d) =>
-      sum +
-      d.hunks.reduce(
-        (hs, h) => hs + h.lines.filter((l) => l.line_type === "add").length,
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<WorkingTreeChangedEvent>("git:working-tree-changed", () => loadDiffs(true)).then((fn) => {
+      unlisten = fn;
+    });
+    return () => unlisten?.();
+  }, [loadDiffs]);
+
+  // Paths this session's Edit/Write calls touched, to tell its work apart
+  // from other changes in the working tree
+  const touchedPaths = new Set(touched.map((c) => c.file_path));
+  const visibleDiffs = onlyTouched
+    ? diffs.filter((d) => touchedPaths.has(d.path))
+    : diffs;
+  const visibleFiles = onlyTouched
+    ? changedFiles.filter((f) => touchedPaths.has(f.path))
+    : changedFiles;
+  const visibleCount = lazy ? visibleFiles.length : visibleDiffs.length;
+
+  const totalAdditions = lazy
+    ? changedFiles.reduce((sum, f) => sum + (f.insertions ?? 0), 0)
+    : diffs.reduce(
+        (sum, d) =>
+          sum +
+          d.hunks.reduce(
+            (hs, h) => hs + h.lines.filter((l) => l.line_type === "add").length,
+            0,
+          ),
         0,
-      ),
-    0,
-  );
-  const totalDeletions = diffs.reduce(
-    (sum, d) =>
-      sum +
-      d.hunks.reduce(
-        (hs, h) =>
-          hs + h.lines.filter((l) => l.line_type === "remove").length,
+      );
+  const totalDeletions = lazy
+    ? changedFiles.reduce((sum, f) => sum + (f.deletions ?? 0), 0)
+    : diffs.reduce(
+        (sum, d) =>
+          sum +
+          d.hunks.reduce(
+            (hs, h) =>
+              hs + h.lines.filter((l) => l.line_type === "remove").length,
+            0,
+          ),
         0,
-      ),
-    0,
-  );
+      );
 
   const handleApprove = async () => {
     if (linkedSpec) {
@@ -93,6 +164,61 @@ export function ReviewPanel({ session }: Props) {
     setShowRevisionInput(false);
   };
 
+  const handleDiscard = async (
+    path: string,
+    hunkHeader?: string,
+    oldPath?: string | null,
+    staged = false,
+  ) => {
+    const what = hunkHeader ? `this hunk of ${path}` : `all changes to ${path}`;
+    if (!confirm(`Discard ${what}? This cannot be undone.`)) return;
+    try {
+      await (hunkHeader ? discardHunk(path, hunkHeader, staged) : discardFile(path));
+      // Undoing a rename also brings back the original file
+      if (!hunkHeader && oldPath) await discardFile(oldPath);
+      await loadDiffs();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const handleSuggestCommit = async () => {
+    setSuggesting(true);
+    setSuggestError(null);
+    try {
+      // Describe only what's shown when filtered to the agent's files
+      const paths = !onlyTouched
+        ? undefined
+        : lazy
+          ? visibleFiles.map((f) => f.path)
+          : visibleDiffs.map((d) => d.path);
+      setCommitMessage(await suggestCommitMessage(paths));
+    } catch (e) {
+      setSuggestError(String(e));
+    } finally {
+      setSuggesting(false);
+    }
+  };
+
+  const renderDiff = (diff: FileDiff) => (
+    <DiffViewer
+      diff={diff}
+      // Snapshot hunks don't line up with the working tree
+      onDiscardFile={
+        sessionOnly ? undefined : () => handleDiscard(diff.path, undefined, diff.old_path)
+      }
+      onDiscardHunk={
+        sessionOnly ? undefined : (header) => handleDiscard(diff.path, header, null, diff.staged)
+      }
+      // Old line numbers are HEAD's only in the working-tree diff
+      onBlame={
+        sessionOnly || diff.change_type === "added"
+          ? undefined
+          : (start, end) => getBlame(diff.old_path ?? diff.path, start, end, "HEAD")
+      }
+    />
+  );
+
   if (loading) {
     return (
       <div className="flex h-full items-center justify-center">
@@ -107,11 +233,19 @@ export function ReviewPanel({ session }: Props) {
       <div className="flex h-full flex-col items-center justify-center gap-3">
         <p className="text-sm text-red-400">{error}</p>
         <button
-          onClick={loadDiffs}
+          onClick={() => loadDiffs()}
           className="flex items-center gap-1 text-xs text-zinc-400 hover:text-zinc-200"
         >
           <RefreshCw size={12} /> Retry
         </button>
+        {sessionOnly && (
+          <button
+            onClick={() => setSessionOnly(false)}
+            className="text-xs text-zinc-400 hover:text-zinc-200"
+          >
+            Show the whole working tree
+          </button>
+        )}
       </div>
     );
   }
@@ -125,6 +259,11 @@ export function ReviewPanel({ session }: Props) {
           <div>
             <h3 className="text-sm font-medium text-zinc-100">
               Output Review
+              {session.branch && (
+                <span className="ml-2 font-mono text-[11px] font-normal text-zinc-500">
+                  {session.branch}
+                </span>
+              )}
             </h3>
             <p className="text-[11px] text-zinc-500">
               {changedFiles.length} file{changedFiles.length !== 1 ? "s" : ""}{" "}
@@ -143,18 +282,78 @@ export function ReviewPanel({ session }: Props) {
           </div>
         </div>
 
-        <button
-          onClick={loadDiffs}
-          className="rounded p-1.5 text-zinc-500 transition-colors hover:bg-surface-2 hover:text-zinc-300"
-          title="Refresh diffs"
-        >
-          <RefreshCw size={14} />
-        </button>
+        <div className="flex items-center gap-1">
+          <button
+            onClick={() => setSessionOnly(!sessionOnly)}
+            className={`flex items-center gap-1 rounded px-2 py-1 text-[11px] transition-colors ${
+              sessionOnly
+                ? "bg-surface-2 text-zinc-200"
+                : "text-zinc-500 hover:text-zinc-300"
+            }`}
+            title="Only changes made while this session ran"
+          >
+            <History size={12} />
+            This session
+          </button>
+          {touchedPaths.size > 0 && (
+            <button
+              onClick={() => setOnlyTouched(!onlyTouched)}
+              className={`flex items-center gap-1 rounded px-2 py-1 text-[11px] transition-colors ${
+                onlyTouched
+                  ? "bg-surface-2 text-zinc-200"
+                  : "text-zinc-500 hover:text-zinc-300"
+              }`}
+              title={Array.from(touchedPaths).join("\n")}
+            >
+              <Bot size={12} />
+              {touchedPaths.size} touched by agent
+            </button>
+          )}
+          <button
+            onClick={() => setShowApplyPatch(true)}
+            className="rounded p-1.5 text-zinc-500 transition-colors hover:bg-surface-2 hover:text-zinc-300"
+            title="Apply a patch"
+          >
+            <FileDiffIcon size={14} />
+          </button>
+          <button
+            onClick={() => loadDiffs()}
+            className="rounded p-1.5 text-zinc-500 transition-colors hover:bg-surface-2 hover:text-zinc-300"
+            title="Refresh diffs"
+          >
+            <RefreshCw size={14} />
+          </button>
+        </div>
       </div>
 
+      <ApplyPatchDialog
+        open={showApplyPatch}
+        onOpenChange={setShowApplyPatch}
+        onApplied={() => loadDiffs()}
+      />
+
       {/* Diff list */}
       <div className="flex-1 overflow-y-auto p-4">
-        {diffs.length === 0 ? (
+        {commits.length > 0 && (
+          <div className="mb-3 rounded-lg border border-zinc-800">
+            <div className="border-b border-zinc-800 bg-surface-2 px-3 py-2 text-[11px] font-medium text-zinc-400">
+              Committed during this session
+            </div>
+            {commits.map((commit) => (
+              <div
+                key={commit.hash}
+                className="flex items-center gap-2 px-3 py-1.5 text-xs"
+                title={`${commit.author_name} <${commit.author_email}>\n${new Date(commit.date).toLocaleString()}`}
+              >
+                <GitCommitHorizontal size={13} className="flex-shrink-0 text-zinc-500" />
+                <span className="font-mono text-[11px] text-blue-400">{commit.hash.slice(0, 8)}</span>
+                <span className="flex-1 truncate text-zinc-300">{commit.subject}</span>
+                <span className="text-[11px] text-zinc-600">{commit.author_name}</span>
+              </div>
+            ))}
+          </div>
+        )}
+        {visibleCount === 0 ? (
           <div className="flex flex-col items-center justify-center gap-2 py-12">
             <GitBranch size={32} className="text-zinc-700" />
             <p className="text-sm text-zinc-500">No diffs found</p>
@@ -164,13 +363,58 @@ export function ReviewPanel({ session }: Props) {
           </div>
         ) : (
           <div className="flex flex-col gap-3">
-            {diffs.map((diff) => (
-              <DiffViewer key={diff.path} diff={diff} />
-            ))}
+            {lazy
+              ? visibleFiles.map((file) => (
+                  // Changed stats remount it, dropping a stale loaded diff
+                  <LazyFileDiff
+                    key={`${file.path}:${file.insertions}:${file.deletions}`}
+                    file={file}
+                    renderDiff={renderDiff}
+                  />
+                ))
+              : visibleDiffs.map((diff) => <div key={diff.path}>{renderDiff(diff)}</div>)}
           </div>
         )}
       </div>
 
+      {/* Commit message */}
+      {visibleCount > 0 && (
+        <div className="flex flex-shrink-0 flex-col gap-2 border-t border-zinc-800 px-4 py-3">
+          <textarea
+            value={commitMessage}
+            onChange={(e) => setCommitMessage(e.target.value)}
+            placeholder="Commit message"
+            rows={commitMessage.includes("\n") ? 5 : 2}
+            spellCheck={false}
+            className="resize-none rounded-lg border border-zinc-700 bg-surface-0 px-3 py-2 font-mono text-xs text-zinc-100 placeholder:text-zinc-600 focus:border-blue-600 focus:outline-none"
+          />
+          <div className="flex items-center gap-2">
+            {suggestError && (
+              <span className="flex-1 truncate text-[11px] text-red-400" title={suggestError}>
+                {suggestError}
+              </span>
+            )}
+            <div className="flex-1" />
+            <button
+              onClick={() => navigator.clipboard.writeText(commitMessage)}
+              disabled={!commitMessage.trim()}
+              className="flex items-center gap-1.5 rounded-lg border border-zinc-700 px-3 py-1.5 text-xs text-zinc-300 transition-colors hover:bg-surface-2 disabled:opacity-40"
+            >
+              <Copy size={13} />
+              Copy
+            </button>
+            <button
+              onClick={handleSuggestCommit}
+              disabled={suggesting}
+              className="flex items-center gap-1.5 rounded-lg border border-zinc-700 px-3 py-1.5 text-xs text-zinc-300 transition-colors hover:bg-surface-2 disabled:opacity-40"
+            >
+              {suggesting ? <Loader2 size={13} className="animate-spin" /> : <Sparkles size={13} />}
+              Suggest
+            </button>
+          </div>
+        </div>
+      )}
+
       {/* Actions footer */}
       {(linkedSpec || session.status === "completed") && (
         <div className="flex flex-shrink-0 flex-col border-t border-zinc-800">
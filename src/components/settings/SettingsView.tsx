@@ -0,0 +1,282 @@
+import { useEffect, useState } from "react";
+import { Copy, Download, RefreshCw, Upload, X } from "lucide-react";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
+import {
+  allowProjectEnv,
+  exportSettings,
+  getHttpApi,
+  getProjectEnv,
+  importSettings,
+  regenerateHttpApiToken,
+  revokeProjectEnv,
+  setHttpApi,
+} from "../../lib/tauri";
+import type { HttpApiStatus, ProjectEnv } from "../../lib/types";
+
+const ARCHIVE_FILTERS = [{ name: "Clautron settings", extensions: ["gz"] }];
+
+export function SettingsView() {
+  const [busy, setBusy] = useState(false);
+
+  async function handleExport() {
+    const path = await save({ defaultPath: "clautron-settings.json.gz", filters: ARCHIVE_FILTERS });
+    if (!path) return;
+    setBusy(true);
+    try {
+      await exportSettings(path);
+      toast.success("Settings exported", {
+        description: "The archive contains your integration credentials; keep it private.",
+      });
+    } catch (e) {
+      toast.error(`Export failed: ${e}`);
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function handleImport() {
+    const path = await open({ multiple: false, filters: ARCHIVE_FILTERS });
+    if (!path) return;
+    if (!confirm("Replace your settings with the archive's? Approvals and templates are added to yours.")) {
+      return;
+    }
+    setBusy(true);
+    try {
+      const report = await importSettings(path as string);
+      toast.success("Settings imported", {
+        description: `${report.agent_approvals} agent approval(s) and ${report.workflow_templates} workflow template(s) added.`,
+      });
+    } catch (e) {
+      toast.error(`Import failed: ${e}`);
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  return (
+    <div className="flex flex-1 flex-col overflow-hidden">
+      <header className="flex h-12 flex-shrink-0 items-center border-b border-zinc-800 px-6">
+        <h2 className="text-sm font-medium text-zinc-300">Settings</h2>
+      </header>
+      <div className="flex-1 overflow-y-auto p-6">
+        <section className="max-w-lg rounded-lg border border-zinc-800 bg-surface-1 p-4">
+          <h3 className="text-sm font-medium text-zinc-200">Move to another machine</h3>
+          <p className="mt-1 text-xs text-zinc-500">
+            One archive with the app config, Claude profiles, agent approvals and workflow
+            templates.
+          </p>
+          <div className="mt-3 flex gap-2">
+            <button
+              onClick={handleExport}
+              disabled={busy}
+              className="flex items-center gap-1.5 rounded-lg border border-zinc-700 px-3 py-1.5 text-xs text-zinc-300 hover:bg-surface-2 disabled:opacity-50"
+            >
+              <Download size={12} />
+              Export
+            </button>
+            <button
+              onClick={handleImport}
+              disabled={busy}
+              className="flex items-center gap-1.5 rounded-lg border border-zinc-700 px-3 py-1.5 text-xs text-zinc-300 hover:bg-surface-2 disabled:opacity-50"
+            >
+              <Upload size={12} />
+              Import
+            </button>
+          </div>
+        </section>
+        <ProjectEnvSection />
+        <HttpApiSection />
+      </div>
+    </div>
+  );
+}
+
+function ProjectEnvSection() {
+  const [env, setEnv] = useState<ProjectEnv | null>(null);
+  const [name, setName] = useState("");
+
+  useEffect(() => {
+    getProjectEnv().then(setEnv).catch(() => setEnv(null));
+  }, []);
+
+  async function handleAllow(names: string[]) {
+    if (names.length === 0) return;
+    const acknowledged = confirm(
+      `Every agent in this project will be able to read ${names.join(", ")}. Allow?`,
+    );
+    if (!acknowledged) return;
+    try {
+      setEnv(await allowProjectEnv(names, acknowledged));
+      setName("");
+    } catch (e) {
+      toast.error(`${e}`);
+    }
+  }
+
+  async function handleRevoke(variable: string) {
+    try {
+      setEnv(await revokeProjectEnv([variable]));
+    } catch (e) {
+      toast.error(`${e}`);
+    }
+  }
+
+  if (!env) return null;
+
+  return (
+    <section className="mt-4 max-w-lg rounded-lg border border-zinc-800 bg-surface-1 p-4">
+      <h3 className="text-sm font-medium text-zinc-200">Agent environment</h3>
+      <p className="mt-1 text-xs text-zinc-500">
+        Variables from the app's environment passed to this project's agents, on top of the
+        defaults (PATH, HOME, proxies, …).
+      </p>
+      {env.allowed.length > 0 && (
+        <div className="mt-3 flex flex-wrap gap-1.5">
+          {env.allowed.map((variable) => (
+            <span
+              key={variable}
+              className="flex items-center gap-1 rounded bg-surface-2 px-2 py-0.5 font-mono text-xs text-zinc-300"
+            >
+              {variable}
+              <button
+                onClick={() => handleRevoke(variable)}
+                className="text-zinc-500 hover:text-zinc-300"
+                title="Stop passing"
+              >
+                <X size={10} />
+              </button>
+            </span>
+          ))}
+        </div>
+      )}
+      {env.requested.length > 0 && (
+        <div className="mt-3 rounded border border-amber-900/50 bg-amber-950/20 p-2 text-xs text-amber-300">
+          <p>
+            The project's config asks for {env.requested.map((v) => <code key={v} className="mr-1">{v}</code>)}
+          </p>
+          <button
+            onClick={() => handleAllow(env.requested)}
+            className="mt-1.5 rounded border border-amber-800 px-2 py-0.5 hover:bg-amber-950/40"
+          >
+            Allow all
+          </button>
+        </div>
+      )}
+      {env.refused.length > 0 && (
+        <p className="mt-3 rounded border border-red-900/50 bg-red-950/20 p-2 text-xs text-red-300">
+          The project's config also asks for {env.refused.map((v) => <code key={v} className="mr-1">{v}</code>)}
+          which can't be passed to agents: they let their value run code inside every agent.
+        </p>
+      )}
+      <form
+        className="mt-3 flex gap-2"
+        onSubmit={(e) => {
+          e.preventDefault();
+          handleAllow(name.trim() ? [name.trim()] : []);
+        }}
+      >
+        <input
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+          placeholder="VARIABLE_NAME"
+          className="flex-1 rounded-lg border border-zinc-700 bg-surface-0 px-2 py-1 font-mono text-xs text-zinc-200 placeholder:text-zinc-600"
+        />
+        <button
+          type="submit"
+          className="rounded-lg border border-zinc-700 px-3 py-1 text-xs text-zinc-300 hover:bg-surface-2"
+        >
+          Allow
+        </button>
+      </form>
+    </section>
+  );
+}
+
+function HttpApiSection() {
+  const [api, setApi] = useState<HttpApiStatus | null>(null);
+  const [port, setPort] = useState("");
+
+  useEffect(() => {
+    getHttpApi()
+      .then((status) => {
+        setApi(status);
+        setPort(String(status.port));
+      })
+      .catch(() => setApi(null));
+  }, []);
+
+  async function update(action: () => Promise<HttpApiStatus>) {
+    try {
+      setApi(await action());
+    } catch (e) {
+      toast.error(`${e}`);
+    }
+  }
+
+  function handleToggle() {
+    if (!api) return;
+    const parsed = Number(port);
+    update(() => setHttpApi(!api.enabled, Number.isInteger(parsed) && parsed > 0 ? parsed : undefined));
+  }
+
+  function handleRegenerate() {
+    if (!confirm("Replace the token? Clients using the current one will be refused.")) return;
+    update(regenerateHttpApiToken);
+  }
+
+  if (!api) return null;
+
+  return (
+    <section className="mt-4 max-w-lg rounded-lg border border-zinc-800 bg-surface-1 p-4">
+      <h3 className="text-sm font-medium text-zinc-200">Local HTTP API</h3>
+      <p className="mt-1 text-xs text-zinc-500">
+        Lets scripts and editors start and stop agents, run specs and workflows, and read sessions
+        and logs on <code>127.0.0.1</code>. Requests send the token as{" "}
+        <code>Authorization: Bearer</code>.
+      </p>
+      <div className="mt-3 flex items-center gap-2">
+        <input
+          value={port}
+          onChange={(e) => setPort(e.target.value)}
+          disabled={api.enabled}
+          inputMode="numeric"
+          className="w-20 rounded-lg border border-zinc-700 bg-surface-0 px-2 py-1 font-mono text-xs text-zinc-200 disabled:opacity-50"
+        />
+        <button
+          onClick={handleToggle}
+          className="rounded-lg border border-zinc-700 px-3 py-1 text-xs text-zinc-300 hover:bg-surface-2"
+        >
+          {api.enabled ? "Disable" : "Enable"}
+        </button>
+        <span className="text-xs text-zinc-500">
+          {api.listening ? `Listening on port ${api.listening}` : "Stopped"}
+        </span>
+      </div>
+      {api.token && (
+        <div className="mt-3 flex items-center gap-2">
+          <code className="flex-1 truncate rounded bg-surface-2 px-2 py-1 text-xs text-zinc-300">
+            {api.token}
+          </code>
+          <button
+            onClick={() => {
+              navigator.clipboard.writeText(api.token ?? "");
+              toast.success("Token copied");
+            }}
+            className="text-zinc-500 hover:text-zinc-300"
+            title="Copy token"
+          >
+            <Copy size={12} />
+          </button>
+          <button
+            onClick={handleRegenerate}
+            className="text-zinc-500 hover:text-zinc-300"
+            title="New token"
+          >
+            <RefreshCw size={12} />
+          </button>
+        </div>
+      )}
+    </section>
+  );
+}
@@ -0,0 +1,111 @@
+import { useEffect, useState } from "react";
+import { Loader2, X } from "lucide-react";
+import * as tauri from "../../lib/tauri";
+import type { ProposedSpec, Spec } from "../../lib/types";
+import { SPEC_PRIORITY_COLORS } from "../../lib/types";
+
+/** Preview an AI-generated breakdown and create the selected child specs. */
+export function BreakdownDialog({
+  spec,
+  onClose,
+  onCreated,
+}: {
+  spec: Spec;
+  onClose: () => void;
+  onCreated: () => void;
+}) {
+  const [proposals, setProposals] = useState<ProposedSpec[] | null>(null);
+  const [selected, setSelected] = useState<boolean[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [creating, setCreating] = useState(false);
+
+  useEffect(() => {
+    tauri
+      .breakdownSpec(spec.file_path)
+      .then((result) => {
+        setProposals(result);
+        setSelected(result.map(() => true));
+      })
+      .catch((e) => setError(String(e)));
+  }, [spec.file_path]);
+
+  async function handleCreate() {
+    if (!proposals) return;
+    setCreating(true);
+    try {
+      await tauri.applySpecBreakdown(
+        spec.file_path,
+        proposals.filter((_, i) => selected[i]),
+      );
+      onCreated();
+      onClose();
+    } catch (e) {
+      setError(String(e));
+      setCreating(false);
+    }
+  }
+
+  const count = selected.filter(Boolean).length;
+
+  return (
+    <div className="border-b border-zinc-800 bg-surface-1 px-4 py-3">
+      <div className="mb-2 flex items-center justify-between">
+        <span className="text-xs font-medium text-zinc-300">Break down into child specs</span>
+        <button onClick={onClose} className="text-zinc-500 hover:text-zinc-300">
+          <X size={14} />
+        </button>
+      </div>
+
+      {error && <div className="mb-2 text-xs text-red-400">{error}</div>}
+
+      {!proposals && !error && (
+        <div className="flex items-center gap-2 text-xs text-zinc-500">
+          <Loader2 size={12} className="animate-spin" /> Asking Claude for a breakdown...
+        </div>
+      )}
+
+      {proposals && (
+        <>
+          <div className="max-h-72 space-y-1 overflow-y-auto">
+            {proposals.map((p, i) => (
+              <label key={i} className="flex items-start gap-2 rounded bg-surface-2 px-2 py-1.5 text-xs text-zinc-300">
+                <input
+                  type="checkbox"
+                  checked={selected[i]}
+                  onChange={() => setSelected(selected.map((s, j) => (j === i ? !s : s)))}
+                  className="mt-0.5"
+                />
+                <div className="flex-1">
+                  <div className="flex items-center gap-2">
+                    <span className="text-[10px] font-medium" style={{ color: SPEC_PRIORITY_COLORS[p.priority] }}>
+                      {p.priority.toUpperCase()}
+                    </span>
+                    <span className="text-zinc-100">{p.title}</span>
+                    {p.suggested_agent && <span className="text-[10px] text-zinc-500">@{p.suggested_agent}</span>}
+                  </div>
+                  {p.acceptance_criteria.length > 0 && (
+                    <ul className="mt-0.5 list-disc pl-4 text-[10px] text-zinc-500">
+                      {p.acceptance_criteria.map((c, j) => <li key={j}>{c}</li>)}
+                    </ul>
+                  )}
+                </div>
+              </label>
+            ))}
+          </div>
+          <div className="mt-2 flex items-center gap-3">
+            <button
+              onClick={handleCreate}
+              disabled={creating || count === 0}
+              className="rounded bg-green-600 px-3 py-1.5 text-xs font-medium text-white hover:bg-green-500 disabled:opacity-50"
+            >
+              Create {count} child spec{count === 1 ? "" : "s"}
+            </button>
+            <button onClick={onClose} className="text-xs text-zinc-400 hover:text-zinc-200">
+              Cancel
+            </button>
+          </div>
+        </>
+      )}
+    </div>
+  );
+}
@@ -4,14 +4,29 @@ import { EditorView, keymap } from "@codemirror/view";
 import { markdown } from "@codemirror/lang-markdown";
 import { oneDark } from "@codemirror/theme-one-dark";
 import { defaultKeymap, history, historyKeymap } from "@codemirror/commands";
-import { Save, X, Plus, Play, Check, XCircle, Eye } from "lucide-react";
+import { Save, X, Plus, Play, Check, XCircle, Eye, ShieldCheck, GitFork, AlertTriangle } from "lucide-react";
 import { useSpecStore } from "../../stores/specStore";
 import { useAgentStore } from "../../stores/agentStore";
-import type { Spec, SpecPriority, SpecStatus, SpecUpdate } from "../../lib/types";
+import { BreakdownDialog } from "./BreakdownDialog";
+import * as tauri from "../../lib/tauri";
+import { formatRunEstimate } from "../../lib/formatters";
+import type {
+  AcceptanceCriterion,
+  RunEstimate,
+  Spec,
+  SpecConflict,
+  SpecPriority,
+  SpecRevision,
+  SpecSessionRecord,
+  SpecStatus,
+  SpecUpdate,
+} from "../../lib/types";
 import {
-  SPEC_STATUS_COLORS,
-  SPEC_STATUS_LABELS,
+  isSpecConflict,
+  specStatusColor,
+  specStatusLabel,
   SPEC_PRIORITY_COLORS,
+  SPEC_STATUS_LABELS,
 } from "../../lib/types";
 
 export function SpecEditor() {
@@ -19,6 +34,9 @@ export function SpecEditor() {
   const selectedSpecPath = useSpecStore((s) => s.selectedSpecPath);
   const updateSpec = useSpecStore((s) => s.updateSpec);
   const selectSpec = useSpecStore((s) => s.selectSpec);
+  const loadSpecs = useSpecStore((s) => s.loadSpecs);
+  // Bumped to remount the editor with the on-disk version after a conflict
+  const [reloadCount, setReloadCount] = useState(0);
 
   const spec = useMemo(
     () => specs.find((s) => s.file_path === selectedSpecPath) ?? null,
@@ -33,42 +51,97 @@ export function SpecEditor() {
     );
   }
 
-  return <SpecEditorInner key={spec.file_path} spec={spec} updateSpec={updateSpec} onClose={() => selectSpec(null)} />;
+  return (
+    <SpecEditorInner
+      key={`${spec.file_path}:${reloadCount}`}
+      spec={spec}
+      updateSpec={updateSpec}
+      onClose={() => selectSpec(null)}
+      onReload={async () => {
+        await loadSpecs();
+        setReloadCount((n) => n + 1);
+      }}
+    />
+  );
 }
 
 function SpecEditorInner({
   spec,
   updateSpec,
   onClose,
+  onReload,
 }: {
   spec: Spec;
-  updateSpec: (filePath: string, update: SpecUpdate) => Promise<void>;
+  updateSpec: (filePath: string, update: SpecUpdate) => Promise<Spec>;
   onClose: () => void;
+  onReload: () => void;
 }) {
   const editorRef = useRef<HTMLDivElement>(null);
   const viewRef = useRef<EditorView | null>(null);
   const saveTimerRef = useRef<ReturnType<typeof setTimeout> | null>(null);
+  // mtime of the version this editor was loaded from or last saved
+  const mtimeRef = useRef(spec.file_mtime);
 
   const runSpec = useSpecStore((s) => s.runSpec);
+  const runSpecPair = useSpecStore((s) => s.runSpecPair);
+  const verifySpec = useSpecStore((s) => s.verifySpec);
+  const reviewSpec = useSpecStore((s) => s.reviewSpec);
+  const loadSpecs = useSpecStore((s) => s.loadSpecs);
+  const workflow = useSpecStore((s) => s.workflow);
   const configs = useAgentStore((s) => s.configs);
   const openDetail = useAgentStore((s) => s.openDetail);
 
   // Local state for frontmatter fields
   const [title, setTitle] = useState(spec.title);
   const [priority, setPriority] = useState<SpecPriority>(spec.priority);
+  const [estimate, setEstimate] = useState(spec.estimate?.toString() ?? "");
+  const [dueDate, setDueDate] = useState(spec.due_date ?? "");
+  const [branch, setBranch] = useState(spec.branch ?? "");
+  const [assignees, setAssignees] = useState(spec.assigned_agents.join(", "));
+  const [labels, setLabels] = useState(spec.labels.join(", "));
   const [status, setStatus] = useState<SpecStatus>(spec.status);
-  const [criteria, setCriteria] = useState<string[]>(spec.acceptance_criteria);
+  const [criteria, setCriteria] = useState<AcceptanceCriterion[]>(spec.acceptance_criteria);
   const [newCriterion, setNewCriterion] = useState("");
+  const [reviewComment, setReviewComment] = useState("");
   const [dirty, setDirty] = useState(false);
-  const [showRunDialog, setShowRunDialog] = useState(false);
-  const [selectedAgent, setSelectedAgent] = useState(configs[0]?.name ?? "");
-  const [selectedModel, setSelectedModel] = useState(configs[0]?.model ?? "sonnet");
+  const [runDialog, setRunDialog] = useState<"run" | "verify" | null>(null);
+  const [showBreakdown, setShowBreakdown] = useState(false);
+  const [history, setHistory] = useState<SpecRevision[] | null>(null);
+  const [sessions, setSessions] = useState<SpecSessionRecord[]>(spec.sessions);
+  const [conflict, setConflict] = useState<{ conflict: SpecConflict; update: SpecUpdate } | null>(null);
+  const defaultAgent = configs.find((c) => c.name === spec.suggested_agent) ?? configs[0];
+  const [selectedAgent, setSelectedAgent] = useState(defaultAgent?.name ?? "");
+  const [selectedModel, setSelectedModel] = useState(defaultAgent?.model ?? "sonnet");
+  const [runEstimate, setRunEstimate] = useState<RunEstimate | null>(null);
+
+  // Predict the run from the selected agent's past sessions
+  useEffect(() => {
+    if (runDialog !== "run" || !selectedAgent) {
+      setRunEstimate(null);
+      return;
+    }
+    tauri
+      .estimateCost({ specPath: spec.file_path, agentName: selectedAgent })
+      .then(setRunEstimate)
+      .catch(() => setRunEstimate(null));
+  }, [runDialog, selectedAgent, spec.file_path]);
 
   // Sync from spec prop changes (e.g., when status updates from backend)
   useEffect(() => {
     setStatus(spec.status);
   }, [spec.status]);
 
+  // Verification results are written by the backend
+  useEffect(() => {
+    setCriteria(spec.acceptance_criteria);
+  }, [spec.acceptance_criteria]);
+
+  // Session history, with live usage for runs still in progress
+  useEffect(() => {
+    if (spec.sessions.length === 0) return;
+    tauri.getSpecSessions(spec.file_path).then(setSessions).catch(() => setSessions(spec.sessions));
+  }, [spec.file_path, spec.sessions]);
+
   useEffect(() => {
     if (spec.assigned_agent) {
       setSelectedAgent(spec.assigned_agent);
@@ -79,14 +152,31 @@ function SpecEditorInner({
   const debouncedSave = useCallback(
     (update: SpecUpdate) => {
       if (saveTimerRef.current) clearTimeout(saveTimerRef.current);
-      saveTimerRef.current = setTimeout(() => {
-        updateSpec(spec.file_path, update);
-        setDirty(false);
+      saveTimerRef.current = setTimeout(async () => {
+        try {
+          const saved = await updateSpec(spec.file_path, { ...update, expected_mtime: mtimeRef.current });
+          mtimeRef.current = saved.file_mtime;
+          setDirty(false);
+        } catch (e) {
+          if (!isSpecConflict(e)) throw e;
+          setConflict({ conflict: e, update });
+        }
       }, 1000);
     },
     [spec.file_path, updateSpec],
   );
 
+  async function handleOverwrite() {
+    if (!conflict) return;
+    const saved = await updateSpec(spec.file_path, {
+      ...conflict.update,
+      expected_mtime: conflict.conflict.current.file_mtime,
+    });
+    mtimeRef.current = saved.file_mtime;
+    setConflict(null);
+    setDirty(false);
+  }
+
   const saveFrontmatter = useCallback(
     (overrides?: Partial<SpecUpdate>) => {
       const update: SpecUpdate = {
@@ -141,12 +231,18 @@ function SpecEditorInner({
 
   function addCriterion() {
     if (!newCriterion.trim()) return;
-    const updated = [...criteria, newCriterion.trim()];
+    const updated = [...criteria, { text: newCriterion.trim(), done: false }];
     setCriteria(updated);
     setNewCriterion("");
     saveFrontmatter({ acceptance_criteria: updated });
   }
 
+  function toggleCriterion(index: number) {
+    const updated = criteria.map((c, i) => (i === index ? { ...c, done: !c.done } : c));
+    setCriteria(updated);
+    saveFrontmatter({ acceptance_criteria: updated });
+  }
+
   function removeCriterion(index: number) {
     const updated = criteria.filter((_, i) => i !== index);
     setCriteria(updated);
@@ -155,23 +251,45 @@ function SpecEditorInner({
 
   async function handleRun() {
     if (!selectedAgent) return;
-    await runSpec(spec.file_path, selectedAgent, selectedModel);
-    setShowRunDialog(false);
+    if (runDialog === "verify") {
+      await verifySpec(spec.file_path, selectedAgent, selectedModel);
+    } else {
+      await runSpec(spec.file_path, selectedAgent, selectedModel);
+    }
+    setRunDialog(null);
+  }
+
+  async function handleRunPair() {
+    await runSpecPair(spec.file_path, selectedModel);
+    setRunDialog(null);
   }
 
   async function handleMarkDone() {
     setStatus("done");
-    await updateSpec(spec.file_path, { status: "done" });
+    await reviewSpec(spec.file_path, "approved", reviewComment);
+    setReviewComment("");
   }
 
   async function handleReject() {
+    if (!reviewComment.trim()) return;
     setStatus("rejected");
-    await updateSpec(spec.file_path, { status: "rejected" });
+    await reviewSpec(spec.file_path, "rejected", reviewComment);
+    setReviewComment("");
   }
 
+  // Statuses from the project's workflow; the current one stays selectable even if undefined
+  const statusOptions = Array.from(
+    new Set<SpecStatus>([...(workflow?.statuses.map((s) => s.id) ?? Object.keys(SPEC_STATUS_LABELS)), spec.status]),
+  );
+  const allowedNext = workflow?.transitions[spec.status];
+  const canMoveTo = (s: SpecStatus) => s === spec.status || !allowedNext || allowedNext.includes(s);
+
   const canRun = spec.status === "draft" || spec.status === "rejected";
-  const canMarkDone = spec.status === "review";
-  const canReject = spec.status === "review";
+  const inReview = spec.status === "review" || spec.status === workflow?.on_agent_completed;
+  const canMarkDone = inReview && canMoveTo("done");
+  const canReject = inReview && canMoveTo("rejected");
+  const verifying = spec.verification?.status === "running";
+  const canVerify = criteria.length > 0 && !verifying;
 
   return (
     <div className="flex h-full flex-col">
@@ -180,14 +298,14 @@ function SpecEditorInner({
         <div className="flex items-center gap-2">
           <span
             className="h-2 w-2 rounded-full"
-            style={{ backgroundColor: SPEC_STATUS_COLORS[spec.status] }}
+            style={{ backgroundColor: specStatusColor(spec.status, workflow) }}
           />
           <span className="text-sm font-medium text-zinc-100">{spec.title}</span>
           <span
             className="rounded px-1.5 py-0.5 text-[10px] font-medium"
-            style={{ color: SPEC_STATUS_COLORS[spec.status], backgroundColor: `${SPEC_STATUS_COLORS[spec.status]}20` }}
+            style={{ color: specStatusColor(spec.status, workflow), backgroundColor: `${specStatusColor(spec.status, workflow)}20` }}
           >
-            {SPEC_STATUS_LABELS[spec.status]}
+            {specStatusLabel(spec.status, workflow)}
           </span>
           {dirty && (
             <span className="flex items-center gap-1 text-[10px] text-zinc-500">
@@ -198,13 +316,34 @@ function SpecEditorInner({
         <div className="flex items-center gap-2">
           {canRun && (
             <button
-              onClick={() => setShowRunDialog(true)}
+              onClick={() => setRunDialog("run")}
               className="flex items-center gap-1 rounded bg-green-600 px-2.5 py-1 text-xs font-medium text-white hover:bg-green-500"
             >
               <Play size={12} />
               Run
             </button>
           )}
+          {canRun && (
+            <button
+              onClick={() => setShowBreakdown(true)}
+              className="flex items-center gap-1 rounded bg-surface-2 px-2.5 py-1 text-xs font-medium text-zinc-300 hover:text-zinc-100"
+            >
+              <GitFork size={12} />
+              Break down
+            </button>
+          )}
+          {canVerify && (
+            <button
+              onClick={() => setRunDialog("verify")}
+              className="flex items-center gap-1 rounded bg-surface-2 px-2.5 py-1 text-xs font-medium text-zinc-300 hover:text-zinc-100"
+            >
+              <ShieldCheck size={12} />
+              Verify
+            </button>
+          )}
+          {verifying && (
+            <span className="text-[10px] text-zinc-500">verifying...</span>
+          )}
           {canMarkDone && (
             <button
               onClick={handleMarkDone}
@@ -217,7 +356,9 @@ function SpecEditorInner({
           {canReject && (
             <button
               onClick={handleReject}
-              className="flex items-center gap-1 rounded bg-red-600 px-2.5 py-1 text-xs font-medium text-white hover:bg-red-500"
+              disabled={!reviewComment.trim()}
+              title={reviewComment.trim() ? undefined : "Add a review comment to reject"}
+              className="flex items-center gap-1 rounded bg-red-600 px-2.5 py-1 text-xs font-medium text-white hover:bg-red-500 disabled:opacity-50"
             >
               <XCircle size={12} />
               Reject
@@ -229,8 +370,40 @@ function SpecEditorInner({
         </div>
       </div>
 
+      {inReview && (
+        <div className="border-b border-zinc-800 px-4 py-2">
+          <input
+            type="text"
+            value={reviewComment}
+            onChange={(e) => setReviewComment(e.target.value)}
+            placeholder="Review comment (required to reject, sent to the agent on the next run)..."
+            className="w-full rounded border border-zinc-700 bg-surface-2 px-2 py-1 text-xs text-zinc-100 outline-none focus:border-blue-500"
+          />
+        </div>
+      )}
+
+      {conflict && (
+        <div className="flex items-center gap-3 border-b border-amber-800 bg-amber-950/40 px-4 py-2 text-xs text-amber-300">
+          <AlertTriangle size={14} />
+          <span className="flex-1">This spec was changed on disk since you opened it. Your edits were not saved.</span>
+          <button onClick={onReload} className="rounded bg-surface-2 px-2 py-1 text-zinc-300 hover:bg-surface-3">
+            Reload
+          </button>
+          <button
+            onClick={handleOverwrite}
+            className="rounded bg-amber-600 px-2 py-1 font-medium text-white hover:bg-amber-500"
+          >
+            Overwrite
+          </button>
+        </div>
+      )}
+
+      {showBreakdown && (
+        <BreakdownDialog spec={spec} onClose={() => setShowBreakdown(false)} onCreated={loadSpecs} />
+      )}
+
       {/* Run dialog */}
-      {showRunDialog && (
+      {runDialog && (
         <div className="border-b border-zinc-800 bg-surface-1 px-4 py-3">
           <div className="flex items-center gap-3">
             <select
@@ -261,10 +434,24 @@ function SpecEditorInner({
               onClick={handleRun}
               className="rounded bg-green-600 px-3 py-1.5 text-xs font-medium text-white hover:bg-green-500"
             >
-              Start Agent
+              {runDialog === "verify" ? "Start Verification" : "Start Agent"}
             </button>
+            {runEstimate && (
+              <span className="text-[11px] text-zinc-500" title="Average of the agent's past runs in this project">
+                {formatRunEstimate(runEstimate)}
+              </span>
+            )}
+            {runDialog === "run" && spec.assigned_agents.length >= 2 && (
+              <button
+                onClick={handleRunPair}
+                title="Implement with the first assignee, then review with the others"
+                className="rounded bg-blue-600 px-3 py-1.5 text-xs font-medium text-white hover:bg-blue-500"
+              >
+                Run Pair ({spec.assigned_agents.join(" → ")})
+              </button>
+            )}
             <button
-              onClick={() => setShowRunDialog(false)}
+              onClick={() => setRunDialog(null)}
               className="text-xs text-zinc-400 hover:text-zinc-200"
             >
               Cancel
@@ -296,12 +483,101 @@ function SpecEditorInner({
                 onChange={(e) => { const val = e.target.value as SpecStatus; setStatus(val); saveFrontmatter({ status: val }); }}
                 className="w-full rounded border border-zinc-700 bg-surface-2 px-2 py-1.5 text-sm text-zinc-300 outline-none"
               >
-                {Object.entries(SPEC_STATUS_LABELS).map(([key, label]) => (
-                  <option key={key} value={key}>{label}</option>
+                {statusOptions.map((s) => (
+                  <option key={s} value={s} disabled={!canMoveTo(s)}>
+                    {specStatusLabel(s, workflow)}
+                  </option>
                 ))}
               </select>
             </div>
 
+            {/* Labels */}
+            <div>
+              <label className="mb-1 block text-[11px] font-medium uppercase tracking-wider text-zinc-500">Labels</label>
+              <input
+                type="text"
+                value={labels}
+                placeholder="backend, auth"
+                onChange={(e) => {
+                  setLabels(e.target.value);
+                  saveFrontmatter({
+                    labels: e.target.value.split(",").map((l) => l.trim()).filter(Boolean),
+                  });
+                }}
+                className="w-full rounded border border-zinc-700 bg-surface-2 px-2 py-1.5 text-sm text-zinc-100 outline-none focus:border-blue-500"
+              />
+            </div>
+
+            {/* Estimate */}
+            <div>
+              <label className="mb-1 block text-[11px] font-medium uppercase tracking-wider text-zinc-500">Estimate</label>
+              <input
+                type="text"
+                value={estimate}
+                placeholder="S / M / L or points"
+                onChange={(e) => {
+                  const raw = e.target.value;
+                  setEstimate(raw);
+                  const trimmed = raw.trim();
+                  const points = Number(trimmed);
+                  saveFrontmatter({
+                    estimate: trimmed === "" ? null : Number.isFinite(points) ? points : trimmed.toUpperCase(),
+                  });
+                }}
+                className="w-full rounded border border-zinc-700 bg-surface-2 px-2 py-1.5 text-sm text-zinc-100 outline-none focus:border-blue-500"
+              />
+            </div>
+
+            {/* Due date */}
+            <div>
+              <label className="mb-1 block text-[11px] font-medium uppercase tracking-wider text-zinc-500">Due</label>
+              <input
+                type="date"
+                value={dueDate.slice(0, 10)}
+                onChange={(e) => {
+                  setDueDate(e.target.value);
+                  saveFrontmatter({ due_date: e.target.value || null });
+                }}
+                className="w-full rounded border border-zinc-700 bg-surface-2 px-2 py-1.5 text-sm text-zinc-100 outline-none focus:border-blue-500"
+              />
+            </div>
+
+            {/* Assignees */}
+            <div>
+              <label className="mb-1 block text-[11px] font-medium uppercase tracking-wider text-zinc-500">Assignees</label>
+              <input
+                type="text"
+                value={assignees}
+                placeholder="coder, reviewer"
+                title="First agent implements, the others review in order"
+                onChange={(e) => setAssignees(e.target.value)}
+                onBlur={() => {
+                  const agents = assignees.split(",").map((a) => a.trim()).filter(Boolean);
+                  if (agents.join(",") !== spec.assigned_agents.join(",")) {
+                    saveFrontmatter({ assigned_agents: agents });
+                  }
+                }}
+                className="w-full rounded border border-zinc-700 bg-surface-2 px-2 py-1.5 text-sm text-zinc-100 outline-none focus:border-blue-500"
+              />
+            </div>
+
+            {/* Branch */}
+            <div>
+              <label className="mb-1 block text-[11px] font-medium uppercase tracking-wider text-zinc-500">Branch</label>
+              <input
+                type="text"
+                value={branch}
+                placeholder="feature/my-change"
+                title="Merging this branch moves the spec from review to done"
+                onChange={(e) => setBranch(e.target.value)}
+                onBlur={() => {
+                  const value = branch.trim() || null;
+                  if (value !== (spec.branch ?? null)) saveFrontmatter({ branch: value });
+                }}
+                className="w-full rounded border border-zinc-700 bg-surface-2 px-2 py-1.5 font-mono text-sm text-zinc-100 outline-none focus:border-blue-500"
+              />
+            </div>
+
             {/* Priority */}
             <div>
               <label className="mb-1 block text-[11px] font-medium uppercase tracking-wider text-zinc-500">Priority</label>
@@ -325,8 +601,18 @@ function SpecEditorInner({
               <div className="space-y-1">
                 {criteria.map((criterion, i) => (
                   <div key={i} className="group flex items-start gap-2 rounded bg-surface-2 px-2 py-1.5 text-xs text-zinc-300">
-                    <span className="mt-0.5 flex-shrink-0 text-zinc-600">{i + 1}.</span>
-                    <span className="flex-1">{criterion}</span>
+                    <input
+                      type="checkbox"
+                      checked={criterion.done}
+                      onChange={() => toggleCriterion(i)}
+                      className="mt-0.5 flex-shrink-0"
+                    />
+                    <div className="flex-1">
+                      <span className={criterion.done ? "text-zinc-500 line-through" : ""}>{criterion.text}</span>
+                      {criterion.finding && (
+                        <div className="mt-0.5 text-[10px] text-zinc-500">{criterion.finding}</div>
+                      )}
+                    </div>
                     <button onClick={() => removeCriterion(i)} className="flex-shrink-0 opacity-0 group-hover:opacity-100">
                       <X size={12} className="text-zinc-500 hover:text-red-400" />
                     </button>
@@ -346,8 +632,79 @@ function SpecEditorInner({
                   <Plus size={12} />
                 </button>
               </div>
+              {spec.verification?.summary && (
+                <button
+                  onClick={() => openDetail(spec.verification!.session_id)}
+                  className={`mt-1 block text-left text-[10px] hover:underline ${spec.verification.status === "passed" ? "text-green-400" : "text-red-400"}`}
+                >
+                  {spec.verification.summary}
+                </button>
+              )}
             </div>
 
+            {/* Review log */}
+            {spec.reviews.length > 0 && (
+              <div>
+                <div className="mb-1 text-[11px] font-medium uppercase tracking-wider text-zinc-500">Reviews</div>
+                <div className="space-y-1">
+                  {spec.reviews
+                    .slice()
+                    .reverse()
+                    .map((r) => (
+                      <div key={r.at} className="rounded bg-surface-2 px-2 py-1 text-[11px] text-zinc-400">
+                        <div className="flex gap-2">
+                          <span className={r.decision === "approved" ? "text-green-400" : "text-red-400"}>
+                            {r.decision}
+                          </span>
+                          <span>{r.reviewer}</span>
+                          <span className="text-zinc-600">{new Date(r.at).toLocaleString()}</span>
+                        </div>
+                        {r.comment && <div className="mt-0.5 text-zinc-300">{r.comment}</div>}
+                      </div>
+                    ))}
+                </div>
+              </div>
+            )}
+
+            {/* Session history */}
+            {sessions.length > 0 && (
+              <div>
+                <div className="mb-1 text-[11px] font-medium uppercase tracking-wider text-zinc-500">
+                  Sessions
+                  <span className="ml-1 normal-case text-zinc-600">
+                    ${sessions.reduce((sum, r) => sum + (r.cost_usd ?? 0), 0).toFixed(2)}
+                  </span>
+                </div>
+                <div className="space-y-1">
+                  {sessions
+                    .slice()
+                    .reverse()
+                    .map((r) => (
+                      <button
+                        key={r.session_id}
+                        onClick={() => openDetail(r.session_id)}
+                        className="flex w-full gap-2 rounded bg-surface-2 px-2 py-1 text-left text-[11px] text-zinc-400 hover:bg-surface-3"
+                      >
+                        <span
+                          className={
+                            r.outcome === "completed"
+                              ? "text-green-400"
+                              : r.outcome
+                                ? "text-red-400"
+                                : "text-blue-400"
+                          }
+                        >
+                          {r.outcome ?? "running"}
+                        </span>
+                        <span className="truncate">{r.agent_name}</span>
+                        <span className="text-zinc-600">{new Date(r.started_at).toLocaleString()}</span>
+                        {r.cost_usd != null && <span className="ml-auto">${r.cost_usd.toFixed(2)}</span>}
+                      </button>
+                    ))}
+                </div>
+              </div>
+            )}
+
             {/* Linked session */}
             {spec.assigned_agent && (
               <div className="rounded border border-zinc-700 bg-surface-2 p-3">
@@ -367,6 +724,36 @@ function SpecEditorInner({
               </div>
             )}
 
+            {/* History */}
+            <div>
+              <button
+                onClick={() =>
+                  history
+                    ? setHistory(null)
+                    : tauri.getSpecHistory(spec.file_path, 20).then(setHistory).catch(() => setHistory([]))
+                }
+                className="text-[11px] font-medium uppercase tracking-wider text-zinc-500 hover:text-zinc-300"
+              >
+                {history ? "Hide history" : "Show history"}
+              </button>
+              {history && (
+                <div className="mt-1 space-y-1">
+                  {history.length === 0 && <div className="text-[11px] text-zinc-600">No committed versions</div>}
+                  {history.map((rev) => (
+                    <div key={rev.commit} className="rounded bg-surface-2 px-2 py-1 text-[11px] text-zinc-400" title={rev.subject}>
+                      <div className="truncate text-zinc-300">{rev.subject}</div>
+                      <div className="flex gap-2 text-zinc-500">
+                        <span>{rev.commit.slice(0, 7)}</span>
+                        <span>{rev.author_name}</span>
+                        {rev.status && <span style={{ color: specStatusColor(rev.status, workflow) }}>{specStatusLabel(rev.status, workflow)}</span>}
+                        {rev.assigned_agent && <span>@{rev.assigned_agent}</span>}
+                      </div>
+                    </div>
+                  ))}
+                </div>
+              )}
+            </div>
+
             {/* Metadata */}
             <div className="space-y-1 pt-2 text-[11px] text-zinc-500">
               <div>Created: {new Date(spec.created_at).toLocaleString()}</div>
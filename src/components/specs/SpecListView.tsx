@@ -1,12 +1,10 @@
-import { useState } from "react";
-import { Plus, FileText, Trash2 } from "lucide-react";
+import { useEffect, useState } from "react";
+import { Plus, FileText, Trash2, AlertTriangle, Download } from "lucide-react";
+import { save } from "@tauri-apps/plugin-dialog";
 import { useSpecStore } from "../../stores/specStore";
-import type { Spec, SpecPriority, SpecStatus } from "../../lib/types";
-import {
-  SPEC_STATUS_COLORS,
-  SPEC_STATUS_LABELS,
-  SPEC_PRIORITY_COLORS,
-} from "../../lib/types";
+import * as tauri from "../../lib/tauri";
+import type { Spec, SpecDiagnostic, SpecFilter, SpecPriority, SpecStats, SpecStatus } from "../../lib/types";
+import { specStatusColor, specStatusLabel, SPEC_PRIORITY_COLORS } from "../../lib/types";
 
 const STATUS_ORDER: SpecStatus[] = [
   "in_progress",
@@ -19,6 +17,7 @@ const STATUS_ORDER: SpecStatus[] = [
 
 export function SpecListView() {
   const specs = useSpecStore((s) => s.specs);
+  const workflow = useSpecStore((s) => s.workflow);
   const selectedSpecPath = useSpecStore((s) => s.selectedSpecPath);
   const selectSpec = useSpecStore((s) => s.selectSpec);
   const createSpec = useSpecStore((s) => s.createSpec);
@@ -28,25 +27,79 @@ export function SpecListView() {
     "all",
   );
   const [filterStatus, setFilterStatus] = useState<SpecStatus | "all">("all");
+  const [filterLabel, setFilterLabel] = useState<string>("all");
+  const [filterEpic, setFilterEpic] = useState<string>("all");
   const [creating, setCreating] = useState(false);
   const [newTitle, setNewTitle] = useState("");
   const [newPriority, setNewPriority] = useState<SpecPriority>("p1");
+  const [newEpic, setNewEpic] = useState("");
+  const [newStructured, setNewStructured] = useState(false);
+  const [stats, setStats] = useState<SpecStats | null>(null);
+  const [diagnostics, setDiagnostics] = useState<SpecDiagnostic[]>([]);
+  const [showDiagnostics, setShowDiagnostics] = useState(false);
+
+  useEffect(() => {
+    tauri.getSpecStats().then(setStats).catch(() => setStats(null));
+    tauri.validateSpecs().then(setDiagnostics).catch(() => setDiagnostics([]));
+  }, [specs]);
 
   const filtered = specs.filter((s) => {
     if (filterPriority !== "all" && s.priority !== filterPriority) return false;
     if (filterStatus !== "all" && s.status !== filterStatus) return false;
+    if (filterLabel !== "all" && !s.labels.some((l) => l.toLowerCase() === filterLabel)) return false;
+    if (filterEpic !== "all" && s.epic !== filterEpic && !s.epic?.startsWith(`${filterEpic}/`)) return false;
     return true;
   });
 
-  // Group by status
-  const grouped = STATUS_ORDER.map((status) => ({
+  const allEpics = Array.from(
+    new Set(specs.flatMap((s) => (s.epic ? [s.epic] : []))),
+  ).sort();
+
+  const allLabels = Array.from(
+    new Set(specs.flatMap((s) => s.labels.map((l) => l.toLowerCase()))),
+  ).sort();
+
+  // Group by status: built-ins first, then the project's custom statuses
+  // and any undefined ones still found in spec files
+  const statusOrder = Array.from(
+    new Set<SpecStatus>([
+      ...STATUS_ORDER,
+      ...(workflow?.statuses.map((s) => s.id) ?? []),
+      ...specs.map((s) => s.status),
+    ]),
+  );
+  const grouped = statusOrder.map((status) => ({
     status,
     specs: filtered.filter((s) => s.status === status),
   })).filter((g) => g.specs.length > 0);
 
+  async function handleExport() {
+    const path = await save({
+      defaultPath: "specs.csv",
+      filters: [
+        { name: "CSV", extensions: ["csv"] },
+        { name: "JSON", extensions: ["json"] },
+      ],
+    });
+    if (!path) return;
+    const format = path.toLowerCase().endsWith(".json") ? "json" : "csv";
+    const filter: SpecFilter = {
+      status: filterStatus === "all" ? null : filterStatus,
+      priority: filterPriority === "all" ? null : filterPriority,
+      labels: filterLabel === "all" ? [] : [filterLabel],
+      epic: filterEpic === "all" ? null : filterEpic,
+    };
+    await tauri.exportSpecs(format, filter, path);
+  }
+
   async function handleCreate() {
     if (!newTitle.trim()) return;
-    await createSpec(newTitle.trim(), newPriority);
+    await createSpec(
+      newTitle.trim(),
+      newPriority,
+      newEpic.trim() || null,
+      newStructured ? "standard" : "blank",
+    );
     setNewTitle("");
     setCreating(false);
   }
@@ -54,14 +107,36 @@ export function SpecListView() {
   return (
     <div className="flex h-full flex-col">
       <div className="flex items-center justify-between border-b border-zinc-800 px-4 py-3">
-        <h2 className="text-sm font-semibold text-zinc-100">Specs</h2>
-        <button
-          onClick={() => setCreating(true)}
-          className="flex items-center gap-1 rounded-md bg-blue-600 px-2.5 py-1 text-xs font-medium text-white transition-colors hover:bg-blue-500"
-        >
-          <Plus size={12} />
-          New Spec
-        </button>
+        <div className="flex items-baseline gap-2">
+          <h2 className="text-sm font-semibold text-zinc-100">Specs</h2>
+          {stats && stats.open > 0 && (
+            <span
+              className="text-[11px] text-zinc-500"
+              title={stats.by_priority
+                .map((b) => `${b.key.toUpperCase()}: ${b.count} open, ${b.points} pts`)
+                .join("\n")}
+            >
+              {stats.open} open · {stats.open_points} pts
+              {stats.unestimated > 0 && ` · ${stats.unestimated} unestimated`}
+            </span>
+          )}
+        </div>
+        <div className="flex items-center gap-1">
+          <button
+            onClick={handleExport}
+            title="Export the filtered specs as CSV or JSON"
+            className="rounded-md p-1 text-zinc-500 transition-colors hover:bg-surface-2 hover:text-zinc-300"
+          >
+            <Download size={14} />
+          </button>
+          <button
+            onClick={() => setCreating(true)}
+            className="flex items-center gap-1 rounded-md bg-blue-600 px-2.5 py-1 text-xs font-medium text-white transition-colors hover:bg-blue-500"
+          >
+            <Plus size={12} />
+            New Spec
+          </button>
+        </div>
       </div>
 
       {/* Filters */}
@@ -74,9 +149,9 @@ export function SpecListView() {
           className="rounded border border-zinc-700 bg-surface-2 px-2 py-1 text-xs text-zinc-300"
         >
           <option value="all">All Status</option>
-          {STATUS_ORDER.map((s) => (
+          {statusOrder.map((s) => (
             <option key={s} value={s}>
-              {SPEC_STATUS_LABELS[s]}
+              {specStatusLabel(s, workflow)}
             </option>
           ))}
         </select>
@@ -92,8 +167,61 @@ export function SpecListView() {
           <option value="p1">P1</option>
           <option value="p2">P2</option>
         </select>
+        {allLabels.length > 0 && (
+          <select
+            value={filterLabel}
+            onChange={(e) => setFilterLabel(e.target.value)}
+            className="rounded border border-zinc-700 bg-surface-2 px-2 py-1 text-xs text-zinc-300"
+          >
+            <option value="all">All Labels</option>
+            {allLabels.map((l) => (
+              <option key={l} value={l}>
+                {l}
+              </option>
+            ))}
+          </select>
+        )}
+        {allEpics.length > 0 && (
+          <select
+            value={filterEpic}
+            onChange={(e) => setFilterEpic(e.target.value)}
+            className="rounded border border-zinc-700 bg-surface-2 px-2 py-1 text-xs text-zinc-300"
+          >
+            <option value="all">All Folders</option>
+            {allEpics.map((e) => (
+              <option key={e} value={e}>
+                {e}
+              </option>
+            ))}
+          </select>
+        )}
       </div>
 
+      {diagnostics.length > 0 && (
+        <div className="border-b border-zinc-800 px-4 py-2 text-xs">
+          <button
+            onClick={() => setShowDiagnostics((v) => !v)}
+            className="flex items-center gap-1.5 text-amber-400 hover:text-amber-300"
+          >
+            <AlertTriangle size={12} />
+            {diagnostics.length} spec {diagnostics.length === 1 ? "problem" : "problems"}
+          </button>
+          {showDiagnostics && (
+            <ul className="mt-2 space-y-1">
+              {diagnostics.map((d, i) => (
+                <li key={i} className="flex gap-2">
+                  <span className={d.severity === "error" ? "text-red-400" : "text-amber-400"}>
+                    {d.severity}
+                  </span>
+                  <span className="text-zinc-400">{d.file_path.split(/[\\/]/).pop()}</span>
+                  <span className="text-zinc-300">{d.message}</span>
+                </li>
+              ))}
+            </ul>
+          )}
+        </div>
+      )}
+
       {/* New spec form */}
       {creating && (
         <div className="border-b border-zinc-800 px-4 py-3">
@@ -121,6 +249,30 @@ export function SpecListView() {
               <option value="p1">P1</option>
               <option value="p2">P2</option>
             </select>
+            <input
+              type="text"
+              value={newEpic}
+              onChange={(e) => setNewEpic(e.target.value)}
+              placeholder="Folder (optional)"
+              list="spec-epics"
+              className="w-36 rounded border border-zinc-700 bg-surface-2 px-2 py-1 text-xs text-zinc-300 outline-none focus:border-blue-500"
+            />
+            <datalist id="spec-epics">
+              {allEpics.map((e) => (
+                <option key={e} value={e} />
+              ))}
+            </datalist>
+            <label
+              className="flex items-center gap-1 text-xs text-zinc-400"
+              title="Pre-fill Context, Requirements, Out of Scope and Acceptance Criteria sections"
+            >
+              <input
+                type="checkbox"
+                checked={newStructured}
+                onChange={(e) => setNewStructured(e.target.checked)}
+              />
+              Sections
+            </label>
             <button
               onClick={handleCreate}
               className="rounded bg-blue-600 px-3 py-1 text-xs font-medium text-white hover:bg-blue-500"
@@ -152,9 +304,9 @@ export function SpecListView() {
             <div className="sticky top-0 z-10 bg-surface-0 px-4 py-1.5">
               <span
                 className="text-[10px] font-semibold uppercase tracking-wider"
-                style={{ color: SPEC_STATUS_COLORS[status] }}
+                style={{ color: specStatusColor(status, workflow) }}
               >
-                {SPEC_STATUS_LABELS[status]} ({groupSpecs.length})
+                {specStatusLabel(status, workflow)} ({groupSpecs.length})
               </span>
             </div>
             {groupSpecs.map((spec) => (
@@ -173,6 +325,16 @@ export function SpecListView() {
   );
 }
 
+function DueBadge({ spec }: { spec: Spec }) {
+  const due = /^\d{4}-\d{2}-\d{2}$/.test(spec.due_date!)
+    ? new Date(`${spec.due_date}T23:59:59`)
+    : new Date(spec.due_date!);
+  const open = spec.status !== "done" && spec.status !== "rejected";
+  const hoursLeft = (due.getTime() - Date.now()) / 3_600_000;
+  const color = !open ? "" : hoursLeft < 0 ? "text-red-400" : hoursLeft <= 24 ? "text-amber-400" : "";
+  return <span className={color}>due {due.toLocaleDateString()}</span>;
+}
+
 function SpecRow({
   spec,
   isSelected,
@@ -207,6 +369,27 @@ function SpecRow({
           </span>
         </div>
         <div className="mt-0.5 flex items-center gap-2 text-[11px] text-zinc-500">
+          {spec.blocked_by?.length > 0 && spec.status !== "done" && (
+            <span
+              className="text-amber-500"
+              title={`Blocked by: ${spec.blocked_by.join(", ")}`}
+            >
+              Blocked
+            </span>
+          )}
+          {spec.epic && <span className="text-zinc-400">{spec.epic}/</span>}
+          {spec.due_date && <DueBadge spec={spec} />}
+          {spec.estimate != null && <span>{spec.estimate}</span>}
+          {spec.labels.map((l) => (
+            <span key={l} className="rounded bg-surface-2 px-1 text-zinc-400">
+              {l}
+            </span>
+          ))}
+          {spec.child_count > 0 && (
+            <span>
+              {spec.children_done}/{spec.child_count} subtasks
+            </span>
+          )}
           {spec.assigned_agent && <span>{spec.assigned_agent}</span>}
           <span>{new Date(spec.updated_at).toLocaleDateString()}</span>
         </div>
@@ -17,6 +17,8 @@ import { AgentNode, type AgentNodeData } from "./AgentNode";
 import { useWorkflowStore } from "../../stores/workflowStore";
 import { useAgentStore } from "../../stores/agentStore";
 import type { WorkflowStep, WorkflowEdge as WFEdge } from "../../lib/types";
+import { estimateCost } from "../../lib/tauri";
+import { formatRunEstimate } from "../../lib/formatters";
 import { toast } from "sonner";
 
 const nodeTypes: NodeTypes = {
@@ -166,6 +168,11 @@ export function WorkflowCanvas({ workflowId, onNodeSelect }: Props) {
   const handleStart = async () => {
     try {
       await validateWorkflowAction(workflowId);
+      // An estimate is a courtesy; a failure to compute one shouldn't block the start
+      const estimate = await estimateCost({ workflowId }).catch(() => null);
+      if (estimate && !confirm(`Start workflow?\n\nEstimated from past runs: ${formatRunEstimate(estimate)}`)) {
+        return;
+      }
       await startWorkflowAction(workflowId);
       toast.success("Workflow started");
     } catch (e) {
@@ -3,6 +3,8 @@ import { Trash2 } from "lucide-react";
 import { useWorkflowStore } from "../../stores/workflowStore";
 import { useAgentStore } from "../../stores/agentStore";
 import { WORKFLOW_STATUS_COLORS } from "../../lib/types";
+import { ModelSelect } from "../agents/ModelSelect";
+import type { FailurePolicy, JoinMode, StepKind } from "../../lib/types";
 
 interface Props {
   workflowId: string;
@@ -26,6 +28,13 @@ export function WorkflowSidebar({ workflowId, selectedStepId }: Props) {
   const [model, setModel] = useState("");
   const [prompt, setPrompt] = useState("");
   const [passContext, setPassContext] = useState(false);
+  const [failurePolicy, setFailurePolicy] =
+    useState<FailurePolicy>("fail_workflow");
+  const [joinMode, setJoinMode] = useState<JoinMode>("all");
+  const [kind, setKind] = useState<StepKind>("agent");
+  const [gateCommand, setGateCommand] = useState("");
+  const [autoCommit, setAutoCommit] = useState(false);
+  const [commitMessage, setCommitMessage] = useState("");
 
   useEffect(() => {
     if (selectedStep) {
@@ -34,6 +43,12 @@ export function WorkflowSidebar({ workflowId, selectedStepId }: Props) {
       setPrompt(selectedStep.prompt);
       // Guard: pass_context may be missing on older data; never set undefined for controlled checkbox
       setPassContext(Boolean(selectedStep.pass_context));
+      setFailurePolicy(selectedStep.failure_policy ?? "fail_workflow");
+      setJoinMode(selectedStep.join_mode ?? "all");
+      setKind(selectedStep.kind ?? "agent");
+      setGateCommand(selectedStep.gate_command ?? "");
+      setAutoCommit(selectedStep.commit_message != null);
+      setCommitMessage(selectedStep.commit_message ?? "");
     }
   }, [selectedStep]);
 
@@ -45,6 +60,11 @@ export function WorkflowSidebar({ workflowId, selectedStepId }: Props) {
       model,
       prompt,
       pass_context: passContext,
+      failure_policy: failurePolicy,
+      join_mode: joinMode,
+      kind,
+      gate_command: kind === "gate" ? gateCommand : null,
+      commit_message: autoCommit ? commitMessage : null,
     });
   };
 
@@ -67,6 +87,39 @@ export function WorkflowSidebar({ workflowId, selectedStepId }: Props) {
         </div>
 
         <div className="flex flex-col gap-4 p-4">
+          {/* Step Type */}
+          <div>
+            <label className="mb-1 block text-[11px] font-medium text-zinc-400">
+              Step type
+            </label>
+            <select
+              value={kind}
+              onChange={(e) => setKind(e.target.value as StepKind)}
+              className="w-full rounded-lg border border-zinc-700 bg-surface-0 px-3 py-2 text-sm text-zinc-100 focus:border-blue-600 focus:outline-none"
+            >
+              <option value="agent">Agent</option>
+              <option value="gate">Quality gate (command)</option>
+            </select>
+          </div>
+
+          {kind === "gate" ? (
+          /* Gate Command */
+          <div>
+            <label className="mb-1 block text-[11px] font-medium text-zinc-400">
+              Command
+            </label>
+            <input
+              value={gateCommand}
+              onChange={(e) => setGateCommand(e.target.value)}
+              className="w-full rounded-lg border border-zinc-700 bg-surface-0 px-3 py-2 font-mono text-sm text-zinc-100 placeholder:text-zinc-600 focus:border-blue-600 focus:outline-none"
+              placeholder="cargo test"
+            />
+            <p className="mt-1 text-[10px] text-zinc-500">
+              Runs in the project directory without a shell. Passes on exit code 0.
+            </p>
+          </div>
+          ) : (
+          <>
           {/* Agent */}
           <div>
             <label className="mb-1 block text-[11px] font-medium text-zinc-400">
@@ -90,15 +143,11 @@ export function WorkflowSidebar({ workflowId, selectedStepId }: Props) {
             <label className="mb-1 block text-[11px] font-medium text-zinc-400">
               Model
             </label>
-            <select
+            <ModelSelect
               value={model}
-              onChange={(e) => setModel(e.target.value)}
+              onChange={setModel}
               className="w-full rounded-lg border border-zinc-700 bg-surface-0 px-3 py-2 text-sm text-zinc-100 focus:border-blue-600 focus:outline-none"
-            >
-              <option value="opus">Opus</option>
-              <option value="sonnet">Sonnet</option>
-              <option value="haiku">Haiku</option>
-            </select>
+            />
           </div>
 
           {/* Prompt */}
@@ -114,6 +163,8 @@ export function WorkflowSidebar({ workflowId, selectedStepId }: Props) {
               placeholder="Instructions for this step..."
             />
           </div>
+          </>
+          )}
 
           {/* Pass Context */}
           <div className="flex items-center gap-2">
@@ -129,6 +180,67 @@ export function WorkflowSidebar({ workflowId, selectedStepId }: Props) {
             </label>
           </div>
 
+          {/* Auto-commit */}
+          <div>
+            <div className="flex items-center gap-2">
+              <input
+                type="checkbox"
+                id="autoCommit"
+                checked={autoCommit}
+                onChange={(e) => setAutoCommit(e.target.checked)}
+                className="h-4 w-4 rounded border-zinc-600 bg-surface-0"
+              />
+              <label htmlFor="autoCommit" className="text-[11px] text-zinc-400">
+                Commit the working tree when this step completes
+              </label>
+            </div>
+            {autoCommit && (
+              <>
+                <input
+                  value={commitMessage}
+                  onChange={(e) => setCommitMessage(e.target.value)}
+                  className="mt-2 w-full rounded-lg border border-zinc-700 bg-surface-0 px-3 py-2 font-mono text-sm text-zinc-100 placeholder:text-zinc-600 focus:border-blue-600 focus:outline-none"
+                  placeholder="{{workflow}}: {{step}}"
+                />
+                <p className="mt-1 text-[10px] text-zinc-500">
+                  Variables: {"{{workflow}}"}, {"{{step}}"}, {"{{agent_name}}"}, {"{{model}}"},{" "}
+                  {"{{spec}}"}, {"{{spec_path}}"}
+                </p>
+              </>
+            )}
+          </div>
+
+          {/* Join Mode */}
+          <div>
+            <label className="mb-1 block text-[11px] font-medium text-zinc-400">
+              Start when
+            </label>
+            <select
+              value={joinMode}
+              onChange={(e) => setJoinMode(e.target.value as JoinMode)}
+              className="w-full rounded-lg border border-zinc-700 bg-surface-0 px-3 py-2 text-sm text-zinc-100 focus:border-blue-600 focus:outline-none"
+            >
+              <option value="all">All parent steps are done</option>
+              <option value="any">Any parent step is done</option>
+            </select>
+          </div>
+
+          {/* Failure Policy */}
+          <div>
+            <label className="mb-1 block text-[11px] font-medium text-zinc-400">
+              On failure
+            </label>
+            <select
+              value={failurePolicy}
+              onChange={(e) => setFailurePolicy(e.target.value as FailurePolicy)}
+              className="w-full rounded-lg border border-zinc-700 bg-surface-0 px-3 py-2 text-sm text-zinc-100 focus:border-blue-600 focus:outline-none"
+            >
+              <option value="fail_workflow">Fail workflow</option>
+              <option value="continue">Continue</option>
+              <option value="skip_descendants">Skip dependent steps</option>
+            </select>
+          </div>
+
           {/* Status */}
           <div>
             <label className="mb-1 block text-[11px] font-medium text-zinc-400">
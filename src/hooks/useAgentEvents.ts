@@ -9,6 +9,7 @@ import type {
   AgentStatusEvent,
   AgentMessageEvent,
   AgentUsageEvent,
+  FileConflictEvent,
 } from "../lib/types";
 import { useAgentStore } from "../stores/agentStore";
 
@@ -16,7 +17,7 @@ const NOTIFY_STATUSES = new Set(["completed", "error", "stopped"]);
 
 export function useAgentEvents() {
   const handleStatusChange = useAgentStore((s) => s.handleStatusChange);
-  const handleMessage = useAgentStore((s) => s.handleMessage);
+  const handleMessages = useAgentStore((s) => s.handleMessages);
   const handleUsageUpdate = useAgentStore((s) => s.handleUsageUpdate);
   const handleRateLimited = useAgentStore((s) => s.handleRateLimited);
 
@@ -55,10 +56,11 @@ export function useAgentEvents() {
       );
       unlisteners.push(unlisten1);
 
-      const unlisten2 = await listen<AgentMessageEvent>(
-        "agent:message",
+      // Messages arrive coalesced, up to every 100ms
+      const unlisten2 = await listen<AgentMessageEvent[]>(
+        "agent:messages",
         (event) => {
-          handleMessage(event.payload);
+          handleMessages(event.payload);
         },
       );
       unlisteners.push(unlisten2);
@@ -88,6 +90,20 @@ export function useAgentEvents() {
         }
       });
       unlisteners.push(unlisten4);
+
+      const unlisten5 = await listen<FileConflictEvent>(
+        "conflict:detected",
+        (event) => {
+          if (permissionGranted) {
+            const { path, agent_name, other_agent_name } = event.payload;
+            sendNotification({
+              title: "File Conflict",
+              body: `${agent_name} and ${other_agent_name} are both editing ${path}.`,
+            });
+          }
+        },
+      );
+      unlisteners.push(unlisten5);
     }
 
     setup();
@@ -95,5 +111,5 @@ export function useAgentEvents() {
     return () => {
       unlisteners.forEach((fn) => fn());
     };
-  }, [handleStatusChange, handleMessage, handleUsageUpdate, handleRateLimited]);
+  }, [handleStatusChange, handleMessages, handleUsageUpdate, handleRateLimited]);
 }
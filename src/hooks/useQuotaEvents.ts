@@ -1,21 +1,35 @@
 import { useEffect } from "react";
 import { listen } from "@tauri-apps/api/event";
+import { toast } from "sonner";
 import { useQuotaStore, type QuotaUpdateEvent } from "../stores/quotaStore";
+import type { BudgetStatus, RateWindow } from "../lib/types";
+
+function budgetText(status: BudgetStatus): string {
+  return `$${status.spent_usd.toFixed(2)} of the $${status.limit_usd.toFixed(2)} ${status.period} budget spent`;
+}
 
 export function useQuotaEvents() {
   const handleQuotaUpdate = useQuotaStore((s) => s.handleQuotaUpdate);
+  const handleWindowUpdate = useQuotaStore((s) => s.handleWindowUpdate);
 
   useEffect(() => {
-    let unlisten: (() => void) | undefined;
-
-    listen<QuotaUpdateEvent>("quota:update", (event) => {
-      handleQuotaUpdate(event.payload);
-    }).then((fn) => {
-      unlisten = fn;
-    });
+    const unlisteners: Array<Promise<() => void>> = [
+      listen<QuotaUpdateEvent>("quota:update", (event) => {
+        handleQuotaUpdate(event.payload);
+      }),
+      listen<RateWindow>("quota:window-update", (event) => {
+        handleWindowUpdate(event.payload);
+      }),
+      listen<BudgetStatus>("quota:budget-warning", (event) => {
+        toast.warning(budgetText(event.payload));
+      }),
+      listen<BudgetStatus>("quota:budget-exceeded", (event) => {
+        toast.error(`Budget used up: ${budgetText(event.payload)}`);
+      }),
+    ];
 
     return () => {
-      unlisten?.();
+      unlisteners.forEach((p) => p.then((fn) => fn()));
     };
-  }, [handleQuotaUpdate]);
+  }, [handleQuotaUpdate, handleWindowUpdate]);
 }
@@ -1,3 +1,5 @@
+import type { RunEstimate } from "./types";
+
 export function formatElapsed(startedAt: string, endMs?: number): string {
   const start = new Date(startedAt).getTime();
   const now = endMs ?? Date.now();
@@ -16,6 +18,16 @@ export function formatStatus(status: string): string {
   return status.charAt(0).toUpperCase() + status.slice(1);
 }
 
+/** One-line summary of a run estimate, e.g. "~$1.20 · ~14m (no history: docs)". */
+export function formatRunEstimate(estimate: RunEstimate): string {
+  const minutes = Math.max(Math.round(estimate.duration_secs / 60), 1);
+  let text = `~$${estimate.cost_usd.toFixed(2)} · ~${minutes}m`;
+  if (estimate.missing_history.length > 0) {
+    text += ` (no history: ${estimate.missing_history.join(", ")})`;
+  }
+  return text;
+}
+
 export function formatTokens(count: number): string {
   if (count < 1000) return count.toString();
   if (count < 1_000_000) return `${(count / 1000).toFixed(1)}k`;
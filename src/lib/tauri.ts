@@ -1,14 +1,15 @@
 // Typed wrappers for Tauri IPC commands
 
 import { invoke } from "@tauri-apps/api/core";
-import type { AgentConfig, AgentConfigUpdate, AgentRelationship, AgentSession, AppConfig, ChangedFile, FileDiff, LogEntry, Spec, SpecPriority, SpecUpdate, UnapprovedAgent, Workflow, WorkflowStep, WorkflowEdge } from "./types";
+import type { AgentApproval, AgentConfig, AgentConfigUpdate, AgentDiagnostic, AgentRelationship, AgentSession, AgentStats, AgentTestRun, AgentVersion, AppConfig, BlameLine, ChangedFile, CostGroup, CostGroupBy, CustomCommand, DbInfo, DbIntegrityReport, DbVacuumResult, EdgeCondition, ErrorDigest, FailurePolicy, FileChange, FileDiff, GitBranch, GitCommit, GithubIssue, HooksConfig, HooksSettings, HttpApiStatus, JoinMode, KnownProject, LinearSyncConflict, LinearSyncReport, LogCompressionStats, LogEntry, LogQueryFilter, McpHealth, McpScope, McpServer, MemoryFile, MemoryScope, ModelCatalog, ProjectEnv, ProjectSettings, PromptTemplate, ProposedSpec, RedactionConfig, RedactionPattern, RunEstimate, RunSpecsMode, SessionCostBreakdown, SessionLogStats, SessionSearchResult, SettingsImportReport, SettingsScope, Skill, SlashCommand, SlashCommandUpdate, Spec, SpecDiagnostic, SpecFilter, SpecPriority, SpecRevision, SpecSessionRecord, SpecStats, SpecStatus, SpecStructure, SpecUpdate, SpecWorkflow, StepKind, TemplateInstantiation, ToolCall, TranscriptImportReport, UnapprovedAgent, UsageGranularity, UsagePeriod, UsageRange, Workflow, WorkflowEdge, WorkflowSimulation, WorkflowStep, WorkflowTemplate } from "./types";
 
 export async function startAgent(
   name: string,
   model: string,
   prompt: string,
+  profile?: string | null,
 ): Promise<string> {
-  return invoke("start_agent", { name, model, prompt });
+  return invoke("start_agent", { name, model, prompt, profile: profile ?? null });
 }
 
 export async function stopAgent(sessionId: string): Promise<void> {
@@ -30,10 +31,22 @@ export async function getSession(sessionId: string): Promise<AgentSession> {
   return invoke("get_session", { sessionId });
 }
 
+export async function getAgentStats(): Promise<AgentStats[]> {
+  return invoke("get_agent_stats");
+}
+
+export async function testAgent(filePath: string, samplePrompt: string): Promise<AgentTestRun> {
+  return invoke("test_agent", { filePath, samplePrompt });
+}
+
 export async function listAgents(): Promise<AgentConfig[]> {
   return invoke("list_agents");
 }
 
+export async function validateAgents(): Promise<AgentDiagnostic[]> {
+  return invoke("validate_agents");
+}
+
 export async function setProjectDir(path: string): Promise<void> {
   return invoke("set_project_dir", { path });
 }
@@ -58,12 +71,105 @@ export async function getSessionLogs(
   return invoke("get_session_logs", { sessionId, offset, limit });
 }
 
+/** Entries after the last id already seen; poll with the newest id to tail a session. */
+export async function getSessionLogsAfter(
+  sessionId: string,
+  afterId?: number,
+  limit?: number,
+): Promise<LogEntry[]> {
+  return invoke("get_session_logs_after", { sessionId, afterId, limit });
+}
+
+export async function getSessionLogsTail(
+  sessionId: string,
+  limit?: number,
+): Promise<LogEntry[]> {
+  return invoke("get_session_logs_tail", { sessionId, limit });
+}
+
+export async function getSessionLogsFiltered(
+  sessionId: string,
+  filter: LogQueryFilter,
+  afterId?: number,
+  limit?: number,
+): Promise<LogEntry[]> {
+  return invoke("get_session_logs_filtered", { sessionId, filter, afterId, limit });
+}
+
 export async function getSessionLogCount(
   sessionId: string,
 ): Promise<number> {
   return invoke("get_session_log_count", { sessionId });
 }
 
+export async function searchProjectLogs(
+  query: string,
+  limit?: number,
+): Promise<SessionSearchResult[]> {
+  return invoke("search_project_logs", { query, limit: limit ?? null });
+}
+
+/** Import sessions run with `claude` directly in the terminal for this project. */
+export async function importTranscripts(): Promise<TranscriptImportReport> {
+  return invoke("import_transcripts");
+}
+
+export async function getSessionStats(sessionId: string): Promise<SessionLogStats> {
+  return invoke("get_session_stats", { sessionId });
+}
+
+export async function getErrorDigest(since?: string): Promise<ErrorDigest> {
+  return invoke("get_error_digest", { since: since ?? null });
+}
+
+export async function dbInfo(): Promise<DbInfo> {
+  return invoke("db_info");
+}
+
+export async function dbVacuum(): Promise<DbVacuumResult> {
+  return invoke("db_vacuum");
+}
+
+export async function dbIntegrityCheck(): Promise<DbIntegrityReport> {
+  return invoke("db_integrity_check");
+}
+
+export async function getLogCompressionStats(): Promise<LogCompressionStats> {
+  return invoke("get_log_compression_stats");
+}
+
+export async function getSessionCostBreakdown(
+  sessionId: string,
+): Promise<SessionCostBreakdown> {
+  return invoke("get_session_cost_breakdown", { sessionId });
+}
+
+/** The project's session costs summed per workflow, agent or spec, costliest first. */
+export async function getCostBreakdown(groupBy: CostGroupBy): Promise<CostGroup[]> {
+  return invoke("get_cost_breakdown", { groupBy });
+}
+
+export async function getSessionToolCalls(sessionId: string): Promise<ToolCall[]> {
+  return invoke("get_session_tool_calls", { sessionId });
+}
+
+export async function getSessionFileChanges(sessionId: string): Promise<FileChange[]> {
+  return invoke("get_session_file_changes", { sessionId });
+}
+
+export async function exportLogs(
+  sessionId: string,
+  format: "markdown" | "html" | "jsonl",
+  path: string,
+): Promise<void> {
+  return invoke("export_logs", { sessionId, format, path });
+}
+
+/** Write the project's usage per day, model and agent within `range` to `path` as CSV. */
+export async function exportUsage(range: UsageRange, path: string): Promise<void> {
+  return invoke("export_usage", { range, path });
+}
+
 // Config commands
 
 export async function getConfig(): Promise<AppConfig> {
@@ -74,14 +180,80 @@ export async function saveConfig(config: AppConfig): Promise<void> {
   return invoke("save_config", { config });
 }
 
-export async function setProjectPath(path: string): Promise<void> {
-  return invoke("set_project_path", { path });
+export async function openProject(path: string): Promise<void> {
+  return invoke("open_project", { path });
+}
+
+/** Write config, approvals and workflow templates to one archive. */
+export async function exportSettings(path: string): Promise<void> {
+  return invoke("export_settings", { path });
+}
+
+export async function importSettings(path: string): Promise<SettingsImportReport> {
+  return invoke("import_settings", { path });
+}
+
+/** Models for model pickers, and the default to preselect. */
+export async function listModels(): Promise<ModelCatalog> {
+  return invoke("list_models");
+}
+
+/** The open project's settings, with its `.clautron.json` applied. */
+export async function getProjectSettings(): Promise<ProjectSettings> {
+  return invoke("get_project_settings");
+}
+
+export async function getProjectEnv(): Promise<ProjectEnv> {
+  return invoke("get_project_env");
+}
+
+export async function allowProjectEnv(names: string[], acknowledged: boolean): Promise<ProjectEnv> {
+  return invoke("allow_project_env", { names, acknowledged });
+}
+
+export async function revokeProjectEnv(names: string[]): Promise<ProjectEnv> {
+  return invoke("revoke_project_env", { names });
+}
+
+export async function getHttpApi(): Promise<HttpApiStatus> {
+  return invoke("get_http_api");
+}
+
+export async function setHttpApi(enabled: boolean, port?: number): Promise<HttpApiStatus> {
+  return invoke("set_http_api", { enabled, port: port ?? null });
+}
+
+export async function regenerateHttpApiToken(): Promise<HttpApiStatus> {
+  return invoke("regenerate_http_api_token");
+}
+
+export async function listProjects(): Promise<KnownProject[]> {
+  return invoke("list_projects");
+}
+
+export async function addProject(path: string, name?: string | null): Promise<void> {
+  return invoke("add_project", { path, name: name ?? null });
+}
+
+export async function removeProject(path: string): Promise<void> {
+  return invoke("remove_project", { path });
 }
 
 export async function getProjectPath(): Promise<string | null> {
   return invoke("get_project_path");
 }
 
+export async function listRedactionBuiltins(): Promise<RedactionPattern[]> {
+  return invoke("list_redaction_builtins");
+}
+
+export async function testRedaction(
+  sample: string,
+  redaction?: RedactionConfig,
+): Promise<string> {
+  return invoke("test_redaction", { sample, redaction: redaction ?? null });
+}
+
 export async function checkAgentApproval(): Promise<UnapprovedAgent[]> {
   return invoke("check_agent_approval");
 }
@@ -92,6 +264,14 @@ export async function approveAgents(
   return invoke("approve_agents", { agents });
 }
 
+export async function revokeAgentApprovals(filePaths: string[]): Promise<number> {
+  return invoke("revoke_agent_approvals", { filePaths });
+}
+
+export async function listAgentApprovals(): Promise<AgentApproval[]> {
+  return invoke("list_agent_approvals");
+}
+
 // Agent config CRUD commands
 
 export async function getAgent(filePath: string): Promise<AgentConfig> {
@@ -107,6 +287,43 @@ export async function createAgentConfig(
   return invoke("create_agent_config", { name, model, description, color });
 }
 
+/** Agent definitions bundled with the app; `file_path` is empty. */
+export async function generateAgent(name: string, goals: string, color?: string): Promise<AgentConfig> {
+  return invoke("generate_agent", { name, goals, color: color ?? null });
+}
+
+export async function listSlashCommands(): Promise<SlashCommand[]> {
+  return invoke("list_slash_commands");
+}
+
+export async function getSlashCommand(filePath: string): Promise<SlashCommand> {
+  return invoke("get_slash_command", { filePath });
+}
+
+export async function createSlashCommand(name: string, description?: string): Promise<SlashCommand> {
+  return invoke("create_slash_command", { name, description: description ?? null });
+}
+
+export async function updateSlashCommand(filePath: string, update: SlashCommandUpdate): Promise<SlashCommand> {
+  return invoke("update_slash_command", { filePath, update });
+}
+
+export async function deleteSlashCommand(filePath: string): Promise<void> {
+  return invoke("delete_slash_command", { filePath });
+}
+
+export async function listSkills(): Promise<Skill[]> {
+  return invoke("list_skills");
+}
+
+export async function listAgentTemplates(): Promise<AgentConfig[]> {
+  return invoke("list_agent_templates");
+}
+
+export async function installAgentTemplate(name: string): Promise<AgentConfig> {
+  return invoke("install_agent_template", { name });
+}
+
 export async function updateAgentConfig(
   filePath: string,
   update: AgentConfigUpdate,
@@ -114,6 +331,14 @@ export async function updateAgentConfig(
   return invoke("update_agent_config", { filePath, update });
 }
 
+export async function getAgentVersions(filePath: string): Promise<AgentVersion[]> {
+  return invoke("get_agent_versions", { filePath });
+}
+
+export async function restoreAgentVersion(filePath: string, versionId: string): Promise<AgentConfig> {
+  return invoke("restore_agent_version", { filePath, versionId });
+}
+
 export async function deleteAgentConfig(filePath: string): Promise<void> {
   return invoke("delete_agent_config", { filePath });
 }
@@ -128,19 +353,25 @@ export async function generateText(prompt: string): Promise<string> {
 
 // Spec commands
 
-export async function listSpecs(): Promise<Spec[]> {
-  return invoke("list_specs");
+export async function listSpecs(filter?: SpecFilter): Promise<Spec[]> {
+  return invoke("list_specs", { filter: filter ?? null });
 }
 
 export async function getSpec(filePath: string): Promise<Spec> {
   return invoke("get_spec", { filePath });
 }
 
+export async function getSpecSessions(filePath: string): Promise<SpecSessionRecord[]> {
+  return invoke("get_spec_sessions", { filePath });
+}
+
 export async function createSpec(
   title: string,
   priority: SpecPriority,
+  epic?: string | null,
+  structure?: SpecStructure,
 ): Promise<Spec> {
-  return invoke("create_spec", { title, priority });
+  return invoke("create_spec", { title, priority, epic: epic ?? null, structure: structure ?? null });
 }
 
 export async function updateSpec(
@@ -154,12 +385,143 @@ export async function deleteSpec(filePath: string): Promise<void> {
   return invoke("delete_spec", { filePath });
 }
 
+export async function reorderSpec(
+  filePath: string,
+  status: SpecStatus,
+  index: number,
+): Promise<Spec> {
+  return invoke("reorder_spec", { filePath, status, index });
+}
+
+export async function listChildSpecs(filePath: string): Promise<Spec[]> {
+  return invoke("list_child_specs", { filePath });
+}
+
+export async function createChildSpec(
+  parentPath: string,
+  title: string,
+  priority: SpecPriority,
+): Promise<Spec> {
+  return invoke("create_child_spec", { parentPath, title, priority });
+}
+
+export async function getSpecHistory(
+  filePath: string,
+  limit?: number,
+): Promise<SpecRevision[]> {
+  return invoke("get_spec_history", { filePath, limit: limit ?? null });
+}
+
+export async function getSpecStats(): Promise<SpecStats> {
+  return invoke("get_spec_stats");
+}
+
+export async function approveSpec(filePath: string, comment?: string | null): Promise<Spec> {
+  return invoke("approve_spec", { filePath, comment: comment ?? null });
+}
+
+export async function rejectSpec(filePath: string, comment: string): Promise<Spec> {
+  return invoke("reject_spec", { filePath, comment });
+}
+
+/** CSV or JSON export of specs for reporting; also written to `outputPath` if given. */
+export async function exportSpecs(
+  format: "csv" | "json",
+  filter?: SpecFilter | null,
+  outputPath?: string | null,
+): Promise<string> {
+  return invoke("export_specs", { format, filter: filter ?? null, outputPath: outputPath ?? null });
+}
+
+export async function getSpecWorkflow(): Promise<SpecWorkflow> {
+  return invoke("get_spec_workflow");
+}
+
+export async function validateSpecs(): Promise<SpecDiagnostic[]> {
+  return invoke("validate_specs");
+}
+
+export async function breakdownSpec(specPath: string): Promise<ProposedSpec[]> {
+  return invoke("breakdown_spec", { specPath });
+}
+
+export async function applySpecBreakdown(
+  specPath: string,
+  children: ProposedSpec[],
+): Promise<Spec[]> {
+  return invoke("apply_spec_breakdown", { specPath, children });
+}
+
+export async function listGithubIssues(
+  repo: string,
+  limit?: number,
+): Promise<GithubIssue[]> {
+  return invoke("list_github_issues", { repo, limit: limit ?? null });
+}
+
+export async function importGithubIssues(
+  repo: string,
+  numbers: number[],
+): Promise<Spec[]> {
+  return invoke("import_github_issues", { repo, numbers });
+}
+
+export async function importJiraIssues(
+  jql: string,
+  limit?: number,
+): Promise<Spec[]> {
+  return invoke("import_jira_issues", { jql, limit: limit ?? null });
+}
+
+export async function importLinearIssues(
+  teamKey?: string,
+  limit?: number,
+): Promise<Spec[]> {
+  return invoke("import_linear_issues", {
+    teamKey: teamKey ?? null,
+    limit: limit ?? null,
+  });
+}
+
+export async function syncLinear(): Promise<LinearSyncReport> {
+  return invoke("sync_linear");
+}
+
+export async function listLinearConflicts(): Promise<LinearSyncConflict[]> {
+  return invoke("list_linear_conflicts");
+}
+
 export async function runSpec(
   specPath: string,
   agentName: string,
   model: string,
+  force?: boolean,
 ): Promise<string> {
-  return invoke("run_spec", { specPath, agentName, model });
+  return invoke("run_spec", { specPath, agentName, model, force: force ?? null });
+}
+
+/** Run a spec with its implementer and reviewers in turn. Returns the workflow ID. */
+export async function runSpecPair(specPath: string, model: string, force?: boolean): Promise<string> {
+  return invoke("run_spec_pair", { specPath, model, force: force ?? null });
+}
+
+/** Run several specs through an ad-hoc workflow. Returns the workflow ID. */
+export async function runSpecs(
+  specPaths: string[],
+  agentName: string,
+  model: string,
+  mode: RunSpecsMode,
+  force?: boolean,
+): Promise<string> {
+  return invoke("run_specs", { specPaths, agentName, model, mode, force: force ?? null });
+}
+
+export async function verifySpec(
+  specPath: string,
+  agentName: string,
+  model: string,
+): Promise<string> {
+  return invoke("verify_spec", { specPath, agentName, model });
 }
 
 // Review commands
@@ -172,6 +534,78 @@ export async function getDiff(paths?: string[]): Promise<FileDiff[]> {
   return invoke("get_diff", { paths: paths ?? null });
 }
 
+/** One file's staged or unstaged diff; null when that side has no changes. */
+export async function getFileDiff(path: string, staged: boolean): Promise<FileDiff | null> {
+  return invoke("get_file_diff", { path, staged });
+}
+
+/** Changes made while a session ran, from its start and end snapshots. */
+export async function getSessionDiff(sessionId: string, paths?: string[]): Promise<FileDiff[]> {
+  return invoke("get_session_diff", { sessionId, paths: paths ?? null });
+}
+
+/** Commits made between two dates (any branch), linked to the session running at the time. */
+export async function getCommits(
+  since: string,
+  until?: string | null,
+  author?: string | null,
+): Promise<GitCommit[]> {
+  return invoke("get_commits", { since, until: until ?? null, author: author ?? null });
+}
+
+/** Who last changed each line in a range, at `revision` or in the working tree. */
+export async function getBlame(
+  path: string,
+  startLine: number,
+  endLine: number,
+  revision?: string | null,
+): Promise<BlameLine[]> {
+  return invoke("get_blame", { path, startLine, endLine, revision: revision ?? null });
+}
+
+/** Revert every change to a file; untracked files are deleted. */
+export async function discardFile(path: string): Promise<void> {
+  return invoke("discard_file", { path });
+}
+
+/** Revert one hunk of a file, identified by its `@@` header and side. */
+export async function discardHunk(path: string, hunkHeader: string, staged: boolean): Promise<void> {
+  return invoke("discard_hunk", { path, hunkHeader, staged });
+}
+
+/** The files and hunks a unified diff would change, without applying it. */
+export async function previewPatch(diffText: string): Promise<FileDiff[]> {
+  return invoke("preview_patch", { diffText });
+}
+
+/** Apply a unified diff to the working tree, only `paths` when given. */
+/** Apply a patch; `hunks` keeps only the hunks at those indexes of the files it lists. */
+export async function applyPatch(
+  diffText: string,
+  paths?: string[],
+  hunks?: Record<string, number[]>,
+): Promise<string[]> {
+  return invoke("apply_patch", { diffText, paths: paths ?? null, hunks: hunks ?? null });
+}
+
+export async function listBranches(): Promise<GitBranch[]> {
+  return invoke("list_branches");
+}
+
+/** Create a branch at HEAD, switching to it unless `checkout` is false. */
+export async function createBranch(name: string, checkout?: boolean): Promise<void> {
+  return invoke("create_branch", { name, checkout: checkout ?? null });
+}
+
+export async function checkoutBranch(name: string): Promise<void> {
+  return invoke("checkout_branch", { name });
+}
+
+/** A conventional-commit message for the current changes, written by Claude. */
+export async function suggestCommitMessage(paths?: string[]): Promise<string> {
+  return invoke("suggest_commit_message", { paths: paths ?? null });
+}
+
 // Workflow commands
 
 export async function createWorkflow(
@@ -202,6 +636,12 @@ export async function addWorkflowStep(
   positionX: number,
   positionY: number,
   passContext?: boolean,
+  failurePolicy?: FailurePolicy,
+  joinMode?: JoinMode,
+  kind?: StepKind,
+  gateCommand?: string | null,
+  promptTemplateId?: string | null,
+  templateVars?: Record<string, string>,
 ): Promise<WorkflowStep> {
   return invoke("add_workflow_step", {
     workflowId,
@@ -212,6 +652,12 @@ export async function addWorkflowStep(
     positionX,
     positionY,
     passContext: passContext ?? false,
+    failurePolicy: failurePolicy ?? null,
+    joinMode: joinMode ?? null,
+    kind: kind ?? null,
+    gateCommand: gateCommand ?? null,
+    promptTemplateId: promptTemplateId ?? null,
+    templateVars: templateVars ?? null,
   });
 }
 
@@ -235,11 +681,13 @@ export async function addWorkflowEdge(
   workflowId: string,
   sourceStepId: string,
   targetStepId: string,
+  condition?: EdgeCondition,
 ): Promise<WorkflowEdge> {
   return invoke("add_workflow_edge", {
     workflowId,
     sourceStepId,
     targetStepId,
+    condition: condition ?? null,
   });
 }
 
@@ -253,6 +701,52 @@ export async function getWorkflowEdges(
   return invoke("get_workflow_edges", { workflowId });
 }
 
+// --- Step prompt templates ---
+
+export async function listPromptTemplates(): Promise<PromptTemplate[]> {
+  return invoke("list_prompt_templates");
+}
+
+export async function createPromptTemplate(
+  name: string,
+  body: string,
+  variables?: Record<string, string>,
+): Promise<PromptTemplate> {
+  return invoke("create_prompt_template", {
+    name,
+    body,
+    variables: variables ?? null,
+  });
+}
+
+export async function updatePromptTemplate(
+  template: PromptTemplate,
+): Promise<PromptTemplate> {
+  return invoke("update_prompt_template", { template });
+}
+
+export async function deletePromptTemplate(id: string): Promise<void> {
+  return invoke("delete_prompt_template", { id });
+}
+
+// --- Import from Claude Code custom commands ---
+
+export async function listCustomCommands(): Promise<CustomCommand[]> {
+  return invoke("list_custom_commands");
+}
+
+export async function importCustomCommand(
+  commandName: string,
+  agentName: string,
+  model?: string,
+): Promise<Workflow> {
+  return invoke("import_custom_command", {
+    commandName,
+    agentName,
+    model: model ?? null,
+  });
+}
+
 export async function startWorkflow(id: string): Promise<void> {
   return invoke("start_workflow", { id });
 }
@@ -265,6 +759,104 @@ export async function validateWorkflow(id: string): Promise<void> {
   return invoke("validate_workflow", { id });
 }
 
+export async function simulateWorkflow(id: string): Promise<WorkflowSimulation> {
+  return invoke("simulate_workflow", { id });
+}
+
+/** Predicted cost and duration of running a workflow or a spec, from past sessions. */
+export async function estimateCost(target: {
+  workflowId?: string;
+  specPath?: string;
+  /** Agent a spec runs with; its assignees when omitted. */
+  agentName?: string;
+}): Promise<RunEstimate> {
+  return invoke("estimate_cost", {
+    workflowId: target.workflowId ?? null,
+    specPath: target.specPath ?? null,
+    agentName: target.agentName ?? null,
+  });
+}
+
+// Workflow template commands
+
+export async function listWorkflowTemplates(): Promise<WorkflowTemplate[]> {
+  return invoke("list_workflow_templates");
+}
+
+export async function saveWorkflowTemplate(
+  workflowId: string,
+  name?: string,
+): Promise<WorkflowTemplate> {
+  return invoke("save_workflow_template", { workflowId, name: name ?? null });
+}
+
+export async function deleteWorkflowTemplate(id: string): Promise<void> {
+  return invoke("delete_workflow_template", { id });
+}
+
+export async function instantiateWorkflowTemplate(
+  templateId: string,
+  name?: string,
+): Promise<TemplateInstantiation> {
+  return invoke("instantiate_workflow_template", {
+    templateId,
+    name: name ?? null,
+  });
+}
+
 export async function refreshQuota(): Promise<void> {
   return invoke("refresh_quota");
 }
+
+/** Spend per period between two `YYYY-MM-DD` dates (inclusive, open-ended when omitted). */
+export async function getUsageHistory(
+  from?: string,
+  to?: string,
+  granularity?: UsageGranularity,
+): Promise<UsagePeriod[]> {
+  return invoke("get_usage_history", {
+    from: from ?? null,
+    to: to ?? null,
+    granularity: granularity ?? null,
+  });
+}
+
+export async function listMemoryFiles(): Promise<MemoryFile[]> {
+  return invoke("list_memory_files");
+}
+
+export async function getMemoryFile(scope: MemoryScope): Promise<MemoryFile> {
+  return invoke("get_memory_file", { scope });
+}
+
+export async function saveMemoryFile(scope: MemoryScope, content: string): Promise<MemoryFile> {
+  return invoke("save_memory_file", { scope, content });
+}
+
+export async function listHooks(): Promise<HooksSettings[]> {
+  return invoke("list_hooks");
+}
+
+export async function saveHooks(scope: SettingsScope, hooks: HooksConfig): Promise<HooksSettings> {
+  return invoke("save_hooks", { scope, hooks });
+}
+
+export async function listMcpServers(): Promise<McpServer[]> {
+  return invoke("list_mcp_servers");
+}
+
+export async function addMcpServer(server: McpServer): Promise<void> {
+  return invoke("add_mcp_server", { server });
+}
+
+export async function removeMcpServer(name: string, scope: McpScope): Promise<void> {
+  return invoke("remove_mcp_server", { name, scope });
+}
+
+export async function checkMcpServers(): Promise<McpHealth[]> {
+  return invoke("check_mcp_servers");
+}
+
+export async function approveMcpServer(name: string): Promise<void> {
+  return invoke("approve_mcp_server", { name });
+}
@@ -12,7 +12,14 @@ export interface AgentConfig {
   name: string;
   description: string;
   model: string;
+  /** Always one of `AGENT_COLOR_OPTIONS`; off-palette values load as gray. */
   color: string;
+  /** One of the names in `AGENT_ICONS`, or `null`. */
+  icon: string | null;
+  /** Allowed tools; `null` inherits all tools. */
+  tools: string[] | null;
+  /** Agents this one is meant to work with (`pairs_with:` frontmatter). */
+  pairs_with: string[];
   file_path: string;
   body: string;
 }
@@ -22,14 +29,44 @@ export interface AgentConfigUpdate {
   description?: string;
   model?: string;
   color?: string;
+  /** `null` removes the icon. */
+  icon?: string | null;
+  tools?: string[] | null;
+  pairs_with?: string[];
   body?: string;
 }
 
+/** Claude Code's built-in tools; MCP tools (`mcp__server__tool`) are allowed too. */
+export const KNOWN_AGENT_TOOLS = [
+  "Agent",
+  "Bash",
+  "BashOutput",
+  "Edit",
+  "ExitPlanMode",
+  "Glob",
+  "Grep",
+  "KillShell",
+  "LS",
+  "MultiEdit",
+  "NotebookEdit",
+  "NotebookRead",
+  "Read",
+  "SlashCommand",
+  "Task",
+  "TodoWrite",
+  "WebFetch",
+  "WebSearch",
+  "Write",
+] as const;
+
 export interface AgentRelationship {
   source_agent: string;
   target_agent: string;
   workflow_names: string[];
+  /** Workflow edges between the two; 0 when the pairing is only declared. */
   edge_count: number;
+  /** The source agent lists the target in `pairs_with:`. */
+  declared: boolean;
 }
 
 export const AGENT_COLOR_OPTIONS = [
@@ -38,6 +75,7 @@ export const AGENT_COLOR_OPTIONS = [
   { value: "green", label: "Green", hex: "#22c55e" },
   { value: "yellow", label: "Yellow", hex: "#f59e0b" },
   { value: "purple", label: "Purple", hex: "#a855f7" },
+  { value: "orange", label: "Orange", hex: "#f97316" },
   { value: "pink", label: "Pink", hex: "#ec4899" },
   { value: "cyan", label: "Cyan", hex: "#06b6d4" },
   { value: "gray", label: "Gray", hex: "#71717a" },
@@ -54,6 +92,161 @@ export interface AgentSession {
   input_tokens: number;
   output_tokens: number;
   cost_usd: number;
+  /** Project the session was started in; null when none was open. */
+  project_dir?: string | null;
+  /** Git branch created for the session's work. */
+  branch?: string | null;
+  /** `cost_usd` was estimated from tokens because Claude Code reported none (API-key mode). */
+  cost_estimated?: boolean;
+  /** Claude profile the session ran under; null for the default config directory. */
+  profile?: string | null;
+}
+
+/** Usage of one agent aggregated over its sessions. */
+export interface AgentStats {
+  agent_name: string;
+  run_count: number;
+  completed_count: number;
+  error_count: number;
+  success_rate: number;
+  error_rate: number;
+  avg_duration_secs: number | null;
+  input_tokens: number;
+  output_tokens: number;
+  total_cost_usd: number;
+  avg_cost_usd: number;
+  last_used_at: string | null;
+}
+
+/** A saved copy of an agent definition, taken before it was overwritten. */
+export interface AgentVersion {
+  id: string;
+  saved_at: string;
+  content: string;
+}
+
+/** Outcome of a sandboxed agent test run; never stored as a session. */
+export interface AgentTestRun {
+  agent_name: string;
+  model: string;
+  status: AgentStatus;
+  result: string | null;
+  transcript: AgentTestMessage[];
+  input_tokens: number;
+  output_tokens: number;
+  cost_usd: number;
+  duration_ms: number;
+}
+
+export interface AgentTestMessage {
+  message_type: string;
+  content: string;
+}
+
+/** A skill package: a `.claude/skills/<dir>/` folder with a `SKILL.md`. */
+export interface Skill {
+  name: string;
+  description: string;
+  dir_path: string;
+  /** Relative to `dir_path`. */
+  files: string[];
+  /** Agents whose definition mentions this skill. */
+  referenced_by: string[];
+}
+
+/** A Claude Code slash command from `.claude/commands/`. */
+export interface SlashCommand {
+  /** Name as typed after `/`; subdirectories become `dir:name`. */
+  name: string;
+  file_path: string;
+  description: string | null;
+  argument_hint: string | null;
+  allowed_tools: string | null;
+  model: string | null;
+  body: string;
+}
+
+/** An empty string removes the frontmatter field. */
+export interface SlashCommandUpdate {
+  description?: string;
+  argument_hint?: string;
+  allowed_tools?: string;
+  model?: string;
+  body?: string;
+}
+
+/** Where an MCP server is configured: `.mcp.json` or `~/.claude.json`. */
+export type McpScope = "project" | "user";
+
+export interface McpServer {
+  name: string;
+  scope: McpScope;
+  transport: "stdio" | "http" | "sse";
+  command: string | null;
+  args: string[];
+  env: Record<string, string>;
+  url: string | null;
+}
+
+/** `unapproved`: a `.mcp.json` stdio server that isn't launched until approved. */
+export type McpHealthStatus = "ok" | "error" | "timeout" | "unapproved";
+
+export interface McpHealth {
+  name: string;
+  scope: McpScope;
+  status: McpHealthStatus;
+  message: string;
+  latency_ms: number;
+}
+
+/** Which CLAUDE.md memory file: shared project, private project, or global. */
+export type MemoryScope = "project" | "project_local" | "user";
+
+export interface MemoryFile {
+  scope: MemoryScope;
+  path: string;
+  exists: boolean;
+  content: string;
+}
+
+/** Which Claude Code settings file: `.claude/settings.json`, `.claude/settings.local.json`, or `~/.claude/settings.json`. */
+export type SettingsScope = "project" | "project_local" | "user";
+
+/** Hook events Claude Code fires, in lifecycle order. */
+export const HOOK_EVENTS = [
+  "SessionStart",
+  "UserPromptSubmit",
+  "PreToolUse",
+  "PostToolUse",
+  "Notification",
+  "Stop",
+  "SubagentStop",
+  "PreCompact",
+  "SessionEnd",
+] as const;
+
+export interface HookCommand {
+  type: "command" | "prompt";
+  command?: string;
+  prompt?: string;
+  /** Seconds. */
+  timeout?: number;
+}
+
+export interface HookMatcherGroup {
+  matcher?: string;
+  hooks: HookCommand[];
+}
+
+export type HooksConfig = Partial<Record<(typeof HOOK_EVENTS)[number], HookMatcherGroup[]>>;
+
+/** The `hooks` section of one settings file; `errors` lists schema problems by JSON path. */
+export interface HooksSettings {
+  scope: SettingsScope;
+  path: string;
+  exists: boolean;
+  hooks: HooksConfig;
+  errors: string[];
 }
 
 export interface LogEntry {
@@ -62,6 +255,193 @@ export interface LogEntry {
   message_type: string;
   content: string;
   timestamp: string;
+  input_tokens?: number | null;
+  output_tokens?: number | null;
+}
+
+/** One assistant turn with its share of the session's cost. */
+export interface TurnCost {
+  log_id: number;
+  timestamp: string;
+  input_tokens: number;
+  output_tokens: number;
+  estimated_cost_usd: number;
+  tools: string[];
+  text_preview: string;
+}
+
+export interface SessionCostBreakdown {
+  session_id: string;
+  total_cost_usd: number;
+  input_tokens: number;
+  output_tokens: number;
+  turns: TurnCost[];
+}
+
+export type CostGroupBy = "workflow" | "agent" | "spec";
+
+/** Total cost of one group's sessions, e.g. every run of a workflow. */
+export interface CostGroup {
+  /** Workflow id, agent name or spec path; null for sessions outside any workflow or spec. */
+  key: string | null;
+  label: string;
+  cost_usd: number;
+  input_tokens: number;
+  output_tokens: number;
+  session_count: number;
+}
+
+/** Token counts and cost of one model, cumulative or over a period. */
+export interface ModelUsage {
+  model: string;
+  input_tokens: number;
+  output_tokens: number;
+  cache_read_tokens: number;
+  cache_creation_tokens: number;
+  cost_usd: number;
+}
+
+export type UsageGranularity = "day" | "week" | "month";
+
+/** Usage within one period of the history. */
+export interface UsagePeriod {
+  /** First day of the period, `YYYY-MM-DD`. */
+  start: string;
+  cost_usd: number;
+  /** Costliest model first. */
+  models: ModelUsage[];
+}
+
+/** Inclusive range of local dates (`YYYY-MM-DD`); open-ended on a null side. */
+export interface UsageRange {
+  from: string | null;
+  to: string | null;
+}
+
+/** A stored session with the log entries that matched a search, newest first. */
+export interface SessionSearchResult {
+  session_id: string;
+  agent_name: string;
+  model: string;
+  prompt: string;
+  started_at: string;
+  project_dir: string | null;
+  matches: LogEntry[];
+}
+
+/** Outcome of importing the project's terminal transcripts. */
+export interface TranscriptImportReport {
+  imported: number;
+  skipped: number;
+  failed: string[];
+}
+
+/** Summary figures for one session's log, computed without loading it. */
+export interface SessionLogStats {
+  session_id: string;
+  total_entries: number;
+  messages_by_type: Record<string, number>;
+  tool_calls_by_name: Record<string, number>;
+  /** Content size as written by the agent, before compression. */
+  content_bytes: number;
+  first_timestamp: string | null;
+  last_timestamp: string | null;
+  /** stderr lines, failed tool calls and error results. */
+  error_count: number;
+}
+
+export interface DbTableInfo {
+  name: string;
+  rows: number;
+}
+
+export interface DbInfo {
+  path: string;
+  /** Database plus WAL files on disk. */
+  file_size_bytes: number;
+  /** Space in free pages, reclaimable with a vacuum. */
+  free_bytes: number;
+  page_count: number;
+  tables: DbTableInfo[];
+}
+
+export interface DbVacuumResult {
+  size_before: number;
+  size_after: number;
+}
+
+export interface DbIntegrityReport {
+  ok: boolean;
+  problems: string[];
+}
+
+export type ErrorCategory =
+  | "rate_limit"
+  | "overloaded"
+  | "auth"
+  | "network"
+  | "tool_failure"
+  | "other";
+
+export interface ErrorExample {
+  session_id: string;
+  timestamp: string;
+  message: string;
+}
+
+/** Errors that look alike once numbers, ids and paths are masked. */
+export interface ErrorGroup {
+  category: ErrorCategory;
+  signature: string;
+  count: number;
+  session_count: number;
+  first_seen: string;
+  last_seen: string;
+  examples: ErrorExample[];
+}
+
+export interface ErrorDigest {
+  since: string | null;
+  total_errors: number;
+  /** Most frequent first. */
+  groups: ErrorGroup[];
+}
+
+export interface LogCompressionStats {
+  total_entries: number;
+  compressed_entries: number;
+  raw_bytes: number;
+  stored_bytes: number;
+}
+
+export interface LogQueryFilter {
+  /** Stored message types, or `tool_use` / `tool_result`; empty means all */
+  message_types?: string[];
+  since?: string | null;
+  until?: string | null;
+  errors_only?: boolean;
+}
+
+export interface ToolCall {
+  id: number;
+  session_id: string;
+  tool_use_id: string;
+  tool_name: string;
+  input_summary: string;
+  started_at: string;
+  finished_at: string | null;
+  duration_ms: number | null;
+  /** null while the call is still running */
+  success: boolean | null;
+}
+
+export interface FileChange {
+  id: number;
+  session_id: string;
+  /** Relative to the project when the agent wrote inside it */
+  file_path: string;
+  operation: "write" | "edit" | "notebook_edit";
+  timestamp: string;
 }
 
 // Tauri event payloads
@@ -87,6 +467,19 @@ export interface AgentUsageEvent {
   output_tokens: number;
   /** Actual cost in USD from Claude Code's result message. Zero for intermediate updates. */
   cost_usd: number;
+  /** The result reported no cost, so `cost_usd` is estimated from tokens. */
+  cost_estimated: boolean;
+}
+
+/** Two running sessions wrote the same file. */
+export interface FileConflictEvent {
+  path: string;
+  /** The session that just wrote it. */
+  session_id: string;
+  agent_name: string;
+  /** The session that wrote it first. */
+  other_session_id: string;
+  other_agent_name: string;
 }
 
 // App config (mirrors Rust AppConfig)
@@ -94,16 +487,266 @@ export interface AppConfig {
   project_path: string | null;
   window_width: number | null;
   window_height: number | null;
-  approved_agent_hashes: Record<string, string>;
+  /** Legacy; approvals now live in a per-project store. */
+  approved_agent_hashes?: Record<string, string>;
+  integrations: IntegrationsConfig;
+  /** Desktop notification when a spec becomes due soon or overdue. */
+  notify_spec_due: boolean;
+  /** Secret redaction for every project; merged with the project's own. */
+  redaction: RedactionConfig;
+  /** Spending limits, checked against Claude Code's recorded usage. */
+  budgets?: BudgetConfig;
+  /** Expected capacity of the subscription's rolling usage window. */
+  rate_window?: RateWindowConfig;
+  /** Which quota thresholds show a desktop notification. */
+  quota_notifications?: QuotaNotifyConfig;
+  /** Which agent events show a desktop notification. */
+  agent_notifications?: AgentNotifyConfig;
+  /** Local HTTP API for scripts and editors. */
+  http_api?: HttpApiConfig;
+  /** Prices overriding the built-in table for estimated costs, keyed by model name or part of it. */
+  pricing?: Record<string, ModelPrice>;
+  /** Model preselected when starting an agent that doesn't name one; a project's own takes precedence. */
+  default_model?: string | null;
+  /** Claude config directories besides the default one, e.g. one per account. */
+  profiles?: ClaudeProfile[];
+  /** Known projects and their settings, keyed by project path. */
+  projects?: Record<string, ProjectSettings>;
+}
+
+/** Desktop notifications for quota thresholds; all off by default. */
+export interface QuotaNotifyConfig {
+  budget_warning: boolean;
+  budget_exceeded: boolean;
+  /** A session hit Claude's usage limit. */
+  rate_limited: boolean;
+  /** Percent of `rate_window.token_limit` used in the current window at which to notify. */
+  window_percent: number | null;
+  /** The limit hit, or a window past `window_percent`, has reset. */
+  window_reset: boolean;
+}
+
+/** Local HTTP API on 127.0.0.1; clients send `token` as a bearer token. */
+export interface HttpApiConfig {
+  enabled: boolean;
+  /** 7777 when unset. */
+  port?: number | null;
+  token?: string | null;
+}
+
+export interface HttpApiStatus {
+  enabled: boolean;
+  port: number;
+  token: string | null;
+  /** The port it's listening on; null while stopped. */
+  listening: number | null;
+}
+
+export type AgentNotifyEvent = "started" | "completed" | "failed" | "stopped" | "file_conflict";
+
+/** The first rule matching an agent event decides whether it shows a notification. */
+export interface AgentNotifyConfig {
+  rules: NotificationRule[];
+  /** `HH:MM` local times between which nothing is shown; may span midnight. */
+  quiet_hours?: { start: string; end: string } | null;
+}
+
+export interface NotificationRule {
+  event: AgentNotifyEvent;
+  /** Only sessions of these agents; every agent when empty. */
+  agents?: string[];
+  /** False mutes matching events. */
+  notify?: boolean;
+}
+
+/** USD limits per calendar day, week (from Monday) and month; unset ones aren't checked. */
+export interface BudgetConfig {
+  daily_usd?: number | null;
+  weekly_usd?: number | null;
+  monthly_usd?: number | null;
+  /** Percentage of a limit at which to warn; 80 when unset. */
+  warn_percent?: number | null;
+  /** Refuse to start new sessions while any limit is used up. */
+  hard_stop?: boolean;
+}
+
+export type BudgetPeriod = "daily" | "weekly" | "monthly";
+
+/** A budget in its current period; payload of `quota:budget-warning` and `quota:budget-exceeded`. */
+export interface BudgetStatus {
+  period: BudgetPeriod;
+  /** First day of the current period, `YYYY-MM-DD`. */
+  start: string;
+  limit_usd: number;
+  spent_usd: number;
+  level: "warning" | "exceeded" | null;
+}
+
+/** USD per million tokens of one model. */
+export interface ModelPrice {
+  input_per_mtok: number;
+  output_per_mtok: number;
+}
+
+/** The 5-hour window's limit isn't published, so headroom is only estimated when set. */
+export interface RateWindowConfig {
+  /** Input plus output tokens a window is expected to allow. */
+  token_limit?: number | null;
+}
+
+/** Usage in the current 5-hour window; payload of `quota:window-update`. */
+export interface RateWindow {
+  /** Null when no window is open and the next session starts a fresh one. */
+  started_at: string | null;
+  resets_at: string | null;
+  resets_in_secs: number | null;
+  session_count: number;
+  input_tokens: number;
+  output_tokens: number;
+  cost_usd: number;
+  token_limit: number | null;
+  remaining_tokens: number | null;
+}
+
+export interface ProjectSettings {
+  /** Shown in the recent projects list instead of the folder name. */
+  name?: string | null;
+  last_opened?: string | null;
+  /** Also write session logs to `~/.clautron/logs/<session_id>.jsonl`. */
+  mirror_logs: boolean;
+  redaction?: RedactionConfig;
+  /** Start each agent on a new branch named after its spec or agent. */
+  auto_branch?: boolean;
+  /** Pause a workflow step's agent when it writes a file another agent has written. */
+  hold_on_conflict?: boolean;
+  /** What starting an agent does when the working tree has uncommitted changes. */
+  dirty_tree?: DirtyTreePolicy;
+  /** Profile sessions run under unless started with another. */
+  profile?: string | null;
+  /** Model preselected when starting an agent that doesn't name one. */
+  default_model?: string | null;
+  /** Variables passed to the project's agents on top of the default allowlist. */
+  extra_env?: string[];
+  /** `.mcp.json` stdio servers the health check may launch, with their approved launch hash. */
+  approved_mcp_servers?: Record<string, string>;
+}
+
+/** Variables the open project's agents receive, and those its config file asks for. */
+export interface ProjectEnv {
+  allowed: string[];
+  /** Requested by `.clautron.json` and not allowed yet. */
+  requested: string[];
+  /** Requested by `.clautron.json` but never passed, since they can inject code into agents. */
+  refused: string[];
+}
+
+/** A Claude config directory (`CLAUDE_CONFIG_DIR`), e.g. for a second account. */
+export interface ClaudeProfile {
+  name: string;
+  /** `~` expands to the home directory. */
+  config_dir: string;
+}
+
+/** What importing a settings archive added next to the replaced config. */
+export interface SettingsImportReport {
+  agent_approvals: number;
+  workflow_templates: number;
+}
+
+/** A model offered by model pickers. */
+export interface ModelOption {
+  /** Passed to `claude --model`. */
+  id: string;
+  label: string;
+  /** A family alias following the latest release, rather than a dated ID. */
+  alias: boolean;
+}
+
+export interface ModelCatalog {
+  models: ModelOption[];
+  default_model: string;
+}
+
+/** Entry of the recent projects list. */
+export interface KnownProject {
+  path: string;
+  /** The configured name, else the folder name. */
+  name: string;
+  last_opened: string | null;
+  /** False once the folder has been moved or deleted. */
+  exists: boolean;
+}
+
+export type DirtyTreePolicy = "allow" | "warn" | "block" | "stash";
+
+export interface RedactionConfig {
+  /** Names of built-in patterns to skip. */
+  disabled_builtins: string[];
+  /** Extra regexes, e.g. internal token formats or customer identifiers. */
+  custom_patterns: string[];
+}
+
+export interface RedactionPattern {
+  name: string;
+  pattern: string;
+}
+
+export interface IntegrationsConfig {
+  github_token: string | null;
+  jira: JiraConfig | null;
+  linear_api_key: string | null;
+  /** Endpoint notified of agent events. */
+  webhook?: WebhookConfig | null;
+}
+
+export type WebhookEventKind = "status" | "message" | "usage" | "rate_limited" | "file_conflict";
+
+/** Agent events POSTed as JSON batches; signed with HMAC-SHA256 when `secret` is set. */
+export interface WebhookConfig {
+  url: string;
+  secret?: string | null;
+  events?: WebhookEventKind[];
+}
+
+export interface JiraConfig {
+  base_url: string;
+  email: string | null;
+  api_token: string;
+}
+
+export interface LinearSyncConflict {
+  file_path: string;
+  external_id: string;
+  local_status: SpecStatus;
+  remote_state: string;
+  detected_at: string;
+}
+
+export interface LinearSyncReport {
+  pushed: string[];
+  pulled: string[];
+  conflicts: LinearSyncConflict[];
+  errors: string[];
 }
 
 // Unapproved agent for security prompt (P0 Security #4)
+export interface AgentApproval {
+  /** Relative to the project root, `/`-separated. */
+  file_path: string;
+  hash: string;
+  approved_at: string;
+}
+
 export interface UnapprovedAgent {
   file_path: string;
   name: string;
   model: string;
   description: string;
   hash: string;
+  /** An earlier version of this file was approved. */
+  previously_approved: boolean;
+  /** Changes since the last approved version, when its content is on record. */
+  diff: DiffHunk[] | null;
 }
 
 // Agent config change event
@@ -113,7 +756,7 @@ export interface AgentConfigChangedEvent {
 
 // --- Specs ---
 
-export type SpecStatus =
+export type BuiltInSpecStatus =
   | "draft"
   | "assigned"
   | "in_progress"
@@ -121,35 +764,231 @@ export type SpecStatus =
   | "done"
   | "rejected";
 
+/** A built-in status or a custom one from the project's spec workflow. */
+export type SpecStatus = BuiltInSpecStatus | (string & {});
+
+export interface SpecStatusDefinition {
+  id: SpecStatus;
+  label: string | null;
+  color: string | null;
+}
+
+/** From `.clautron/spec-workflow.json`, or the built-in defaults. */
+export interface SpecWorkflow {
+  /** Every valid status in board order. */
+  statuses: SpecStatusDefinition[];
+  /** Allowed next statuses; statuses without an entry may move anywhere. */
+  transitions: Record<string, SpecStatus[]>;
+  on_agent_completed: SpecStatus;
+}
+
 export type SpecPriority = "p0" | "p1" | "p2";
 
+/** T-shirt size (`S`, `M`, `L`, ...) or story points. */
+export type SpecEstimate = string | number;
+
+export type RunSpecsMode =
+  | { type: "sequential" }
+  | { type: "parallel"; max_parallel?: number | null };
+
+/** `list_specs` filter; omitted fields match everything. Labels must all match. */
+export interface SpecFilter {
+  labels?: string[];
+  status?: SpecStatus | null;
+  priority?: SpecPriority | null;
+  assigned_agent?: string | null;
+  /** Folder under specs/, subfolders included. */
+  epic?: string | null;
+}
+
+export interface SpecStatBucket {
+  key: string;
+  count: number;
+  points: number;
+}
+
+/** A frontmatter problem found by `validate_specs`. Errors mean the file is missing from the board. */
+export interface SpecDiagnostic {
+  file_path: string;
+  severity: "error" | "warning";
+  field: string | null;
+  message: string;
+}
+
+/** A frontmatter problem found by `validate_agents`. Errors mean the agent can't be loaded. */
+export interface AgentDiagnostic {
+  file_path: string;
+  severity: "error" | "warning";
+  field: string | null;
+  message: string;
+}
+
+export interface SpecStats {
+  total: number;
+  open: number;
+  open_points: number;
+  unestimated: number;
+  by_priority: SpecStatBucket[];
+  by_status: SpecStatBucket[];
+}
+
+export interface AcceptanceCriterion {
+  text: string;
+  done: boolean;
+  /** Latest finding from a verification run. */
+  finding?: string | null;
+}
+
+export type VerificationStatus = "running" | "passed" | "failed" | "error";
+
+export interface SpecVerification {
+  session_id: string;
+  status: VerificationStatus;
+  started_at: string;
+  finished_at?: string | null;
+  summary?: string | null;
+}
+
+export type ReviewDecision = "approved" | "rejected";
+
+export interface SpecReview {
+  decision: ReviewDecision;
+  reviewer: string;
+  at: string;
+  comment?: string | null;
+}
+
+/** Body layout for a new spec: empty, or Context / Requirements / Out of Scope / Acceptance Criteria. */
+export type SpecStructure = "blank" | "standard";
+
+/** One agent run on a spec; usage is filled in when the run ends. */
+export interface SpecSessionRecord {
+  session_id: string;
+  agent_name: string;
+  started_at: string;
+  ended_at?: string | null;
+  /** `null` while the run is in progress. */
+  outcome?: AgentStatus | null;
+  cost_usd?: number | null;
+  input_tokens?: number | null;
+  output_tokens?: number | null;
+}
+
 export interface Spec {
   title: string;
   priority: SpecPriority;
   status: SpecStatus;
-  acceptance_criteria: string[];
+  estimate: SpecEstimate | null;
+  labels: string[];
+  acceptance_criteria: AcceptanceCriterion[];
   assigned_agent: string | null;
   assigned_session_id: string | null;
+  /** Implementer first, then reviewers; run together with `runSpecPair`. */
+  assigned_agents: string[];
   parent_spec: string | null;
+  suggested_agent: string | null;
+  depends_on: string[];
+  rank: number | null;
+  /** "YYYY-MM-DD" or an RFC 3339 timestamp. */
+  due_date: string | null;
+  /** Git branch whose merge auto-completes the spec from review. */
+  branch: string | null;
+  external_id: string | null;
+  external_url: string | null;
+  verification: SpecVerification | null;
+  /** Review log, oldest first. */
+  reviews: SpecReview[];
+  /** Every agent run bound to this spec, oldest first. */
+  sessions: SpecSessionRecord[];
   created_at: string;
   updated_at: string;
   file_path: string;
   body: string;
+  /** Computed: dependencies that aren't done yet. */
+  blocked_by: string[];
+  /** Computed: number of child specs and how many are done. */
+  child_count: number;
+  children_done: number;
+  /** Modification time (ms) of the file when it was read. */
+  file_mtime: number | null;
+  /** Computed: folder relative to specs/ (e.g. "auth/login"), null at the top level. */
+  epic: string | null;
+}
+
+/** Error payload from `update_spec` when the file changed on disk since it was read. */
+export interface SpecConflict {
+  kind: "spec_conflict";
+  message: string;
+  file_path: string;
+  current: Spec;
+  attempted: Spec;
+}
+
+export function isSpecConflict(e: unknown): e is SpecConflict {
+  return typeof e === "object" && e !== null && (e as { kind?: unknown }).kind === "spec_conflict";
+}
+
+export interface GithubIssue {
+  number: number;
+  title: string;
+  body: string | null;
+  labels: { name: string }[];
+  url: string | null;
 }
 
 export interface SpecUpdate {
   title?: string;
   priority?: SpecPriority;
   status?: SpecStatus;
-  acceptance_criteria?: string[];
+  estimate?: SpecEstimate | null;
+  labels?: string[];
+  acceptance_criteria?: AcceptanceCriterion[];
   assigned_agent?: string | null;
   assigned_session_id?: string | null;
+  assigned_agents?: string[];
   parent_spec?: string | null;
+  suggested_agent?: string | null;
+  depends_on?: string[];
+  due_date?: string | null;
+  branch?: string | null;
   body?: string;
+  /** Reject the write if the file's mtime no longer matches. */
+  expected_mtime?: number | null;
+}
+
+/** Payload of `spec:due-soon` and `spec:overdue`. */
+export interface SpecDueEvent {
+  file_path: string;
+  title: string;
+  due_date: string;
+  state: "due_soon" | "overdue";
+}
+
+/** A child spec proposed by `breakdown_spec`, not yet written. */
+export interface ProposedSpec {
+  title: string;
+  priority: SpecPriority;
+  body: string;
+  acceptance_criteria: string[];
+  suggested_agent: string | null;
+}
+
+export type SpecChangeKind = "created" | "modified" | "deleted" | "invalid";
+
+/** What a file change did to a spec, compared with the last version seen. */
+export interface SpecFileChange {
+  file_path: string;
+  kind: SpecChangeKind;
+  title: string | null;
+  old_status: SpecStatus | null;
+  new_status: SpecStatus | null;
+  /** Frontmatter fields (and `body`) that changed. */
+  changed_fields: string[];
 }
 
 export interface SpecsChangedEvent {
   changed_files: string[];
+  changes: SpecFileChange[];
 }
 
 export interface SpecStatusChangedEvent {
@@ -157,7 +996,7 @@ export interface SpecStatusChangedEvent {
   status: string;
 }
 
-export const SPEC_STATUS_COLORS: Record<SpecStatus, string> = {
+export const SPEC_STATUS_COLORS: Record<BuiltInSpecStatus, string> = {
   draft: "#71717a",      // zinc-500
   assigned: "#3b82f6",   // blue-500
   in_progress: "#f59e0b", // amber-500
@@ -166,7 +1005,7 @@ export const SPEC_STATUS_COLORS: Record<SpecStatus, string> = {
   rejected: "#ef4444",   // red-500
 };
 
-export const SPEC_STATUS_LABELS: Record<SpecStatus, string> = {
+export const SPEC_STATUS_LABELS: Record<BuiltInSpecStatus, string> = {
   draft: "Draft",
   assigned: "Assigned",
   in_progress: "In Progress",
@@ -175,6 +1014,20 @@ export const SPEC_STATUS_LABELS: Record<SpecStatus, string> = {
   rejected: "Rejected",
 };
 
+export function specStatusLabel(status: SpecStatus, workflow?: SpecWorkflow | null): string {
+  const custom = workflow?.statuses.find((s) => s.id === status)?.label;
+  return (
+    custom ??
+    SPEC_STATUS_LABELS[status as BuiltInSpecStatus] ??
+    status.replace(/_/g, " ").replace(/\b\w/g, (c) => c.toUpperCase())
+  );
+}
+
+export function specStatusColor(status: SpecStatus, workflow?: SpecWorkflow | null): string {
+  const custom = workflow?.statuses.find((s) => s.id === status)?.color;
+  return custom ?? SPEC_STATUS_COLORS[status as BuiltInSpecStatus] ?? SPEC_STATUS_COLORS.draft;
+}
+
 export const SPEC_PRIORITY_COLORS: Record<SpecPriority, string> = {
   p0: "#ef4444", // red
   p1: "#f59e0b", // amber
@@ -186,6 +1039,48 @@ export const SPEC_PRIORITY_COLORS: Record<SpecPriority, string> = {
 export interface ChangedFile {
   path: string;
   status: string; // "M", "A", "D", "R", "?"
+  /** Lines added and removed, staged and unstaged together; null for binaries. */
+  insertions: number | null;
+  deletions: number | null;
+}
+
+/** Payload of `git:working-tree-changed`; paths are relative to the project. */
+export interface WorkingTreeChangedEvent {
+  changed_files: string[];
+}
+
+export interface GitBranch {
+  name: string;
+  /** Checked out in the working tree. */
+  current: boolean;
+  upstream: string | null;
+  commit: string;
+  subject: string;
+}
+
+export interface GitCommit {
+  hash: string;
+  author_name: string;
+  author_email: string;
+  /** ISO 8601 committer date. */
+  date: string;
+  subject: string;
+  /** Session running in the project when it was committed. */
+  session_id: string | null;
+}
+
+export interface BlameLine {
+  /** Line number in the blamed revision. */
+  line: number;
+  commit: string;
+  author_name: string;
+  author_email: string;
+  /** ISO 8601 author date. */
+  date: string;
+  summary: string;
+  content: string;
+  /** Changed in the working tree and not committed yet. */
+  uncommitted: boolean;
 }
 
 export interface DiffLine {
@@ -193,6 +1088,13 @@ export interface DiffLine {
   content: string;
   old_line: number | null;
   new_line: number | null;
+  /** Unchanged/changed runs of a modified line; absent for whole-line changes. */
+  segments?: WordSegment[];
+}
+
+export interface WordSegment {
+  text: string;
+  changed: boolean;
 }
 
 export interface DiffHunk {
@@ -204,6 +1106,28 @@ export interface FileDiff {
   path: string;
   change_type: string; // "modified", "added", "deleted", "renamed"
   hunks: DiffHunk[];
+  old_path: string | null;
+  is_binary: boolean;
+  /** Bytes per side, when the contents aren't shown. */
+  old_size: number | null;
+  new_size: number | null;
+  /** Index against HEAD rather than the working tree. */
+  staged: boolean;
+}
+
+/** A committed version of a spec (`get_spec_history`). */
+export interface SpecRevision {
+  commit: string;
+  author_name: string;
+  author_email: string;
+  date: string;
+  subject: string;
+  path: string;
+  content: string;
+  diff: FileDiff[];
+  status: SpecStatus | null;
+  assigned_agent: string | null;
+  acceptance_criteria: AcceptanceCriterion[];
 }
 
 export const CHANGE_TYPE_COLORS: Record<string, string> = {
@@ -230,6 +1154,14 @@ export type StepStatus =
   | "failed"
   | "skipped";
 
+export type FailurePolicy = "fail_workflow" | "continue" | "skip_descendants";
+
+export type JoinMode = "all" | "any";
+
+export type StepKind = "agent" | "gate";
+
+export type EdgeCondition = "on_success" | "on_failure";
+
 export interface Workflow {
   id: string;
   name: string;
@@ -237,6 +1169,8 @@ export interface Workflow {
   status: WorkflowStatus;
   created_at: string;
   updated_at: string;
+  /** Most steps running at once; no limit when null. */
+  max_parallel?: number | null;
 }
 
 export interface WorkflowStep {
@@ -253,6 +1187,14 @@ export interface WorkflowStep {
   created_at: string;
   pass_context: boolean;
   result_output: string | null;
+  failure_policy: FailurePolicy;
+  join_mode: JoinMode;
+  kind: StepKind;
+  gate_command: string | null;
+  prompt_template_id: string | null;
+  template_vars: Record<string, string>;
+  /** Commit message template; when set, the step commits on completion. */
+  commit_message: string | null;
 }
 
 export interface WorkflowEdge {
@@ -260,6 +1202,109 @@ export interface WorkflowEdge {
   workflow_id: string;
   source_step_id: string;
   target_step_id: string;
+  condition: EdgeCondition;
+}
+
+export interface PromptTemplate {
+  id: string;
+  name: string;
+  body: string;
+  variables: Record<string, string>;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface CommandPhase {
+  title: string;
+  prompt: string;
+}
+
+export interface CustomCommand {
+  name: string;
+  file_path: string;
+  description: string | null;
+  model: string | null;
+  phases: CommandPhase[];
+}
+
+export interface SimulatedStep {
+  step_id: string;
+  agent_name: string;
+  wave: number;
+  estimated_cost_usd: number | null;
+  prompt_chars: number;
+  max_prompt_chars: number;
+  estimated_prompt_tokens: number;
+}
+
+export interface WorkflowSimulation {
+  workflow_id: string;
+  execution_order: string[];
+  waves: string[][];
+  steps: SimulatedStep[];
+  estimated_cost_usd: number;
+  warnings: string[];
+}
+
+/** What one planned agent run is expected to use, from the agent's past finished sessions. */
+export interface AgentRunEstimate {
+  agent_name: string;
+  /** Zero-based wave the run starts in; runs in a wave go in parallel. */
+  wave: number;
+  /** Past sessions the averages come from; zero leaves them null. */
+  sample_size: number;
+  avg_cost_usd: number | null;
+  avg_input_tokens: number | null;
+  avg_output_tokens: number | null;
+  avg_duration_secs: number | null;
+}
+
+/** Predicted usage of a workflow or spec run. */
+export interface RunEstimate {
+  runs: AgentRunEstimate[];
+  cost_usd: number;
+  input_tokens: number;
+  output_tokens: number;
+  /** Wall-clock seconds: the slowest run of each wave, one wave after another. */
+  duration_secs: number;
+  /** Agents without finished sessions, left out of the totals. */
+  missing_history: string[];
+}
+
+export interface TemplateStep {
+  key: string;
+  agent_name: string;
+  model: string;
+  prompt: string;
+  position_x: number;
+  position_y: number;
+  pass_context: boolean;
+  failure_policy: FailurePolicy;
+  join_mode: JoinMode;
+  kind: StepKind;
+  gate_command: string | null;
+  prompt_template_id: string | null;
+  template_vars: Record<string, string>;
+  commit_message: string | null;
+}
+
+export interface TemplateEdge {
+  source: string;
+  target: string;
+  condition: EdgeCondition;
+}
+
+export interface WorkflowTemplate {
+  id: string;
+  name: string;
+  description: string | null;
+  steps: TemplateStep[];
+  edges: TemplateEdge[];
+}
+
+export interface TemplateInstantiation {
+  workflow: Workflow;
+  missing_agents: string[];
 }
 
 export const WORKFLOW_STATUS_COLORS: Record<WorkflowStatus, string> = {
@@ -36,7 +36,7 @@ interface AgentState {
   // Actions
   loadConfigs: () => Promise<void>;
   loadSessions: () => Promise<void>;
-  startAgent: (name: string, model: string, prompt: string) => Promise<string>;
+  startAgent: (name: string, model: string, prompt: string, profile?: string | null) => Promise<string>;
   stopAgent: (sessionId: string) => Promise<void>;
   resumeAgent: (sessionId: string, prompt: string) => Promise<string>;
   selectSession: (sessionId: string | null) => void;
@@ -46,12 +46,15 @@ interface AgentState {
   // Agent template CRUD
   selectAgent: (path: string | null) => void;
   createAgent: (name: string, model: string, description: string, color: string) => Promise<void>;
+  installTemplate: (name: string) => Promise<void>;
+  generateAgent: (name: string, goals: string, color: string) => Promise<void>;
   updateAgent: (filePath: string, update: AgentConfigUpdate) => Promise<void>;
+  restoreAgentVersion: (filePath: string, versionId: string) => Promise<void>;
   deleteAgent: (filePath: string) => Promise<void>;
 
   // Event handlers
   handleStatusChange: (event: AgentStatusEvent) => void;
-  handleMessage: (event: AgentMessageEvent) => void;
+  handleMessages: (events: AgentMessageEvent[]) => void;
   handleUsageUpdate: (event: AgentUsageEvent) => void;
   handleRateLimited: (event: { session_id: string; reset_at: string | null; raw_message: string }) => void;
   clearRateLimit: () => void;
@@ -82,8 +85,8 @@ export const useAgentStore = create<AgentState>((set, get) => ({
     set({ sessions });
   },
 
-  startAgent: async (name, model, prompt) => {
-    const sessionId = await tauri.startAgent(name, model, prompt);
+  startAgent: async (name, model, prompt, profile) => {
+    const sessionId = await tauri.startAgent(name, model, prompt, profile);
     // Session will be added via status-changed event
     return sessionId;
   },
@@ -119,6 +122,18 @@ export const useAgentStore = create<AgentState>((set, get) => ({
     await get().loadConfigs();
   },
 
+  installTemplate: async (name) => {
+    const installed = await tauri.installAgentTemplate(name);
+    await get().loadConfigs();
+    set({ selectedAgentPath: installed.file_path });
+  },
+
+  generateAgent: async (name, goals, color) => {
+    const generated = await tauri.generateAgent(name, goals, color);
+    await get().loadConfigs();
+    set({ selectedAgentPath: generated.file_path });
+  },
+
   updateAgent: async (filePath, update) => {
     const updated = await tauri.updateAgentConfig(filePath, update);
     set((state) => ({
@@ -128,6 +143,15 @@ export const useAgentStore = create<AgentState>((set, get) => ({
     }));
   },
 
+  restoreAgentVersion: async (filePath, versionId) => {
+    const restored = await tauri.restoreAgentVersion(filePath, versionId);
+    set((state) => ({
+      configs: state.configs.map((c) =>
+        c.file_path === filePath ? restored : c,
+      ),
+    }));
+  },
+
   deleteAgent: async (filePath) => {
     await tauri.deleteAgentConfig(filePath);
     set((state) => ({
@@ -170,18 +194,28 @@ export const useAgentStore = create<AgentState>((set, get) => ({
     });
   },
 
-  handleMessage: (event) => {
+  handleMessages: (events) => {
+    if (events.length === 0) return;
     set((state) => {
       const logs = new Map(state.logs);
-      const sessionLogs = logs.get(event.session_id) ?? [];
-
-      // Ring buffer behavior: cap at MAX_LOGS_PER_SESSION
-      const newLogs =
-        sessionLogs.length >= MAX_LOGS_PER_SESSION
-          ? [...sessionLogs.slice(1), event]
-          : [...sessionLogs, event];
+      const bySession = new Map<string, AgentMessageEvent[]>();
+      for (const event of events) {
+        const batch = bySession.get(event.session_id) ?? [];
+        batch.push(event);
+        bySession.set(event.session_id, batch);
+      }
 
-      logs.set(event.session_id, newLogs);
+      for (const [sessionId, batch] of bySession) {
+        const sessionLogs = logs.get(sessionId) ?? [];
+        // Ring buffer behavior: cap at MAX_LOGS_PER_SESSION
+        const newLogs = [...sessionLogs, ...batch];
+        logs.set(
+          sessionId,
+          newLogs.length > MAX_LOGS_PER_SESSION
+            ? newLogs.slice(newLogs.length - MAX_LOGS_PER_SESSION)
+            : newLogs,
+        );
+      }
       return { logs };
     });
   },
@@ -197,6 +231,7 @@ export const useAgentStore = create<AgentState>((set, get) => ({
           output_tokens: event.output_tokens,
           // Only overwrite cost when the final result message arrives (cost_usd > 0)
           cost_usd: event.cost_usd > 0 ? event.cost_usd : existing.cost_usd,
+          cost_estimated: event.cost_usd > 0 ? event.cost_estimated : existing.cost_estimated,
         });
       }
       return { sessions };
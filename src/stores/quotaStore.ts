@@ -1,4 +1,5 @@
 import { create } from "zustand";
+import type { BudgetStatus, RateWindow } from "../lib/types";
 
 export interface ModelUsageEntry {
   model: string;
@@ -15,13 +16,25 @@ export interface DailyStats {
   message_count: number;
 }
 
+/** Usage read from one Claude profile's stats file. */
+export interface ProfileUsage {
+  /** null for the default config directory. */
+  profile: string | null;
+  models: ModelUsageEntry[];
+  total_cost_usd: number;
+  available: boolean;
+  error: string | null;
+}
+
 export interface QuotaUpdateEvent {
   models: ModelUsageEntry[];
   today: DailyStats | null;
   total_cost_usd: number;
+  profiles: ProfileUsage[];
   fetched_at: string;
   available: boolean;
   error: string | null;
+  budgets: BudgetStatus[];
 }
 
 interface QuotaState {
@@ -29,29 +42,43 @@ interface QuotaState {
   models: ModelUsageEntry[] | null;
   today: DailyStats | null;
   totalCostUsd: number;
+  profiles: ProfileUsage[];
   available: boolean | null;
   fetchedAt: string | null;
   error: string | null;
+  budgets: BudgetStatus[];
+  /** null = not yet fetched */
+  window: RateWindow | null;
 
   handleQuotaUpdate: (event: QuotaUpdateEvent) => void;
+  handleWindowUpdate: (window: RateWindow) => void;
 }
 
 export const useQuotaStore = create<QuotaState>((set) => ({
   models: null,
   today: null,
   totalCostUsd: 0,
+  profiles: [],
   available: null,
   fetchedAt: null,
   error: null,
+  budgets: [],
+  window: null,
 
   handleQuotaUpdate: (event) => {
     set({
       models: event.available ? event.models : null,
       today: event.available ? event.today : null,
       totalCostUsd: event.total_cost_usd,
+      profiles: event.profiles ?? [],
       available: event.available,
       fetchedAt: event.fetched_at,
       error: event.error,
+      budgets: event.budgets,
     });
   },
+
+  handleWindowUpdate: (window) => {
+    set({ window });
+  },
 }));
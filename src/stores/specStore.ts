@@ -1,37 +1,51 @@
 import { create } from "zustand";
-import type { Spec, SpecPriority, SpecUpdate } from "../lib/types";
+import type { ReviewDecision, Spec, SpecPriority, SpecStructure, SpecUpdate, SpecWorkflow } from "../lib/types";
 import * as tauri from "../lib/tauri";
 
 interface SpecState {
   specs: Spec[];
+  /** Project's statuses and transitions; null until loaded. */
+  workflow: SpecWorkflow | null;
   selectedSpecPath: string | null;
   loading: boolean;
 
   loadSpecs: () => Promise<void>;
-  createSpec: (title: string, priority: SpecPriority) => Promise<Spec>;
-  updateSpec: (filePath: string, update: SpecUpdate) => Promise<void>;
+  createSpec: (
+    title: string,
+    priority: SpecPriority,
+    epic?: string | null,
+    structure?: SpecStructure,
+  ) => Promise<Spec>;
+  updateSpec: (filePath: string, update: SpecUpdate) => Promise<Spec>;
   deleteSpec: (filePath: string) => Promise<void>;
   selectSpec: (filePath: string | null) => void;
   runSpec: (filePath: string, agentName: string, model: string) => Promise<string>;
+  runSpecPair: (filePath: string, model: string) => Promise<string>;
+  verifySpec: (filePath: string, agentName: string, model: string) => Promise<string>;
+  reviewSpec: (filePath: string, decision: ReviewDecision, comment: string) => Promise<void>;
 }
 
 export const useSpecStore = create<SpecState>((set, get) => ({
   specs: [],
+  workflow: null,
   selectedSpecPath: null,
   loading: false,
 
   loadSpecs: async () => {
     set({ loading: true });
     try {
-      const specs = await tauri.listSpecs();
-      set({ specs });
+      const [specs, workflow] = await Promise.all([
+        tauri.listSpecs(),
+        tauri.getSpecWorkflow().catch(() => null),
+      ]);
+      set({ specs, workflow });
     } finally {
       set({ loading: false });
     }
   },
 
-  createSpec: async (title, priority) => {
-    const spec = await tauri.createSpec(title, priority);
+  createSpec: async (title, priority, epic, structure) => {
+    const spec = await tauri.createSpec(title, priority, epic, structure);
     // Reload full list to stay in sync with disk
     await get().loadSpecs();
     set({ selectedSpecPath: spec.file_path });
@@ -45,6 +59,7 @@ export const useSpecStore = create<SpecState>((set, get) => ({
         s.file_path === filePath ? updated : s,
       ),
     }));
+    return updated;
   },
 
   deleteSpec: async (filePath) => {
@@ -66,4 +81,26 @@ export const useSpecStore = create<SpecState>((set, get) => ({
     await get().loadSpecs();
     return sessionId;
   },
+
+  runSpecPair: async (filePath, model) => {
+    const workflowId = await tauri.runSpecPair(filePath, model);
+    await get().loadSpecs();
+    return workflowId;
+  },
+
+  verifySpec: async (filePath, agentName, model) => {
+    const sessionId = await tauri.verifySpec(filePath, agentName, model);
+    await get().loadSpecs();
+    return sessionId;
+  },
+
+  reviewSpec: async (filePath, decision, comment) => {
+    if (decision === "approved") {
+      await tauri.approveSpec(filePath, comment);
+    } else {
+      await tauri.rejectSpec(filePath, comment);
+    }
+    // Parents may have rolled up too
+    await get().loadSpecs();
+  },
 }));